@@ -4,15 +4,29 @@
 // different LLM providers (Claude, OpenAI, Grok, etc.) to be used interchangeably.
 
 use anyhow::{Context, Result};
+use std::sync::Arc;
 use tokio::sync::mpsc;
 
 use super::types::{MessageRequest, MessageResponse};
 use crate::generators::StreamChunk;
-use crate::providers::{claude::ClaudeProvider, LlmProvider, ProviderRequest};
+use crate::providers::{
+    claude::ClaudeProvider, CircuitBreaker, CircuitBreakerStatus, ContextPreview, LlmProvider,
+    ProviderRequest, TemperatureOverride, ThinkingOverride,
+};
 
 #[derive(Clone)]
 pub struct ClaudeClient {
-    provider: std::sync::Arc<dyn LlmProvider>,
+    provider: Arc<dyn LlmProvider>,
+    /// Shared by all clones so every caller sees the same teacher health
+    breaker: Arc<CircuitBreaker>,
+    /// Session thinking setting (/think), also shared by all clones
+    thinking: Arc<ThinkingOverride>,
+    /// Next-call temperature (/temp), also shared by all clones
+    temperature: Arc<TemperatureOverride>,
+    /// System prompt for every request (the [constitution] file)
+    system_prompt: Option<String>,
+    /// Model for every request, overriding the requests' own (`--model`)
+    model: Option<String>,
 }
 
 impl ClaudeClient {
@@ -20,14 +34,49 @@ impl ClaudeClient {
     pub fn new(api_key: String) -> Result<Self> {
         let provider = ClaudeProvider::new(api_key)?;
         Ok(Self {
-            provider: std::sync::Arc::new(provider),
+            provider: Arc::new(provider),
+            breaker: Arc::new(CircuitBreaker::default()),
+            thinking: Arc::new(ThinkingOverride::default()),
+            temperature: Arc::new(TemperatureOverride::default()),
+            system_prompt: None,
+            model: None,
         })
     }
 
     /// Create a ClaudeClient with a custom provider
     pub fn with_provider(provider: Box<dyn LlmProvider>) -> Self {
         Self {
-            provider: std::sync::Arc::from(provider),
+            provider: Arc::from(provider),
+            breaker: Arc::new(CircuitBreaker::default()),
+            thinking: Arc::new(ThinkingOverride::default()),
+            temperature: Arc::new(TemperatureOverride::default()),
+            system_prompt: None,
+            model: None,
+        }
+    }
+
+    /// Send `system_prompt` ahead of every request's own system prompt
+    pub fn with_system_prompt(mut self, system_prompt: impl Into<String>) -> Self {
+        self.system_prompt = Some(system_prompt.into());
+        self
+    }
+
+    /// Send every request to `model` instead of the teacher's configured one
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    /// Model set with `with_model`, if any
+    pub fn model_override(&self) -> Option<&str> {
+        self.model.as_deref()
+    }
+
+    /// System prompt for `request`: the configured one, then the request's own
+    pub fn system_prompt_for(&self, request: &MessageRequest) -> Option<String> {
+        match (&self.system_prompt, &request.system) {
+            (Some(configured), Some(own)) => Some(format!("{}\n\n{}", configured, own)),
+            (configured, own) => configured.clone().or_else(|| own.clone()),
         }
     }
 
@@ -36,23 +85,81 @@ impl ClaudeClient {
         self.provider.name()
     }
 
-    /// Convert MessageRequest to ProviderRequest
-    fn to_provider_request(&self, request: &MessageRequest) -> ProviderRequest {
-        let mut provider_req = ProviderRequest::new(request.messages.clone())
-            .with_model(request.model.clone())
-            .with_max_tokens(request.max_tokens);
+    /// Circuit breaker state for health/status output
+    pub fn circuit_status(&self) -> CircuitBreakerStatus {
+        self.breaker.status()
+    }
+
+    /// Whether the circuit is open (calls would fail fast)
+    ///
+    /// Callers can use this to route to the local model instead.
+    pub fn is_circuit_open(&self) -> bool {
+        self.breaker.is_open()
+    }
+
+    /// Shared breaker, so a TeacherSession for the same teacher trips together
+    pub fn circuit_breaker(&self) -> Arc<CircuitBreaker> {
+        Arc::clone(&self.breaker)
+    }
+
+    /// Session thinking override, applied to every request this client sends
+    pub fn thinking_override(&self) -> Arc<ThinkingOverride> {
+        Arc::clone(&self.thinking)
+    }
+
+    /// One-shot temperature override, used up by the next request sent
+    pub fn temperature_override(&self) -> Arc<TemperatureOverride> {
+        Arc::clone(&self.temperature)
+    }
+
+    /// Convert MessageRequest to ProviderRequest (`temperature` overrides the request's)
+    fn to_provider_request(
+        &self,
+        request: &MessageRequest,
+        temperature: Option<f32>,
+    ) -> ProviderRequest {
+        let model = self.model.as_ref().unwrap_or(&request.model);
+        let mut provider_req =
+            ProviderRequest::new(request.messages.clone()).with_model(model.clone());
+
+        if let Some(max_tokens) = request.max_tokens {
+            provider_req = provider_req.with_max_tokens(max_tokens);
+        }
 
         if let Some(tools) = &request.tools {
             provider_req = provider_req.with_tools(tools.clone());
         }
 
+        if let Some(temperature) = temperature.or(request.temperature) {
+            provider_req = provider_req.with_temperature(temperature);
+        }
+
+        if let Some(thinking) = self.thinking.get() {
+            provider_req = provider_req.with_thinking(thinking);
+        }
+
+        if let Some(system) = self.system_prompt_for(request) {
+            provider_req = provider_req.with_system(system);
+        }
+
         provider_req
     }
 
+    /// What `send_message` would send for `request`, without sending it (/context)
+    pub fn preview_context(&self, request: &MessageRequest) -> ContextPreview {
+        ContextPreview::as_sent(
+            self.provider.as_ref(),
+            self.to_provider_request(request, self.temperature.get()),
+        )
+    }
+
     /// Send a message to the configured provider with retry logic
     pub async fn send_message(&self, request: &MessageRequest) -> Result<MessageResponse> {
-        let provider_request = self.to_provider_request(request);
-        let provider_response = self.provider.send_message(&provider_request).await?;
+        self.breaker.check(self.provider.name())?;
+        let provider_request = self.to_provider_request(request, self.temperature.take());
+        let result = self.provider.send_message(&provider_request).await;
+        self.breaker.record(&result);
+        let provider_response = result?;
 
         // Convert ProviderResponse to MessageResponse
         Ok(provider_response.into())
@@ -64,8 +171,13 @@ impl ClaudeClient {
         &self,
         request: &MessageRequest,
     ) -> Result<mpsc::Receiver<Result<StreamChunk>>> {
-        let provider_request = self.to_provider_request(request).with_stream(true);
-        self.provider.send_message_stream(&provider_request).await
+        self.breaker.check(self.provider.name())?;
+        let provider_request = self
+            .to_provider_request(request, self.temperature.take())
+            .with_stream(true);
+        let result = self.provider.send_message_stream(&provider_request).await;
+        self.breaker.record(&result);
+        result
     }
 }
 
@@ -86,4 +198,46 @@ mod tests {
         assert_eq!(request.messages[0].role, "user");
         assert_eq!(request.messages[0].text(), "Hello");
     }
+
+    #[test]
+    fn test_system_prompt_for() {
+        let request = MessageRequest::new("Hello");
+        let client = ClaudeClient::new("test-key".to_string()).unwrap();
+        assert_eq!(client.system_prompt_for(&request), None);
+
+        let client = client.with_system_prompt("Be kind.");
+        assert_eq!(client.system_prompt_for(&request).as_deref(), Some("Be kind."));
+        assert_eq!(
+            client
+                .system_prompt_for(&request.with_system("Answer in French."))
+                .as_deref(),
+            Some("Be kind.\n\nAnswer in French.")
+        );
+    }
+
+    #[test]
+    fn test_model_override() {
+        let request = MessageRequest::new("Hello");
+        let client = ClaudeClient::new("test-key".to_string()).unwrap();
+        assert_eq!(client.to_provider_request(&request, None).model, request.model);
+
+        let client = client.with_model("claude-haiku-4-5");
+        assert_eq!(client.model_override(), Some("claude-haiku-4-5"));
+        assert_eq!(client.to_provider_request(&request, None).model, "claude-haiku-4-5");
+    }
+
+    #[test]
+    fn test_temperature_override_is_one_shot() {
+        let client = ClaudeClient::new("test-key".to_string()).unwrap();
+        let overrides = client.temperature_override();
+        assert!(overrides.set(Some(2.5)).is_err());
+        overrides.set(Some(0.3)).unwrap();
+
+        let request = MessageRequest::new("Hello").with_temperature(0.9);
+        let sent = client.to_provider_request(&request, client.temperature.take());
+        assert_eq!(sent.temperature, Some(0.3));
+        assert_eq!(overrides.get(), None);
+        let sent = client.to_provider_request(&request, client.temperature.take());
+        assert_eq!(sent.temperature, Some(0.9));
+    }
 }
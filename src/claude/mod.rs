@@ -8,4 +8,4 @@ pub(crate) mod types; // Make types available to providers module
 
 pub use client::ClaudeClient;
 pub use streaming::{StreamDelta, StreamEvent};
-pub use types::{ContentBlock, Message, MessageRequest, MessageResponse};
+pub use types::{ContentBlock, ImageSource, Message, MessageRequest, MessageResponse};
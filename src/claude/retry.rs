@@ -1,39 +1,289 @@
 // Retry logic with exponential backoff
+//
+// Transient failures (network errors, 408/429/5xx) are retried with
+// jittered exponential backoff, or after the server's Retry-After when it
+// sends one. Other HTTP errors (400, 401, 403, 404, ...) are permanent and
+// fail straight away.
 
 use anyhow::Result;
+use rand::Rng;
 use std::time::Duration;
 use tokio::time::sleep;
 
-const MAX_RETRIES: u32 = 3;
-const BASE_DELAY_MS: u64 = 1000;
+/// Attempts per request, including the first
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+/// Backoff before the first retry (doubles for each one after)
+pub const DEFAULT_BASE_DELAY_MS: u64 = 1000;
+/// Longest Retry-After worth waiting for; longer ones fail right away
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
 
-/// Execute a function with exponential backoff retry logic
+/// Retry knobs (`max_retries` / `base_delay_ms` on a teacher entry)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryConfig {
+    pub max_retries: u32,
+    pub base_delay_ms: u64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: DEFAULT_MAX_RETRIES,
+            base_delay_ms: DEFAULT_BASE_DELAY_MS,
+        }
+    }
+}
+
+impl RetryConfig {
+    /// Defaults, overridden by whichever knobs are set
+    pub fn with_overrides(max_retries: Option<u32>, base_delay_ms: Option<u64>) -> Self {
+        let defaults = Self::default();
+        Self {
+            max_retries: max_retries.unwrap_or(defaults.max_retries).max(1),
+            base_delay_ms: base_delay_ms.unwrap_or(defaults.base_delay_ms),
+        }
+    }
+
+    /// Jittered backoff before retry number `attempt + 1`: a random point in
+    /// the upper half of `base_delay * 2^attempt`
+    fn backoff(&self, attempt: u32) -> Duration {
+        let full = self
+            .base_delay_ms
+            .saturating_mul(2u64.saturating_pow(attempt));
+        let jittered = if full < 2 {
+            full
+        } else {
+            rand::thread_rng().gen_range(full / 2..=full)
+        };
+        Duration::from_millis(jittered)
+    }
+}
+
+/// A non-success HTTP response from a provider
+///
+/// Displays as "<what> failed\n\nStatus: ...\nBody: ..." (the format the
+/// teacher pool's rate-limit check looks for).
+#[derive(Debug)]
+pub struct HttpStatusError {
+    pub what: String,
+    pub status: reqwest::StatusCode,
+    pub retry_after: Option<Duration>,
+    pub body: String,
+}
+
+impl HttpStatusError {
+    /// Build from a failed response, reading its Retry-After header and body
+    pub async fn from_response(what: impl Into<String>, response: reqwest::Response) -> Self {
+        let status = response.status();
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| parse_retry_after(value, chrono::Utc::now()));
+        let body = response.text().await.unwrap_or_default();
+        Self {
+            what: what.into(),
+            status,
+            retry_after,
+            body,
+        }
+    }
+
+    /// Whether trying again might work (timeouts, rate limits, server errors)
+    pub fn is_transient(&self) -> bool {
+        matches!(self.status.as_u16(), 408 | 425 | 429) || self.status.is_server_error()
+    }
+}
+
+impl std::fmt::Display for HttpStatusError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} failed\n\nStatus: {}\nBody: {}",
+            self.what, self.status, self.body
+        )
+    }
+}
+
+impl std::error::Error for HttpStatusError {}
+
+/// Parse a Retry-After value: delay seconds or an HTTP date
+pub fn parse_retry_after(value: &str, now: chrono::DateTime<chrono::Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (at.with_timezone(&chrono::Utc) - now)
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+/// How long to wait before retrying after `error`, or None to give up
+fn retry_delay(error: &anyhow::Error, config: &RetryConfig, attempt: u32) -> Option<Duration> {
+    match error
+        .chain()
+        .find_map(|e| e.downcast_ref::<HttpStatusError>())
+    {
+        Some(http) if !http.is_transient() => None,
+        Some(HttpStatusError {
+            retry_after: Some(after),
+            ..
+        }) if *after > MAX_RETRY_AFTER => None,
+        Some(HttpStatusError {
+            retry_after: Some(after),
+            ..
+        }) => Some(*after),
+        // Transient status without Retry-After, or a network error
+        _ => Some(config.backoff(attempt)),
+    }
+}
+
+/// Execute a function with the default retry settings
 pub async fn with_retry<F, Fut, T>(f: F) -> Result<T>
 where
     F: Fn() -> Fut,
     Fut: std::future::Future<Output = Result<T>>,
 {
-    let mut last_error = None;
+    with_retry_config(&RetryConfig::default(), f).await
+}
 
-    for attempt in 0..MAX_RETRIES {
-        match f().await {
+/// Execute a function with exponential backoff retry logic
+pub async fn with_retry_config<F, Fut, T>(config: &RetryConfig, f: F) -> Result<T>
+where
+    F: Fn() -> Fut,
+    Fut: std::future::Future<Output = Result<T>>,
+{
+    let max_attempts = config.max_retries.max(1);
+    let mut attempt = 0;
+    loop {
+        let error = match f().await {
             Ok(result) => return Ok(result),
-            Err(e) => {
-                last_error = Some(e);
-
-                if attempt < MAX_RETRIES - 1 {
-                    let delay = Duration::from_millis(BASE_DELAY_MS * 2u64.pow(attempt));
-                    tracing::warn!(
-                        "Request failed (attempt {}/{}), retrying in {:?}",
-                        attempt + 1,
-                        MAX_RETRIES,
-                        delay
-                    );
-                    sleep(delay).await;
-                }
-            }
+            Err(e) => e,
+        };
+
+        attempt += 1;
+        if attempt >= max_attempts {
+            return Err(error);
+        }
+        let Some(delay) = retry_delay(&error, config, attempt - 1) else {
+            return Err(error);
+        };
+        tracing::warn!(
+            "Request failed (attempt {}/{}), retrying in {:?}",
+            attempt,
+            max_attempts,
+            delay
+        );
+        sleep(delay).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn http_error(status: u16, retry_after: Option<Duration>) -> anyhow::Error {
+        HttpStatusError {
+            what: "Test API request".to_string(),
+            status: reqwest::StatusCode::from_u16(status).unwrap(),
+            retry_after,
+            body: String::new(),
         }
+        .into()
     }
 
-    Err(last_error.unwrap())
+    async fn attempts_for(status: u16) -> u32 {
+        let calls = AtomicU32::new(0);
+        let config = RetryConfig {
+            max_retries: 3,
+            base_delay_ms: 1,
+        };
+        let result: Result<()> = with_retry_config(&config, || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(http_error(status, None))
+        })
+        .await;
+        assert!(result.is_err());
+        calls.load(Ordering::SeqCst)
+    }
+
+    #[tokio::test]
+    async fn test_permanent_errors_are_not_retried() {
+        assert_eq!(attempts_for(400).await, 1);
+        assert_eq!(attempts_for(401).await, 1);
+        assert_eq!(attempts_for(429).await, 3);
+        assert_eq!(attempts_for(503).await, 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_succeeds_after_transient_failure() {
+        let calls = AtomicU32::new(0);
+        let config = RetryConfig {
+            max_retries: 3,
+            base_delay_ms: 1,
+        };
+        let result = with_retry_config(&config, || async {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 => Err(anyhow::anyhow!("connection reset")),
+                _ => Ok("ok"),
+            }
+        })
+        .await;
+        assert_eq!(result.unwrap(), "ok");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        let config = RetryConfig::default();
+        let wait = Some(Duration::from_secs(2));
+        assert_eq!(retry_delay(&http_error(429, wait), &config, 0), wait);
+        assert_eq!(
+            retry_delay(&http_error(429, Some(Duration::from_secs(600))), &config, 0),
+            None
+        );
+        assert_eq!(retry_delay(&http_error(401, None), &config, 0), None);
+
+        let backoff = retry_delay(&http_error(500, None), &config, 2).unwrap();
+        assert!(backoff >= Duration::from_millis(2000) && backoff <= Duration::from_millis(4000));
+
+        // Still recognized under added context, and the message keeps the status
+        let wrapped = http_error(400, None).context("Teacher request");
+        assert_eq!(retry_delay(&wrapped, &config, 0), None);
+        assert!(format!("{:#}", http_error(429, None)).contains("Status: 429"));
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let now = chrono::DateTime::parse_from_rfc3339("2015-10-21T07:28:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        assert_eq!(
+            parse_retry_after("120", now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:30 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:27:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    #[test]
+    fn test_retry_config_overrides() {
+        assert_eq!(
+            RetryConfig::with_overrides(None, None),
+            RetryConfig::default()
+        );
+        let config = RetryConfig::with_overrides(Some(0), Some(250));
+        assert_eq!(config.max_retries, 1);
+        assert_eq!(config.base_delay_ms, 250);
+    }
 }
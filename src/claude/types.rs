@@ -1,18 +1,25 @@
 // Claude API request/response types
 
+use anyhow::{Context, Result};
+use base64::Engine as _;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::path::Path;
 
 // Re-export tool types for convenience
 pub use crate::tools::types::ToolDefinition;
 
-/// Content block - supports text, tool_use, and tool_result
+/// Content block - supports text, image, tool_use, and tool_result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum ContentBlock {
     #[serde(rename = "text")]
     Text { text: String },
 
+    /// Image input for vision-capable teachers (never sent to the local model)
+    #[serde(rename = "image")]
+    Image { source: ImageSource },
+
     #[serde(rename = "tool_use")]
     ToolUse {
         id: String,
@@ -27,6 +34,17 @@ pub enum ContentBlock {
         #[serde(skip_serializing_if = "Option::is_none")]
         is_error: Option<bool>,
     },
+
+    /// Extended thinking, sent back unchanged while a tool loop continues
+    #[serde(rename = "thinking")]
+    Thinking {
+        thinking: String,
+        #[serde(default)]
+        signature: String,
+    },
+
+    #[serde(rename = "redacted_thinking")]
+    RedactedThinking { data: String },
 }
 
 impl ContentBlock {
@@ -40,6 +58,11 @@ impl ContentBlock {
         matches!(self, ContentBlock::ToolUse { .. })
     }
 
+    /// Check if this is a (possibly redacted) thinking block
+    pub fn is_thinking(&self) -> bool {
+        matches!(self, ContentBlock::Thinking { .. } | ContentBlock::RedactedThinking { .. })
+    }
+
     /// Extract text from text block
     pub fn as_text(&self) -> Option<&str> {
         match self {
@@ -53,6 +76,38 @@ impl ContentBlock {
         Self::Text { text: text.into() }
     }
 
+    /// Check if this is an image block
+    pub fn is_image(&self) -> bool {
+        matches!(self, ContentBlock::Image { .. })
+    }
+
+    /// Create an image block from a local file (png, jpeg, gif or webp)
+    pub fn image_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase());
+        let media_type = match extension.as_deref() {
+            Some("png") => "image/png",
+            Some("jpg" | "jpeg") => "image/jpeg",
+            Some("gif") => "image/gif",
+            Some("webp") => "image/webp",
+            _ => anyhow::bail!(
+                "Unsupported image type: {} (expected png, jpg, gif or webp)",
+                path.display()
+            ),
+        };
+        let bytes =
+            std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        Ok(Self::Image {
+            source: ImageSource::Base64 {
+                media_type: media_type.to_string(),
+                data: base64::engine::general_purpose::STANDARD.encode(bytes),
+            },
+        })
+    }
+
     /// Create a tool result content block
     pub fn tool_result(tool_use_id: String, content: String, is_error: Option<bool>) -> Self {
         Self::ToolResult {
@@ -63,6 +118,29 @@ impl ContentBlock {
     }
 }
 
+/// Where an image block's data comes from (Anthropic `source` object)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum ImageSource {
+    #[serde(rename = "base64")]
+    Base64 { media_type: String, data: String },
+
+    #[serde(rename = "url")]
+    Url { url: String },
+}
+
+impl ImageSource {
+    /// The image as a URL (base64 data becomes a `data:` URL)
+    pub fn to_url(&self) -> String {
+        match self {
+            ImageSource::Base64 { media_type, data } => {
+                format!("data:{};base64,{}", media_type, data)
+            }
+            ImageSource::Url { url } => url.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Message {
     pub role: String,
@@ -182,6 +260,11 @@ impl Message {
             .any(|block| matches!(block, ContentBlock::ToolResult { .. }))
     }
 
+    /// Check if message contains images
+    pub fn has_images(&self) -> bool {
+        self.content.iter().any(ContentBlock::is_image)
+    }
+
     /// Check if message has no text content
     pub fn is_empty_text(&self) -> bool {
         self.text().is_empty()
@@ -191,19 +274,29 @@ impl Message {
 #[derive(Debug, Clone, Serialize)]
 pub struct MessageRequest {
     pub model: String,
-    pub max_tokens: u32,
+    /// Output token limit (None uses the teacher's configured limit)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
     pub messages: Vec<Message>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tools: Option<Vec<ToolDefinition>>,
+    /// Sampling temperature (None uses the provider's default)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    /// System prompt for this request (added after the configured constitution)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system: Option<String>,
 }
 
 impl MessageRequest {
     pub fn new(user_query: &str) -> Self {
         Self {
             model: "claude-sonnet-4-20250514".to_string(),
-            max_tokens: 4096,
+            max_tokens: None,
             messages: vec![Message::user(user_query)],
             tools: None,
+            temperature: None,
+            system: None,
         }
     }
 
@@ -211,9 +304,11 @@ impl MessageRequest {
     pub fn with_context(messages: Vec<Message>) -> Self {
         Self {
             model: "claude-sonnet-4-20250514".to_string(),
-            max_tokens: 4096,
+            max_tokens: None,
             messages,
             tools: None,
+            temperature: None,
+            system: None,
         }
     }
 
@@ -228,6 +323,31 @@ impl MessageRequest {
         self.tools = Some(tools);
         self
     }
+
+    /// Set sampling temperature
+    pub fn with_temperature(mut self, temperature: f32) -> Self {
+        self.temperature = Some(temperature);
+        self
+    }
+
+    /// Set the system prompt
+    pub fn with_system(mut self, system: impl Into<String>) -> Self {
+        self.system = Some(system.into());
+        self
+    }
+
+    /// Text of the most recent user message that has any
+    ///
+    /// Tool-result turns are skipped, so during a tool loop this is still the
+    /// query the user typed.
+    pub fn latest_user_query(&self) -> Option<String> {
+        self.messages
+            .iter()
+            .rev()
+            .filter(|message| message.role == "user")
+            .map(Message::text)
+            .find(|text| !text.trim().is_empty())
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -239,6 +359,8 @@ pub struct MessageResponse {
     pub content: Vec<ContentBlock>,
     pub model: String,
     pub stop_reason: Option<String>,
+    #[serde(default)]
+    pub usage: Option<crate::providers::TokenUsage>,
 }
 
 impl MessageResponse {
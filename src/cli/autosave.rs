@@ -0,0 +1,211 @@
+// Conversation autosave
+//
+// Persists the conversation to ~/.shammah/sessions/autosave.json after each
+// completed turn so a panic or OOM doesn't lose the session. Writes are
+// debounced and atomic (temp file + rename). The autosave is removed on clean
+// exit, so its presence at startup means the previous session ended abruptly.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use super::ConversationHistory;
+
+/// Minimum time between autosave writes
+pub const AUTOSAVE_DEBOUNCE: Duration = Duration::from_secs(5);
+
+static GLOBAL_AUTOSAVE: OnceLock<Arc<ConversationAutosave>> = OnceLock::new();
+
+/// Debounced, atomic conversation autosave
+#[derive(Debug)]
+pub struct ConversationAutosave {
+    path: PathBuf,
+    debounce: Duration,
+    state: Mutex<AutosaveState>,
+}
+
+#[derive(Debug, Default)]
+struct AutosaveState {
+    last_write: Option<Instant>,
+    /// Serialized conversation not yet written (debounced)
+    pending: Option<String>,
+}
+
+impl ConversationAutosave {
+    pub fn new(path: PathBuf, debounce: Duration) -> Self {
+        Self {
+            path,
+            debounce,
+            state: Mutex::new(AutosaveState::default()),
+        }
+    }
+
+    /// Default autosave location: ~/.shammah/sessions/autosave.json
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| home.join(".shammah").join("sessions").join("autosave.json"))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Record a completed turn; writes immediately unless debounced
+    pub fn record(&self, history: &ConversationHistory) {
+        let json = match serde_json::to_string_pretty(history) {
+            Ok(json) => json,
+            Err(e) => {
+                tracing::warn!("Failed to serialize conversation for autosave: {}", e);
+                return;
+            }
+        };
+
+        let mut state = self.lock_state();
+        state.pending = Some(json);
+
+        let due = state
+            .last_write
+            .map_or(true, |last| last.elapsed() >= self.debounce);
+        if due {
+            self.write_pending(&mut state);
+        }
+    }
+
+    /// Write any debounced changes now
+    pub fn flush(&self) {
+        let mut state = self.lock_state();
+        self.write_pending(&mut state);
+    }
+
+    /// Flush without blocking (safe to call from a panic hook)
+    pub fn try_flush(&self) {
+        if let Ok(mut state) = self.state.try_lock() {
+            self.write_pending(&mut state);
+        }
+    }
+
+    /// Remove the autosave (clean exit)
+    pub fn clear(&self) -> Result<()> {
+        self.lock_state().pending = None;
+        match fs::remove_file(&self.path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e)
+                .with_context(|| format!("Failed to remove autosave {}", self.path.display())),
+        }
+    }
+
+    /// Whether an autosave exists that is newer than the normal saved session
+    pub fn is_restorable(&self, session_path: Option<&Path>) -> bool {
+        let autosave_modified = match fs::metadata(&self.path).and_then(|m| m.modified()) {
+            Ok(modified) => modified,
+            Err(_) => return false,
+        };
+
+        match session_path.and_then(|path| fs::metadata(path).and_then(|m| m.modified()).ok()) {
+            Some(session_modified) => autosave_modified > session_modified,
+            None => true,
+        }
+    }
+
+    /// Load the autosaved conversation
+    pub fn load(&self) -> Result<ConversationHistory> {
+        ConversationHistory::load(&self.path)
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, AutosaveState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn write_pending(&self, state: &mut AutosaveState) {
+        if let Some(json) = state.pending.take() {
+            match write_atomic(&self.path, &json) {
+                Ok(()) => state.last_write = Some(Instant::now()),
+                Err(e) => {
+                    tracing::warn!("Conversation autosave failed: {}", e);
+                    state.pending = Some(json);
+                }
+            }
+        }
+    }
+}
+
+/// Register the process-wide autosave (used by the panic handler)
+pub fn install_global_autosave(autosave: Arc<ConversationAutosave>) {
+    let _ = GLOBAL_AUTOSAVE.set(autosave);
+}
+
+/// Get the process-wide autosave, if installed
+pub fn global_autosave() -> Option<&'static Arc<ConversationAutosave>> {
+    GLOBAL_AUTOSAVE.get()
+}
+
+/// Write `contents` to a temp file next to `path`, then rename into place
+fn write_atomic(path: &Path, contents: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, contents)
+        .with_context(|| format!("Failed to write {}", tmp_path.display()))?;
+    fs::rename(&tmp_path, path)
+        .with_context(|| format!("Failed to move autosave into {}", path.display()))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history_with(text: &str) -> ConversationHistory {
+        let mut history = ConversationHistory::new();
+        history.add_user_message(text.to_string());
+        history
+    }
+
+    #[test]
+    fn test_record_debounces_and_flushes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sessions").join("autosave.json");
+        let autosave = ConversationAutosave::new(path.clone(), Duration::from_secs(3600));
+
+        autosave.record(&history_with("first"));
+        assert_eq!(autosave.load().unwrap().message_count(), 1);
+
+        // Within the debounce window: held in memory until flushed
+        let mut history = history_with("first");
+        history.add_assistant_message("reply".to_string());
+        autosave.record(&history);
+        assert_eq!(autosave.load().unwrap().message_count(), 1);
+
+        autosave.flush();
+        assert_eq!(autosave.load().unwrap().message_count(), 2);
+        assert!(!path.with_extension("json.tmp").exists());
+
+        autosave.clear().unwrap();
+        assert!(!path.exists());
+        autosave.clear().unwrap();
+    }
+
+    #[test]
+    fn test_is_restorable() {
+        let dir = tempfile::tempdir().unwrap();
+        let session_path = dir.path().join("restart_state.json");
+        let autosave = ConversationAutosave::new(dir.path().join("autosave.json"), Duration::ZERO);
+
+        assert!(!autosave.is_restorable(None));
+
+        autosave.record(&history_with("hello"));
+        assert!(autosave.is_restorable(None));
+        assert!(autosave.is_restorable(Some(&session_path)));
+
+        // A newer normal session wins
+        std::thread::sleep(Duration::from_millis(20));
+        history_with("saved").save(&session_path).unwrap();
+        assert!(!autosave.is_restorable(Some(&session_path)));
+    }
+}
@@ -0,0 +1,322 @@
+// Code block presentation (/format, /copy)
+//
+// Fenced code blocks in responses are numbered as they're rendered. With
+// line numbers on, each code line gets a dimmed gutter; in the TUI each
+// block is labelled with its number and the Alt+<n> shortcut that copies it
+// to the clipboard (/copy <n> does the same). Plain mode never shows the
+// shortcut hints. Shortcuts always refer to the latest response's blocks.
+// Blocks tagged with a known language are syntax-highlighted unless
+// `features.render_markdown` is off.
+
+use crate::cli::highlight;
+use crate::config::CodeColors;
+use anyhow::{bail, Context, Result};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+static LINE_NUMBERS: AtomicBool = AtomicBool::new(false);
+static COPY_HINTS: AtomicBool = AtomicBool::new(true);
+static HIGHLIGHT: AtomicBool = AtomicBool::new(true);
+static INTERACTIVE: AtomicBool = AtomicBool::new(false);
+static LATEST: Mutex<Vec<CodeBlock>> = Mutex::new(Vec::new());
+
+const DIM: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+/// Blocks reachable with Alt+<digit>
+pub const MAX_SHORTCUT_BLOCKS: usize = 9;
+
+/// A fenced code block from a response
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// 1-based position in the response
+    pub index: usize,
+    /// Info string after the opening fence (e.g. "rust")
+    pub language: Option<String>,
+    pub code: String,
+}
+
+/// Set the initial options (from `features.code_line_numbers` /
+/// `code_copy_hints` / `render_markdown`)
+pub fn init(line_numbers: bool, copy_hints: bool, highlight: bool) {
+    LINE_NUMBERS.store(line_numbers, Ordering::Relaxed);
+    COPY_HINTS.store(copy_hints, Ordering::Relaxed);
+    HIGHLIGHT.store(highlight, Ordering::Relaxed);
+}
+
+/// Mark output as going to the TUI, where copy shortcuts work
+pub fn set_interactive(interactive: bool) {
+    INTERACTIVE.store(interactive, Ordering::Relaxed);
+}
+
+pub fn line_numbers() -> bool {
+    LINE_NUMBERS.load(Ordering::Relaxed)
+}
+
+pub fn copy_hints() -> bool {
+    COPY_HINTS.load(Ordering::Relaxed)
+}
+
+/// Toggle line numbers (/format). Returns the new setting.
+pub fn toggle_line_numbers() -> bool {
+    !LINE_NUMBERS.fetch_xor(true, Ordering::Relaxed)
+}
+
+/// Toggle copy hints (/format hints). Returns the new setting.
+pub fn toggle_copy_hints() -> bool {
+    !COPY_HINTS.fetch_xor(true, Ordering::Relaxed)
+}
+
+/// Status line for the current options
+pub fn status_message() -> String {
+    let on_off = |on: bool| if on { "on" } else { "off" };
+    format!(
+        "Code blocks: line numbers {} (/format), copy hints {} (/format hints)",
+        on_off(line_numbers()),
+        on_off(copy_hints())
+    )
+}
+
+/// Opening or closing fence: the fence characters and any info string
+fn fence(line: &str) -> Option<(&str, &str)> {
+    let trimmed = line.trim_start();
+    ["```", "~~~"].into_iter().find_map(|marker| {
+        trimmed.starts_with(marker).then(|| {
+            let len = trimmed.len() - trimmed.trim_start_matches(marker.as_bytes()[0] as char).len();
+            (&trimmed[..len], trimmed[len..].trim())
+        })
+    })
+}
+
+/// Walk a response's lines, tracking fenced blocks
+///
+/// Calls `on_line` with each line and the block it belongs to (None
+/// outside blocks and for fence lines). An unterminated block (still
+/// streaming) runs to the end of the text.
+fn scan<'a>(text: &'a str, mut on_line: impl FnMut(&'a str, Option<&CodeBlock>, usize)) -> Vec<CodeBlock> {
+    let mut blocks = Vec::new();
+    let mut open: Option<(String, CodeBlock)> = None;
+    let mut line_in_block = 0;
+
+    for line in text.lines() {
+        match (&mut open, fence(line)) {
+            (None, Some((marker, info))) => {
+                let block = CodeBlock {
+                    index: blocks.len() + 1,
+                    language: (!info.is_empty()).then(|| info.to_string()),
+                    code: String::new(),
+                };
+                on_line(line, None, 0);
+                open = Some((marker.to_string(), block));
+                line_in_block = 0;
+            }
+            (Some((marker, _)), Some((closing, info)))
+                if info.is_empty() && closing.starts_with(marker.as_str()) =>
+            {
+                on_line(line, None, 0);
+                if let Some((_, block)) = open.take() {
+                    blocks.push(block);
+                }
+            }
+            (Some((_, block)), _) => {
+                line_in_block += 1;
+                if line_in_block > 1 {
+                    block.code.push('\n');
+                }
+                block.code.push_str(line);
+                on_line(line, Some(block), line_in_block);
+            }
+            (None, None) => on_line(line, None, 0),
+        }
+    }
+    if let Some((_, block)) = open {
+        blocks.push(block);
+    }
+    blocks
+}
+
+/// Fenced code blocks in a response, numbered from 1
+pub fn extract(text: &str) -> Vec<CodeBlock> {
+    scan(text, |_, _, _| {})
+}
+
+/// Render a response's code blocks with the current options
+pub fn format_response(text: &str, colors: &CodeColors) -> String {
+    render(
+        text,
+        line_numbers(),
+        copy_hints() && INTERACTIVE.load(Ordering::Relaxed),
+        HIGHLIGHT.load(Ordering::Relaxed).then_some(colors),
+    )
+}
+
+/// Render code blocks with line numbers, copy hints and/or highlighting
+pub fn render(text: &str, line_numbers: bool, hints: bool, highlight: Option<&CodeColors>) -> String {
+    if !line_numbers && !hints && highlight.is_none() {
+        return text.to_string();
+    }
+
+    // Gutter width from the longest block
+    let mut width = 1;
+    for block in extract(text) {
+        width = width.max(block.code.lines().count().to_string().len());
+    }
+
+    let mut out = Vec::new();
+    let mut index = 0;
+    let mut in_block = false;
+    scan(text, |line, block, number| {
+        let code = block
+            .and_then(|block| Some((block.language.as_deref()?, highlight?)))
+            .and_then(|(language, colors)| highlight::highlight_line(line, language, colors))
+            .unwrap_or_else(|| line.to_string());
+        match block {
+            Some(_) if line_numbers => {
+                out.push(format!("{}{:>width$} │{} {}", DIM, number, RESET, code, width = width));
+            }
+            Some(_) => out.push(code),
+            None => {
+                let opening = !in_block && fence(line).is_some();
+                if fence(line).is_some() {
+                    in_block = !in_block;
+                }
+                if opening {
+                    index += 1;
+                    if hints {
+                        out.push(copy_hint(index));
+                    }
+                }
+                out.push(line.to_string());
+            }
+        }
+    });
+    let mut rendered = out.join("\n");
+    if text.ends_with('\n') {
+        rendered.push('\n');
+    }
+    rendered
+}
+
+fn copy_hint(index: usize) -> String {
+    if index <= MAX_SHORTCUT_BLOCKS {
+        format!("{}[{}] Alt+{} to copy{}", DIM, index, index, RESET)
+    } else {
+        format!("{}[{}] /copy {} to copy{}", DIM, index, index, RESET)
+    }
+}
+
+/// Remember the latest response's blocks for /copy and Alt+<n>
+///
+/// Returns the number of blocks.
+pub fn set_latest(response: &str) -> usize {
+    let blocks = extract(response);
+    let count = blocks.len();
+    *LATEST.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = blocks;
+    count
+}
+
+/// Status bar hint for the latest response's blocks (None without blocks)
+pub fn status_hint(count: usize) -> Option<String> {
+    match count {
+        0 => None,
+        1 => Some("📋 1 code block · Alt+1 to copy".to_string()),
+        n => Some(format!(
+            "📋 {} code blocks · Alt+1..{} to copy",
+            n,
+            n.min(MAX_SHORTCUT_BLOCKS)
+        )),
+    }
+}
+
+/// Copy block `index` (1-based) of the latest response to the clipboard
+///
+/// Returns the number of lines copied.
+pub fn copy_latest(index: usize) -> Result<usize> {
+    let block = {
+        let latest = LATEST.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if latest.is_empty() {
+            bail!("The last response has no code blocks");
+        }
+        match index.checked_sub(1).and_then(|i| latest.get(i)) {
+            Some(block) => block.clone(),
+            None => bail!("No code block {} (the last response has {})", index, latest.len()),
+        }
+    };
+    let mut clipboard = arboard::Clipboard::new().context("Clipboard unavailable")?;
+    clipboard
+        .set_text(block.code.clone())
+        .context("Failed to copy to clipboard")?;
+    Ok(block.code.lines().count())
+}
+
+/// Copy block `index` and describe the outcome (/copy, Alt+<n>)
+pub fn copy_status(index: usize) -> String {
+    match copy_latest(index) {
+        Ok(lines) => format!(
+            "📋 Copied code block {} ({} line{})",
+            index,
+            lines,
+            if lines == 1 { "" } else { "s" }
+        ),
+        Err(e) => format!("Copy failed: {:#}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESPONSE: &str = "Try this:\n\n```rust\nfn main() {\n    println!(\"hi\");\n}\n```\n\nThen run:\n\n```\ncargo run\n```\n";
+
+    fn strip(text: &str) -> String {
+        text.replace(DIM, "").replace(RESET, "")
+    }
+
+    #[test]
+    fn test_extract_blocks() {
+        let blocks = extract(RESPONSE);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].index, 1);
+        assert_eq!(blocks[0].language.as_deref(), Some("rust"));
+        assert_eq!(blocks[0].code, "fn main() {\n    println!(\"hi\");\n}");
+        assert_eq!(blocks[1].language, None);
+        assert_eq!(blocks[1].code, "cargo run");
+
+        // Still streaming: the open block runs to the end
+        let partial = extract("```sh\nls\nls -la");
+        assert_eq!(partial[0].code, "ls\nls -la");
+    }
+
+    #[test]
+    fn test_render_line_numbers_and_hints() {
+        assert_eq!(render(RESPONSE, false, false, None), RESPONSE);
+
+        let rendered = strip(&render(RESPONSE, true, true, None));
+        assert!(rendered.contains("[1] Alt+1 to copy\n```rust\n1 │ fn main() {\n2 │     println!"));
+        assert!(rendered.contains("[2] Alt+2 to copy\n```\n1 │ cargo run\n```"));
+        assert!(rendered.starts_with("Try this:\n"));
+
+        // Plain mode: line numbers without hints
+        let plain = strip(&render(RESPONSE, true, false, None));
+        assert!(!plain.contains("Alt+"));
+        assert!(plain.contains("3 │ }"));
+    }
+
+    #[test]
+    fn test_render_highlighting() {
+        let colors = CodeColors::default();
+        let rendered = render(RESPONSE, false, false, Some(&colors));
+
+        // Tagged block highlighted, untagged block and prose left alone
+        assert!(rendered.contains("```rust\n\x1b[35mfn\x1b[0m main() {"));
+        assert!(rendered.contains("```\ncargo run\n```"));
+        assert!(rendered.starts_with("Try this:\n"));
+    }
+
+    #[test]
+    fn test_status_hint() {
+        assert_eq!(status_hint(0), None);
+        assert_eq!(status_hint(3).unwrap(), "📋 3 code blocks · Alt+1..3 to copy");
+        assert_eq!(status_hint(12).unwrap(), "📋 12 code blocks · Alt+1..9 to copy");
+    }
+}
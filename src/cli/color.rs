@@ -0,0 +1,120 @@
+// Terminal color switch
+//
+// Color is off when `--no-color` is passed, when NO_COLOR is set to a
+// non-empty value (https://no-color.org), or when stdout isn't a terminal.
+// Output that carries ANSI styling passes through `for_terminal()`, and
+// direct status lines use `paint()`, so both come out as plain text then.
+// crossterm colors (`Stylize`, the TUI) follow the same switch.
+
+use std::borrow::Cow;
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static COLOR: AtomicBool = AtomicBool::new(true);
+
+/// Decide whether to use color (`no_color` is the `--no-color` flag)
+pub fn init(no_color: bool) {
+    let no_color_env = std::env::var_os("NO_COLOR");
+    let enabled = !no_color
+        && !no_color_requested(no_color_env.as_deref())
+        && std::io::stdout().is_terminal();
+    set_enabled(enabled);
+    if !enabled {
+        tracing::debug!("Color output disabled");
+    }
+}
+
+/// Force color on or off (crossterm styling included)
+pub fn set_enabled(enabled: bool) {
+    COLOR.store(enabled, Ordering::Relaxed);
+    crossterm::style::force_color_output(enabled);
+}
+
+/// Whether output may contain ANSI color and style codes
+pub fn enabled() -> bool {
+    COLOR.load(Ordering::Relaxed)
+}
+
+/// NO_COLOR counts only when set to a non-empty value
+fn no_color_requested(value: Option<&std::ffi::OsStr>) -> bool {
+    value.is_some_and(|value| !value.is_empty())
+}
+
+/// Wrap `text` in an SGR style (e.g. "1;32" for bold green) when color is on
+pub fn paint(style: &str, text: impl std::fmt::Display) -> String {
+    if enabled() {
+        format!("\x1b[{}m{}\x1b[0m", style, text)
+    } else {
+        text.to_string()
+    }
+}
+
+/// Remove ANSI escapes from styled text if color is off
+pub fn for_terminal(text: String) -> String {
+    if enabled() {
+        text
+    } else {
+        strip_ansi(&text).into_owned()
+    }
+}
+
+/// Remove ANSI escape sequences (CSI such as colors, and OSC such as hyperlinks)
+pub fn strip_ansi(text: &str) -> Cow<'_, str> {
+    if !text.contains('\x1b') {
+        return Cow::Borrowed(text);
+    }
+
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            // CSI: parameters, then a final byte in @..~
+            Some('[') => {
+                for ch in chars.by_ref() {
+                    if ('@'..='~').contains(&ch) {
+                        break;
+                    }
+                }
+            }
+            // OSC: ends with BEL or ESC \
+            Some(']') => {
+                while let Some(ch) = chars.next() {
+                    if ch == '\x07' {
+                        break;
+                    }
+                    if ch == '\x1b' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Cow::Owned(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::OsStr;
+
+    #[test]
+    fn test_strip_ansi() {
+        assert_eq!(strip_ansi("\x1b[1;32m✓ Saved\x1b[0m\n"), "✓ Saved\n");
+        assert_eq!(strip_ansi("\x1b[38;2;1;2;3mrgb\x1b[0m \x1b[2K"), "rgb ");
+        assert_eq!(strip_ansi("\x1b]8;;https://x.y\x1b\\link\x1b]8;;\x07"), "link");
+        assert!(matches!(strip_ansi("plain"), Cow::Borrowed("plain")));
+    }
+
+    #[test]
+    fn test_no_color_env() {
+        assert!(no_color_requested(Some(OsStr::new("1"))));
+        assert!(!no_color_requested(Some(OsStr::new(""))));
+        assert!(!no_color_requested(None));
+    }
+}
@@ -2,6 +2,9 @@
 
 use anyhow::Result;
 
+use super::help::format_help;
+use super::summarize::SummaryDetail;
+use crate::config::ThinkingLevel;
 use crate::metrics::MetricsLogger;
 use crate::models::ThresholdValidator;
 use crate::router::Router;
@@ -14,13 +17,50 @@ pub enum CommandOutput {
 
 #[derive(Debug)]
 pub enum Command {
-    Help,
+    Help(Option<String>), // Optional search term (/help <term>)
     Quit,
     Metrics,
     Memory,
+    Sampling, // Show teacher temperature per query category
+    Think(Option<ThinkingLevel>), // Session thinking budget (/think <level>), None = teacher config
+    ThinkStatus,                  // Show the session thinking budget (/think)
+    ThinkingToggle,               // Expand/collapse the teacher's thinking (/thinking, Ctrl+T)
+    Temp(Option<f32>),            // Temperature for the next teacher call (/temp <t>), None = clear
+    TempStatus,                   // Show the pending temperature override (/temp)
+    FormatLineNumbers,            // Toggle code block line numbers (/format)
+    FormatHints,                  // Toggle code block copy hints (/format hints)
+    CopyBlock(usize),             // Copy code block n of the last response (/copy [n], Alt+n)
     Debug,
     Training,
+    ModelInfo, // Show the loaded local model: family, size, device, context, adapter (/model-info)
+    // Background LoRA training control (daemon)
+    TrainingPause,
+    TrainingResume,
+    TrainingCancel,
     Clear,
+    Vary(usize),       // Regenerate the last response n times and pick one (/vary [n])
+    Retry,             // Answer the last prompt again with the teacher (/retry)
+    Undo,              // Drop the last prompt and its response from context (/undo)
+    Compact(Option<usize>), // Summarize all but the last n turns (/compact [n]), None = [compaction] keep_turns
+    Pin(Option<String>), // Pin the last message, or a note, against trimming (/pin [text])
+    Pins,                // List pins
+    Context,             // Show the messages the next teacher call would send (/context)
+    Export { path: Option<String> }, // Save the conversation as Markdown (/export [file])
+    Unpin(usize),        // Remove pin n (1-based)
+    Summarize { path: String, detail: SummaryDetail }, // Summarize a file (/summarize <path> [--detail <level>])
+    Image(String),     // Attach an image file to the next query (/image <path>)
+    ImageClear,        // Drop pending image attachments (/image clear)
+    HistoryClear,      // Delete saved input history (/history clear)
+    Tools,             // List registered tools with permission status
+    ToolsToggle(bool), // Enable/disable tool definitions for this session
+    DryRun(bool),      // Session-wide bash dry-run: report commands without running them (/dryrun on|off)
+    DryRunStatus,      // Show whether bash dry-run is on (/dryrun)
+    BenchmarkTools { network: bool }, // Self-test each tool (/benchmark-tools [--network])
+    ReplayTool(usize), // Run recent tool call n again (1 = last), with the usual approval
+    ReplayToolList,    // Number the recent tool calls (/replay-tool list)
+    Watch(super::watch::WatchSpec), // Re-run a prompt when matching files change (/watch <glob> :: <prompt>)
+    WatchStatus,                    // Show the active watch (/watch)
+    WatchStop,                      // Stop watching (/watch stop)
     PatternsList,
     PatternsRemove(String),
     PatternsClear,
@@ -28,12 +68,20 @@ pub enum Command {
     // Plan mode commands
     PlanModeToggle,  // Toggle plan mode on/off (Shift+Tab or /plan without args)
     Plan(String),
+    Done, // Summarize tool changes since the last /done and leave plan mode
     // Feedback commands for weighted LoRA training
     FeedbackCritical(Option<String>), // High-weight (10x) - critical strategy errors
     FeedbackMedium(Option<String>),   // Medium-weight (3x) - improvements
     FeedbackGood(Option<String>),     // Normal-weight (1x) - good examples
-    // Local model testing
-    Local { query: String }, // Query local model directly (bypass routing)
+    // Explicit routing (bypass the router)
+    Local { query: String },   // Query local model directly
+    Forward { query: String }, // Send query straight to the teacher
+    ForwardMode(bool),         // Sticky: forward every query (/mode forward | /mode auto)
+    TtsToggle(bool),           // Speak responses aloud (/tts on | /tts off)
+    ProfileToggle(Option<bool>), // Time query stages (/profile [on|off], no arg toggles)
+    ProviderLatency,           // Teacher pool rotation and per-key latency
+    // One-shot tool override (/no-tools, /with-tools)
+    ToolsOverride { query: String, enabled: bool },
     // MCP plugin management
     McpList,                  // List connected MCP servers
     McpTools(Option<String>), // List tools from specific server (or all if None)
@@ -47,13 +95,57 @@ impl Command {
 
         // Handle simple commands without arguments
         match trimmed {
-            "/help" => return Some(Command::Help),
+            "/help" => return Some(Command::Help(None)),
             "/quit" | "/exit" => return Some(Command::Quit),
             "/metrics" => return Some(Command::Metrics),
             "/memory" => return Some(Command::Memory),
+            "/sampling" => return Some(Command::Sampling),
+            "/think" => return Some(Command::ThinkStatus),
+            "/think default" => return Some(Command::Think(None)),
+            "/thinking" => return Some(Command::ThinkingToggle),
+            "/temp" => return Some(Command::TempStatus),
+            "/temp off" => return Some(Command::Temp(None)),
             "/debug" => return Some(Command::Debug),
             "/training" => return Some(Command::Training),
+            "/model-info" => return Some(Command::ModelInfo),
+            "/training pause" => return Some(Command::TrainingPause),
+            "/training resume" => return Some(Command::TrainingResume),
+            "/training cancel" | "/cancel-training" => return Some(Command::TrainingCancel),
             "/clear" | "/reset" => return Some(Command::Clear),
+            "/history clear" => return Some(Command::HistoryClear),
+            "/image clear" => return Some(Command::ImageClear),
+            "/vary" => return Some(Command::Vary(super::vary::DEFAULT_VARIANTS)),
+            "/retry" => return Some(Command::Retry),
+            "/undo" => return Some(Command::Undo),
+            "/compact" => return Some(Command::Compact(None)),
+            "/pin" => return Some(Command::Pin(None)),
+            "/pins" => return Some(Command::Pins),
+            "/context" => return Some(Command::Context),
+            "/export" => return Some(Command::Export { path: None }),
+            "/tools" => return Some(Command::Tools),
+            "/tools on" => return Some(Command::ToolsToggle(true)),
+            "/tools off" => return Some(Command::ToolsToggle(false)),
+            "/dryrun" => return Some(Command::DryRunStatus),
+            "/dryrun on" => return Some(Command::DryRun(true)),
+            "/dryrun off" => return Some(Command::DryRun(false)),
+            "/benchmark-tools" => return Some(Command::BenchmarkTools { network: false }),
+            "/benchmark-tools --network" => return Some(Command::BenchmarkTools { network: true }),
+            "/replay-tool" => return Some(Command::ReplayTool(1)),
+            "/replay-tool list" => return Some(Command::ReplayToolList),
+            "/watch" => return Some(Command::WatchStatus),
+            "/format" => return Some(Command::FormatLineNumbers),
+            "/format hints" => return Some(Command::FormatHints),
+            "/copy" => return Some(Command::CopyBlock(1)),
+            "/watch stop" => return Some(Command::WatchStop),
+            "/mode forward" => return Some(Command::ForwardMode(true)),
+            "/mode auto" => return Some(Command::ForwardMode(false)),
+            "/done" => return Some(Command::Done),
+            "/tts on" | "/voice on" => return Some(Command::TtsToggle(true)),
+            "/tts off" | "/voice off" => return Some(Command::TtsToggle(false)),
+            "/profile" => return Some(Command::ProfileToggle(None)),
+            "/provider-latency" => return Some(Command::ProviderLatency),
+            "/profile on" => return Some(Command::ProfileToggle(Some(true))),
+            "/profile off" => return Some(Command::ProfileToggle(Some(false))),
             // Feedback commands (simple form)
             "/critical" => return Some(Command::FeedbackCritical(None)),
             "/medium" => return Some(Command::FeedbackMedium(None)),
@@ -61,6 +153,87 @@ impl Command {
             _ => {}
         }
 
+        if let Some(rest) = trimmed.strip_prefix("/help ") {
+            let topic = rest.trim();
+            return Some(Command::Help(if topic.is_empty() {
+                None
+            } else {
+                Some(topic.to_string())
+            }));
+        }
+
+        if let Some(count) = trimmed.strip_prefix("/vary ") {
+            return match count.trim().parse::<usize>() {
+                Ok(count) if count > 0 => Some(Command::Vary(count)),
+                _ => None,
+            };
+        }
+
+        if let Some(turns) = trimmed.strip_prefix("/compact ") {
+            return match turns.trim().parse::<usize>() {
+                Ok(turns) if turns > 0 => Some(Command::Compact(Some(turns))),
+                _ => None,
+            };
+        }
+
+        if let Some(rest) = trimmed.strip_prefix("/summarize ") {
+            return parse_summarize(rest);
+        }
+
+        if let Some(path) = trimmed.strip_prefix("/export ") {
+            let path = path.trim();
+            return Some(Command::Export {
+                path: (!path.is_empty()).then(|| path.to_string()),
+            });
+        }
+
+        if let Some(path) = trimmed.strip_prefix("/image ") {
+            let path = path.trim();
+            return (!path.is_empty()).then(|| Command::Image(path.to_string()));
+        }
+
+        if let Some(note) = trimmed.strip_prefix("/pin ") {
+            let note = note.trim();
+            return Some(Command::Pin(if note.is_empty() {
+                None
+            } else {
+                Some(note.to_string())
+            }));
+        }
+
+        if let Some(level) = trimmed.strip_prefix("/think ") {
+            return ThinkingLevel::parse(level).ok().map(|level| Command::Think(Some(level)));
+        }
+
+        if let Some(value) = trimmed.strip_prefix("/temp ") {
+            return value.trim().parse::<f32>().ok().map(|value| Command::Temp(Some(value)));
+        }
+
+        if let Some(number) = trimmed.strip_prefix("/replay-tool ") {
+            return match number.trim().parse::<usize>() {
+                Ok(number) if number > 0 => Some(Command::ReplayTool(number)),
+                _ => None,
+            };
+        }
+
+        if let Some(args) = trimmed.strip_prefix("/watch ") {
+            return super::watch::WatchSpec::parse(args).ok().map(Command::Watch);
+        }
+
+        if let Some(number) = trimmed.strip_prefix("/copy ") {
+            return match number.trim().parse::<usize>() {
+                Ok(number) if number > 0 => Some(Command::CopyBlock(number)),
+                _ => None,
+            };
+        }
+
+        if let Some(number) = trimmed.strip_prefix("/unpin ") {
+            return match number.trim().parse::<usize>() {
+                Ok(number) if number > 0 => Some(Command::Unpin(number)),
+                _ => None,
+            };
+        }
+
         // Handle /plan command
         if trimmed == "/plan" {
             // Without arguments: toggle plan mode
@@ -81,6 +254,7 @@ impl Command {
         if let Some(rest) = trimmed
             .strip_prefix("/feedback critical ")
             .or_else(|| trimmed.strip_prefix("/feedback high "))
+            .or_else(|| trimmed.strip_prefix("/critical "))
         {
             let explanation = rest.trim();
             return Some(Command::FeedbackCritical(if explanation.is_empty() {
@@ -94,7 +268,10 @@ impl Command {
             return Some(Command::FeedbackCritical(None));
         }
 
-        if let Some(rest) = trimmed.strip_prefix("/feedback medium ") {
+        if let Some(rest) = trimmed
+            .strip_prefix("/feedback medium ")
+            .or_else(|| trimmed.strip_prefix("/medium "))
+        {
             let explanation = rest.trim();
             return Some(Command::FeedbackMedium(if explanation.is_empty() {
                 None
@@ -110,6 +287,7 @@ impl Command {
         if let Some(rest) = trimmed
             .strip_prefix("/feedback good ")
             .or_else(|| trimmed.strip_prefix("/feedback normal "))
+            .or_else(|| trimmed.strip_prefix("/good "))
         {
             let explanation = rest.trim();
             return Some(Command::FeedbackGood(if explanation.is_empty() {
@@ -133,6 +311,29 @@ impl Command {
             }
         }
 
+        // Handle /forward command with query
+        if let Some(rest) = trimmed.strip_prefix("/forward ") {
+            let query = rest.trim();
+            if !query.is_empty() {
+                return Some(Command::Forward {
+                    query: query.to_string(),
+                });
+            }
+        }
+
+        // Handle /no-tools and /with-tools one-shot queries
+        for (prefix, enabled) in [("/no-tools ", false), ("/with-tools ", true)] {
+            if let Some(rest) = trimmed.strip_prefix(prefix) {
+                let query = rest.trim();
+                if !query.is_empty() {
+                    return Some(Command::ToolsOverride {
+                        query: query.to_string(),
+                        enabled,
+                    });
+                }
+            }
+        }
+
         // Handle /mcp commands with subcommands
         if trimmed == "/mcp" || trimmed == "/mcp list" {
             return Some(Command::McpList);
@@ -189,6 +390,26 @@ impl Command {
     }
 }
 
+/// Parse the arguments of `/summarize <path> [--detail <level>]`
+///
+/// `--detail` may come before or after the path; the remaining words are
+/// the path, so paths with spaces work unquoted.
+fn parse_summarize(args: &str) -> Option<Command> {
+    let mut words: Vec<&str> = args.split_whitespace().collect();
+    let mut detail = SummaryDetail::default();
+    if let Some(index) = words.iter().position(|word| *word == "--detail") {
+        detail = words.get(index + 1)?.parse().ok()?;
+        words.drain(index..index + 2);
+    }
+    if words.is_empty() {
+        return None;
+    }
+    Some(Command::Summarize {
+        path: words.join(" "),
+        detail,
+    })
+}
+
 pub fn handle_command(
     command: Command,
     metrics_logger: &MetricsLogger,
@@ -198,9 +419,13 @@ pub fn handle_command(
 ) -> Result<CommandOutput> {
     match command {
         // Long-form outputs go to scrollback
-        Command::Help => Ok(CommandOutput::Message(format_help())),
+        Command::Help(topic) => Ok(CommandOutput::Message(format_help(topic.as_deref(), true))),
         Command::Metrics => Ok(CommandOutput::Message(format_metrics(metrics_logger)?)),
         Command::Training => Ok(CommandOutput::Message(format_training(router, validator)?)),
+        // Needs the generator state (or the daemon), handled directly in REPL
+        Command::ModelInfo => {
+            Ok(CommandOutput::Status("Model info command should be handled in REPL.".to_string()))
+        }
 
         // Short outputs go to status bar
         Command::Debug => {
@@ -212,6 +437,57 @@ pub fn handle_command(
         }
         Command::Quit => Ok(CommandOutput::Status("Goodbye!".to_string())),
         Command::Clear => Ok(CommandOutput::Status("".to_string())), // Handled in REPL directly
+        // Variants need the generators and conversation, handled directly in REPL
+        Command::Vary(_) => {
+            Ok(CommandOutput::Status("Vary command should be handled in REPL.".to_string()))
+        }
+        // Retrying re-runs a query, handled directly in REPL
+        Command::Retry => {
+            Ok(CommandOutput::Status("Retry command should be handled in REPL.".to_string()))
+        }
+        // Undo edits the conversation, handled directly in REPL
+        Command::Undo => {
+            Ok(CommandOutput::Status("Undo command should be handled in REPL.".to_string()))
+        }
+        // Compaction needs the teacher and conversation, handled directly in REPL
+        Command::Compact(_) => {
+            Ok(CommandOutput::Status("Compact command should be handled in REPL.".to_string()))
+        }
+        // Summaries need the generators, handled directly in REPL
+        Command::Summarize { .. } => {
+            Ok(CommandOutput::Status("Summarize command should be handled in REPL.".to_string()))
+        }
+        // Attachments go out with the next query, handled directly in REPL
+        Command::Image(_) | Command::ImageClear => {
+            Ok(CommandOutput::Status("Image command should be handled in REPL.".to_string()))
+        }
+        // Exports read the conversation, handled directly in REPL
+        Command::Export { .. } => {
+            Ok(CommandOutput::Status("Export command should be handled in REPL.".to_string()))
+        }
+        // Pins live in the conversation, handled directly in REPL
+        Command::Pin(_) | Command::Pins | Command::Unpin(_) => {
+            Ok(CommandOutput::Status("Pin command should be handled in REPL.".to_string()))
+        }
+        // History lives in the input handler, handled directly in REPL
+        Command::HistoryClear => {
+            Ok(CommandOutput::Status("History command should be handled in REPL.".to_string()))
+        }
+        // Watches submit queries, handled directly in REPL
+        Command::Watch(_) | Command::WatchStatus | Command::WatchStop => {
+            Ok(CommandOutput::Status("Watch command should be handled in REPL.".to_string()))
+        }
+        // Tool commands need the tool executor or submit queries, handled directly in REPL
+        Command::Tools
+        | Command::ToolsToggle(_)
+        | Command::DryRun(_)
+        | Command::DryRunStatus
+        | Command::ToolsOverride { .. }
+        | Command::BenchmarkTools { .. }
+        | Command::ReplayTool(_)
+        | Command::ReplayToolList => {
+            Ok(CommandOutput::Status("Tools command should be handled in REPL.".to_string()))
+        }
         // Pattern commands are now handled directly in REPL
         Command::PatternsList
         | Command::PatternsRemove(_)
@@ -221,16 +497,63 @@ pub fn handle_command(
         }
         // Plan mode commands are handled directly in REPL
         Command::PlanModeToggle
-        | Command::Plan(_) => {
+        | Command::Plan(_)
+        | Command::Done => {
             Ok(CommandOutput::Status("Plan mode commands should be handled in REPL.".to_string()))
         }
         // Feedback commands are handled directly in REPL
         Command::FeedbackCritical(_) | Command::FeedbackMedium(_) | Command::FeedbackGood(_) => {
             Ok(CommandOutput::Status("Feedback commands should be handled in REPL.".to_string()))
         }
-        // Local command is handled directly in REPL
-        Command::Local { .. } => {
-            Ok(CommandOutput::Status("Local command should be handled in REPL.".to_string()))
+        // Explicit routing commands are handled directly in REPL
+        Command::Local { .. } | Command::Forward { .. } | Command::ForwardMode(_) => {
+            Ok(CommandOutput::Status("Routing commands should be handled in REPL.".to_string()))
+        }
+        Command::TtsToggle(enabled) => {
+            crate::cli::tts::set_enabled(enabled);
+            Ok(CommandOutput::Status(if enabled {
+                format!("Speech on ({})", crate::cli::tts::command_line())
+            } else {
+                "Speech off".to_string()
+            }))
+        }
+        Command::ProfileToggle(enabled) => {
+            let enabled = enabled.unwrap_or(!crate::cli::profile::is_enabled());
+            crate::cli::profile::set_enabled(enabled);
+            Ok(CommandOutput::Status(crate::cli::profile::status_message(enabled)))
+        }
+        Command::ProviderLatency => Ok(CommandOutput::Message(format_provider_latency())),
+        // Training control commands are handled directly in REPL (daemon mode)
+        Command::TrainingPause | Command::TrainingResume | Command::TrainingCancel => {
+            Ok(CommandOutput::Status("Training control commands should be handled in REPL.".to_string()))
+        }
+        // Temperatures live in the REPL's config, handled directly in REPL
+        Command::Sampling => {
+            Ok(CommandOutput::Status("Sampling command should be handled in REPL.".to_string()))
+        }
+        // The thinking override lives on the teacher client, handled directly in REPL
+        Command::Think(_) | Command::ThinkStatus => {
+            Ok(CommandOutput::Status("Think command should be handled in REPL.".to_string()))
+        }
+        // So does the temperature override
+        Command::Temp(_) | Command::TempStatus => {
+            Ok(CommandOutput::Status("Temp command should be handled in REPL.".to_string()))
+        }
+        Command::ThinkingToggle => Ok(CommandOutput::Status(super::thinking::status_message(
+            super::thinking::toggle(),
+        ))),
+        Command::FormatLineNumbers => {
+            super::code_blocks::toggle_line_numbers();
+            Ok(CommandOutput::Status(super::code_blocks::status_message()))
+        }
+        Command::FormatHints => {
+            super::code_blocks::toggle_copy_hints();
+            Ok(CommandOutput::Status(super::code_blocks::status_message()))
+        }
+        Command::CopyBlock(index) => Ok(CommandOutput::Status(super::code_blocks::copy_status(index))),
+        // The teacher request is built in the REPL
+        Command::Context => {
+            Ok(CommandOutput::Status("Context command should be handled in REPL.".to_string()))
         }
         // Memory command is handled directly in REPL
         Command::Memory => {
@@ -243,89 +566,179 @@ pub fn handle_command(
     }
 }
 
-pub fn format_help() -> String {
+/// The session's thinking budget (/think)
+pub fn thinking_status(level: Option<ThinkingLevel>) -> String {
+    match level {
+        None => "Thinking: each teacher's configured setting \
+                 (set with /think off|low|medium|high|<tokens>)"
+            .to_string(),
+        Some(ThinkingLevel::Off) => {
+            "Thinking: off for this session (/think default restores the teacher settings)".to_string()
+        }
+        Some(level) => format!(
+            "Thinking: {} for this session (Claude extended thinking, OpenAI reasoning effort; \
+             other teachers ignore it)",
+            level
+        ),
+    }
+}
+
+/// Listing for /tools: every tool with its permission rule, approvals and uses
+///
+/// `allowed_in_mode` says whether the current REPL mode lets a tool run.
+pub async fn tools_listing(
+    executor: &crate::tools::executor::ToolExecutor,
+    allowed_in_mode: impl Fn(&str) -> bool,
+) -> String {
+    use crate::tools::permissions::PermissionRule;
+
+    let mut tools: Vec<(String, String)> = executor
+        .registry()
+        .get_all_tools()
+        .into_iter()
+        .map(|tool| (tool.name().to_string(), tool.description().to_string()))
+        .collect();
+    tools.sort_by(|a, b| a.0.cmp(&b.0));
+
+    if let Some(mcp_client) = executor.mcp_client() {
+        tools.extend(
+            mcp_client
+                .list_tools()
+                .await
+                .into_iter()
+                .map(|tool| (tool.name, tool.description)),
+        );
+    }
+
+    let mut output = format!("🔧 Available Tools ({}):\n\n", tools.len());
+    for (name, description) in &tools {
+        let in_mode = if allowed_in_mode(name) {
+            "allowed in current mode"
+        } else {
+            "blocked in plan mode"
+        };
+        let rule = match executor.permissions().rule_for(name) {
+            PermissionRule::Allow => "auto-allow",
+            PermissionRule::Ask => "ask",
+            PermissionRule::Deny => "denied",
+        };
+        let approvals = executor.approval_counts(name);
+        let uses = executor.usage_count(name);
+
+        output.push_str(&format!("  • {}\n", name));
+        // First line of the description keeps the listing compact
+        output.push_str(&format!(
+            "    {}\n",
+            description.lines().next().unwrap_or("")
+        ));
+        output.push_str(&format!(
+            "    {} | rule: {} | approvals: {} task, {} session, {} saved | used {}× this session\n",
+            in_mode, rule, approvals.task, approvals.session, approvals.persistent, uses
+        ));
+    }
+    output.push_str("\nManage saved approvals with /patterns");
+    output
+}
+
+/// Message for /dryrun, after a toggle or as a status check
+pub fn dry_run_status(enabled: bool) -> &'static str {
+    if enabled {
+        "🧪 Bash dry-run is on: commands are shown, not run (/dryrun off to run them)."
+    } else {
+        "🧪 Bash dry-run is off: approved commands run normally."
+    }
+}
+
+/// Result of /undo: None if there was nothing to remove
+pub fn undo_status(removed: Option<&[crate::claude::Message]>, remaining: usize) -> String {
+    let Some(removed) = removed else {
+        return "Nothing to undo - the conversation has no prompts.".to_string();
+    };
+    let prompt = removed.first().map(|message| message.text()).unwrap_or_default();
+    let preview: String = prompt.chars().take(60).collect();
+    let ellipsis = if prompt.chars().count() > 60 { "…" } else { "" };
     format!(
-        "\x1b[1;36m╔═══════════════════════════════════════════════════════════════════════╗\x1b[0m\n\
-         \x1b[1;36m║\x1b[0m                  \x1b[1;32mShammah Help - Commands & Shortcuts\x1b[0m                  \x1b[1;36m║\x1b[0m\n\
-         \x1b[1;36m╚═══════════════════════════════════════════════════════════════════════╝\x1b[0m\n\n\
-         \x1b[1;33m📋 Basic Commands:\x1b[0m\n\
-         \x1b[36m  /help\x1b[0m              Show this help message\n\
-         \x1b[36m  /quit\x1b[0m              Exit the REPL (also: Ctrl+D)\n\
-         \x1b[36m  /clear\x1b[0m             Clear conversation history (start fresh)\n\
-         \x1b[36m  /debug\x1b[0m             Toggle debug output\n\
-         \x1b[36m  /metrics\x1b[0m           Display usage statistics\n\
-         \x1b[36m  /memory\x1b[0m            Show memory usage (system and process)\n\
-         \x1b[36m  /training\x1b[0m          Show detailed training statistics\n\n\
-         \x1b[1;33m🤖 Model Commands:\x1b[0m\n\
-         \x1b[36m  /local <query>\x1b[0m     Query local model directly (bypass routing)\n\
-         \x1b[0m                     Example: /local What is 2+2?\n\n\
-         \x1b[1;33m🔌 MCP Plugin Commands:\x1b[0m\n\
-         \x1b[36m  /mcp list\x1b[0m          List connected MCP servers\n\
-         \x1b[36m  /mcp tools\x1b[0m         List all MCP tools from all servers\n\
-         \x1b[36m  /mcp tools <srv>\x1b[0m   List tools from specific server\n\
-         \x1b[36m  /mcp refresh\x1b[0m       Refresh tool list from all servers\n\
-         \x1b[36m  /mcp reload\x1b[0m        Reconnect to all MCP servers\n\
-         \x1b[0m\n\
-         \x1b[90m  What is MCP?\x1b[0m Model Context Protocol - extend Shammah with external\n\
-         \x1b[90m  tools (GitHub, filesystem, databases, etc.) via MCP servers.\n\n\
-         \x1b[1;33m🔒 Tool Confirmation Patterns:\x1b[0m\n\
-         \x1b[36m  /patterns\x1b[0m          List all saved confirmation patterns\n\
-         \x1b[36m  /patterns add\x1b[0m      Add a new pattern (interactive wizard)\n\
-         \x1b[36m  /patterns rm <id>\x1b[0m  Remove a specific pattern by ID\n\
-         \x1b[36m  /patterns clear\x1b[0m    Remove all patterns (requires confirmation)\n\
-         \x1b[0m\n\
-         \x1b[90m  What are patterns?\x1b[0m Saved rules for auto-approving tool executions.\n\
-         \x1b[90m  Example:\x1b[0m \"Always allow reading *.rs files\" or \"Allow git status\"\n\n\
-         \x1b[1;33m📝 Plan Mode:\x1b[0m\n\
-         \x1b[90m  Claude can enter plan mode to explore your codebase in read-only mode,\x1b[0m\n\
-         \x1b[90m  then present a plan for your approval via an interactive dialog.\x1b[0m\n\
-         \x1b[0m\n\
-         \x1b[90m  Workflow:\x1b[0m 1. Ask Claude to plan → 2. Claude explores (read-only) →\n\
-         \x1b[90m            3. Claude presents plan → 4. Dialog appears automatically →\n\
-         \x1b[90m            5. You approve/request changes/reject → 6. Execution\n\n\
-         \x1b[1;33m🎓 Weighted Feedback (LoRA Fine-Tuning):\x1b[0m\n\
-         \x1b[36m  /critical [note]\x1b[0m   Mark response as \x1b[31mcritical error\x1b[0m (10x training weight)\n\
-         \x1b[36m  /medium [note]\x1b[0m     Mark response \x1b[33mneeds improvement\x1b[0m (3x weight)\n\
-         \x1b[36m  /good [note]\x1b[0m       Mark response as \x1b[32mgood example\x1b[0m (1x weight)\n\
-         \x1b[0m\n\
-         \x1b[90m  Aliases:\x1b[0m /feedback critical|high|medium|good [note]\n\
-         \x1b[0m\n\
-         \x1b[90m  Examples:\x1b[0m\n\
-         \x1b[90m    /critical\x1b[0m Never use .unwrap() in production code\n\
-         \x1b[90m    /medium\x1b[0m Prefer iterator chains over manual loops\n\
-         \x1b[90m    /good\x1b[0m This is exactly the right approach\n\n\
-         \x1b[1;33m⌨️  Keyboard Shortcuts:\x1b[0m\n\
-         \x1b[36m  Ctrl+C\x1b[0m             Cancel current query (interrupts generation)\n\
-         \x1b[36m  Ctrl+D\x1b[0m             Exit REPL (same as /quit)\n\
-         \x1b[36m  Ctrl+G\x1b[0m             Mark last response as \x1b[32mgood\x1b[0m (1x training weight)\n\
-         \x1b[36m  Ctrl+B\x1b[0m             Mark last response as \x1b[31mbad\x1b[0m (10x training weight)\n\
-         \x1b[36m  Shift+Tab\x1b[0m          Toggle plan mode on/off\n\
-         \x1b[36m  Shift+Enter\x1b[0m        Multi-line input (insert newline)\n\
-         \x1b[36m  Shift+PgUp\x1b[0m         Scroll up in history\n\
-         \x1b[36m  Shift+PgDown\x1b[0m       Scroll down in history\n\
-         \x1b[90m  ↑ / ↓ arrows\x1b[0m       Navigate command history\n\n\
-         \x1b[1;33m🛠️  Tool Execution:\x1b[0m\n\
-         When Claude needs to use tools (read files, run commands, etc.), you'll\n\
-         be asked to approve each action. You can:\n\
-         \x1b[32m  • Approve once\x1b[0m              Execute this time only\n\
-         \x1b[32m  • Approve for session\x1b[0m      Allow during this session\n\
-         \x1b[32m  • Remember pattern\x1b[0m         Always allow (saves to /patterns)\n\
-         \x1b[31m  • Deny\x1b[0m                     Reject the action\n\n\
-         Available tools: Read, Glob, Grep, WebFetch, Bash, Restart\n\n\
-         \x1b[1;33m📚 Learn More:\x1b[0m\n\
-         \x1b[36m  GitHub:\x1b[0m   https://github.com/schancel/shammah\n\
-         \x1b[36m  Issues:\x1b[0m   https://github.com/schancel/shammah/issues\n\
-         \x1b[36m  Docs:\x1b[0m     See README.md and docs/ folder\n\n\
-         \x1b[1;33m💡 Quick Start:\x1b[0m\n\
-         Just type your question! Examples:\n\
-         \x1b[90m  • How do I implement a binary search in Rust?\x1b[0m\n\
-         \x1b[90m  • Can you read my Cargo.toml and explain the dependencies?\x1b[0m\n\
-         \x1b[90m  • Find all TODO comments in my code\x1b[0m\n\n\
-         \x1b[1;36m─────────────────────────────────────────────────────────────────────────\x1b[0m\n\
-         \x1b[90mTip: Use Ctrl+C to cancel long-running queries\x1b[0m"
+        "↩️  Removed \"{}{}\" ({} message{}) - {} turn{} left in context",
+        preview,
+        ellipsis,
+        removed.len(),
+        if removed.len() == 1 { "" } else { "s" },
+        remaining,
+        if remaining == 1 { "" } else { "s" }
     )
 }
 
+/// Result of /compact: turns before, turns kept verbatim, and what was reclaimed
+pub fn compact_status(
+    turns_before: usize,
+    turns_kept: usize,
+    report: &super::conversation::CompactionReport,
+) -> String {
+    format!(
+        "🗜️  Compacted {} turn{} into a summary + {} recent turn{} ({} → {} messages, ~{} tokens reclaimed)",
+        turns_before,
+        if turns_before == 1 { "" } else { "s" },
+        turns_kept,
+        if turns_kept == 1 { "" } else { "s" },
+        report.messages_before,
+        report.messages_after,
+        report.reclaimed_tokens()
+    )
+}
+
+/// The pending temperature override (/temp)
+pub fn temperature_status(value: Option<f32>) -> String {
+    match value {
+        None => "Temperature: configured per query category (set the next teacher call's with /temp <0.0-2.0>)"
+            .to_string(),
+        Some(value) => format!(
+            "Temperature: {} for the next teacher call (/temp off cancels)",
+            value
+        ),
+    }
+}
+
+/// Teacher pool rotation state and per-key latency (/provider-latency)
+pub fn format_provider_latency() -> String {
+    use crate::providers::pool::RATE_LIMIT_COOLDOWN;
+
+    let pools = crate::providers::pool_statuses();
+    if pools.is_empty() {
+        return "No teacher pools configured.\n\
+                Give several [[teachers]] entries the same `pool = \"name\"` to rotate between their keys."
+            .to_string();
+    }
+
+    let format_secs = |d: std::time::Duration| format!("{:.2}s", d.as_secs_f64());
+    let mut output = format!(
+        "Teacher pools (round-robin, rate-limited keys skipped for {}s):\n",
+        RATE_LIMIT_COOLDOWN.as_secs()
+    );
+    for pool in pools {
+        output.push_str(&format!("\n  {} ({})\n", pool.name, pool.provider));
+        for (idx, member) in pool.members.iter().enumerate() {
+            let marker = if idx == pool.next { "→" } else { " " };
+            let mut line = format!("    {} {:<16} {:>4} requests", marker, member.label, member.requests);
+            if member.failures > 0 {
+                line.push_str(&format!(", {} failed", member.failures));
+            }
+            if let Some(avg) = member.avg_latency {
+                line.push_str(&format!("   avg {}", format_secs(avg)));
+            }
+            if let Some(last) = member.last_latency {
+                line.push_str(&format!("   last {}", format_secs(last)));
+            }
+            if let Some(remaining) = member.throttled_for {
+                line.push_str(&format!("   rate limited ({}s left)", remaining.as_secs().max(1)));
+            }
+            output.push_str(&line);
+            output.push('\n');
+        }
+    }
+    output.push_str("\n→ marks the key the next request starts with");
+    output
+}
+
 fn format_metrics(metrics_logger: &MetricsLogger) -> Result<String> {
     let summary = metrics_logger.get_today_summary()?;
 
@@ -531,15 +944,207 @@ mod tests {
     #[test]
     fn test_parse_existing_commands() {
         // Ensure existing commands still work
-        assert!(matches!(Command::parse("/help"), Some(Command::Help)));
+        assert!(matches!(Command::parse("/help"), Some(Command::Help(None))));
+        match Command::parse("/help  patterns ") {
+            Some(Command::Help(Some(topic))) => assert_eq!(topic, "patterns"),
+            other => panic!("unexpected parse: {:?}", other),
+        }
         assert!(matches!(Command::parse("/quit"), Some(Command::Quit)));
         assert!(matches!(Command::parse("/metrics"), Some(Command::Metrics)));
         assert!(matches!(Command::parse("/debug"), Some(Command::Debug)));
+        assert!(matches!(Command::parse("/model-info"), Some(Command::ModelInfo)));
         assert!(matches!(
             Command::parse("/training"),
             Some(Command::Training)
         ));
         assert!(matches!(Command::parse("/clear"), Some(Command::Clear)));
+        assert!(matches!(Command::parse("/history clear"), Some(Command::HistoryClear)));
+        assert!(matches!(Command::parse("/vary"), Some(Command::Vary(3))));
+        assert!(matches!(Command::parse("/vary 5"), Some(Command::Vary(5))));
+        assert!(Command::parse("/vary 0").is_none());
+        assert!(Command::parse("/vary lots").is_none());
+        assert!(matches!(Command::parse("/retry"), Some(Command::Retry)));
+        assert!(matches!(Command::parse("/undo"), Some(Command::Undo)));
+        assert!(matches!(Command::parse("/compact"), Some(Command::Compact(None))));
+        assert!(matches!(Command::parse("/compact 3"), Some(Command::Compact(Some(3)))));
+        assert!(Command::parse("/compact 0").is_none());
+        assert!(matches!(Command::parse("/pin"), Some(Command::Pin(None))));
+        match Command::parse("/pin  Use metric units ") {
+            Some(Command::Pin(Some(note))) => assert_eq!(note, "Use metric units"),
+            other => panic!("unexpected parse: {:?}", other),
+        }
+        assert!(matches!(Command::parse("/pins"), Some(Command::Pins)));
+        match Command::parse("/summarize src/main.rs --detail brief") {
+            Some(Command::Summarize { path, detail }) => {
+                assert_eq!(path, "src/main.rs");
+                assert_eq!(detail, SummaryDetail::Brief);
+            }
+            other => panic!("unexpected parse: {:?}", other),
+        }
+        match Command::parse("/summarize --detail detailed my notes.txt") {
+            Some(Command::Summarize { path, detail }) => {
+                assert_eq!(path, "my notes.txt");
+                assert_eq!(detail, SummaryDetail::Detailed);
+            }
+            other => panic!("unexpected parse: {:?}", other),
+        }
+        assert!(matches!(
+            Command::parse("/summarize README.md"),
+            Some(Command::Summarize { detail: SummaryDetail::Normal, .. })
+        ));
+        assert!(Command::parse("/summarize").is_none());
+        assert!(Command::parse("/summarize a.rs --detail huge").is_none());
+        assert!(Command::parse("/summarize --detail brief").is_none());
+        match Command::parse("/image  shots/login page.png ") {
+            Some(Command::Image(path)) => assert_eq!(path, "shots/login page.png"),
+            other => panic!("unexpected parse: {:?}", other),
+        }
+        assert!(matches!(Command::parse("/image clear"), Some(Command::ImageClear)));
+        assert!(matches!(Command::parse("/export"), Some(Command::Export { path: None })));
+        match Command::parse("/export notes/session.md") {
+            Some(Command::Export { path }) => assert_eq!(path.as_deref(), Some("notes/session.md")),
+            other => panic!("unexpected parse: {:?}", other),
+        }
+        assert!(Command::parse("/image").is_none());
+        assert!(matches!(Command::parse("/unpin 2"), Some(Command::Unpin(2))));
+        assert!(Command::parse("/unpin 0").is_none());
+        assert!(Command::parse("/unpin").is_none());
+        assert!(matches!(Command::parse("/tools"), Some(Command::Tools)));
+    }
+
+    #[test]
+    fn test_parse_tools_override() {
+        assert!(matches!(Command::parse("/tools on"), Some(Command::ToolsToggle(true))));
+        assert!(matches!(Command::parse("/tools off"), Some(Command::ToolsToggle(false))));
+        assert!(matches!(Command::parse("/dryrun"), Some(Command::DryRunStatus)));
+        assert!(matches!(Command::parse("/dryrun on"), Some(Command::DryRun(true))));
+        assert!(matches!(Command::parse("/dryrun off"), Some(Command::DryRun(false))));
+        assert!(matches!(
+            Command::parse("/benchmark-tools"),
+            Some(Command::BenchmarkTools { network: false })
+        ));
+        assert!(matches!(
+            Command::parse("/benchmark-tools --network"),
+            Some(Command::BenchmarkTools { network: true })
+        ));
+        assert!(matches!(Command::parse("/replay-tool"), Some(Command::ReplayTool(1))));
+        assert!(matches!(Command::parse("/replay-tool 3"), Some(Command::ReplayTool(3))));
+        assert!(matches!(Command::parse("/replay-tool list"), Some(Command::ReplayToolList)));
+        assert!(Command::parse("/replay-tool 0").is_none());
+        match Command::parse("/watch src/**/*.rs :: run the tests") {
+            Some(Command::Watch(spec)) => {
+                assert_eq!(spec.pattern, "src/**/*.rs");
+                assert_eq!(spec.prompt, "run the tests");
+            }
+            other => panic!("unexpected: {:?}", other),
+        }
+        assert!(matches!(Command::parse("/watch"), Some(Command::WatchStatus)));
+        assert!(matches!(Command::parse("/copy"), Some(Command::CopyBlock(1))));
+        assert!(matches!(Command::parse("/copy 3"), Some(Command::CopyBlock(3))));
+        assert!(Command::parse("/copy x").is_none());
+        assert!(matches!(Command::parse("/format hints"), Some(Command::FormatHints)));
+        assert!(matches!(Command::parse("/watch stop"), Some(Command::WatchStop)));
+        assert!(Command::parse("/watch src/**/*.rs").is_none());
+
+        match Command::parse("/no-tools  What is a monad? ") {
+            Some(Command::ToolsOverride { query, enabled }) => {
+                assert_eq!(query, "What is a monad?");
+                assert!(!enabled);
+            }
+            other => panic!("unexpected parse: {:?}", other),
+        }
+        assert!(matches!(
+            Command::parse("/with-tools list files"),
+            Some(Command::ToolsOverride { enabled: true, .. })
+        ));
+        assert!(Command::parse("/no-tools").is_none());
+    }
+
+    #[test]
+    fn test_parse_routing_commands() {
+        match Command::parse("/forward  Explain lifetimes ") {
+            Some(Command::Forward { query }) => assert_eq!(query, "Explain lifetimes"),
+            other => panic!("unexpected parse: {:?}", other),
+        }
+        match Command::parse("/local What is 2+2?") {
+            Some(Command::Local { query }) => assert_eq!(query, "What is 2+2?"),
+            other => panic!("unexpected parse: {:?}", other),
+        }
+        assert!(Command::parse("/forward").is_none());
+        assert!(matches!(Command::parse("/mode forward"), Some(Command::ForwardMode(true))));
+        assert!(matches!(Command::parse("/mode auto"), Some(Command::ForwardMode(false))));
+        assert!(Command::parse("/mode local").is_none());
+    }
+
+    #[test]
+    fn test_parse_tts() {
+        assert!(matches!(Command::parse("/tts on"), Some(Command::TtsToggle(true))));
+        assert!(matches!(Command::parse("/voice off"), Some(Command::TtsToggle(false))));
+        assert!(Command::parse("/tts loud").is_none());
+    }
+
+    #[test]
+    fn test_parse_provider_latency() {
+        assert!(matches!(Command::parse("/provider-latency"), Some(Command::ProviderLatency)));
+    }
+
+    #[test]
+    fn test_parse_profile() {
+        assert!(matches!(Command::parse("/profile"), Some(Command::ProfileToggle(None))));
+        assert!(matches!(Command::parse("/profile on"), Some(Command::ProfileToggle(Some(true)))));
+        assert!(matches!(Command::parse("/profile off"), Some(Command::ProfileToggle(Some(false)))));
+        assert!(Command::parse("/profile fast").is_none());
+    }
+
+    #[test]
+    fn test_parse_think() {
+        assert!(matches!(Command::parse("/think"), Some(Command::ThinkStatus)));
+        assert!(matches!(Command::parse("/think default"), Some(Command::Think(None))));
+        assert!(matches!(
+            Command::parse("/think high"),
+            Some(Command::Think(Some(ThinkingLevel::High)))
+        ));
+        assert!(matches!(
+            Command::parse("/think 6000"),
+            Some(Command::Think(Some(ThinkingLevel::Budget(6000))))
+        ));
+        assert!(Command::parse("/think hard").is_none());
+        assert!(matches!(Command::parse("/thinking"), Some(Command::ThinkingToggle)));
+    }
+
+    #[test]
+    fn test_parse_temp() {
+        assert!(matches!(Command::parse("/temp"), Some(Command::TempStatus)));
+        assert!(matches!(Command::parse("/temp off"), Some(Command::Temp(None))));
+        assert!(matches!(Command::parse("/temp 0.3"), Some(Command::Temp(Some(t))) if t == 0.3));
+        assert!(Command::parse("/temp warm").is_none());
+    }
+
+    #[test]
+    fn test_parse_done() {
+        assert!(matches!(Command::parse("/done"), Some(Command::Done)));
+        assert!(matches!(Command::parse("  /done "), Some(Command::Done)));
+    }
+
+    #[test]
+    fn test_parse_training_control() {
+        assert!(matches!(
+            Command::parse("/training pause"),
+            Some(Command::TrainingPause)
+        ));
+        assert!(matches!(
+            Command::parse("/training resume"),
+            Some(Command::TrainingResume)
+        ));
+        assert!(matches!(
+            Command::parse("/training cancel"),
+            Some(Command::TrainingCancel)
+        ));
+        assert!(matches!(
+            Command::parse("/cancel-training"),
+            Some(Command::TrainingCancel)
+        ));
+        assert!(matches!(Command::parse("/training stop"), None));
     }
 
     #[test]
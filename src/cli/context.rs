@@ -0,0 +1,179 @@
+// Teacher context inspection (/context)
+//
+// Shows the messages the next teacher call would send, after context
+// optimization: role, a token estimate and the start of each message, which
+// older turns were trimmed, and where the provider puts cache breakpoints.
+// Built from a dry run of the same request path as a real call, so it shows
+// what's sent rather than what's in the conversation. API keys and tokens in
+// the content are masked; everything else is shown as-is.
+
+use crate::claude::{ContentBlock, Message};
+use crate::daemon::redact_secrets;
+use crate::providers::{CacheBreakpoint, ContextPreview, MessageFate};
+
+/// Characters of each message shown in the listing
+const PREVIEW_CHARS: usize = 80;
+
+/// Rough token estimate (1 token ≈ 4 characters, as in `ConversationHistory`)
+fn estimate_tokens(chars: usize) -> usize {
+    chars / 4
+}
+
+/// A message's content as one line of text, tool blocks included
+fn message_text(message: &Message) -> String {
+    message
+        .content
+        .iter()
+        .map(|block| match block {
+            ContentBlock::Text { text } => text.clone(),
+            ContentBlock::Image { .. } => "[image]".to_string(),
+            ContentBlock::ToolUse { name, input, .. } => format!("[tool_use {} {}]", name, input),
+            ContentBlock::ToolResult { content, .. } => format!("[tool_result {}]", content),
+            ContentBlock::Thinking { thinking, .. } => format!("[thinking {}]", thinking),
+            ContentBlock::RedactedThinking { .. } => "[redacted_thinking]".to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// One listing line: position, role, token estimate and the start of the text
+fn format_message(index: usize, message: &Message) -> String {
+    let text = message_text(message);
+    let preview = redact_secrets(&crate::text::ellipsize(
+        &text.split_whitespace().collect::<Vec<_>>().join(" "),
+        PREVIEW_CHARS,
+    ));
+    format!(
+        "  #{:<3} {:<9} ~{:>5} tok  {}",
+        index + 1,
+        message.role,
+        estimate_tokens(text.chars().count()),
+        preview
+    )
+}
+
+/// Render a context preview for display
+///
+/// Messages are numbered by their position in the conversation, so gaps
+/// show where older turns were trimmed.
+pub fn format_context_preview(preview: &ContextPreview) -> String {
+    let request = &preview.request;
+    let message_tokens: usize = request
+        .messages
+        .iter()
+        .map(|m| estimate_tokens(message_text(m).chars().count()))
+        .sum();
+    let tool_tokens = request.tools.as_ref().map_or(0, |tools| {
+        estimate_tokens(serde_json::to_string(tools).map_or(0, |json| json.chars().count()))
+    });
+
+    let mut lines = vec![format!(
+        "Context for the next {} call: {} messages, ~{} tokens{}",
+        preview.provider,
+        request.messages.len(),
+        message_tokens + tool_tokens,
+        if preview.optimized {
+            ""
+        } else {
+            " (sent without trimming)"
+        }
+    )];
+
+    if let Some(tools) = &request.tools {
+        lines.push(format!(
+            "  tools: {} definitions, ~{} tokens{}",
+            tools.len(),
+            tool_tokens,
+            if preview.cache_breakpoints.contains(&CacheBreakpoint::Tools) {
+                "  ◆ cache breakpoint"
+            } else {
+                ""
+            }
+        ));
+    }
+
+    // The system prompt breakpoint goes after the last system message
+    let system_breakpoint = request
+        .messages
+        .iter()
+        .rposition(|m| m.role == "system")
+        .filter(|_| preview.cache_breakpoints.contains(&CacheBreakpoint::System));
+
+    for (position, (message, &origin)) in request.messages.iter().zip(&preview.origins).enumerate() {
+        let mut line = format_message(origin, message);
+        if preview.breakpoint_after(position) || system_breakpoint == Some(position) {
+            line.push_str("  ◆ cache breakpoint");
+        }
+        if preview
+            .trimmed
+            .iter()
+            .any(|t| t.index == origin && t.fate == MessageFate::ToolResultsDropped)
+        {
+            line.push_str("  (old tool results removed)");
+        }
+        lines.push(line);
+    }
+
+    let dropped: Vec<_> = preview
+        .trimmed
+        .iter()
+        .filter(|t| t.fate == MessageFate::Dropped)
+        .collect();
+    if !dropped.is_empty() {
+        let tokens: usize = dropped
+            .iter()
+            .map(|t| estimate_tokens(message_text(&t.message).chars().count()))
+            .sum();
+        lines.push(String::new());
+        lines.push(format!("Trimmed ({} messages, ~{} tokens not sent):", dropped.len(), tokens));
+        lines.extend(dropped.iter().map(|t| format_message(t.index, &t.message)));
+    }
+
+    lines.push(String::new());
+    lines.push("Your next message is added after these.".to_string());
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::{ProviderRequest, TrimmedMessage};
+
+    #[test]
+    fn test_format_context_preview() {
+        let system = Message {
+            role: "system".to_string(),
+            content: vec![ContentBlock::text("Be brief.")],
+        };
+        let old = Message::user("an old question that was trimmed");
+        let preview = ContextPreview {
+            provider: "claude".to_string(),
+            request: ProviderRequest::new(vec![
+                system,
+                Message::assistant("ok"),
+                Message::user("my key is sk-ant-REDACTED"),
+            ]),
+            origins: vec![0, 2, 3],
+            trimmed: vec![TrimmedMessage {
+                index: 1,
+                message: old,
+                fate: MessageFate::Dropped,
+            }],
+            cache_breakpoints: vec![CacheBreakpoint::System, CacheBreakpoint::Message(1)],
+            optimized: true,
+        };
+
+        let text = format_context_preview(&preview);
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(lines[0].contains("next claude call: 3 messages"));
+        assert!(lines[1].contains("#1") && lines[1].contains("system") && lines[1].contains("cache breakpoint"));
+        assert!(lines[2].contains("#3") && lines[2].contains("cache breakpoint"));
+        assert!(lines[3].contains("#4") && !lines[3].contains("cache breakpoint"));
+
+        // Secrets are masked, ordinary text isn't
+        assert!(text.contains("sk-ant-[REDACTED]"));
+        assert!(!text.contains("abcdefghijklmnop"));
+        assert!(text.contains("Trimmed (1 messages"));
+        assert!(text.contains("an old question that was trimmed"));
+    }
+}
@@ -5,11 +5,27 @@ use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
+use std::sync::Arc;
+
+use super::autosave::ConversationAutosave;
+
+/// Heading of the system message that carries pinned context
+pub const PINNED_CONTEXT_HEADER: &str = "Pinned context (always keep in mind):";
+
+/// Context the user pinned with /pin, exempt from trimming
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Pin {
+    /// Role of the pinned message, or "note" for `/pin <text>`
+    pub source: String,
+    pub text: String,
+}
 
 /// Manages conversation history for multi-turn interactions with context window management
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConversationHistory {
     messages: Vec<Message>,
+    #[serde(default)]
+    pins: Vec<Pin>,
     #[serde(skip)]
     max_messages: usize,
     #[serde(skip)]
@@ -18,6 +34,8 @@ pub struct ConversationHistory {
     compaction_threshold_percent: f32, // Trigger compaction at this % of max tokens (e.g., 0.8 = 80%)
     #[serde(skip)]
     auto_compact_enabled: bool, // Whether auto-compaction is enabled
+    #[serde(skip)]
+    autosave: Option<Arc<ConversationAutosave>>, // Persist after each completed turn
 }
 
 impl ConversationHistory {
@@ -25,10 +43,12 @@ impl ConversationHistory {
     pub fn new() -> Self {
         Self {
             messages: Vec::new(),
+            pins: Vec::new(),
             max_messages: 20, // Keep last 20 messages (10 user + 10 assistant turns)
             max_tokens_estimate: 32_000, // ~8K tokens * 4 chars/token
             compaction_threshold_percent: 0.8, // Compact at 80% of max tokens
             auto_compact_enabled: true, // Auto-compaction enabled by default
+            autosave: None,
         }
     }
 
@@ -36,15 +56,18 @@ impl ConversationHistory {
     pub fn with_limits(max_messages: usize, max_tokens_estimate: usize) -> Self {
         Self {
             messages: Vec::new(),
+            pins: Vec::new(),
             max_messages,
             max_tokens_estimate,
             compaction_threshold_percent: 0.8,
             auto_compact_enabled: true,
+            autosave: None,
         }
     }
 
     /// Add a user message to the conversation
     pub fn add_user_message(&mut self, content: String) {
+        self.drop_thinking();
         self.messages.push(Message {
             role: "user".to_string(),
             content: vec![ContentBlock::Text { text: content }],
@@ -53,26 +76,236 @@ impl ConversationHistory {
     }
 
     /// Add an assistant message to the conversation
+    ///
+    /// This completes a turn, so the conversation is autosaved if enabled.
     pub fn add_assistant_message(&mut self, content: String) {
         self.messages.push(Message {
             role: "assistant".to_string(),
             content: vec![ContentBlock::Text { text: content }],
         });
         self.trim_if_needed();
+
+        if let Some(autosave) = &self.autosave {
+            autosave.record(self);
+        }
+    }
+
+    /// Enable (or disable) autosave after each completed turn
+    pub fn set_autosave(&mut self, autosave: Option<Arc<ConversationAutosave>>) {
+        self.autosave = autosave;
     }
 
     /// Add a complete message to the conversation
+    ///
+    /// Thinking blocks are kept until the next prompt, so a tool loop sends
+    /// them back with its tool_use turns (the API needs them to keep
+    /// thinking on).
     pub fn add_message(&mut self, message: Message) {
+        if is_prompt(&message) {
+            self.drop_thinking();
+        }
         self.messages.push(message);
         self.trim_if_needed();
     }
 
+    /// Answer the tool calls of an unfinished tool turn with errors
+    ///
+    /// If the last message is an assistant tool_use turn (its tools were
+    /// cancelled before they returned), each call gets an error result
+    /// saying `reason`, so the next request doesn't carry tool_use blocks
+    /// without results. Returns how many calls were closed.
+    pub fn close_tool_calls(&mut self, reason: &str) -> usize {
+        let Some(last) = self.messages.last().filter(|message| message.role == "assistant") else {
+            return 0;
+        };
+        let results: Vec<ContentBlock> = last
+            .content
+            .iter()
+            .filter_map(|block| match block {
+                ContentBlock::ToolUse { id, .. } => Some(ContentBlock::ToolResult {
+                    tool_use_id: id.clone(),
+                    content: reason.to_string(),
+                    is_error: Some(true),
+                }),
+                _ => None,
+            })
+            .collect();
+        let closed = results.len();
+        if closed > 0 {
+            self.add_message(Message::with_content("user", results));
+        }
+        closed
+    }
+
+    /// Drop thinking blocks from earlier turns
+    ///
+    /// Once a new prompt starts, finished turns don't need them: they're
+    /// shown while streaming but aren't part of the context.
+    fn drop_thinking(&mut self) {
+        for message in &mut self.messages {
+            message.content.retain(|block| !block.is_thinking());
+        }
+    }
+
     /// Get all messages for API request
+    ///
+    /// Pins come first, as a system message, so context truncation (which
+    /// keeps system messages) never drops them.
     pub fn get_messages(&self) -> Vec<Message> {
-        self.messages.clone()
+        let mut messages = Vec::with_capacity(self.messages.len() + 1);
+        messages.extend(self.pinned_message());
+        messages.extend(self.messages.iter().cloned());
+        messages
+    }
+
+    /// Index of the last prompt the user typed (tool-result turns don't count)
+    fn last_prompt_index(&self) -> Option<usize> {
+        self.messages.iter().rposition(is_prompt)
+    }
+
+    /// Messages up to and including the last user prompt
+    ///
+    /// This is the context that produced the latest response (None if the
+    /// user hasn't said anything yet).
+    pub fn last_prompt_context(&self) -> Option<Vec<Message>> {
+        self.last_prompt_index().map(|index| {
+            self.pinned_message()
+                .into_iter()
+                .chain(self.messages[..=index].iter().cloned())
+                .collect()
+        })
+    }
+
+    /// Replace the response to the last prompt with `content`
+    ///
+    /// Everything after the last user prompt (assistant turns, tool calls
+    /// and their results) is dropped before `content` is added.
+    pub fn replace_last_response(&mut self, content: String) {
+        if let Some(index) = self.last_prompt_index() {
+            self.messages.truncate(index + 1);
+        }
+        self.add_assistant_message(content);
+    }
+
+    /// Drop the last exchange if it was started by `prompt`
+    ///
+    /// Removes the prompt and everything after it, so the prompt can be sent
+    /// again without a duplicate turn. Returns whether anything was removed.
+    pub fn remove_last_exchange(&mut self, prompt: &str) -> bool {
+        match self.last_prompt_index() {
+            Some(index) if self.messages[index].text() == prompt => {
+                self.messages.truncate(index);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Remove the last exchange (/undo): the last user prompt and everything
+    /// after it - assistant turns, tool calls and their results
+    ///
+    /// The conversation then ends where the previous exchange did, so roles
+    /// still alternate. Returns the removed messages, or None if there is no
+    /// prompt to remove.
+    pub fn pop_last_exchange(&mut self) -> Option<Vec<Message>> {
+        let index = self.last_prompt_index()?;
+        let removed = self.messages.split_off(index);
+
+        if let Some(autosave) = &self.autosave {
+            autosave.record(self);
+        }
+        Some(removed)
+    }
+
+    /// Text of the last prompt and the final response to it
+    ///
+    /// None if there is no prompt yet or it hasn't been answered.
+    pub fn last_exchange(&self) -> Option<(String, String)> {
+        let index = self.last_prompt_index()?;
+        let response = self.messages[index + 1..]
+            .iter()
+            .rev()
+            .find(|message| message.role == "assistant" && !message.is_empty_text())?;
+        Some((self.messages[index].text(), response.text()))
+    }
+
+    /// Number of prompts the user typed (tool-result turns don't count)
+    pub fn exchange_count(&self) -> usize {
+        self.messages.iter().filter(|message| is_prompt(message)).count()
+    }
+
+    /// Pin the latest message so it is always sent, however long the
+    /// conversation gets
+    ///
+    /// Returns the pin number (1-based), or None if there is nothing to pin.
+    pub fn pin_last_message(&mut self) -> Option<usize> {
+        let message = self
+            .messages
+            .iter()
+            .rev()
+            .find(|message| !message.has_tool_results() && !message.is_empty_text())?;
+        let pin = Pin {
+            source: message.role.clone(),
+            text: message.text(),
+        };
+        Some(self.add_pin(pin))
+    }
+
+    /// Pin a note (`/pin <text>`); returns its pin number
+    pub fn pin_note(&mut self, text: String) -> usize {
+        self.add_pin(Pin {
+            source: "note".to_string(),
+            text,
+        })
+    }
+
+    fn add_pin(&mut self, pin: Pin) -> usize {
+        match self.pins.iter().position(|existing| existing.text == pin.text) {
+            Some(index) => index + 1,
+            None => {
+                self.pins.push(pin);
+                self.trim_if_needed();
+                self.pins.len()
+            }
+        }
+    }
+
+    /// Pinned context, in pin order
+    pub fn pins(&self) -> &[Pin] {
+        &self.pins
+    }
+
+    /// Remove pin `number` (1-based, as listed by /pins)
+    pub fn unpin(&mut self, number: usize) -> Option<Pin> {
+        if number == 0 || number > self.pins.len() {
+            return None;
+        }
+        Some(self.pins.remove(number - 1))
+    }
+
+    /// System message carrying the pins (None when nothing is pinned)
+    fn pinned_message(&self) -> Option<Message> {
+        if self.pins.is_empty() {
+            return None;
+        }
+        let mut text = PINNED_CONTEXT_HEADER.to_string();
+        for pin in &self.pins {
+            text.push_str("\n\n");
+            text.push_str(&pin.text);
+        }
+        Some(Message {
+            role: "system".to_string(),
+            content: vec![ContentBlock::Text { text }],
+        })
+    }
+
+    fn pinned_chars(&self) -> usize {
+        self.pinned_message().map_or(0, |message| message.text().len())
     }
 
     /// Clear conversation history (start fresh)
+    ///
+    /// Pins are kept; remove them with /unpin.
     pub fn clear(&mut self) {
         self.messages.clear();
     }
@@ -104,6 +337,9 @@ impl ConversationHistory {
     }
 
     /// Trim old messages if context exceeds limits
+    ///
+    /// Pins count toward the token budget but are never trimmed: unpinned
+    /// messages go first.
     fn trim_if_needed(&mut self) {
         // Trim by message count
         if self.messages.len() > self.max_messages {
@@ -112,15 +348,15 @@ impl ConversationHistory {
         }
 
         // Estimate token count (rough: 1 token ≈ 4 characters)
+        let budget = self.max_tokens_estimate.saturating_sub(self.pinned_chars());
         let total_chars: usize = self.messages.iter().map(|m| m.text().len()).sum();
 
-        if total_chars > self.max_tokens_estimate {
+        if total_chars > budget {
             // Remove oldest messages until under limit
             // BUT: Always keep at least 2 messages (1 user + 1 assistant minimum)
             // This prevents conversation from becoming empty during tool execution
             while self.messages.len() > 2
-                && self.messages.iter().map(|m| m.text().len()).sum::<usize>()
-                    > self.max_tokens_estimate
+                && self.messages.iter().map(|m| m.text().len()).sum::<usize>() > budget
             {
                 self.messages.remove(0);
             }
@@ -129,7 +365,8 @@ impl ConversationHistory {
 
     /// Get estimated token count (rough approximation)
     pub fn estimated_tokens(&self) -> usize {
-        let total_chars: usize = self.messages.iter().map(|m| m.text().len()).sum();
+        let total_chars: usize =
+            self.messages.iter().map(|m| m.text().len()).sum::<usize>() + self.pinned_chars();
         total_chars / 4 // Rough estimate: 1 token ≈ 4 characters
     }
 
@@ -176,6 +413,13 @@ impl ConversationHistory {
         self.compaction_threshold_percent = threshold.clamp(0.0, 1.0);
     }
 
+    /// Apply the `[compaction]` settings
+    pub fn with_compaction(mut self, config: &crate::config::CompactionConfig) -> Self {
+        self.set_auto_compact(config.enabled);
+        self.set_compaction_threshold(config.threshold);
+        self
+    }
+
     /// Save conversation to JSON file
     pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
         let json =
@@ -219,83 +463,144 @@ impl ConversationHistory {
     }
 }
 
+/// Whether a message is a prompt the user typed (not a tool-result turn)
+fn is_prompt(message: &Message) -> bool {
+    message.role == "user" && !message.has_tool_results() && !message.is_empty_text()
+}
+
+/// Index where the last `turns` exchanges start (0 if there are no more
+/// exchanges than that)
+///
+/// An exchange is a prompt and everything after it, so splitting here never
+/// separates a tool call from its result.
+pub fn recent_turns_start(messages: &[Message], turns: usize) -> usize {
+    let Some(skip) = turns.checked_sub(1) else {
+        return messages.len();
+    };
+    messages
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, message)| is_prompt(message))
+        .nth(skip)
+        .map_or(0, |(index, _)| index)
+}
+
+/// Numbered list of pins for /pins
+pub fn format_pins(pins: &[Pin]) -> String {
+    if pins.is_empty() {
+        return "No pins. Use /pin to pin the last message, or /pin <text> to pin a note.".to_string();
+    }
+
+    let mut out = format!("Pinned context ({}):\n", pins.len());
+    for (index, pin) in pins.iter().enumerate() {
+        let preview = super::vary::preview(&pin.text);
+        out.push_str(&format!("  {}. [{}] {}\n", index + 1, pin.source, preview));
+    }
+    out.push_str("Remove one with /unpin <n>.");
+    out
+}
+
 impl Default for ConversationHistory {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Result of a compaction, for logging and the user notice
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompactionReport {
+    pub messages_before: usize,
+    pub messages_after: usize,
+    pub tokens_before: usize,
+    pub tokens_after: usize,
+}
+
+impl CompactionReport {
+    /// Estimated tokens freed
+    pub fn reclaimed_tokens(&self) -> usize {
+        self.tokens_before.saturating_sub(self.tokens_after)
+    }
+}
+
+impl ConversationHistory {
+    /// Replace `summarized` (the oldest messages) with a summary message
+    ///
+    /// Returns None, changing nothing, if the conversation no longer starts
+    /// with `summarized` (cleared or trimmed while the summary was being
+    /// generated). Pins are untouched.
+    pub fn apply_compaction(&mut self, summarized: &[Message], summary: &str) -> Option<CompactionReport> {
+        let unchanged = summarized.len() < self.messages.len()
+            && summarized
+                .iter()
+                .zip(&self.messages)
+                .all(|(old, current)| old.role == current.role && old.text() == current.text());
+        if summarized.is_empty() || !unchanged {
+            return None;
+        }
+
+        let messages_before = self.messages.len();
+        let tokens_before = self.estimated_tokens();
+
+        let summary_message = Message {
+            role: "user".to_string(),
+            content: vec![ContentBlock::Text {
+                text: format!("[Summary of previous conversation]\n\n{}", summary),
+            }],
+        };
+        self.messages.splice(..summarized.len(), std::iter::once(summary_message));
+
+        Some(CompactionReport {
+            messages_before,
+            messages_after: self.messages.len(),
+            tokens_before,
+            tokens_after: self.estimated_tokens(),
+        })
+    }
+}
+
 /// Conversation compactor that summarizes older messages to reduce token usage
 ///
 /// When conversations grow too large, this compactor:
-/// 1. Keeps the most recent messages intact (for context continuity)
+/// 1. Keeps the most recent turns intact (for context continuity)
 /// 2. Summarizes older messages into a single summary message
-/// 3. Uses the teacher API to generate high-quality summaries
-///
-/// # Usage
-///
-/// ```rust,ignore
-/// use crate::cli::conversation::{ConversationHistory, ConversationCompactor};
-/// use crate::providers::fallback_chain::FallbackChain;
-///
-/// let mut history = ConversationHistory::new();
-/// // ... add many messages ...
+/// 3. Uses the teacher to generate the summary
 ///
-/// // Check if compaction is needed
-/// let compactor = ConversationCompactor::new(&fallback_chain);
-/// if compactor.should_compact(&history) {
-///     // Compact conversation in background
-///     compactor.compact(&mut history).await?;
-/// }
-/// ```
+/// Pins aren't part of the summarized messages, so they survive unchanged.
 ///
-/// # Integration Points
+/// # Usage
 ///
-/// The compactor should be called in the REPL event loop after each query completion:
-/// - File: `src/cli/repl_event/event_loop.rs`
-/// - Location: After successful query completion, before next query
-/// - Async: Yes, runs in background (non-blocking)
+/// Summarize from a snapshot so the conversation isn't locked during the
+/// teacher call, then apply the result:
 ///
-/// Example integration:
 /// ```rust,ignore
-/// // In event_loop.rs, after query completes:
-/// if self.compactor.should_compact(&self.conversation) {
-///     tokio::spawn(async move {
-///         if let Err(e) = compactor.compact(&mut conversation).await {
-///             tracing::warn!("Failed to compact conversation: {}", e);
-///         }
-///     });
+/// let compactor = ConversationCompactor::new(claude_gen.as_ref());
+/// let snapshot = conversation.read().await.snapshot();
+/// if let Some((summarized, summary)) = compactor.summarize(&snapshot).await? {
+///     conversation.write().await.apply_compaction(summarized, &summary);
 /// }
 /// ```
 pub struct ConversationCompactor<'a> {
-    /// Fallback chain for API calls
-    fallback_chain: &'a crate::providers::fallback_chain::FallbackChain,
-    /// Number of recent messages to keep intact (default: 4)
-    keep_recent_count: usize,
-    /// Compaction threshold as percentage of max tokens (default: 0.8 = 80%)
-    threshold_percent: f32,
+    /// Generator used to write the summary (the teacher)
+    generator: &'a dyn crate::generators::Generator,
+    /// Number of recent turns to keep intact (default: 2)
+    keep_turns: usize,
 }
 
 impl<'a> ConversationCompactor<'a> {
     /// Create a new conversation compactor
-    pub fn new(fallback_chain: &'a crate::providers::fallback_chain::FallbackChain) -> Self {
+    pub fn new(generator: &'a dyn crate::generators::Generator) -> Self {
         Self {
-            fallback_chain,
-            keep_recent_count: 4, // Keep last 4 messages (2 turns)
-            threshold_percent: 0.8,
+            generator,
+            keep_turns: 2,
         }
     }
 
-    /// Create with custom settings
-    pub fn with_settings(
-        fallback_chain: &'a crate::providers::fallback_chain::FallbackChain,
-        keep_recent_count: usize,
-        threshold_percent: f32,
-    ) -> Self {
+    /// Create with a custom number of recent turns kept verbatim
+    pub fn with_keep_turns(generator: &'a dyn crate::generators::Generator, keep_turns: usize) -> Self {
         Self {
-            fallback_chain,
-            keep_recent_count,
-            threshold_percent: threshold_percent.clamp(0.0, 1.0),
+            generator,
+            keep_turns: keep_turns.max(1),
         }
     }
 
@@ -304,39 +609,36 @@ impl<'a> ConversationCompactor<'a> {
         history.should_compact()
     }
 
-    /// Compact conversation history by summarizing older messages
+    /// Summarize all but the most recent turns
     ///
-    /// Returns the compacted conversation history or an error if compaction fails
-    pub async fn compact(&self, history: &mut ConversationHistory) -> anyhow::Result<()> {
-        use crate::claude::types::ContentBlock;
-        use crate::providers::ProviderRequest;
-
-        // Check if compaction is needed
-        if !self.should_compact(history) {
-            tracing::debug!("Conversation does not need compaction");
-            return Ok(());
-        }
-
-        let messages = history.get_messages();
+    /// Returns the summarized messages (a prefix of `messages`) and the
+    /// summary, or None if there are too few turns to compact.
+    pub async fn summarize<'m>(&self, messages: &'m [Message]) -> anyhow::Result<Option<(&'m [Message], String)>> {
+        self.summarize_turns(messages, self.keep_turns).await
+    }
 
-        // If we have fewer messages than keep_recent_count, nothing to compact
-        if messages.len() <= self.keep_recent_count {
-            tracing::debug!("Not enough messages to compact (need at least {})", self.keep_recent_count + 1);
-            return Ok(());
+    /// Summarize all but the last `turns` exchanges (/compact)
+    ///
+    /// Returns the summarized messages (a prefix of `messages`) and the
+    /// summary, or None if there are no older exchanges to summarize.
+    pub async fn summarize_turns<'m>(
+        &self,
+        messages: &'m [Message],
+        turns: usize,
+    ) -> anyhow::Result<Option<(&'m [Message], String)>> {
+        let split_point = recent_turns_start(messages, turns);
+        if split_point == 0 {
+            tracing::debug!("Not enough turns to compact (need more than {})", turns);
+            return Ok(None);
         }
 
-        // Split messages into "to summarize" and "to keep"
-        let split_point = messages.len() - self.keep_recent_count;
         let to_summarize = &messages[..split_point];
-        let to_keep = &messages[split_point..];
-
-        tracing::info!(
-            "Compacting conversation: {} messages total, summarizing {}, keeping {}",
-            messages.len(),
-            to_summarize.len(),
-            to_keep.len()
-        );
+        let summary_text = self.summarize_messages(to_summarize).await?;
+        Ok(Some((to_summarize, summary_text)))
+    }
 
+    /// Ask the generator for a summary of `to_summarize`
+    async fn summarize_messages(&self, to_summarize: &[Message]) -> anyhow::Result<String> {
         // Build summarization prompt
         let mut conversation_text = String::new();
         for msg in to_summarize {
@@ -351,73 +653,62 @@ impl<'a> ConversationCompactor<'a> {
             conversation_text
         );
 
-        // Send summarization request to teacher API
-        let request = ProviderRequest {
-            model: String::new(), // Use provider default
-            messages: vec![Message {
-                role: "user".to_string(),
-                content: vec![ContentBlock::Text {
-                    text: summarization_prompt,
-                }],
-            }],
-            max_tokens: 1024,
-            tools: None,
-            temperature: None,
-            stream: false,
-        };
-
         let response = self
-            .fallback_chain
-            .send_message_with_fallback(&request)
+            .generator
+            .generate(
+                vec![Message {
+                    role: "user".to_string(),
+                    content: vec![ContentBlock::Text {
+                        text: summarization_prompt,
+                    }],
+                }],
+                None,
+            )
             .await?;
 
-        // Extract summary text from response
-        let summary_text = response
-            .content
-            .iter()
-            .filter_map(|block| match block {
-                ContentBlock::Text { text } => Some(text.as_str()),
-                _ => None,
-            })
-            .collect::<Vec<_>>()
-            .join("\n");
-
+        let summary_text = response.text.trim().to_string();
         if summary_text.is_empty() {
             anyhow::bail!("Failed to generate conversation summary (empty response)");
         }
 
         tracing::debug!("Generated summary: {} chars", summary_text.len());
+        Ok(summary_text)
+    }
 
-        // Build compacted conversation:
-        // 1. A single summary message (user role, for context)
-        // 2. All recent messages (to_keep)
-        let mut compacted_messages = Vec::new();
-
-        // Add summary as a user message
-        compacted_messages.push(Message {
-            role: "user".to_string(),
-            content: vec![ContentBlock::Text {
-                text: format!("[Summary of previous conversation]\n\n{}", summary_text),
-            }],
-        });
-
-        // Add recent messages
-        compacted_messages.extend(to_keep.iter().cloned());
-
-        // Replace conversation history with compacted version
-        history.restore_snapshot(compacted_messages);
-
-        tracing::info!(
-            "Conversation compacted: {} → {} messages (saved ~{} tokens)",
-            messages.len(),
-            history.message_count(),
-            to_summarize.iter().map(|m| m.text().len() / 4).sum::<usize>() - summary_text.len() / 4
-        );
+    /// Compact conversation history by summarizing older messages
+    ///
+    /// Returns what was reclaimed, or None if nothing needed compacting.
+    pub async fn compact(&self, history: &mut ConversationHistory) -> anyhow::Result<Option<CompactionReport>> {
+        // Check if compaction is needed
+        if !self.should_compact(history) {
+            tracing::debug!("Conversation does not need compaction");
+            return Ok(None);
+        }
 
-        Ok(())
+        let messages = history.snapshot();
+        let Some((summarized, summary)) = self.summarize(&messages).await? else {
+            return Ok(None);
+        };
+        let report = history.apply_compaction(summarized, &summary);
+        if let Some(report) = report {
+            log_compaction(&report);
+        }
+        Ok(report)
     }
 }
 
+/// Log a finished compaction
+pub fn log_compaction(report: &CompactionReport) {
+    tracing::info!(
+        "Conversation compacted: {} → {} messages, ~{} → ~{} tokens (reclaimed ~{})",
+        report.messages_before,
+        report.messages_after,
+        report.tokens_before,
+        report.tokens_after,
+        report.reclaimed_tokens()
+    );
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -443,6 +734,40 @@ mod tests {
         assert_eq!(conv.turn_count(), 1); // Now we have 1 complete turn
     }
 
+    #[test]
+    fn test_thinking_dropped_at_next_prompt() {
+        let mut conv = ConversationHistory::new();
+        conv.add_user_message("Read a.rs".to_string());
+        conv.add_message(Message {
+            role: "assistant".to_string(),
+            content: vec![
+                ContentBlock::Thinking {
+                    thinking: "Reading the file first".to_string(),
+                    signature: "sig".to_string(),
+                },
+                ContentBlock::ToolUse {
+                    id: "toolu_1".to_string(),
+                    name: "read".to_string(),
+                    input: serde_json::json!({ "file_path": "a.rs" }),
+                },
+            ],
+        });
+
+        conv.add_message(Message::with_content(
+            "user",
+            vec![ContentBlock::tool_result("toolu_1".to_string(), "fn main() {}".to_string(), None)],
+        ));
+
+        // The tool loop still sends the thinking back
+        assert_eq!(conv.get_messages()[1].content.len(), 2);
+
+        // The next prompt drops it
+        conv.add_user_message("Thanks".to_string());
+        let messages = conv.get_messages();
+        assert_eq!(messages[1].content.len(), 1);
+        assert!(messages[1].content[0].is_tool_use());
+    }
+
     #[test]
     fn test_get_messages() {
         let mut conv = ConversationHistory::new();
@@ -458,6 +783,196 @@ mod tests {
         assert_eq!(messages[1].text_content(), "4");
     }
 
+    #[test]
+    fn test_replace_last_response() {
+        let mut conv = ConversationHistory::new();
+        conv.add_user_message("Name a color".to_string());
+        conv.add_assistant_message("Red".to_string());
+        conv.add_user_message("Name a fruit".to_string());
+        conv.add_message(Message {
+            role: "assistant".to_string(),
+            content: vec![ContentBlock::Text {
+                text: "Let me check".to_string(),
+            }],
+        });
+        conv.add_message(Message {
+            role: "user".to_string(),
+            content: vec![ContentBlock::tool_result("t1".to_string(), "apple".to_string(), None)],
+        });
+        conv.add_assistant_message("Apple".to_string());
+
+        let context = conv.last_prompt_context().unwrap();
+        assert_eq!(context.len(), 3);
+        assert_eq!(context[2].text(), "Name a fruit");
+
+        conv.replace_last_response("Banana".to_string());
+        let messages = conv.get_messages();
+        assert_eq!(messages.len(), 4);
+        assert_eq!(messages[3].text(), "Banana");
+
+        assert!(ConversationHistory::new().last_prompt_context().is_none());
+    }
+
+    #[test]
+    fn test_remove_last_exchange() {
+        let mut conv = ConversationHistory::new();
+        conv.add_user_message("Name a color".to_string());
+        conv.add_assistant_message("Red".to_string());
+        conv.add_user_message("Name a fruit".to_string());
+        conv.add_assistant_message("Apple".to_string());
+
+        assert!(!conv.remove_last_exchange("Name a color"));
+        assert_eq!(conv.get_messages().len(), 4);
+
+        assert!(conv.remove_last_exchange("Name a fruit"));
+        let messages = conv.get_messages();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[1].text(), "Red");
+    }
+
+    #[test]
+    fn test_pop_last_exchange() {
+        let mut conv = ConversationHistory::new();
+        assert!(conv.pop_last_exchange().is_none());
+
+        conv.add_user_message("Name a color".to_string());
+        conv.add_assistant_message("Red".to_string());
+        conv.add_user_message("Read a.rs and b.rs".to_string());
+        for id in ["t1", "t2"] {
+            conv.add_message(Message {
+                role: "assistant".to_string(),
+                content: vec![ContentBlock::ToolUse {
+                    id: id.to_string(),
+                    name: "read".to_string(),
+                    input: serde_json::json!({ "file_path": "a.rs" }),
+                }],
+            });
+            conv.add_message(Message {
+                role: "user".to_string(),
+                content: vec![ContentBlock::tool_result(id.to_string(), "fn main() {}".to_string(), None)],
+            });
+        }
+        conv.add_assistant_message("Both files are empty".to_string());
+        assert_eq!(conv.exchange_count(), 2);
+
+        // The tool turns go with the prompt that started them
+        let removed = conv.pop_last_exchange().unwrap();
+        assert_eq!(removed.len(), 6);
+        assert_eq!(removed[0].text(), "Read a.rs and b.rs");
+        let messages = conv.get_messages();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[1].role, "assistant");
+        assert_eq!(conv.exchange_count(), 1);
+        assert_eq!(
+            conv.last_exchange(),
+            Some(("Name a color".to_string(), "Red".to_string()))
+        );
+
+        // An unanswered prompt is removed on its own
+        conv.add_user_message("Name a fruit".to_string());
+        assert!(conv.last_exchange().is_none());
+        assert_eq!(conv.pop_last_exchange().unwrap().len(), 1);
+        assert_eq!(conv.message_count(), 2);
+
+        assert_eq!(conv.pop_last_exchange().unwrap().len(), 2);
+        assert!(conv.is_empty());
+        assert!(conv.pop_last_exchange().is_none());
+    }
+
+    #[test]
+    fn test_pins_survive_trimming() {
+        let mut conv = ConversationHistory::with_limits(2, 100_000);
+        conv.add_user_message("Always answer in French".to_string());
+        assert_eq!(conv.pin_last_message(), Some(1));
+        assert_eq!(conv.pin_last_message(), Some(1)); // Already pinned
+        assert_eq!(conv.pin_note("Project uses Rust 2021".to_string()), 2);
+
+        for i in 0..3 {
+            conv.add_assistant_message(format!("Réponse {}", i));
+            conv.add_user_message(format!("Question {}", i));
+        }
+        assert_eq!(conv.message_count(), 2);
+
+        let messages = conv.get_messages();
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[0].role, "system");
+        assert!(messages[0].text().contains("Always answer in French"));
+        assert!(messages[0].text().contains("Project uses Rust 2021"));
+
+        // Pins count toward the token budget
+        let pinned_tokens = messages[0].text().len() / 4;
+        assert!(conv.estimated_tokens() >= pinned_tokens);
+
+        // Pins persist with the session
+        let json = serde_json::to_string(&conv).unwrap();
+        let restored: ConversationHistory = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.pins(), conv.pins());
+
+        assert_eq!(conv.unpin(1).unwrap().text, "Always answer in French");
+        assert!(conv.unpin(2).is_none());
+        assert_eq!(conv.pins().len(), 1);
+    }
+
+    #[test]
+    fn test_apply_compaction() {
+        let mut conv = ConversationHistory::new();
+        conv.pin_note("Use metric units".to_string());
+        for i in 0..4 {
+            conv.add_user_message(format!("Question {} {}", i, "detail ".repeat(50)));
+            conv.add_assistant_message(format!("Answer {} {}", i, "detail ".repeat(50)));
+        }
+
+        let snapshot = conv.snapshot();
+        let report = conv.apply_compaction(&snapshot[..6], "Talked about units").unwrap();
+        assert_eq!(report.messages_before, 8);
+        assert_eq!(report.messages_after, 3);
+        assert!(report.reclaimed_tokens() > 0);
+
+        let messages = conv.get_messages();
+        assert_eq!(messages[0].role, "system"); // Pin survives
+        assert!(messages[1].text().contains("Talked about units"));
+        assert!(messages[2].text().starts_with("Question 3"));
+
+        // Stale snapshot (conversation changed meanwhile): nothing applied
+        assert!(conv.apply_compaction(&snapshot[..6], "again").is_none());
+        assert_eq!(conv.message_count(), 3);
+    }
+
+    #[test]
+    fn test_recent_turns_start() {
+        let mut conv = ConversationHistory::new();
+        conv.add_user_message("First".to_string());
+        conv.add_assistant_message("One".to_string());
+        conv.add_user_message("Second".to_string());
+        conv.add_message(Message {
+            role: "assistant".to_string(),
+            content: vec![ContentBlock::ToolUse {
+                id: "toolu_1".to_string(),
+                name: "read".to_string(),
+                input: serde_json::json!({ "file_path": "a.rs" }),
+            }],
+        });
+        conv.add_message(Message {
+            role: "user".to_string(),
+            content: vec![ContentBlock::ToolResult {
+                tool_use_id: "toolu_1".to_string(),
+                content: "fn main() {}".to_string(),
+                is_error: None,
+            }],
+        });
+        conv.add_assistant_message("Two".to_string());
+        conv.add_user_message("Third".to_string());
+        conv.add_assistant_message("Three".to_string());
+
+        let messages = conv.snapshot();
+        assert_eq!(recent_turns_start(&messages, 1), 6);
+        // The tool call stays with its prompt
+        assert_eq!(recent_turns_start(&messages, 2), 2);
+        assert_eq!(recent_turns_start(&messages, 3), 0);
+        assert_eq!(recent_turns_start(&messages, 5), 0);
+        assert_eq!(recent_turns_start(&messages, 0), messages.len());
+    }
+
     #[test]
     fn test_clear() {
         let mut conv = ConversationHistory::new();
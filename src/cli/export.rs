@@ -0,0 +1,193 @@
+// Conversation export to Markdown (/export)
+//
+// Writes the conversation as a readable transcript: a header with the export
+// time, then one section per turn. Text is copied as-is, so fenced code blocks
+// survive; tool calls are listed inline and tool results folded into
+// <details> sections so long outputs don't drown the conversation. Messages
+// don't carry their own timestamps, so the transcript is stamped with the
+// time it was exported.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::claude::{ContentBlock, Message};
+
+/// Default export location: ~/.shammah/exports/session-<timestamp>.md
+pub fn default_export_path(now: DateTime<Local>) -> Option<PathBuf> {
+    dirs::home_dir().map(|home| {
+        home.join(".shammah")
+            .join("exports")
+            .join(format!("session-{}.md", now.format("%Y%m%d-%H%M%S")))
+    })
+}
+
+/// Write `messages` as Markdown to `path` (or the default location)
+///
+/// Returns the written path, or None if there was nothing to export.
+pub fn export_markdown(messages: &[Message], path: Option<&str>) -> Result<Option<PathBuf>> {
+    if messages.is_empty() {
+        return Ok(None);
+    }
+
+    let now = Local::now();
+    let path = match path {
+        Some(path) => expand_home(path),
+        None => default_export_path(now).context("Could not determine home directory")?,
+    };
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    fs::write(&path, to_markdown(messages, now))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(Some(path))
+}
+
+/// `~/x` relative to the home directory, anything else unchanged
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Render a conversation as a Markdown transcript
+pub fn to_markdown(messages: &[Message], exported_at: DateTime<Local>) -> String {
+    let mut out = format!(
+        "# Shammah session\n\n_Exported {} · {} messages_\n",
+        exported_at.format("%Y-%m-%d %H:%M:%S %:z"),
+        messages.len()
+    );
+
+    for message in messages {
+        // Tool results come back as user messages; they fold under the
+        // assistant turn that asked for them instead of getting a header
+        if message.content.iter().any(|block| !is_tool_result(block)) {
+            out.push_str(&format!("\n## {}\n", role_title(&message.role)));
+        }
+        for block in &message.content {
+            out.push('\n');
+            match block {
+                ContentBlock::Text { text } => {
+                    out.push_str(text.trim_end());
+                    out.push('\n');
+                }
+                ContentBlock::Image { .. } => out.push_str("_[image]_\n"),
+                ContentBlock::ToolUse { name, input, .. } => {
+                    out.push_str(&format!("**Tool call:** `{}` `{}`\n", name, input));
+                }
+                ContentBlock::ToolResult {
+                    content, is_error, ..
+                } => {
+                    let label = if *is_error == Some(true) {
+                        "Tool error"
+                    } else {
+                        "Tool result"
+                    };
+                    let fence = fence_for(content);
+                    out.push_str(&format!(
+                        "<details>\n<summary>{}</summary>\n\n{}\n{}\n{}\n\n</details>\n",
+                        label,
+                        fence,
+                        content.trim_end(),
+                        fence
+                    ));
+                }
+                ContentBlock::Thinking { .. } | ContentBlock::RedactedThinking { .. } => {}
+            }
+        }
+    }
+    out
+}
+
+fn is_tool_result(block: &ContentBlock) -> bool {
+    matches!(block, ContentBlock::ToolResult { .. })
+}
+
+fn role_title(role: &str) -> String {
+    let mut chars = role.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => "Unknown".to_string(),
+    }
+}
+
+/// A backtick fence longer than any backtick run in `content`
+fn fence_for(content: &str) -> String {
+    let longest = content.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    "`".repeat(longest.max(2) + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exported_at() -> DateTime<Local> {
+        DateTime::parse_from_rfc3339("2026-10-16T09:30:00+00:00")
+            .unwrap()
+            .with_timezone(&Local)
+    }
+
+    #[test]
+    fn test_markdown_transcript() {
+        let messages = vec![
+            Message::user("Show me a loop"),
+            Message::with_content(
+                "assistant",
+                vec![
+                    ContentBlock::text("Here:\n\n```rust\nloop {}\n```"),
+                    ContentBlock::ToolUse {
+                        id: "t1".to_string(),
+                        name: "read".to_string(),
+                        input: serde_json::json!({"path": "a.rs"}),
+                    },
+                ],
+            ),
+            Message::with_content(
+                "user",
+                vec![ContentBlock::tool_result(
+                    "t1".to_string(),
+                    "```\nfn main() {}\n```".to_string(),
+                    None,
+                )],
+            ),
+        ];
+
+        let markdown = to_markdown(&messages, exported_at());
+        assert!(markdown.starts_with("# Shammah session\n\n_Exported 2026-10-16"));
+        assert!(markdown.contains("\n## User\n\nShow me a loop\n"));
+        assert!(markdown.contains("\n## Assistant\n\nHere:\n\n```rust\nloop {}\n```\n"));
+        assert!(markdown.contains("**Tool call:** `read` `{\"path\":\"a.rs\"}`"));
+        assert!(markdown.contains(
+            "<details>\n<summary>Tool result</summary>\n\n````\n```\nfn main() {}\n```\n````\n\n</details>"
+        ));
+        // The tool-result turn doesn't get its own header
+        assert_eq!(markdown.matches("## User").count(), 1);
+    }
+
+    #[test]
+    fn test_export_writes_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("notes/session.md");
+
+        let written = export_markdown(&[Message::user("Hi")], path.to_str()).unwrap();
+        assert_eq!(written.as_deref(), Some(path.as_path()));
+        assert!(fs::read_to_string(&path).unwrap().contains("Hi"));
+
+        assert!(export_markdown(&[], path.to_str()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_default_export_path() {
+        let path = default_export_path(exported_at()).unwrap();
+        let name = path.file_name().unwrap().to_str().unwrap();
+        assert!(name.starts_with("session-2026"));
+        assert!(name.ends_with(".md"));
+        assert!(path.parent().unwrap().ends_with(".shammah/exports"));
+    }
+}
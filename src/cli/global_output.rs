@@ -8,14 +8,21 @@
 // - output_response!() prints to stdout (actual model output)
 // - output_status!() is silent unless SHAMMAH_LOG=1
 // - Other macros write to buffer (for potential logging)
+//
+// Startup output (output_startup!, output_startup_warning!) is additionally
+// filtered by features.startup_verbosity.
 
 use once_cell::sync::Lazy;
 use std::io::{self, IsTerminal};
+use std::sync::atomic::{AtomicU8, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
 
 use super::{OutputManager, StatusBar};
 use super::tui::TuiRenderer;
-use crate::config::ColorScheme;
+use crate::config::{ColorScheme, StartupVerbosity};
+
+/// Startup verbosity (set from config in main())
+static STARTUP_VERBOSITY: AtomicU8 = AtomicU8::new(StartupVerbosity::Normal as u8);
 
 /// Global singleton OutputManager (swappable - set by main())
 /// Starts with a minimal default, replaced with the real instance in main()
@@ -115,6 +122,20 @@ pub fn is_non_interactive() -> bool {
     !io::stdout().is_terminal()
 }
 
+/// Set how much startup output is shown (from features.startup_verbosity)
+pub fn set_startup_verbosity(verbosity: StartupVerbosity) {
+    STARTUP_VERBOSITY.store(verbosity as u8, Ordering::Relaxed);
+}
+
+/// Current startup verbosity
+pub fn startup_verbosity() -> StartupVerbosity {
+    match STARTUP_VERBOSITY.load(Ordering::Relaxed) {
+        0 => StartupVerbosity::Quiet,
+        2 => StartupVerbosity::Verbose,
+        _ => StartupVerbosity::Normal,
+    }
+}
+
 /// Check if SHAMMAH_LOG environment variable is set
 pub fn logging_enabled() -> bool {
     std::env::var("SHAMMAH_LOG")
@@ -188,6 +209,32 @@ macro_rules! output_status {
     }};
 }
 
+/// Output a startup step (e.g. "✓ TUI mode enabled")
+/// Only shown when startup_verbosity = "verbose"
+#[macro_export]
+macro_rules! output_startup {
+    ($($arg:tt)*) => {{
+        if $crate::cli::global_output::startup_verbosity()
+            >= $crate::config::StartupVerbosity::Verbose
+        {
+            $crate::output_status!($($arg)*);
+        }
+    }};
+}
+
+/// Output a startup warning (e.g. a feature falling back)
+/// Hidden when startup_verbosity = "quiet"
+#[macro_export]
+macro_rules! output_startup_warning {
+    ($($arg:tt)*) => {{
+        if $crate::cli::global_output::startup_verbosity()
+            >= $crate::config::StartupVerbosity::Normal
+        {
+            $crate::output_status!($($arg)*);
+        }
+    }};
+}
+
 /// Output error message
 /// In non-interactive mode, prints to stderr if SHAMMAH_LOG=1
 /// In interactive mode, writes to buffer for TUI
@@ -0,0 +1,236 @@
+// Terminal glyph fallback
+//
+// Some terminals (or non-UTF-8 locales) render box-drawing characters and
+// emoji as mojibake. When ASCII-only mode is active, output passes through
+// `for_terminal()` which swaps known glyphs for ASCII equivalents.
+
+use ratatui::symbols::border;
+use ratatui::text::Line;
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ASCII_ONLY: AtomicBool = AtomicBool::new(false);
+
+/// Unicode glyphs and their ASCII replacements
+///
+/// Multi-codepoint sequences (emoji + variation selector) must come before
+/// their single-codepoint prefixes.
+const GLYPH_TABLE: &[(&str, &str)] = &[
+    ("ℹ️", "[i]"),
+    ("ℹ", "[i]"),
+    ("⚠️", "[!]"),
+    ("⚠", "[!]"),
+    ("✓", "[OK]"),
+    ("✔", "[OK]"),
+    ("✅", "[OK]"),
+    ("✗", "[X]"),
+    ("✘", "[X]"),
+    ("❌", "[X]"),
+    ("👍", "[+]"),
+    ("👎", "[-]"),
+    ("🤔", "[?]"),
+    ("⏳", "[...]"),
+    ("💭", "[~]"),
+    ("☑", "[x]"),
+    ("☐", "[ ]"),
+    ("❯", ">"),
+    ("▸", ">"),
+    ("•", "*"),
+    ("─", "-"),
+    ("━", "="),
+    ("│", "|"),
+    ("║", "|"),
+    ("═", "="),
+    ("╔", "+"),
+    ("╗", "+"),
+    ("╚", "+"),
+    ("╝", "+"),
+    ("┌", "+"),
+    ("┐", "+"),
+    ("└", "+"),
+    ("┘", "+"),
+    ("█", "#"),
+    ("░", "."),
+    ("↑", "^"),
+    ("↓", "v"),
+    ("←", "<-"),
+    ("→", "->"),
+    ("…", "..."),
+];
+
+/// Block border drawn with `+`, `-` and `|`
+const ASCII_BORDER: border::Set = border::Set {
+    top_left: "+",
+    top_right: "+",
+    bottom_left: "+",
+    bottom_right: "+",
+    vertical_left: "|",
+    vertical_right: "|",
+    horizontal_top: "-",
+    horizontal_bottom: "-",
+};
+
+/// Enable ASCII-only mode from config, or detect it from the locale
+///
+/// `configured` is `features.ascii_only`; `None` falls back to detection.
+pub fn init(configured: Option<bool>) {
+    let ascii_only = configured.unwrap_or_else(detect_ascii_only);
+    set_ascii_only(ascii_only);
+    if ascii_only {
+        tracing::debug!("ASCII-only output enabled");
+    }
+}
+
+/// Force ASCII-only mode on or off
+pub fn set_ascii_only(enabled: bool) {
+    ASCII_ONLY.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether output should avoid Unicode glyphs
+pub fn ascii_only() -> bool {
+    ASCII_ONLY.load(Ordering::Relaxed)
+}
+
+/// Heuristic: the terminal lacks Unicode if the effective locale isn't UTF-8
+///
+/// Follows POSIX precedence: LC_ALL, then LC_CTYPE, then LANG.
+pub fn detect_ascii_only() -> bool {
+    let lc_all = std::env::var("LC_ALL").ok();
+    let lc_ctype = std::env::var("LC_CTYPE").ok();
+    let lang = std::env::var("LANG").ok();
+    !locale_is_utf8(lc_all.as_deref(), lc_ctype.as_deref(), lang.as_deref())
+}
+
+fn locale_is_utf8(lc_all: Option<&str>, lc_ctype: Option<&str>, lang: Option<&str>) -> bool {
+    let locale = [lc_all, lc_ctype, lang]
+        .into_iter()
+        .flatten()
+        .find(|value| !value.is_empty());
+
+    match locale {
+        Some(locale) => {
+            let locale = locale.to_ascii_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        }
+        // No locale set at all: assume a modern terminal rather than degrading output
+        None => true,
+    }
+}
+
+/// Pick a glyph based on the current mode
+pub fn glyph(unicode: &'static str, ascii: &'static str) -> &'static str {
+    if ascii_only() {
+        ascii
+    } else {
+        unicode
+    }
+}
+
+/// Apply the ASCII fallback to a string if ASCII-only mode is active
+pub fn for_terminal(text: String) -> String {
+    if ascii_only() {
+        to_ascii(&text).into_owned()
+    } else {
+        text
+    }
+}
+
+/// Apply the ASCII fallback to every span of ratatui lines
+pub fn lines_for_terminal(lines: Vec<Line<'_>>) -> Vec<Line<'_>> {
+    if !ascii_only() {
+        return lines;
+    }
+
+    lines
+        .into_iter()
+        .map(|mut line| {
+            for span in &mut line.spans {
+                if !span.content.is_ascii() {
+                    span.content = Cow::Owned(to_ascii(&span.content).into_owned());
+                }
+            }
+            line
+        })
+        .collect()
+}
+
+/// Border symbols for ratatui blocks
+pub fn border_set() -> border::Set {
+    if ascii_only() {
+        ASCII_BORDER
+    } else {
+        border::PLAIN
+    }
+}
+
+/// Replace known glyphs with ASCII equivalents (unconditionally)
+///
+/// Unknown symbols and emoji become `*`; other non-ASCII text (accented or
+/// non-Latin letters in user content) is left untouched.
+pub fn to_ascii(text: &str) -> Cow<'_, str> {
+    if text.is_ascii() {
+        return Cow::Borrowed(text);
+    }
+
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    'outer: while let Some(ch) = rest.chars().next() {
+        if !ch.is_ascii() {
+            for (unicode, ascii) in GLYPH_TABLE {
+                if let Some(after) = rest.strip_prefix(unicode) {
+                    result.push_str(ascii);
+                    rest = after;
+                    continue 'outer;
+                }
+            }
+        }
+
+        match ch {
+            // Variation selectors and zero-width joiners have no ASCII form
+            '\u{FE0E}' | '\u{FE0F}' | '\u{200D}' => {}
+            c if is_symbol(c) => result.push('*'),
+            c => result.push(c),
+        }
+        rest = &rest[ch.len_utf8()..];
+    }
+
+    Cow::Owned(result)
+}
+
+/// Symbol/emoji ranges that won't render without Unicode support
+fn is_symbol(c: char) -> bool {
+    matches!(c as u32,
+        0x2190..=0x2BFF      // arrows, math, technical, box drawing, dingbats
+        | 0x1F000..=0x1FAFF  // emoji and pictographs
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locale_detection() {
+        assert!(locale_is_utf8(None, None, Some("en_US.UTF-8")));
+        assert!(locale_is_utf8(None, Some("C.utf8"), Some("C")));
+        assert!(!locale_is_utf8(Some("C"), None, Some("en_US.UTF-8")));
+        assert!(!locale_is_utf8(None, None, Some("POSIX")));
+        // Empty values are skipped, matching POSIX behavior
+        assert!(locale_is_utf8(Some(""), None, Some("en_US.UTF-8")));
+        assert!(locale_is_utf8(None, None, None));
+    }
+
+    #[test]
+    fn test_to_ascii() {
+        assert_eq!(to_ascii("plain text"), "plain text");
+        assert_eq!(to_ascii("✓ done"), "[OK] done");
+        assert_eq!(to_ascii("⚠️  careful"), "[!]  careful");
+        assert_eq!(to_ascii("───"), "---");
+        assert_eq!(to_ascii("[██░░]"), "[##..]");
+        assert_eq!(to_ascii("🚀 launch"), "* launch");
+        // Non-symbol text is preserved
+        assert_eq!(to_ascii("café"), "café");
+        assert!(to_ascii("↑/↓: Navigate | ←/→: Move").is_ascii());
+    }
+}
@@ -0,0 +1,394 @@
+// Slash command reference
+//
+// Single registry of every slash command, used to render /help (optionally
+// filtered by a search term) and the TUI's command ghost text. A test checks
+// each entry against `Command::parse`, so the reference can't drift from the
+// parser.
+
+/// Help section a command is listed under
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HelpCategory {
+    Conversation,
+    Models,
+    Tools,
+    Planning,
+    Training,
+    Feedback,
+    Mcp,
+    Config,
+}
+
+impl HelpCategory {
+    const ALL: [HelpCategory; 8] = [
+        HelpCategory::Conversation,
+        HelpCategory::Models,
+        HelpCategory::Tools,
+        HelpCategory::Planning,
+        HelpCategory::Training,
+        HelpCategory::Feedback,
+        HelpCategory::Mcp,
+        HelpCategory::Config,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            HelpCategory::Conversation => "Conversation",
+            HelpCategory::Models => "Models & Routing",
+            HelpCategory::Tools => "Tools & Approvals",
+            HelpCategory::Planning => "Plan Mode",
+            HelpCategory::Training => "Training",
+            HelpCategory::Feedback => "Feedback (LoRA Fine-Tuning)",
+            HelpCategory::Mcp => "MCP Plugins",
+            HelpCategory::Config => "Settings",
+        }
+    }
+}
+
+/// Reference entry for one slash command
+#[derive(Debug, Clone, Copy)]
+pub struct CommandHelp {
+    /// Command as typed; `a|b` in the last word lists alternatives
+    pub name: &'static str,
+    /// Argument placeholder ("" if none, `[..]` if optional)
+    pub args: &'static str,
+    pub aliases: &'static [&'static str],
+    pub category: HelpCategory,
+    pub description: &'static str,
+    /// Complete invocation (required when `args` has a mandatory argument)
+    pub example: Option<&'static str>,
+}
+
+const fn cmd(
+    name: &'static str,
+    category: HelpCategory,
+    description: &'static str,
+) -> CommandHelp {
+    CommandHelp {
+        name,
+        args: "",
+        aliases: &[],
+        category,
+        description,
+        example: None,
+    }
+}
+
+impl CommandHelp {
+    const fn args(mut self, args: &'static str) -> Self {
+        self.args = args;
+        self
+    }
+
+    const fn aliases(mut self, aliases: &'static [&'static str]) -> Self {
+        self.aliases = aliases;
+        self
+    }
+
+    const fn example(mut self, example: &'static str) -> Self {
+        self.example = Some(example);
+        self
+    }
+
+    /// Name and argument placeholder, e.g. `/local <query>`
+    pub fn usage(&self) -> String {
+        if self.args.is_empty() {
+            self.name.to_string()
+        } else {
+            format!("{} {}", self.name, self.args)
+        }
+    }
+
+    /// Whether `term` (lowercase) appears in the name, aliases, description or category
+    fn matches(&self, term: &str) -> bool {
+        let term = term.trim_start_matches('/');
+        self.name.to_lowercase().contains(term)
+            || self.aliases.iter().any(|a| a.to_lowercase().contains(term))
+            || self.description.to_lowercase().contains(term)
+            || self.category.title().to_lowercase().contains(term)
+    }
+}
+
+use HelpCategory::*;
+
+/// Every slash command, in display order within each category
+pub const COMMAND_REFERENCE: &[CommandHelp] = &[
+    // Conversation
+    cmd("/help", Conversation, "Show this reference, or only commands matching a term")
+        .args("[term]")
+        .example("/help tools"),
+    cmd("/clear", Conversation, "Clear conversation history (start fresh)").aliases(&["/reset"]),
+    cmd("/vary", Conversation, "Regenerate the last response n times (default 3) and pick one")
+        .args("[n]")
+        .example("/vary 4"),
+    cmd("/retry", Conversation, "Answer the last prompt again with the teacher, replacing the response"),
+    cmd("/undo", Conversation, "Drop the last prompt and its response (including tool calls) from context"),
+    cmd("/compact", Conversation, "Summarize older turns with the teacher, keeping the last n verbatim (default 2)")
+        .args("[n]")
+        .example("/compact 3"),
+    cmd("/pin", Conversation, "Pin the last message, or a note, so trimming never drops it")
+        .args("[text]")
+        .example("/pin Always use metric units"),
+    cmd("/context", Conversation, "Show what the next teacher call sends: messages, trimming, cache breakpoints"),
+    cmd("/pins", Conversation, "List pinned context"),
+    cmd("/unpin", Conversation, "Remove a pin by number (see /pins)")
+        .args("<n>")
+        .example("/unpin 1"),
+    cmd("/export", Conversation, "Save the conversation as Markdown (default: ~/.shammah/exports/)")
+        .args("[file]")
+        .example("/export notes/session.md"),
+    cmd("/image", Conversation, "Attach an image to your next query (always sent to the teacher)")
+        .args("<path>|clear")
+        .example("/image screenshot.png"),
+    cmd("/history clear", Conversation, "Delete saved input history (~/.shammah/history.txt)"),
+    cmd("/copy", Conversation, "Copy a code block from the last response to the clipboard (also: Alt+<n>)")
+        .args("[n]")
+        .example("/copy 2"),
+    cmd("/quit", Conversation, "Exit the REPL (also: Ctrl+D)").aliases(&["/exit"]),
+    // Models & routing
+    cmd("/local", Models, "Query the local model directly (bypass routing)")
+        .args("<query>")
+        .example("/local What is 2+2?"),
+    cmd("/forward", Models, "Send a query straight to the teacher (still learned from)")
+        .args("<query>")
+        .example("/forward Explain lifetimes"),
+    cmd("/mode forward|auto", Models, "Forward every query to the teacher, or return to routing"),
+    cmd("/no-tools", Models, "Answer this query as plain text (no tools offered)")
+        .args("<query>")
+        .example("/no-tools What is a monad?"),
+    cmd("/model-info", Models, "Show the local model: family, size, device, context length, LoRA adapter"),
+    cmd("/sampling", Models, "Show the teacher temperature used for each query category"),
+    cmd("/temp", Models, "Set the temperature for the next teacher call only, or show it")
+        .args("[<0.0-2.0>|off]")
+        .example("/temp 0.3"),
+    cmd("/think", Models, "Set the teacher's thinking budget for this session, or show it")
+        .args("[off|low|medium|high|<tokens>|default]")
+        .example("/think high"),
+    cmd("/thinking", Models, "Expand or collapse the teacher's thinking above its responses (also: Ctrl+T)"),
+    cmd("/with-tools", Models, "Answer this query with tools, even if /tools off")
+        .args("<query>")
+        .example("/with-tools list the files in src"),
+    // Tools & approvals
+    cmd("/tools", Tools, "List tools, permission status, and usage this session"),
+    cmd("/tools on|off", Tools, "Offer tools to the model for this session (default: on)"),
+    cmd("/dryrun", Tools, "Show bash commands instead of running them, for this session")
+        .args("[on|off]")
+        .example("/dryrun on"),
+    cmd("/replay-tool", Tools, "Run a recent tool call again (default: the last one)")
+        .args("[n|list]")
+        .example("/replay-tool 2"),
+    cmd("/benchmark-tools", Tools, "Run each tool once on a harmless input and report pass/fail")
+        .args("[--network]")
+        .example("/benchmark-tools --network"),
+    cmd("/watch", Tools, "Re-run a prompt whenever files matching a glob change (any input stops it)")
+        .args("<glob> :: <prompt> | stop")
+        .example("/watch src/**/*.rs :: run the tests and fix any failures"),
+    cmd("/summarize", Tools, "Summarize a file (local model for short files, teacher for long ones)")
+        .args("<path> [--detail brief|normal|detailed]")
+        .example("/summarize src/main.rs --detail brief"),
+    cmd("/patterns", Tools, "List saved tool approval patterns").aliases(&["/patterns list"]),
+    cmd("/patterns add", Tools, "Add an approval pattern (interactive wizard)"),
+    cmd("/patterns rm", Tools, "Remove a saved pattern by ID")
+        .args("<id>")
+        .aliases(&["/patterns remove"])
+        .example("/patterns rm 1a2b3c4d"),
+    cmd("/patterns clear", Tools, "Remove all saved patterns (asks for confirmation)"),
+    // Plan mode
+    cmd("/plan", Planning, "Toggle plan mode, or start planning a task (also: Shift+Tab)")
+        .args("[task]")
+        .example("/plan Add retry logic to the HTTP client"),
+    cmd("/done", Planning, "Summarize changes since the last /done and exit plan mode"),
+    // Training
+    cmd("/training", Training, "Show detailed training statistics"),
+    cmd("/training pause", Training, "Pause background LoRA training (examples stay buffered)"),
+    cmd("/training resume", Training, "Resume background LoRA training"),
+    cmd("/training cancel", Training, "Cancel the running training job")
+        .aliases(&["/cancel-training"]),
+    cmd("/metrics", Training, "Display usage statistics"),
+    // Feedback
+    cmd("/critical", Feedback, "Mark the last response as a critical error (10x weight)")
+        .args("[note]")
+        .aliases(&["/feedback critical", "/feedback high"])
+        .example("/critical Never use .unwrap() in production code"),
+    cmd("/medium", Feedback, "Mark the last response as needing improvement (3x weight)")
+        .args("[note]")
+        .aliases(&["/feedback medium"])
+        .example("/medium Prefer iterator chains over manual loops"),
+    cmd("/good", Feedback, "Mark the last response as a good example (1x weight)")
+        .args("[note]")
+        .aliases(&["/feedback good", "/feedback normal"])
+        .example("/good This is exactly the right approach"),
+    // MCP
+    cmd("/mcp list", Mcp, "List connected MCP servers").aliases(&["/mcp"]),
+    cmd("/mcp tools", Mcp, "List MCP tools, from all servers or one")
+        .args("[server]")
+        .example("/mcp tools github"),
+    cmd("/mcp refresh", Mcp, "Refresh the tool list from all servers"),
+    cmd("/mcp reload", Mcp, "Reconnect to all MCP servers"),
+    // Settings
+    cmd("/debug", Config, "Toggle debug output"),
+    cmd("/format", Config, "Toggle line numbers on response code blocks (/format hints: copy hints)")
+        .args("[hints]"),
+    cmd("/memory", Config, "Show memory usage (system and process)"),
+    cmd("/tts on|off", Config, "Speak each response aloud (also: --tts)")
+        .aliases(&["/voice on", "/voice off"]),
+    cmd("/provider-latency", Config, "Show teacher pool rotation and per-key latency"),
+    cmd("/profile", Config, "Time each stage of a query (also: --profile)")
+        .args("[on|off]")
+        .example("/profile on"),
+];
+
+/// Keyboard shortcuts shown with the full reference
+const SHORTCUTS: &[(&str, &str)] = &[
+    ("Ctrl+C", "Cancel current query (at an idle prompt: exit)"),
+    ("Ctrl+D", "Exit REPL (same as /quit)"),
+    ("Ctrl+G", "Mark last response as good (1x training weight)"),
+    ("Ctrl+B", "Mark last response as bad (10x training weight)"),
+    ("Shift+Tab", "Toggle plan mode on/off"),
+    ("Shift+Enter", "Multi-line input (insert newline)"),
+    ("Shift+PgUp/PgDown", "Scroll history"),
+    ("Up / Down", "Navigate command history"),
+];
+
+/// Command names and aliases, for prefix completion
+pub fn command_names() -> impl Iterator<Item = &'static str> {
+    COMMAND_REFERENCE
+        .iter()
+        .flat_map(|entry| std::iter::once(entry.name).chain(entry.aliases.iter().copied()))
+        .filter(|name| !name.contains('|'))
+}
+
+/// Render the command reference
+///
+/// With a topic, only commands whose name, alias, description or category
+/// contains it are listed. `color` adds ANSI styling (TUI); plain mode
+/// gets plain text.
+pub fn format_help(topic: Option<&str>, color: bool) -> String {
+    let topic = topic.map(|t| t.trim().to_lowercase()).filter(|t| !t.is_empty());
+    let style = |code: &str, text: &str| {
+        if color {
+            format!("\x1b[{}m{}\x1b[0m", code, text)
+        } else {
+            text.to_string()
+        }
+    };
+
+    let entries: Vec<&CommandHelp> = COMMAND_REFERENCE
+        .iter()
+        .filter(|entry| topic.as_deref().map_or(true, |t| entry.matches(t)))
+        .collect();
+
+    let mut out = match &topic {
+        Some(t) => format!("{}\n", style("1;32", &format!("Commands matching \"{}\"", t))),
+        None => format!("{}\n", style("1;32", "Shammah Help - Commands & Shortcuts")),
+    };
+
+    if entries.is_empty() {
+        out.push_str("\nNo commands match. Type /help for the full list.\n");
+        return out;
+    }
+
+    for category in HelpCategory::ALL {
+        let in_category: Vec<&&CommandHelp> =
+            entries.iter().filter(|e| e.category == category).collect();
+        if in_category.is_empty() {
+            continue;
+        }
+
+        out.push('\n');
+        out.push_str(&style("1;33", &format!("{}:", category.title())));
+        out.push('\n');
+        for entry in in_category {
+            let usage = entry.usage();
+            out.push_str(&format!(
+                "  {}{} {}\n",
+                style("36", &usage),
+                " ".repeat(22usize.saturating_sub(usage.chars().count())),
+                entry.description
+            ));
+            if !entry.aliases.is_empty() {
+                out.push_str(&format!(
+                    "  {:22} {}\n",
+                    "",
+                    style("90", &format!("Aliases: {}", entry.aliases.join(", ")))
+                ));
+            }
+            if let Some(example) = entry.example {
+                out.push_str(&format!(
+                    "  {:22} {}\n",
+                    "",
+                    style("90", &format!("Example: {}", example))
+                ));
+            }
+        }
+    }
+
+    if topic.is_none() {
+        out.push('\n');
+        out.push_str(&style("1;33", "Keyboard Shortcuts:"));
+        out.push('\n');
+        for (keys, description) in SHORTCUTS {
+            out.push_str(&format!(
+                "  {}{} {}\n",
+                style("36", keys),
+                " ".repeat(22usize.saturating_sub(keys.chars().count())),
+                description
+            ));
+        }
+        out.push_str(&format!(
+            "\n{}\n",
+            style("90", "Tip: /help <term> shows only matching commands, e.g. /help patterns")
+        ));
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::commands::Command;
+
+    /// Every documented form must be accepted by the parser
+    #[test]
+    fn test_reference_matches_parser() {
+        for entry in COMMAND_REFERENCE {
+            let mandatory_args = entry.args.starts_with('<');
+            let mut forms: Vec<String> = Vec::new();
+
+            if !mandatory_args {
+                match entry.name.rsplit_once(' ') {
+                    Some((head, choices)) if choices.contains('|') => {
+                        forms.extend(choices.split('|').map(|c| format!("{} {}", head, c)));
+                    }
+                    _ => forms.push(entry.name.to_string()),
+                }
+                forms.extend(entry.aliases.iter().map(|a| a.to_string()));
+            } else {
+                assert!(entry.example.is_some(), "{} needs an example", entry.name);
+                for alias in entry.aliases {
+                    let example = entry.example.unwrap();
+                    forms.push(example.replacen(entry.name, alias, 1));
+                }
+            }
+            forms.extend(entry.example.map(str::to_string));
+
+            for form in forms {
+                assert!(Command::parse(&form).is_some(), "`{}` doesn't parse", form);
+            }
+        }
+    }
+
+    #[test]
+    fn test_help_filter() {
+        let help = format_help(Some("pattern"), false);
+        assert!(help.contains("/patterns add"));
+        assert!(!help.contains("/training pause"));
+        assert!(!help.contains('\x1b'));
+
+        let help = format_help(Some("/cancel-training"), false);
+        assert!(help.contains("/training cancel"));
+
+        assert!(format_help(Some("nonexistent"), false).contains("No commands match"));
+        assert!(format_help(None, true).contains("Keyboard Shortcuts"));
+    }
+}
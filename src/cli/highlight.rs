@@ -0,0 +1,289 @@
+// Syntax highlighting for fenced code blocks
+//
+// A small line-at-a-time tokenizer rather than a full grammar: keywords,
+// strings, numbers and comments for common languages, colored from
+// `[colors.code]`. Each line is highlighted on its own, so block comments
+// and strings that span lines are only colored on their first line.
+// Unknown languages are left as plain text.
+
+use crate::cli::messages::concrete::color_to_ansi;
+use crate::config::{CodeColors, SyntaxColors};
+
+const RESET: &str = "\x1b[0m";
+
+/// How to tokenize one language
+struct Syntax {
+    /// Canonical name, the key for `[colors.code.languages.<name>]`
+    name: &'static str,
+    /// Fence tags that select this syntax
+    aliases: &'static [&'static str],
+    /// Space-separated keyword list
+    keywords: &'static str,
+    line_comments: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+    quotes: &'static [char],
+    /// `'` starts a char literal, not a string (so Rust lifetimes stay plain)
+    char_literals: bool,
+}
+
+const C_FAMILY_COMMENTS: &[&str] = &["//"];
+const HASH_COMMENTS: &[&str] = &["#"];
+
+const SYNTAXES: &[Syntax] = &[
+    Syntax {
+        name: "rust",
+        aliases: &["rust", "rs"],
+        keywords: "as async await break const continue crate dyn else enum extern false fn for if \
+                   impl in let loop match mod move mut pub ref return self Self static struct \
+                   super trait true type unsafe use where while",
+        line_comments: C_FAMILY_COMMENTS,
+        block_comment: Some(("/*", "*/")),
+        quotes: &['"', '\''],
+        char_literals: true,
+    },
+    Syntax {
+        name: "python",
+        aliases: &["python", "py", "python3"],
+        keywords: "and as assert async await break class continue def del elif else except False \
+                   finally for from global if import in is lambda None nonlocal not or pass raise \
+                   return True try while with yield",
+        line_comments: HASH_COMMENTS,
+        block_comment: None,
+        quotes: &['"', '\''],
+        char_literals: false,
+    },
+    Syntax {
+        name: "javascript",
+        aliases: &["javascript", "js", "jsx", "typescript", "ts", "tsx"],
+        keywords: "async await break case catch class const continue default delete do else \
+                   export extends false finally for from function if import in instanceof \
+                   interface let new null return switch this throw true try type typeof undefined \
+                   var void while yield",
+        line_comments: C_FAMILY_COMMENTS,
+        block_comment: Some(("/*", "*/")),
+        quotes: &['"', '\'', '`'],
+        char_literals: false,
+    },
+    Syntax {
+        name: "go",
+        aliases: &["go", "golang"],
+        keywords: "break case chan const continue default defer else false for func go if import \
+                   interface map nil package range return select struct switch true type var",
+        line_comments: C_FAMILY_COMMENTS,
+        block_comment: Some(("/*", "*/")),
+        quotes: &['"', '\'', '`'],
+        char_literals: false,
+    },
+    Syntax {
+        name: "c",
+        aliases: &["c", "h", "cpp", "c++", "cc", "hpp", "java", "csharp", "cs"],
+        keywords: "auto bool break case catch char class const continue default delete do double \
+                   else enum extends false final float for if implements import include int long \
+                   namespace new null nullptr package private protected public return short \
+                   signed sizeof static struct switch template this throw true try typedef union \
+                   unsigned using virtual void while",
+        line_comments: C_FAMILY_COMMENTS,
+        block_comment: Some(("/*", "*/")),
+        quotes: &['"', '\''],
+        char_literals: false,
+    },
+    Syntax {
+        name: "shell",
+        aliases: &["sh", "bash", "shell", "zsh", "console"],
+        keywords: "case do done elif else esac export fi for function if in local return then \
+                   until while",
+        line_comments: HASH_COMMENTS,
+        block_comment: None,
+        quotes: &['"', '\''],
+        char_literals: false,
+    },
+    Syntax {
+        name: "json",
+        aliases: &["json", "jsonc"],
+        keywords: "true false null",
+        line_comments: C_FAMILY_COMMENTS,
+        block_comment: None,
+        quotes: &['"'],
+        char_literals: false,
+    },
+    Syntax {
+        name: "toml",
+        aliases: &["toml", "yaml", "yml", "ini"],
+        keywords: "true false null",
+        line_comments: HASH_COMMENTS,
+        block_comment: None,
+        quotes: &['"', '\''],
+        char_literals: false,
+    },
+];
+
+/// Syntax for a fence info string ("rust", "rust,ignore", "py title=x")
+fn syntax_for(info: &str) -> Option<&'static Syntax> {
+    let tag = info
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .next()?
+        .to_lowercase();
+    SYNTAXES
+        .iter()
+        .find(|syntax| syntax.aliases.contains(&tag.as_str()))
+}
+
+/// Highlight one line of a block tagged `info` (None for unknown languages)
+pub fn highlight_line(line: &str, info: &str, colors: &CodeColors) -> Option<String> {
+    let syntax = syntax_for(info)?;
+    Some(tokenize(line, syntax, colors.for_language(syntax.name)))
+}
+
+fn tokenize(line: &str, syntax: &Syntax, colors: &SyntaxColors) -> String {
+    let paint = |out: &mut String, color, text: &str| {
+        out.push_str(&color_to_ansi(color));
+        out.push_str(text);
+        out.push_str(RESET);
+    };
+
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(c) = rest.chars().next() {
+        // Comments run to the end of the line (or the block comment's end)
+        if syntax
+            .line_comments
+            .iter()
+            .any(|marker| rest.starts_with(marker))
+        {
+            paint(&mut out, &colors.comment, rest);
+            break;
+        }
+        if let Some((open, close)) = syntax.block_comment {
+            if rest.starts_with(open) {
+                let end = rest[open.len()..]
+                    .find(close)
+                    .map_or(rest.len(), |i| open.len() + i + close.len());
+                paint(&mut out, &colors.comment, &rest[..end]);
+                rest = &rest[end..];
+                continue;
+            }
+        }
+
+        if syntax.quotes.contains(&c) {
+            if let Some(end) = string_end(rest, c, syntax.char_literals) {
+                paint(&mut out, &colors.string, &rest[..end]);
+                rest = &rest[end..];
+                continue;
+            }
+        }
+
+        if c.is_ascii_digit() {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '.'))
+                .unwrap_or(rest.len());
+            paint(&mut out, &colors.number, &rest[..end]);
+            rest = &rest[end..];
+            continue;
+        }
+
+        if c.is_alphabetic() || c == '_' {
+            let end = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            let word = &rest[..end];
+            if syntax
+                .keywords
+                .split_whitespace()
+                .any(|keyword| keyword == word)
+            {
+                paint(&mut out, &colors.keyword, word);
+            } else {
+                out.push_str(word);
+            }
+            rest = &rest[end..];
+            continue;
+        }
+
+        out.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    out
+}
+
+/// Byte length of the string literal opening `text` (None if it doesn't close)
+fn string_end(text: &str, quote: char, char_literals: bool) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in text.char_indices().skip(1) {
+        if char_literals && quote == '\'' && i > 3 {
+            return None; // Longer than 'x' or '\n': a lifetime
+        }
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            c if c == quote => return Some(i + c.len_utf8()),
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strip(text: &str) -> String {
+        let mut out = String::new();
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                chars.by_ref().find(|&c| c == 'm');
+            } else {
+                out.push(c);
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_highlight_rust() {
+        let colors = CodeColors::default();
+        let line = r#"fn main() { let s = "a \" b"; } // done"#;
+        let highlighted = highlight_line(line, "rust,ignore", &colors).unwrap();
+
+        assert_eq!(strip(&highlighted), line);
+        assert!(highlighted.starts_with("\x1b[35mfn\x1b[0m main"));
+        assert!(highlighted.contains("\x1b[32m\"a \\\" b\"\x1b[0m"));
+        assert!(highlighted.ends_with("\x1b[90m// done\x1b[0m"));
+    }
+
+    #[test]
+    fn test_rust_lifetimes_and_chars() {
+        let colors = CodeColors::default();
+        let line = "fn f<'a>(x: &'a str) -> char { 'x' }";
+        let highlighted = highlight_line(line, "rs", &colors).unwrap();
+
+        assert_eq!(strip(&highlighted), line);
+        assert!(highlighted.contains("<'a>"));
+        assert!(highlighted.contains("\x1b[32m'x'\x1b[0m"));
+    }
+
+    #[test]
+    fn test_numbers_and_language_colors() {
+        let mut colors = CodeColors::default();
+        colors.languages.insert(
+            "python".to_string(),
+            SyntaxColors {
+                keyword: crate::config::ColorSpec::Named("blue".to_string()),
+                ..SyntaxColors::default()
+            },
+        );
+        let highlighted = highlight_line("def f(): return 42 # x", "py", &colors).unwrap();
+
+        assert!(highlighted.starts_with("\x1b[34mdef\x1b[0m"));
+        assert!(highlighted.contains("\x1b[33m42\x1b[0m"));
+        assert!(highlighted.ends_with("\x1b[90m# x\x1b[0m"));
+    }
+
+    #[test]
+    fn test_unknown_language() {
+        let colors = CodeColors::default();
+        assert!(highlight_line("x = 1", "brainfuck", &colors).is_none());
+        assert!(highlight_line("x = 1", "", &colors).is_none());
+        assert!(highlight_line("let x = 1", "TypeScript", &colors).is_some());
+    }
+}
@@ -0,0 +1,147 @@
+// Input history policy
+//
+// Decides which submitted lines are kept in the up-arrow history and in
+// ~/.shammah/history.txt: lines containing API keys, lines starting with a
+// space (like bash's HISTCONTROL=ignorespace) and consecutive duplicates are
+// dropped. When saving, repeated lines are collapsed to their most recent
+// copy and the file is capped at a configurable number of entries.
+
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+use crate::config::HistoryConfig;
+use crate::daemon::redact_secrets;
+
+static POLICY: Mutex<Option<HistoryConfig>> = Mutex::new(None);
+
+/// Set the history policy from config (call once at startup)
+pub fn init(config: HistoryConfig) {
+    *POLICY.lock().unwrap() = Some(config);
+}
+
+/// Current history policy (defaults if `init` wasn't called)
+pub fn config() -> HistoryConfig {
+    POLICY.lock().unwrap().clone().unwrap_or_default()
+}
+
+/// Whether `line` should be remembered, given the previous history entry
+///
+/// `line` is the raw input, before any trimming, so the leading-space
+/// convention can be detected.
+pub fn should_store(config: &HistoryConfig, line: &str, previous: Option<&str>) -> bool {
+    if line.trim().is_empty() {
+        return false;
+    }
+    if config.ignore_space && line.starts_with(' ') {
+        return false;
+    }
+    if config.ignore_dups && previous == Some(line.trim()) {
+        return false;
+    }
+    if config.filter_secrets && contains_secret(line) {
+        return false;
+    }
+    true
+}
+
+/// Append a submitted line to an in-memory history if the policy allows it
+///
+/// Returns true if the line was stored.
+pub fn push(history: &mut Vec<String>, line: &str) -> bool {
+    let config = config();
+    if should_store(&config, line, history.last().map(String::as_str)) {
+        history.push(line.trim().to_string());
+        true
+    } else {
+        false
+    }
+}
+
+/// Entries to write to disk: the policy re-applied (which also scrubs
+/// secrets from history files written before filtering existed), repeats
+/// collapsed if `dedupe` is set, then capped to the newest `max_entries`
+pub fn entries_to_save(history: &[String]) -> Vec<&str> {
+    select_entries(&config(), history)
+}
+
+fn select_entries<'a>(config: &HistoryConfig, history: &'a [String]) -> Vec<&'a str> {
+    let mut kept: Vec<&str> = Vec::with_capacity(history.len());
+    for entry in history {
+        if should_store(config, entry, kept.last().copied()) {
+            kept.push(entry.as_str());
+        }
+    }
+
+    if config.dedupe {
+        // Walk newest first so each line keeps its most recent position
+        let mut seen = HashSet::new();
+        kept.reverse();
+        kept.retain(|entry| seen.insert(*entry));
+        kept.reverse();
+    }
+
+    let skip = kept.len().saturating_sub(config.max_entries);
+    kept.split_off(skip)
+}
+
+fn contains_secret(line: &str) -> bool {
+    redact_secrets(line) != line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_store_policy() {
+        let config = HistoryConfig::default();
+
+        assert!(should_store(&config, "cargo test", None));
+        assert!(!should_store(&config, " cargo test", None));
+        assert!(!should_store(&config, "cargo test", Some("cargo test")));
+        assert!(should_store(&config, "cargo test", Some("cargo build")));
+        assert!(!should_store(&config, "my key is sk-ant-api03-abcdefghijkl", None));
+        assert!(!should_store(&config, "   ", None));
+
+        let permissive = HistoryConfig {
+            ignore_space: false,
+            ignore_dups: false,
+            filter_secrets: false,
+            ..HistoryConfig::default()
+        };
+        assert!(should_store(&permissive, " cargo test", Some("cargo test")));
+        assert!(should_store(&permissive, "sk-ant-api03-abcdefghijkl", None));
+    }
+
+    #[test]
+    fn test_select_entries_dedupes_before_cap() {
+        let history: Vec<String> = ["/help", "cargo test", "/help", "/help", "git status", "cargo test"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let config = HistoryConfig {
+            max_entries: 4,
+            ..HistoryConfig::default()
+        };
+        assert_eq!(select_entries(&config, &history), vec!["/help", "git status", "cargo test"]);
+
+        let consecutive_only = HistoryConfig {
+            dedupe: false,
+            ..config.clone()
+        };
+        assert_eq!(
+            select_entries(&consecutive_only, &history),
+            vec!["cargo test", "/help", "git status", "cargo test"]
+        );
+
+        let raw = HistoryConfig {
+            ignore_dups: false,
+            ..consecutive_only
+        };
+        assert_eq!(
+            select_entries(&raw, &history),
+            vec!["/help", "/help", "git status", "cargo test"]
+        );
+    }
+}
@@ -0,0 +1,100 @@
+// Idle exit
+//
+// On shared machines the REPL can save and exit after a period without
+// input (`idle_exit_minutes`). Input paths call `record_activity` on every
+// keystroke; the REPL polls `check` while it sits at the prompt and warns a
+// minute before exiting.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How long before exiting the warning is shown
+pub const WARNING_LEAD: Duration = Duration::from_secs(60);
+
+/// Idle limit in seconds (0 = disabled)
+static LIMIT_SECS: AtomicU64 = AtomicU64::new(0);
+static LAST_ACTIVITY: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Where the REPL stands relative to the idle limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdleState {
+    /// Recently used (or idle exit disabled)
+    Active,
+    /// Less than `WARNING_LEAD` left; holds the time remaining
+    Warning(Duration),
+    /// Limit reached: save and exit
+    Expired,
+}
+
+/// Set the idle limit from config (call once at startup, 0 = disabled)
+pub fn init(idle_exit_minutes: u64) {
+    LIMIT_SECS.store(idle_exit_minutes.saturating_mul(60), Ordering::Relaxed);
+    record_activity();
+}
+
+/// Configured limit (None when disabled)
+pub fn limit() -> Option<Duration> {
+    match LIMIT_SECS.load(Ordering::Relaxed) {
+        0 => None,
+        secs => Some(Duration::from_secs(secs)),
+    }
+}
+
+/// Reset the idle timer (keystrokes, and while a query is running)
+pub fn record_activity() {
+    *LAST_ACTIVITY.lock().unwrap() = Some(Instant::now());
+}
+
+/// Time since the last recorded activity
+pub fn idle_for() -> Duration {
+    LAST_ACTIVITY
+        .lock()
+        .unwrap()
+        .map_or(Duration::ZERO, |last| last.elapsed())
+}
+
+/// Current idle state (call only while waiting at the prompt)
+pub fn check() -> IdleState {
+    match limit() {
+        Some(limit) => state(idle_for(), limit),
+        None => IdleState::Active,
+    }
+}
+
+/// Idle state after `idle` without input, given `limit`
+pub fn state(idle: Duration, limit: Duration) -> IdleState {
+    if idle >= limit {
+        IdleState::Expired
+    } else if limit - idle <= WARNING_LEAD {
+        IdleState::Warning(limit - idle)
+    } else {
+        IdleState::Active
+    }
+}
+
+/// Status bar text for the warning
+pub fn warning_message(remaining: Duration) -> String {
+    format!(
+        "⏳ Idle - saving and exiting in {}s (press any key to stay)",
+        remaining.as_secs().max(1)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_idle_state() {
+        let limit = Duration::from_secs(10 * 60);
+
+        assert_eq!(state(Duration::from_secs(60), limit), IdleState::Active);
+        assert_eq!(
+            state(Duration::from_secs(9 * 60 + 30), limit),
+            IdleState::Warning(Duration::from_secs(30))
+        );
+        assert_eq!(state(limit, limit), IdleState::Expired);
+        assert_eq!(state(Duration::from_secs(3600), limit), IdleState::Expired);
+    }
+}
@@ -1,6 +1,7 @@
 // Readline input handler with history and editing support
 
 use anyhow::{Context, Result};
+use rustyline::config::Configurer;
 use rustyline::error::ReadlineError;
 use rustyline::DefaultEditor;
 use std::path::PathBuf;
@@ -8,12 +9,17 @@ use std::path::PathBuf;
 pub struct InputHandler {
     editor: DefaultEditor,
     history_path: PathBuf,
+    /// Most recent history entry (for de-duplication)
+    last_entry: Option<String>,
 }
 
 impl InputHandler {
     /// Create new input handler with history support
     pub fn new() -> Result<Self> {
         let mut editor = DefaultEditor::new().context("Failed to initialize readline editor")?;
+        editor
+            .set_max_history_size(super::history::config().max_entries)
+            .context("Failed to set history size")?;
 
         // History path: ~/.shammah/history.txt
         let history_path = dirs::home_dir()
@@ -29,6 +35,7 @@ impl InputHandler {
         Ok(Self {
             editor,
             history_path,
+            last_entry: None,
         })
     }
 
@@ -40,13 +47,16 @@ impl InputHandler {
     /// - `Err(e)` - I/O or other error
     pub fn read_line(&mut self, prompt: &str) -> Result<Option<String>> {
         match self.editor.readline(prompt) {
-            Ok(line) => {
-                let line = line.trim().to_string();
-                if !line.is_empty() {
+            Ok(raw) => {
+                let line = raw.trim().to_string();
+                // Check the raw line so a leading space can opt out of history
+                let config = super::history::config();
+                if super::history::should_store(&config, &raw, self.last_entry.as_deref()) {
                     // Add to history (in-memory)
                     self.editor
                         .add_history_entry(&line)
                         .context("Failed to add history entry")?;
+                    self.last_entry = Some(line.clone());
                 }
                 Ok(Some(line))
             }
@@ -82,6 +92,15 @@ impl InputHandler {
 
         Ok(())
     }
+
+    /// Forget all history, in memory and on disk
+    pub fn clear_history(&mut self) -> Result<()> {
+        self.editor
+            .clear_history()
+            .context("Failed to clear history")?;
+        self.last_entry = None;
+        self.save_history()
+    }
 }
 
 #[cfg(test)]
@@ -48,15 +48,51 @@ pub struct AskUserQuestionInput {
 }
 
 /// Output from AskUserQuestion tool
+///
+/// Serialized with a `status` tag ("answered" or "cancelled") so the model
+/// can tell a declined question from an answered one.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AskUserQuestionOutput {
-    /// The questions that were asked (echoed back)
-    pub questions: Vec<Question>,
+#[serde(tag = "status", rename_all = "lowercase")]
+pub enum AskUserQuestionOutput {
+    /// User answered every question
+    Answered {
+        /// The questions that were asked (echoed back)
+        questions: Vec<Question>,
+
+        /// Answers provided by user (question text → selected label(s))
+        /// For single-select: value is the label string
+        /// For multi-select: value is comma-separated labels
+        answers: HashMap<String, String>,
+    },
+
+    /// User dismissed the dialog without answering
+    Cancelled {
+        /// The questions that were asked (echoed back)
+        questions: Vec<Question>,
+    },
+}
 
-    /// Answers provided by user (question text → selected label(s))
-    /// For single-select: value is the label string
-    /// For multi-select: value is comma-separated labels
-    pub answers: HashMap<String, String>,
+impl AskUserQuestionOutput {
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self, Self::Cancelled { .. })
+    }
+
+    /// Tool result text sent back to the model
+    ///
+    /// A cancellation is a normal result, not an error: the JSON is followed
+    /// by a note telling the model to carry on without the answers.
+    pub fn to_tool_result(&self) -> serde_json::Result<String> {
+        let json = serde_json::to_string_pretty(self)?;
+        Ok(match self {
+            Self::Answered { .. } => json,
+            Self::Cancelled { .. } => format!(
+                "{}\n\nThe user declined to answer. Don't ask the same question again; \
+                 continue with reasonable defaults (and say which ones you chose), \
+                 or ask in a different way if you can't proceed without an answer.",
+                json
+            ),
+        })
+    }
 }
 
 /// Validation errors for AskUserQuestion input
@@ -382,6 +418,36 @@ mod tests {
         assert_eq!(answer, Some("Option B".to_string()));
     }
 
+    #[test]
+    fn test_cancelled_output_is_tool_result() {
+        let output = AskUserQuestionOutput::Cancelled {
+            questions: vec![Question {
+                question: "Which approach?".to_string(),
+                header: "Approach".to_string(),
+                options: vec![
+                    QuestionOption { label: "A".to_string(), description: "Fast".to_string() },
+                    QuestionOption { label: "B".to_string(), description: "Simple".to_string() },
+                ],
+                multi_select: false,
+            }],
+        };
+
+        assert!(output.is_cancelled());
+        let result = output.to_tool_result().unwrap();
+        assert!(result.contains("\"status\": \"cancelled\""));
+        assert!(result.contains("Which approach?"));
+        assert!(result.contains("declined to answer"));
+
+        let answered = AskUserQuestionOutput::Answered {
+            questions: vec![],
+            answers: HashMap::from([("Which approach?".to_string(), "A".to_string())]),
+        };
+        assert!(!answered.is_cancelled());
+        let json: serde_json::Value = serde_json::from_str(&answered.to_tool_result().unwrap()).unwrap();
+        assert_eq!(json["status"], "answered");
+        assert_eq!(json["answers"]["Which approach?"], "A");
+    }
+
     #[test]
     fn test_extract_answer_multi_select() {
         use crate::cli::tui::DialogResult;
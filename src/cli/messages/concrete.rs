@@ -4,13 +4,18 @@
 // No need for downcasting - handlers receive concrete types directly.
 
 use super::{Message, MessageId, MessageStatus};
+use crate::cli::spinner;
 use crate::config::{ColorScheme, ColorSpec};
 use std::sync::{Arc, RwLock};
+use std::time::Instant;
 
-/// Helper to convert ColorSpec to ANSI escape code
-fn color_to_ansi(color: &ColorSpec) -> String {
+/// Helper to convert ColorSpec to ANSI escape code (empty when color is off)
+pub(crate) fn color_to_ansi(color: &ColorSpec) -> String {
     use ratatui::style::Color;
 
+    if !crate::cli::color::enabled() {
+        return String::new();
+    }
     match color {
         ColorSpec::Named(name) => {
             // Map named colors to ANSI codes
@@ -103,6 +108,9 @@ pub struct StreamingResponseMessage {
     content: Arc<RwLock<String>>,
     status: Arc<RwLock<MessageStatus>>,
     thinking: Arc<RwLock<bool>>,
+    /// Streamed teacher thinking, shown above the text
+    thinking_text: Arc<RwLock<String>>,
+    started_at: Instant,
 }
 
 impl StreamingResponseMessage {
@@ -112,6 +120,8 @@ impl StreamingResponseMessage {
             content: Arc::new(RwLock::new(String::new())),
             status: Arc::new(RwLock::new(MessageStatus::InProgress)),
             thinking: Arc::new(RwLock::new(false)),
+            thinking_text: Arc::new(RwLock::new(String::new())),
+            started_at: Instant::now(),
         }
     }
 
@@ -127,6 +137,17 @@ impl StreamingResponseMessage {
         }
     }
 
+    /// Append a chunk of streamed thinking
+    pub fn append_thinking_text(&self, text: &str) {
+        match self.thinking_text.write() {
+            Ok(mut thinking_text) => thinking_text.push_str(text),
+            Err(poisoned) => {
+                tracing::warn!("StreamingResponseMessage thinking_text lock poisoned, recovering");
+                poisoned.into_inner().push_str(text);
+            }
+        }
+    }
+
     /// Set whether the model is thinking (for UI indicator)
     pub fn set_thinking(&self, thinking: bool) {
         match self.thinking.write() {
@@ -192,17 +213,27 @@ impl Message for StreamingResponseMessage {
             }
         };
 
+        let thinking_text = match self.thinking_text.read() {
+            Ok(t) => t.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        };
+        let thinking_section = crate::cli::thinking::format_section(
+            &thinking_text,
+            crate::cli::thinking::display(),
+            status == MessageStatus::InProgress,
+        );
+
         // No cleaning - already cleaned by daemon during streaming
-        let text = content.clone();
+        let text = crate::cli::code_blocks::format_response(&content, &colors.code);
 
-        match status {
+        let body = match status {
             MessageStatus::InProgress if thinking => {
-                format!("🤔 [thinking...]\n{}", text)
+                format!("{} [thinking...]\n{}", spinner::frame(self.started_at.elapsed()), text)
             }
             MessageStatus::InProgress => {
                 // Regular streaming (not thinking)
                 if text.is_empty() {
-                    "⏳ [streaming...]".to_string()
+                    format!("{} [streaming...]", spinner::frame(self.started_at.elapsed()))
                 } else {
                     format!("{}▸", text)  // Streaming indicator at end
                 }
@@ -216,6 +247,11 @@ impl Message for StreamingResponseMessage {
                 )
             }
             MessageStatus::Complete => text,
+        };
+
+        match thinking_section {
+            Some(section) => format!("{}\n{}", section, body),
+            None => body,
         }
     }
 
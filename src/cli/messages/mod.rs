@@ -62,6 +62,12 @@ pub trait Message: Send + Sync {
     /// Format this message for display (with ANSI colors and styling)
     fn format(&self, colors: &crate::config::ColorScheme) -> String;
 
+    /// Format for the terminal, applying the ASCII fallback when enabled and
+    /// dropping ANSI styling when color is off
+    fn format_for_terminal(&self, colors: &crate::config::ColorScheme) -> String {
+        crate::cli::color::for_terminal(crate::cli::glyphs::for_terminal(self.format(colors)))
+    }
+
     /// Get the current status of this message
     fn status(&self) -> MessageStatus;
 
@@ -1,24 +1,42 @@
 // CLI module
 // Public interface for command-line interface
 
+pub mod autosave; // Crash-safe conversation autosave
+pub mod code_blocks; // Code block line numbers and copy shortcuts (/format, /copy)
+pub mod color; // NO_COLOR / --no-color: plain output without ANSI escapes
 mod commands;
+pub mod context; // Teacher context inspection (/context)
+pub mod export; // Markdown conversation transcripts (/export)
 mod conversation;
+pub mod glyphs; // ASCII fallback for terminals without Unicode support
 pub mod global_output; // Phase 3.5: Global output system with macros
+pub mod help; // Slash command reference (/help, ghost text)
+pub mod highlight; // Syntax highlighting for fenced code blocks
+pub mod history; // Input history policy (secret filtering, de-duplication)
+pub mod idle; // Save and exit after a period without input
 mod input;
 pub mod llm_dialogs; // LLM-prompted user dialogs (AskUserQuestion)
 pub mod menu;
 pub mod messages; // Trait-based polymorphic message system
 pub mod output_layer; // Phase 3.5: Tracing integration
 mod output_manager;
+pub mod profile; // Per-query stage timings (--profile, /profile)
 mod repl;
+pub mod replay; // Replay saved sessions to spot regressions after retraining
 pub mod repl_event; // Phase 2-3: Event loop infrastructure
 pub mod setup_wizard; // First-run setup wizard (API keys + device selection)
+pub mod spinner; // Generating indicator animation
 mod status_bar;
 pub mod suggestions; // Contextual prompt suggestions (like Claude Code)
+pub mod tts; // Speak responses via an external TTS command
 pub mod tui; // Phase 2: Terminal UI
+pub mod summarize; // File summaries (/summarize)
+pub mod thinking; // Teacher thinking display (/thinking)
+pub mod vary; // Regenerate the last response with more variation (/vary)
+pub mod watch; // Re-run a prompt when watched files change (/watch)
 
 pub use commands::handle_command;
-pub use conversation::ConversationHistory;
+pub use conversation::{recent_turns_start, ConversationHistory};
 pub use input::InputHandler;
 pub use llm_dialogs::{AskUserQuestionInput, AskUserQuestionOutput, Question, QuestionOption};
 pub use messages::{Message, MessageId, MessageRef, MessageStatus};
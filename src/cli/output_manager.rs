@@ -111,7 +111,7 @@ impl OutputManager {
 
     /// Write a trait-based message to terminal
     fn write_trait_to_terminal(&self, message: &MessageRef) {
-        let formatted = message.format(&self.colors);
+        let formatted = message.format_for_terminal(&self.colors);
 
         let buffering = *self.buffering_mode.read().unwrap();
         let write_stdout = *self.write_to_stdout.read().unwrap();
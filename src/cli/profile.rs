@@ -0,0 +1,296 @@
+// Per-query profiling
+//
+// With `--profile` (or `/profile on`) each query records how long routing,
+// local generation, the teacher call, every tool execution and rendering
+// took. After the response a small waterfall is printed and the timings are
+// appended to `~/.shammah/profile.jsonl` for later comparison.
+//
+// Stages are timed with `span` guards that stop when dropped. When profiling
+// is off, `span` and `record_render` are a single atomic load.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// Width of the waterfall bars, in columns
+const BAR_WIDTH: usize = 30;
+
+/// Stage name for the aggregated TUI render time
+pub const RENDER_STAGE: &str = "render";
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static ACTIVE: Mutex<Vec<ActiveProfile>> = Mutex::new(Vec::new());
+static FINISHED: Mutex<Vec<QueryProfile>> = Mutex::new(Vec::new());
+
+/// Time spent in one stage of a query
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StageTiming {
+    pub name: String,
+    /// Offset from the start of the query
+    pub start_ms: u64,
+    pub duration_ms: u64,
+    /// Number of samples folded into this stage (renders are aggregated)
+    pub count: u32,
+}
+
+/// Timings for one completed query (one line of profile.jsonl)
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryProfile {
+    pub query_id: Uuid,
+    /// The query, shortened
+    pub query: String,
+    pub timestamp: DateTime<Utc>,
+    pub total_ms: u64,
+    pub stages: Vec<StageTiming>,
+}
+
+struct ActiveProfile {
+    started: Instant,
+    profile: QueryProfile,
+}
+
+impl ActiveProfile {
+    fn offset_ms(&self, at: Instant) -> u64 {
+        at.saturating_duration_since(self.started).as_millis() as u64
+    }
+}
+
+/// Times a stage until dropped (does nothing when profiling is off)
+#[must_use = "the stage is timed until the span is dropped"]
+pub struct Span {
+    inner: Option<(Uuid, String, Instant)>,
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        if let Some((query_id, name, started)) = self.inner.take() {
+            let mut active = ACTIVE.lock().unwrap();
+            if let Some(entry) = active.iter_mut().find(|a| a.profile.query_id == query_id) {
+                let start_ms = entry.offset_ms(started);
+                entry.profile.stages.push(StageTiming {
+                    name,
+                    start_ms,
+                    duration_ms: started.elapsed().as_millis() as u64,
+                    count: 1,
+                });
+            }
+        }
+    }
+}
+
+/// Turn profiling on from the command line (call once at startup)
+pub fn init(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Turn profiling on or off for this session
+///
+/// Turning it off drops any half-recorded queries.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+    if !enabled {
+        ACTIVE.lock().unwrap().clear();
+    }
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Confirmation shown by `/profile`
+pub fn status_message(enabled: bool) -> String {
+    if enabled {
+        let path = log_path().map_or_else(|| "profile.jsonl".to_string(), |p| p.display().to_string());
+        format!("⏱  Profiling on: a waterfall follows each response (logged to {})", path)
+    } else {
+        "Profiling off".to_string()
+    }
+}
+
+/// Start profiling a query
+pub fn begin(query_id: Uuid, query: &str) {
+    if !is_enabled() {
+        return;
+    }
+    let query = crate::cli::vary::preview(query);
+    ACTIVE.lock().unwrap().push(ActiveProfile {
+        started: Instant::now(),
+        profile: QueryProfile {
+            query_id,
+            query,
+            timestamp: Utc::now(),
+            total_ms: 0,
+            stages: Vec::new(),
+        },
+    });
+}
+
+/// Time a stage of a query until the returned guard is dropped
+pub fn span(query_id: Uuid, name: impl Into<String>) -> Span {
+    if !is_enabled() {
+        return Span { inner: None };
+    }
+    Span {
+        inner: Some((query_id, name.into(), Instant::now())),
+    }
+}
+
+/// Add one render pass to every query being profiled
+///
+/// Renders aren't tied to a query, so they're summed into a single stage.
+pub fn record_render(duration: Duration) {
+    if !is_enabled() {
+        return;
+    }
+    let now = Instant::now();
+    let mut active = ACTIVE.lock().unwrap();
+    for entry in active.iter_mut() {
+        let start_ms = entry.offset_ms(now.checked_sub(duration).unwrap_or(now));
+        let duration_ms = duration.as_millis() as u64;
+        match entry.profile.stages.iter_mut().find(|s| s.name == RENDER_STAGE) {
+            Some(stage) => {
+                stage.duration_ms += duration_ms;
+                stage.count += 1;
+            }
+            None => entry.profile.stages.push(StageTiming {
+                name: RENDER_STAGE.to_string(),
+                start_ms,
+                duration_ms,
+                count: 1,
+            }),
+        }
+    }
+}
+
+/// Stop profiling a query; its profile is queued for `take_finished`
+pub fn finish(query_id: Uuid) {
+    let mut active = ACTIVE.lock().unwrap();
+    let Some(index) = active.iter().position(|a| a.profile.query_id == query_id) else {
+        return;
+    };
+    let entry = active.remove(index);
+    drop(active);
+
+    let mut profile = entry.profile;
+    profile.total_ms = entry.started.elapsed().as_millis() as u64;
+    profile.stages.sort_by_key(|s| s.start_ms);
+    FINISHED.lock().unwrap().push(profile);
+}
+
+/// Profiles finished since the last call, oldest first
+pub fn take_finished() -> Vec<QueryProfile> {
+    std::mem::take(&mut *FINISHED.lock().unwrap())
+}
+
+/// Where profiles are appended (~/.shammah/profile.jsonl)
+pub fn log_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".shammah").join("profile.jsonl"))
+}
+
+/// Append a profile to profile.jsonl
+pub fn append_jsonl(profile: &QueryProfile) -> Result<()> {
+    let path = log_path().context("Could not determine home directory")?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(profile)?)?;
+    Ok(())
+}
+
+/// Log a finished profile and return its waterfall for display
+pub fn report(profile: &QueryProfile) -> String {
+    if let Err(e) = append_jsonl(profile) {
+        tracing::warn!("Failed to write profile: {}", e);
+    }
+    waterfall(profile)
+}
+
+/// Text waterfall: one row per stage, bars placed on the query's timeline
+pub fn waterfall(profile: &QueryProfile) -> String {
+    let total = profile.total_ms.max(1);
+    let labels: Vec<String> = profile
+        .stages
+        .iter()
+        .map(|stage| match stage.count {
+            1 => stage.name.clone(),
+            count => format!("{} ({}×)", stage.name, count),
+        })
+        .collect();
+    let label_width = labels.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+
+    let mut out = format!("⏱  Profile: {}", format_ms(profile.total_ms));
+    if profile.stages.is_empty() {
+        out.push_str("\n  (no stages recorded)");
+    }
+    for (stage, label) in profile.stages.iter().zip(&labels) {
+        let offset = (stage.start_ms.min(total) as usize * BAR_WIDTH) / total as usize;
+        let len = ((stage.duration_ms as usize * BAR_WIDTH) / total as usize)
+            .clamp(1, BAR_WIDTH - offset.min(BAR_WIDTH - 1));
+        out.push_str(&format!(
+            "\n  {:<label_width$}  {:>7}  {}{}",
+            label,
+            format_ms(stage.duration_ms),
+            " ".repeat(offset),
+            "█".repeat(len),
+        ));
+    }
+    out
+}
+
+fn format_ms(ms: u64) -> String {
+    if ms < 1000 {
+        format!("{}ms", ms)
+    } else {
+        format!("{:.2}s", ms as f64 / 1000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stage(name: &str, start_ms: u64, duration_ms: u64, count: u32) -> StageTiming {
+        StageTiming {
+            name: name.to_string(),
+            start_ms,
+            duration_ms,
+            count,
+        }
+    }
+
+    #[test]
+    fn test_waterfall() {
+        let profile = QueryProfile {
+            query_id: Uuid::nil(),
+            query: "hello".to_string(),
+            timestamp: Utc::now(),
+            total_ms: 3000,
+            stages: vec![
+                stage("routing", 0, 2, 1),
+                stage("teacher", 0, 1500, 1),
+                stage("tool: Read", 1500, 1500, 1),
+                stage(RENDER_STAGE, 10, 40, 12),
+            ],
+        };
+
+        let text = waterfall(&profile);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "⏱  Profile: 3.00s");
+        // Sub-column stages still get a one-column bar
+        assert!(lines[1].ends_with("  2ms  █"));
+        assert!(lines[2].ends_with(&format!("1.50s  {}", "█".repeat(15))));
+        assert!(lines[3].ends_with(&format!("1.50s  {}{}", " ".repeat(15), "█".repeat(15))));
+        assert!(lines[4].contains("render (12×)"));
+    }
+}
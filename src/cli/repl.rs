@@ -15,27 +15,34 @@ use std::sync::Arc;
 use std::time::Instant;
 use tokio::sync::{mpsc, RwLock};
 
-use crate::claude::{ClaudeClient, MessageRequest};
-use crate::config::Config;
+use crate::claude::{ClaudeClient, ContentBlock, Message, MessageRequest};
+use crate::cli::profile;
+use crate::metrics::audit::{self, AuditOutcome};
+use crate::config::{Config, ToolLimits};
 use crate::local::LocalGenerator;
 use crate::metrics::{MetricsLogger, RequestMetric, ResponseComparison, TrainingTrends};
 use crate::models::tokenizer::TextTokenizer;
+use crate::models::quality::{QualityScorer, LEARN_THRESHOLD};
 use crate::models::ThresholdValidator;
 use crate::models::{
-    BootstrapLoader, GeneratorState, Sampler, SamplingConfig, TrainingCoordinator, WeightedExample,
+    wait_for_ready, BootstrapLoader, GeneratorState, LocalWaitOutcome, Sampler, SamplingConfig,
+    TrainingCoordinator, WeightedExample,
 };
 use crate::providers::{TeacherContextConfig, TeacherSession};
 use crate::router::{ForwardReason, RouteDecision, Router};
 use crate::tools::executor::{generate_tool_signature, ApprovalSource, ToolSignature};
 use crate::tools::implementations::{
-    AskUserQuestionTool, BashTool, EnterPlanModeTool, GlobTool, GrepTool, PresentPlanTool,
-    ReadTool, RestartTool, SaveAndExecTool, WebFetchTool,
+    AskUserQuestionTool, BashTool, EditTool, EnterPlanModeTool, GlobTool, GrepTool, ListDirTool,
+    PresentPlanTool, ReadTool, RestartTool, SaveAndExecTool, WebFetchTool, WebSearchTool, WriteTool,
 };
 #[cfg(target_os = "macos")]
 use crate::tools::implementations::{GuiClickTool, GuiInspectTool, GuiTypeTool};
 use crate::tools::patterns::ToolPattern;
 use crate::tools::types::{ToolDefinition, ToolUse};
-use crate::tools::{PermissionManager, PermissionRule, ToolExecutor, ToolRegistry};
+use crate::tools::{
+    PermissionManager, PermissionRule, SessionChanges, ToolAuditLogger, ToolExecutor, ToolRegistry, ToolSlots,
+    SUMMARY_MIN_TOOL_CALLS,
+};
 use crate::training::batch_trainer::BatchTrainer;
 
 use super::commands::{handle_command, Command, CommandOutput};
@@ -43,16 +50,18 @@ use super::conversation::ConversationHistory;
 use super::input::InputHandler;
 use super::menu::{Menu, MenuOption};
 use super::output_manager::OutputManager;
-use super::status_bar::StatusBar;
+use super::status_bar::{fit_sections, StatusBar, StatusLine, StatusLineType, SECTION_SEPARATOR};
 use super::tui::TuiRenderer;
 
 // Phase 3.5: Import output macros for global output routing
-use crate::{output_error, output_progress, output_status};
+use crate::{output_error, output_progress, output_startup, output_startup_warning, output_status};
 
 /// User's menu choice for tool confirmation
 #[derive(Debug, Clone)]
 enum ConfirmationChoice {
     ApproveOnce,
+    ApproveExactTask,
+    ApprovePatternTask,
     ApproveExactSession,
     ApprovePatternSession,
     ApproveExactPersistent,
@@ -68,12 +77,25 @@ fn terminal_width() -> usize {
     terminal::size().map(|(w, _)| w as usize).unwrap_or(80)
 }
 
+/// Tool permissions per `[tools.auto_approve]`
+///
+/// The deprecated `features.auto_approve_tools = true` still approves every tool.
+fn tool_permissions(config: &Config) -> PermissionManager {
+    if config.features.auto_approve_tools {
+        tracing::warn!(
+            "features.auto_approve_tools is deprecated - use [tools.auto_approve] instead (auto-approving every tool)"
+        );
+        return PermissionManager::new().with_default_rule(PermissionRule::Allow);
+    }
+    PermissionManager::new().with_auto_approve(&config.tools.auto_approve)
+}
+
 /// REPL operating mode
 #[derive(Debug, Clone, PartialEq)]
 pub enum ReplMode {
     /// Normal mode - all tools require confirmation
     Normal,
-    /// Planning mode - only inspection tools allowed (read, glob, grep, web_fetch)
+    /// Planning mode - only inspection tools allowed (read, glob, grep, list_dir, web_fetch)
     Planning {
         task: String,
         plan_path: PathBuf,
@@ -87,8 +109,24 @@ pub enum ReplMode {
     },
 }
 
+impl ReplMode {
+    /// Identity of the executing plan, used to scope task approvals
+    ///
+    /// Returns None outside of `Executing`, so task approvals end with the task.
+    pub fn task_scope(&self) -> Option<String> {
+        match self {
+            ReplMode::Executing {
+                plan_path,
+                approved_at,
+                ..
+            } => Some(format!("{}@{}", plan_path.display(), approved_at.to_rfc3339())),
+            _ => None,
+        }
+    }
+}
+
 pub struct Repl {
-    _config: Config,
+    config: Config,
     claude_client: ClaudeClient,
     // Daemon client (optional - for daemon-only mode)
     daemon_client: Option<Arc<crate::client::DaemonClient>>,
@@ -98,6 +136,7 @@ pub struct Repl {
     metrics_logger: MetricsLogger,
     // Online learning models
     threshold_validator: ThresholdValidator, // Keep validator separate
+    quality_scorer: Box<dyn QualityScorer>, // Scores teacher responses before learning
     local_generator: Arc<RwLock<crate::local::LocalGenerator>>, // Local generation
     // Training metrics
     training_trends: TrainingTrends,
@@ -108,17 +147,38 @@ pub struct Repl {
     tokenizer: Arc<crate::models::tokenizer::TextTokenizer>,
     // Tool execution
     tool_executor: Arc<tokio::sync::Mutex<ToolExecutor>>,
+    // Tool effects this session (for tool-loop and /done summaries)
+    session_changes: Arc<SessionChanges>,
+    // Concurrent tool limit (shared with the executor, for the status bar)
+    tool_slots: ToolSlots,
+    done_checkpoint: usize,
     tool_definitions: Vec<ToolDefinition>, // Cached tool definitions for Claude API
+    // Tool loop guards ([tools.limits])
+    tool_limits: ToolLimits,
     // UI state
     is_interactive: bool,
     streaming_enabled: bool,
     debug_enabled: bool,
+    // Set by the Ctrl+C handler (shutdown, and cancels a wait for the local model)
+    shutdown_flag: Arc<AtomicBool>,
     // Readline input handler
     input_handler: Option<InputHandler>,
     // Conversation history
     conversation: Arc<RwLock<ConversationHistory>>,
     // REPL mode (normal, planning, executing)
     mode: ReplMode,
+    // Sticky teacher routing (/mode forward)
+    forward_mode: bool,
+    // Images attached with /image, sent with the next query
+    pending_images: Vec<ContentBlock>,
+    /// Send tool definitions with teacher requests (off: `--no-tools`, `/tools off`)
+    tools_enabled: bool,
+    /// One-shot /no-tools or /with-tools override for the query being answered
+    tools_override: Option<bool>,
+    // Query being timed for --profile (None when profiling is off)
+    profile_id: Option<uuid::Uuid>,
+    // Query being recorded in the audit log (None between queries)
+    audit_id: Option<uuid::Uuid>,
     // LoRA fine-tuning (NEW)
     training_coordinator: Arc<TrainingCoordinator>,
     sampler: Arc<RwLock<Sampler>>,
@@ -164,8 +224,8 @@ impl Repl {
             match InputHandler::new() {
                 Ok(handler) => Some(handler),
                 Err(e) => {
-                    output_status!("⚠️  Failed to initialize readline: {}", e);
-                    output_status!("   Falling back to basic input mode");
+                    output_startup_warning!("⚠️  Failed to initialize readline: {}", e);
+                    output_startup_warning!("   Falling back to basic input mode");
                     None
                 }
             }
@@ -174,11 +234,15 @@ impl Repl {
         };
 
         // Initialize tool execution system
-        let mut tool_registry = ToolRegistry::new();
+        let mut tool_registry = ToolRegistry::new().with_disabled(config.tools.disabled.clone());
         tool_registry.register(Box::new(ReadTool));
         tool_registry.register(Box::new(GlobTool));
         tool_registry.register(Box::new(GrepTool));
+        tool_registry.register(Box::new(ListDirTool));
+        tool_registry.register(Box::new(WriteTool));
+        tool_registry.register(Box::new(EditTool));
         tool_registry.register(Box::new(WebFetchTool::new()));
+        tool_registry.register(Box::new(WebSearchTool::new(config.tools.web_search.clone())));
         tool_registry.register(Box::new(BashTool));
 
         // Self-improvement tools
@@ -207,14 +271,8 @@ impl Repl {
             }
         }
 
-        // Create permission manager
-        // Use config.features.auto_approve_tools to determine default rule
-        let default_rule = if config.features.auto_approve_tools {
-            PermissionRule::Allow // Auto-approve: skip confirmations
-        } else {
-            PermissionRule::Ask // Default: require user confirmation
-        };
-        let permissions = PermissionManager::new().with_default_rule(default_rule);
+        // Create permission manager from [tools.auto_approve]
+        let permissions = tool_permissions(&config);
 
         // Determine patterns path
         let patterns_path = dirs::home_dir()
@@ -224,14 +282,20 @@ impl Repl {
         // Create tool executor
         let executor = ToolExecutor::new(tool_registry, permissions, patterns_path)
             .unwrap_or_else(|e| {
-                output_status!("⚠️  Failed to initialize tool executor: {}", e);
-                output_status!("   Tool pattern persistence may not work correctly");
+                output_startup_warning!("⚠️  Failed to initialize tool executor: {}", e);
+                output_startup_warning!("   Tool pattern persistence may not work correctly");
                 // Create fresh registry and try with temp path
-                let mut fallback_registry = ToolRegistry::new();
+                let mut fallback_registry =
+                    ToolRegistry::new().with_disabled(config.tools.disabled.clone());
                 fallback_registry.register(Box::new(ReadTool));
                 fallback_registry.register(Box::new(GlobTool));
                 fallback_registry.register(Box::new(GrepTool));
+                fallback_registry.register(Box::new(ListDirTool));
+                fallback_registry.register(Box::new(WriteTool));
+                fallback_registry.register(Box::new(EditTool));
                 fallback_registry.register(Box::new(WebFetchTool::new()));
+                fallback_registry
+                    .register(Box::new(WebSearchTool::new(config.tools.web_search.clone())));
                 fallback_registry.register(Box::new(BashTool));
                 fallback_registry.register(Box::new(RestartTool::new(session_state_file.clone())));
                 fallback_registry
@@ -250,16 +314,36 @@ impl Repl {
             });
 
         // Add MCP support if configured (graceful - always returns even on error)
-        let executor = executor.with_mcp(&config).await;
+        let executor = executor
+            .with_max_concurrent_tools(config.features.max_concurrent_tools)
+            .with_tool_timeouts(config.tools.timeouts.clone())
+            .with_tool_audit(ToolAuditLogger::new(&config.tools).unwrap_or_else(|e| {
+                tracing::warn!("Tool audit log disabled: {:#}", e);
+                None
+            }))
+            .with_mcp(&config)
+            .await;
+        let session_changes = executor.session_changes();
+        let tool_slots = executor.slots();
 
         let tool_executor = Arc::new(tokio::sync::Mutex::new(executor));
 
         // Only show tool execution log in standalone mode (not daemon mode)
         if is_interactive && !daemon_mode {
-            output_status!(
-                "✓ Tool execution enabled ({} built-in tools)",
-                tool_executor.lock().await.registry().len()
-            );
+            let executor = tool_executor.lock().await;
+            let disabled = executor.registry().disabled_names();
+            if disabled.is_empty() {
+                output_startup!(
+                    "✓ Tool execution enabled ({} built-in tools)",
+                    executor.registry().len()
+                );
+            } else {
+                output_startup!(
+                    "✓ Tool execution enabled ({} built-in tools; disabled: {})",
+                    executor.registry().len(),
+                    disabled.join(", ")
+                );
+            }
         }
 
         let streaming_enabled = config.features.streaming_enabled;
@@ -270,6 +354,14 @@ impl Repl {
             .await
             .list_all_tools()
             .await;
+        let mut enabled_tools: Vec<&str> =
+            tool_definitions.iter().map(|tool| tool.name.as_str()).collect();
+        enabled_tools.sort_unstable();
+        tracing::info!(
+            disabled = ?config.tools.disabled,
+            "Enabled tools: {}",
+            enabled_tools.join(", ")
+        );
 
         // Get global OutputManager and StatusBar (created in main.rs)
         // DO NOT create new instances - that would break stdout control!
@@ -290,8 +382,8 @@ impl Repl {
 
         // Initialize tokenizer (still needed for tool execution)
         let tokenizer = Arc::new(TextTokenizer::default().unwrap_or_else(|e| {
-            output_status!("⚠️  Failed to create tokenizer: {}", e);
-            output_status!("   Active learning tools may not work correctly");
+            output_startup_warning!("⚠️  Failed to create tokenizer: {}", e);
+            output_startup_warning!("   Active learning tools may not work correctly");
             panic!("Cannot create tokenizer")
         }));
 
@@ -302,7 +394,12 @@ impl Repl {
             Arc::clone(&generator_state),
             Some(Arc::new(output_manager.clone())),
         ));
-        let local_generator = Arc::new(RwLock::new(LocalGenerator::new()));
+        let local_generator = Arc::new(RwLock::new(
+            LocalGenerator::new()
+                .with_generation_timeout(config.backend.local_generation_timeout())
+                .with_context_length(config.backend.context_length)
+                .with_confidence_threshold(config.backend.local_confidence_threshold),
+        ));
 
         // Initialize LoRA fine-tuning system
         let training_coordinator = Arc::new(TrainingCoordinator::new(
@@ -316,7 +413,7 @@ impl Repl {
 
         // Only show LoRA log in standalone mode (daemon handles training)
         if is_interactive && !daemon_mode {
-            output_status!("✓ LoRA fine-tuning enabled (weighted training)");
+            output_startup!("✓ LoRA fine-tuning enabled (weighted training)");
         }
 
         // Initialize TUI renderer if enabled (Phase 2: Ratatui interface)
@@ -326,9 +423,10 @@ impl Repl {
                 Arc::new(output_manager.clone()),
                 Arc::new(status_bar.clone()),
                 config.colors.clone(),
+                crate::cli::tui::OutputLayout::from_features(&config.features),
             ) {
                 Ok(renderer) => {
-                    output_status!("✓ TUI mode enabled (Ratatui)");
+                    output_startup!("✓ TUI mode enabled (Ratatui)");
 
                     // Enable buffering for TUI mode (fixes output regression)
                     output_manager.enable_buffering();
@@ -338,8 +436,8 @@ impl Repl {
                     set_global_tui_renderer(renderer);
                 }
                 Err(e) => {
-                    output_status!("⚠️  Failed to initialize TUI: {}", e);
-                    output_status!("   Falling back to standard output mode");
+                    output_startup_warning!("⚠️  Failed to initialize TUI: {}", e);
+                    output_startup_warning!("   Falling back to standard output mode");
                 }
             }
         }
@@ -351,54 +449,70 @@ impl Repl {
             prompt_caching_enabled: true,
         };
 
-        let teacher_provider = match crate::providers::create_provider(&config.teachers) {
+        let teacher_provider = match crate::providers::create_provider(&config.teachers, config.default_max_tokens) {
             Ok(provider) => provider,
             Err(e) => {
-                output_status!("⚠️  Failed to create teacher provider: {}", e);
-                output_status!("   Using default Claude fallback");
+                output_startup_warning!("⚠️  Failed to create teacher provider: {}", e);
+                output_startup_warning!("   Using default Claude fallback");
                 // This shouldn't happen since claude_client already works,
                 // but we need to handle the error case
                 panic!("Cannot create teacher provider")
             }
         };
 
-        let teacher_session = Arc::new(RwLock::new(TeacherSession::with_config(
-            teacher_provider,
-            teacher_config,
-        )));
+        let teacher_session = Arc::new(RwLock::new(
+            TeacherSession::with_config(teacher_provider, teacher_config)
+                .with_circuit_breaker(claude_client.circuit_breaker()),
+        ));
 
         // Only show teacher optimization log in standalone mode
         if is_interactive && !daemon_mode {
-            output_status!("✓ Teacher context optimization enabled (Level 3)");
+            output_startup!("✓ Teacher context optimization enabled (Level 3)");
         }
 
         // daemon_client is now passed as a parameter (checked in main.rs)
         // Show warning only if in standalone mode and interactive
         if daemon_client.is_none() && is_interactive && !daemon_mode {
-            output_status!("⚠️  Daemon not available, using teacher API directly");
+            output_startup_warning!("⚠️  Daemon not available, using teacher API directly");
         }
 
+        let conversation = ConversationHistory::new().with_compaction(&config.compaction);
+        let tool_limits = config.tools.limits;
+        let quality_scorer = config.training.quality_scorer.build();
+
         Self {
-            _config: config,
+            config,
             claude_client,
             daemon_client,
             teacher_session,
             router, // Contains ThresholdRouter now
             metrics_logger,
             threshold_validator,
+            quality_scorer,
             local_generator,
             training_trends: TrainingTrends::new(20), // Track last 20 queries
             models_dir,
             bootstrap_loader,
             tokenizer,
             tool_executor,
+            session_changes,
+            tool_slots,
+            done_checkpoint: 0,
             tool_definitions,
+            tool_limits,
             is_interactive,
             streaming_enabled,
             debug_enabled: false,
+            shutdown_flag: Arc::new(AtomicBool::new(false)),
             input_handler,
-            conversation: Arc::new(RwLock::new(ConversationHistory::new())),
+            conversation: Arc::new(RwLock::new(conversation)),
             mode: ReplMode::Normal,
+            forward_mode: false,
+            pending_images: Vec::new(),
+            tools_enabled: true,
+            tools_override: None,
+            profile_id: None,
+            audit_id: None,
             // LoRA fine-tuning
             training_coordinator,
             sampler,
@@ -437,21 +551,73 @@ impl Repl {
         }
     }
 
-    /// Call teacher with context optimization (helper for MessageRequest → ProviderRequest conversion)
-    async fn call_teacher(&self, request: &MessageRequest) -> Result<crate::claude::types::MessageResponse> {
-        use crate::providers::ProviderRequest;
+    /// Temperature for a teacher request: the request's own, else the
+    /// configured one for the user's query category
+    fn request_temperature(&self, request: &MessageRequest) -> Option<f32> {
+        request.temperature.or_else(|| {
+            request
+                .latest_user_query()
+                .and_then(|query| self.config.temperature.for_query(&query))
+        })
+    }
 
-        // Convert MessageRequest to ProviderRequest
-        let provider_request = ProviderRequest {
-            messages: request.messages.clone(),
-            model: request.model.clone(),
-            max_tokens: request.max_tokens,
-            temperature: None,
-            tools: request.tools.clone(),
-            stream: false,
+    /// Wait for a still-loading local model when `wait_for_local` is on
+    ///
+    /// On timeout or load failure a notice is shown and routing continues
+    /// (forwarding to the teacher). Ctrl+C aborts the query.
+    async fn wait_for_local_model(&self) -> Result<()> {
+        let Some(timeout) = self.config.features.local_wait_timeout() else {
+            return Ok(());
         };
+        if self.bootstrap_loader.state().read().await.is_ready() {
+            return Ok(());
+        }
+
+        if self.is_interactive {
+            self.output_status(format!(
+                "⏳ Waiting up to {}s for the local model (Ctrl+C to cancel)...",
+                timeout.as_secs()
+            ));
+        }
+        let mut last_status = String::new();
+        let outcome = wait_for_ready(
+            self.bootstrap_loader.state(),
+            timeout,
+            || std::future::ready(self.shutdown_flag.load(Ordering::SeqCst)),
+            |state, _| {
+                let status = state.status_message();
+                if self.is_interactive && status != last_status {
+                    self.output_status(format!("   {}", status));
+                    last_status = status;
+                }
+            },
+        )
+        .await;
+
+        match outcome {
+            LocalWaitOutcome::Ready => {}
+            LocalWaitOutcome::Cancelled => {
+                anyhow::bail!("Cancelled while waiting for the local model")
+            }
+            LocalWaitOutcome::TimedOut => self.output_status(format!(
+                "⚠️  Local model still loading after {}s - forwarding to the teacher",
+                timeout.as_secs()
+            )),
+            LocalWaitOutcome::Unavailable(reason) => self.output_status(format!(
+                "⚠️  Local model unavailable ({}) - forwarding to the teacher",
+                reason
+            )),
+        }
+        Ok(())
+    }
+
+    /// Call teacher with context optimization (helper for MessageRequest → ProviderRequest conversion)
+    async fn call_teacher(&self, request: &MessageRequest) -> Result<crate::claude::types::MessageResponse> {
+        let provider_request = self.provider_request(request, false);
+        self.use_temperature_override();
 
         // Send with Level 3 optimization (smart strategies)
+        let _span = self.profile_span("teacher");
         let mut session = self.teacher_session.write().await;
         let response = session.send_message_with_optimization(&provider_request).await?;
 
@@ -461,21 +627,59 @@ impl Repl {
 
     /// Call teacher with streaming and context optimization
     async fn call_teacher_stream(&self, request: &MessageRequest) -> Result<tokio::sync::mpsc::Receiver<Result<crate::providers::StreamChunk>>> {
-        use crate::providers::ProviderRequest;
+        let provider_request = self.provider_request(request, true);
+        self.use_temperature_override();
+
+        // Send with streaming (Level 1 tracking only, no truncation for streaming)
+        let _span = self.profile_span("teacher");
+        let mut session = self.teacher_session.write().await;
+        session.send_message_stream(&provider_request).await
+    }
+
+    /// Clear the /temp override once a teacher request has been built with it
+    fn use_temperature_override(&self) {
+        if self.claude_client.temperature_override().take().is_some() {
+            self.status_bar.update_temperature_override(None);
+        }
+    }
 
-        // Convert MessageRequest to ProviderRequest
-        let provider_request = ProviderRequest {
+    /// Convert a MessageRequest to the ProviderRequest the teacher session sends
+    fn provider_request(&self, request: &MessageRequest, stream: bool) -> crate::providers::ProviderRequest {
+        crate::providers::ProviderRequest {
             messages: request.messages.clone(),
-            model: request.model.clone(),
+            system: self.claude_client.system_prompt_for(request),
+            model: self
+                .claude_client
+                .model_override()
+                .map_or_else(|| request.model.clone(), str::to_string),
             max_tokens: request.max_tokens,
-            temperature: None,
+            temperature: self
+                .claude_client
+                .temperature_override()
+                .get()
+                .or_else(|| self.request_temperature(request)),
             tools: request.tools.clone(),
-            stream: true,
-        };
+            thinking: self.claude_client.thinking_override().get(),
+            stream,
+        }
+    }
 
-        // Send with streaming (Level 1 tracking only, no truncation for streaming)
-        let mut session = self.teacher_session.write().await;
-        session.send_message_stream(&provider_request).await
+    /// Show what the next teacher call would send (/context)
+    ///
+    /// Dry run of the forwarding path: the same request `call_teacher` or
+    /// `call_teacher_stream` would get, passed through the session's context
+    /// optimization without being sent.
+    async fn show_context(&self) {
+        let request = self.with_session_tools(MessageRequest::with_context(
+            self.conversation.read().await.get_messages(),
+        ));
+        let use_streaming = self.streaming_enabled && self.is_interactive;
+        let preview = self
+            .teacher_session
+            .read()
+            .await
+            .preview_context(&self.provider_request(&request, use_streaming));
+        self.output_status(super::context::format_context_preview(&preview));
     }
 
     /// Load local generator from disk or create new one WITH neural models
@@ -500,7 +704,7 @@ impl Repl {
                 match LocalGenerator::load(&generator_path) {
                     Ok(_generator) => {
                         if is_interactive {
-                            output_status!(
+                            output_startup!(
                                 "✓ Loaded local generator from: {}",
                                 generator_path.display()
                             );
@@ -512,8 +716,8 @@ impl Repl {
                     }
                     Err(e) => {
                         if is_interactive {
-                            output_status!("⚠️  Failed to load local generator: {}", e);
-                            output_status!("   Starting with new generator");
+                            output_startup_warning!("⚠️  Failed to load local generator: {}", e);
+                            output_startup_warning!("   Starting with new generator");
                         }
                     }
                 }
@@ -536,7 +740,7 @@ impl Repl {
                 Ok(validator) => {
                     // Only show validator log in standalone mode (daemon has its own)
                     if is_interactive && !daemon_mode {
-                        output_status!(
+                        output_startup!(
                             "✓ Loaded validator with {} validations",
                             validator.stats().total_validations
                         );
@@ -545,7 +749,7 @@ impl Repl {
                 }
                 Err(e) => {
                     if is_interactive {
-                        output_status!("⚠️  Failed to load validator: {}", e);
+                        output_startup_warning!("⚠️  Failed to load validator: {}", e);
                     }
                     ThresholdValidator::new()
                 }
@@ -574,12 +778,14 @@ impl Repl {
 
     /// Output a provider response (dual: buffer + stdout, or TUI only)
     fn output_response(&self, content: impl Into<String> + Clone) {
+        let started = Instant::now();
         let content_str = content.clone().into();
         self.output_manager.write_response(content_str.clone());
         // Only print to stdout if TUI is not active
         if self.is_interactive && !self.is_tui_active() {
             println!("{}", content_str);
         }
+        profile::record_render(started.elapsed());
     }
 
     /// Append to the last provider response (for streaming)
@@ -624,6 +830,28 @@ impl Repl {
         }
     }
 
+    /// Time a stage of the current query (None when it isn't being profiled)
+    fn profile_span(&self, name: impl Into<String>) -> Option<profile::Span> {
+        self.profile_id.map(|id| profile::span(id, name))
+    }
+
+    /// Record where the current query was routed in the audit log
+    fn audit_route(&self, route: &str) {
+        if let Some(id) = self.audit_id {
+            audit::set_route(id, route);
+        }
+    }
+
+    /// Stop timing the current query and print its waterfall
+    fn finish_profile(&mut self) {
+        if let Some(id) = self.profile_id.take() {
+            profile::finish(id);
+            for finished in profile::take_finished() {
+                self.output_status(profile::report(&finished));
+            }
+        }
+    }
+
     /// Update training statistics in status bar
     fn update_training_stats(&self, total_queries: usize, local_percentage: f64, quality: f64) {
         self.status_bar
@@ -719,14 +947,278 @@ impl Repl {
     }
 
     /// Execute tools and re-invoke Claude until no more tool uses
+    ///
+    /// Prints a summary of changed files, commands and errors after runs with
+    /// several tool calls.
     async fn execute_tool_loop(
         &mut self,
         initial_response: crate::claude::MessageResponse,
+        text_shown: bool,
+    ) -> Result<String> {
+        let checkpoint = self.session_changes.checkpoint();
+        let result = self.run_tool_loop(initial_response, text_shown).await;
+
+        // Recap what a tool-heavy run did
+        let summary = self.session_changes.summary_since(checkpoint);
+        if self.is_interactive && summary.tool_calls >= SUMMARY_MIN_TOOL_CALLS {
+            self.output_status(summary.format());
+        }
+
+        result
+    }
+
+    /// Check mode and approval for one tool use, run it and show the result
+    ///
+    /// A blocked or denied call comes back as an error result for the model.
+    async fn run_tool_use(&mut self, tool_use: &ToolUse) -> Result<crate::tools::types::ToolResult> {
+        if self.is_interactive {
+            self.output_tool(&tool_use.name, format!("  → {}", tool_use.name));
+        }
+
+        // Bash dry runs only report the command (always allowed, never prompt)
+        let is_dry_run = self.tool_executor.lock().await.is_dry_run(tool_use);
+
+        // Check mode-based permissions first
+        if !is_dry_run && !Self::is_tool_allowed_in_mode(&tool_use.name, &self.mode) {
+            use crate::tools::types::ToolResult;
+            let error_result = ToolResult::error(
+                tool_use.id.clone(),
+                format!(
+                    "Tool '{}' is not allowed in planning mode.\n\
+                     Reason: This tool can modify system state.\n\
+                     Available tools: read, glob, grep, list_dir, web_fetch, bash (dry_run only)\n\
+                     Type /approve to execute your plan with all tools enabled.",
+                    tool_use.name
+                ),
+            );
+            if self.is_interactive {
+                self.output_tool(&tool_use.name, "    ✗ Blocked by plan mode");
+            }
+            return Ok(error_result);
+        }
+
+        // Generate tool signature for approval checking
+        let working_dir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        let signature = generate_tool_signature(tool_use, &working_dir);
+
+        // Auto-approve certain non-destructive operations
+        let is_auto_approved = {
+            let tool_name = tool_use.name.as_str();
+
+            // Always auto-approve EnterPlanMode (non-destructive mode change)
+            if tool_name == "EnterPlanMode" || tool_name == "enter_plan_mode" {
+                true
+            } else if is_dry_run {
+                true
+            } else {
+                // Auto-approve read-only tools and user interaction tools when in plan mode
+                let is_plan_mode = matches!(self.mode, ReplMode::Planning { .. });
+                let is_readonly_tool = matches!(
+                    tool_name,
+                    "read" | "Read" | "glob" | "Glob" | "grep" | "Grep" | "list_dir" | "web_fetch" | "WebFetch" | "web_search" |
+                    "AskUserQuestion" | "ask_user_question"
+                );
+
+                is_plan_mode && is_readonly_tool
+            }
+        };
+
+        // Check if pre-approved in cache (task approvals follow the executing plan)
+        // or auto-approved by [tools.auto_approve]
+        let (approval_source, allowed_by_config) = {
+            let mut executor = self.tool_executor.lock().await;
+            executor.set_task_scope(self.mode.task_scope());
+            (
+                executor.is_approved(&signature),
+                executor.permissions().rule_for(&tool_use.name) == PermissionRule::Allow,
+            )
+        };
+        let is_auto_approved = is_auto_approved || allowed_by_config;
+
+        match approval_source {
+            ApprovalSource::NotApproved if !is_auto_approved => {
+                // Show prompt if interactive
+                if self.is_interactive {
+                    match self.confirm_tool_execution(tool_use, &signature)? {
+                        ConfirmationResult::ApproveOnce => {
+                            self.output_tool(&tool_use.name, "  ✓ Approved");
+                        }
+                        ConfirmationResult::ApproveExactTask(sig) => {
+                            self.tool_executor.lock().await.approve_exact_task(sig);
+                            self.output_tool(&tool_use.name, "  ✓ Approved (remembered for this task)");
+                        }
+                        ConfirmationResult::ApprovePatternTask(pattern) => {
+                            self.output_tool(&tool_use.name, format!("  ✓ Approved pattern: {} (task)", pattern.pattern));
+                            self.tool_executor.lock().await.approve_pattern_task(pattern);
+                        }
+                        ConfirmationResult::ApproveExactSession(sig) => {
+                            self.tool_executor.lock().await.approve_exact_session(sig);
+                            self.output_tool(&tool_use.name, "  ✓ Approved (remembered for session)");
+                        }
+                        ConfirmationResult::ApprovePatternSession(pattern) => {
+                            self.output_tool(&tool_use.name, format!("  ✓ Approved pattern: {} (session)", pattern.pattern));
+                            self.tool_executor.lock().await.approve_pattern_session(pattern);
+                        }
+                        ConfirmationResult::ApproveExactPersistent(sig) => {
+                            self.tool_executor.lock().await.approve_exact_persistent(sig);
+                            // IMMEDIATE SAVE: Don't wait for checkpoint
+                            if let Err(e) = self.tool_executor.lock().await.save_patterns() {
+                                self.output_status(format!("  ⚠️  Warning: Failed to save pattern: {}", e));
+                                self.output_tool(&tool_use.name, "  ✓ Approved (this session only - save failed)");
+                            } else {
+                                self.output_tool(&tool_use.name, "  ✓ Approved (saved permanently)");
+                            }
+                        }
+                        ConfirmationResult::ApprovePatternPersistent(pattern) => {
+                            let pattern_str = pattern.pattern.clone();
+                            self.tool_executor.lock().await.approve_pattern_persistent(pattern);
+                            // IMMEDIATE SAVE: Don't wait for checkpoint
+                            if let Err(e) = self.tool_executor.lock().await.save_patterns() {
+                                self.output_status(format!("  ⚠️  Warning: Failed to save pattern: {}", e));
+                                self.output_tool(&tool_use.name, format!(
+                                    "  ✓ Approved pattern: {} (this session only - save failed)",
+                                    pattern_str
+                                ));
+                            } else {
+                                self.output_tool(&tool_use.name, format!(
+                                    "  ✓ Approved pattern: {} (saved permanently)",
+                                    pattern_str
+                                ));
+                            }
+                        }
+                        ConfirmationResult::Deny => {
+                            use crate::tools::types::ToolResult;
+                            let error_result = ToolResult::error(
+                                tool_use.id.clone(),
+                                "Tool execution denied by user".to_string(),
+                            );
+                            self.output_tool(&tool_use.name, "    ✗ Denied by user");
+                            return Ok(error_result);
+                        }
+                    }
+                }
+            }
+            ApprovalSource::NotApproved => {
+                // Auto-approved (non-destructive operation)
+                // Continue to execution without prompting
+            }
+            ApprovalSource::SessionExact => {
+                // Already approved, execute silently
+            }
+            ApprovalSource::TaskExact => {
+                if self.is_interactive {
+                    self.output_tool(&tool_use.name, "  ✓ Matched task approval");
+                }
+            }
+            ApprovalSource::TaskPattern(ref id) => {
+                if self.is_interactive {
+                    self.output_tool(&tool_use.name, format!("  ✓ Matched task pattern ({})", &id[..8]));
+                }
+            }
+            ApprovalSource::SessionPattern(ref id) => {
+                if self.is_interactive {
+                    self.output_tool(&tool_use.name, format!("  ✓ Matched session pattern ({})", &id[..8]));
+                }
+            }
+            ApprovalSource::PersistentExact => {
+                if self.is_interactive {
+                    self.output_tool(&tool_use.name, "  ✓ Matched saved approval");
+                }
+            }
+            ApprovalSource::PersistentPattern(ref id) => {
+                if self.is_interactive {
+                    self.output_tool(&tool_use.name, format!("  ✓ Matched saved pattern ({})", &id[..8]));
+                }
+            }
+            ApprovalSource::User | ApprovalSource::Automatic => {
+                // Not returned by the approval cache
+            }
+        }
+        let approval = match approval_source {
+            ApprovalSource::NotApproved if is_auto_approved => ApprovalSource::Automatic,
+            ApprovalSource::NotApproved if self.is_interactive => ApprovalSource::User,
+            other => other,
+        };
+
+        // Create save function that captures necessary state
+        let models_dir = self.models_dir.clone();
+        let router_ref = &self.router;
+        let validator_ref = &self.threshold_validator;
+        let save_fn = || -> Result<()> {
+            if let Some(ref dir) = models_dir {
+                std::fs::create_dir_all(dir)?;
+                router_ref.save(dir.join("threshold_router.json"))?;
+                validator_ref.save(dir.join("threshold_validator.json"))?;
+            }
+            Ok(())
+        };
+
+        let conversation_snapshot = self.conversation.read().await.clone();
+        let tool_span = self.profile_span(format!("tool: {}", tool_use.name));
+        let result = self
+            .tool_executor
+            .lock()
+            .await
+            .runner()
+            .with_approval(approval)
+            .execute_tool(
+                tool_use,
+                Some(&conversation_snapshot),
+                Some(save_fn),
+                None, // TODO: Add training via BootstrapLoader's generator
+                Some(Arc::clone(&self.local_generator)),
+                Some(Arc::clone(&self.tokenizer)),
+                None, // repl_mode (not available in raw mode)
+                None, // plan_content
+            )
+            .await?;
+        drop(tool_span);
+        if let Some(id) = self.audit_id {
+            let files = self.session_changes.files_for(&tool_use.id);
+            audit::record_tool(id, tool_use, result.is_error, files);
+        }
+
+        // Display tool result to user (Phase 1: Visibility)
+        if self.is_interactive {
+            if result.is_error {
+                self.output_tool(&tool_use.name, format!("    ✗ Error: {}", result.content));
+            } else {
+                self.output_tool(&tool_use.name, "    ✓ Success");
+
+                // Show preview of result (first 500 chars)
+                let preview = if result.content.len() > 500 {
+                    format!(
+                        "{}... [truncated, {} chars total]",
+                        crate::text::truncate_chars(&result.content, 500),
+                        result.content.len()
+                    )
+                } else {
+                    result.content.clone()
+                };
+
+                // Indent output for readability
+                for line in preview.lines() {
+                    self.output_tool(&tool_use.name, format!("      {}", line));
+                }
+            }
+            self.output_status(""); // Blank line after each tool
+        }
+
+        Ok(result)
+    }
+
+    /// Tool loop body (see `execute_tool_loop`)
+    ///
+    /// `text_shown` is true when the initial response's text was already
+    /// streamed to the screen.
+    async fn run_tool_loop(
+        &mut self,
+        initial_response: crate::claude::MessageResponse,
+        mut text_shown: bool,
     ) -> Result<String> {
         let mut current_response = initial_response;
         let mut iteration = 0;
-        const MAX_ITERATIONS: u32 = 25; // Higher safety limit (prevents truly infinite loops)
-        const MAX_CONSECUTIVE_SAME_TOOL: usize = 3; // Limit per tool
+        let limits = self.tool_limits;
 
         // Track tool calls to detect infinite loops (signature-based)
         let mut tool_call_history: Vec<(String, String)> = Vec::new();
@@ -734,7 +1226,7 @@ impl Repl {
         // Track consecutive usage per tool
         let mut consecutive_tool_usage: HashMap<String, usize> = HashMap::new();
 
-        while current_response.has_tool_uses() && iteration < MAX_ITERATIONS {
+        while current_response.has_tool_uses() && iteration < limits.max_iterations {
             iteration += 1;
 
             let tool_uses = current_response.tool_uses();
@@ -747,10 +1239,11 @@ impl Repl {
             for tool_use in &tool_uses {
                 let count = consecutive_tool_usage.get(&tool_use.name).unwrap_or(&0);
 
-                if *count >= MAX_CONSECUTIVE_SAME_TOOL {
+                if *count >= limits.max_consecutive_same_tool {
                     let error_msg = format!(
-                        "⚠️  Tool '{}' called {} times consecutively. Possible infinite loop detected.",
-                        tool_use.name, count
+                        "⚠️  Tool '{}' called {} times consecutively (limit: tools.limits.max_consecutive_same_tool = {}). \
+                         Possible infinite loop detected.",
+                        tool_use.name, count, limits.max_consecutive_same_tool
                     );
 
                     if self.is_interactive {
@@ -761,8 +1254,9 @@ impl Repl {
                     // Add explanation to conversation
                     let explanation = format!(
                         "Tool execution stopped: Detected possible infinite loop. \
-                         Tool '{}' was called {} times consecutively without switching to different tools.",
-                        tool_use.name, count
+                         Tool '{}' was called {} times consecutively without switching to different tools \
+                         (limit {}).",
+                        tool_use.name, count, limits.max_consecutive_same_tool
                     );
 
                     return Ok(explanation);
@@ -780,12 +1274,14 @@ impl Repl {
                     .filter(|sig| *sig == &signature)
                     .count();
 
-                if repeat_count >= 2 {
+                if repeat_count >= limits.max_identical_calls {
                     if self.is_interactive {
                         self.output_error(format!(
-                            "⚠️  Warning: Tool '{}' called {} times with same input",
+                            "⚠️  Warning: Tool '{}' called {} times with same input \
+                             (limit: tools.limits.max_identical_calls = {})",
                             tool_use.name,
-                            repeat_count + 1
+                            repeat_count + 1,
+                            limits.max_identical_calls
                         ));
                         self.output_error("⚠️  Possible infinite loop detected. Breaking...");
                     }
@@ -793,9 +1289,10 @@ impl Repl {
                     // Add error message to conversation explaining the issue
                     let error_msg = format!(
                         "Tool execution stopped: Detected infinite loop. \
-                         Tool '{}' was called {} times with the same input.",
+                         Tool '{}' was called {} times with the same input (limit {}).",
                         tool_use.name,
-                        repeat_count + 1
+                        repeat_count + 1,
+                        limits.max_identical_calls
                     );
 
                     return Ok(error_msg);
@@ -807,194 +1304,7 @@ impl Repl {
             // Execute all tool uses
             let mut tool_results = Vec::new();
             for tool_use in &tool_uses {
-                if self.is_interactive {
-                    self.output_tool(&tool_use.name, format!("  → {}", tool_use.name));
-                }
-
-                // Check mode-based permissions first
-                if !Self::is_tool_allowed_in_mode(&tool_use.name, &self.mode) {
-                    use crate::tools::types::ToolResult;
-                    let error_result = ToolResult::error(
-                        tool_use.id.clone(),
-                        format!(
-                            "Tool '{}' is not allowed in planning mode.\n\
-                             Reason: This tool can modify system state.\n\
-                             Available tools: read, glob, grep, web_fetch\n\
-                             Type /approve to execute your plan with all tools enabled.",
-                            tool_use.name
-                        ),
-                    );
-                    tool_results.push(error_result);
-                    if self.is_interactive {
-                        self.output_tool(&tool_use.name, "    ✗ Blocked by plan mode");
-                    }
-                    continue;
-                }
-
-                // Generate tool signature for approval checking
-                let working_dir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
-                let signature = generate_tool_signature(tool_use, &working_dir);
-
-                // Auto-approve certain non-destructive operations
-                let is_auto_approved = {
-                    let tool_name = tool_use.name.as_str();
-
-                    // Always auto-approve EnterPlanMode (non-destructive mode change)
-                    if tool_name == "EnterPlanMode" || tool_name == "enter_plan_mode" {
-                        true
-                    } else {
-                        // Auto-approve read-only tools and user interaction tools when in plan mode
-                        let is_plan_mode = matches!(self.mode, ReplMode::Planning { .. });
-                        let is_readonly_tool = matches!(
-                            tool_name,
-                            "read" | "Read" | "glob" | "Glob" | "grep" | "Grep" | "web_fetch" | "WebFetch" |
-                            "AskUserQuestion" | "ask_user_question"
-                        );
-
-                        is_plan_mode && is_readonly_tool
-                    }
-                };
-
-                // Check if pre-approved in cache
-                let approval_source = self.tool_executor.lock().await.is_approved(&signature);
-
-                match approval_source {
-                    ApprovalSource::NotApproved if !is_auto_approved => {
-                        // Show prompt if interactive
-                        if self.is_interactive {
-                            match self.confirm_tool_execution(tool_use, &signature)? {
-                                ConfirmationResult::ApproveOnce => {
-                                    self.output_tool(&tool_use.name, "  ✓ Approved");
-                                }
-                                ConfirmationResult::ApproveExactSession(sig) => {
-                                    self.tool_executor.lock().await.approve_exact_session(sig);
-                                    self.output_tool(&tool_use.name, "  ✓ Approved (remembered for session)");
-                                }
-                                ConfirmationResult::ApprovePatternSession(pattern) => {
-                                    self.output_tool(&tool_use.name, format!("  ✓ Approved pattern: {} (session)", pattern.pattern));
-                                    self.tool_executor.lock().await.approve_pattern_session(pattern);
-                                }
-                                ConfirmationResult::ApproveExactPersistent(sig) => {
-                                    self.tool_executor.lock().await.approve_exact_persistent(sig);
-                                    // IMMEDIATE SAVE: Don't wait for checkpoint
-                                    if let Err(e) = self.tool_executor.lock().await.save_patterns() {
-                                        self.output_status(format!("  ⚠️  Warning: Failed to save pattern: {}", e));
-                                        self.output_tool(&tool_use.name, "  ✓ Approved (this session only - save failed)");
-                                    } else {
-                                        self.output_tool(&tool_use.name, "  ✓ Approved (saved permanently)");
-                                    }
-                                }
-                                ConfirmationResult::ApprovePatternPersistent(pattern) => {
-                                    let pattern_str = pattern.pattern.clone();
-                                    self.tool_executor.lock().await.approve_pattern_persistent(pattern);
-                                    // IMMEDIATE SAVE: Don't wait for checkpoint
-                                    if let Err(e) = self.tool_executor.lock().await.save_patterns() {
-                                        self.output_status(format!("  ⚠️  Warning: Failed to save pattern: {}", e));
-                                        self.output_tool(&tool_use.name, format!(
-                                            "  ✓ Approved pattern: {} (this session only - save failed)",
-                                            pattern_str
-                                        ));
-                                    } else {
-                                        self.output_tool(&tool_use.name, format!(
-                                            "  ✓ Approved pattern: {} (saved permanently)",
-                                            pattern_str
-                                        ));
-                                    }
-                                }
-                                ConfirmationResult::Deny => {
-                                    use crate::tools::types::ToolResult;
-                                    let error_result = ToolResult::error(
-                                        tool_use.id.clone(),
-                                        "Tool execution denied by user".to_string(),
-                                    );
-                                    tool_results.push(error_result);
-                                    self.output_tool(&tool_use.name, "    ✗ Denied by user");
-                                    continue;
-                                }
-                            }
-                        }
-                    }
-                    ApprovalSource::NotApproved => {
-                        // Auto-approved (non-destructive operation)
-                        // Continue to execution without prompting
-                    }
-                    ApprovalSource::SessionExact => {
-                        // Already approved, execute silently
-                    }
-                    ApprovalSource::SessionPattern(ref id) => {
-                        if self.is_interactive {
-                            self.output_tool(&tool_use.name, format!("  ✓ Matched session pattern ({})", &id[..8]));
-                        }
-                    }
-                    ApprovalSource::PersistentExact => {
-                        if self.is_interactive {
-                            self.output_tool(&tool_use.name, "  ✓ Matched saved approval");
-                        }
-                    }
-                    ApprovalSource::PersistentPattern(ref id) => {
-                        if self.is_interactive {
-                            self.output_tool(&tool_use.name, format!("  ✓ Matched saved pattern ({})", &id[..8]));
-                        }
-                    }
-                }
-
-                // Create save function that captures necessary state
-                let models_dir = self.models_dir.clone();
-                let router_ref = &self.router;
-                let validator_ref = &self.threshold_validator;
-                let save_fn = || -> Result<()> {
-                    if let Some(ref dir) = models_dir {
-                        std::fs::create_dir_all(dir)?;
-                        router_ref.save(dir.join("threshold_router.json"))?;
-                        validator_ref.save(dir.join("threshold_validator.json"))?;
-                    }
-                    Ok(())
-                };
-
-                let conversation_snapshot = self.conversation.read().await.clone();
-                let result = self
-                    .tool_executor
-                    .lock()
-                    .await
-                    .execute_tool(
-                        tool_use,
-                        Some(&conversation_snapshot),
-                        Some(save_fn),
-                        None, // TODO: Add training via BootstrapLoader's generator
-                        Some(Arc::clone(&self.local_generator)),
-                        Some(Arc::clone(&self.tokenizer)),
-                        None, // repl_mode (not available in raw mode)
-                        None, // plan_content
-                    )
-                    .await?;
-
-                // Display tool result to user (Phase 1: Visibility)
-                if self.is_interactive {
-                    if result.is_error {
-                        self.output_tool(&tool_use.name, format!("    ✗ Error: {}", result.content));
-                    } else {
-                        self.output_tool(&tool_use.name, "    ✓ Success");
-
-                        // Show preview of result (first 500 chars)
-                        let preview = if result.content.len() > 500 {
-                            format!(
-                                "{}... [truncated, {} chars total]",
-                                &result.content[..500],
-                                result.content.len()
-                            )
-                        } else {
-                            result.content.clone()
-                        };
-
-                        // Indent output for readability
-                        for line in preview.lines() {
-                            self.output_tool(&tool_use.name, format!("      {}", line));
-                        }
-                    }
-                    self.output_status(""); // Blank line after each tool
-                }
-
-                tool_results.push(result);
+                tool_results.push(self.run_tool_use(tool_use).await?);
             }
 
             // Update consecutive tool usage counters
@@ -1053,7 +1363,7 @@ impl Repl {
                 // Response has both text and tool_use blocks
                 self.conversation.write().await.add_assistant_message(assistant_text.clone());
 
-                if self.is_interactive && !assistant_text.trim().is_empty() {
+                if self.is_interactive && !text_shown && !assistant_text.trim().is_empty() {
                     self.output_response(format!("    Claude: {}", assistant_text));
                 }
             }
@@ -1062,18 +1372,20 @@ impl Repl {
             self.conversation.write().await.add_user_message(tool_result_text);
 
             // Re-invoke teacher with tool results (using optimized context)
-            let request = MessageRequest::with_context(self.conversation.read().await.get_messages())
-                .with_tools(self.tool_definitions.clone());
+            let request = self.with_session_tools(MessageRequest::with_context(
+                self.conversation.read().await.get_messages(),
+            ));
 
             current_response = self.call_teacher(&request).await?;
+            text_shown = false;
         }
 
         // Handle max iterations or completion
-        if iteration >= MAX_ITERATIONS {
+        if iteration >= limits.max_iterations {
             if self.is_interactive {
                 self.output_error(format!(
-                    "⚠️  Warning: Max tool iterations reached ({})",
-                    MAX_ITERATIONS
+                    "⚠️  Warning: Max tool iterations reached ({}, set by tools.limits.max_iterations)",
+                    limits.max_iterations
                 ));
                 self.output_error("⚠️  Claude may be stuck in a loop. Returning last response.");
             }
@@ -1103,12 +1415,60 @@ impl Repl {
         Ok(current_response.text())
     }
 
+    /// Send a request to the teacher and run any tool calls in its answer
+    ///
+    /// Streams when enabled, tool turns included: the text shows as it
+    /// arrives, tool calls come in as complete blocks after it, and the tool
+    /// loop starts once the stream ends.
+    async fn answer_with_teacher(&mut self, request: &MessageRequest, start_time: Instant) -> Result<String> {
+        let use_streaming = self.streaming_enabled && self.is_interactive;
+        let response = if use_streaming {
+            let rx = self.call_teacher_stream(request).await?;
+            self.display_streaming_response(rx).await?
+        } else {
+            let response = self.call_teacher(request).await?;
+            let elapsed = start_time.elapsed().as_millis();
+            if self.is_interactive {
+                self.output_status(format!("✓ Received response ({}ms)", elapsed));
+            }
+            response
+        };
+
+        if response.has_tool_uses() {
+            self.execute_tool_loop(response, use_streaming).await
+        } else {
+            Ok(response.text())
+        }
+    }
+
+    /// Show streamed thinking (per the /thinking display) and clear it
+    fn flush_thinking(&self, thinking_text: &mut String) {
+        if let Some(section) = super::thinking::format_section(
+            thinking_text,
+            super::thinking::display(),
+            false,
+        ) {
+            self.output_manager
+                .add_trait_message(Arc::new(super::messages::StaticMessage::plain(section)));
+        }
+        thinking_text.clear();
+    }
+
     /// Display streaming response character-by-character (handles new StreamChunk format)
+    ///
+    /// Returns the whole response: any thinking, the streamed text, then any
+    /// tool calls, which arrive as `ContentBlockComplete` blocks once their
+    /// input is complete.
     async fn display_streaming_response(
         &mut self,
         mut rx: mpsc::Receiver<Result<crate::generators::StreamChunk>>,
-    ) -> Result<String> {
+    ) -> Result<crate::claude::MessageResponse> {
+        let _span = self.profile_span("teacher (streaming)");
         let mut full_response = String::new();
+        let mut tool_blocks = Vec::new();
+        let mut thinking_blocks = Vec::new();
+        let mut thinking_text = String::new();
+        let mut stop_reason = None;
 
         // Print newline to start response area
         if self.is_interactive {
@@ -1122,7 +1482,12 @@ impl Repl {
 
         while let Some(result) = rx.recv().await {
             match result {
+                Ok(crate::generators::StreamChunk::ThinkingDelta(thinking)) => {
+                    thinking_text.push_str(&thinking);
+                }
                 Ok(crate::generators::StreamChunk::TextDelta(text_chunk)) => {
+                    // Thinking comes first; show it once the answer starts
+                    self.flush_thinking(&mut thinking_text);
                     full_response.push_str(&text_chunk);
 
                     // Update the output buffer with the chunk
@@ -1138,8 +1503,19 @@ impl Repl {
                         let _ = std::io::stdout().flush();
                     }
                 }
-                Ok(crate::generators::StreamChunk::ContentBlockComplete(_block)) => {
-                    // Tool block completed - ignore for now (event loop handles tools)
+                Ok(crate::generators::StreamChunk::OutputTokens(_)) => {}
+                Ok(crate::generators::StreamChunk::StopReason(reason)) => {
+                    stop_reason = Some(reason);
+                }
+                Ok(crate::generators::StreamChunk::ContentBlockComplete(block)) => {
+                    // Text blocks were already streamed as deltas
+                    if block.is_tool_use() {
+                        tool_blocks.push(block);
+                    } else if block.is_thinking() {
+                        // Part of the returned response only: run_tool_loop
+                        // saves tool turns as text, so it isn't sent back
+                        thinking_blocks.push(block);
+                    }
                 }
                 Err(e) => {
                     return Err(e);
@@ -1147,19 +1523,140 @@ impl Repl {
             }
         }
 
+        self.flush_thinking(&mut thinking_text);
+
         // Final newline after response
         if self.is_interactive {
             self.output_status("");
         }
 
-        Ok(full_response)
+        // Tool calls always continue the tool loop, whatever the provider reported
+        let stop_reason = if tool_blocks.is_empty() {
+            stop_reason.unwrap_or_else(|| "end_turn".to_string())
+        } else {
+            "tool_use".to_string()
+        };
+        let mut content = thinking_blocks;
+        if !full_response.is_empty() {
+            content.push(ContentBlock::text(full_response));
+        }
+        content.extend(tool_blocks);
+
+        Ok(crate::claude::MessageResponse {
+            id: String::new(),
+            response_type: "message".to_string(),
+            role: "assistant".to_string(),
+            content,
+            model: String::new(),
+            stop_reason: Some(stop_reason),
+            usage: None,
+        })
+    }
+
+    /// Summarize older turns once the context passes the compaction threshold
+    async fn auto_compact(&self) {
+        use crate::cli::conversation::{log_compaction, ConversationCompactor};
+        use crate::generators::claude::ClaudeGenerator;
+
+        let usage = {
+            let conversation = self.conversation.read().await;
+            if !conversation.should_compact() {
+                return;
+            }
+            conversation.context_usage_percent()
+        };
+        tracing::info!("Context at {:.0}% - auto-compacting conversation", usage * 100.0);
+        self.output_status("🗜️  Context is nearly full - summarizing older turns...");
+
+        let generator = ClaudeGenerator::new(Arc::new(self.claude_client.clone()));
+        let compactor = ConversationCompactor::with_keep_turns(&generator, self.config.compaction.keep_turns);
+        let snapshot = self.conversation.read().await.snapshot();
+
+        match compactor.summarize(&snapshot).await {
+            Ok(Some((summarized, summary))) => {
+                let report = self.conversation.write().await.apply_compaction(summarized, &summary);
+                if let Some(report) = report {
+                    log_compaction(&report);
+                    self.output_status(format!(
+                        "🗜️  Compacted {} older messages into a summary (~{} tokens reclaimed).",
+                        summarized.len(),
+                        report.reclaimed_tokens()
+                    ));
+                }
+            }
+            Ok(None) => {}
+            Err(e) => {
+                tracing::warn!("Auto-compaction failed: {}", e);
+                self.output_error(format!(
+                    "Auto-compaction failed ({}) - older turns will be trimmed instead.",
+                    e
+                ));
+            }
+        }
+    }
+
+    /// Summarize all but the last `turns` exchanges with the teacher (/compact)
+    async fn compact_conversation(&self, turns: usize) -> Result<()> {
+        use crate::cli::conversation::{log_compaction, ConversationCompactor};
+        use crate::generators::claude::ClaudeGenerator;
+
+        let (snapshot, turns_before) = {
+            let conversation = self.conversation.read().await;
+            (conversation.snapshot(), conversation.exchange_count())
+        };
+
+        let generator = ClaudeGenerator::new(Arc::new(self.claude_client.clone()));
+        let compactor = ConversationCompactor::new(&generator);
+        let Some((summarized, summary)) = compactor.summarize_turns(&snapshot, turns).await? else {
+            self.output_status(format!(
+                "Nothing to compact - the conversation has no more than {} turn{}.",
+                turns,
+                if turns == 1 { "" } else { "s" }
+            ));
+            return Ok(());
+        };
+
+        if let Some(report) = self.conversation.write().await.apply_compaction(summarized, &summary) {
+            log_compaction(&report);
+            self.output_status(super::commands::compact_status(turns_before, turns, &report));
+        }
+        Ok(())
     }
 
     /// Restore conversation from a saved state
     pub fn restore_conversation(&mut self, history: ConversationHistory) {
+        let history = history.with_compaction(&self.config.compaction);
         self.conversation = Arc::new(RwLock::new(history));
     }
 
+    /// Autosave the conversation after each completed turn
+    pub async fn enable_autosave(&self, autosave: Arc<crate::cli::autosave::ConversationAutosave>) {
+        self.conversation.write().await.set_autosave(Some(autosave));
+    }
+
+    /// Send (or stop sending) tool definitions with teacher requests
+    ///
+    /// Plan mode always sends tools, since planning needs the read-only ones.
+    pub fn set_tools_enabled(&mut self, enabled: bool) {
+        self.tools_enabled = enabled;
+        self.status_bar.update_tools_enabled(enabled);
+    }
+
+    /// Attach tool definitions to `request` unless tools are off for the session
+    ///
+    /// A one-shot override (/no-tools, /with-tools) wins over the session toggle.
+    fn with_session_tools(&self, request: MessageRequest) -> MessageRequest {
+        let enabled = self.tools_override.unwrap_or(
+            self.tools_enabled
+                || matches!(self.mode, ReplMode::Planning { .. } | ReplMode::Executing { .. }),
+        );
+        if enabled {
+            request.with_tools(self.tool_definitions.clone())
+        } else {
+            request
+        }
+    }
+
     /// Run REPL with an optional initial prompt
     pub async fn run_with_initial_prompt(&mut self, initial_prompt: Option<String>) -> Result<()> {
         if let Some(prompt) = initial_prompt {
@@ -1172,6 +1669,7 @@ impl Repl {
             if self.is_interactive {
                 self.output_response(&response);
             }
+            self.finish_profile();
         }
 
         // Continue with normal REPL loop
@@ -1204,7 +1702,10 @@ impl Repl {
         // Create generators
         use crate::generators::{claude::ClaudeGenerator, qwen::QwenGenerator};
         let claude_gen: Arc<dyn crate::generators::Generator> =
-            Arc::new(ClaudeGenerator::new(Arc::new(self.claude_client.clone())));
+            Arc::new(
+                ClaudeGenerator::new(Arc::new(self.claude_client.clone()))
+                    .with_temperatures(self.config.temperature.clone()),
+            );
         let qwen_gen: Arc<dyn crate::generators::Generator> = Arc::new(QwenGenerator::new(
             Arc::clone(&self.local_generator),
             Arc::clone(&self.tokenizer),
@@ -1225,6 +1726,7 @@ impl Repl {
             Arc::new(self.router.clone()),
             generator_state,
             self.tool_definitions.clone(),
+            self.tools_enabled,
             Arc::clone(&self.tool_executor),
             tui_renderer,
             Arc::new(self.output_manager.clone()),
@@ -1234,6 +1736,14 @@ impl Repl {
             Arc::clone(&self.tokenizer),
             self.daemon_client.clone(),
             mode,
+            Arc::clone(&self.session_changes),
+            self.tool_slots.clone(),
+            self.config.temperature.clone(),
+            self.claude_client.thinking_override(),
+            self.claude_client.temperature_override(),
+            self.config.features.local_wait_timeout(),
+            self.config.backend.local_generation_timeout(),
+            self.config.compaction.clone(),
         );
 
         // Run the event loop
@@ -1245,7 +1755,10 @@ impl Repl {
             // Fancy startup for interactive mode
             self.output_status("Shammah v0.1.0 - Constitutional AI Proxy");
             self.output_status("Using API key from: ~/.shammah/config.toml ✓");
-            self.output_status("Loaded crisis detection keywords ✓");
+            self.output_status(format!(
+                "Loaded {} crisis detection rules ✓",
+                self.router.crisis_detector().rule_count()
+            ));
             self.output_status("Online learning: ENABLED (threshold models) ✓");
             self.output_status("");
             self.output_status("Ready. Type /help for commands.");
@@ -1256,7 +1769,7 @@ impl Repl {
         }
 
         // Register Ctrl+C handler for graceful shutdown
-        let shutdown_flag = Arc::new(AtomicBool::new(false));
+        let shutdown_flag = Arc::clone(&self.shutdown_flag);
         let flag_clone = shutdown_flag.clone();
 
         ctrlc::set_handler(move || {
@@ -1402,6 +1915,9 @@ impl Repl {
                 input.trim().to_string()
             };
 
+            // Any input interrupts a response still being spoken
+            crate::cli::tts::stop();
+
             if input.is_empty() {
                 continue;
             }
@@ -1421,17 +1937,203 @@ impl Repl {
                                 self.output_status(format!("⚠️  Failed to save history: {}", e));
                             }
                         }
-                        if self.is_interactive {
-                            self.output_status("Models saved. Goodbye!");
+                        if self.is_interactive {
+                            self.output_status("Models saved. Goodbye!");
+                        }
+                        break;
+                    }
+                    Command::Clear => {
+                        self.conversation.write().await.clear();
+                        self.output_status("Conversation history cleared. Starting fresh.");
+                        if self.is_interactive {
+                            self.output_status("");
+                            self.print_status_line().await;
+                        }
+                        continue;
+                    }
+                    Command::Sampling => {
+                        self.output_status(self.config.temperature.format_mapping());
+                        continue;
+                    }
+                    Command::ThinkStatus => {
+                        self.output_status(super::commands::thinking_status(
+                            self.claude_client.thinking_override().get(),
+                        ));
+                        continue;
+                    }
+                    Command::Think(level) => {
+                        self.claude_client.thinking_override().set(level);
+                        self.output_status(super::commands::thinking_status(level));
+                        continue;
+                    }
+                    Command::TempStatus => {
+                        self.output_status(super::commands::temperature_status(
+                            self.claude_client.temperature_override().get(),
+                        ));
+                        continue;
+                    }
+                    Command::Temp(value) => {
+                        match self.claude_client.temperature_override().set(value) {
+                            Ok(()) => {
+                                self.status_bar.update_temperature_override(value);
+                                self.output_status(super::commands::temperature_status(value));
+                            }
+                            Err(e) => self.output_error(e.to_string()),
+                        }
+                        continue;
+                    }
+                    Command::Vary(count) => {
+                        if let Err(e) = self.vary_last_response(count).await {
+                            self.output_error(format!("Failed to generate variants: {}", e));
+                        }
+                        continue;
+                    }
+                    Command::Compact(turns) => {
+                        let turns = turns.unwrap_or(self.config.compaction.keep_turns);
+                        if let Err(e) = self.compact_conversation(turns).await {
+                            self.output_error(format!("Failed to compact the conversation: {}", e));
+                        }
+                        continue;
+                    }
+                    Command::Summarize { ref path, detail } => {
+                        if let Err(e) = self.summarize_file(path, detail).await {
+                            self.output_error(format!("Failed to summarize {}: {}", path, e));
+                        }
+                        continue;
+                    }
+                    Command::Image(ref path) => {
+                        match ContentBlock::image_file(path) {
+                            Ok(image) => {
+                                self.pending_images.push(image);
+                                self.output_status(format!(
+                                    "📎 Attached {} ({} pending) - sent to the teacher with your next query",
+                                    path,
+                                    self.pending_images.len()
+                                ));
+                            }
+                            Err(e) => self.output_error(format!("{:#}", e)),
+                        }
+                        continue;
+                    }
+                    Command::ImageClear => {
+                        let count = std::mem::take(&mut self.pending_images).len();
+                        self.output_status(format!("📎 Dropped {} pending image(s)", count));
+                        continue;
+                    }
+                    Command::Pin(ref note) => {
+                        let mut conversation = self.conversation.write().await;
+                        let pinned = match note {
+                            Some(note) => Some(conversation.pin_note(note.clone())),
+                            None => conversation.pin_last_message(),
+                        };
+                        drop(conversation);
+                        match pinned {
+                            Some(number) => self.output_status(format!(
+                                "Pinned #{} - it stays in context until /unpin {}",
+                                number, number
+                            )),
+                            None => self.output_status("Nothing to pin yet."),
+                        }
+                        continue;
+                    }
+                    Command::Context => {
+                        self.show_context().await;
+                        continue;
+                    }
+                    Command::Export { ref path } => {
+                        let messages = self.conversation.read().await.snapshot();
+                        match super::export::export_markdown(&messages, path.as_deref()) {
+                            Ok(Some(path)) => self.output_status(format!(
+                                "📝 Exported {} messages to {}",
+                                messages.len(),
+                                path.display()
+                            )),
+                            Ok(None) => {
+                                self.output_status("Nothing to export yet - the conversation is empty.")
+                            }
+                            Err(e) => self.output_error(format!("Export failed: {:#}", e)),
+                        }
+                        continue;
+                    }
+                    Command::Pins => {
+                        let list = super::conversation::format_pins(
+                            self.conversation.read().await.pins(),
+                        );
+                        self.output_status(list);
+                        continue;
+                    }
+                    Command::Unpin(number) => {
+                        let removed = self.conversation.write().await.unpin(number);
+                        match removed {
+                            Some(_) => self.output_status(format!("Removed pin #{}.", number)),
+                            None => self.output_status(format!("No pin #{} (see /pins).", number)),
                         }
-                        break;
+                        continue;
                     }
-                    Command::Clear => {
-                        self.conversation.write().await.clear();
-                        self.output_status("Conversation history cleared. Starting fresh.");
-                        if self.is_interactive {
-                            self.output_status("");
-                            self.print_status_line().await;
+                    Command::Help(ref topic) => {
+                        // Plain text unless the TUI can render ANSI styling
+                        let color = self.is_tui_active();
+                        self.output_status(crate::cli::help::format_help(topic.as_deref(), color));
+                        continue;
+                    }
+                    Command::HistoryClear => {
+                        let result = if self.is_tui_active() {
+                            use crate::cli::global_output::get_global_tui_renderer;
+                            let tui_renderer = get_global_tui_renderer();
+                            let mut tui_lock = tui_renderer.lock().unwrap();
+                            match *tui_lock {
+                                Some(ref mut tui) => tui.clear_history(),
+                                None => Ok(()),
+                            }
+                        } else {
+                            match self.input_handler {
+                                Some(ref mut handler) => handler.clear_history(),
+                                None => Ok(()),
+                            }
+                        };
+                        match result {
+                            Ok(()) => self.output_status("Input history cleared."),
+                            Err(e) => self.output_error(format!("Failed to clear history: {}", e)),
+                        }
+                        continue;
+                    }
+                    Command::ReplayTool(number) => {
+                        let replay = self.tool_executor.lock().await.recent_calls().replay(number);
+                        match replay {
+                            Some(tool_use) => {
+                                self.output_status(format!(
+                                    "↻ Replaying #{}: {}",
+                                    number,
+                                    crate::tools::recent_calls::describe(&tool_use)
+                                ));
+                                if let Err(e) = self.run_tool_use(&tool_use).await {
+                                    self.output_error(format!("Replay failed: {}", e));
+                                }
+                            }
+                            None => self.output_status(format!(
+                                "No tool call #{} (see /replay-tool list).",
+                                number
+                            )),
+                        }
+                        continue;
+                    }
+                    Command::ReplayToolList => {
+                        let list = self.tool_executor.lock().await.recent_calls().format_list();
+                        self.output_status(list);
+                        continue;
+                    }
+                    Command::Watch(_) | Command::WatchStatus | Command::WatchStop => {
+                        // Watching needs input and queries to run concurrently
+                        self.output_status("/watch needs the TUI (run without --raw).");
+                        continue;
+                    }
+                    Command::BenchmarkTools { network } => {
+                        let executor = self.tool_executor.lock().await;
+                        match crate::tools::self_test::run_self_test(executor.registry(), network).await {
+                            Ok(checks) => {
+                                self.output_status(crate::tools::self_test::format_report(&checks))
+                            }
+                            Err(e) => self.output_error(format!("Tool self-test failed: {}", e)),
                         }
                         continue;
                     }
@@ -1472,11 +2174,105 @@ impl Repl {
                         self.handle_feedback(1.0, note.clone()).await?;
                         continue;
                     }
-                    // Local model query command
+                    // Explicit routing commands
                     Command::Local { ref query } => {
                         self.handle_local_query(query).await?;
                         continue;
                     }
+                    Command::Forward { ref query } => {
+                        self.handle_forward_query(query).await;
+                        continue;
+                    }
+                    Command::Retry => {
+                        self.handle_retry().await;
+                        continue;
+                    }
+                    Command::ModelInfo => {
+                        let info = match &self.daemon_client {
+                            Some(daemon_client) => daemon_client.model_info().await,
+                            None => Ok(crate::local::model_info(
+                                self.bootstrap_loader.state(),
+                                &self.local_generator,
+                            )
+                            .await),
+                        };
+                        match info {
+                            Ok(info) => self.output_status(info.format()),
+                            Err(e) => self.output_error(format!("Failed to get model info: {}", e)),
+                        }
+                        continue;
+                    }
+                    Command::Undo => {
+                        let mut conversation = self.conversation.write().await;
+                        let removed = conversation.pop_last_exchange();
+                        let (last_query, last_response) = conversation.last_exchange().unzip();
+                        let remaining = conversation.exchange_count();
+                        drop(conversation);
+
+                        if removed.is_some() {
+                            self.last_query = last_query;
+                            self.last_response = last_response;
+                        }
+                        self.output_status(super::commands::undo_status(removed.as_deref(), remaining));
+                        continue;
+                    }
+                    Command::Done => {
+                        let summary = self.session_changes.summary_since(self.done_checkpoint);
+                        self.done_checkpoint = self.session_changes.checkpoint();
+                        if summary.is_empty() {
+                            self.output_status("No tool calls since the last /done.");
+                        } else {
+                            self.output_status(summary.format());
+                        }
+                        self.tool_executor.lock().await.clear_task_approvals();
+                        if matches!(self.mode, ReplMode::Planning { .. } | ReplMode::Executing { .. }) {
+                            self.mode = ReplMode::Normal;
+                            self.output_status("✅ Exited plan mode. Returned to normal mode.");
+                        }
+                        continue;
+                    }
+                    Command::Tools => {
+                        let listing = super::commands::tools_listing(&*self.tool_executor.lock().await, |name| {
+                            Self::is_tool_allowed_in_mode(name, &self.mode)
+                        })
+                        .await;
+                        self.output_status(listing);
+                        continue;
+                    }
+                    Command::ToolsOverride { ref query, enabled } => {
+                        self.tools_override = Some(enabled);
+                        self.handle_routed_query(query, self.forward_mode).await;
+                        self.tools_override = None;
+                        continue;
+                    }
+                    Command::ToolsToggle(enabled) => {
+                        self.set_tools_enabled(enabled);
+                        self.output_status(if enabled {
+                            "🔧 Tools enabled for this session."
+                        } else {
+                            "🔧 Tools disabled for this session (plan mode still uses tools)."
+                        });
+                        continue;
+                    }
+                    Command::DryRun(enabled) => {
+                        self.tool_executor.lock().await.set_bash_dry_run(enabled);
+                        self.output_status(super::commands::dry_run_status(enabled));
+                        continue;
+                    }
+                    Command::DryRunStatus => {
+                        let enabled = self.tool_executor.lock().await.bash_dry_run();
+                        self.output_status(super::commands::dry_run_status(enabled));
+                        continue;
+                    }
+                    Command::ForwardMode(enabled) => {
+                        self.forward_mode = enabled;
+                        self.output_status(if enabled {
+                            "→ Forward mode: every query goes to the teacher (/mode auto to undo)"
+                        } else {
+                            "→ Auto mode: queries are routed normally"
+                        });
+                        continue;
+                    }
                     _ => {
                         let output = handle_command(
                             command,
@@ -1499,6 +2295,10 @@ impl Repl {
             match self.process_query(&input).await {
                 Ok(response) => {
                     self.output_response(&response);
+                    self.finish_profile();
+                    crate::cli::tts::speak(&response);
+                    crate::cli::code_blocks::set_latest(&response);
+                    self.auto_compact().await;
                     if self.is_interactive {
                         self.output_status("");
                         self.print_status_line().await;
@@ -1506,6 +2306,7 @@ impl Repl {
                 }
                 Err(e) => {
                     self.output_error(format!("Error: {}", e));
+                    self.finish_profile();
                     if self.is_interactive {
                         self.output_status("");
                         self.print_status_line().await;
@@ -1565,12 +2366,27 @@ impl Repl {
         self.output_status("");
 
         // Build menu options
-        let options = vec![
-            MenuOption::with_description(
-                "Yes (once only)",
-                "Execute this time, ask again next time",
-                ConfirmationChoice::ApproveOnce,
-            ),
+        let mut options = vec![MenuOption::with_description(
+            "Yes (once only)",
+            "Execute this time, ask again next time",
+            ConfirmationChoice::ApproveOnce,
+        )];
+
+        // Task-scoped approvals only make sense while executing a plan
+        if matches!(self.mode, ReplMode::Executing { .. }) {
+            options.push(MenuOption::with_description(
+                "Yes, and remember exact command for this task",
+                "Won't ask again for this exact command until the task is done",
+                ConfirmationChoice::ApproveExactTask,
+            ));
+            options.push(MenuOption::with_description(
+                "Yes, and remember pattern for this task",
+                "Won't ask again for similar commands until the task is done",
+                ConfirmationChoice::ApprovePatternTask,
+            ));
+        }
+
+        options.extend([
             MenuOption::with_description(
                 "Yes, and remember exact command for this session",
                 "Won't ask again for this exact command in this session",
@@ -1596,18 +2412,25 @@ impl Repl {
                 "Block this tool execution",
                 ConfirmationChoice::Deny,
             ),
-        ];
+        ]);
 
         // Show menu
-        let choice = Menu::select(
-            "Do you want to proceed?",
-            options,
-            Some("[↑↓ or j/k to move, Enter to select, or type 1-6]"),
-        )?;
+        let hint = format!(
+            "[↑↓ or j/k to move, Enter to select, or type 1-{}]",
+            options.len()
+        );
+        let choice = Menu::select("Do you want to proceed?", options, Some(&hint))?;
 
         // Convert choice to ConfirmationResult
         match choice {
             ConfirmationChoice::ApproveOnce => Ok(ConfirmationResult::ApproveOnce),
+            ConfirmationChoice::ApproveExactTask => {
+                Ok(ConfirmationResult::ApproveExactTask(signature.clone()))
+            }
+            ConfirmationChoice::ApprovePatternTask => {
+                let pattern = self.build_pattern_from_signature(signature)?;
+                Ok(ConfirmationResult::ApprovePatternTask(pattern))
+            }
             ConfirmationChoice::ApproveExactSession => {
                 Ok(ConfirmationResult::ApproveExactSession(signature.clone()))
             }
@@ -1626,6 +2449,108 @@ impl Repl {
         }
     }
 
+    /// Handle /vary [n]: regenerate the last response n times and let the
+    /// user pick one, which replaces it in the conversation
+    async fn vary_last_response(&mut self, count: usize) -> Result<()> {
+        use crate::cli::vary::{generate_variants, preview, variant_label, MAX_VARIANTS};
+        use crate::generators::{claude::ClaudeGenerator, qwen::QwenGenerator, Generator};
+
+        let Some(context) = self.conversation.read().await.last_prompt_context() else {
+            self.output_status("Nothing to vary yet - send a prompt first.");
+            return Ok(());
+        };
+        let count = count.min(MAX_VARIANTS);
+
+        // Vary with the model that would answer this prompt
+        let query = context.last().map(|message| message.text()).unwrap_or_default();
+        let generator_ready = self.bootstrap_loader.state().read().await.is_ready();
+        let use_local = !self.forward_mode
+            && matches!(
+                self.router.route_with_generator_check(&query, generator_ready),
+                RouteDecision::Local { .. }
+            );
+        let generator: Box<dyn Generator> = if use_local {
+            Box::new(QwenGenerator::new(
+                Arc::clone(&self.local_generator),
+                Arc::clone(&self.tokenizer),
+                None,
+            ))
+        } else {
+            Box::new(
+                ClaudeGenerator::new(Arc::new(self.claude_client.clone()))
+                    .with_temperatures(self.config.temperature.clone()),
+            )
+        };
+
+        let mut variants = generate_variants(generator.as_ref(), context, count, |number| {
+            self.output_status(format!("🎲 Generating variant {}/{}...", number, count));
+        })
+        .await?;
+
+        let mut options: Vec<MenuOption<Option<usize>>> = variants
+            .iter()
+            .enumerate()
+            .map(|(i, text)| MenuOption::with_description(variant_label(i + 1, text), preview(text), Some(i)))
+            .collect();
+        options.push(MenuOption::new("Keep the current response", None));
+
+        let hint = format!(
+            "[↑↓ or j/k to move, Enter to select, or type 1-{}]",
+            options.len()
+        );
+        match Menu::select("Pick a response:", options, Some(&hint))? {
+            Some(index) => {
+                let chosen = variants.swap_remove(index);
+                self.conversation.write().await.replace_last_response(chosen.clone());
+                self.last_response = Some(chosen.clone());
+                self.output_status(format!("✓ Using variant {}", index + 1));
+                self.output_response(chosen);
+            }
+            None => self.output_status("Kept the current response."),
+        }
+        Ok(())
+    }
+
+    /// Handle /summarize <path>: summarize a file with the local model when
+    /// it's ready and the file is short, otherwise with the teacher
+    async fn summarize_file(&mut self, path: &str, detail: crate::cli::summarize::SummaryDetail) -> Result<()> {
+        use crate::generators::{claude::ClaudeGenerator, qwen::QwenGenerator};
+
+        let local = if !self.forward_mode && self.bootstrap_loader.state().read().await.is_ready() {
+            Some(QwenGenerator::new(
+                Arc::clone(&self.local_generator),
+                Arc::clone(&self.tokenizer),
+                None,
+            ))
+        } else {
+            None
+        };
+        let teacher = ClaudeGenerator::new(Arc::new(self.claude_client.clone()))
+            .with_temperatures(self.config.temperature.clone());
+
+        self.output_status(format!("📄 Summarizing {}...", path));
+        let summary = crate::cli::summarize::summarize_file(
+            path,
+            detail,
+            local.as_ref().map(|g| g as &dyn crate::generators::Generator),
+            &teacher,
+            |chunk, chunks| {
+                if chunks > 1 {
+                    self.output_status(format!("📄 Summarizing part {}/{}...", chunk, chunks));
+                }
+            },
+        )
+        .await?;
+
+        let parts = match summary.chunks {
+            1 => String::new(),
+            chunks => format!(", {} parts", chunks),
+        };
+        self.output_status(format!("📄 Summary of {} ({}{})", path, summary.generator, parts));
+        self.output_response(summary.text);
+        Ok(())
+    }
+
     /// Helper to read a single line choice
     fn read_choice(&mut self, prompt: &str) -> Result<Option<String>> {
         if let Some(ref mut handler) = self.input_handler {
@@ -1755,8 +2680,8 @@ impl Repl {
                 true
             }
             ReplMode::Planning { .. } => {
-                // Only inspection tools allowed
-                matches!(tool_name, "read" | "glob" | "grep" | "web_fetch")
+                // Only inspection tools allowed; write, edit and bash wait for plan approval
+                matches!(tool_name, "read" | "glob" | "grep" | "list_dir" | "web_fetch" | "web_search")
             }
         }
     }
@@ -1787,6 +2712,22 @@ impl Repl {
                     self.output_status(format!("  File: {}", path));
                 }
             }
+            "write" => {
+                if let Some(path) = tool_use.input["file_path"].as_str() {
+                    self.output_status(format!("  File: {}", path));
+                }
+                if let Some(content) = tool_use.input["content"].as_str() {
+                    self.output_status(format!("  Lines: {}", content.lines().count()));
+                }
+            }
+            "edit" => {
+                if let Some(path) = tool_use.input["file_path"].as_str() {
+                    self.output_status(format!("  File: {}", path));
+                }
+                if tool_use.input["replace_all"].as_bool().unwrap_or(false) {
+                    self.output_status("  Replace: all occurrences".to_string());
+                }
+            }
             "web_fetch" => {
                 if let Some(url) = tool_use.input["url"].as_str() {
                     self.output_status(format!("  URL: {}", url));
@@ -1795,6 +2736,11 @@ impl Repl {
                     self.output_status(format!("  Prompt: {}", prompt));
                 }
             }
+            "web_search" => {
+                if let Some(query) = tool_use.input["query"].as_str() {
+                    self.output_status(format!("  Query: {}", query));
+                }
+            }
             "grep" => {
                 if let Some(pattern) = tool_use.input["pattern"].as_str() {
                     self.output_status(format!("  Pattern: {}", pattern));
@@ -1808,6 +2754,10 @@ impl Repl {
                     self.output_status(format!("  Pattern: {}", pattern));
                 }
             }
+            "list_dir" => {
+                let path = tool_use.input.get("path").and_then(|v| v.as_str()).unwrap_or(".");
+                self.output_status(format!("  Path: {}", path));
+            }
             "save_and_exec" => {
                 if let Some(command) = tool_use.input["command"].as_str() {
                     self.output_status(format!("  Command: {}", command));
@@ -2210,74 +3160,156 @@ impl Repl {
         let quality_avg = self.training_trends.avg_quality();
         let similarity_avg = self.training_trends.avg_similarity();
 
-        // Build mode indicator
-        let mode_indicator = match &self.mode {
-            ReplMode::Normal => String::new(),
-            ReplMode::Planning { .. } => {
-                format!(" {}", "[PLANNING MODE - Inspection Only]".blue().bold())
-            }
-            ReplMode::Executing { .. } => format!(" {}", "[EXECUTING PLAN]".green().bold()),
-        };
-
-        // Build single-line status string with training effectiveness and conversation context
-        let turn_count = self.conversation.read().await.turn_count();
-        let context_indicator = if turn_count > 0 {
-            format!(" | Context: {} turns", turn_count)
-        } else {
-            String::new()
-        };
-
-        let status = if self.training_trends.measurement_count() > 0 {
+        // Sections, in display order; narrow terminals drop the least
+        // important first (the mode indicator always stays)
+        let mut sections = Vec::new();
+        match &self.mode {
+            ReplMode::Normal => {}
+            ReplMode::Planning { .. } => sections.push(StatusLine {
+                line_type: StatusLineType::Mode,
+                content: "[PLANNING MODE - Inspection Only]".to_string(),
+            }),
+            ReplMode::Executing { .. } => sections.push(StatusLine {
+                line_type: StatusLineType::Mode,
+                content: "[EXECUTING PLAN]".to_string(),
+            }),
+        }
+
+        let training = if self.training_trends.measurement_count() > 0 {
             format!(
-                "{}Training: {} queries | Local: {:.0}% | Success: {:.0}% | Quality: {:.2} | Similarity: {:.2} | Confidence: {:.2}{}",
-                mode_indicator,
+                "Training: {} queries | Local: {:.0}% | Success: {:.0}% | Quality: {:.2} | Similarity: {:.2} | Confidence: {:.2}",
                 router_stats.total_queries,
                 local_pct,
                 success_pct,
                 quality_avg,
                 similarity_avg,
                 router_stats.confidence_threshold,
-                context_indicator
             )
         } else {
             // Fallback if no training data yet
             format!(
-                "{}Training: {} queries | Local: {:.0}% | Success: {:.0}% | Confidence: {:.2} | Approval: {:.0}%{}",
-                mode_indicator,
+                "Training: {} queries | Local: {:.0}% | Success: {:.0}% | Confidence: {:.2} | Approval: {:.0}%",
                 router_stats.total_queries,
                 local_pct,
                 success_pct,
                 router_stats.confidence_threshold,
                 validator_stats.approval_rate * 100.0,
-                context_indicator
             )
         };
+        sections.push(StatusLine {
+            line_type: StatusLineType::TrainingStats,
+            content: training,
+        });
 
-        // Truncate to terminal width if needed
-        let width = terminal_width();
-        let truncated = if status.len() > width {
-            format!("{}...", &status[..width.saturating_sub(3)])
-        } else {
-            status
-        };
+        // With the TUI up, the status bar already shows the tools line
+        if !self.tools_enabled && !self.is_tui_active() {
+            sections.push(StatusLine {
+                line_type: StatusLineType::Custom("tools".to_string()),
+                content: "Tools: off".to_string(),
+            });
+        }
+
+        let turn_count = self.conversation.read().await.turn_count();
+        if turn_count > 0 {
+            sections.push(StatusLine {
+                line_type: StatusLineType::Context,
+                content: format!("Context: {} turns", turn_count),
+            });
+        }
 
-        // Print in gray, all on one line
-        self.output_status(format!("{}", truncated.dark_grey()));
+        // Print in gray, all on one line (mode highlighted)
+        let line = fit_sections(sections, terminal_width())
+            .into_iter()
+            .map(|section| match (&section.line_type, &self.mode) {
+                (StatusLineType::Mode, ReplMode::Executing { .. }) => section.content.green().bold().to_string(),
+                (StatusLineType::Mode, _) => section.content.blue().bold().to_string(),
+                _ => section.content.dark_grey().to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(&SECTION_SEPARATOR.dark_grey().to_string());
+        self.output_status(line);
     }
 
     pub async fn process_query(&mut self, query: &str) -> Result<String> {
-        let start_time = Instant::now();
+        self.process_query_routed(query, self.forward_mode).await
+    }
 
-        // Add user message to conversation history
-        self.conversation.write().await.add_user_message(query.to_string());
+    /// Process a query, optionally skipping the router and forwarding to the teacher
+    ///
+    /// Forced forwards still go through metrics logging and router learning
+    /// (as `user_forced` forwards), so the router learns from them.
+    async fn process_query_routed(&mut self, query: &str, force_forward: bool) -> Result<String> {
+        let audit_id = uuid::Uuid::new_v4();
+        audit::begin(audit_id, query);
+        self.audit_id = Some(audit_id);
+
+        let result = self.answer_query(query, force_forward).await;
+
+        self.audit_id = None;
+        match &result {
+            Ok(response) => audit::finish(audit_id, AuditOutcome::Completed, Some(response)),
+            Err(e) => audit::finish(audit_id, AuditOutcome::Failed, Some(&e.to_string())),
+        }
+        result
+    }
+
+    /// Route and answer a query (see `process_query_routed`)
+    async fn answer_query(&mut self, query: &str, force_forward: bool) -> Result<String> {
+        let start_time = Instant::now();
+        self.profile_id = profile::is_enabled().then(uuid::Uuid::new_v4);
+        if let Some(id) = self.profile_id {
+            profile::begin(id, query);
+        }
+
+        // Add user message to conversation history, with any /image
+        // attachments (the local model can't see them, so they force a forward)
+        let images = std::mem::take(&mut self.pending_images);
+        let has_images = !images.is_empty();
+        let force_forward = force_forward || has_images;
+        let mut message = Message::user(query);
+        message.content.extend(images);
+        self.conversation.write().await.add_message(message);
+
+        // Crisis check comes before any routing (daemon included)
+        let crisis = self.router.check_crisis(query);
+        if let Some(response) = crisis.as_ref().and_then(|c| c.canned_response()) {
+            if self.is_interactive {
+                self.output_status("⚠️  Crisis check: MATCH (answering with the configured response)");
+            }
+            self.audit_route("crisis");
+            let metric = RequestMetric::new(
+                MetricsLogger::hash_query(query),
+                "crisis".to_string(),
+                None,
+                None,
+                Some(ForwardReason::Crisis.as_str().to_string()),
+                start_time.elapsed().as_millis() as u64,
+                ResponseComparison {
+                    local_response: None,
+                    claude_response: response.to_string(),
+                    quality_score: 1.0,
+                    similarity_score: None,
+                    divergence: None,
+                },
+                None,
+                None,
+            );
+            self.metrics_logger.log(&metric)?;
+            self.conversation.write().await.add_assistant_message(response.to_string());
+            self.last_query = Some(query.to_string());
+            self.last_response = Some(response.to_string());
+            return Ok(response.to_string());
+        }
+        let forward_only = force_forward || crisis.is_some();
 
         if self.is_interactive {
             print!("{}", "Analyzing...".dark_grey());
             io::stdout().flush()?;
         }
 
-        // DAEMON MODE: If daemon client is available, use it
-        if let Some(daemon_client) = &self.daemon_client {
+        // DAEMON MODE: If daemon client is available, use it (text-only, so
+        // queries with images go straight to the teacher)
+        if let Some(daemon_client) = self.daemon_client.as_ref().filter(|_| !has_images) {
             if self.is_interactive {
                 io::stdout()
                     .execute(cursor::MoveToColumn(0))?
@@ -2285,7 +3317,21 @@ impl Repl {
                 self.output_status("→ Using daemon for query");
             }
 
-            match daemon_client.query_text(query).await {
+            let daemon_span = self.profile_span("daemon");
+            self.audit_route(if forward_only { "daemon (forward)" } else { "daemon" });
+            let result = if forward_only {
+                daemon_client.query_forward_only(query).await
+            } else if self.streaming_enabled && self.is_interactive {
+                match daemon_client.query_text_stream(query).await {
+                    Ok(rx) => self.display_streaming_response(rx).await.map(|response| response.text()),
+                    Err(e) => Err(e),
+                }
+            } else {
+                daemon_client.query_text(query).await
+            };
+            drop(daemon_span);
+
+            match result {
                 Ok(response) => {
                     // Add assistant response to conversation
                     self.conversation.write().await.add_assistant_message(response.clone());
@@ -2309,13 +3355,48 @@ impl Repl {
         }
 
         // FALLBACK: Normal routing (local model or teacher API)
+        // Optionally hold the query until the local model finishes loading
+        if !forward_only {
+            self.wait_for_local_model().await?;
+        }
+
         // Make routing decision (uses threshold router internally)
         // Check if local generator is ready before routing (progressive bootstrap support)
+        let routing_span = self.profile_span("routing");
         let generator_ready = matches!(
             *self.bootstrap_loader.state().read().await,
             GeneratorState::Ready { .. }
         );
-        let decision = self.router.route_with_generator_check(query, generator_ready);
+        let decision = if has_images {
+            self.router.route_image(query)
+        } else if force_forward {
+            self.router.route_forced_forward(query)
+        } else {
+            self.router.route_with_generator_check(query, generator_ready)
+        };
+
+        // Teacher circuit open: answer locally instead of failing fast
+        let decision = match decision {
+            RouteDecision::Forward { reason }
+                if !matches!(
+                    reason,
+                    ForwardReason::UserForced | ForwardReason::Crisis | ForwardReason::Image
+                )
+                    && generator_ready
+                    && self.claude_client.is_circuit_open() =>
+            {
+                if self.is_interactive {
+                    self.output_status("⚠️  Teacher unavailable (circuit open), trying local model");
+                }
+                RouteDecision::Local {
+                    pattern_id: "teacher_circuit_open".to_string(),
+                    confidence: 0.0,
+                }
+            }
+            decision => decision,
+        };
+
+        drop(routing_span);
 
         if self.is_interactive {
             io::stdout()
@@ -2356,52 +3437,22 @@ impl Repl {
 
                 if !is_model_ready {
                     // Model not ready, forward to Claude
-                    let request =
-                        MessageRequest::with_context(self.conversation.read().await.get_messages())
-                            .with_tools(self.tool_definitions.clone());
-
-                    // Try streaming first, fallback to buffered if tools detected
-                    let use_streaming = self.streaming_enabled && self.is_interactive;
-
-                    if use_streaming {
-                        let rx = self.call_teacher_stream(&request).await?;
-                        match self.display_streaming_response(rx).await {
-                            Ok(text) => {
-                                claude_response = text;
-                            }
-                            Err(e) if e.to_string().contains("TOOLS_DETECTED") => {
-                                if self.is_interactive {
-                                    self.output_status("\n🔧 Tools needed - switching to buffered mode...");
-                                }
-                                let response = self.call_teacher(&request).await?;
-                                let elapsed = start_time.elapsed().as_millis();
-                                if self.is_interactive {
-                                    self.output_status(format!("✓ Received response ({}ms)", elapsed));
-                                }
-                                claude_response = self.execute_tool_loop(response).await?;
-                            }
-                            Err(e) => return Err(e),
-                        }
-                    } else {
-                        let response = self.call_teacher(&request).await?;
-                        let elapsed = start_time.elapsed().as_millis();
-                        if self.is_interactive {
-                            self.output_status(format!("✓ Received response ({}ms)", elapsed));
-                        }
-                        if response.has_tool_uses() {
-                            claude_response = self.execute_tool_loop(response).await?;
-                        } else {
-                            claude_response = response.text();
-                        }
-                    }
+                    let request = self.with_session_tools(MessageRequest::with_context(
+                        self.conversation.read().await.get_messages(),
+                    ));
+
+                    claude_response = self.answer_with_teacher(&request, start_time).await?;
 
                     routing_decision_str = "forward".to_string();
                     forward_reason = Some("model_not_ready".to_string());
                 } else {
                     // Model is ready, proceed with local generation
                     // Try local generation
+                    let local_span = self.profile_span("local generation");
                     let mut gen = self.local_generator.write().await;
-                    match gen.try_generate_from_pattern(query) {
+                    let generated = gen.try_generate_from_pattern(query);
+                    drop(local_span);
+                    match generated {
                     Ok(Some(response_text)) => {
                         // Successfully generated locally
                         if self.is_interactive {
@@ -2414,60 +3465,38 @@ impl Repl {
                         routing_confidence = Some(confidence);
                     }
                     Ok(None) | Err(_) => {
-                        // Local generation insufficient or failed - forward to Claude
+                        // Local generation insufficient, failed or timed out - forward to Claude
                         drop(gen); // Drop the read lock before forwarding to Claude
 
-                        if self.is_interactive {
+                        let timeout = match &generated {
+                            Err(e) => e.downcast_ref::<crate::local::LocalTimeout>().copied(),
+                            Ok(_) => None,
+                        };
+                        if let Some(timeout) = timeout {
+                            self.router.route_local_timeout(query, timeout.after);
+                            if self.is_interactive {
+                                self.output_status(format!("⏱  {}", timeout));
+                                self.output_status("→ Forwarding to Claude");
+                            }
+                        } else if self.is_interactive {
                             self.output_status("⚠️  Local generation insufficient confidence");
                             self.output_status("→ Forwarding to Claude");
                         }
 
                         // Forward to Claude
-                        let request =
-                            MessageRequest::with_context(self.conversation.read().await.get_messages())
-                                .with_tools(self.tool_definitions.clone());
-
-                        // Try streaming first, fallback to buffered if tools detected
-                        let use_streaming = self.streaming_enabled && self.is_interactive;
-
-                        if use_streaming {
-                            let rx = self.call_teacher_stream(&request).await?;
-                            match self.display_streaming_response(rx).await {
-                                Ok(text) => {
-                                    claude_response = text;
-                                }
-                                Err(e) if e.to_string().contains("TOOLS_DETECTED") => {
-                                    if self.is_interactive {
-                                        self.output_status("
-🔧 Tools needed - switching to buffered mode...");
-                                    }
-                                    let response =
-                                        self.call_teacher(&request).await?;
-                                    let elapsed = start_time.elapsed().as_millis();
-                                    if self.is_interactive {
-                                        self.output_status(format!("✓ Received response ({}ms)", elapsed));
-                                    }
-                                    claude_response = self.execute_tool_loop(response).await?;
-                                }
-                                Err(e) => return Err(e),
-                            }
-                        } else {
-                            let response = self.call_teacher(&request).await?;
-                            let elapsed = start_time.elapsed().as_millis();
-                            if self.is_interactive {
-                                self.output_status(format!("✓ Received response ({}ms)", elapsed));
-                            }
-                            if response.has_tool_uses() {
-                                claude_response = self.execute_tool_loop(response).await?;
-                            } else {
-                                claude_response = response.text();
-                            }
-                        }
+                        let request = self.with_session_tools(MessageRequest::with_context(
+                            self.conversation.read().await.get_messages(),
+                        ));
+
+                        claude_response = self.answer_with_teacher(&request, start_time).await?;
 
                         routing_decision_str = "local_attempted".to_string();
                         pattern_id = Some(local_pattern_id);
                         routing_confidence = Some(confidence);
-                        forward_reason = Some("insufficient_confidence".to_string());
+                        forward_reason = Some(match timeout {
+                            Some(_) => ForwardReason::LocalTimeout.as_str().to_string(),
+                            None => "insufficient_confidence".to_string(),
+                        });
                     }
                     } // Close the else block for generator readiness check
                 }
@@ -2483,6 +3512,16 @@ impl Repl {
                             self.output_status(format!("ℹ️  Model status: {}", status_msg));
                             self.output_status("→ Routing: FORWARDING TO TEACHER");
                         }
+                        ForwardReason::UserForced => {
+                            self.output_status("→ Routing: FORWARDING TO TEACHER (user forced)");
+                        }
+                        ForwardReason::Image => {
+                            self.output_status("→ Routing: FORWARDING TO TEACHER (image attached)");
+                        }
+                        ForwardReason::Crisis => {
+                            self.output_status("⚠️  Crisis check: MATCH");
+                            self.output_status("→ Routing: FORWARDING TO TEACHER");
+                        }
                         _ => {
                             self.output_status("✗ Threshold check: FAIL (confidence too low)");
                             self.output_status("→ Routing: FORWARDING TO TEACHER");
@@ -2491,69 +3530,27 @@ impl Repl {
                 }
 
                 // Use full conversation context with tool definitions
-                let request = MessageRequest::with_context(self.conversation.read().await.get_messages())
-                    .with_tools(self.tool_definitions.clone());
-
-                // Try streaming first, fallback to buffered if tools detected
-                let use_streaming = self.streaming_enabled && self.is_interactive;
-
-                if use_streaming {
-                    // Streaming path - will abort if tools detected
-                    let rx = self.call_teacher_stream(&request).await?;
-                    match self.display_streaming_response(rx).await {
-                        Ok(text) => {
-                            // Streaming succeeded (no tools)
-                            claude_response = text;
-                        }
-                        Err(e) if e.to_string().contains("TOOLS_DETECTED") => {
-                            // Tools detected in stream - fallback to buffered mode
-                            if self.is_interactive {
-                                self.output_status("\n🔧 Tools needed - switching to buffered mode...");
-                            }
-                            let response = self.call_teacher(&request).await?;
-
-                            let elapsed = start_time.elapsed().as_millis();
-                            if self.is_interactive {
-                                self.output_status(format!("✓ Received response ({}ms)", elapsed));
-                            }
-
-                            // Execute tool loop
-                            claude_response = self.execute_tool_loop(response).await?;
-                        }
-                        Err(e) => {
-                            // Real error - propagate
-                            return Err(e);
-                        }
-                    }
-                } else {
-                    // Non-streaming path (supports tool use detection)
-                    let response = self.call_teacher(&request).await?;
-
-                    let elapsed = start_time.elapsed().as_millis();
-                    if self.is_interactive {
-                        self.output_status(format!("✓ Received response ({}ms)", elapsed));
-                    }
+                let request = self.with_session_tools(MessageRequest::with_context(
+                    self.conversation.read().await.get_messages(),
+                ));
 
-                    // Check for tool uses and execute them
-                    if response.has_tool_uses() {
-                        claude_response = self.execute_tool_loop(response).await?;
-                    } else {
-                        claude_response = response.text();
-                    }
-                }
+                claude_response = self.answer_with_teacher(&request, start_time).await?;
                 routing_decision_str = "forward".to_string();
                 forward_reason = Some(reason.as_str().to_string());
             }
         };
 
-        // Calculate quality and similarity
+        // Calculate quality and similarity (a local answer is only a
+        // comparison point when the teacher answered too)
+        let compare_with = local_response.as_deref().filter(|_| routing_decision_str != "local");
         let quality_score = self
-            .threshold_validator
-            .quality_score(query, &claude_response);
+            .quality_scorer
+            .score(query, &claude_response, compare_with)
+            .await;
 
         let (similarity_score, divergence) = if let Some(ref local_resp) = local_response {
             use crate::metrics::semantic_similarity;
-            let sim = semantic_similarity(local_resp, &claude_response)?;
+            let sim = semantic_similarity(local_resp, &claude_response).await?;
             (Some(sim), Some(1.0 - sim))
         } else {
             (None, None)
@@ -2566,11 +3563,17 @@ impl Repl {
             io::stdout().flush()?;
         }
 
+        self.audit_route(match routing_decision_str.as_str() {
+            "forward" if force_forward => "forward",
+            "forward" => "teacher",
+            route => route,
+        });
+
         // Learn from this interaction
         match routing_decision_str.as_str() {
             "local" => {
                 // We successfully generated locally
-                let was_successful = quality_score >= 0.7;
+                let was_successful = quality_score >= LEARN_THRESHOLD;
                 self.router.learn_local_attempt(query, was_successful);
             }
             "local_attempted" => {
@@ -2587,7 +3590,7 @@ impl Repl {
         }
 
         self.threshold_validator
-            .learn(query, &claude_response, quality_score >= 0.7);
+            .learn(query, &claude_response, quality_score >= LEARN_THRESHOLD);
 
         // Learn from Claude response (for local generation and neural training)
         {
@@ -2775,6 +3778,49 @@ impl Repl {
         Ok(())
     }
 
+    /// Handle /forward command - send query straight to the teacher (bypass routing)
+    async fn handle_forward_query(&mut self, query: &str) {
+        self.handle_routed_query(query, true).await;
+    }
+
+    /// Answer `query` (sent straight to the teacher if `force_forward`) and show the result
+    async fn handle_routed_query(&mut self, query: &str, force_forward: bool) {
+        match self.process_query_routed(query, force_forward).await {
+            Ok(response) => {
+                self.output_response(&response);
+                self.finish_profile();
+                crate::cli::tts::speak(&response);
+                crate::cli::code_blocks::set_latest(&response);
+                if self.is_interactive {
+                    self.output_status("");
+                    self.print_status_line().await;
+                }
+            }
+            Err(e) => {
+                self.output_error(format!("Error: {}", e));
+                self.finish_profile();
+                if self.is_interactive {
+                    self.output_status("");
+                    self.print_status_line().await;
+                }
+            }
+        }
+    }
+
+    /// Handle /retry: answer the last query again with the teacher
+    ///
+    /// The previous exchange is dropped first so the conversation doesn't
+    /// hold the query twice; the new answer replaces the last response.
+    async fn handle_retry(&mut self) {
+        let Some(query) = self.last_query.clone() else {
+            self.output_status("Nothing to retry yet - send a prompt first.");
+            return;
+        };
+        self.conversation.write().await.remove_last_exchange(&query);
+        self.output_status(format!("🔁 Retrying with the teacher: {}", query));
+        self.handle_forward_query(&query).await;
+    }
+
     /// Handle /local command - query local model directly (bypass routing)
     async fn handle_local_query(&mut self, query: &str) -> Result<()> {
         // Show status
@@ -2973,7 +4019,7 @@ impl Repl {
         self.output_status(format!("📁 Plan will be saved to: {}", plan_path.display()));
         self.output_status("");
         self.output_status(format!("{}", "Available tools:".green()));
-        self.output_status("  read, glob, grep, web_fetch");
+        self.output_status("  read, glob, grep, list_dir, web_fetch");
         self.output_status(format!("{}", "Blocked tools:".red()));
         self.output_status("  bash, save_and_exec");
         self.output_status("");
@@ -2986,7 +4032,7 @@ impl Repl {
         // Add mode change notification to conversation
         self.conversation.write().await.add_user_message(format!(
             "[System: Entered planning mode for task: {}]\n\
-             Available tools: read, glob, grep, web_fetch\n\
+             Available tools: read, glob, grep, list_dir, web_fetch\n\
              Blocked tools: bash, save_and_exec\n\
              Please explore the codebase and generate a detailed plan.",
             task
@@ -3,30 +3,43 @@
 use anyhow::{Context, Result};
 use chrono::Utc;
 use crossterm::style::Stylize;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::{mpsc, Mutex, RwLock};
 use uuid::Uuid;
 
-use crate::cli::commands::{Command, format_help};
-use crate::cli::conversation::ConversationHistory;
+use crate::cli::commands::Command;
+use crate::cli::help::format_help;
+use crate::cli::conversation::{
+    log_compaction, recent_turns_start, ConversationCompactor, ConversationHistory,
+};
 use crate::cli::output_manager::OutputManager;
+use crate::cli::profile;
 use crate::cli::repl::ReplMode;
-use crate::cli::status_bar::StatusBar;
+use crate::cli::status_bar::{StatusBar, ThroughputMeter};
+use crate::cli::watch::{FileWatch, WatchDecision};
+use crate::config::CompactionConfig;
 use crate::cli::tui::{spawn_input_task, TuiRenderer};
 use crate::claude::ContentBlock;
 use crate::generators::{Generator, StreamChunk};
 use crate::local::LocalGenerator;
-use crate::models::bootstrap::GeneratorState;
+use crate::models::bootstrap::{wait_for_ready, GeneratorState, LocalWaitOutcome};
+use crate::models::TemperatureConfig;
 use crate::models::tokenizer::TextTokenizer;
+use crate::providers::{TemperatureOverride, ThinkingOverride};
 use crate::router::Router;
-use crate::tools::executor::ToolExecutor;
+use crate::tools::executor::{ToolExecutor, ToolSlots};
+use crate::tools::session_changes::{SessionChanges, SUMMARY_MIN_TOOL_CALLS};
 use crate::tools::types::{ToolDefinition, ToolUse};
 
 use super::events::ReplEvent;
 use super::query_state::{QueryState, QueryStateManager};
 use super::tool_execution::ToolExecutionCoordinator;
 
+/// Tool results collected per query (query_id -> Vec<(tool_id, result)>)
+type ToolResults = std::collections::HashMap<Uuid, Vec<(String, Result<String>)>>;
+
 /// Main event loop for concurrent REPL
 pub struct EventLoop {
     /// Channel for receiving events
@@ -55,9 +68,21 @@ pub struct EventLoop {
     /// Generator state for bootstrap tracking
     generator_state: Arc<RwLock<GeneratorState>>,
 
+    /// Local generator (tracks the applied LoRA adapter, for /model-info)
+    local_generator: Arc<RwLock<LocalGenerator>>,
+
     /// Tool definitions for Claude API
     tool_definitions: Arc<Vec<ToolDefinition>>,
 
+    /// Whether tool definitions are sent with queries (toggled by /tools on|off)
+    tools_enabled: bool,
+
+    /// Whether every query skips routing and goes to the teacher (/mode forward)
+    forward_mode: bool,
+
+    /// Images attached with /image, sent with the next query
+    pending_images: Vec<ContentBlock>,
+
     /// TUI renderer
     tui_renderer: Arc<Mutex<TuiRenderer>>,
 
@@ -74,7 +99,7 @@ pub struct EventLoop {
     tool_coordinator: ToolExecutionCoordinator,
 
     /// Tool results collected per query (query_id -> Vec<(tool_id, result)>)
-    tool_results: Arc<RwLock<std::collections::HashMap<Uuid, Vec<(String, Result<String>)>>>>,
+    tool_results: Arc<RwLock<ToolResults>>,
 
     /// Currently active query ID (for cancellation)
     active_query_id: Arc<RwLock<Option<Uuid>>>,
@@ -82,6 +107,9 @@ pub struct EventLoop {
     /// Pending tool approval requests (query_id -> (tool_use, response_tx))
     pending_approvals: Arc<RwLock<std::collections::HashMap<Uuid, (crate::tools::types::ToolUse, tokio::sync::oneshot::Sender<super::events::ConfirmationResult>)>>>,
 
+    /// Whether the active approval dialog offers task-scoped options
+    approval_dialog_has_task_options: bool,
+
     /// Daemon client (for /local command)
     daemon_client: Option<Arc<crate::client::DaemonClient>>,
 
@@ -90,6 +118,42 @@ pub struct EventLoop {
 
     /// Plan content storage (for PresentPlan tool)
     plan_content: Arc<RwLock<Option<String>>>,
+
+    /// Tool effects this session (files, commands, errors)
+    session_changes: Arc<SessionChanges>,
+
+    /// Session change log position at the last /done
+    done_checkpoint: usize,
+
+    /// Concurrent tool limit and in-flight count (features.max_concurrent_tools)
+    tool_slots: ToolSlots,
+
+    /// Query IDs of /replay-tool runs (results are shown, not sent to the model)
+    tool_replays: std::collections::HashSet<Uuid>,
+
+    /// Teacher temperature per query category (shown by /sampling)
+    temperatures: TemperatureConfig,
+
+    /// Session thinking budget (/think), shared with the teacher client
+    thinking: Arc<ThinkingOverride>,
+
+    /// Next teacher call's temperature (/temp), shared with the teacher client
+    temperature: Arc<TemperatureOverride>,
+
+    /// How long queries wait for a still-loading local model (None: forward immediately)
+    local_wait: Option<Duration>,
+
+    /// Local generations running longer than this fall back to the teacher
+    local_timeout: Option<Duration>,
+
+    /// Auto-compaction settings
+    compaction: CompactionConfig,
+
+    /// Whether an auto-compaction is running in the background
+    compacting: Arc<AtomicBool>,
+
+    /// Active /watch (re-runs its prompt when matching files change)
+    watch: Option<FileWatch>,
 }
 
 impl EventLoop {
@@ -102,6 +166,7 @@ impl EventLoop {
         router: Arc<Router>,
         generator_state: Arc<RwLock<GeneratorState>>,
         tool_definitions: Vec<ToolDefinition>,
+        tools_enabled: bool,
         tool_executor: Arc<Mutex<ToolExecutor>>,
         tui_renderer: TuiRenderer,
         output_manager: Arc<OutputManager>,
@@ -111,6 +176,14 @@ impl EventLoop {
         tokenizer: Arc<TextTokenizer>,
         daemon_client: Option<Arc<crate::client::DaemonClient>>,
         mode: Arc<RwLock<ReplMode>>,
+        session_changes: Arc<SessionChanges>,
+        tool_slots: ToolSlots,
+        temperatures: TemperatureConfig,
+        thinking: Arc<ThinkingOverride>,
+        temperature: Arc<TemperatureOverride>,
+        local_wait: Option<Duration>,
+        local_timeout: Option<Duration>,
+        compaction: CompactionConfig,
     ) -> Self {
         let (event_tx, event_rx) = mpsc::unbounded_channel();
 
@@ -123,6 +196,8 @@ impl EventLoop {
         // Initialize plan content storage
         let plan_content = Arc::new(RwLock::new(None));
 
+        status_bar.update_tools_enabled(tools_enabled);
+
         // Create tool coordinator
         let tool_coordinator = ToolExecutionCoordinator::new(
             event_tx.clone(),
@@ -144,7 +219,11 @@ impl EventLoop {
             qwen_gen,
             router,
             generator_state,
+            local_generator,
             tool_definitions: Arc::new(tool_definitions),
+            tools_enabled,
+            forward_mode: false,
+            pending_images: Vec::new(),
             tui_renderer,
             output_manager,
             status_bar,
@@ -153,9 +232,22 @@ impl EventLoop {
             tool_results: Arc::new(RwLock::new(std::collections::HashMap::new())),
             active_query_id: Arc::new(RwLock::new(None)),
             pending_approvals: Arc::new(RwLock::new(std::collections::HashMap::new())),
+            approval_dialog_has_task_options: false,
             daemon_client,
             mode,
             plan_content,
+            session_changes,
+            done_checkpoint: 0,
+            tool_slots,
+            tool_replays: std::collections::HashSet::new(),
+            temperatures,
+            thinking,
+            temperature,
+            local_wait,
+            local_timeout,
+            compaction,
+            compacting: Arc::new(AtomicBool::new(false)),
+            watch: None,
         }
     }
 
@@ -166,6 +258,9 @@ impl EventLoop {
         // Initialize compaction status display
         self.update_compaction_status().await;
 
+        // Code blocks get Alt+<n> copy hints in the TUI
+        crate::cli::code_blocks::set_interactive(true);
+
         // Initialize plan mode indicator (starts in Normal mode)
         self.update_plan_mode_indicator(&crate::cli::repl::ReplMode::Normal);
 
@@ -175,6 +270,10 @@ impl EventLoop {
         // Cleanup interval (30 seconds)
         let mut cleanup_interval = tokio::time::interval(Duration::from_secs(30));
 
+        // Idle exit check (features.idle_exit_minutes)
+        let idle_exit_enabled = crate::cli::idle::limit().is_some();
+        let mut idle_interval = tokio::time::interval(Duration::from_secs(1));
+
         // Flag to control the loop
         let mut should_exit = false;
 
@@ -183,6 +282,10 @@ impl EventLoop {
                 // User input event
                 Some(input) = self.input_rx.recv() => {
                     tracing::debug!("Received input: {}", input);
+                    // Typing anything ends a watch (except managing it)
+                    if !input.trim().starts_with("/watch") {
+                        self.stop_watch("input received");
+                    }
                     self.handle_user_input(input).await?;
                 }
 
@@ -197,6 +300,7 @@ impl EventLoop {
                         ReplEvent::OutputReady { .. } => "OutputReady",
                         ReplEvent::UserInput { .. } => "UserInput",
                         ReplEvent::StatsUpdate { .. } => "StatsUpdate",
+                        ReplEvent::WatchChanged { .. } => "WatchChanged",
                         ReplEvent::CancelQuery => "CancelQuery",
                         ReplEvent::Shutdown => "Shutdown",
                     };
@@ -250,6 +354,10 @@ impl EventLoop {
 
                     // Don't spam logs, but good to know the loop is alive
                     // tracing::debug!("[EVENT_LOOP] Render tick");
+                    for finished in profile::take_finished() {
+                        self.output_manager.write_info(profile::report(&finished));
+                    }
+                    update_tool_activity(&self.status_bar, &self.tool_slots);
                     if let Err(e) = self.render_tui().await {
                         tracing::warn!("TUI render failed in event loop: {}", e);
                         // Set recovery flag for next tick
@@ -260,9 +368,21 @@ impl EventLoop {
                     }
                 }
 
+                // Idle exit: warn, then save and exit like /quit
+                _ = idle_interval.tick(), if idle_exit_enabled => {
+                    if self.check_idle_exit().await? {
+                        should_exit = true;
+                    }
+                }
+
                 // Periodic cleanup
                 _ = cleanup_interval.tick() => {
                     self.cleanup_old_queries().await;
+                    self.refresh_training_status();
+                    // Write debounced autosave changes
+                    if let Some(autosave) = crate::cli::autosave::global_autosave() {
+                        autosave.flush();
+                    }
                 }
             }
         }
@@ -270,8 +390,67 @@ impl EventLoop {
         Ok(())
     }
 
+    /// Warn about, then perform, the idle exit
+    ///
+    /// Only counts time spent waiting at the prompt: while a query or tool
+    /// is running the timer is held at zero. Returns true when the REPL
+    /// should exit.
+    async fn check_idle_exit(&mut self) -> Result<bool> {
+        use crate::cli::idle::{self, IdleState};
+        use crate::cli::StatusLineType;
+
+        let idle_line = StatusLineType::Custom("idle".to_string());
+
+        let busy = self
+            .query_states
+            .count_by_state(|state| {
+                matches!(state, QueryState::Processing | QueryState::ExecutingTools { .. })
+            })
+            .await
+            > 0
+            || !self.pending_approvals.read().await.is_empty();
+        if busy {
+            idle::record_activity();
+            self.status_bar.remove_line(&idle_line);
+            return Ok(false);
+        }
+
+        // The input task holds the TUI briefly while polling; a dialog holds it
+        // until answered. Either way, check again next tick.
+        match self.tui_renderer.try_lock() {
+            Ok(tui) if tui.active_dialog.is_none() && tui.active_tabbed_dialog.is_none() => {}
+            _ => return Ok(false),
+        }
+
+        match idle::check() {
+            IdleState::Active => {
+                self.status_bar.remove_line(&idle_line);
+                Ok(false)
+            }
+            IdleState::Warning(remaining) => {
+                self.status_bar
+                    .update_line(idle_line, idle::warning_message(remaining));
+                Ok(false)
+            }
+            IdleState::Expired => {
+                self.status_bar.remove_line(&idle_line);
+                self.output_manager
+                    .write_info("Idle timeout reached - saving and exiting.");
+                self.render_tui().await?;
+                if let Some(autosave) = crate::cli::autosave::global_autosave() {
+                    autosave.flush();
+                }
+                tracing::info!("Exiting after {:?} idle", idle::idle_for());
+                Ok(true)
+            }
+        }
+    }
+
     /// Handle user input (query or command)
     async fn handle_user_input(&mut self, input: String) -> Result<()> {
+        // New input interrupts any response still being spoken
+        crate::cli::tts::stop();
+
         // Check if it's a command
         if input.trim().starts_with('/') {
             // Echo the command to output (like user queries)
@@ -284,8 +463,8 @@ impl EventLoop {
                             .send(ReplEvent::Shutdown)
                             .context("Failed to send shutdown event")?;
                     }
-                    Command::Help => {
-                        let help_text = format_help();
+                    Command::Help(topic) => {
+                        let help_text = format_help(topic.as_deref(), true);
                         self.output_manager.write_info(help_text);
                         self.render_tui().await?;
                     }
@@ -303,16 +482,196 @@ impl EventLoop {
                         );
                         self.render_tui().await?;
                     }
+                    Command::ModelInfo => {
+                        self.handle_model_info().await?;
+                    }
+                    Command::TrainingPause => {
+                        self.handle_training_control(crate::server::TrainingAction::Pause).await?;
+                    }
+                    Command::TrainingResume => {
+                        self.handle_training_control(crate::server::TrainingAction::Resume).await?;
+                    }
+                    Command::TrainingCancel => {
+                        self.handle_training_control(crate::server::TrainingAction::Cancel).await?;
+                    }
                     Command::Memory => {
                         use crate::monitoring::MemoryInfo;
                         let info = MemoryInfo::current();
                         self.output_manager.write_info(info.format_with_warning());
                         self.render_tui().await?;
                     }
+                    Command::Vary(count) => {
+                        self.handle_vary(count).await?;
+                    }
+                    Command::Retry => {
+                        self.handle_retry().await?;
+                    }
+                    Command::Undo => {
+                        if self.active_query_id.read().await.is_some() {
+                            self.output_manager
+                                .write_error("A query is still running - cancel it (Ctrl+C) before /undo.");
+                        } else {
+                            let mut conversation = self.conversation.write().await;
+                            let removed = conversation.pop_last_exchange();
+                            let remaining = conversation.exchange_count();
+                            drop(conversation);
+
+                            self.output_manager.write_info(crate::cli::commands::undo_status(
+                                removed.as_deref(),
+                                remaining,
+                            ));
+                            self.update_compaction_status().await;
+                        }
+                        self.render_tui().await?;
+                    }
+                    Command::Compact(turns) => {
+                        if self.active_query_id.read().await.is_some() {
+                            self.output_manager
+                                .write_error("A query is still running - cancel it (Ctrl+C) before /compact.");
+                        } else {
+                            self.handle_compact(turns.unwrap_or(self.compaction.keep_turns)).await;
+                        }
+                        self.render_tui().await?;
+                    }
+                    Command::Summarize { path, detail } => {
+                        self.handle_summarize(path, detail).await?;
+                    }
+                    Command::Image(path) => {
+                        match ContentBlock::image_file(&path) {
+                            Ok(image) => {
+                                self.pending_images.push(image);
+                                self.output_manager.write_info(format!(
+                                    "📎 Attached {} ({} pending) - sent to the teacher with your next query",
+                                    path,
+                                    self.pending_images.len()
+                                ));
+                            }
+                            Err(e) => self.output_manager.write_error(format!("{:#}", e)),
+                        }
+                        self.render_tui().await?;
+                    }
+                    Command::ImageClear => {
+                        let count = std::mem::take(&mut self.pending_images).len();
+                        self.output_manager
+                            .write_info(format!("📎 Dropped {} pending image(s)", count));
+                        self.render_tui().await?;
+                    }
+                    Command::Pin(note) => {
+                        let mut conversation = self.conversation.write().await;
+                        let pinned = match note {
+                            Some(note) => Some(conversation.pin_note(note)),
+                            None => conversation.pin_last_message(),
+                        };
+                        drop(conversation);
+                        match pinned {
+                            Some(number) => self.output_manager.write_info(format!(
+                                "📌 Pinned #{} - it stays in context until /unpin {}",
+                                number, number
+                            )),
+                            None => self.output_manager.write_info("Nothing to pin yet."),
+                        }
+                        self.render_tui().await?;
+                    }
+                    Command::Export { path } => {
+                        let messages = self.conversation.read().await.snapshot();
+                        match crate::cli::export::export_markdown(&messages, path.as_deref()) {
+                            Ok(Some(path)) => self.output_manager.write_info(format!(
+                                "📝 Exported {} messages to {}",
+                                messages.len(),
+                                path.display()
+                            )),
+                            Ok(None) => self
+                                .output_manager
+                                .write_info("Nothing to export yet - the conversation is empty."),
+                            Err(e) => self.output_manager.write_error(format!("Export failed: {:#}", e)),
+                        }
+                        self.render_tui().await?;
+                    }
+                    Command::Context => {
+                        // Dry run of the request the teacher generator would build
+                        let messages = self.conversation.read().await.get_messages();
+                        let tools = if self.session_tools_enabled().await {
+                            Some((*self.tool_definitions).clone())
+                        } else {
+                            None
+                        };
+                        match self.claude_gen.preview_context(messages, tools) {
+                            Some(preview) => self
+                                .output_manager
+                                .write_info(crate::cli::context::format_context_preview(&preview)),
+                            None => self.output_manager.write_info(format!(
+                                "{} has no teacher request to show.",
+                                self.claude_gen.name()
+                            )),
+                        }
+                        self.render_tui().await?;
+                    }
+                    Command::Pins => {
+                        let list = crate::cli::conversation::format_pins(
+                            self.conversation.read().await.pins(),
+                        );
+                        self.output_manager.write_info(list);
+                        self.render_tui().await?;
+                    }
+                    Command::Unpin(number) => {
+                        match self.conversation.write().await.unpin(number) {
+                            Some(_) => self.output_manager.write_info(format!("Removed pin #{}.", number)),
+                            None => self
+                                .output_manager
+                                .write_info(format!("No pin #{} (see /pins).", number)),
+                        }
+                        self.render_tui().await?;
+                    }
+                    Command::Sampling => {
+                        self.output_manager.write_info(self.temperatures.format_mapping());
+                        self.render_tui().await?;
+                    }
+                    Command::ThinkStatus => {
+                        self.output_manager
+                            .write_info(crate::cli::commands::thinking_status(self.thinking.get()));
+                        self.render_tui().await?;
+                    }
+                    Command::Think(level) => {
+                        self.thinking.set(level);
+                        self.output_manager
+                            .write_info(crate::cli::commands::thinking_status(level));
+                        self.render_tui().await?;
+                    }
+                    Command::TempStatus => {
+                        self.output_manager.write_info(crate::cli::commands::temperature_status(
+                            self.temperature.get(),
+                        ));
+                        self.render_tui().await?;
+                    }
+                    Command::Temp(value) => {
+                        match self.temperature.set(value) {
+                            Ok(()) => {
+                                self.status_bar.update_temperature_override(value);
+                                self.output_manager
+                                    .write_info(crate::cli::commands::temperature_status(value));
+                            }
+                            Err(e) => self.output_manager.write_error(e.to_string()),
+                        }
+                        self.render_tui().await?;
+                    }
                     Command::Local { query } => {
                         // Handle /local command - query local model directly (bypass routing)
                         self.handle_local_query(query).await?;
                     }
+                    Command::Forward { query } => {
+                        // Handle /forward command - send straight to the teacher (bypass routing)
+                        self.submit_query(query, None, true).await?;
+                    }
+                    Command::ForwardMode(enabled) => {
+                        self.forward_mode = enabled;
+                        self.output_manager.write_info(if enabled {
+                            "☁️  Forward mode: every query goes to the teacher.\n\
+                             Use /mode auto to return to normal routing."
+                        } else {
+                            "🔀 Auto mode: queries are routed normally."
+                        });
+                        self.render_tui().await?;
+                    }
                     Command::PlanModeToggle | Command::Plan(_) => {
                         // Check current mode and toggle
                         let current_mode = self.mode.read().await.clone();
@@ -329,7 +688,7 @@ impl EventLoop {
                                 self.output_manager.write_info(
                                     "📋 Entered plan mode.\n\
                                      You can explore the codebase using read-only tools:\n\
-                                     - Read files, glob, grep, web_fetch are allowed\n\
+                                     - Read files, glob, grep, list_dir, web_fetch are allowed\n\
                                      - Write, edit, bash are restricted\n\
                                      Use /plan to exit plan mode."
                                 );
@@ -339,6 +698,7 @@ impl EventLoop {
                             ReplMode::Planning { .. } | ReplMode::Executing { .. } => {
                                 // Exit plan mode, return to normal
                                 *self.mode.write().await = ReplMode::Normal;
+                                self.clear_task_approvals().await;
                                 self.output_manager.write_info(
                                     "✅ Exited plan mode. Returned to normal mode."
                                 );
@@ -348,6 +708,201 @@ impl EventLoop {
                         }
                         self.render_tui().await?;
                     }
+                    Command::HistoryClear => {
+                        let result = self.tui_renderer.lock().await.clear_history();
+                        match result {
+                            Ok(()) => self.output_manager.write_info("🧹 Input history cleared."),
+                            Err(e) => self
+                                .output_manager
+                                .write_error(format!("Failed to clear history: {}", e)),
+                        }
+                        self.render_tui().await?;
+                    }
+                    Command::TtsToggle(enabled) => {
+                        crate::cli::tts::set_enabled(enabled);
+                        self.output_manager.write_info(if enabled {
+                            format!("🔊 Speech on ({})", crate::cli::tts::command_line())
+                        } else {
+                            "🔇 Speech off".to_string()
+                        });
+                        self.render_tui().await?;
+                    }
+                    Command::ProviderLatency => {
+                        self.output_manager
+                            .write_info(crate::cli::commands::format_provider_latency());
+                        self.render_tui().await?;
+                    }
+                    Command::ProfileToggle(enabled) => {
+                        let enabled = enabled.unwrap_or(!crate::cli::profile::is_enabled());
+                        crate::cli::profile::set_enabled(enabled);
+                        self.output_manager
+                            .write_info(crate::cli::profile::status_message(enabled));
+                        self.render_tui().await?;
+                    }
+                    Command::Watch(spec) => {
+                        self.watch = None;
+                        let event_tx = self.event_tx.clone();
+                        match FileWatch::start(spec, move |paths| {
+                            let _ = event_tx.send(ReplEvent::WatchChanged { paths });
+                        }) {
+                            Ok(watch) => {
+                                self.output_manager
+                                    .write_info(crate::cli::watch::started_message(&watch.spec));
+                                self.watch = Some(watch);
+                            }
+                            Err(e) => self.output_manager.write_error(format!("Watch failed: {:#}", e)),
+                        }
+                        self.render_tui().await?;
+                    }
+                    Command::WatchStatus => {
+                        self.output_manager.write_info(match &self.watch {
+                            Some(watch) => format!(
+                                "👀 Watching {} — \"{}\" ({}/{} runs). /watch stop to stop.",
+                                watch.spec.pattern,
+                                watch.spec.prompt,
+                                watch.guard.runs(),
+                                watch.guard.max_runs()
+                            ),
+                            None => "Not watching. Usage: /watch <glob> :: <prompt>".to_string(),
+                        });
+                        self.render_tui().await?;
+                    }
+                    Command::WatchStop => {
+                        if self.watch.is_none() {
+                            self.output_manager.write_info("Not watching.");
+                        }
+                        self.stop_watch("stopped");
+                        self.render_tui().await?;
+                    }
+                    Command::FormatLineNumbers => {
+                        crate::cli::code_blocks::toggle_line_numbers();
+                        self.output_manager
+                            .write_info(crate::cli::code_blocks::status_message());
+                        self.render_tui().await?;
+                    }
+                    Command::FormatHints => {
+                        crate::cli::code_blocks::toggle_copy_hints();
+                        self.output_manager
+                            .write_info(crate::cli::code_blocks::status_message());
+                        self.render_tui().await?;
+                    }
+                    Command::CopyBlock(index) => {
+                        self.output_manager
+                            .write_info(crate::cli::code_blocks::copy_status(index));
+                        self.render_tui().await?;
+                    }
+                    Command::ThinkingToggle => {
+                        let display = crate::cli::thinking::toggle();
+                        self.output_manager
+                            .write_info(crate::cli::thinking::status_message(display));
+                        self.render_tui().await?;
+                    }
+                    Command::Done => {
+                        let summary = self.session_changes.summary_since(self.done_checkpoint);
+                        self.done_checkpoint = self.session_changes.checkpoint();
+                        if summary.is_empty() {
+                            self.output_manager.write_info("No tool calls since the last /done.");
+                        } else {
+                            self.output_manager.write_info(summary.format());
+                        }
+                        self.clear_task_approvals().await;
+
+                        if matches!(*self.mode.read().await, ReplMode::Planning { .. } | ReplMode::Executing { .. }) {
+                            *self.mode.write().await = ReplMode::Normal;
+                            self.output_manager.write_info("✅ Exited plan mode. Returned to normal mode.");
+                            self.update_plan_mode_indicator(&ReplMode::Normal);
+                        }
+                        self.render_tui().await?;
+                    }
+                    Command::Tools => {
+                        self.handle_tools_list().await?;
+                    }
+                    Command::ReplayTool(number) => {
+                        let replay = self
+                            .tool_coordinator
+                            .tool_executor()
+                            .lock()
+                            .await
+                            .recent_calls()
+                            .replay(number);
+                        match replay {
+                            Some(tool_use) => {
+                                self.output_manager.write_info(format!(
+                                    "↻ Replaying #{}: {}",
+                                    number,
+                                    crate::tools::recent_calls::describe(&tool_use)
+                                ));
+                                // Its own query ID, so the result isn't sent to the model
+                                let query_id = Uuid::new_v4();
+                                self.tool_replays.insert(query_id);
+                                self.tool_coordinator.spawn_tool_execution(query_id, tool_use);
+                            }
+                            None => self.output_manager.write_info(format!(
+                                "No tool call #{} (see /replay-tool list).",
+                                number
+                            )),
+                        }
+                        self.render_tui().await?;
+                    }
+                    Command::ReplayToolList => {
+                        let list = self
+                            .tool_coordinator
+                            .tool_executor()
+                            .lock()
+                            .await
+                            .recent_calls()
+                            .format_list();
+                        self.output_manager.write_info(list);
+                        self.render_tui().await?;
+                    }
+                    Command::BenchmarkTools { network } => {
+                        self.output_manager.write_info(if network {
+                            "Running tool self-test (including network)..."
+                        } else {
+                            "Running tool self-test..."
+                        });
+                        self.render_tui().await?;
+                        let tool_executor = self.tool_coordinator.tool_executor();
+                        let executor_guard = tool_executor.lock().await;
+                        let result =
+                            crate::tools::self_test::run_self_test(executor_guard.registry(), network).await;
+                        drop(executor_guard);
+                        match result {
+                            Ok(checks) => self
+                                .output_manager
+                                .write_info(crate::tools::self_test::format_report(&checks)),
+                            Err(e) => self
+                                .output_manager
+                                .write_error(format!("Tool self-test failed: {}", e)),
+                        }
+                        self.render_tui().await?;
+                    }
+                    Command::ToolsToggle(enabled) => {
+                        self.tools_enabled = enabled;
+                        self.status_bar.update_tools_enabled(enabled);
+                        self.output_manager.write_info(if enabled {
+                            "🔧 Tools enabled for this session."
+                        } else {
+                            "🔧 Tools disabled for this session (plan mode still uses tools).\n\
+                             Use /with-tools <query> to allow tools for a single query."
+                        });
+                        self.render_tui().await?;
+                    }
+                    Command::DryRun(enabled) => {
+                        self.tool_coordinator.tool_executor().lock().await.set_bash_dry_run(enabled);
+                        self.output_manager
+                            .write_info(crate::cli::commands::dry_run_status(enabled));
+                        self.render_tui().await?;
+                    }
+                    Command::DryRunStatus => {
+                        let enabled = self.tool_coordinator.tool_executor().lock().await.bash_dry_run();
+                        self.output_manager
+                            .write_info(crate::cli::commands::dry_run_status(enabled));
+                        self.render_tui().await?;
+                    }
+                    Command::ToolsOverride { query, enabled } => {
+                        self.submit_query(query, Some(enabled), false).await?;
+                    }
                     Command::McpList => {
                         // List connected MCP servers
                         self.handle_mcp_list().await?;
@@ -396,15 +951,43 @@ impl EventLoop {
         // Echo user input to output buffer
         self.output_manager.write_user(input.clone());
 
+        self.submit_query(input, None, false).await
+    }
+
+    /// Add a user query to the conversation and start processing it
+    ///
+    /// `tools_override` forces tool definitions on/off for this query only;
+    /// `force_forward` skips routing and sends it to the teacher. Pending
+    /// /image attachments go with the query, which always forwards it (the
+    /// local model can't see images).
+    async fn submit_query(
+        &mut self,
+        input: String,
+        tools_override: Option<bool>,
+        force_forward: bool,
+    ) -> Result<()> {
+        let images = std::mem::take(&mut self.pending_images);
+        let force_forward = force_forward || !images.is_empty();
+
         // Create a new query
         let conversation_snapshot = self.conversation.read().await.snapshot();
         let query_id = self.query_states.create_query(conversation_snapshot).await;
+        profile::begin(query_id, &input);
+        crate::metrics::audit::begin(query_id, &input);
+        if let Some(enabled) = tools_override {
+            self.query_states.set_tools_override(query_id, enabled).await;
+        }
+        if force_forward {
+            self.query_states.set_force_forward(query_id).await;
+        }
+        self.query_states
+            .set_changes_checkpoint(query_id, self.session_changes.checkpoint())
+            .await;
 
         // Add user message to conversation
-        self.conversation
-            .write()
-            .await
-            .add_user_message(input.clone());
+        let mut message = crate::claude::Message::user(input.clone());
+        message.content.extend(images);
+        self.conversation.write().await.add_message(message);
 
         // Update compaction percentage in status bar
         self.update_compaction_status().await;
@@ -418,6 +1001,59 @@ impl EventLoop {
         Ok(())
     }
 
+    /// Re-run the watched prompt for a file change, unless the guard says not to
+    async fn handle_watch_change(&mut self, paths: Vec<std::path::PathBuf>) -> Result<()> {
+        let busy = self.active_query_id.read().await.is_some();
+        let Some(watch) = self.watch.as_mut() else {
+            return Ok(());
+        };
+        match watch.guard.on_change(Instant::now(), busy) {
+            WatchDecision::Run(run) => {
+                let prompt = watch.spec.prompt.clone();
+                self.output_manager.write_info(crate::cli::watch::run_message(
+                    &paths,
+                    run,
+                    watch.guard.max_runs(),
+                ));
+                self.output_manager.write_user(prompt.clone());
+                self.submit_query(prompt, None, false).await?;
+            }
+            WatchDecision::Skip => {
+                tracing::debug!("[WATCH] Ignoring change during run or cooldown: {:?}", paths);
+            }
+            WatchDecision::Exhausted => {
+                let max_runs = watch.guard.max_runs();
+                self.stop_watch(&format!("reached {} runs", max_runs));
+            }
+        }
+        self.render_tui().await?;
+        Ok(())
+    }
+
+    /// Number the response's code blocks for /copy and show the shortcut
+    fn update_code_blocks(&self, response: &str) {
+        use crate::cli::StatusLineType;
+
+        let line = StatusLineType::Custom("code_blocks".to_string());
+        match crate::cli::code_blocks::status_hint(crate::cli::code_blocks::set_latest(response)) {
+            Some(hint) => self.status_bar.update_line(line, hint),
+            None => self.status_bar.remove_line(&line),
+        }
+    }
+
+    /// End the active /watch, if any, saying why
+    fn stop_watch(&mut self, reason: &str) {
+        if let Some(watch) = self.watch.take() {
+            self.output_manager.write_info(format!(
+                "👀 Stopped watching {} ({}, {} run{})",
+                watch.spec.pattern,
+                reason,
+                watch.guard.runs(),
+                if watch.guard.runs() == 1 { "" } else { "s" }
+            ));
+        }
+    }
+
     /// Handle /local command - query local model directly (bypass routing)
     async fn handle_local_query(&mut self, query: String) -> Result<()> {
         use crate::cli::messages::StreamingResponseMessage;
@@ -465,6 +1101,74 @@ impl EventLoop {
         Ok(())
     }
 
+    /// Handle /model-info - describe the local model (the daemon's, when connected)
+    async fn handle_model_info(&mut self) -> Result<()> {
+        let info = match &self.daemon_client {
+            Some(daemon_client) => daemon_client.model_info().await,
+            None => Ok(crate::local::model_info(&self.generator_state, &self.local_generator).await),
+        };
+        match info {
+            Ok(info) => self.output_manager.write_info(info.format()),
+            Err(e) => self.output_manager.write_error(format!("Failed to get model info: {}", e)),
+        }
+        self.render_tui().await?;
+
+        Ok(())
+    }
+
+    /// Handle /training pause|resume|cancel - control daemon background training
+    async fn handle_training_control(&mut self, action: crate::server::TrainingAction) -> Result<()> {
+        if let Some(daemon_client) = &self.daemon_client {
+            match daemon_client.training_control(action).await {
+                Ok(state) => {
+                    self.output_manager.write_info(format!(
+                        "Training {}: worker is now {}",
+                        action.as_str(),
+                        state
+                    ));
+                    update_training_indicator(&self.status_bar, &state);
+                }
+                Err(e) => {
+                    self.output_manager.write_error(format!("Training {} failed: {}", action.as_str(), e));
+                }
+            }
+        } else {
+            self.output_manager.write_error("Error: training control requires daemon mode.");
+            self.output_manager.write_info("    Background training runs in the daemon: shammah daemon-start");
+        }
+        self.render_tui().await?;
+
+        Ok(())
+    }
+
+    /// Refresh the training state indicator from the daemon (non-blocking)
+    fn refresh_training_status(&self) {
+        if let Some(daemon_client) = &self.daemon_client {
+            let daemon_client = Arc::clone(daemon_client);
+            let status_bar = Arc::clone(&self.status_bar);
+            tokio::spawn(async move {
+                match daemon_client.training_state().await {
+                    Ok(state) => update_training_indicator(&status_bar, &state),
+                    Err(e) => tracing::debug!("Failed to refresh training state: {}", e),
+                }
+            });
+        }
+    }
+
+    /// Handle /tools command - list tools with permission status and usage
+    async fn handle_tools_list(&mut self) -> Result<()> {
+        let mode = self.mode.read().await.clone();
+        let tool_executor = self.tool_coordinator.tool_executor();
+        let output = crate::cli::commands::tools_listing(&*tool_executor.lock().await, |name| {
+            Self::is_tool_allowed_in_mode(name, &mode)
+        })
+        .await;
+
+        self.output_manager.write_info(output);
+        self.render_tui().await?;
+        Ok(())
+    }
+
     /// Handle /mcp list command - list connected MCP servers
     async fn handle_mcp_list(&mut self) -> Result<()> {
         let tool_executor = self.tool_coordinator.tool_executor();
@@ -590,6 +1294,152 @@ impl EventLoop {
         Ok(())
     }
 
+    /// Handle /vary [n] - regenerate the last response n times and let the
+    /// user pick one, which replaces it in the conversation
+    async fn handle_vary(&mut self, count: usize) -> Result<()> {
+        use crate::cli::tui::{Dialog, DialogOption, DialogResult};
+        use crate::cli::vary::{generate_variants, preview, variant_label, MAX_VARIANTS};
+
+        let Some(context) = self.conversation.read().await.last_prompt_context() else {
+            self.output_manager.write_info("Nothing to vary yet - send a prompt first.");
+            self.render_tui().await?;
+            return Ok(());
+        };
+        if count > MAX_VARIANTS {
+            self.output_manager
+                .write_info(format!("Generating {} variants (the maximum)", MAX_VARIANTS));
+        }
+        let count = count.min(MAX_VARIANTS);
+
+        // Vary with the model that would answer this prompt
+        let query = context.last().map(|message| message.text()).unwrap_or_default();
+        let use_local = !self.forward_mode
+            && self.generator_state.read().await.is_ready()
+            && matches!(
+                self.router.route(&query),
+                crate::router::RouteDecision::Local { confidence, .. } if confidence > 0.7
+            );
+        let generator = if use_local {
+            Arc::clone(&self.qwen_gen)
+        } else {
+            Arc::clone(&self.claude_gen)
+        };
+
+        let conversation = Arc::clone(&self.conversation);
+        let tui_renderer = Arc::clone(&self.tui_renderer);
+        let output_manager = Arc::clone(&self.output_manager);
+        let status_bar = Arc::clone(&self.status_bar);
+
+        tokio::spawn(async move {
+            let result = generate_variants(generator.as_ref(), context, count, |number| {
+                status_bar.update_operation(format!(
+                    "🎲 Generating variant {}/{} ({})",
+                    number,
+                    count,
+                    generator.name()
+                ));
+            })
+            .await;
+            status_bar.clear_operation();
+
+            let mut variants = match result {
+                Ok(variants) => variants,
+                Err(e) => {
+                    output_manager.write_error(format!("Failed to generate variants: {}", e));
+                    return;
+                }
+            };
+
+            let options = variants
+                .iter()
+                .enumerate()
+                .map(|(i, text)| DialogOption::with_description(variant_label(i + 1, text), preview(text)))
+                .collect();
+            let dialog = Dialog::select(format!("Pick a response ({} variants)", variants.len()), options)
+                .with_help("Use ↑↓ or j/k to navigate, Enter to use a variant, Esc to keep the current response");
+
+            let selection = tui_renderer.lock().await.show_dialog(dialog);
+            match selection {
+                Ok(DialogResult::Selected(index)) if index < variants.len() => {
+                    let chosen = variants.swap_remove(index);
+                    conversation.write().await.replace_last_response(chosen.clone());
+                    output_manager.write_info(format!("✓ Using variant {}", index + 1));
+                    output_manager.write_response(chosen);
+                }
+                _ => output_manager.write_info("Kept the current response."),
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Handle /retry - send the last prompt again, straight to the teacher
+    ///
+    /// The previous exchange is dropped first so the prompt isn't in the
+    /// conversation twice; the new answer takes the old response's place.
+    async fn handle_retry(&mut self) -> Result<()> {
+        let prompt = self
+            .conversation
+            .read()
+            .await
+            .last_prompt_context()
+            .and_then(|context| context.last().map(|message| message.text()));
+        let Some(prompt) = prompt else {
+            self.output_manager.write_info("Nothing to retry yet - send a prompt first.");
+            self.render_tui().await?;
+            return Ok(());
+        };
+
+        self.conversation.write().await.remove_last_exchange(&prompt);
+        self.output_manager.write_info("🔁 Retrying with the teacher");
+        self.output_manager.write_user(prompt.clone());
+        self.submit_query(prompt, None, true).await
+    }
+
+    /// Handle /summarize <path> - summarize a file in the background, with
+    /// the local model when it's ready and the file is short
+    async fn handle_summarize(
+        &mut self,
+        path: String,
+        detail: crate::cli::summarize::SummaryDetail,
+    ) -> Result<()> {
+        use crate::cli::summarize::summarize_file;
+
+        let local = if !self.forward_mode && self.generator_state.read().await.is_ready() {
+            Some(Arc::clone(&self.qwen_gen))
+        } else {
+            None
+        };
+        let teacher = Arc::clone(&self.claude_gen);
+        let output_manager = Arc::clone(&self.output_manager);
+        let status_bar = Arc::clone(&self.status_bar);
+
+        status_bar.update_operation(format!("📄 Summarizing {}", path));
+        tokio::spawn(async move {
+            let result = summarize_file(&path, detail, local.as_deref(), teacher.as_ref(), |chunk, chunks| {
+                if chunks > 1 {
+                    status_bar.update_operation(format!("📄 Summarizing {} (part {}/{})", path, chunk, chunks));
+                }
+            })
+            .await;
+            status_bar.clear_operation();
+
+            match result {
+                Ok(summary) => {
+                    let parts = match summary.chunks {
+                        1 => String::new(),
+                        chunks => format!(", {} parts", chunks),
+                    };
+                    output_manager.write_info(format!("📄 Summary of {} ({}{})", path, summary.generator, parts));
+                    output_manager.write_response(summary.text);
+                }
+                Err(e) => output_manager.write_error(format!("Failed to summarize {}: {}", path, e)),
+            }
+        });
+
+        Ok(())
+    }
+
     /// Spawn a background task to process a query
     async fn spawn_query_task(&self, query_id: Uuid, query: String) {
         let event_tx = self.event_tx.clone();
@@ -597,7 +1447,17 @@ impl EventLoop {
         let qwen_gen = Arc::clone(&self.qwen_gen);
         let router = Arc::clone(&self.router);
         let generator_state = Arc::clone(&self.generator_state);
-        let tool_definitions = Arc::clone(&self.tool_definitions);
+        let tool_definitions = if self.tools_enabled_for(query_id).await {
+            Some(Arc::clone(&self.tool_definitions))
+        } else {
+            None
+        };
+        let force_forward = self.forward_mode
+            || self
+                .query_states
+                .get_metadata(query_id)
+                .await
+                .is_some_and(|metadata| metadata.force_forward);
         let conversation = Arc::clone(&self.conversation);
         let query_states = Arc::clone(&self.query_states);
         let tool_coordinator = self.tool_coordinator.clone();
@@ -605,9 +1465,17 @@ impl EventLoop {
         let mode = Arc::clone(&self.mode);
         let output_manager = Arc::clone(&self.output_manager);
         let status_bar = Arc::clone(&self.status_bar);
+        let local_wait = self.local_wait;
+        let local_timeout = self.local_timeout;
+        let cancellation_token = self
+            .query_states
+            .get_metadata(query_id)
+            .await
+            .map(|metadata| metadata.cancellation_token)
+            .unwrap_or_default();
 
         tokio::spawn(async move {
-            Self::process_query_with_tools(
+            let process = Self::process_query_with_tools(
                 query_id,
                 query,
                 event_tx,
@@ -616,6 +1484,7 @@ impl EventLoop {
                 router,
                 generator_state,
                 tool_definitions,
+                force_forward,
                 conversation,
                 query_states,
                 tool_coordinator,
@@ -623,11 +1492,48 @@ impl EventLoop {
                 mode,
                 output_manager,
                 status_bar,
-            )
-            .await;
+                local_wait,
+                local_timeout,
+            );
+            // Ctrl+C drops the query wherever it is (teacher stream, tools,
+            // waiting for approval). Polling the query first lets an active
+            // stream mark its message cancelled before it's dropped.
+            tokio::select! {
+                biased;
+                _ = process => {}
+                _ = cancellation_token.cancelled() => {
+                    tracing::debug!("Query {} task dropped after cancellation", query_id);
+                }
+            }
         });
     }
 
+    /// Whether tool definitions should be attached to this query's requests
+    ///
+    /// A one-shot override always wins. Otherwise plan mode and plan execution
+    /// keep tools (they depend on them), and normal mode uses the session toggle.
+    async fn tools_enabled_for(&self, query_id: Uuid) -> bool {
+        let tools_override = self
+            .query_states
+            .get_metadata(query_id)
+            .await
+            .and_then(|metadata| metadata.tools_override);
+
+        match tools_override {
+            Some(enabled) => enabled,
+            None => self.session_tools_enabled().await,
+        }
+    }
+
+    /// Whether queries without a one-shot override get tool definitions
+    async fn session_tools_enabled(&self) -> bool {
+        self.tools_enabled
+            || matches!(
+                *self.mode.read().await,
+                ReplMode::Planning { .. } | ReplMode::Executing { .. }
+            )
+    }
+
     /// Process a query with potential tool execution loop using unified generators
     #[allow(clippy::too_many_arguments)]
     async fn process_query_with_tools(
@@ -638,7 +1544,8 @@ impl EventLoop {
         qwen_gen: Arc<dyn Generator>,
         router: Arc<Router>,
         generator_state: Arc<RwLock<GeneratorState>>,
-        tool_definitions: Arc<Vec<ToolDefinition>>,
+        tool_definitions: Option<Arc<Vec<ToolDefinition>>>,
+        force_forward: bool,
         conversation: Arc<RwLock<ConversationHistory>>,
         query_states: Arc<QueryStateManager>,
         tool_coordinator: ToolExecutionCoordinator,
@@ -646,20 +1553,93 @@ impl EventLoop {
         mode: Arc<RwLock<ReplMode>>,
         output_manager: Arc<OutputManager>,
         status_bar: Arc<crate::cli::StatusBar>,
+        local_wait: Option<Duration>,
+        local_timeout: Option<Duration>,
     ) {
         tracing::debug!("process_query_with_tools starting for query_id: {:?}", query_id);
 
+        // Crisis queries get the configured response or go to the teacher
+        let crisis = router.check_crisis(&query);
+        if let Some(response) = crisis.as_ref().and_then(|c| c.canned_response()) {
+            crate::metrics::audit::set_route(query_id, "crisis");
+            let _ = event_tx.send(ReplEvent::QueryComplete {
+                query_id,
+                response: response.to_string(),
+            });
+            return;
+        }
+
         // Step 1: Routing decision
+        let routing_span = profile::span(query_id, "routing");
         let generator: Arc<dyn Generator> = {
             // Check if Qwen is ready
             let state = generator_state.read().await;
-            let qwen_ready = state.is_ready();
+            let mut qwen_ready = state.is_ready();
             drop(state);
 
+            // Optionally hold the query until the local model finishes loading
+            if let Some(timeout) =
+                local_wait.filter(|_| !qwen_ready && !force_forward && crisis.is_none())
+            {
+                let outcome = wait_for_ready(
+                    &generator_state,
+                    timeout,
+                    || {
+                        let query_states = Arc::clone(&query_states);
+                        async move {
+                            matches!(
+                                query_states.get_state(query_id).await,
+                                Some(QueryState::Failed { .. } | QueryState::Cancelled) | None
+                            )
+                        }
+                    },
+                    |state, waited| {
+                        status_bar.update_operation(format!(
+                            "⏳ Waiting for local model ({}s/{}s): {}",
+                            waited.as_secs(),
+                            timeout.as_secs(),
+                            state.status_message()
+                        ));
+                    },
+                )
+                .await;
+                status_bar.clear_operation();
+
+                match outcome {
+                    LocalWaitOutcome::Ready => qwen_ready = true,
+                    // Ctrl+C already reported the cancellation
+                    LocalWaitOutcome::Cancelled => return,
+                    LocalWaitOutcome::TimedOut => output_manager.write_info(format!(
+                        "⚠️  Local model still loading after {}s - forwarding to the teacher",
+                        timeout.as_secs()
+                    )),
+                    LocalWaitOutcome::Unavailable(reason) => output_manager.write_info(format!(
+                        "⚠️  Local model unavailable ({}) - forwarding to the teacher",
+                        reason
+                    )),
+                }
+            }
+
             // Route based on readiness and confidence
             // NOTE: In daemon mode, these logs are misleading (daemon makes actual routing decision)
             // TODO: Detect daemon mode and skip client-side routing entirely
-            if qwen_ready {
+            if force_forward {
+                // User asked for the teacher (/forward or /mode forward)
+                router.route_forced_forward(&query);
+                Arc::clone(&claude_gen)
+            } else if crisis.is_some() {
+                // Never the local model, even with the teacher circuit open
+                router.route(&query);
+                output_manager.write_info("⚠️  Crisis check: MATCH - forwarding to the teacher");
+                Arc::clone(&claude_gen)
+            } else if qwen_ready && !claude_gen.is_available() {
+                // Teacher is failing fast (circuit open) - answer locally instead
+                tracing::info!("Client-side routing: Qwen (teacher circuit open)");
+                output_manager.write_info(
+                    "⚠️  Teacher unavailable (circuit open) - answering with the local model",
+                );
+                Arc::clone(&qwen_gen)
+            } else if qwen_ready {
                 match router.route(&query) {
                     crate::router::RouteDecision::Local { confidence, .. } if confidence > 0.7 => {
                         // Use Qwen
@@ -678,6 +1658,20 @@ impl EventLoop {
                 Arc::clone(&claude_gen)
             }
         };
+        drop(routing_span);
+        let generation_stage = if Arc::ptr_eq(&generator, &qwen_gen) {
+            "local generation"
+        } else {
+            "teacher"
+        };
+        crate::metrics::audit::set_route(
+            query_id,
+            match generation_stage {
+                "local generation" => "local",
+                _ if force_forward => "forward",
+                _ => "teacher",
+            },
+        );
 
         const MAX_TOOL_ITERATIONS: usize = 10;
         let mut iteration = 0;
@@ -696,6 +1690,7 @@ impl EventLoop {
             // Get conversation context
             let messages = conversation.read().await.get_messages();
             let caps = generator.capabilities();
+            let generation_span = profile::span(query_id, generation_stage);
 
             // Try streaming first if supported
             if caps.supports_streaming {
@@ -708,7 +1703,7 @@ impl EventLoop {
                 output_manager.add_trait_message(msg.clone() as Arc<dyn crate::cli::messages::Message>);
 
                 match generator
-                    .generate_stream(messages.clone(), Some((*tool_definitions).clone()))
+                    .generate_stream(messages.clone(), tool_definitions.as_ref().map(|defs| (**defs).clone()))
                     .await
                 {
                     Ok(Some(mut rx)) => {
@@ -718,15 +1713,42 @@ impl EventLoop {
                         // Process stream (handles tools via StreamChunk::ContentBlockComplete)
                         let mut blocks = Vec::new();
                         let mut text = String::new();
-
-                        while let Some(result) = rx.recv().await {
+                        let mut throughput = ThroughputMeter::new();
+                        let cancellation_token = query_states
+                            .get_metadata(query_id)
+                            .await
+                            .map(|metadata| metadata.cancellation_token)
+                            .unwrap_or_default();
+
+                        loop {
+                            let result = tokio::select! {
+                                result = rx.recv() => result,
+                                _ = cancellation_token.cancelled() => {
+                                    // Dropping the receiver stops the teacher stream
+                                    msg.set_failed();
+                                    status_bar.clear_throughput();
+                                    return;
+                                }
+                            };
+                            let Some(result) = result else {
+                                break;
+                            };
                             match result {
                                 Ok(StreamChunk::TextDelta(delta)) => {
                                     tracing::debug!("Received TextDelta: {} bytes", delta.len());
                                     text.push_str(&delta);
+                                    throughput.record_text(&delta);
+                                    status_bar.update_throughput(&mut throughput);
                                     // Update message directly - no event needed
                                     msg.append_chunk(&delta);
                                 }
+                                Ok(StreamChunk::OutputTokens(tokens)) => {
+                                    throughput.record_tokens(tokens);
+                                }
+                                Ok(StreamChunk::StopReason(_)) => {}
+                                Ok(StreamChunk::ThinkingDelta(thinking)) => {
+                                    msg.append_thinking_text(&thinking);
+                                }
                                 Ok(StreamChunk::ContentBlockComplete(block)) => {
                                     tracing::debug!("Received ContentBlockComplete: {:?}", block);
                                     blocks.push(block);
@@ -734,6 +1756,7 @@ impl EventLoop {
                                 Err(e) => {
                                     tracing::error!("Stream error in event loop: {}", e);
                                     msg.set_failed();
+                                    status_bar.clear_throughput();
                                     let _ = event_tx.send(ReplEvent::QueryFailed {
                                         query_id,
                                         error: format!("{}", e),
@@ -748,12 +1771,14 @@ impl EventLoop {
 
                         // Mark message as complete
                         msg.set_complete();
+                        status_bar.clear_throughput();
+                        drop(generation_span);
 
-                        // Send stats update with basic info (streaming doesn't provide token counts)
+                        // Send stats update with basic info (output tokens are estimated unless the provider counted them)
                         let _ = event_tx.send(ReplEvent::StatsUpdate {
                             model: "streaming".to_string(),  // TODO: Get actual model name from generator
                             input_tokens: None,  // Not available in streaming
-                            output_tokens: Some(text.split_whitespace().count() as u32),  // Rough estimate
+                            output_tokens: Some(throughput.tokens()),
                             latency_ms: None,  // TODO: Track timing
                         });
 
@@ -811,7 +1836,7 @@ impl EventLoop {
                                     let error_msg = format!(
                                         "Tool '{}' is not allowed in planning mode.\n\
                                          Reason: This tool can modify system state.\n\
-                                         Available tools: read, glob, grep, web_fetch\n\
+                                         Available tools: read, glob, grep, list_dir, web_fetch\n\
                                          Type /approve to execute your plan with all tools enabled.",
                                         tool_use.name
                                     );
@@ -876,10 +1901,30 @@ impl EventLoop {
             }
 
             // Non-streaming path (for Qwen or fallback)
-            match generator
-                .generate(messages, Some((*tool_definitions).clone()))
-                .await
-            {
+            let tools = tool_definitions.as_ref().map(|defs| (**defs).clone());
+            let result = match local_timeout.filter(|_| Arc::ptr_eq(&generator, &qwen_gen)) {
+                Some(timeout) => {
+                    use crate::local::timeout::{generate_with_fallback, Answered};
+                    match generate_with_fallback(qwen_gen.as_ref(), claude_gen.as_ref(), messages, tools, timeout).await {
+                        Ok((response, Answered::TeacherAfterTimeout)) => {
+                            router.route_local_timeout(&query, timeout);
+                            crate::metrics::audit::set_route(
+                                query_id,
+                                crate::router::ForwardReason::LocalTimeout.as_str(),
+                            );
+                            output_manager.write_info(format!(
+                                "⏱  Local model took longer than {}ms - answered by the teacher",
+                                timeout.as_millis()
+                            ));
+                            Ok(response)
+                        }
+                        result => result.map(|(response, _)| response),
+                    }
+                }
+                None => generator.generate(messages, tools).await,
+            };
+            drop(generation_span);
+            match result {
                 Ok(response) => {
                     // Send stats update
                     let _ = event_tx.send(ReplEvent::StatsUpdate {
@@ -935,7 +1980,7 @@ impl EventLoop {
                                 let error_msg = format!(
                                     "Tool '{}' is not allowed in planning mode.\n\
                                      Reason: This tool can modify system state.\n\
-                                     Available tools: read, glob, grep, web_fetch\n\
+                                     Available tools: read, glob, grep, list_dir, web_fetch\n\
                                      Type /approve to execute your plan with all tools enabled.",
                                     tool_use.name
                                 );
@@ -1008,6 +2053,8 @@ impl EventLoop {
 
                 // Update compaction percentage in status bar
                 self.update_compaction_status().await;
+                self.maybe_auto_compact().await;
+                self.status_bar.update_temperature_override(self.temperature.get());
 
                 // Update query state
                 self.query_states
@@ -1016,6 +2063,17 @@ impl EventLoop {
 
                 // Display response
                 self.output_manager.write_response(&response);
+                crate::cli::tts::speak(&response);
+                self.update_code_blocks(&response);
+                self.render_tui().await?;
+
+                // Clear active query
+                {
+                    let mut active = self.active_query_id.write().await;
+                    if *active == Some(query_id) {
+                        *active = None;
+                    }
+                }
             }
 
             ReplEvent::QueryFailed { query_id, error } => {
@@ -1026,6 +2084,7 @@ impl EventLoop {
                 self.query_states
                     .update_state(query_id, QueryState::Failed { error: error.clone() })
                     .await;
+                self.status_bar.update_temperature_override(self.temperature.get());
 
                 // Display error
                 self.output_manager.write_error(format!("Query failed: {}", error));
@@ -1065,14 +2124,14 @@ impl EventLoop {
 
                 // Clear streaming status
                 self.status_bar.clear_operation();
+                self.status_bar.update_temperature_override(self.temperature.get());
 
                 // Check if this query is executing tools
                 // If so, the assistant message was already added with ToolUse blocks
-                let is_executing_tools = if let Some(metadata) = self.query_states.get_metadata(query_id).await {
-                    matches!(metadata.state, QueryState::ExecutingTools { .. })
-                } else {
-                    false
-                };
+                let metadata = self.query_states.get_metadata(query_id).await;
+                let is_executing_tools = metadata
+                    .as_ref()
+                    .is_some_and(|m| matches!(m.state, QueryState::ExecutingTools { .. }));
 
                 if !is_executing_tools {
                     tracing::debug!("[EVENT_LOOP] No tools, adding assistant message to conversation");
@@ -1088,6 +2147,17 @@ impl EventLoop {
                         .update_state(query_id, QueryState::Completed { response: full_response.clone() })
                         .await;
                     tracing::debug!("[EVENT_LOOP] Updated query state");
+
+                    // Recap what a tool-heavy query did
+                    if let Some(metadata) = &metadata {
+                        let summary = self.session_changes.summary_since(metadata.changes_checkpoint);
+                        if summary.tool_calls >= SUMMARY_MIN_TOOL_CALLS {
+                            self.output_manager.write_info(summary.format());
+                        }
+                    }
+
+                    crate::cli::tts::speak(&full_response);
+                    self.update_code_blocks(&full_response);
                 } else {
                     tracing::debug!("[EVENT_LOOP] Tools executing, skipping duplicate message");
                 }
@@ -1122,7 +2192,13 @@ impl EventLoop {
                 self.render_tui().await?;
             }
 
+            ReplEvent::WatchChanged { paths } => {
+                self.handle_watch_change(paths).await?;
+            }
+
             ReplEvent::CancelQuery => {
+                self.stop_watch("cancelled");
+
                 // Get the active query ID
                 let query_id = {
                     let active = self.active_query_id.read().await;
@@ -1130,24 +2206,27 @@ impl EventLoop {
                 };
 
                 if let Some(qid) = query_id {
-                    // Update query state to cancelled
-                    self.query_states
-                        .update_state(qid, QueryState::Failed {
-                            error: "Cancelled by user".to_string(),
-                        })
-                        .await;
+                    // Fire the query's cancellation token (aborts its task and
+                    // drops the teacher stream), mark it cancelled and close
+                    // the tool calls it was running
+                    cancel_query(&self.query_states, &self.conversation, &self.tool_results, qid).await;
+                    self.pending_approvals.write().await.remove(&qid);
 
                     // Clear active query
                     *self.active_query_id.write().await = None;
 
                     // Show cancellation message
-                    self.output_manager.write_info("⚠️  Query cancelled by user (Ctrl+C)");
+                    self.output_manager.write_info("⨯ cancelled (Ctrl+C again to exit)");
                     self.status_bar.clear_operation();
                     self.render_tui().await?;
 
                     tracing::info!("Query {} cancelled by user", qid);
                 } else {
-                    tracing::debug!("Ctrl+C pressed but no active query to cancel");
+                    // Nothing running: Ctrl+C at the idle prompt exits
+                    tracing::debug!("Ctrl+C at idle prompt, exiting");
+                    self.event_tx
+                        .send(ReplEvent::Shutdown)
+                        .context("Failed to send shutdown event")?;
                 }
             }
 
@@ -1162,6 +2241,7 @@ impl EventLoop {
 
     /// Render the TUI
     async fn render_tui(&self) -> Result<()> {
+        let started = std::time::Instant::now();
         let mut tui = self.tui_renderer.lock().await;
 
         // Check if recovery needed from previous render failure
@@ -1177,6 +2257,7 @@ impl EventLoop {
         tui.check_and_refresh()?;
         // Actually render the TUI after flushing output
         tui.render()?;
+        profile::record_render(started.elapsed());
         Ok(())
     }
 
@@ -1189,17 +2270,128 @@ impl EventLoop {
 
     /// Update the compaction percentage in the status bar
     async fn update_compaction_status(&self) {
-        let conversation = self.conversation.read().await;
-        let percent_remaining = conversation.compaction_percent_remaining();
+        show_compaction_status(&self.status_bar, &*self.conversation.read().await);
+    }
+
+    /// Summarize all but the last `turns` exchanges with the teacher (/compact)
+    ///
+    /// Runs in the background like auto-compaction, and shares its guard so
+    /// the two never overlap.
+    async fn handle_compact(&self, turns: usize) {
+        let (snapshot, turns_before) = {
+            let conversation = self.conversation.read().await;
+            (conversation.snapshot(), conversation.exchange_count())
+        };
+        if recent_turns_start(&snapshot, turns) == 0 {
+            self.output_manager.write_info(format!(
+                "Nothing to compact - the conversation has no more than {} turn{}.",
+                turns,
+                if turns == 1 { "" } else { "s" }
+            ));
+            return;
+        }
+        if self.compacting.swap(true, Ordering::SeqCst) {
+            self.output_manager.write_info("Already compacting the conversation.");
+            return;
+        }
 
-        // Format percentage (0-100%)
-        let percent_display = (percent_remaining * 100.0) as u8;
+        let conversation = Arc::clone(&self.conversation);
+        let generator = Arc::clone(&self.claude_gen);
+        let output_manager = Arc::clone(&self.output_manager);
+        let status_bar = Arc::clone(&self.status_bar);
+        let compacting = Arc::clone(&self.compacting);
 
-        // Update status bar with compaction percentage (matches Claude Code format)
-        self.status_bar.update_line(
-            crate::cli::status_bar::StatusLineType::CompactionPercent,
-            format!("Context left until auto-compact: {}%", percent_display),
-        );
+        status_bar.update_operation("🗜️  Compacting conversation");
+        tokio::spawn(async move {
+            let compactor = ConversationCompactor::new(generator.as_ref());
+            let result = compactor.summarize_turns(&snapshot, turns).await;
+            status_bar.clear_operation();
+
+            match result {
+                Ok(Some((summarized, summary))) => {
+                    let mut conversation = conversation.write().await;
+                    match conversation.apply_compaction(summarized, &summary) {
+                        Some(report) => {
+                            log_compaction(&report);
+                            output_manager.write_info(crate::cli::commands::compact_status(
+                                turns_before,
+                                turns,
+                                &report,
+                            ));
+                            show_compaction_status(&status_bar, &conversation);
+                        }
+                        None => output_manager
+                            .write_info("The conversation changed while compacting - summary discarded."),
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => output_manager.write_error(format!("Failed to compact the conversation: {}", e)),
+            }
+
+            compacting.store(false, Ordering::SeqCst);
+        });
+    }
+
+    /// Summarize older turns in the background once the context passes the
+    /// compaction threshold
+    ///
+    /// The conversation stays usable meanwhile; if it changes underneath
+    /// (cleared, trimmed) the summary is discarded.
+    async fn maybe_auto_compact(&self) {
+        let usage = {
+            let conversation = self.conversation.read().await;
+            if !conversation.should_compact() {
+                return;
+            }
+            conversation.context_usage_percent()
+        };
+        if self.compacting.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        tracing::info!("Context at {:.0}% - auto-compacting conversation", usage * 100.0);
+        self.output_manager
+            .write_info("🗜️  Context is nearly full - summarizing older turns...");
+
+        let conversation = Arc::clone(&self.conversation);
+        let generator = Arc::clone(&self.claude_gen);
+        let output_manager = Arc::clone(&self.output_manager);
+        let status_bar = Arc::clone(&self.status_bar);
+        let compacting = Arc::clone(&self.compacting);
+        let keep_turns = self.compaction.keep_turns;
+
+        tokio::spawn(async move {
+            let snapshot = conversation.read().await.snapshot();
+            let compactor = ConversationCompactor::with_keep_turns(generator.as_ref(), keep_turns);
+
+            match compactor.summarize(&snapshot).await {
+                Ok(Some((summarized, summary))) => {
+                    let mut conversation = conversation.write().await;
+                    match conversation.apply_compaction(summarized, &summary) {
+                        Some(report) => {
+                            log_compaction(&report);
+                            output_manager.write_info(format!(
+                                "🗜️  Compacted {} older messages into a summary (~{} tokens reclaimed).",
+                                summarized.len(),
+                                report.reclaimed_tokens()
+                            ));
+                            show_compaction_status(&status_bar, &conversation);
+                        }
+                        None => tracing::info!("Conversation changed while compacting - summary discarded"),
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::warn!("Auto-compaction failed: {}", e);
+                    output_manager.write_error(format!(
+                        "Auto-compaction failed ({}) - older turns will be trimmed instead.",
+                        e
+                    ));
+                }
+            }
+
+            compacting.store(false, Ordering::SeqCst);
+        });
     }
 
     /// Handle a tool result
@@ -1209,16 +2401,18 @@ impl EventLoop {
         tool_id: String,
         result: Result<String>,
     ) -> Result<()> {
+        if self.tool_replays.remove(&query_id) {
+            self.output_manager
+                .write_info(crate::tools::recent_calls::format_replay_result(&result));
+            return self.render_tui().await;
+        }
+
         // Display tool result
         match &result {
             Ok(content) => {
                 self.output_manager.write_tool(
                     &tool_id,
-                    format!("✓ Success ({})", if content.len() > 100 {
-                        format!("{}...", &content[..100])
-                    } else {
-                        content.clone()
-                    }),
+                    format!("✓ Success ({})", crate::text::ellipsize(content, 100)),
                 );
             }
             Err(e) => {
@@ -1232,6 +2426,13 @@ impl EventLoop {
         let current_mode = self.mode.read().await.clone();
         self.update_plan_mode_indicator(&current_mode);
 
+        // Results that arrive after the query was cancelled are dropped
+        let metadata = self.query_states.get_metadata(query_id).await;
+        let Some(QueryState::ExecutingTools { tools_pending, .. }) = metadata.map(|meta| meta.state) else {
+            tracing::debug!("Dropping result of tool {} (query {} isn't running tools)", tool_id, query_id);
+            return Ok(());
+        };
+
         // Store tool result
         self.tool_results
             .write()
@@ -1241,22 +2442,17 @@ impl EventLoop {
             .push((tool_id, result));
 
         // Check if all tools for this query have completed
-        let metadata = self.query_states.get_metadata(query_id).await;
-        if let Some(meta) = metadata {
-            if let QueryState::ExecutingTools { tools_pending, .. } = meta.state {
-                let results_count = self
-                    .tool_results
-                    .read()
-                    .await
-                    .get(&query_id)
-                    .map(|v| v.len())
-                    .unwrap_or(0);
+        let results_count = self
+            .tool_results
+            .read()
+            .await
+            .get(&query_id)
+            .map(|v| v.len())
+            .unwrap_or(0);
 
-                if results_count >= tools_pending {
-                    // All tools completed, format results and add to conversation
-                    self.finalize_tool_execution(query_id).await?;
-                }
-            }
+        if results_count >= tools_pending {
+            // All tools completed, format results and add to conversation
+            self.finalize_tool_execution(query_id).await?;
         }
 
         Ok(())
@@ -1329,7 +2525,7 @@ impl EventLoop {
         let summary = match tool_name.as_str() {
             "bash" | "Bash" => {
                 if let Some(cmd) = tool_use.input.get("command").and_then(|v| v.as_str()) {
-                    format!("Command: {}", if cmd.len() > 60 { format!("{}...", &cmd[..60]) } else { cmd.to_string() })
+                    format!("Command: {}", crate::text::ellipsize(cmd, 60))
                 } else {
                     "Execute shell command".to_string()
                 }
@@ -1343,7 +2539,7 @@ impl EventLoop {
             }
             "grep" | "Grep" => {
                 if let Some(pattern) = tool_use.input.get("pattern").and_then(|v| v.as_str()) {
-                    format!("Pattern: {}", if pattern.len() > 40 { format!("{}...", &pattern[..40]) } else { pattern.to_string() })
+                    format!("Pattern: {}", crate::text::ellipsize(pattern, 40))
                 } else {
                     "Search files".to_string()
                 }
@@ -1355,9 +2551,13 @@ impl EventLoop {
                     "Find files".to_string()
                 }
             }
+            "list_dir" => {
+                let path = tool_use.input.get("path").and_then(|v| v.as_str()).unwrap_or(".");
+                format!("List: {}", path)
+            }
             "EnterPlanMode" => {
                 if let Some(reason) = tool_use.input.get("reason").and_then(|v| v.as_str()) {
-                    format!("Reason: {}", if reason.len() > 50 { format!("{}...", &reason[..50]) } else { reason.to_string() })
+                    format!("Reason: {}", crate::text::ellipsize(reason, 50))
                 } else {
                     "Enter planning mode".to_string()
                 }
@@ -1365,14 +2565,22 @@ impl EventLoop {
             _ => format!("Execute {} tool", tool_name)
         };
 
-        let options = vec![
-            DialogOption::with_description("Allow Once", "Execute this tool once without saving approval"),
+        // Task-scoped options only while executing an approved plan
+        let in_task = matches!(*self.mode.read().await, ReplMode::Executing { .. });
+        self.approval_dialog_has_task_options = in_task;
+
+        let mut options = vec![DialogOption::with_description("Allow Once", "Execute this tool once without saving approval")];
+        if in_task {
+            options.push(DialogOption::with_description("Allow Exact (Task)", "Allow this exact tool call until the task is done"));
+            options.push(DialogOption::with_description("Allow Pattern (Task)", "Allow similar tool calls until the task is done"));
+        }
+        options.extend([
             DialogOption::with_description("Allow Exact (Session)", "Allow this exact tool call for this session"),
             DialogOption::with_description("Allow Pattern (Session)", "Allow similar tool calls for this session"),
             DialogOption::with_description("Allow Exact (Persistent)", "Always allow this exact tool call"),
             DialogOption::with_description("Allow Pattern (Persistent)", "Always allow similar tool calls"),
             DialogOption::with_description("Deny", "Do not execute this tool"),
-        ];
+        ]);
 
         let dialog = Dialog::select_with_custom(
             format!("Tool '{}' requires approval\n{}", tool_name, summary),
@@ -1398,6 +2606,11 @@ impl EventLoop {
         Ok(())
     }
 
+    /// Drop task-scoped approvals when the current task ends
+    async fn clear_task_approvals(&self) {
+        self.tool_coordinator.tool_executor().lock().await.clear_task_approvals();
+    }
+
     /// Convert dialog result to confirmation result
     fn dialog_result_to_confirmation(
         &self,
@@ -1408,37 +2621,42 @@ impl EventLoop {
         use crate::tools::executor::generate_tool_signature;
         use crate::tools::patterns::ToolPattern;
 
+        let auto_pattern = || {
+            ToolPattern::new(
+                format!("{}:*", tool_use.name),
+                tool_use.name.clone(),
+                format!("Auto-generated pattern for {}", tool_use.name),
+            )
+        };
+
         match dialog_result {
-            crate::cli::tui::DialogResult::Selected(index) => match index {
-                0 => ConfirmationResult::ApproveOnce,
-                1 => {
-                    let signature = generate_tool_signature(tool_use, std::path::Path::new("."));
-                    ConfirmationResult::ApproveExactSession(signature)
-                }
-                2 => {
-                    // Create pattern from tool use
-                    let pattern = ToolPattern::new(
-                        format!("{}:*", tool_use.name),
-                        tool_use.name.clone(),
-                        format!("Auto-generated pattern for {}", tool_use.name),
-                    );
-                    ConfirmationResult::ApprovePatternSession(pattern)
-                }
-                3 => {
-                    let signature = generate_tool_signature(tool_use, std::path::Path::new("."));
-                    ConfirmationResult::ApproveExactPersistent(signature)
-                }
-                4 => {
-                    // Create pattern from tool use
-                    let pattern = ToolPattern::new(
-                        format!("{}:*", tool_use.name),
-                        tool_use.name.clone(),
-                        format!("Auto-generated pattern for {}", tool_use.name),
-                    );
-                    ConfirmationResult::ApprovePatternPersistent(pattern)
+            crate::cli::tui::DialogResult::Selected(index) => {
+                // Without the two task options, later choices sit two slots higher
+                let index = if self.approval_dialog_has_task_options || index == 0 {
+                    index
+                } else {
+                    index + 2
+                };
+                match index {
+                    0 => ConfirmationResult::ApproveOnce,
+                    1 => {
+                        let signature = generate_tool_signature(tool_use, std::path::Path::new("."));
+                        ConfirmationResult::ApproveExactTask(signature)
+                    }
+                    2 => ConfirmationResult::ApprovePatternTask(auto_pattern()),
+                    3 => {
+                        let signature = generate_tool_signature(tool_use, std::path::Path::new("."));
+                        ConfirmationResult::ApproveExactSession(signature)
+                    }
+                    4 => ConfirmationResult::ApprovePatternSession(auto_pattern()),
+                    5 => {
+                        let signature = generate_tool_signature(tool_use, std::path::Path::new("."));
+                        ConfirmationResult::ApproveExactPersistent(signature)
+                    }
+                    6 => ConfirmationResult::ApprovePatternPersistent(auto_pattern()),
+                    _ => ConfirmationResult::Deny, // Deny or cancelled
                 }
-                _ => ConfirmationResult::Deny, // Index 5 or cancelled
-            },
+            }
             crate::cli::tui::DialogResult::CustomText(text) => {
                 // User provided custom response - log it and deny for safety
                 tracing::info!("Tool approval custom response: {}", text);
@@ -1460,10 +2678,7 @@ impl EventLoop {
             ReplMode::Executing { .. } => "▶ executing plan (shift+tab disabled)",
         };
 
-        self.status_bar.update_line(
-            StatusLineType::Custom("plan_mode".to_string()),
-            indicator,
-        );
+        self.status_bar.update_line(StatusLineType::Mode, indicator);
     }
 
     /// Check if a tool is allowed in the current mode
@@ -1474,8 +2689,8 @@ impl EventLoop {
                 true
             }
             ReplMode::Planning { .. } => {
-                // Only inspection tools allowed
-                matches!(tool_name, "read" | "glob" | "grep" | "web_fetch")
+                // Only inspection tools allowed; write, edit and bash wait for plan approval
+                matches!(tool_name, "read" | "glob" | "grep" | "list_dir" | "web_fetch" | "web_search")
             }
         }
     }
@@ -1531,7 +2746,7 @@ impl EventLoop {
         self.output_manager.write_info(format!("📁 Plan will be saved to: {}", plan_path.display()));
         self.output_manager.write_info("");
         self.output_manager.write_info(format!("{}", "Available tools:".green()));
-        self.output_manager.write_info("  read, glob, grep, web_fetch");
+        self.output_manager.write_info("  read, glob, grep, list_dir, web_fetch");
         self.output_manager.write_info(format!("{}", "Blocked tools:".red()));
         self.output_manager.write_info("  bash, save_and_exec");
         self.output_manager.write_info("");
@@ -1544,7 +2759,7 @@ impl EventLoop {
         // Add mode change notification to conversation
         self.conversation.write().await.add_user_message(format!(
             "[System: Entered planning mode for task: {}]\n\
-             Available tools: read, glob, grep, web_fetch\n\
+             Available tools: read, glob, grep, list_dir, web_fetch\n\
              Blocked tools: bash, save_and_exec\n\
              Please explore the codebase and generate a detailed plan.",
             task
@@ -1758,8 +2973,11 @@ async fn handle_ask_user_question(
 
     match result {
         Ok(output) => {
-            // Serialize output as JSON
-            match serde_json::to_string_pretty(&output) {
+            if output.is_cancelled() {
+                tracing::debug!("[EVENT_LOOP] AskUserQuestion declined by user");
+            }
+            // Serialize output as JSON (a cancellation is a normal result too)
+            match output.to_tool_result() {
                 Ok(json) => Some(Ok(json)),
                 Err(e) => Some(Err(anyhow::anyhow!("Failed to serialize output: {}", e))),
             }
@@ -1769,3 +2987,121 @@ async fn handle_ask_user_question(
         }
     }
 }
+
+/// Show the daemon training state in the status bar (hidden while idle)
+fn update_training_indicator(status_bar: &StatusBar, state: &str) {
+    use crate::cli::status_bar::StatusLineType;
+
+    let line_type = StatusLineType::Custom("training_state".to_string());
+    match state {
+        "training" => status_bar.update_line(line_type, "🧠 LoRA training in progress (/training pause|cancel)"),
+        "paused" => status_bar.update_line(line_type, "⏸ LoRA training paused (/training resume)"),
+        _ => status_bar.remove_line(&line_type),
+    }
+}
+
+/// Show how many tools are running in the status bar (hidden when none are)
+fn update_tool_activity(status_bar: &StatusBar, slots: &ToolSlots) {
+    use crate::cli::status_bar::StatusLineType;
+
+    let line_type = StatusLineType::Custom("tools_in_flight".to_string());
+    match slots.in_flight() {
+        0 => status_bar.remove_line(&line_type),
+        running => status_bar.update_line(
+            line_type,
+            format!("🔧 {}/{} tools running", running, slots.limit()),
+        ),
+    }
+}
+
+/// Show how much context is left before auto-compaction in the status bar
+fn show_compaction_status(status_bar: &StatusBar, conversation: &ConversationHistory) {
+    let percent_remaining = conversation.compaction_percent_remaining();
+
+    // Format percentage (0-100%)
+    let percent_display = (percent_remaining * 100.0) as u8;
+
+    // Update status bar with compaction percentage (matches Claude Code format)
+    status_bar.update_line(
+        crate::cli::status_bar::StatusLineType::CompactionPercent,
+        format!("Context left until auto-compact: {}%", percent_display),
+    );
+}
+
+/// Cancel `query_id` and close any tool calls it left open
+///
+/// A query cancelled while its tools run (or wait for approval) already has
+/// its assistant tool_use turn in the conversation; each call gets an error
+/// result so the next request is still valid, and results collected so far
+/// are dropped.
+async fn cancel_query(
+    query_states: &QueryStateManager,
+    conversation: &RwLock<ConversationHistory>,
+    tool_results: &RwLock<ToolResults>,
+    query_id: Uuid,
+) {
+    let running_tools = matches!(
+        query_states.get_state(query_id).await,
+        Some(QueryState::ExecutingTools { .. })
+    );
+    query_states.cancel_query(query_id).await;
+    tool_results.write().await.remove(&query_id);
+
+    if running_tools {
+        let closed = conversation.write().await.close_tool_calls("Cancelled by the user");
+        tracing::debug!("Closed {} tool call(s) of cancelled query {}", closed, query_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::claude::Message;
+
+    #[tokio::test]
+    async fn test_cancel_while_executing_tools() {
+        let query_states = QueryStateManager::new();
+        let conversation = RwLock::new(ConversationHistory::new());
+        let tool_results = RwLock::new(std::collections::HashMap::new());
+
+        conversation.write().await.add_user_message("Read both files".to_string());
+        let tool_use = |id: &str| ContentBlock::ToolUse {
+            id: id.to_string(),
+            name: "read".to_string(),
+            input: serde_json::json!({ "file_path": "a.rs" }),
+        };
+        conversation
+            .write()
+            .await
+            .add_message(Message::with_content("assistant", vec![tool_use("toolu_1"), tool_use("toolu_2")]));
+
+        let query_id = query_states.create_query(conversation.read().await.get_messages()).await;
+        query_states
+            .update_state(query_id, QueryState::ExecutingTools { tools_pending: 2, tools_completed: 0 })
+            .await;
+        tool_results
+            .write()
+            .await
+            .insert(query_id, vec![("toolu_1".to_string(), Ok("fn main() {}".to_string()))]);
+
+        cancel_query(&query_states, &conversation, &tool_results, query_id).await;
+
+        assert!(matches!(query_states.get_state(query_id).await, Some(QueryState::Cancelled)));
+        assert!(tool_results.read().await.is_empty());
+
+        // Every tool call has an (error) result
+        let messages = conversation.read().await.get_messages();
+        let results = &messages.last().unwrap().content;
+        assert_eq!(results.len(), 2);
+        for (block, id) in results.iter().zip(["toolu_1", "toolu_2"]) {
+            assert!(matches!(
+                block,
+                ContentBlock::ToolResult { tool_use_id, is_error: Some(true), .. } if tool_use_id == id
+            ));
+        }
+
+        // Cancelling a query that isn't running tools adds nothing
+        cancel_query(&query_states, &conversation, &tool_results, query_id).await;
+        assert_eq!(conversation.read().await.get_messages().len(), messages.len());
+    }
+}
@@ -11,6 +11,8 @@ use uuid::Uuid;
 #[derive(Debug, Clone)]
 pub enum ConfirmationResult {
     ApproveOnce,
+    ApproveExactTask(ToolSignature),
+    ApprovePatternTask(ToolPattern),
     ApproveExactSession(ToolSignature),
     ApprovePatternSession(ToolPattern),
     ApproveExactPersistent(ToolSignature),
@@ -71,6 +73,11 @@ pub enum ReplEvent {
         latency_ms: Option<u64>,
     },
 
+    /// Files matching the active /watch changed (debounced)
+    WatchChanged {
+        paths: Vec<std::path::PathBuf>,
+    },
+
     /// User requested query cancellation (Ctrl+C)
     CancelQuery,
 
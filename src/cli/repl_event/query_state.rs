@@ -50,6 +50,15 @@ pub struct QueryMetadata {
 
     /// When this query was created
     pub created_at: std::time::Instant,
+
+    /// One-shot tool override (/no-tools, /with-tools); None uses session setting
+    pub tools_override: Option<bool>,
+
+    /// Skip routing and send this query to the teacher (/forward)
+    pub force_forward: bool,
+
+    /// Session change log position when the query started (for its tool summary)
+    pub changes_checkpoint: usize,
 }
 
 /// Manages state for all in-flight queries
@@ -74,6 +83,9 @@ impl QueryStateManager {
             conversation_snapshot,
             cancellation_token: CancellationToken::new(),
             created_at: std::time::Instant::now(),
+            tools_override: None,
+            force_forward: false,
+            changes_checkpoint: 0,
         };
 
         self.states.write().await.insert(id, metadata);
@@ -82,11 +94,46 @@ impl QueryStateManager {
 
     /// Update the state of a query
     pub async fn update_state(&self, query_id: Uuid, state: QueryState) {
+        use crate::metrics::audit::{self, AuditOutcome};
+
+        if matches!(
+            state,
+            QueryState::Completed { .. } | QueryState::Failed { .. } | QueryState::Cancelled
+        ) {
+            crate::cli::profile::finish(query_id);
+        }
+        match &state {
+            QueryState::Completed { response } => audit::finish(query_id, AuditOutcome::Completed, Some(response)),
+            QueryState::Failed { error } => audit::finish(query_id, AuditOutcome::Failed, Some(error)),
+            QueryState::Cancelled => audit::finish(query_id, AuditOutcome::Cancelled, None),
+            _ => {}
+        }
         if let Some(metadata) = self.states.write().await.get_mut(&query_id) {
             metadata.state = state;
         }
     }
 
+    /// Force tools on or off for every turn of this query
+    pub async fn set_tools_override(&self, query_id: Uuid, enabled: bool) {
+        if let Some(metadata) = self.states.write().await.get_mut(&query_id) {
+            metadata.tools_override = Some(enabled);
+        }
+    }
+
+    /// Route this query straight to the teacher
+    pub async fn set_force_forward(&self, query_id: Uuid) {
+        if let Some(metadata) = self.states.write().await.get_mut(&query_id) {
+            metadata.force_forward = true;
+        }
+    }
+
+    /// Record where this query's tool executions start in the session change log
+    pub async fn set_changes_checkpoint(&self, query_id: Uuid, checkpoint: usize) {
+        if let Some(metadata) = self.states.write().await.get_mut(&query_id) {
+            metadata.changes_checkpoint = checkpoint;
+        }
+    }
+
     /// Get the current state of a query
     pub async fn get_state(&self, query_id: Uuid) -> Option<QueryState> {
         self.states
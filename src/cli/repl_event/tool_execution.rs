@@ -12,6 +12,7 @@ use crate::local::LocalGenerator;
 use crate::models::tokenizer::TextTokenizer;
 use crate::tools::executor::{generate_tool_signature, ApprovalSource, ToolExecutor, ToolSignature};
 use crate::tools::patterns::ToolPattern;
+use crate::tools::permissions::PermissionRule;
 use crate::tools::types::ToolUse;
 
 use super::events::ReplEvent;
@@ -88,8 +89,17 @@ impl ToolExecutionCoordinator {
             // Generate tool signature for approval checking
             let signature = generate_tool_signature(&tool_use, std::path::Path::new("."));
 
-            // Check if tool needs approval
-            let approval_source = tool_executor.lock().await.is_approved(&signature);
+            // Check if tool needs approval (task approvals follow the executing plan)
+            let (approval_source, is_dry_run, allowed_by_config) = {
+                let task_scope = repl_mode.read().await.task_scope();
+                let mut executor = tool_executor.lock().await;
+                executor.set_task_scope(task_scope);
+                (
+                    executor.is_approved(&signature),
+                    executor.is_dry_run(&tool_use),
+                    executor.permissions().rule_for(&tool_use.name) == PermissionRule::Allow,
+                )
+            };
 
             // Auto-approve certain non-destructive operations
             let is_auto_approved = {
@@ -98,13 +108,19 @@ impl ToolExecutionCoordinator {
                 // Always auto-approve EnterPlanMode (non-destructive mode change)
                 if tool_name == "EnterPlanMode" || tool_name == "enter_plan_mode" {
                     true
+                } else if is_dry_run {
+                    // Bash dry runs only report the command
+                    true
+                } else if allowed_by_config {
+                    // [tools.auto_approve]
+                    true
                 } else {
                     // Auto-approve read-only tools and user interaction tools when in plan mode
                     let current_mode = repl_mode.read().await;
                     let is_plan_mode = matches!(*current_mode, crate::cli::ReplMode::Planning { .. });
                     let is_readonly_tool = matches!(
                         tool_name,
-                        "read" | "Read" | "glob" | "Glob" | "grep" | "Grep" | "web_fetch" | "WebFetch" |
+                        "read" | "Read" | "glob" | "Glob" | "grep" | "Grep" | "list_dir" | "web_fetch" | "WebFetch" | "web_search" |
                         "AskUserQuestion" | "ask_user_question"
                     );
 
@@ -113,7 +129,7 @@ impl ToolExecutionCoordinator {
             };
 
             let needs_approval = !is_auto_approved
-                && matches!(approval_source, crate::tools::executor::ApprovalSource::NotApproved);
+                && matches!(approval_source, ApprovalSource::NotApproved);
 
             if needs_approval {
                 // Request approval from user (non-blocking for other queries)
@@ -140,6 +156,13 @@ impl ToolExecutionCoordinator {
                             ConfirmationResult::ApproveOnce => {
                                 // Approved for this execution only, continue
                             }
+                            ConfirmationResult::ApproveExactTask(sig) => {
+                                // Save approval until the current task ends
+                                tool_executor.lock().await.approve_exact_task(sig);
+                            }
+                            ConfirmationResult::ApprovePatternTask(pattern) => {
+                                tool_executor.lock().await.approve_pattern_task(pattern);
+                            }
                             ConfirmationResult::ApproveExactSession(sig) => {
                                 // Save session approval
                                 tool_executor.lock().await.approve_exact_session(sig);
@@ -193,33 +216,50 @@ impl ToolExecutionCoordinator {
                 }
             }
 
+            // How the tool came to run, for the tool audit log
+            let approval = match approval_source {
+                ApprovalSource::NotApproved if needs_approval => ApprovalSource::User,
+                ApprovalSource::NotApproved => ApprovalSource::Automatic,
+                other => other,
+            };
+
             // Tool approved (or doesn't need approval), execute it
             let conversation_snapshot = conversation.read().await.clone();
 
-            // Execute with timeout to prevent system freezing (especially for CPU-heavy operations)
-            let timeout_duration = std::time::Duration::from_secs(30);
-            let result = tokio::time::timeout(
-                timeout_duration,
-                tool_executor
-                    .lock()
-                    .await
-                    .execute_tool::<fn() -> anyhow::Result<()>>(
-                        &tool_use,
-                        Some(&conversation_snapshot),
-                        None, // save_fn (not needed in event loop)
-                        None, // router (for training)
-                        Some(Arc::clone(&local_generator)),
-                        Some(Arc::clone(&tokenizer)),
-                        Some(Arc::clone(&repl_mode)),
-                        Some(Arc::clone(&plan_content)),
-                    )
-            )
-            .await;
+            // Run outside the executor lock so other tools can run alongside,
+            // up to features.max_concurrent_tools. The wait for a slot doesn't
+            // count against the tool's timeout ([tools.timeouts]), which the
+            // runner applies.
+            let runner = tool_executor.lock().await.runner().with_approval(approval);
+            let slot = runner.slots().acquire().await;
+
+            let tool_span = crate::cli::profile::span(query_id, format!("tool: {}", tool_use.name));
+            let result = runner
+                .execute_tool_in_slot::<fn() -> anyhow::Result<()>>(
+                    &slot,
+                    &tool_use,
+                    Some(&conversation_snapshot),
+                    None, // save_fn (not needed in event loop)
+                    None, // router (for training)
+                    Some(Arc::clone(&local_generator)),
+                    Some(Arc::clone(&tokenizer)),
+                    Some(Arc::clone(&repl_mode)),
+                    Some(Arc::clone(&plan_content)),
+                )
+                .await;
+            drop(tool_span);
+            drop(slot);
+
+            if crate::metrics::audit::is_enabled() {
+                let is_error = !matches!(&result, Ok(tool_result) if !tool_result.is_error);
+                let files = tool_executor.lock().await.session_changes().files_for(&tool_use.id);
+                crate::metrics::audit::record_tool(query_id, &tool_use, is_error, files);
+            }
 
             // Send result back to event loop
             match result {
-                Ok(Ok(tool_result)) => {
-                    // Tool executed successfully within timeout
+                Ok(tool_result) => {
+                    // Tool ran (a timeout comes back as an error result)
                     tracing::info!("[tool_exec] Tool {} succeeded, sending result ({} chars)",
                         tool_use.name, tool_result.content.len());
                     let _ = event_tx.send(ReplEvent::ToolResult {
@@ -228,7 +268,7 @@ impl ToolExecutionCoordinator {
                         result: Ok(tool_result.content),
                     });
                 }
-                Ok(Err(e)) => {
+                Err(e) => {
                     // Tool executed but returned error
                     tracing::warn!("[tool_exec] Tool {} returned error: {}", tool_use.name, e);
                     let _ = event_tx.send(ReplEvent::ToolResult {
@@ -237,20 +277,6 @@ impl ToolExecutionCoordinator {
                         result: Err(e),
                     });
                 }
-                Err(_) => {
-                    // Timeout elapsed
-                    tracing::error!("[tool_exec] Tool {} timed out after {} seconds",
-                        tool_use.name, timeout_duration.as_secs());
-                    let _ = event_tx.send(ReplEvent::ToolResult {
-                        query_id,
-                        tool_id: tool_use.id.clone(),
-                        result: Err(anyhow::anyhow!(
-                            "Tool execution timed out after {} seconds. \
-                             Try restarting or check daemon logs for errors.",
-                            timeout_duration.as_secs()
-                        )),
-                    });
-                }
             }
         });
     }
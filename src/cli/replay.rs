@@ -0,0 +1,267 @@
+// Session replay for regression checks
+//
+// Re-runs the user turns of a saved conversation through a generator (normally
+// the daemon's local model) and compares each new answer with the recorded one
+// using `semantic_similarity`. Turns whose similarity falls below a threshold
+// are flagged as possible regressions, e.g. after retraining an adapter.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::future::Future;
+
+use super::ConversationHistory;
+use crate::metrics::semantic_similarity;
+
+/// Default similarity below which a replayed turn is flagged
+pub const DEFAULT_DIVERGENCE_THRESHOLD: f64 = 0.3;
+
+/// A user prompt and the response recorded for it
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedTurn {
+    pub prompt: String,
+    pub response: String,
+}
+
+/// Outcome of replaying one turn
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayedTurn {
+    /// 1-based turn number in the session
+    pub turn: usize,
+    pub prompt: String,
+    pub recorded: String,
+    /// New response (None if generation failed)
+    pub replayed: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Similarity to the recorded response (None if generation failed)
+    pub similarity: Option<f64>,
+    /// Similarity is below the divergence threshold
+    pub diverged: bool,
+}
+
+/// Regression report for a replayed session
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayReport {
+    pub threshold: f64,
+    pub turns: Vec<ReplayedTurn>,
+}
+
+impl ReplayReport {
+    /// Mean similarity over turns that produced a response
+    pub fn mean_similarity(&self) -> Option<f64> {
+        let scores: Vec<f64> = self.turns.iter().filter_map(|t| t.similarity).collect();
+        if scores.is_empty() {
+            None
+        } else {
+            Some(scores.iter().sum::<f64>() / scores.len() as f64)
+        }
+    }
+
+    /// Turns whose new answer diverged from the recording
+    pub fn diverged(&self) -> impl Iterator<Item = &ReplayedTurn> {
+        self.turns.iter().filter(|t| t.diverged)
+    }
+
+    /// Turns where generation failed
+    pub fn failed(&self) -> impl Iterator<Item = &ReplayedTurn> {
+        self.turns.iter().filter(|t| t.error.is_some())
+    }
+
+    /// Human-readable report
+    pub fn format(&self) -> String {
+        let mut out = format!("Replayed {} turn(s)\n", self.turns.len());
+
+        for turn in &self.turns {
+            let status = match (&turn.error, turn.similarity) {
+                (Some(error), _) => format!("ERROR  {}", first_line(error, 60)),
+                (None, Some(similarity)) if turn.diverged => format!("DIVERGED  {:.2}", similarity),
+                (None, Some(similarity)) => format!("ok  {:.2}", similarity),
+                (None, None) => "skipped".to_string(),
+            };
+            out.push_str(&format!(
+                "  #{:<3} {:<20} {}\n",
+                turn.turn,
+                status,
+                first_line(&turn.prompt, 50)
+            ));
+        }
+
+        out.push('\n');
+        match self.mean_similarity() {
+            Some(mean) => out.push_str(&format!("Mean similarity: {:.2}\n", mean)),
+            None => out.push_str("Mean similarity: n/a\n"),
+        }
+        out.push_str(&format!(
+            "Diverged (< {:.2}): {}   Failed: {}\n",
+            self.threshold,
+            self.diverged().count(),
+            self.failed().count()
+        ));
+
+        for turn in self.diverged() {
+            out.push_str(&format!("\n--- Turn #{}: {}\n", turn.turn, first_line(&turn.prompt, 70)));
+            out.push_str(&format!("Recorded: {}\n", first_line(&turn.recorded, 200)));
+            if let Some(replayed) = &turn.replayed {
+                out.push_str(&format!("Replayed: {}\n", first_line(replayed, 200)));
+            }
+        }
+
+        out
+    }
+}
+
+/// Pair each user prompt with the final assistant answer to it
+///
+/// Tool round-trips are skipped: tool-result messages aren't prompts, and
+/// `[Tool request]` placeholders aren't answers. When the assistant replied
+/// several times to one prompt (tool loop), the last reply is used.
+pub fn recorded_turns(history: &ConversationHistory) -> Vec<RecordedTurn> {
+    let mut turns: Vec<RecordedTurn> = Vec::new();
+    let mut current: Option<RecordedTurn> = None;
+
+    for message in history.get_messages() {
+        let text = message.text();
+        match message.role.as_str() {
+            "user" => {
+                if message.has_tool_results() || text.trim_start().starts_with("<tool_result>") {
+                    continue;
+                }
+                if text.trim().is_empty() {
+                    continue;
+                }
+                if let Some(turn) = current.take() {
+                    if !turn.response.is_empty() {
+                        turns.push(turn);
+                    }
+                }
+                current = Some(RecordedTurn {
+                    prompt: text,
+                    response: String::new(),
+                });
+            }
+            "assistant" => {
+                if text.trim().is_empty() || text == "[Tool request]" {
+                    continue;
+                }
+                if let Some(turn) = current.as_mut() {
+                    turn.response = text;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(turn) = current {
+        if !turn.response.is_empty() {
+            turns.push(turn);
+        }
+    }
+
+    turns
+}
+
+/// Replay recorded turns through `generate` and score each new answer
+///
+/// Turns are replayed one at a time without the earlier conversation as
+/// context, so answers that depended on prior turns may diverge.
+pub async fn replay_turns<F, Fut>(
+    turns: &[RecordedTurn],
+    threshold: f64,
+    mut generate: F,
+) -> ReplayReport
+where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<String>>,
+{
+    let mut replayed = Vec::with_capacity(turns.len());
+
+    for (index, turn) in turns.iter().enumerate() {
+        let (response, error) = match generate(turn.prompt.clone()).await {
+            Ok(response) => (Some(response), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+
+        let similarity = match response.as_deref() {
+            Some(response) => semantic_similarity(&turn.response, response).await.ok(),
+            None => None,
+        };
+
+        replayed.push(ReplayedTurn {
+            turn: index + 1,
+            prompt: turn.prompt.clone(),
+            recorded: turn.response.clone(),
+            replayed: response,
+            error,
+            similarity,
+            diverged: similarity.is_some_and(|s| s < threshold),
+        });
+    }
+
+    ReplayReport {
+        threshold,
+        turns: replayed,
+    }
+}
+
+fn first_line(text: &str, max_chars: usize) -> String {
+    let line = text.lines().next().unwrap_or("");
+    if line.chars().count() > max_chars {
+        format!("{}...", line.chars().take(max_chars).collect::<String>())
+    } else {
+        line.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session() -> ConversationHistory {
+        let mut history = ConversationHistory::new();
+        history.add_user_message("What is the capital of France?".to_string());
+        history.add_assistant_message("The capital of France is Paris.".to_string());
+        history.add_user_message("List the files in src".to_string());
+        history.add_assistant_message("[Tool request]".to_string());
+        history.add_user_message("<tool_result>\n<tool_name>glob</tool_name>\n</tool_result>".to_string());
+        history.add_assistant_message("The src directory contains main.rs and lib.rs.".to_string());
+        history.add_user_message("Unanswered prompt".to_string());
+        history
+    }
+
+    #[test]
+    fn test_recorded_turns_skip_tool_round_trips() {
+        let turns = recorded_turns(&session());
+
+        assert_eq!(turns.len(), 2);
+        assert_eq!(turns[0].prompt, "What is the capital of France?");
+        assert_eq!(turns[1].prompt, "List the files in src");
+        assert_eq!(turns[1].response, "The src directory contains main.rs and lib.rs.");
+    }
+
+    #[tokio::test]
+    async fn test_replay_flags_divergent_turns() {
+        let turns = recorded_turns(&session());
+
+        let report = replay_turns(&turns, 0.5, |prompt| async move {
+            if prompt.contains("France") {
+                Ok("The capital of France is Paris.".to_string())
+            } else {
+                anyhow::bail!("Local model not ready")
+            }
+        })
+        .await;
+
+        assert_eq!(report.turns[0].similarity, Some(1.0));
+        assert!(!report.turns[0].diverged);
+        assert!(report.turns[1].error.is_some());
+        assert_eq!(report.failed().count(), 1);
+        assert_eq!(report.mean_similarity(), Some(1.0));
+
+        let report = replay_turns(&turns[..1], 0.5, |_| async {
+            Ok("Bananas are yellow.".to_string())
+        })
+        .await;
+        assert_eq!(report.diverged().count(), 1);
+        assert!(report.format().contains("DIVERGED"));
+    }
+}
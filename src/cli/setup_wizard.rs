@@ -199,6 +199,13 @@ impl WizardState {
                     model: None,
                     base_url: None,
                     name: Some("Claude (Primary)".to_string()),
+                    prompt_caching: None,
+                    max_tokens: None,
+                    pool: None,
+                    thinking: None,
+                    max_retries: None,
+                    base_delay_ms: None,
+                    request_timeout_seconds: None,
                 }]
             });
         sections.insert(
@@ -764,6 +771,13 @@ fn build_setup_result(state: &WizardState) -> Result<SetupResult> {
             model: None,
             base_url: None,
             name: Some("Claude (Primary)".to_string()),
+            prompt_caching: None,
+            max_tokens: None,
+            pool: None,
+            thinking: None,
+            max_retries: None,
+            base_delay_ms: None,
+            request_timeout_seconds: None,
         }]
     };
 
@@ -941,7 +955,7 @@ fn render_api_keys_section(
         Style::default().fg(Color::Gray)
     };
     let claude_display = if claude_key.len() > 60 {
-        format!("{}...{} ({} chars)", &claude_key[..30], &claude_key[claude_key.len()-10..], claude_key.len())
+        format!("{}...{} ({} chars)", crate::text::truncate_chars(claude_key, 30), crate::text::last_chars(claude_key, 10), claude_key.len())
     } else if !claude_key.is_empty() {
         claude_key.to_string()
     } else {
@@ -1445,6 +1459,13 @@ fn run_wizard_loop(
                 model: None,
                 base_url: None,
                 name: Some("Claude (Primary)".to_string()),
+                prompt_caching: None,
+                max_tokens: None,
+                pool: None,
+                thinking: None,
+                max_retries: None,
+                base_delay_ms: None,
+                request_timeout_seconds: None,
             }]
         });
 
@@ -1894,6 +1915,13 @@ fn run_wizard_loop(
                                 model: if model_input.is_empty() { None } else { Some(model_input.clone()) },
                                 base_url: None,
                                 name: None,
+                                prompt_caching: None,
+                                max_tokens: None,
+                                pool: None,
+                                thinking: None,
+                                max_retries: None,
+                                base_delay_ms: None,
+                                request_timeout_seconds: None,
                             });
                             step = WizardStep::TeacherConfig(new_teachers, teacher_list.len());
                         }
@@ -1912,6 +1940,13 @@ fn run_wizard_loop(
                                 model: None,
                                 base_url: None,
                                 name: None,
+                                prompt_caching: None,
+                                max_tokens: None,
+                                pool: None,
+                                thinking: None,
+                                max_retries: None,
+                                base_delay_ms: None,
+                                request_timeout_seconds: None,
                             });
                             step = WizardStep::TeacherConfig(new_teachers, teacher_list.len());
                         }
@@ -2148,8 +2183,8 @@ fn render_api_key_input(f: &mut Frame, area: Rect, input: &str) {
     // For long API keys (>60 chars), show truncated version with indication
     let display_text = if input.len() > 60 {
         format!("{}...{} ({}characters) _",
-            &input[..40],
-            &input[input.len()-10..],
+            crate::text::truncate_chars(input, 40),
+            crate::text::last_chars(input, 10),
             input.len())
     } else if !input.is_empty() {
         format!("{}_", input)
@@ -0,0 +1,74 @@
+// Generating indicator animation
+//
+// The TUI re-renders in-progress messages on every render tick (100ms), so the
+// spinner frame is derived from elapsed time rather than advanced by a timer.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::Duration;
+
+use super::glyphs;
+use crate::config::SpinnerStyle;
+
+static STYLE: AtomicU8 = AtomicU8::new(0);
+
+/// Time each frame stays on screen (matches the TUI render interval)
+const FRAME_DURATION: Duration = Duration::from_millis(100);
+
+const DOTS_FRAMES: &[&str] = &["∙∙∙", "●∙∙", "∙●∙", "∙∙●"];
+const BRAILLE_FRAMES: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+const ASCII_FRAMES: &[&str] = &["|", "/", "-", "\\"];
+
+/// Select the spinner style (from `features.spinner_style`)
+pub fn init(style: SpinnerStyle) {
+    STYLE.store(style as u8, Ordering::Relaxed);
+}
+
+/// Currently selected spinner style
+pub fn style() -> SpinnerStyle {
+    match STYLE.load(Ordering::Relaxed) {
+        1 => SpinnerStyle::Braille,
+        2 => SpinnerStyle::Ascii,
+        _ => SpinnerStyle::Dots,
+    }
+}
+
+fn frames(style: SpinnerStyle) -> &'static [&'static str] {
+    // Unicode spinners would be mangled by the ASCII fallback
+    if glyphs::ascii_only() {
+        return ASCII_FRAMES;
+    }
+
+    match style {
+        SpinnerStyle::Dots => DOTS_FRAMES,
+        SpinnerStyle::Braille => BRAILLE_FRAMES,
+        SpinnerStyle::Ascii => ASCII_FRAMES,
+    }
+}
+
+/// Spinner frame to show after `elapsed` time
+pub fn frame(elapsed: Duration) -> &'static str {
+    frame_for(style(), elapsed)
+}
+
+fn frame_for(style: SpinnerStyle, elapsed: Duration) -> &'static str {
+    let frames = frames(style);
+    let tick = (elapsed.as_millis() / FRAME_DURATION.as_millis()) as usize;
+    frames[tick % frames.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frames_cycle() {
+        assert_eq!(frame_for(SpinnerStyle::Ascii, Duration::ZERO), "|");
+        assert_eq!(frame_for(SpinnerStyle::Ascii, Duration::from_millis(150)), "/");
+        assert_eq!(frame_for(SpinnerStyle::Ascii, Duration::from_millis(400)), "|");
+
+        let braille: Vec<_> = (0..10)
+            .map(|i| frame_for(SpinnerStyle::Braille, FRAME_DURATION * i))
+            .collect();
+        assert_eq!(braille, BRAILLE_FRAMES);
+    }
+}
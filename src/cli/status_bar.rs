@@ -4,23 +4,45 @@
 // - Training statistics
 // - Download progress
 // - Operation status
+// - Output rate (tokens/sec) while a response streams
 //
-// Supports dynamic addition/removal of status lines.
+// Supports dynamic addition/removal of status lines. Each line type has a
+// priority: when the terminal is too short or narrow, the lowest-priority
+// lines (or sections of a one-line status) are dropped first, and text is
+// cut to the display width on char boundaries. The mode indicator is never
+// dropped.
 
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Separator between sections of a one-line status
+pub const SECTION_SEPARATOR: &str = " | ";
+
+/// Narrowest useful cut of a dropped section (below this it stays dropped)
+const MIN_TRUNCATED_WIDTH: usize = 12;
+
+/// How often the throughput line is refreshed while a response streams
+pub const THROUGHPUT_INTERVAL: Duration = Duration::from_millis(500);
 
 /// Types of status lines
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum StatusLineType {
+    /// Plan mode indicator (never dropped)
+    Mode,
     /// Live query statistics (tokens, latency, model)
     LiveStats,
+    /// Output rate of the response being streamed
+    Throughput,
     /// Training statistics (queries, local%, quality)
     TrainingStats,
     /// Model download progress
     DownloadProgress,
     /// Current operation status
     OperationStatus,
+    /// Conversation context (turn count)
+    Context,
     /// Contextual suggestions (like Claude Code)
     Suggestions,
     /// Auto-compaction percentage (displayed on right side)
@@ -29,6 +51,36 @@ pub enum StatusLineType {
     Custom(String),
 }
 
+impl StatusLineType {
+    /// How important the line is when space runs out (higher is kept longer)
+    pub fn priority(&self) -> u8 {
+        match self {
+            StatusLineType::Mode => u8::MAX,
+            StatusLineType::OperationStatus => 90,
+            StatusLineType::DownloadProgress => 80,
+            StatusLineType::LiveStats => 70,
+            StatusLineType::Throughput => 65,
+            StatusLineType::TrainingStats => 60,
+            StatusLineType::Context => 50,
+            StatusLineType::Custom(_) => 40,
+            StatusLineType::Suggestions => 20,
+            StatusLineType::CompactionPercent => 10,
+        }
+    }
+}
+
+/// Display order of the fixed line types (custom lines come before the mode)
+const LINE_ORDER: &[StatusLineType] = &[
+    StatusLineType::LiveStats,
+    StatusLineType::Throughput,
+    StatusLineType::TrainingStats,
+    StatusLineType::DownloadProgress,
+    StatusLineType::OperationStatus,
+    StatusLineType::Context,
+    StatusLineType::Suggestions,
+    StatusLineType::CompactionPercent,
+];
+
 /// A single status line
 #[derive(Debug, Clone)]
 pub struct StatusLine {
@@ -74,53 +126,17 @@ impl StatusBar {
     pub fn get_lines(&self) -> Vec<StatusLine> {
         let lines = self.lines.read().unwrap();
 
-        // Order: LiveStats, TrainingStats, DownloadProgress, OperationStatus, then Custom
-        let mut result = Vec::new();
-
-        // Add in preferred order
-        if let Some(content) = lines.get(&StatusLineType::LiveStats) {
-            result.push(StatusLine {
-                line_type: StatusLineType::LiveStats,
-                content: content.clone(),
-            });
-        }
-
-        if let Some(content) = lines.get(&StatusLineType::TrainingStats) {
-            result.push(StatusLine {
-                line_type: StatusLineType::TrainingStats,
-                content: content.clone(),
-            });
-        }
-
-        if let Some(content) = lines.get(&StatusLineType::DownloadProgress) {
-            result.push(StatusLine {
-                line_type: StatusLineType::DownloadProgress,
-                content: content.clone(),
-            });
-        }
-
-        if let Some(content) = lines.get(&StatusLineType::OperationStatus) {
-            result.push(StatusLine {
-                line_type: StatusLineType::OperationStatus,
-                content: content.clone(),
-            });
-        }
-
-        if let Some(content) = lines.get(&StatusLineType::Suggestions) {
-            result.push(StatusLine {
-                line_type: StatusLineType::Suggestions,
-                content: content.clone(),
-            });
-        }
-
-        if let Some(content) = lines.get(&StatusLineType::CompactionPercent) {
-            result.push(StatusLine {
-                line_type: StatusLineType::CompactionPercent,
-                content: content.clone(),
-            });
-        }
+        // Order: fixed types, then custom lines (sorted by ID), then the mode
+        let mut result: Vec<StatusLine> = LINE_ORDER
+            .iter()
+            .filter_map(|line_type| {
+                lines.get(line_type).map(|content| StatusLine {
+                    line_type: line_type.clone(),
+                    content: content.clone(),
+                })
+            })
+            .collect();
 
-        // Add custom lines (sorted by ID for consistency)
         let mut custom_lines: Vec<_> = lines
             .iter()
             .filter_map(|(k, v)| {
@@ -140,6 +156,13 @@ impl StatusBar {
             });
         }
 
+        if let Some(content) = lines.get(&StatusLineType::Mode) {
+            result.push(StatusLine {
+                line_type: StatusLineType::Mode,
+                content: content.clone(),
+            });
+        }
+
         result
     }
 
@@ -273,6 +296,210 @@ impl StatusBar {
     pub fn clear_live_stats(&self) {
         self.remove_line(&StatusLineType::LiveStats);
     }
+
+    /// Refresh the throughput line from `meter` if a readout is due
+    pub fn update_throughput(&self, meter: &mut ThroughputMeter) {
+        if let Some(readout) = meter.poll(Instant::now()) {
+            self.update_line(StatusLineType::Throughput, readout);
+        }
+    }
+
+    /// Clear the throughput line (shorthand)
+    pub fn clear_throughput(&self) {
+        self.remove_line(&StatusLineType::Throughput);
+    }
+
+    /// Show the pending /temp override, or remove the line once it's used
+    pub fn update_temperature_override(&self, value: Option<f32>) {
+        let line = StatusLineType::Custom("temperature".to_string());
+        match value {
+            Some(value) => self.update_line(line, format!("Temp: {} (next teacher call)", value)),
+            None => self.remove_line(&line),
+        }
+    }
+
+    /// Show "Tools: off" while tool definitions aren't sent with queries
+    pub fn update_tools_enabled(&self, enabled: bool) {
+        let line = StatusLineType::Custom("tools".to_string());
+        if enabled {
+            self.remove_line(&line);
+        } else {
+            self.update_line(line, "Tools: off (/tools on to enable)".to_string());
+        }
+    }
+}
+
+/// Keep the lines that fit in `max_lines` rows of `width` columns
+///
+/// Lowest-priority lines are dropped first; the rest keep their order and
+/// are cut to the width. The mode line is kept even when `max_lines` is 0.
+pub fn fit_lines(lines: Vec<StatusLine>, max_lines: usize, width: usize) -> Vec<StatusLine> {
+    let has_mode = lines.iter().any(|line| line.line_type == StatusLineType::Mode);
+    let keep = max_lines.max(usize::from(has_mode));
+
+    let mut kept = lines;
+    while kept.len() > keep {
+        kept.remove(lowest_priority(&kept));
+    }
+    for line in &mut kept {
+        line.content = truncate_to_width(&line.content, width);
+    }
+    kept
+}
+
+/// Keep the sections of a one-line status that fit in `width` columns
+///
+/// Sections are joined with `SECTION_SEPARATOR`. Lowest-priority sections
+/// are dropped until the rest fit; the last one dropped is put back, cut,
+/// if there's reasonable room left. A lone section that's still too wide is
+/// cut to the width.
+pub fn fit_sections(sections: Vec<StatusLine>, width: usize) -> Vec<StatusLine> {
+    let mut kept = sections;
+    // Original position of each kept section, to put a dropped one back in place
+    let mut positions: Vec<usize> = (0..kept.len()).collect();
+    let mut last_dropped = None;
+    while kept.len() > 1 && sections_width(&kept) > width {
+        let index = lowest_priority(&kept);
+        last_dropped = Some((positions.remove(index), kept.remove(index)));
+    }
+
+    if let Some((position, mut section)) = last_dropped {
+        let room = width.saturating_sub(sections_width(&kept) + SECTION_SEPARATOR.width());
+        if room >= MIN_TRUNCATED_WIDTH {
+            section.content = truncate_to_width(&section.content, room);
+            let at = positions.iter().position(|&p| p > position).unwrap_or(kept.len());
+            kept.insert(at, section);
+        }
+    }
+
+    if let [only] = kept.as_mut_slice() {
+        only.content = truncate_to_width(&only.content, width);
+    }
+    kept
+}
+
+/// Cut `text` to at most `width` terminal columns, ending in `…` when cut
+///
+/// Cuts fall between characters, and double-width characters (CJK, most
+/// emoji) count as two columns.
+pub fn truncate_to_width(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    let budget = width.saturating_sub(1); // room for the ellipsis
+    let mut used = 0;
+    let mut out = String::new();
+    for ch in text.chars() {
+        let ch_width = ch.width().unwrap_or(0);
+        if used + ch_width > budget {
+            break;
+        }
+        used += ch_width;
+        out.push(ch);
+    }
+    if width > 0 {
+        out.push('…');
+    }
+    out
+}
+
+/// Columns taken by sections joined with `SECTION_SEPARATOR`
+fn sections_width(sections: &[StatusLine]) -> usize {
+    let text: usize = sections.iter().map(|section| section.content.width()).sum();
+    text + sections.len().saturating_sub(1) * SECTION_SEPARATOR.width()
+}
+
+/// Index of the line to drop first: lowest priority, latest on ties
+fn lowest_priority(lines: &[StatusLine]) -> usize {
+    lines
+        .iter()
+        .enumerate()
+        .rev()
+        .min_by_key(|(_, line)| line.line_type.priority())
+        .map_or(0, |(index, _)| index)
+}
+
+/// Output rate of a streaming response
+///
+/// Uses the provider's output token count once the stream reports one, and
+/// until then estimates a token per word of streamed text.
+pub struct ThroughputMeter {
+    started: Instant,
+    last_readout: Instant,
+    estimated_tokens: u32,
+    reported_tokens: Option<u32>,
+    in_word: bool,
+}
+
+impl ThroughputMeter {
+    /// Start measuring now
+    pub fn new() -> Self {
+        Self::started_at(Instant::now())
+    }
+
+    /// Start measuring at `started`
+    pub fn started_at(started: Instant) -> Self {
+        Self {
+            started,
+            last_readout: started,
+            estimated_tokens: 0,
+            reported_tokens: None,
+            in_word: false,
+        }
+    }
+
+    /// Count a text delta (words may be split across deltas)
+    pub fn record_text(&mut self, text: &str) {
+        for c in text.chars() {
+            let in_word = !c.is_whitespace();
+            if in_word && !self.in_word {
+                self.estimated_tokens += 1;
+            }
+            self.in_word = in_word;
+        }
+    }
+
+    /// Record the provider's count of output tokens so far
+    pub fn record_tokens(&mut self, total: u32) {
+        self.reported_tokens = Some(total);
+    }
+
+    /// Output tokens so far (reported, or estimated)
+    pub fn tokens(&self) -> u32 {
+        self.reported_tokens.unwrap_or(self.estimated_tokens)
+    }
+
+    /// Whether `tokens()` is a word-count estimate
+    pub fn is_estimate(&self) -> bool {
+        self.reported_tokens.is_none()
+    }
+
+    /// Tokens per second at `now`, e.g. "Speed: 42.0 tok/s"
+    pub fn readout(&self, now: Instant) -> String {
+        let elapsed = now.saturating_duration_since(self.started).as_secs_f64();
+        let rate = if elapsed > 0.0 {
+            self.tokens() as f64 / elapsed
+        } else {
+            0.0
+        };
+        let approx = if self.is_estimate() { "~" } else { "" };
+        format!("Speed: {}{:.1} tok/s", approx, rate)
+    }
+
+    /// The readout, if THROUGHPUT_INTERVAL has passed since the last one
+    pub fn poll(&mut self, now: Instant) -> Option<String> {
+        if now.saturating_duration_since(self.last_readout) < THROUGHPUT_INTERVAL {
+            return None;
+        }
+        self.last_readout = now;
+        Some(self.readout(now))
+    }
+}
+
+impl Default for ThroughputMeter {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Default for StatusBar {
@@ -333,6 +560,36 @@ mod tests {
         assert!(status.is_empty());
     }
 
+    #[test]
+    fn test_throughput_meter() {
+        let start = Instant::now();
+        let mut meter = ThroughputMeter::started_at(start);
+
+        meter.record_text("Hel");
+        meter.record_text("lo wor");
+        meter.record_text("ld, how are ");
+        meter.record_text("you?");
+        assert_eq!(meter.tokens(), 5);
+
+        // Nothing until an interval has passed, then one readout per interval
+        assert_eq!(meter.poll(start + Duration::from_millis(100)), None);
+        let second = start + Duration::from_secs(1);
+        assert_eq!(meter.poll(second).as_deref(), Some("Speed: ~5.0 tok/s"));
+        assert_eq!(meter.poll(second + Duration::from_millis(200)), None);
+
+        // A count from the provider replaces the estimate
+        meter.record_tokens(30);
+        assert!(!meter.is_estimate());
+        assert_eq!(meter.readout(start + Duration::from_secs(2)), "Speed: 15.0 tok/s");
+
+        let status = StatusBar::new();
+        status.update_line(StatusLineType::Throughput, meter.readout(second));
+        status.update_line(StatusLineType::LiveStats, "Model: test");
+        assert_eq!(status.get_lines()[1].line_type, StatusLineType::Throughput);
+        status.clear_throughput();
+        assert_eq!(status.len(), 1);
+    }
+
     #[test]
     fn test_line_ordering() {
         let status = StatusBar::new();
@@ -410,4 +667,90 @@ mod tests {
             StatusLineType::Custom("test2".to_string())
         );
     }
+
+    fn line(line_type: StatusLineType, content: &str) -> StatusLine {
+        StatusLine {
+            line_type,
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_mode_line_last() {
+        let status = StatusBar::new();
+
+        status.update_line(StatusLineType::Mode, "⏸ plan mode on");
+        status.update_line(StatusLineType::Custom("idle".to_string()), "Idle");
+        status.update_line(StatusLineType::Suggestions, "Try /help");
+
+        let lines = status.get_lines();
+        assert_eq!(lines[0].line_type, StatusLineType::Suggestions);
+        assert_eq!(lines[2].line_type, StatusLineType::Mode);
+    }
+
+    #[test]
+    fn test_fit_lines_drops_lowest_priority() {
+        let lines = vec![
+            line(StatusLineType::TrainingStats, "Training"),
+            line(StatusLineType::OperationStatus, "Operation"),
+            line(StatusLineType::Suggestions, "Suggestions"),
+            line(StatusLineType::Custom("idle".to_string()), "Idle"),
+            line(StatusLineType::Mode, "Mode"),
+        ];
+
+        let fitted = fit_lines(lines.clone(), 3, 80);
+        let types: Vec<_> = fitted.iter().map(|l| l.line_type.clone()).collect();
+        assert_eq!(
+            types,
+            [StatusLineType::TrainingStats, StatusLineType::OperationStatus, StatusLineType::Mode]
+        );
+
+        // No room at all: the mode indicator still shows
+        let fitted = fit_lines(lines, 0, 80);
+        assert_eq!(fitted.len(), 1);
+        assert_eq!(fitted[0].line_type, StatusLineType::Mode);
+    }
+
+    fn join_sections(sections: &[StatusLine]) -> String {
+        let contents: Vec<&str> = sections.iter().map(|s| s.content.as_str()).collect();
+        contents.join(SECTION_SEPARATOR)
+    }
+
+    #[test]
+    fn test_fit_sections_narrow_terminal() {
+        let sections = vec![
+            line(StatusLineType::Mode, "[PLANNING MODE]"),
+            line(StatusLineType::TrainingStats, "Training: 12 queries | Local: 40%"),
+            line(StatusLineType::Context, "Context: 3 turns"),
+        ];
+
+        let wide = fit_sections(sections.clone(), 200);
+        assert_eq!(
+            join_sections(&wide),
+            "[PLANNING MODE] | Training: 12 queries | Local: 40% | Context: 3 turns"
+        );
+
+        // Context goes first; training is cut into the space that's left
+        let medium = join_sections(&fit_sections(sections.clone(), 50));
+        assert_eq!(medium, "[PLANNING MODE] | Training: 12 queries | Local: 4…");
+        assert_eq!(medium.width(), 50);
+
+        // Too narrow for anything else: only the (cut) mode remains
+        let narrow = fit_sections(sections, 10);
+        assert_eq!(narrow.len(), 1);
+        assert_eq!(narrow[0].content, "[PLANNING…");
+    }
+
+    #[test]
+    fn test_truncate_wide_characters() {
+        // Each CJK character and the emoji take two columns
+        assert_eq!(truncate_to_width("状态栏测试", 7), "状态栏…");
+        assert_eq!(truncate_to_width("ok 😀😀 done", 6), "ok 😀…");
+        assert_eq!(truncate_to_width("ok 😀", 5), "ok 😀");
+        assert_eq!(truncate_to_width("abc", 0), "");
+
+        for width in 0..12 {
+            assert!(truncate_to_width("ab😀cd状态ef", width).width() <= width);
+        }
+    }
 }
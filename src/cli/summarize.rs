@@ -0,0 +1,211 @@
+// File summaries (/summarize)
+//
+// Reads a file with the Read tool, splits long files into chunks at line
+// boundaries, summarizes each chunk and then merges the partial summaries.
+// Short files are summarized by the local model when it's ready; long or
+// dense files (more than one chunk, or very many lines) go to the teacher,
+// as does anything the local model fails on.
+
+use anyhow::{bail, Result};
+use std::str::FromStr;
+
+use crate::claude::Message;
+use crate::generators::Generator;
+use crate::tools::implementations::ReadTool;
+
+/// Largest piece of a file sent in one request, in characters
+pub const CHUNK_CHARS: usize = 12_000;
+
+/// Files with more lines than this skip the local model
+const LOCAL_MAX_LINES: usize = 400;
+
+/// How long the summary should be (`--detail <level>`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SummaryDetail {
+    Brief,
+    #[default]
+    Normal,
+    Detailed,
+}
+
+impl SummaryDetail {
+    fn instruction(&self) -> &'static str {
+        match self {
+            SummaryDetail::Brief => "in 2-3 sentences",
+            SummaryDetail::Normal => "in one short paragraph followed by a few bullet points on the key parts",
+            SummaryDetail::Detailed => {
+                "in detail: its purpose, then each major section, type or function and how they fit together"
+            }
+        }
+    }
+}
+
+impl FromStr for SummaryDetail {
+    type Err = anyhow::Error;
+
+    fn from_str(level: &str) -> Result<Self> {
+        match level.to_lowercase().as_str() {
+            "brief" | "short" => Ok(SummaryDetail::Brief),
+            "normal" | "medium" => Ok(SummaryDetail::Normal),
+            "detailed" | "long" | "full" => Ok(SummaryDetail::Detailed),
+            other => bail!("Unknown detail level '{}' (use brief, normal or detailed)", other),
+        }
+    }
+}
+
+/// A finished summary and how it was made
+#[derive(Debug, Clone)]
+pub struct FileSummary {
+    pub text: String,
+    /// Name of the generator that wrote it
+    pub generator: String,
+    pub chunks: usize,
+}
+
+/// Split `text` into pieces of at most `max_chars` characters, breaking
+/// after a newline where possible (never inside a character)
+pub fn chunk_text(text: &str, max_chars: usize) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        let head = crate::text::truncate_chars(rest, max_chars);
+        let end = if head.len() == rest.len() {
+            rest.len()
+        } else {
+            head.rfind('\n').map_or(head.len(), |newline| newline + 1)
+        };
+        chunks.push(&rest[..end]);
+        rest = &rest[end..];
+    }
+    chunks
+}
+
+/// Whether a file is too long or dense for the local model
+pub fn needs_teacher(contents: &str) -> bool {
+    contents.chars().count() > CHUNK_CHARS || contents.lines().count() > LOCAL_MAX_LINES
+}
+
+/// Read and summarize a file
+///
+/// `local` is used for short files when given; the teacher handles the rest
+/// and anything the local model fails on. `on_progress` is called with the
+/// 1-based chunk number and the chunk count before each chunk.
+pub async fn summarize_file(
+    path: &str,
+    detail: SummaryDetail,
+    local: Option<&dyn Generator>,
+    teacher: &dyn Generator,
+    mut on_progress: impl FnMut(usize, usize),
+) -> Result<FileSummary> {
+    let contents = ReadTool::read_file(path)?;
+    if contents.trim().is_empty() {
+        bail!("{} is empty", path);
+    }
+
+    if let Some(local) = local.filter(|_| !needs_teacher(&contents)) {
+        on_progress(1, 1);
+        match summarize_text(local, path, &contents, detail).await {
+            Ok(text) => {
+                return Ok(FileSummary {
+                    text,
+                    generator: local.name().to_string(),
+                    chunks: 1,
+                })
+            }
+            Err(e) => tracing::warn!("Local summary of {} failed, using the teacher: {}", path, e),
+        }
+    }
+
+    let chunks = chunk_text(&contents, CHUNK_CHARS);
+    let text = if chunks.len() == 1 {
+        on_progress(1, 1);
+        summarize_text(teacher, path, &contents, detail).await?
+    } else {
+        let mut partials = Vec::with_capacity(chunks.len());
+        for (index, chunk) in chunks.iter().enumerate() {
+            on_progress(index + 1, chunks.len());
+            let label = format!("{} (part {} of {})", path, index + 1, chunks.len());
+            partials.push(summarize_text(teacher, &label, chunk, SummaryDetail::Brief).await?);
+        }
+        merge_summaries(teacher, path, &partials, detail).await?
+    };
+
+    Ok(FileSummary {
+        text,
+        generator: teacher.name().to_string(),
+        chunks: chunks.len(),
+    })
+}
+
+async fn summarize_text(
+    generator: &dyn Generator,
+    label: &str,
+    text: &str,
+    detail: SummaryDetail,
+) -> Result<String> {
+    let prompt = format!(
+        "Summarize the file {} {}. Describe what it contains and does; don't repeat it.\n\n\
+         ```\n{}\n```",
+        label,
+        detail.instruction(),
+        text
+    );
+    generate(generator, prompt).await
+}
+
+async fn merge_summaries(
+    generator: &dyn Generator,
+    path: &str,
+    partials: &[String],
+    detail: SummaryDetail,
+) -> Result<String> {
+    let parts = partials
+        .iter()
+        .enumerate()
+        .map(|(i, summary)| format!("Part {}:\n{}", i + 1, summary))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    let prompt = format!(
+        "These are summaries of consecutive parts of the file {}. \
+         Combine them into one summary of the whole file {}.\n\n{}",
+        path,
+        detail.instruction(),
+        parts
+    );
+    generate(generator, prompt).await
+}
+
+async fn generate(generator: &dyn Generator, prompt: String) -> Result<String> {
+    let response = generator.generate(vec![Message::user(prompt)], None).await?;
+    let text = response.text.trim().to_string();
+    if text.is_empty() {
+        bail!("{} returned an empty summary", generator.name());
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunk_text() {
+        let text = "line one\nline two\nline three\n";
+        let chunks = chunk_text(text, 12);
+        assert_eq!(chunks, ["line one\n", "line two\n", "line three\n"]);
+        assert_eq!(chunks.concat(), text);
+
+        // A line longer than a chunk is split mid-line, on a char boundary
+        let chunks = chunk_text("😀😀😀😀😀", 2);
+        assert_eq!(chunks, ["😀😀", "😀😀", "😀"]);
+
+        assert!(chunk_text("", 10).is_empty());
+    }
+
+    #[test]
+    fn test_detail_levels() {
+        assert_eq!("brief".parse::<SummaryDetail>().unwrap(), SummaryDetail::Brief);
+        assert_eq!("Detailed".parse::<SummaryDetail>().unwrap(), SummaryDetail::Detailed);
+        assert!("verbose".parse::<SummaryDetail>().is_err());
+    }
+}
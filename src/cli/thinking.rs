@@ -0,0 +1,134 @@
+// Teacher thinking display
+//
+// Teachers with a thinking budget stream their reasoning before the answer.
+// It's shown above the response, dimmed and marked with 💭 so it can't be
+// mistaken for the answer: collapsed to a one-line summary by default,
+// expanded with /thinking (or Ctrl+T), or hidden with
+// `features.show_thinking = false`. Display only; thinking is never kept in
+// the conversation history.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How streamed thinking is shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThinkingDisplay {
+    Hidden,
+    Collapsed,
+    Expanded,
+}
+
+static DISPLAY: AtomicU8 = AtomicU8::new(ThinkingDisplay::Collapsed as u8);
+
+const DIM: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+/// Set the initial display (from `features.show_thinking`)
+pub fn init(show_thinking: bool) {
+    set_display(if show_thinking {
+        ThinkingDisplay::Collapsed
+    } else {
+        ThinkingDisplay::Hidden
+    });
+}
+
+pub fn display() -> ThinkingDisplay {
+    match DISPLAY.load(Ordering::Relaxed) {
+        0 => ThinkingDisplay::Hidden,
+        2 => ThinkingDisplay::Expanded,
+        _ => ThinkingDisplay::Collapsed,
+    }
+}
+
+pub fn set_display(display: ThinkingDisplay) {
+    DISPLAY.store(display as u8, Ordering::Relaxed);
+}
+
+/// Expand collapsed thinking or collapse expanded thinking (/thinking)
+///
+/// Hidden thinking is shown expanded. Returns the new display.
+pub fn toggle() -> ThinkingDisplay {
+    let next = match display() {
+        ThinkingDisplay::Expanded => ThinkingDisplay::Collapsed,
+        ThinkingDisplay::Collapsed | ThinkingDisplay::Hidden => ThinkingDisplay::Expanded,
+    };
+    set_display(next);
+    next
+}
+
+/// Status line for the display setting
+pub fn status_message(display: ThinkingDisplay) -> String {
+    match display {
+        ThinkingDisplay::Hidden => "Teacher thinking: hidden".to_string(),
+        ThinkingDisplay::Collapsed => {
+            "Teacher thinking: collapsed (/thinking or Ctrl+T to expand)".to_string()
+        }
+        ThinkingDisplay::Expanded => {
+            "Teacher thinking: expanded (/thinking or Ctrl+T to collapse)".to_string()
+        }
+    }
+}
+
+/// Streamed thinking as shown above a response (None when hidden or empty)
+pub fn format_section(thinking: &str, display: ThinkingDisplay, in_progress: bool) -> Option<String> {
+    let thinking = thinking.trim();
+    if thinking.is_empty() || display == ThinkingDisplay::Hidden {
+        return None;
+    }
+
+    let lines = thinking.lines().count();
+    let section = match display {
+        ThinkingDisplay::Expanded => {
+            let mut section = String::from("💭 thinking");
+            for line in thinking.lines() {
+                section.push_str("\n  │ ");
+                section.push_str(line);
+            }
+            section
+        }
+        _ if in_progress => format!("💭 thinking… ({} lines)", lines),
+        _ => format!(
+            "💭 thinking ({} line{}, /thinking to expand)",
+            lines,
+            if lines == 1 { "" } else { "s" }
+        ),
+    };
+
+    // Dim each line: the TUI wraps and re-styles messages line by line
+    Some(
+        section
+            .lines()
+            .map(|line| format!("{}{}{}", DIM, line, RESET))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strip(text: &str) -> String {
+        text.replace(DIM, "").replace(RESET, "")
+    }
+
+    #[test]
+    fn test_format_section() {
+        let thinking = "First check the file.\nThen answer.";
+
+        assert!(format_section(thinking, ThinkingDisplay::Hidden, false).is_none());
+        assert!(format_section("  ", ThinkingDisplay::Expanded, false).is_none());
+
+        let collapsed = format_section(thinking, ThinkingDisplay::Collapsed, false).unwrap();
+        assert_eq!(strip(&collapsed), "💭 thinking (2 lines, /thinking to expand)");
+        assert!(collapsed.starts_with(DIM));
+
+        let streaming = format_section(thinking, ThinkingDisplay::Collapsed, true).unwrap();
+        assert_eq!(strip(&streaming), "💭 thinking… (2 lines)");
+
+        let expanded = format_section(thinking, ThinkingDisplay::Expanded, false).unwrap();
+        assert_eq!(
+            strip(&expanded),
+            "💭 thinking\n  │ First check the file.\n  │ Then answer."
+        );
+    }
+}
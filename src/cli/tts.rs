@@ -0,0 +1,225 @@
+// Text-to-speech output
+//
+// After each complete response, the prose is piped to an external TTS command
+// (`say` on macOS, `espeak` elsewhere by default). Speech runs in a detached
+// child process so the REPL never waits on it; the next input stops it.
+
+use std::io::Write;
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static COMMAND: Mutex<Vec<String>> = Mutex::new(Vec::new());
+static CURRENT: Mutex<Option<Child>> = Mutex::new(None);
+
+/// Platform TTS command that reads the text from stdin
+pub fn default_command() -> Vec<String> {
+    if cfg!(target_os = "macos") {
+        vec!["say".to_string()]
+    } else {
+        vec!["espeak".to_string(), "--stdin".to_string()]
+    }
+}
+
+/// Set up TTS from config (call once at startup)
+///
+/// `command` is the program and its arguments; the text is written to its
+/// stdin. None uses the platform default.
+pub fn init(enabled: bool, command: Option<Vec<String>>) {
+    let command = command
+        .filter(|command| !command.is_empty())
+        .unwrap_or_else(default_command);
+    *COMMAND.lock().unwrap() = command;
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Turn speech on or off for this session
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+    if !enabled {
+        stop();
+    }
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Configured command, for status messages
+pub fn command_line() -> String {
+    COMMAND.lock().unwrap().join(" ")
+}
+
+/// Speak a complete response (no-op when TTS is off)
+///
+/// Any speech still playing is stopped first. Failures are logged, never
+/// surfaced: a missing TTS binary shouldn't break the REPL.
+pub fn speak(response: &str) {
+    if !is_enabled() {
+        return;
+    }
+    stop();
+
+    let text = speakable_text(response);
+    if text.is_empty() {
+        return;
+    }
+
+    let command = COMMAND.lock().unwrap().clone();
+    let Some((program, args)) = command.split_first() else {
+        return;
+    };
+
+    let mut child = match Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            tracing::warn!("Failed to start TTS command '{}': {}", program, e);
+            return;
+        }
+    };
+
+    // Feed stdin from a thread so long responses can't block on a full pipe
+    if let Some(mut stdin) = child.stdin.take() {
+        std::thread::spawn(move || {
+            let _ = stdin.write_all(text.as_bytes());
+        });
+    }
+
+    *CURRENT.lock().unwrap() = Some(child);
+}
+
+/// Stop any speech in progress (called when the user submits new input)
+pub fn stop() {
+    if let Some(mut child) = CURRENT.lock().unwrap().take() {
+        if let Ok(None) = child.try_wait() {
+            let _ = child.kill();
+        }
+        let _ = child.wait();
+    }
+}
+
+/// Reduce a Markdown response to the prose worth reading aloud
+///
+/// Fenced code blocks are dropped entirely; headings, list markers,
+/// emphasis, inline code ticks and link targets are stripped.
+pub fn speakable_text(markdown: &str) -> String {
+    let mut lines = Vec::new();
+    let mut in_code_block = false;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block || trimmed.starts_with('|') || is_rule(trimmed) {
+            continue;
+        }
+
+        let without_marker = trimmed
+            .trim_start_matches('#')
+            .trim_start_matches('>')
+            .trim_start();
+        let without_marker = strip_list_marker(without_marker);
+
+        let prose = strip_inline_markup(without_marker);
+        if !prose.trim().is_empty() {
+            lines.push(prose.trim().to_string());
+        }
+    }
+
+    lines.join("\n")
+}
+
+fn is_rule(line: &str) -> bool {
+    line.len() >= 3 && line.chars().all(|c| matches!(c, '-' | '*' | '_' | ' '))
+}
+
+fn strip_list_marker(line: &str) -> &str {
+    if let Some(rest) = line
+        .strip_prefix("- ")
+        .or_else(|| line.strip_prefix("* "))
+        .or_else(|| line.strip_prefix("+ "))
+    {
+        return rest;
+    }
+
+    // Numbered list: "1. item"
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 {
+        if let Some(rest) = line[digits..].strip_prefix(". ") {
+            return rest;
+        }
+    }
+    line
+}
+
+/// Drop emphasis/code markers and keep only the text of `[text](url)` links
+fn strip_inline_markup(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    let mut prev = ' ';
+
+    while let Some(c) = chars.next() {
+        let after = chars.peek().copied().unwrap_or(' ');
+        match c {
+            '*' | '`' => {}
+            // Underscores inside identifiers (snake_case) are kept
+            '_' if !(prev.is_alphanumeric() && after.is_alphanumeric()) => {}
+            ']' if chars.peek() == Some(&'(') => {
+                // Skip the link target
+                for next in chars.by_ref() {
+                    if next == ')' {
+                        break;
+                    }
+                }
+            }
+            '[' | ']' => {}
+            _ => out.push(c),
+        }
+        prev = c;
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_speakable_text_strips_code_and_markdown() {
+        let response = "## Fixing the build\n\
+                        \n\
+                        The **borrow checker** rejects `my_value` _here_:\n\
+                        \n\
+                        ```rust\n\
+                        let x = &mut y;\n\
+                        ```\n\
+                        \n\
+                        - Clone the value first\n\
+                        2. See [the book](https://doc.rust-lang.org/book/) for details\n\
+                        ---\n\
+                        | a | b |";
+
+        assert_eq!(
+            speakable_text(response),
+            "Fixing the build\n\
+             The borrow checker rejects my_value here:\n\
+             Clone the value first\n\
+             See the book for details"
+        );
+    }
+
+    #[test]
+    fn test_speakable_text_code_only_is_empty() {
+        assert_eq!(speakable_text("```\nls -la\n```"), "");
+    }
+}
@@ -36,6 +36,7 @@ pub fn spawn_input_task(
                     // Process first event
                     let first_event_result = match crossterm::event::read() {
                         Ok(Event::Key(key)) => {
+                            crate::cli::idle::record_activity();
                             // Priority 1: Handle active dialog (if any)
                             if tui.active_dialog.is_some() {
                                 let dialog_result = if let Some(dialog) = tui.active_dialog.as_mut() {
@@ -65,8 +66,8 @@ pub fn spawn_input_task(
                                 // Enter without Shift: Submit input
                                 let input = tui.input_textarea.lines().join("\n");
                                 if !input.trim().is_empty() {
-                                    // Add to command history
-                                    tui.command_history.push(input.clone());
+                                    // Add to command history (subject to the history policy)
+                                    crate::cli::history::push(&mut tui.command_history, &input);
                                     tui.history_index = None;
                                     tui.history_draft = None; // Clear any saved draft
 
@@ -103,6 +104,14 @@ pub fn spawn_input_task(
                                     // Ctrl+/: Show help (send as command)
                                     Ok(Some("/help".to_string()))
                                 }
+                                (KeyCode::Char('t'), m) if m.contains(KeyModifiers::CONTROL) => {
+                                    // Ctrl+T: Expand/collapse teacher thinking (send as command)
+                                    Ok(Some("/thinking".to_string()))
+                                }
+                                (KeyCode::Char(digit @ '1'..='9'), m) if m.contains(KeyModifiers::ALT) => {
+                                    // Alt+<n>: Copy code block n of the last response (send as command)
+                                    Ok(Some(format!("/copy {}", digit)))
+                                }
                                 (KeyCode::BackTab, _) => {
                                     // Shift+Tab: Toggle plan mode (send as command)
                                     Ok(Some("/plan".to_string()))
@@ -213,6 +222,7 @@ pub fn spawn_input_task(
                                 }
                             }
                             Ok(Event::Key(key)) => {
+                                crate::cli::idle::record_activity();
                                 // Pass key event to textarea
                                 tui.input_textarea.input(Event::Key(key));
                                 had_input = true;
@@ -11,6 +11,7 @@ use ratatui::{
 };
 
 use super::dialog::{Dialog, DialogOption, DialogType};
+use crate::cli::glyphs;
 use crate::config::ColorScheme;
 
 /// Widget for rendering dialogs
@@ -466,10 +467,11 @@ impl<'a> Widget for DialogWidget<'a> {
         };
 
         // Create paragraph with top border for header
-        let paragraph = Paragraph::new(lines)
+        let paragraph = Paragraph::new(glyphs::lines_for_terminal(lines))
             .block(
                 Block::default()
                     .borders(Borders::TOP)
+                    .border_set(glyphs::border_set())
                     .title(format!(" {} ", self.dialog.title))
                     .title_alignment(Alignment::Center)
                     .style(Style::default().fg(self.colors.dialog.border.to_color())),
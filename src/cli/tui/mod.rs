@@ -44,7 +44,7 @@ pub use tabbed_dialog::{TabbedDialog, TabbedDialogResult};
 pub use tabbed_dialog_widget::TabbedDialogWidget;
 pub use input_widget::render_input_widget;
 pub use scrollback::ScrollbackBuffer;
-pub use shadow_buffer::{ShadowBuffer, diff_buffers, visible_length, extract_visible_chars};
+pub use shadow_buffer::{ShadowBuffer, OutputLayout, diff_buffers, visible_length, extract_visible_chars};
 pub use status_widget::StatusWidget;
 
 // Import DialogType for internal use
@@ -137,6 +137,9 @@ where
 }
 
 /// Calculate viewport height dynamically based on terminal size
+///
+/// Callers pass the size capped by `OutputLayout::capped_size`, so wrapped
+/// row counts match the message column rather than the full terminal.
 fn calculate_viewport_height(terminal_size: (u16, u16)) -> usize {
     let (_, term_height) = terminal_size;
 
@@ -175,6 +178,8 @@ pub struct TuiRenderer {
     viewport_height: usize,
     /// Current inline viewport size (1 + input_lines + 4)
     current_inline_viewport_size: u16,
+    /// Column messages are wrapped to (`features.max_output_width`)
+    output_layout: OutputLayout,
     /// Shadow buffer for rendering (2D character array)
     shadow_buffer: ShadowBuffer,
     /// Previous frame buffer (for diff-based updates)
@@ -296,23 +301,8 @@ impl TuiRenderer {
             return;
         }
 
-        // Command suggestions (prefix matching)
-        let commands = vec![
-            ("/help", "Show available commands"),
-            ("/local", "Check local model status"),
-            ("/clear", "Clear conversation history"),
-            ("/plan", "Toggle plan mode"),
-            ("/approve", "Approve current plan"),
-            ("/reject", "Reject current plan"),
-            ("/show-plan", "Display current plan"),
-            ("/save-plan", "Save last response to plan"),
-            ("/done", "Exit plan mode"),
-            ("/exit", "Exit shammah"),
-            ("/quit", "Exit shammah"),
-        ];
-
-        // Find matching command
-        for (cmd, _desc) in commands {
+        // Command suggestions (prefix matching against the command reference)
+        for cmd in crate::cli::help::command_names() {
             if cmd.starts_with(trimmed) && cmd != trimmed {
                 // Found a match - suggest the rest
                 self.ghost_text = Some(cmd[trimmed.len()..].to_string());
@@ -348,6 +338,7 @@ impl TuiRenderer {
         output_manager: Arc<OutputManager>,
         status_bar: Arc<StatusBar>,
         colors: crate::config::ColorScheme,
+        output_layout: OutputLayout,
     ) -> Result<Self> {
         // Setup terminal with inline viewport - preserves terminal scrollback
         enable_raw_mode().context("Failed to enable raw mode")?;
@@ -378,13 +369,14 @@ impl TuiRenderer {
         let term_size = crossterm::terminal::size()
             .context("Failed to get terminal size")?;
         let (term_width, _term_height) = term_size;
+        let column_size = output_layout.capped_size(term_size);
 
         // Calculate dynamic viewport height
-        let viewport_height = calculate_viewport_height(term_size);
+        let viewport_height = calculate_viewport_height(column_size);
 
         // ScrollbackBuffer tracks all messages (not for rendering, for structure)
         // We'll use insert_before() to write to terminal scrollback
-        let scrollback = ScrollbackBuffer::new(viewport_height, term_width as usize);
+        let scrollback = ScrollbackBuffer::new(viewport_height, column_size.0 as usize);
 
         // Calculate visible scrollback area (above inline viewport)
         // Inline viewport starts at 6 lines: 1 (separator) + 1 (input) + 4 (status)
@@ -393,8 +385,8 @@ impl TuiRenderer {
         let visible_scrollback_rows = _term_height.saturating_sub(initial_viewport_size) as usize;
 
         // Initialize shadow buffers for diff-based rendering
-        let shadow_buffer = ShadowBuffer::new(term_width as usize, visible_scrollback_rows);
-        let prev_frame_buffer = ShadowBuffer::new(term_width as usize, visible_scrollback_rows);
+        let shadow_buffer = ShadowBuffer::with_layout(term_width as usize, visible_scrollback_rows, output_layout);
+        let prev_frame_buffer = ShadowBuffer::with_layout(term_width as usize, visible_scrollback_rows, output_layout);
 
         // Ensure stdout is disabled - we'll write via insert_before() instead
         // (Already disabled in main.rs, but double-check for safety)
@@ -428,6 +420,7 @@ impl TuiRenderer {
             scrollback,
             viewport_height,
             current_inline_viewport_size: 6, // Initial: 1 separator + 1 input + 4 status
+            output_layout,
             shadow_buffer,
             prev_frame_buffer,
             needs_full_refresh: false,
@@ -490,11 +483,7 @@ impl TuiRenderer {
                     .context("Failed to create feedback logger")?;
 
                 // Create feedback entry
-                let entry = crate::feedback::FeedbackEntry::new(
-                    query.clone(),
-                    response.clone(),
-                    rating,
-                );
+                let entry = logger.entry(query.clone(), response.clone(), rating);
 
                 // Log feedback
                 logger.log(&entry)
@@ -561,14 +550,8 @@ impl TuiRenderer {
                 .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
         }
 
-        // Write history (limit to last 1000 commands)
-        let history_to_save: Vec<&String> = self
-            .command_history
-            .iter()
-            .rev()
-            .take(1000)
-            .rev()
-            .collect();
+        // Write history (secrets/ignored lines dropped, capped to history.max_entries)
+        let history_to_save = crate::cli::history::entries_to_save(&self.command_history);
 
         let mut file = std::fs::File::create(&history_file)
             .with_context(|| format!("Failed to create history file: {}", history_file.display()))?;
@@ -581,6 +564,14 @@ impl TuiRenderer {
         Ok(())
     }
 
+    /// Forget all command history, in memory and on disk
+    pub fn clear_history(&mut self) -> Result<()> {
+        self.command_history.clear();
+        self.history_index = None;
+        self.history_draft = None;
+        self.save_history()
+    }
+
     /// Resize viewport if needed based on input lines and dialog presence
     fn resize_viewport_if_needed(&mut self, input_lines: u16, has_dialog: bool) -> Result<()> {
         // Calculate needed viewport size
@@ -631,8 +622,10 @@ impl TuiRenderer {
             let old_shadow = &self.shadow_buffer;
             let old_prev = &self.prev_frame_buffer;
 
-            let mut new_shadow = ShadowBuffer::new(term_size.0 as usize, visible_scrollback_rows);
-            let mut new_prev = ShadowBuffer::new(term_size.0 as usize, visible_scrollback_rows);
+            let mut new_shadow =
+                ShadowBuffer::with_layout(term_size.0 as usize, visible_scrollback_rows, self.output_layout);
+            let mut new_prev =
+                ShadowBuffer::with_layout(term_size.0 as usize, visible_scrollback_rows, self.output_layout);
 
             // Copy cells from old buffers where possible (preserves background styles)
             for y in 0..old_shadow.height.min(new_shadow.height) {
@@ -768,7 +761,7 @@ impl TuiRenderer {
                             .take(scrollback_height as usize)
                             .rev()
                             .flat_map(|msg| {
-                                let formatted = msg.format(&self.colors);
+                                let formatted = msg.format_for_terminal(&self.colors);
                                 formatted.lines().map(|line| Line::raw(line.to_string())).collect::<Vec<_>>()
                             })
                             .collect();
@@ -821,7 +814,7 @@ impl TuiRenderer {
                             .take(scrollback_height as usize)
                             .rev()
                             .flat_map(|msg| {
-                                let formatted = msg.format(&self.colors);
+                                let formatted = msg.format_for_terminal(&self.colors);
                                 formatted.lines().map(|line| Line::raw(line.to_string())).collect::<Vec<_>>()
                             })
                             .collect();
@@ -858,8 +851,9 @@ impl TuiRenderer {
                     use ratatui::widgets::Paragraph;
                     use ratatui::style::{Color, Style};
 
-                    let separator_char = '─'; // Unicode box-drawing (U+2500)
-                    let separator_line = separator_char.to_string().repeat(chunks[0].width as usize);
+                    // Unicode box-drawing (U+2500), or '-' on terminals without Unicode
+                    let separator_char = crate::cli::glyphs::glyph("─", "-");
+                    let separator_line = separator_char.repeat(chunks[0].width as usize);
                     let separator_widget = Paragraph::new(Line::from(Span::styled(
                         separator_line,
                         Style::default().fg(Color::DarkGray),
@@ -867,7 +861,7 @@ impl TuiRenderer {
                     frame.render_widget(separator_widget, chunks[0]);
 
                     // Render input with ghost text
-                    render_input_widget(frame, &input_textarea, chunks[1], "❯", &self.colors, self.ghost_text.as_deref());
+                    render_input_widget(frame, &input_textarea, chunks[1], crate::cli::glyphs::glyph("❯", ">"), &self.colors, self.ghost_text.as_deref());
 
                     // Render status
                     let status_widget = StatusWidget::new(&status_bar, &self.colors);
@@ -916,7 +910,7 @@ impl TuiRenderer {
             // Format messages with their styles (keep ANSI codes for foreground colors)
             let mut lines: Vec<(String, ratatui::style::Style)> = Vec::new();
             for (idx, msg) in new_messages.iter().enumerate() {
-                let formatted = msg.format(&self.colors);  // Keep ANSI codes!
+                let formatted = msg.format_for_terminal(&self.colors);  // Keep ANSI codes!
                 let style = msg.background_style().unwrap_or_default();
                 for line in formatted.lines() {
                     lines.push((line.to_string(), style));
@@ -927,23 +921,27 @@ impl TuiRenderer {
                 }
             }
 
-            let num_lines = lines.len().min(u16::MAX as usize) as u16;
-
             // Calculate where inline viewport starts (first row after scrollback)
-            let (_term_width, term_height) = crossterm::terminal::size()?;
+            let (term_width, term_height) = crossterm::terminal::size()?;
             let insert_row = term_height.saturating_sub(self.current_inline_viewport_size);
 
             // Revert to using ratatui's insert_before for now
             // Direct crossterm approach caused terminal scrolling issues
             // TODO: Investigate scroll region support for cleaner implementation
 
-            // Strip ANSI codes for ratatui (it expects plain text + Style)
+            // Strip ANSI codes for ratatui (it expects plain text + Style),
+            // wrapping to the message column since set_string doesn't wrap
+            let (column_offset, column_width) = self.output_layout.column(term_width as usize);
             let mut plain_lines: Vec<(String, ratatui::style::Style)> = Vec::new();
             for (line, style) in lines.iter() {
                 let plain_text = strip_ansi_codes(line);
-                plain_lines.push((plain_text, *style));
+                for row in shadow_buffer::wrap_chars(&plain_text, column_width) {
+                    plain_lines.push((row, *style));
+                }
             }
 
+            let num_lines = plain_lines.len().min(u16::MAX as usize) as u16;
+
             use crossterm::terminal::{BeginSynchronizedUpdate, EndSynchronizedUpdate};
             let mut stdout = io::stdout();
             execute!(stdout, BeginSynchronizedUpdate)?;
@@ -952,7 +950,7 @@ impl TuiRenderer {
             self.terminal.insert_before(num_lines, |buf| {
                 for (i, (line, style)) in plain_lines.iter().enumerate() {
                     if i < buf.area.height as usize {
-                        buf.set_string(0, i as u16, line, *style);
+                        buf.set_string(column_offset as u16, i as u16, line, *style);
                     }
                 }
             })?;
@@ -1029,6 +1027,8 @@ impl TuiRenderer {
             // Poll for key events (100ms timeout)
             if event::poll(Duration::from_millis(100))? {
                 if let Event::Key(key) = event::read()? {
+                    crate::cli::idle::record_activity();
+
                     // Handle the key event
                     let dialog = self
                         .active_dialog
@@ -1085,6 +1085,8 @@ impl TuiRenderer {
             // Poll for key events (100ms timeout)
             if event::poll(Duration::from_millis(100))? {
                 if let Event::Key(key) = event::read()? {
+                    crate::cli::idle::record_activity();
+
                     // Handle the key event
                     let dialog = self
                         .active_tabbed_dialog
@@ -1114,7 +1116,8 @@ impl TuiRenderer {
     /// Show LLM-prompted questions and collect answers
     ///
     /// Uses tabbed dialog for multiple questions or single dialog for one question.
-    /// Returns AskUserQuestionOutput with all answers.
+    /// Returns AskUserQuestionOutput with all answers, or the Cancelled variant
+    /// if the user dismisses a dialog (an error only means the dialog failed).
     pub fn show_llm_question(
         &mut self,
         input: &crate::cli::AskUserQuestionInput,
@@ -1140,13 +1143,15 @@ impl TuiRenderer {
             // Check for cancellation
             match result {
                 TabbedDialogResult::Completed(answers) => {
-                    Ok(crate::cli::AskUserQuestionOutput {
+                    Ok(crate::cli::AskUserQuestionOutput::Answered {
                         questions: input.questions.clone(),
                         answers,
                     })
                 }
                 TabbedDialogResult::Cancelled => {
-                    anyhow::bail!("User cancelled dialog")
+                    Ok(crate::cli::AskUserQuestionOutput::Cancelled {
+                        questions: input.questions.clone(),
+                    })
                 }
             }
         } else {
@@ -1164,7 +1169,9 @@ impl TuiRenderer {
 
                 // Check for cancellation
                 if result.is_cancelled() {
-                    anyhow::bail!("User cancelled dialog");
+                    return Ok(crate::cli::AskUserQuestionOutput::Cancelled {
+                        questions: input.questions.clone(),
+                    });
                 }
 
                 // Extract answer
@@ -1175,7 +1182,7 @@ impl TuiRenderer {
                 }
             }
 
-            Ok(crate::cli::AskUserQuestionOutput {
+            Ok(crate::cli::AskUserQuestionOutput::Answered {
                 questions: input.questions.clone(),
                 answers,
             })
@@ -1184,8 +1191,12 @@ impl TuiRenderer {
 
     /// Read a line of input from the integrated text area
     pub fn read_line(&mut self) -> Result<Option<String>> {
+        use crate::cli::idle::IdleState;
         use crossterm::event::{KeyCode, KeyModifiers};
 
+        let idle_line = crate::cli::StatusLineType::Custom("idle".to_string());
+        crate::cli::idle::record_activity();
+
         loop {
             // Check for pending output BEFORE rendering
             let output_mgr = self.output_manager.clone();
@@ -1200,6 +1211,8 @@ impl TuiRenderer {
             if event::poll(Duration::from_millis(100))? {
                 match event::read()? {
                     Event::Key(key) => {
+                        crate::cli::idle::record_activity();
+                        self.status_bar.remove_line(&idle_line);
                         match (key.code, key.modifiers) {
                             (KeyCode::Enter, KeyModifiers::SHIFT) => {
                                 // Shift+Enter: Insert newline (multi-line input)
@@ -1214,8 +1227,8 @@ impl TuiRenderer {
                                     continue; // Don't submit empty input
                                 }
 
-                                // Add to history
-                                self.command_history.push(input.clone());
+                                // Add to history (subject to the history policy)
+                                crate::cli::history::push(&mut self.command_history, &input);
                                 self.history_index = None;
 
                                 // Clear input immediately before returning
@@ -1274,6 +1287,20 @@ impl TuiRenderer {
             if output_mgr.has_pending() {
                 self.flush_output_safe(&output_mgr)?;
             }
+
+            // Idle at the prompt too long: behave like Ctrl+D (save and exit)
+            match crate::cli::idle::check() {
+                IdleState::Active => {}
+                IdleState::Warning(remaining) => {
+                    self.status_bar
+                        .update_line(idle_line.clone(), crate::cli::idle::warning_message(remaining));
+                }
+                IdleState::Expired => {
+                    self.status_bar.remove_line(&idle_line);
+                    self.output_manager.write_info("Idle timeout reached - saving and exiting.");
+                    return Ok(None);
+                }
+            }
         }
     }
 
@@ -1287,6 +1314,8 @@ impl TuiRenderer {
         // Poll with very short timeout (10ms)
         while event::poll(std::time::Duration::from_millis(10))? {
             if let Ok(event_data) = event::read() {
+                crate::cli::idle::record_activity();
+
                 // Update textarea with keystrokes
                 self.input_textarea.input(event_data);
                 had_events = true;
@@ -1581,9 +1610,10 @@ impl TuiRenderer {
     /// Handle terminal resize event
     pub fn handle_resize(&mut self, width: u16, height: u16) -> Result<()> {
         // Update viewport dimensions
-        let new_viewport_height = calculate_viewport_height((width, height));
+        let (column_width, _) = self.output_layout.capped_size((width, height));
+        let new_viewport_height = calculate_viewport_height((column_width, height));
         self.viewport_height = new_viewport_height;
-        self.scrollback.update_viewport(new_viewport_height, width as usize);
+        self.scrollback.update_viewport(new_viewport_height, column_width as usize);
 
         // Resize shadow buffers
         let visible_rows = height.saturating_sub(6) as usize; // -6 for inline viewport
@@ -1640,7 +1670,7 @@ impl TuiRenderer {
                     .take(extra_lines as usize)
                     .rev()
                     .flat_map(|msg| {
-                        let formatted = msg.format(&self.colors);
+                        let formatted = msg.format_for_terminal(&self.colors);
                         formatted.lines().map(|line| Line::raw(line.to_string())).collect::<Vec<_>>()
                     })
                     .collect();
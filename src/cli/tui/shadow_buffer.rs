@@ -6,12 +6,14 @@
 // 3. Update only changed cells in terminal
 //
 // This approach ensures:
-// - Long lines wrap correctly at terminal width
+// - Long lines wrap correctly at terminal width (or the configured
+//   `max_output_width` column, see OutputLayout)
 // - ANSI codes are preserved (zero-width)
 // - No truncation or text bleeding
 // - Efficient updates (only changed cells)
 
 use crate::cli::messages::MessageRef;
+use crate::config::{FeaturesConfig, OutputAlign};
 use ratatui::style::Style;
 
 /// A single cell in the shadow buffer (character + style)
@@ -39,6 +41,58 @@ impl Cell {
     }
 }
 
+/// Column that messages are wrapped to within the terminal
+///
+/// With `max_output_width` unset the column is the whole terminal. Otherwise
+/// it's at most that wide and sits on the left or in the middle.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OutputLayout {
+    pub max_width: Option<usize>,
+    pub align: OutputAlign,
+}
+
+impl OutputLayout {
+    pub fn from_features(features: &FeaturesConfig) -> Self {
+        Self {
+            max_width: features.max_output_width.map(usize::from),
+            align: features.output_align,
+        }
+    }
+
+    /// (offset, width) of the column in a terminal `terminal_width` wide
+    pub fn column(&self, terminal_width: usize) -> (usize, usize) {
+        let width = self
+            .max_width
+            .map_or(terminal_width, |max| max.min(terminal_width));
+        let offset = match self.align {
+            OutputAlign::Left => 0,
+            OutputAlign::Center => (terminal_width - width) / 2,
+        };
+        (offset, width)
+    }
+
+    /// Terminal size with the width narrowed to the column
+    pub fn capped_size(&self, terminal_size: (u16, u16)) -> (u16, u16) {
+        let (_, width) = self.column(terminal_size.0 as usize);
+        (width as u16, terminal_size.1)
+    }
+}
+
+/// Split plain text into rows of at most `width` characters
+///
+/// Used for lines written straight to terminal scrollback, which (unlike
+/// the shadow buffer) aren't wrapped by anything else.
+pub fn wrap_chars(text: &str, width: usize) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    if chars.is_empty() {
+        return vec![String::new()];
+    }
+    chars
+        .chunks(width.max(1))
+        .map(|row| row.iter().collect())
+        .collect()
+}
+
 /// 2D shadow buffer for terminal rendering
 pub struct ShadowBuffer {
     /// 2D array of cells [y][x]
@@ -47,19 +101,32 @@ pub struct ShadowBuffer {
     pub width: usize,
     /// Terminal height (scrollback area)
     pub height: usize,
+    /// Column messages are wrapped to
+    layout: OutputLayout,
 }
 
 impl ShadowBuffer {
     /// Create a new shadow buffer with given dimensions
     pub fn new(width: usize, height: usize) -> Self {
+        Self::with_layout(width, height, OutputLayout::default())
+    }
+
+    /// Create a shadow buffer that wraps messages to a column of the terminal
+    pub fn with_layout(width: usize, height: usize, layout: OutputLayout) -> Self {
         let cells = vec![vec![Cell::empty(); width]; height];
         Self {
             cells,
             width,
             height,
+            layout,
         }
     }
 
+    /// (offset, width) of the message column
+    pub fn column(&self) -> (usize, usize) {
+        self.layout.column(self.width)
+    }
+
     /// Resize the buffer (called on terminal resize)
     pub fn resize(&mut self, width: usize, height: usize) {
         self.width = width;
@@ -106,7 +173,8 @@ impl ShadowBuffer {
         }
 
         // Calculate how many rows this line needs
-        let chars_per_row = self.width.max(1);
+        let (offset, column_width) = self.column();
+        let chars_per_row = column_width.max(1);
         let num_rows = (visible_chars.len() + chars_per_row - 1) / chars_per_row;
         let num_rows = num_rows.min(self.height - y); // Don't exceed buffer
 
@@ -118,13 +186,13 @@ impl ShadowBuffer {
 
             // Write actual characters
             for (col_idx, &ch) in chunk.iter().enumerate() {
-                self.set(col_idx, y + row_idx, Cell { ch, style });
+                self.set(offset + col_idx, y + row_idx, Cell { ch, style });
             }
 
             // Fill remaining cells in row with spaces (but preserve background style)
-            // This ensures the background extends to the full width
+            // This ensures the background extends to the full column width
             for col_idx in chunk.len()..chars_per_row {
-                self.set(col_idx, y + row_idx, Cell { ch: ' ', style });
+                self.set(offset + col_idx, y + row_idx, Cell { ch: ' ', style });
             }
         }
 
@@ -140,7 +208,7 @@ impl ShadowBuffer {
         // Format all messages and collect lines with their styles
         let mut all_lines: Vec<(String, Style)> = Vec::new(); // (line_text, style)
         for msg in messages {
-            let formatted = msg.format(colors);
+            let formatted = msg.format_for_terminal(colors);
             let style = msg.background_style().unwrap_or_default();
             for line in formatted.lines() {
                 all_lines.push((line.to_string(), style));
@@ -154,13 +222,14 @@ impl ShadowBuffer {
         // Calculate how many lines we need (with wrapping)
         let mut total_rows_needed = 0;
         let mut line_row_counts: Vec<usize> = Vec::new();
+        let (_, column_width) = self.column();
 
         for (line, _style) in &all_lines {
             let visible_len = visible_length(line);
             let rows = if visible_len == 0 {
                 1
             } else {
-                (visible_len + self.width - 1) / self.width.max(1)
+                (visible_len + column_width - 1) / column_width.max(1)
             };
             line_row_counts.push(rows);
             total_rows_needed += rows;
@@ -217,6 +286,7 @@ impl ShadowBuffer {
             cells: self.cells.clone(),
             width: self.width,
             height: self.height,
+            layout: self.layout,
         }
     }
 }
@@ -383,6 +453,44 @@ mod tests {
         assert!(rows > 1);
     }
 
+    #[test]
+    fn test_output_layout_column() {
+        let full = OutputLayout::default();
+        assert_eq!(full.column(120), (0, 120));
+
+        let left = OutputLayout { max_width: Some(80), align: OutputAlign::Left };
+        assert_eq!(left.column(120), (0, 80));
+        // Never wider than the terminal
+        assert_eq!(left.column(60), (0, 60));
+        assert_eq!(left.capped_size((120, 40)), (80, 40));
+
+        let center = OutputLayout { max_width: Some(80), align: OutputAlign::Center };
+        assert_eq!(center.column(120), (20, 80));
+        assert_eq!(center.column(60), (0, 60));
+    }
+
+    #[test]
+    fn test_shadow_buffer_wraps_to_column() {
+        let layout = OutputLayout { max_width: Some(4), align: OutputAlign::Center };
+        let mut buf = ShadowBuffer::with_layout(10, 5, layout);
+
+        let rows = buf.write_line(0, "abcdef", Style::default());
+        assert_eq!(rows, 2);
+        // Column is cells 3..7
+        assert_eq!(buf.get(2, 0).unwrap().ch, ' ');
+        assert_eq!(buf.get(3, 0).unwrap().ch, 'a');
+        assert_eq!(buf.get(6, 0).unwrap().ch, 'd');
+        assert_eq!(buf.get(7, 0).unwrap().ch, ' ');
+        assert_eq!(buf.get(3, 1).unwrap().ch, 'e');
+    }
+
+    #[test]
+    fn test_wrap_chars() {
+        assert_eq!(wrap_chars("abcdefg", 3), ["abc", "def", "g"]);
+        assert_eq!(wrap_chars("", 3), [""]);
+        assert_eq!(wrap_chars("héé", 2), ["hé", "é"]);
+    }
+
     #[test]
     fn test_diff_buffers() {
         let mut buf1 = ShadowBuffer::new(5, 3);
@@ -1,6 +1,7 @@
 // Status Widget - Renders the multi-line status bar
 //
-// Displays status lines from StatusBar in the bottom section of the TUI
+// Displays status lines from StatusBar in the bottom section of the TUI.
+// When there are more lines than rows, the lowest-priority ones are left out.
 
 use ratatui::{
     buffer::Buffer,
@@ -10,6 +11,8 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Widget},
 };
 
+use crate::cli::glyphs;
+use crate::cli::status_bar::fit_lines;
 use crate::cli::{StatusBar, StatusLineType};
 use crate::config::ColorScheme;
 
@@ -28,12 +31,22 @@ impl<'a> StatusWidget<'a> {
     /// Get the style for a status line based on its type
     fn get_line_style(&self, line_type: &StatusLineType) -> Style {
         match line_type {
+            StatusLineType::Mode => {
+                // Mode indicator: same accent as suggestions, bold
+                Style::default()
+                    .fg(self.colors.ui.cursor.to_color())
+                    .add_modifier(Modifier::BOLD)
+            }
             StatusLineType::LiveStats => {
                 // Live stats: from color scheme
                 Style::default()
                     .fg(self.colors.status.live_stats.to_color())
                     .add_modifier(Modifier::BOLD)
             }
+            StatusLineType::Throughput => {
+                // Throughput: same color as live stats, not bold
+                Style::default().fg(self.colors.status.live_stats.to_color())
+            }
             StatusLineType::TrainingStats => {
                 // Training stats: from color scheme
                 Style::default().fg(self.colors.status.training.to_color())
@@ -48,6 +61,10 @@ impl<'a> StatusWidget<'a> {
                 // Operation status: from color scheme
                 Style::default().fg(self.colors.status.operation.to_color())
             }
+            StatusLineType::Context => {
+                // Conversation context: same as training stats
+                Style::default().fg(self.colors.status.training.to_color())
+            }
             StatusLineType::Suggestions => {
                 // Suggestions: cyan with subtle styling
                 Style::default()
@@ -67,7 +84,7 @@ impl<'a> StatusWidget<'a> {
     /// Convert a status line to a styled Line
     fn status_line_to_line(&self, line_type: &StatusLineType, content: &str) -> Line<'static> {
         let style = self.get_line_style(line_type);
-        Line::from(Span::styled(content.to_string(), style))
+        Line::from(Span::styled(glyphs::for_terminal(content.to_string()), style))
     }
 }
 
@@ -81,10 +98,15 @@ impl<'a> Widget for StatusWidget<'a> {
             .iter()
             .find(|sl| sl.line_type == StatusLineType::CompactionPercent);
 
-        // Convert to styled lines (excluding compaction line)
-        let lines: Vec<Line> = status_lines
+        // Fit the rest below the top border, dropping low-priority lines first
+        let content_lines = status_lines
             .iter()
             .filter(|sl| sl.line_type != StatusLineType::CompactionPercent)
+            .cloned()
+            .collect();
+        let rows = area.height.saturating_sub(1) as usize;
+        let lines: Vec<Line> = fit_lines(content_lines, rows, area.width as usize)
+            .iter()
             .map(|sl| self.status_line_to_line(&sl.line_type, &sl.content))
             .collect();
 
@@ -100,7 +122,7 @@ impl<'a> Widget for StatusWidget<'a> {
 
         // Build title with optional compaction percentage
         let title = if let Some(compaction) = compaction_line {
-            format!(" Status • {} ", compaction.content)
+            glyphs::for_terminal(format!(" Status • {} ", compaction.content))
         } else {
             " Status ".to_string()
         };
@@ -109,6 +131,7 @@ impl<'a> Widget for StatusWidget<'a> {
         let paragraph = Paragraph::new(lines).block(
             Block::default()
                 .borders(Borders::TOP)
+                .border_set(glyphs::border_set())
                 .title(title)
                 .title_alignment(Alignment::Right)
                 .border_style(Style::default().fg(self.colors.status.border.to_color())),
@@ -11,6 +11,7 @@ use ratatui::{
 };
 
 use super::tabbed_dialog::TabbedDialog;
+use crate::cli::glyphs;
 use crate::config::ColorScheme;
 
 /// Widget for rendering tabbed dialogs
@@ -214,6 +215,7 @@ impl Widget for TabbedDialogWidget<'_> {
 
         let block = Block::default()
             .borders(Borders::TOP | Borders::BOTTOM)
+            .border_set(glyphs::border_set())
             .border_style(Style::default().fg(self.colors.dialog.border.to_color()))
             .title(title)
             .title_alignment(Alignment::Center);
@@ -224,7 +226,7 @@ impl Widget for TabbedDialogWidget<'_> {
 
         // Render content lines
         let content = self.render_content();
-        let paragraph = Paragraph::new(content)
+        let paragraph = Paragraph::new(glyphs::lines_for_terminal(content))
             .wrap(Wrap { trim: false })
             .alignment(Alignment::Left);
 
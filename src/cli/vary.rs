@@ -0,0 +1,97 @@
+// Response variations (/vary)
+//
+// Regenerates the response to the last prompt several times with more
+// randomness, so the user can pick the best one. Teachers get a raised
+// temperature; the local model samples afresh on every call.
+
+use anyhow::Result;
+
+use crate::claude::Message;
+use crate::generators::Generator;
+
+/// Variants generated by a bare `/vary`
+pub const DEFAULT_VARIANTS: usize = 3;
+
+/// Upper bound for `/vary <n>` (each variant is a full request)
+pub const MAX_VARIANTS: usize = 5;
+
+/// Teacher temperature for variants (above the usual ~0.7)
+pub const VARY_TEMPERATURE: f32 = 1.0;
+
+/// Characters of each variant shown in the selection list
+const PREVIEW_CHARS: usize = 80;
+
+/// Generate `count` alternative responses to `context`
+///
+/// Variants are plain text (no tools). `on_progress` is called with the
+/// 1-based number of the variant about to be generated. Stops at the first
+/// failure, returning the variants generated so far unless there are none.
+pub async fn generate_variants(
+    generator: &dyn Generator,
+    context: Vec<Message>,
+    count: usize,
+    mut on_progress: impl FnMut(usize),
+) -> Result<Vec<String>> {
+    let mut variants = Vec::with_capacity(count);
+
+    for number in 1..=count {
+        on_progress(number);
+        match generator
+            .generate_with_temperature(context.clone(), None, VARY_TEMPERATURE)
+            .await
+        {
+            Ok(response) => variants.push(response.text),
+            Err(e) if variants.is_empty() => return Err(e),
+            Err(e) => {
+                tracing::warn!("Variant {} failed, keeping {}: {}", number, variants.len(), e);
+                break;
+            }
+        }
+    }
+
+    Ok(variants)
+}
+
+/// Selection list label, e.g. "Variant 2 (1,234 chars)"
+pub fn variant_label(number: usize, text: &str) -> String {
+    format!("Variant {} ({} chars)", number, group_thousands(text.chars().count()))
+}
+
+/// First line-ish of a variant, whitespace collapsed, for the selection list
+pub fn preview(text: &str) -> String {
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.chars().count() <= PREVIEW_CHARS {
+        collapsed
+    } else {
+        let cut: String = collapsed.chars().take(PREVIEW_CHARS - 1).collect();
+        format!("{}…", cut.trim_end())
+    }
+}
+
+fn group_thousands(n: usize) -> String {
+    let digits = n.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_variant_label_and_preview() {
+        assert_eq!(variant_label(2, &"x".repeat(1234)), "Variant 2 (1,234 chars)");
+        assert_eq!(variant_label(1, "short"), "Variant 1 (5 chars)");
+
+        assert_eq!(preview("Line one\n\n  line   two"), "Line one line two");
+        let long = preview(&"word ".repeat(40));
+        assert!(long.ends_with('…'));
+        assert!(long.chars().count() <= PREVIEW_CHARS);
+    }
+}
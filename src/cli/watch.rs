@@ -0,0 +1,285 @@
+// File watch (/watch <glob> :: <prompt>)
+//
+// Re-submits a prompt whenever a file matching the glob changes, for
+// TDD-style loops. Changes are debounced so a save that touches several
+// files runs the prompt once. A prompt that edits the files it watches
+// would trigger itself forever, so changes are ignored while a run is in
+// flight and for a cooldown after it, and the watch stops itself after
+// WATCH_MAX_RUNS runs. Any input (or /watch stop) ends it.
+
+use anyhow::{bail, Context, Result};
+use notify::{EventKind, RecursiveMode, Watcher};
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// Quiet period after a change before the prompt is re-run
+pub const WATCH_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Changes this soon after a run (or while it's running) are ignored
+pub const WATCH_COOLDOWN: Duration = Duration::from_secs(5);
+
+/// Runs before a watch stops itself
+pub const WATCH_MAX_RUNS: usize = 20;
+
+/// Separator between the glob and the prompt
+const PROMPT_SEPARATOR: &str = "::";
+
+/// What to watch and what to ask when it changes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchSpec {
+    pub pattern: String,
+    pub prompt: String,
+}
+
+impl WatchSpec {
+    /// Parse `<glob> :: <prompt>`
+    pub fn parse(args: &str) -> Result<Self> {
+        let Some((pattern, prompt)) = args.split_once(PROMPT_SEPARATOR) else {
+            bail!("Usage: /watch <glob> :: <prompt>");
+        };
+        let (pattern, prompt) = (pattern.trim(), prompt.trim());
+        if pattern.is_empty() || prompt.is_empty() {
+            bail!("Usage: /watch <glob> :: <prompt>");
+        }
+        glob::Pattern::new(pattern).with_context(|| format!("Invalid glob pattern: {}", pattern))?;
+        Ok(Self {
+            pattern: pattern.to_string(),
+            prompt: prompt.to_string(),
+        })
+    }
+}
+
+/// Directory to watch for a glob: its leading components without wildcards
+fn watch_root(pattern: &Path) -> PathBuf {
+    let mut root = PathBuf::new();
+    for component in pattern.components() {
+        if let Component::Normal(part) = component {
+            if part.to_string_lossy().contains(['*', '?', '[', '{']) {
+                break;
+            }
+        }
+        root.push(component);
+    }
+    // A pattern naming a single file watches its directory
+    if root == pattern {
+        root.pop();
+    }
+    if root.as_os_str().is_empty() {
+        PathBuf::from(".")
+    } else {
+        root
+    }
+}
+
+/// What to do about a change
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchDecision {
+    /// Re-run the prompt (run number, 1-based)
+    Run(usize),
+    /// Ignore it: a run is in flight or just finished
+    Skip,
+    /// The run cap is reached; stop watching
+    Exhausted,
+}
+
+/// Cooldown and run cap for a watch
+#[derive(Debug, Clone)]
+pub struct WatchGuard {
+    runs: usize,
+    max_runs: usize,
+    cooldown: Duration,
+    last_activity: Option<Instant>,
+}
+
+impl Default for WatchGuard {
+    fn default() -> Self {
+        Self::new(WATCH_COOLDOWN, WATCH_MAX_RUNS)
+    }
+}
+
+impl WatchGuard {
+    pub fn new(cooldown: Duration, max_runs: usize) -> Self {
+        Self {
+            runs: 0,
+            max_runs: max_runs.max(1),
+            cooldown,
+            last_activity: None,
+        }
+    }
+
+    /// Decide whether a change at `now` re-runs the prompt
+    ///
+    /// `busy` is whether a query is still running. Changes seen while busy
+    /// are most likely the run's own edits, so they push the cooldown out.
+    pub fn on_change(&mut self, now: Instant, busy: bool) -> WatchDecision {
+        if busy {
+            self.last_activity = Some(now);
+            return WatchDecision::Skip;
+        }
+        if self
+            .last_activity
+            .is_some_and(|last| now.saturating_duration_since(last) < self.cooldown)
+        {
+            return WatchDecision::Skip;
+        }
+        if self.runs >= self.max_runs {
+            return WatchDecision::Exhausted;
+        }
+        self.runs += 1;
+        self.last_activity = Some(now);
+        WatchDecision::Run(self.runs)
+    }
+
+    pub fn runs(&self) -> usize {
+        self.runs
+    }
+
+    pub fn max_runs(&self) -> usize {
+        self.max_runs
+    }
+}
+
+/// An active watch: the file watcher and its debounce task
+pub struct FileWatch {
+    pub spec: WatchSpec,
+    pub guard: WatchGuard,
+    _watcher: notify::RecommendedWatcher,
+    task: JoinHandle<()>,
+}
+
+impl FileWatch {
+    /// Start watching `spec.pattern`
+    ///
+    /// `on_change` is called with the changed files once changes settle
+    /// (after WATCH_DEBOUNCE without another one).
+    pub fn start<F>(spec: WatchSpec, on_change: F) -> Result<Self>
+    where
+        F: Fn(Vec<PathBuf>) + Send + 'static,
+    {
+        let pattern = Path::new(&spec.pattern);
+        let pattern = if pattern.is_absolute() {
+            pattern.to_path_buf()
+        } else {
+            std::env::current_dir()
+                .context("Failed to get current directory")?
+                .join(pattern)
+        };
+        let matcher = glob::Pattern::new(&pattern.to_string_lossy())
+            .with_context(|| format!("Invalid glob pattern: {}", spec.pattern))?;
+        let root = watch_root(&pattern);
+
+        let (tx, mut rx) = mpsc::unbounded_channel::<PathBuf>();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            let Ok(event) = event else {
+                return;
+            };
+            if !matches!(
+                event.kind,
+                EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+            ) {
+                return;
+            }
+            for path in event.paths {
+                if matcher.matches_path(&path) {
+                    let _ = tx.send(path);
+                }
+            }
+        })
+        .context("Failed to create file watcher")?;
+        watcher
+            .watch(&root, RecursiveMode::Recursive)
+            .with_context(|| format!("Failed to watch {}", root.display()))?;
+
+        let task = tokio::spawn(async move {
+            while let Some(first) = rx.recv().await {
+                let mut changed = vec![first];
+                // Collect the rest of the burst (editors often write twice)
+                while let Ok(Some(path)) = tokio::time::timeout(WATCH_DEBOUNCE, rx.recv()).await {
+                    changed.push(path);
+                }
+                changed.sort();
+                changed.dedup();
+                on_change(changed);
+            }
+        });
+
+        Ok(Self {
+            spec,
+            guard: WatchGuard::default(),
+            _watcher: watcher,
+            task,
+        })
+    }
+}
+
+impl Drop for FileWatch {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Shown when a watch starts
+pub fn started_message(spec: &WatchSpec) -> String {
+    format!(
+        "👀 Watching {} — re-running \"{}\" on changes (up to {} runs).\n   \
+         Type anything or /watch stop to stop.",
+        spec.pattern, spec.prompt, WATCH_MAX_RUNS
+    )
+}
+
+/// Shown before a re-run: which files changed and the run count
+pub fn run_message(changed: &[PathBuf], run: usize, max_runs: usize) -> String {
+    let cwd = std::env::current_dir().unwrap_or_default();
+    let names: Vec<String> = changed
+        .iter()
+        .map(|path| path.strip_prefix(&cwd).unwrap_or(path).display().to_string())
+        .collect();
+    let files = match names.len() {
+        0 => "files".to_string(),
+        1..=3 => names.join(", "),
+        n => format!("{} and {} more", names[..2].join(", "), n - 2),
+    };
+    format!("🔁 {} changed — run {}/{}", files, run, max_runs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_watch_spec() {
+        let spec = WatchSpec::parse("src/**/*.rs :: run the tests and fix failures").unwrap();
+        assert_eq!(spec.pattern, "src/**/*.rs");
+        assert_eq!(spec.prompt, "run the tests and fix failures");
+
+        assert!(WatchSpec::parse("src/**/*.rs").is_err());
+        assert!(WatchSpec::parse(":: prompt").is_err());
+        assert!(WatchSpec::parse("src/*.rs ::  ").is_err());
+        assert!(WatchSpec::parse("src/[.rs :: prompt").is_err());
+    }
+
+    #[test]
+    fn test_watch_root() {
+        assert_eq!(watch_root(Path::new("/repo/src/**/*.rs")), PathBuf::from("/repo/src"));
+        assert_eq!(watch_root(Path::new("/repo/Cargo.toml")), PathBuf::from("/repo"));
+        assert_eq!(watch_root(Path::new("*.rs")), PathBuf::from("."));
+    }
+
+    #[test]
+    fn test_watch_guard_cooldown_and_cap() {
+        let start = Instant::now();
+        let at = |secs: u64| start + Duration::from_secs(secs);
+        let mut guard = WatchGuard::new(Duration::from_secs(5), 2);
+
+        assert_eq!(guard.on_change(at(0), false), WatchDecision::Run(1));
+        // The run's own edits, then the tail of them right after it finishes
+        assert_eq!(guard.on_change(at(10), true), WatchDecision::Skip);
+        assert_eq!(guard.on_change(at(12), false), WatchDecision::Skip);
+
+        assert_eq!(guard.on_change(at(20), false), WatchDecision::Run(2));
+        assert_eq!(guard.on_change(at(40), false), WatchDecision::Exhausted);
+        assert_eq!(guard.runs(), 2);
+    }
+}
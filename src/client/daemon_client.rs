@@ -1,25 +1,36 @@
 // Daemon client implementation
 //
-// HTTP client that communicates with the Shammah daemon.
-// Automatically spawns daemon if not running.
+// HTTP client that communicates with the Shammah daemon, over TCP or a Unix
+// socket (see `transport`). Automatically spawns daemon if not running.
 
 use anyhow::{Context, Result};
-use reqwest::Client;
+use serde::Serialize;
 use std::time::Duration;
+use tokio::sync::mpsc;
 use tracing::{debug, error, info};
 
+use super::sse::{self, SseParser};
+use super::transport::DaemonTransport;
 use crate::claude::{ContentBlock, Message};
-use crate::daemon::ensure_daemon_running;
+use crate::daemon::{ensure_daemon_running, BindAddress, DaemonReadiness};
+use crate::generators::StreamChunk;
 use crate::server::openai_types::{
     ChatCompletionRequest, ChatCompletionResponse, ChatMessage, Tool, FunctionDefinition,
 };
+use crate::server::TrainingAction;
 use crate::tools::types::{ToolDefinition, ToolUse};
 use crate::tools::executor::ToolExecutor;
 
+/// OpenAI-compatible chat endpoint
+const CHAT_COMPLETIONS: &str = "/v1/chat/completions";
+
+/// Timeout for streaming requests (generation can take minutes)
+const STREAM_TIMEOUT: Duration = Duration::from_secs(300);
+
 /// Configuration for daemon connection
 #[derive(Debug, Clone)]
 pub struct DaemonConfig {
-    /// Daemon bind address (e.g., "127.0.0.1:11434")
+    /// Daemon bind address ("127.0.0.1:11435", or "unix:/path/to/socket")
     pub bind_address: String,
     /// Whether to auto-spawn daemon if not running
     pub auto_spawn: bool,
@@ -48,44 +59,59 @@ impl DaemonConfig {
     }
 }
 
+/// A query answer with its routing details (`shammah query --json`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct QueryResult {
+    pub response: String,
+    /// "local", "teacher", or "daemon" when the daemon didn't say
+    pub route: String,
+    pub latency_ms: u64,
+    pub model: String,
+}
+
 /// HTTP client for communicating with Shammah daemon
 pub struct DaemonClient {
     base_url: String,
-    client: Client,
+    transport: DaemonTransport,
     config: DaemonConfig,
+    /// Teacher model requested for every query (`--model`)
+    teacher_model: Option<String>,
 }
 
 impl DaemonClient {
     /// Create a new daemon client and ensure daemon is running
     pub async fn connect(config: DaemonConfig) -> Result<Self> {
-        let base_url = format!("http://{}", config.bind_address);
+        let address = BindAddress::parse(&config.bind_address)?;
+        let base_url = address.base_url();
+        let transport =
+            DaemonTransport::new(address, Duration::from_secs(config.timeout_seconds))?;
 
         // Ensure daemon is running (auto-spawn if enabled)
         if config.auto_spawn {
-            ensure_daemon_running(Some(&config.bind_address))
+            ensure_daemon_running(Some(&config.bind_address), DaemonReadiness::Alive)
                 .await
                 .context("Failed to ensure daemon is running")?;
         } else {
             // Just check if daemon is reachable
-            Self::check_health(&base_url).await?;
+            Self::check_health(&transport).await?;
         }
 
-        let client = Client::builder()
-            .timeout(Duration::from_secs(config.timeout_seconds))
-            .pool_idle_timeout(Duration::from_secs(90))
-            .pool_max_idle_per_host(0)  // Disable connection pooling
-            .build()
-            .context("Failed to build HTTP client")?;
-
-        info!(base_url = %base_url, "Connected to daemon");
+        info!(address = %transport.address(), "Connected to daemon");
 
         Ok(Self {
             base_url,
-            client,
+            transport,
             config,
+            teacher_model: None,
         })
     }
 
+    /// Ask the daemon to use `model` for teacher calls instead of its configured one
+    pub fn with_teacher_model(mut self, model: Option<String>) -> Self {
+        self.teacher_model = model;
+        self
+    }
+
     /// Create a client with default configuration
     pub async fn connect_default() -> Result<Self> {
         Self::connect(DaemonConfig::default()).await
@@ -100,6 +126,52 @@ impl DaemonClient {
     ///
     /// This is the main method for CLI to send queries.
     pub async fn query(&self, messages: Vec<Message>) -> Result<String> {
+        self.query_routed(messages, None).await
+    }
+
+    /// Send a query with an optional routing override (`forward_only`)
+    async fn query_routed(
+        &self,
+        messages: Vec<Message>,
+        forward_only: Option<bool>,
+    ) -> Result<String> {
+        let response = self.chat_completion(messages, forward_only).await?;
+        Ok(response_text(&response))
+    }
+
+    /// Post a non-streaming chat completion and parse the response
+    async fn chat_completion(
+        &self,
+        messages: Vec<Message>,
+        forward_only: Option<bool>,
+    ) -> Result<ChatCompletionResponse> {
+        let request = self.chat_request(messages, forward_only, false);
+
+        // Send to daemon
+        debug!(address = %self.transport.address(), "Sending chat completion request");
+
+        let response: ChatCompletionResponse = self
+            .transport
+            .post_json(CHAT_COMPLETIONS, &request, None)
+            .await
+            .map_err(|e| {
+                error!("Daemon request failed: {:#}", e);
+                anyhow::anyhow!("Failed to send request to daemon: {:#}", e)
+            })?
+            .json()
+            .await
+            .context("Failed to parse response from daemon")?;
+
+        Ok(response)
+    }
+
+    /// Routed chat completion request for `messages` (text blocks only)
+    fn chat_request(
+        &self,
+        messages: Vec<Message>,
+        forward_only: Option<bool>,
+        stream: bool,
+    ) -> ChatCompletionRequest {
         // Convert internal messages to OpenAI format
         let openai_messages: Vec<ChatMessage> = messages
             .into_iter()
@@ -124,58 +196,131 @@ impl DaemonClient {
             })
             .collect();
 
-        // Build request
-        let request = ChatCompletionRequest {
+        ChatCompletionRequest {
             model: "qwen-local".to_string(),
             messages: openai_messages,
             max_tokens: None,
             temperature: None,
             top_p: None,
             n: None,
-            stream: false,
+            stream,
             stop: None,
             tools: None,
             local_only: None,
-        };
+            forward_only,
+            teacher_model: self.teacher_model.clone(),
+            max_response_chars: None,
+        }
+    }
 
-        // Send to daemon
-        let url = format!("{}/v1/chat/completions", self.base_url);
-        debug!(url = %url, "Sending chat completion request");
+    /// Send a query and stream the answer as the daemon generates it
+    ///
+    /// Text arrives as `StreamChunk::TextDelta`s read from the daemon's SSE
+    /// chat-completions stream, followed by the whole text as one
+    /// `ContentBlockComplete`. A daemon that answers without
+    /// `text/event-stream` (an older daemon) sends its complete response
+    /// instead, which is passed on as a single delta, as `query` would
+    /// return it.
+    pub async fn query_stream(
+        &self,
+        messages: Vec<Message>,
+    ) -> Result<mpsc::Receiver<Result<StreamChunk>>> {
+        let request = self.chat_request(messages, None, true);
 
-        let response: ChatCompletionResponse = self
-            .client
-            .post(&url)
-            .json(&request)
-            .send()
+        debug!(address = %self.transport.address(), "Sending streaming chat completion request");
+
+        let response = self
+            .transport
+            .post_json(CHAT_COMPLETIONS, &request, Some(STREAM_TIMEOUT))
             .await
             .map_err(|e| {
-                // Log detailed error info
-                error!("HTTP request failed: {}", e);
-                if e.is_timeout() {
-                    error!("  → Error type: TIMEOUT");
-                } else if e.is_connect() {
-                    error!("  → Error type: CONNECTION");
-                } else if e.is_request() {
-                    error!("  → Error type: REQUEST");
-                } else if e.is_body() {
-                    error!("  → Error type: BODY");
-                } else {
-                    error!("  → Error type: OTHER");
+                error!("Daemon request failed: {:#}", e);
+                anyhow::anyhow!("Failed to send request to daemon: {:#}", e)
+            })?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_body = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+            anyhow::bail!("Daemon request failed with status {}: {}", status, error_body);
+        }
+
+        let (tx, rx) = mpsc::channel(100);
+
+        if !response.is_event_stream() {
+            debug!("Daemon answered without streaming, sending the whole response");
+            let response: ChatCompletionResponse = response
+                .json()
+                .await
+                .context("Failed to parse response from daemon")?;
+            let text = response_text(&response);
+            tokio::spawn(async move {
+                if tx.send(Ok(StreamChunk::TextDelta(text.clone()))).await.is_ok() {
+                    let block = ContentBlock::Text { text };
+                    let _ = tx.send(Ok(StreamChunk::ContentBlockComplete(block))).await;
                 }
-                anyhow::anyhow!("Failed to send request to daemon: {}", e)
-            })?
-            .json()
-            .await
-            .context("Failed to parse response from daemon")?;
+            });
+            return Ok(rx);
+        }
+
+        tokio::spawn(async move {
+            use futures::StreamExt;
+
+            let mut stream = response.bytes_stream();
+            let mut parser = SseParser::new();
+            let mut accumulated_text = String::new();
+            let mut done = false;
 
-        // Extract response text
-        let response_text = response
-            .choices
-            .first()
-            .and_then(|choice| choice.message.content.clone())
-            .unwrap_or_else(|| "No response from model".to_string());
+            'stream: while let Some(chunk) = stream.next().await {
+                let bytes = match chunk {
+                    Ok(bytes) => bytes,
+                    Err(e) => {
+                        let _ = tx.send(Err(e.context("Failed to read streaming chunk"))).await;
+                        return;
+                    }
+                };
 
-        Ok(response_text)
+                for data in parser.push(&bytes) {
+                    if data == sse::DONE {
+                        done = true;
+                        break 'stream;
+                    }
+                    if let Some(content) = sse::delta_content(&data) {
+                        accumulated_text.push_str(&content);
+                        if tx.send(Ok(StreamChunk::TextDelta(content))).await.is_err() {
+                            // Receiver dropped, stop streaming
+                            return;
+                        }
+                    }
+                }
+            }
+
+            // A daemon that dies mid-answer closes the stream without [DONE]
+            if !done {
+                let _ = tx.send(Err(anyhow::anyhow!("Daemon closed the stream before the response finished"))).await;
+                return;
+            }
+
+            if !accumulated_text.is_empty() {
+                let block = ContentBlock::Text {
+                    text: accumulated_text,
+                };
+                let _ = tx.send(Ok(StreamChunk::ContentBlockComplete(block))).await;
+            }
+        });
+
+        Ok(rx)
+    }
+
+    /// Send a simple text query and stream the answer (see `query_stream`)
+    pub async fn query_text_stream(&self, query: &str) -> Result<mpsc::Receiver<Result<StreamChunk>>> {
+        let messages = vec![Message {
+            role: "user".to_string(),
+            content: vec![ContentBlock::Text {
+                text: query.to_string(),
+            }],
+        }];
+
+        self.query_stream(messages).await
     }
 
     /// Send a simple text query (convenience method)
@@ -190,6 +335,46 @@ impl DaemonClient {
         self.query(messages).await
     }
 
+    /// Send a simple text query and report how it was answered
+    ///
+    /// Route and latency come from the daemon; an older daemon that doesn't
+    /// report them gets route "daemon" and the latency measured here.
+    pub async fn query_json(&self, query: &str) -> Result<QueryResult> {
+        let messages = vec![Message {
+            role: "user".to_string(),
+            content: vec![ContentBlock::Text {
+                text: query.to_string(),
+            }],
+        }];
+
+        let start = std::time::Instant::now();
+        let response = self.chat_completion(messages, None).await?;
+        Ok(QueryResult {
+            response: response_text(&response),
+            route: response.route.clone().unwrap_or_else(|| "daemon".to_string()),
+            latency_ms: response
+                .latency_ms
+                .unwrap_or_else(|| start.elapsed().as_millis() as u64),
+            model: response.model,
+        })
+    }
+
+    /// Send a text query straight to the teacher, bypassing routing
+    ///
+    /// The counterpart of `query_local_only`. The daemon still records a
+    /// route trace (reason `user_forced`) and collects the response for
+    /// training.
+    pub async fn query_forward_only(&self, query: &str) -> Result<String> {
+        let messages = vec![Message {
+            role: "user".to_string(),
+            content: vec![ContentBlock::Text {
+                text: query.to_string(),
+            }],
+        }];
+
+        self.query_routed(messages, Some(true)).await
+    }
+
     /// Query with tool execution loop
     ///
     /// Handles the full tool execution flow:
@@ -235,16 +420,16 @@ impl DaemonClient {
                 stream: false,
                 stop: None,
                 local_only: None,
+                forward_only: None,
+                teacher_model: self.teacher_model.clone(),
+                max_response_chars: None,
             };
 
-            let url = format!("{}/v1/chat/completions", self.base_url);
-            debug!(url = %url, turn, "Sending chat completion request with tools");
+            debug!(address = %self.transport.address(), turn, "Sending chat completion request with tools");
 
             let response: ChatCompletionResponse = self
-                .client
-                .post(&url)
-                .json(&request)
-                .send()
+                .transport
+                .post_json(CHAT_COMPLETIONS, &request, None)
                 .await
                 .context("Failed to send request to daemon")?
                 .json()
@@ -353,6 +538,13 @@ impl DaemonClient {
                             // This is a limitation - proper implementation would split messages
                             text_parts.push(format!("[Tool Result for {}]: {}", tool_use_id, content));
                         }
+                        ContentBlock::Image { .. } => {
+                            // Chat messages to the daemon are text-only
+                            tracing::warn!("Daemon doesn't accept images, dropping image block");
+                        }
+                        ContentBlock::Thinking { .. } | ContentBlock::RedactedThinking { .. } => {
+                            // Teacher-side reasoning, not sent to the daemon
+                        }
                     }
                 }
 
@@ -386,12 +578,9 @@ impl DaemonClient {
 
     /// Check daemon health
     pub async fn check_health_status(&self) -> Result<serde_json::Value> {
-        let url = format!("{}/health", self.base_url);
         let response = self
-            .client
-            .get(&url)
-            .timeout(Duration::from_secs(30))  // Increased from 5 to 30 seconds
-            .send()
+            .transport
+            .get("/health", Some(Duration::from_secs(30)))  // Increased from 5 to 30 seconds
             .await
             .context("Failed to check daemon health")?
             .json()
@@ -401,17 +590,60 @@ impl DaemonClient {
         Ok(response)
     }
 
+    /// Details of the daemon's local model (or its loading state)
+    pub async fn model_info(&self) -> Result<crate::models::ModelInfo> {
+        self.transport
+            .get("/model", Some(Duration::from_secs(30)))
+            .await
+            .context("Failed to fetch model info")?
+            .json()
+            .await
+            .context("Failed to parse model info")
+    }
+
+    /// Send a training control action (pause/resume/cancel) to the daemon
+    ///
+    /// Returns the training state reported after the action was applied.
+    pub async fn training_control(&self, action: TrainingAction) -> Result<String> {
+        let path = format!("/v1/training/{}", action.as_str());
+        let response = self
+            .transport
+            .post(&path, Some(Duration::from_secs(30)))
+            .await
+            .context("Failed to send training control request")?;
+
+        let status = response.status();
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .context("Failed to parse training control response")?;
+
+        if !status.is_success() {
+            let message = body["message"].as_str().unwrap_or("unknown error");
+            anyhow::bail!("Training {} failed: {}", action.as_str(), message);
+        }
+
+        Ok(body["state"].as_str().unwrap_or("unknown").to_string())
+    }
+
+    /// Get the daemon's current training state ("idle", "training", "paused")
+    pub async fn training_state(&self) -> Result<String> {
+        let body: serde_json::Value = self
+            .transport
+            .get("/v1/training/status", Some(Duration::from_secs(5)))
+            .await
+            .context("Failed to fetch training status")?
+            .json()
+            .await
+            .context("Failed to parse training status")?;
+
+        Ok(body["state"].as_str().unwrap_or("unknown").to_string())
+    }
+
     /// Internal health check (used during connection)
-    async fn check_health(base_url: &str) -> Result<()> {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(30))  // Increased from 5 to 30 seconds
-            .build()
-            .context("Failed to build HTTP client")?;
-
-        let url = format!("{}/health", base_url);
-        let response = client
-            .get(&url)
-            .send()
+    async fn check_health(transport: &DaemonTransport) -> Result<()> {
+        let response = transport
+            .get("/health", Some(Duration::from_secs(30)))  // Increased from 5 to 30 seconds
             .await
             .context("Daemon is not reachable")?;
 
@@ -422,7 +654,7 @@ impl DaemonClient {
         Ok(())
     }
 
-    /// Get base URL
+    /// Get base URL (a placeholder host for Unix socket addresses)
     pub fn base_url(&self) -> &str {
         &self.base_url
     }
@@ -434,11 +666,12 @@ impl DaemonClient {
 
     /// Query local model directly, bypassing routing
     ///
-    /// This sends a request with local_only=true to bypass crisis detection
-    /// and threshold routing, going directly to the local model.
+    /// This sends a request with local_only=true to bypass threshold routing,
+    /// going directly to the local model (crisis queries still get the
+    /// configured crisis response instead).
     /// Returns an error if the model is not ready or generation fails.
     pub async fn query_local_only(&self, query: &str) -> Result<String> {
-        use reqwest::StatusCode;
+        use hyper::StatusCode;
 
         let request = ChatCompletionRequest {
             model: "qwen-local".to_string(),
@@ -457,16 +690,16 @@ impl DaemonClient {
             stop: None,
             tools: None,
             local_only: Some(true), // KEY: Bypass routing
+            forward_only: None,
+            teacher_model: self.teacher_model.clone(),
+            max_response_chars: None,
         };
 
-        let url = format!("{}/v1/chat/completions", self.base_url);
-        debug!(url = %url, "Sending local-only query");
+        debug!(address = %self.transport.address(), "Sending local-only query");
 
         let response = self
-            .client
-            .post(&url)
-            .json(&request)
-            .send()
+            .transport
+            .post_json(CHAT_COMPLETIONS, &request, None)
             .await
             .context("Failed to send request to daemon")?;
 
@@ -529,7 +762,7 @@ impl DaemonClient {
                     info!("Daemon connection failed, attempting auto-restart...");
 
                     // Try to restart daemon
-                    match ensure_daemon_running(Some(&self.config.bind_address)).await {
+                    match ensure_daemon_running(Some(&self.config.bind_address), DaemonReadiness::Alive).await {
                         Ok(_) => {
                             info!("Daemon restarted successfully, retrying query...");
 
@@ -578,7 +811,7 @@ impl DaemonClient {
         F: FnMut(&str) + Send,
     {
         use futures::StreamExt;
-        use reqwest::StatusCode;
+        use hyper::StatusCode;
 
         let request = ChatCompletionRequest {
             model: "qwen-local".to_string(),
@@ -597,17 +830,16 @@ impl DaemonClient {
             stop: None,
             tools: None,
             local_only: Some(true), // Bypass routing
+            forward_only: None,
+            teacher_model: self.teacher_model.clone(),
+            max_response_chars: None,
         };
 
-        let url = format!("{}/v1/chat/completions", self.base_url);
-        debug!(url = %url, "Sending streaming local-only query with callback");
+        debug!(address = %self.transport.address(), "Sending streaming local-only query with callback");
 
         let response = self
-            .client
-            .post(&url)
-            .json(&request)
-            .timeout(Duration::from_secs(300))  // 5 minute timeout for streaming
-            .send()
+            .transport
+            .post_json(CHAT_COMPLETIONS, &request, Some(STREAM_TIMEOUT))
             .await
             .context("Failed to send streaming request to daemon")?;
 
@@ -616,57 +848,19 @@ impl DaemonClient {
                 // Parse SSE stream
                 let mut stream = response.bytes_stream();
                 let mut accumulated_content = String::new();
-                let mut buffer = Vec::new();
+                let mut parser = SseParser::new();
 
-                while let Some(chunk_result) = stream.next().await {
+                'stream: while let Some(chunk_result) = stream.next().await {
                     let chunk = chunk_result.context("Failed to read streaming chunk")?;
-                    buffer.extend_from_slice(&chunk);
-
-                    // Process complete SSE events
-                    // SSE format: "data: {...}\n\n"
-                    // Look for double newline separator
-                    loop {
-                        let separator_pos = buffer.windows(2)
-                            .position(|w| w == b"\n\n")
-                            .or_else(|| buffer.windows(4).position(|w| w == b"\r\n\r\n"));
-
-                        if let Some(pos) = separator_pos {
-                            // Extract event (including separator for proper draining)
-                            let drain_len = if buffer[pos..].starts_with(b"\r\n\r\n") {
-                                pos + 4
-                            } else {
-                                pos + 2
-                            };
-
-                            let event_bytes: Vec<u8> = buffer.drain(..drain_len).collect();
-                            let event_str = String::from_utf8_lossy(&event_bytes);
-
-                            for line in event_str.lines() {
-                                if let Some(data) = line.strip_prefix("data: ") {
-                                    if data.trim() == "[DONE]" {
-                                        break;
-                                    }
-
-                                    // Parse JSON chunk
-                                    if let Ok(chunk_json) = serde_json::from_str::<serde_json::Value>(data) {
-                                        if let Some(choices) = chunk_json["choices"].as_array() {
-                                            if let Some(first_choice) = choices.first() {
-                                                if let Some(delta) = first_choice["delta"].as_object() {
-                                                    // Safely check for "content" key (final chunk has empty delta)
-                                                    if let Some(content) = delta.get("content").and_then(|v| v.as_str()) {
-                                                        accumulated_content.push_str(content);
-                                                        // Call callback for UI update
-                                                        token_callback(content);
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        } else {
-                            // No complete event yet, wait for more data
-                            break;
+                    for data in parser.push(&chunk) {
+                        if data == sse::DONE {
+                            break 'stream;
+                        }
+                        // The final chunk has an empty delta
+                        if let Some(content) = sse::delta_content(&data) {
+                            accumulated_content.push_str(&content);
+                            // Call callback for UI update
+                            token_callback(&content);
                         }
                     }
                 }
@@ -708,7 +902,7 @@ impl DaemonClient {
     /// * `Err` - Error if model not ready or generation fails
     pub async fn query_local_only_streaming(&self, query: &str) -> Result<String> {
         use futures::StreamExt;
-        use reqwest::StatusCode;
+        use hyper::StatusCode;
 
         let request = ChatCompletionRequest {
             model: "qwen-local".to_string(),
@@ -727,17 +921,16 @@ impl DaemonClient {
             stop: None,
             tools: None,
             local_only: Some(true), // Bypass routing
+            forward_only: None,
+            teacher_model: self.teacher_model.clone(),
+            max_response_chars: None,
         };
 
-        let url = format!("{}/v1/chat/completions", self.base_url);
-        debug!(url = %url, "Sending streaming local-only query");
+        debug!(address = %self.transport.address(), "Sending streaming local-only query");
 
         let response = self
-            .client
-            .post(&url)
-            .json(&request)
-            .timeout(Duration::from_secs(300))  // 5 minute timeout for streaming (per chunk, not total)
-            .send()
+            .transport
+            .post_json(CHAT_COMPLETIONS, &request, Some(STREAM_TIMEOUT))
             .await
             .context("Failed to send streaming request to daemon")?;
 
@@ -746,55 +939,17 @@ impl DaemonClient {
                 // Parse SSE stream
                 let mut stream = response.bytes_stream();
                 let mut accumulated_content = String::new();
-                let mut buffer = Vec::new();
+                let mut parser = SseParser::new();
 
-                while let Some(chunk_result) = stream.next().await {
+                'stream: while let Some(chunk_result) = stream.next().await {
                     let chunk = chunk_result.context("Failed to read streaming chunk")?;
-                    buffer.extend_from_slice(&chunk);
-
-                    // Process complete SSE events
-                    // SSE format: "data: {...}\n\n"
-                    // Look for double newline separator
-                    loop {
-                        let separator_pos = buffer.windows(2)
-                            .position(|w| w == b"\n\n")
-                            .or_else(|| buffer.windows(4).position(|w| w == b"\r\n\r\n"));
-
-                        if let Some(pos) = separator_pos {
-                            // Extract event (including separator for proper draining)
-                            let drain_len = if buffer[pos..].starts_with(b"\r\n\r\n") {
-                                pos + 4
-                            } else {
-                                pos + 2
-                            };
-
-                            let event_bytes: Vec<u8> = buffer.drain(..drain_len).collect();
-                            let event_str = String::from_utf8_lossy(&event_bytes);
-
-                            for line in event_str.lines() {
-                                if let Some(data) = line.strip_prefix("data: ") {
-                                    if data.trim() == "[DONE]" {
-                                        break;
-                                    }
-
-                                    // Parse JSON chunk
-                                    if let Ok(chunk_json) = serde_json::from_str::<serde_json::Value>(data) {
-                                        if let Some(choices) = chunk_json["choices"].as_array() {
-                                            if let Some(first_choice) = choices.first() {
-                                                if let Some(delta) = first_choice["delta"].as_object() {
-                                                    // Safely check for "content" key (final chunk has empty delta)
-                                                    if let Some(content) = delta.get("content").and_then(|v| v.as_str()) {
-                                                        accumulated_content.push_str(content);
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        } else {
-                            // No complete event yet, wait for more data
-                            break;
+                    for data in parser.push(&chunk) {
+                        if data == sse::DONE {
+                            break 'stream;
+                        }
+                        // The final chunk has an empty delta
+                        if let Some(content) = sse::delta_content(&data) {
+                            accumulated_content.push_str(&content);
                         }
                     }
                 }
@@ -848,7 +1003,13 @@ impl DaemonClient {
                     info!("Daemon connection failed, attempting auto-restart...");
 
                     // Try to restart daemon
-                    match ensure_daemon_running(Some(&self.config.bind_address)).await {
+                    // A restarted daemon reloads its model; local-only queries need it
+                    match ensure_daemon_running(
+                        Some(&self.config.bind_address),
+                        DaemonReadiness::LocalModel,
+                    )
+                    .await
+                    {
                         Ok(_) => {
                             info!("Daemon restarted successfully, retrying query...");
 
@@ -877,6 +1038,15 @@ impl DaemonClient {
     }
 }
 
+/// Text of the first choice
+fn response_text(response: &ChatCompletionResponse) -> String {
+    response
+        .choices
+        .first()
+        .and_then(|choice| choice.message.content.clone())
+        .unwrap_or_else(|| "No response from model".to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -888,4 +1058,81 @@ mod tests {
         assert!(config.auto_spawn);
         assert_eq!(config.timeout_seconds, 120);
     }
+
+    /// Client for a daemon answering chat completions with `body`
+    #[cfg(unix)]
+    async fn client_for(
+        dir: &std::path::Path,
+        content_type: &'static str,
+        body: &'static str,
+    ) -> DaemonClient {
+        use axum::routing::{get, post};
+
+        let app = axum::Router::new()
+            .route("/health", get(|| async { "ok" }))
+            .route(
+                CHAT_COMPLETIONS,
+                post(move || async move { ([("content-type", content_type)], body) }),
+            );
+        let address = crate::client::transport::serve_test_socket(dir, app);
+
+        DaemonClient::connect(DaemonConfig {
+            bind_address: address.to_string(),
+            auto_spawn: false,
+            timeout_seconds: 5,
+        })
+        .await
+        .unwrap()
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_query_stream_reads_sse() {
+        let dir = tempfile::tempdir().unwrap();
+        let client = client_for(
+            dir.path(),
+            "text/event-stream",
+            "data: {\"choices\":[{\"index\":0,\"delta\":{\"role\":\"assistant\",\"content\":\"Hel\"}}]}\n\n\
+             data: {\"choices\":[{\"index\":0,\"delta\":{\"content\":\"lo\"}}]}\n\n\
+             data: {\"choices\":[{\"index\":0,\"delta\":{},\"finish_reason\":\"stop\"}]}\n\n\
+             data: [DONE]\n\n",
+        )
+        .await;
+
+        let mut rx = client.query_text_stream("hi").await.unwrap();
+        assert!(matches!(rx.recv().await, Some(Ok(StreamChunk::TextDelta(text))) if text == "Hel"));
+        assert!(matches!(rx.recv().await, Some(Ok(StreamChunk::TextDelta(text))) if text == "lo"));
+        match rx.recv().await {
+            Some(Ok(StreamChunk::ContentBlockComplete(block))) => assert_eq!(block.as_text(), Some("Hello")),
+            other => panic!("unexpected chunk: {:?}", other),
+        }
+        assert!(rx.recv().await.is_none());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_query_stream_cut_off() {
+        let dir = tempfile::tempdir().unwrap();
+        let client = client_for(dir.path(), "text/event-stream", ": keep-alive\n\n").await;
+
+        let mut rx = client.query_text_stream("hi").await.unwrap();
+        let error = rx.recv().await.unwrap().unwrap_err();
+        assert!(error.to_string().contains("before the response finished"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_query_stream_without_sse() {
+        let dir = tempfile::tempdir().unwrap();
+        let client = client_for(
+            dir.path(),
+            "application/json",
+            r#"{"id":"1","object":"chat.completion","created":0,"model":"m","choices":[{"index":0,"message":{"role":"assistant","content":"Whole answer"},"finish_reason":"stop"}],"usage":{"prompt_tokens":1,"completion_tokens":2,"total_tokens":3}}"#,
+        )
+        .await;
+
+        let mut rx = client.query_text_stream("hi").await.unwrap();
+        assert!(matches!(rx.recv().await, Some(Ok(StreamChunk::TextDelta(text))) if text == "Whole answer"));
+        assert!(matches!(rx.recv().await, Some(Ok(StreamChunk::ContentBlockComplete(_)))));
+    }
 }
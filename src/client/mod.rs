@@ -4,5 +4,8 @@
 // Handles auto-spawn, health checks, and message passing.
 
 mod daemon_client;
+pub mod sse;
+pub mod transport;
 
-pub use daemon_client::{DaemonClient, DaemonConfig};
+pub use daemon_client::{DaemonClient, DaemonConfig, QueryResult};
+pub use transport::{DaemonResponse, DaemonTransport};
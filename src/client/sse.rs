@@ -0,0 +1,88 @@
+// Server-Sent Events parsing for the daemon's streaming chat completions
+//
+// The daemon streams `chat.completion.chunk` frames as `data: {...}` events
+// separated by a blank line, ending with `data: [DONE]`. Bytes arrive in
+// arbitrary pieces, so the parser buffers until an event is complete.
+
+/// Payload of the event that ends a stream
+pub const DONE: &str = "[DONE]";
+
+/// Splits an SSE byte stream into event data payloads
+#[derive(Debug, Default)]
+pub struct SseParser {
+    buffer: Vec<u8>,
+}
+
+impl SseParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add bytes, returning the data of each event they complete
+    ///
+    /// Multi-line data is joined with newlines; events without data
+    /// (comments, keep-alives) are skipped.
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.buffer.extend_from_slice(bytes);
+
+        let mut events = Vec::new();
+        while let Some((end, separator_len)) = event_end(&self.buffer) {
+            let event: Vec<u8> = self.buffer.drain(..end + separator_len).collect();
+            let event = String::from_utf8_lossy(&event[..end]);
+
+            let data: Vec<&str> = event
+                .lines()
+                .filter_map(|line| line.strip_prefix("data:"))
+                .map(|data| data.strip_prefix(' ').unwrap_or(data))
+                .collect();
+            if !data.is_empty() {
+                events.push(data.join("\n"));
+            }
+        }
+        events
+    }
+}
+
+/// Position and length of the first blank-line separator
+fn event_end(buffer: &[u8]) -> Option<(usize, usize)> {
+    let lf = buffer.windows(2).position(|w| w == b"\n\n");
+    let crlf = buffer.windows(4).position(|w| w == b"\r\n\r\n");
+    match (lf, crlf) {
+        (Some(lf), Some(crlf)) if crlf < lf => Some((crlf, 4)),
+        (Some(lf), _) => Some((lf, 2)),
+        (None, Some(crlf)) => Some((crlf, 4)),
+        (None, None) => None,
+    }
+}
+
+/// Text added by a `chat.completion.chunk` (None for role-only and final chunks)
+pub fn delta_content(data: &str) -> Option<String> {
+    let chunk: serde_json::Value = serde_json::from_str(data).ok()?;
+    let content = chunk.pointer("/choices/0/delta/content")?.as_str()?;
+    (!content.is_empty()).then(|| content.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_events_split_across_chunks() {
+        let mut parser = SseParser::new();
+        assert!(parser.push(b"data: {\"a\"").is_empty());
+        assert_eq!(parser.push(b":1}\n\n: keep-alive\n\ndata: two\r\n\r\ndata: [DO"), vec![
+            "{\"a\":1}".to_string(),
+            "two".to_string()
+        ]);
+        assert_eq!(parser.push(b"NE]\n\n"), vec![DONE.to_string()]);
+    }
+
+    #[test]
+    fn test_delta_content() {
+        let chunk = r#"{"object":"chat.completion.chunk","choices":[{"index":0,"delta":{"content":"Hi"},"finish_reason":null}]}"#;
+        assert_eq!(delta_content(chunk).as_deref(), Some("Hi"));
+        let last = r#"{"choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#;
+        assert_eq!(delta_content(last), None);
+        assert_eq!(delta_content("not json"), None);
+    }
+}
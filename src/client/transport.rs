@@ -0,0 +1,304 @@
+// Daemon transports
+//
+// DaemonClient talks HTTP to the daemon over TCP (reqwest) or, for a
+// `unix:/path` bind address, over a Unix domain socket (hyper, one HTTP/1.1
+// connection per request). Both give back a `DaemonResponse`, so health
+// checks, queries and SSE streaming work the same either way.
+
+use anyhow::{Context, Result};
+use futures::stream::{BoxStream, StreamExt};
+use hyper::body::Bytes;
+use hyper::{Method, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::time::Duration;
+use tracing::error;
+
+use crate::daemon::BindAddress;
+
+/// HTTP transport to the daemon
+#[derive(Clone)]
+pub struct DaemonTransport {
+    address: BindAddress,
+    /// Used for TCP addresses
+    client: reqwest::Client,
+    timeout: Duration,
+}
+
+/// A daemon response: status now, body read or streamed later
+pub struct DaemonResponse {
+    status: StatusCode,
+    content_type: Option<String>,
+    body: BoxStream<'static, Result<Bytes>>,
+}
+
+impl DaemonTransport {
+    /// Transport for `address`, with a default per-request timeout
+    pub fn new(address: BindAddress, timeout: Duration) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .pool_idle_timeout(Duration::from_secs(90))
+            .pool_max_idle_per_host(0) // Disable connection pooling
+            .build()
+            .context("Failed to build HTTP client")?;
+
+        Ok(Self {
+            address,
+            client,
+            timeout,
+        })
+    }
+
+    pub fn address(&self) -> &BindAddress {
+        &self.address
+    }
+
+    /// GET `path` (e.g. "/health")
+    pub async fn get(&self, path: &str, timeout: Option<Duration>) -> Result<DaemonResponse> {
+        self.send(Method::GET, path, None, timeout).await
+    }
+
+    /// POST a JSON body to `path`
+    pub async fn post_json<T: Serialize>(
+        &self,
+        path: &str,
+        body: &T,
+        timeout: Option<Duration>,
+    ) -> Result<DaemonResponse> {
+        let body = serde_json::to_vec(body).context("Failed to serialize request")?;
+        self.send(Method::POST, path, Some(body), timeout).await
+    }
+
+    /// POST with no body (control endpoints)
+    pub async fn post(&self, path: &str, timeout: Option<Duration>) -> Result<DaemonResponse> {
+        self.send(Method::POST, path, None, timeout).await
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<Vec<u8>>,
+        timeout: Option<Duration>,
+    ) -> Result<DaemonResponse> {
+        let timeout = timeout.unwrap_or(self.timeout);
+        match &self.address {
+            BindAddress::Tcp(_) => self.send_tcp(method, path, body, timeout).await,
+            BindAddress::Unix(socket) => {
+                // Bounds the connection and the response headers; a
+                // streamed body is read at the caller's pace
+                tokio::time::timeout(timeout, send_unix(socket, method, path, body))
+                    .await
+                    .with_context(|| format!("Daemon request timed out after {}s", timeout.as_secs()))?
+            }
+        }
+    }
+
+    async fn send_tcp(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<Vec<u8>>,
+        timeout: Duration,
+    ) -> Result<DaemonResponse> {
+        let url = format!("{}{}", self.address.base_url(), path);
+        let mut request = match method {
+            Method::GET => self.client.get(&url),
+            _ => self.client.post(&url),
+        };
+        if let Some(body) = body {
+            request = request.header("content-type", "application/json").body(body);
+        }
+
+        let response = request.timeout(timeout).send().await.map_err(|e| {
+            // Log detailed error info
+            error!("HTTP request failed: {}", e);
+            let kind = if e.is_timeout() {
+                "TIMEOUT"
+            } else if e.is_connect() {
+                "CONNECTION"
+            } else if e.is_request() {
+                "REQUEST"
+            } else if e.is_body() {
+                "BODY"
+            } else {
+                "OTHER"
+            };
+            error!("  → Error type: {}", kind);
+            e
+        })?;
+
+        let status = StatusCode::from_u16(response.status().as_u16())?;
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let body = response
+            .bytes_stream()
+            .map(|chunk| chunk.map_err(anyhow::Error::from))
+            .boxed();
+        Ok(DaemonResponse {
+            status,
+            content_type,
+            body,
+        })
+    }
+}
+
+#[cfg(unix)]
+async fn send_unix(
+    socket: &std::path::Path,
+    method: Method,
+    path: &str,
+    body: Option<Vec<u8>>,
+) -> Result<DaemonResponse> {
+    use http_body_util::{BodyExt, Full};
+    use hyper_util::rt::TokioIo;
+
+    let stream = tokio::net::UnixStream::connect(socket)
+        .await
+        .with_context(|| format!("Failed to connect to daemon socket {}", socket.display()))?;
+    let (mut sender, connection) = hyper::client::conn::http1::handshake(TokioIo::new(stream)).await?;
+    tokio::spawn(async move {
+        if let Err(e) = connection.await {
+            tracing::debug!("Daemon socket connection ended: {}", e);
+        }
+    });
+
+    let mut request = hyper::Request::builder()
+        .method(method)
+        .uri(path)
+        .header(hyper::header::HOST, "localhost");
+    if body.is_some() {
+        request = request.header(hyper::header::CONTENT_TYPE, "application/json");
+    }
+    let request = request.body(Full::new(Bytes::from(body.unwrap_or_default())))?;
+
+    let response = sender.send_request(request).await?;
+    let status = response.status();
+    let content_type = response
+        .headers()
+        .get(hyper::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let body = response
+        .into_body()
+        .into_data_stream()
+        .map(|chunk| chunk.map_err(anyhow::Error::from))
+        .boxed();
+    Ok(DaemonResponse {
+        status,
+        content_type,
+        body,
+    })
+}
+
+#[cfg(not(unix))]
+async fn send_unix(
+    socket: &std::path::Path,
+    _method: Method,
+    _path: &str,
+    _body: Option<Vec<u8>>,
+) -> Result<DaemonResponse> {
+    anyhow::bail!("Unix socket {} is not supported on this platform", socket.display())
+}
+
+impl std::fmt::Debug for DaemonResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DaemonResponse")
+            .field("status", &self.status)
+            .finish_non_exhaustive()
+    }
+}
+
+impl DaemonResponse {
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// Whether the body is Server-Sent Events
+    pub fn is_event_stream(&self) -> bool {
+        self.content_type()
+            .is_some_and(|content_type| content_type.starts_with("text/event-stream"))
+    }
+
+    /// The body as it arrives (for SSE)
+    pub fn bytes_stream(self) -> BoxStream<'static, Result<Bytes>> {
+        self.body
+    }
+
+    pub async fn bytes(mut self) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        while let Some(chunk) = self.body.next().await {
+            bytes.extend_from_slice(&chunk?);
+        }
+        Ok(bytes)
+    }
+
+    pub async fn text(self) -> Result<String> {
+        Ok(String::from_utf8_lossy(&self.bytes().await?).into_owned())
+    }
+
+    pub async fn json<T: DeserializeOwned>(self) -> Result<T> {
+        Ok(serde_json::from_slice(&self.bytes().await?)?)
+    }
+}
+
+/// Serve `app` on a Unix socket in `dir` (test daemons)
+#[cfg(all(test, unix))]
+pub(crate) fn serve_test_socket(dir: &std::path::Path, app: axum::Router) -> BindAddress {
+    let socket = dir.join("daemon.sock");
+    let listener = tokio::net::UnixListener::bind(&socket).unwrap();
+    tokio::spawn(crate::server::serve_unix(listener, app));
+    BindAddress::Unix(socket)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use axum::routing::{get, post};
+    use axum::Json;
+
+    #[tokio::test]
+    async fn test_unix_socket_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = axum::Router::new()
+            .route("/health", get(|| async { Json(serde_json::json!({ "status": "healthy" })) }))
+            .route("/echo", post(|Json(body): Json<serde_json::Value>| async move { Json(body) }));
+
+        let transport =
+            DaemonTransport::new(serve_test_socket(dir.path(), app), Duration::from_secs(5)).unwrap();
+
+        let response = transport.get("/health", None).await.unwrap();
+        assert!(response.status().is_success());
+        let health: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(health["status"], "healthy");
+
+        let echoed: serde_json::Value = transport
+            .post_json("/echo", &serde_json::json!({ "text": "hi" }), None)
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(echoed["text"], "hi");
+    }
+
+    #[tokio::test]
+    async fn test_missing_socket_fails_to_connect() {
+        let dir = tempfile::tempdir().unwrap();
+        let transport = DaemonTransport::new(
+            BindAddress::Unix(dir.path().join("missing.sock")),
+            Duration::from_secs(5),
+        )
+        .unwrap();
+
+        let error = transport.get("/health", None).await.unwrap_err();
+        assert!(error.to_string().contains("Failed to connect to daemon socket"));
+    }
+}
@@ -0,0 +1,88 @@
+// Time source for feedback and metrics
+//
+// Code that timestamps records or computes time-windowed stats takes a
+// `Clock` instead of calling `Utc::now()` itself, so tests can pin the time
+// (`FixedClock`) and step it forward. Constructors default to `SystemClock`;
+// use the `with_clock` builders to inject another.
+
+use chrono::{DateTime, Duration, Utc};
+use std::sync::{Arc, Mutex};
+
+/// Source of the current time
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Current time as seconds since the Unix epoch
+    fn unix_secs(&self) -> u64 {
+        self.now().timestamp().max(0) as u64
+    }
+}
+
+/// Shared handle to a clock
+pub type SharedClock = Arc<dyn Clock>;
+
+/// The system clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// The real clock, as a shared handle (the default for loggers)
+pub fn system_clock() -> SharedClock {
+    Arc::new(SystemClock)
+}
+
+/// A clock that only moves when told to (for tests)
+#[derive(Debug)]
+pub struct FixedClock {
+    now: Mutex<DateTime<Utc>>,
+}
+
+impl FixedClock {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            now: Mutex::new(now),
+        }
+    }
+
+    /// Fixed clock at a Unix timestamp (seconds)
+    pub fn at_unix(secs: i64) -> Self {
+        Self::new(DateTime::from_timestamp(secs, 0).unwrap_or_default())
+    }
+
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().unwrap() = now;
+    }
+
+    pub fn advance(&self, by: Duration) {
+        let mut now = self.now.lock().unwrap();
+        *now += by;
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_clock() {
+        let clock = FixedClock::at_unix(1_700_000_000);
+        assert_eq!(clock.unix_secs(), 1_700_000_000);
+
+        clock.advance(Duration::minutes(5));
+        assert_eq!(clock.unix_secs(), 1_700_000_300);
+
+        clock.set(DateTime::from_timestamp(0, 0).unwrap());
+        assert_eq!(clock.unix_secs(), 0);
+    }
+}
@@ -1,5 +1,6 @@
 // Backend Configuration - Device selection and model management
 
+use crate::models::download::HubSettings;
 use crate::models::unified_loader::{ModelFamily, ModelSize};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -156,10 +157,47 @@ pub struct BackendConfig {
     /// Path to downloaded model
     pub model_path: Option<PathBuf>,
 
+    /// Directory model weights are downloaded to (HuggingFace hub layout)
+    /// Default: $HF_HOME/hub if set, otherwise ~/.cache/huggingface/hub
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub models_dir: Option<PathBuf>,
+
+    /// HuggingFace Hub endpoint, e.g. a mirror or internal proxy
+    /// Default: $HF_ENDPOINT if set, otherwise https://huggingface.co
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hf_endpoint: Option<String>,
+
     /// Fallback execution target chain
     #[serde(default = "default_fallback_chain", deserialize_with = "deserialize_fallback_chain")]
     pub fallback_chain: Vec<ExecutionTarget>,
 
+    /// Soft limit on local response length in characters (default: off)
+    /// Generation stops at the next sentence boundary past the limit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_response_chars: Option<usize>,
+
+    /// Abort local generation after this many milliseconds and forward the
+    /// query to the teacher instead (default: off)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local_generation_timeout_ms: Option<u64>,
+
+    /// Local model context window in tokens; older turns are dropped to fit
+    /// (default: the model's max_position_embeddings from config.json)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_length: Option<usize>,
+
+    /// Minimum pattern and response confidence for answering locally,
+    /// 0.0-1.0 (default: 0.7). Raise it for a weak model, lower it for a
+    /// strong one.
+    #[serde(default = "default_local_confidence_threshold")]
+    pub local_confidence_threshold: f64,
+
+    /// Run a short throwaway generation once the daemon has loaded the
+    /// model, so the first real query doesn't pay for ONNX Runtime's lazy
+    /// allocation (default: true)
+    #[serde(default = "default_warmup")]
+    pub warmup: bool,
+
     /// Legacy field alias for backward compatibility
     #[serde(skip)]
     #[deprecated(note = "Use execution_target instead")]
@@ -174,6 +212,14 @@ fn default_inference_provider() -> crate::models::unified_loader::InferenceProvi
     crate::models::unified_loader::InferenceProvider::Onnx  // ONNX Runtime is the default
 }
 
+fn default_local_confidence_threshold() -> f64 {
+    crate::local::DEFAULT_LOCAL_CONFIDENCE_THRESHOLD
+}
+
+fn default_warmup() -> bool {
+    true
+}
+
 fn default_model_family() -> ModelFamily {
     ModelFamily::Qwen2
 }
@@ -199,6 +245,14 @@ fn default_fallback_chain() -> Vec<ExecutionTarget> {
     return vec![ExecutionTarget::Cpu];
 }
 
+/// `~/x` relative to the home directory, anything else unchanged
+fn expand_home(path: &std::path::Path) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
 /// Custom deserializer for fallback_chain that filters out deprecated/invalid entries (like "metal")
 fn deserialize_fallback_chain<'de, D>(deserializer: D) -> Result<Vec<ExecutionTarget>, D::Error>
 where
@@ -250,7 +304,14 @@ impl Default for BackendConfig {
             model_size: default_model_size(),
             model_repo: None,
             model_path: None,
+            models_dir: None,
+            hf_endpoint: None,
             fallback_chain: default_fallback_chain(),
+            max_response_chars: None,
+            local_generation_timeout_ms: None,
+            context_length: None,
+            local_confidence_threshold: default_local_confidence_threshold(),
+            warmup: default_warmup(),
             #[allow(deprecated)]
             device: None,
         }
@@ -268,7 +329,14 @@ impl BackendConfig {
             model_size: default_model_size(),
             model_repo: None,
             model_path: None,
+            models_dir: None,
+            hf_endpoint: None,
             fallback_chain: default_fallback_chain(),
+            max_response_chars: None,
+            local_generation_timeout_ms: None,
+            context_length: None,
+            local_confidence_threshold: default_local_confidence_threshold(),
+            warmup: default_warmup(),
             #[allow(deprecated)]
             device: None,
         }
@@ -290,12 +358,68 @@ impl BackendConfig {
             model_size: size,
             model_repo: None,
             model_path: None,
+            models_dir: None,
+            hf_endpoint: None,
             fallback_chain: default_fallback_chain(),
+            max_response_chars: None,
+            local_generation_timeout_ms: None,
+            context_length: None,
+            local_confidence_threshold: default_local_confidence_threshold(),
+            warmup: default_warmup(),
             #[allow(deprecated)]
             device: None,
         }
     }
 
+    /// Model download location and endpoint (env vars and defaults resolve later)
+    pub fn hub(&self) -> HubSettings {
+        HubSettings {
+            models_dir: self.models_dir.as_deref().map(expand_home),
+            endpoint: self.hf_endpoint.clone(),
+        }
+    }
+
+    /// Check the model directory is usable when the local model is enabled
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if let Some(endpoint) = &self.hf_endpoint {
+            if !endpoint.starts_with("http://") && !endpoint.starts_with("https://") {
+                anyhow::bail!(
+                    "backend.hf_endpoint must be an http(s) URL, got '{}'",
+                    endpoint
+                );
+            }
+        }
+        if self.enabled {
+            self.hub().ensure_writable()?;
+        }
+        Ok(())
+    }
+
+    /// Local generation timeout, if set (0 = off)
+    pub fn local_generation_timeout(&self) -> Option<std::time::Duration> {
+        self.local_generation_timeout_ms
+            .filter(|ms| *ms > 0)
+            .map(std::time::Duration::from_millis)
+    }
+
+    /// Clamp `local_confidence_threshold` into 0.0-1.0, warning if it was out of range
+    pub fn clamp_local_confidence_threshold(&mut self) {
+        let threshold = self.local_confidence_threshold;
+        let clamped = if threshold.is_nan() {
+            default_local_confidence_threshold()
+        } else {
+            threshold.clamp(0.0, 1.0)
+        };
+        if clamped != threshold {
+            tracing::warn!(
+                "backend.local_confidence_threshold {} is outside 0.0-1.0, using {}",
+                threshold,
+                clamped
+            );
+            self.local_confidence_threshold = clamped;
+        }
+    }
+
     /// Get the model repository for the selected target and model size
     ///
     /// Uses compatibility matrix to resolve repository automatically
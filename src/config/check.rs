@@ -0,0 +1,173 @@
+// Config file checks for `shammah config validate`
+//
+// Loading a config stops at the first invalid setting. The checks here
+// report every problem in the settings that most often break a setup (the
+// teacher list, the daemon address, the models directory),
+// each pointed at its line in config.toml when it can be found. Everything
+// else goes through `Config::validate`, which reports its first problem.
+
+use anyhow::Result;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use super::loader::read_config_file;
+use super::settings::Config;
+use crate::daemon::BindAddress;
+
+/// A problem found in a config file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigProblem {
+    pub message: String,
+    /// 1-based line in the config file the problem refers to
+    pub line: Option<usize>,
+}
+
+impl fmt::Display for ConfigProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// Load a config file and list its problems (empty when it is valid)
+///
+/// Fails if the file can't be read or parsed; TOML syntax errors carry
+/// their own line and column.
+pub fn check_config_file(path: &Path) -> Result<Vec<ConfigProblem>> {
+    let source = fs::read_to_string(path).ok();
+    let config = read_config_file(path)?;
+    Ok(check_config(&config, source.as_deref()))
+}
+
+/// List the problems in a loaded config
+///
+/// `source` is the config file text, used to find the line of each problem.
+pub fn check_config(config: &Config, source: Option<&str>) -> Vec<ConfigProblem> {
+    let source = source.unwrap_or("");
+    let mut problems = Vec::new();
+    let mut problem = |message: String, line: Option<usize>| {
+        problems.push(ConfigProblem { message, line });
+    };
+
+    if config.teachers.is_empty() {
+        problem(
+            "No teachers configured (add a [[teachers]] entry or run 'shammah setup')".to_string(),
+            None,
+        );
+    }
+
+    // Reported here, so validate() runs on a copy with these fixed
+    let mut rest = config.clone();
+    if BindAddress::parse(&config.client.daemon_address).is_err() {
+        problem(
+            format!("Invalid [client] daemon_address '{}'", config.client.daemon_address),
+            key_line(source, "client", "daemon_address"),
+        );
+        rest.client.daemon_address = super::ClientConfig::default().daemon_address;
+    }
+    if let Some(models_dir) = &config.backend.hub().models_dir {
+        if !models_dir.is_dir() {
+            problem(
+                format!("[backend] models_dir {} does not exist", models_dir.display()),
+                key_line(source, "backend", "models_dir"),
+            );
+            // validate() would create it
+            rest.backend.enabled = false;
+        }
+    }
+
+    if !config.teachers.is_empty() {
+        if let Err(e) = rest.validate() {
+            let message = format!("{:#}", e);
+            let line = teacher_index(&message).and_then(|idx| teacher_line(source, idx));
+            problem(message, line);
+        }
+    }
+
+    problems
+}
+
+/// Line of `key = ...` inside `[table]`
+fn key_line(source: &str, table: &str, key: &str) -> Option<usize> {
+    let mut current = "";
+    for (idx, line) in source.lines().enumerate() {
+        let line = line.trim();
+        if let Some(header) = line.strip_prefix('[') {
+            current = header.trim_start_matches('[').trim_end_matches(']').trim();
+            continue;
+        }
+        let Some((name, _)) = line.split_once('=') else {
+            continue;
+        };
+        if current == table && name.trim() == key {
+            return Some(idx + 1);
+        }
+    }
+    None
+}
+
+/// Line of the `idx`th `[[teachers]]` header
+fn teacher_line(source: &str, idx: usize) -> Option<usize> {
+    source
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| line.trim() == "[[teachers]]")
+        .nth(idx)
+        .map(|(line, _)| line + 1)
+}
+
+/// The N in a "teacher[N]" validation message
+fn teacher_index(message: &str) -> Option<usize> {
+    let (_, rest) = message.split_once("teacher[")?;
+    rest.split_once(']')?.0.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "\
+[client]
+use_daemon = true
+daemon_address = \"not an address\"
+auto_spawn = true
+timeout_seconds = 120
+
+[[teachers]]
+provider = \"claude\"
+api_key = \"sk-ant-REDACTED\"
+
+[[teachers]]
+provider = \"nope\"
+api_key = \"key\"
+
+[backend]
+execution_target = \"cpu\"
+models_dir = \"/nonexistent/shammah-models\"
+";
+
+    #[test]
+    fn test_check_config_lists_problems() {
+        let path = std::env::temp_dir().join(format!("shammah-check-{}.toml", std::process::id()));
+        fs::write(&path, SOURCE).unwrap();
+        let problems = check_config_file(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let lines: Vec<_> = problems.iter().map(|p| p.line).collect();
+        assert_eq!(lines, vec![Some(3), Some(17), Some(11)]);
+        assert!(problems[0].to_string().starts_with("line 3: Invalid [client] daemon_address"));
+        assert!(problems[2].message.contains("Invalid provider 'nope'"));
+    }
+
+    #[test]
+    fn test_key_line() {
+        assert_eq!(key_line(SOURCE, "client", "daemon_address"), Some(3));
+        assert_eq!(key_line(SOURCE, "backend", "models_dir"), Some(17));
+        assert_eq!(key_line(SOURCE, "backend", "daemon_address"), None);
+        assert_eq!(teacher_line(SOURCE, 1), Some(11));
+        assert_eq!(teacher_index("Invalid provider 'x' in teacher[1]"), Some(1));
+    }
+}
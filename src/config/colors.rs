@@ -5,6 +5,7 @@
 
 use ratatui::style::Color;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Color scheme for TUI elements
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,6 +25,10 @@ pub struct ColorScheme {
     /// Dialog colors
     #[serde(default = "default_dialog_colors")]
     pub dialog: DialogColors,
+
+    /// Syntax highlighting for fenced code blocks in responses
+    #[serde(default)]
+    pub code: CodeColors,
 }
 
 impl Default for ColorScheme {
@@ -33,6 +38,7 @@ impl Default for ColorScheme {
             messages: default_message_colors(),
             ui: default_ui_colors(),
             dialog: default_dialog_colors(),
+            code: CodeColors::default(),
         }
     }
 }
@@ -168,6 +174,66 @@ fn default_dialog_colors() -> DialogColors {
     }
 }
 
+/// Token colors for syntax highlighting
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyntaxColors {
+    /// Language keywords (fn, def, if, ...)
+    #[serde(default = "default_magenta")]
+    pub keyword: ColorSpec,
+
+    /// String and character literals
+    #[serde(default = "default_green")]
+    pub string: ColorSpec,
+
+    /// Numeric literals
+    #[serde(default = "default_yellow")]
+    pub number: ColorSpec,
+
+    /// Comments
+    #[serde(default = "default_dark_gray")]
+    pub comment: ColorSpec,
+}
+
+impl Default for SyntaxColors {
+    fn default() -> Self {
+        Self {
+            keyword: default_magenta(),
+            string: default_green(),
+            number: default_yellow(),
+            comment: default_dark_gray(),
+        }
+    }
+}
+
+/// Code block colors: defaults plus per-language tables
+///
+/// ```toml
+/// [colors.code]
+/// keyword = "blue"
+///
+/// [colors.code.languages.python]
+/// keyword = [255, 128, 0]
+/// ```
+///
+/// A language table replaces the defaults for that language; fields it
+/// leaves out use the built-in colors.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CodeColors {
+    #[serde(flatten)]
+    pub default: SyntaxColors,
+
+    /// Overrides by language name (rust, python, javascript, ...)
+    #[serde(default)]
+    pub languages: BTreeMap<String, SyntaxColors>,
+}
+
+impl CodeColors {
+    /// Colors for `language` (a canonical name, e.g. "rust" for ```rs)
+    pub fn for_language(&self, language: &str) -> &SyntaxColors {
+        self.languages.get(language).unwrap_or(&self.default)
+    }
+}
+
 /// Color specification - supports named colors and RGB
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -179,8 +245,11 @@ pub enum ColorSpec {
 }
 
 impl ColorSpec {
-    /// Convert to ratatui Color
+    /// Convert to ratatui Color (the terminal default when color is off)
     pub fn to_color(&self) -> Color {
+        if !crate::cli::color::enabled() {
+            return Color::Reset;
+        }
         match self {
             ColorSpec::Named(name) => parse_named_color(name),
             ColorSpec::Rgb(r, g, b) => Color::Rgb(*r, *g, *b),
@@ -244,6 +313,10 @@ fn default_black() -> ColorSpec {
     ColorSpec::Named("black".to_string())
 }
 
+fn default_magenta() -> ColorSpec {
+    ColorSpec::Named("magenta".to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -262,6 +335,24 @@ mod tests {
         assert!(matches!(scheme.ui.border, ColorSpec::Named(_)));
     }
 
+    #[test]
+    fn test_code_colors_per_language() {
+        let code: CodeColors = toml::from_str(
+            r#"
+            keyword = "blue"
+
+            [languages.python]
+            string = [1, 2, 3]
+            "#,
+        )
+        .unwrap();
+
+        assert!(matches!(&code.for_language("rust").keyword, ColorSpec::Named(n) if n == "blue"));
+        let python = code.for_language("python");
+        assert!(matches!(python.string, ColorSpec::Rgb(1, 2, 3)));
+        assert!(matches!(&python.keyword, ColorSpec::Named(n) if n == "magenta"));
+    }
+
     #[test]
     fn test_named_color_parsing() {
         let color = parse_named_color("cyan");
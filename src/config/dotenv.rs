@@ -0,0 +1,191 @@
+// Minimal .env loader
+//
+// Loads KEY=VALUE pairs from `./.env` and `~/.shammah/.env` into the process
+// environment so `${VAR}` references in config.toml resolve without manual
+// exports. Variables already present in the process environment always win.
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Environment variable that disables .env loading (set by `--no-dotenv`)
+pub const NO_DOTENV_VAR: &str = "SHAMMAH_NO_DOTENV";
+
+static LOADED_FILES: OnceLock<Vec<PathBuf>> = OnceLock::new();
+
+/// Load .env files once per process and return the paths that were loaded
+///
+/// Precedence (highest first): process environment, `./.env`, `~/.shammah/.env`.
+/// Returns an empty list if loading is disabled via `SHAMMAH_NO_DOTENV`.
+pub fn ensure_dotenv_loaded() -> &'static [PathBuf] {
+    LOADED_FILES.get_or_init(|| {
+        if dotenv_disabled() {
+            return Vec::new();
+        }
+
+        let mut candidates = Vec::new();
+        if let Ok(cwd) = std::env::current_dir() {
+            candidates.push(cwd.join(".env"));
+        }
+        if let Some(home) = dirs::home_dir() {
+            candidates.push(home.join(".shammah").join(".env"));
+        }
+
+        candidates
+            .into_iter()
+            .filter(|path| load_dotenv_file(path))
+            .collect()
+    })
+}
+
+fn dotenv_disabled() -> bool {
+    std::env::var(NO_DOTENV_VAR)
+        .map(|v| !v.is_empty() && v != "0")
+        .unwrap_or(false)
+}
+
+/// Load a single .env file without overriding existing variables
+///
+/// Returns true if the file existed and was read.
+fn load_dotenv_file(path: &Path) -> bool {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(_) => return false,
+    };
+
+    for (key, value) in parse_dotenv(&contents) {
+        if std::env::var_os(&key).is_none() {
+            std::env::set_var(key, value);
+        }
+    }
+
+    true
+}
+
+/// Parse .env contents into key/value pairs
+///
+/// Supports `KEY=value`, `export KEY=value`, single/double quoted values,
+/// blank lines, and `#` comments (full-line, or after unquoted values).
+pub fn parse_dotenv(contents: &str) -> Vec<(String, String)> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                return None;
+            }
+
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (key, value) = line.split_once('=')?;
+            let key = key.trim();
+            if key.is_empty() || key.contains(char::is_whitespace) {
+                return None;
+            }
+
+            Some((key.to_string(), parse_value(value.trim())))
+        })
+        .collect()
+}
+
+fn parse_value(raw: &str) -> String {
+    for quote in ['"', '\''] {
+        if let Some(inner) = raw.strip_prefix(quote) {
+            if let Some(end) = inner.find(quote) {
+                return inner[..end].to_string();
+            }
+        }
+    }
+
+    // Unquoted: strip trailing inline comment
+    match raw.find(" #") {
+        Some(idx) => raw[..idx].trim_end().to_string(),
+        None => raw.to_string(),
+    }
+}
+
+/// Expand `${VAR}` references using the process environment
+///
+/// Unset variables are left as-is so validation errors point at the reference.
+pub fn expand_env_vars(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(start) = rest.find("${") {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after.find('}') {
+            Some(end) => {
+                let name = &after[..end];
+                match std::env::var(name) {
+                    Ok(var) => result.push_str(&var),
+                    Err(_) => result.push_str(&rest[start..start + 2 + end + 1]),
+                }
+                rest = &after[end + 1..];
+            }
+            None => {
+                result.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    result.push_str(rest);
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dotenv() {
+        let contents = r#"
+# comment
+ANTHROPIC_API_KEY=sk-ant-123
+export OPENAI_API_KEY="sk-openai # not a comment"
+SINGLE='quoted value'
+INLINE=value # trailing comment
+not a valid line
+"#;
+        let pairs = parse_dotenv(contents);
+        assert_eq!(
+            pairs,
+            vec![
+                ("ANTHROPIC_API_KEY".to_string(), "sk-ant-123".to_string()),
+                ("OPENAI_API_KEY".to_string(), "sk-openai # not a comment".to_string()),
+                ("SINGLE".to_string(), "quoted value".to_string()),
+                ("INLINE".to_string(), "value".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expand_env_vars() {
+        std::env::set_var("SHAMMAH_TEST_EXPAND", "secret");
+        assert_eq!(expand_env_vars("${SHAMMAH_TEST_EXPAND}"), "secret");
+        assert_eq!(expand_env_vars("pre-${SHAMMAH_TEST_EXPAND}-post"), "pre-secret-post");
+        assert_eq!(
+            expand_env_vars("${SHAMMAH_TEST_UNSET_VAR}"),
+            "${SHAMMAH_TEST_UNSET_VAR}"
+        );
+        assert_eq!(expand_env_vars("no refs"), "no refs");
+        assert_eq!(expand_env_vars("${unterminated"), "${unterminated");
+    }
+
+    #[test]
+    fn test_process_env_wins() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".env");
+        std::fs::write(
+            &path,
+            "SHAMMAH_TEST_DOTENV_EXISTING=from_file\nSHAMMAH_TEST_DOTENV_NEW=from_file\n",
+        )
+        .unwrap();
+
+        std::env::set_var("SHAMMAH_TEST_DOTENV_EXISTING", "from_process");
+        assert!(load_dotenv_file(&path));
+
+        assert_eq!(std::env::var("SHAMMAH_TEST_DOTENV_EXISTING").unwrap(), "from_process");
+        assert_eq!(std::env::var("SHAMMAH_TEST_DOTENV_NEW").unwrap(), "from_file");
+        assert!(!load_dotenv_file(&dir.path().join("missing.env")));
+    }
+}
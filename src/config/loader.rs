@@ -3,12 +3,20 @@
 
 use anyhow::{bail, Context, Result};
 use std::fs;
+use std::path::{Path, PathBuf};
 
 use super::settings::Config;
 use crate::errors;
 
 /// Load configuration from Shammah config file or environment
+///
+/// `.env` files are loaded first (once per process) so `${VAR}` references
+/// in the config file can resolve against them.
 pub fn load_config() -> Result<Config> {
+    for path in super::dotenv::ensure_dotenv_loaded() {
+        tracing::info!(path = %path.display(), "Loaded .env file");
+    }
+
     // Try loading from ~/.shammah/config.toml first
     if let Some(config) = try_load_from_shammah_config()? {
         return Ok(config);
@@ -23,6 +31,13 @@ pub fn load_config() -> Result<Config> {
                 model: None,
                 base_url: None,
                 name: Some("Claude (Environment)".to_string()),
+                prompt_caching: None,
+                max_tokens: None,
+                pool: None,
+                thinking: None,
+                max_retries: None,
+                base_delay_ms: None,
+                request_timeout_seconds: None,
             }];
             return Ok(Config::new(teachers));
         }
@@ -42,20 +57,41 @@ pub fn load_config() -> Result<Config> {
     );
 }
 
-fn try_load_from_shammah_config() -> Result<Option<Config>> {
-    use super::backend::BackendConfig;
-    use super::colors::ColorScheme;
-    use super::settings::{ClientConfig, FeaturesConfig};
-    use super::TeacherEntry;
-
+/// Path of the config file (`~/.shammah/config.toml`), whether or not it exists
+pub fn config_path() -> Result<PathBuf> {
     let home = dirs::home_dir().context("Could not determine home directory")?;
-    let config_path = home.join(".shammah/config.toml");
+    Ok(home.join(".shammah/config.toml"))
+}
+
+fn try_load_from_shammah_config() -> Result<Option<Config>> {
+    let config_path = config_path()?;
 
     if !config_path.exists() {
         return Ok(None);
     }
 
-    let contents = fs::read_to_string(&config_path)
+    let config = read_config_file(&config_path)?;
+
+    // Validate configuration
+    config.validate()
+        .context("Configuration validation failed")?;
+
+    Ok(Some(config))
+}
+
+/// Read and parse a config file without validating it
+///
+/// Deprecated settings are migrated as in `load_config`. `${VAR}`
+/// references are kept as written, so saving the config doesn't write the
+/// secrets they point at; they are resolved when a provider is built (see
+/// `TeacherEntry::resolved_api_key`).
+pub(crate) fn read_config_file(config_path: &Path) -> Result<Config> {
+    use super::backend::BackendConfig;
+    use super::colors::ColorScheme;
+    use super::settings::{ClientConfig, FeaturesConfig};
+    use super::TeacherEntry;
+
+    let contents = fs::read_to_string(config_path)
         .map_err(|e| {
             anyhow::anyhow!(errors::file_not_found_error(
                 &config_path.display().to_string(),
@@ -75,6 +111,8 @@ fn try_load_from_shammah_config() -> Result<Option<Config>> {
         #[serde(default)]
         client: Option<ClientConfig>,
         #[serde(default)]
+        server: TomlServerConfig,
+        #[serde(default)]
         teachers: Vec<TeacherEntry>,
         #[serde(default)]
         colors: Option<ColorScheme>,
@@ -82,19 +120,49 @@ fn try_load_from_shammah_config() -> Result<Option<Config>> {
         features: Option<FeaturesConfig>,
         #[serde(default)]
         mcp_servers: Option<std::collections::HashMap<String, crate::tools::mcp::McpServerConfig>>,
+        #[serde(default)]
+        daemon_log_format: Option<super::settings::LogFormat>, // Old location (deprecated)
+        #[serde(default)]
+        training: super::settings::TrainingConfig,
+        #[serde(default = "default_max_tokens")]
+        default_max_tokens: u32,
+        #[serde(default)]
+        history: super::settings::HistoryConfig,
+        #[serde(default)]
+        temperature: crate::models::TemperatureConfig,
+        #[serde(default)]
+        embeddings: crate::embeddings::EmbeddingConfig,
+        #[serde(default)]
+        compaction: super::settings::CompactionConfig,
+        #[serde(default)]
+        audit: crate::metrics::AuditConfig,
+        #[serde(default)]
+        tools: super::settings::ToolsConfig,
+        #[serde(default)]
+        constitution: super::settings::ConstitutionConfig,
+    }
+
+    /// `[server]`, with log_format kept apart so an explicit setting can be
+    /// told from the default
+    #[derive(Default, serde::Deserialize)]
+    struct TomlServerConfig {
+        #[serde(flatten)]
+        config: super::settings::ServerConfig,
+        #[serde(default)]
+        log_format: Option<super::settings::LogFormat>,
     }
 
     fn default_tui_enabled() -> bool {
         true
     }
 
+    fn default_max_tokens() -> u32 {
+        super::DEFAULT_TEACHER_MAX_TOKENS
+    }
+
     let toml_config: TomlConfig = toml::from_str(&contents)
         .map_err(|e| anyhow::anyhow!(errors::config_parse_error(&e.to_string())))?;
 
-    if toml_config.teachers.is_empty() {
-        bail!("Config is missing teachers array. Please run 'shammah setup' to configure.");
-    }
-
     let mut config = Config::new(toml_config.teachers);
 
     // Migrate streaming_enabled to features if not present
@@ -109,6 +177,21 @@ fn try_load_from_shammah_config() -> Result<Option<Config>> {
     config.streaming_enabled = config.features.streaming_enabled;
     config.tui_enabled = toml_config.tui_enabled;
     config.backend = toml_config.backend;
+    config.backend.clamp_local_confidence_threshold();
+    config.training = toml_config.training;
+    config.default_max_tokens = toml_config.default_max_tokens;
+    config.history = toml_config.history;
+    config.temperature = toml_config.temperature;
+    config.embeddings = toml_config.embeddings;
+    config.compaction = toml_config.compaction;
+    config.audit = toml_config.audit;
+    config.tools = toml_config.tools;
+    config.constitution = toml_config.constitution;
+    config.server = toml_config.server.config;
+    // Migrate top-level daemon_log_format unless [server] log_format overrides it
+    if let Some(format) = toml_config.server.log_format.or(toml_config.daemon_log_format) {
+        config.server.log_format = format;
+    }
 
     if let Some(client) = toml_config.client {
         config.client = client;
@@ -122,11 +205,7 @@ fn try_load_from_shammah_config() -> Result<Option<Config>> {
         config.mcp_servers = mcp_servers;
     }
 
-    // Validate configuration
-    config.validate()
-        .context("Configuration validation failed")?;
-
-    Ok(Some(config))
+    Ok(config)
 }
 
 #[cfg(test)]
@@ -136,4 +215,52 @@ mod tests {
     // Note: Config creation tests removed - Config structure changed to use
     // teachers array instead of single api_key. Config is now loaded from
     // ~/.shammah/config.toml via Config::load() or created via setup wizard.
+
+    #[test]
+    fn test_save_keeps_env_var_references() {
+        std::env::set_var("SHAMMAH_TEST_SAVE_KEY", "sk-ant-REDACTED");
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(
+            &path,
+            "[[teachers]]\nprovider = \"claude\"\napi_key = \"${SHAMMAH_TEST_SAVE_KEY}\"\n",
+        )
+        .unwrap();
+
+        let config = read_config_file(&path).unwrap();
+        config.save_to(&path).unwrap();
+        let saved = fs::read_to_string(&path).unwrap();
+        assert!(saved.contains("${SHAMMAH_TEST_SAVE_KEY}"));
+        assert!(!saved.contains("resolved-secret"));
+
+        let teacher = &read_config_file(&path).unwrap().teachers[0];
+        assert_eq!(teacher.api_key, "${SHAMMAH_TEST_SAVE_KEY}");
+        assert_eq!(teacher.resolved_api_key(), "sk-ant-REDACTED");
+    }
+
+    #[test]
+    fn test_daemon_log_format_migration() {
+        use crate::config::LogFormat;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let log_format = |contents: &str| {
+            fs::write(&path, contents).unwrap();
+            read_config_file(&path).unwrap().server.log_format
+        };
+
+        // The old top-level setting still applies on its own
+        assert_eq!(log_format("daemon_log_format = \"json\"\n"), LogFormat::Json);
+        // An explicit [server] log_format wins, even when it's the default
+        assert_eq!(
+            log_format("daemon_log_format = \"json\"\n\n[server]\nlog_format = \"text\"\n"),
+            LogFormat::Text
+        );
+        // Other [server] settings still load
+        let contents = "[server]\nmax_sessions = 3\nlog_format = \"json\"\n";
+        fs::write(&path, contents).unwrap();
+        let server = read_config_file(&path).unwrap().server;
+        assert_eq!(server.max_sessions, 3);
+        assert_eq!(server.log_format, LogFormat::Json);
+    }
 }
@@ -2,13 +2,25 @@
 // Public interface for configuration loading
 
 mod backend;
+mod check;
 mod colors;
+mod dotenv;
 mod loader;
 mod settings;
+mod thinking;
 
 pub use backend::{BackendConfig, ExecutionTarget};
+pub use check::{check_config, check_config_file, ConfigProblem};
 #[allow(deprecated)]
 pub use backend::BackendDevice; // Deprecated alias for ExecutionTarget
-pub use colors::{ColorScheme, ColorSpec, DialogColors, MessageColors, StatusColors, UiColors};
-pub use loader::load_config;
-pub use settings::{ClientConfig, Config, FeaturesConfig, ServerConfig, TeacherEntry};
+pub use colors::{
+    CodeColors, ColorScheme, ColorSpec, DialogColors, MessageColors, StatusColors, SyntaxColors,
+    UiColors,
+};
+pub use dotenv::{ensure_dotenv_loaded, expand_env_vars, NO_DOTENV_VAR};
+pub use loader::{config_path, load_config};
+pub use settings::{
+    ClientConfig, CompactionConfig, Config, ConstitutionConfig, FeaturesConfig, HistoryConfig, LogFormat, OutputAlign,
+    PromptCachingConfig, ServerConfig, SpinnerStyle, StartupVerbosity, TeacherEntry, ToolLimits, ToolsConfig, TrainingConfig, DEFAULT_TEACHER_MAX_TOKENS,
+};
+pub use thinking::{ThinkingLevel, MIN_THINKING_BUDGET};
@@ -2,6 +2,14 @@
 
 use super::backend::BackendConfig;
 use super::colors::ColorScheme;
+use super::thinking::ThinkingLevel;
+use crate::claude::retry::RetryConfig;
+use crate::embeddings::EmbeddingConfig;
+use crate::metrics::AuditConfig;
+use crate::tools::implementations::WebSearchConfig;
+use crate::tools::ToolTimeouts;
+use crate::models::quality::QualityScorerKind;
+use crate::models::TemperatureConfig;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -11,6 +19,9 @@ use std::path::PathBuf;
 pub struct FeaturesConfig {
     /// Auto-approve all tools (skip confirmation dialogs)
     /// ⚠️  Use with caution - tools can modify files
+    ///
+    /// Deprecated: use `[tools.auto_approve]`. `true` still approves every
+    /// tool, overriding that table.
     #[serde(default)]
     pub auto_approve_tools: bool,
 
@@ -26,6 +37,124 @@ pub struct FeaturesConfig {
     #[cfg(target_os = "macos")]
     #[serde(default)]
     pub gui_automation: bool,
+
+    /// Replace Unicode glyphs (box drawing, emoji) with ASCII
+    /// If unset, enabled when LC_ALL/LC_CTYPE/LANG isn't UTF-8
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ascii_only: Option<bool>,
+
+    /// How much the REPL prints while starting up (quiet, normal, verbose)
+    #[serde(default)]
+    pub startup_verbosity: StartupVerbosity,
+
+    /// Animation used by the generating indicator (dots, braille, ascii)
+    #[serde(default)]
+    pub spinner_style: SpinnerStyle,
+
+    /// Speak each complete response with an external TTS command
+    #[serde(default)]
+    pub tts_enabled: bool,
+
+    /// TTS program and arguments; the text is written to its stdin
+    /// If unset, uses `say` on macOS and `espeak --stdin` elsewhere
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tts_command: Option<Vec<String>>,
+
+    /// Hold queries while the local model is still loading, instead of
+    /// forwarding them to the teacher straight away
+    #[serde(default)]
+    pub wait_for_local: bool,
+
+    /// Longest a query waits for the local model before falling back to the
+    /// teacher (seconds)
+    #[serde(default = "default_local_wait_timeout")]
+    pub local_wait_timeout_secs: u64,
+
+    /// Save and exit the REPL after this many minutes without input (0 = never)
+    #[serde(default)]
+    pub idle_exit_minutes: u64,
+
+    /// Widest column messages are wrapped to in the TUI (unset = terminal width)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output_width: Option<u16>,
+
+    /// Where the capped message column sits (left, center)
+    #[serde(default)]
+    pub output_align: OutputAlign,
+
+    /// Most tool calls that run at once; the rest wait for a free slot
+    #[serde(default = "default_max_concurrent_tools")]
+    pub max_concurrent_tools: usize,
+
+    /// Show the teacher's streamed thinking above its response
+    /// (collapsed; /thinking expands it)
+    #[serde(default = "default_true")]
+    pub show_thinking: bool,
+
+    /// Number the lines of fenced code blocks in responses (/format toggles)
+    #[serde(default)]
+    pub code_line_numbers: bool,
+
+    /// Label code blocks with their Alt+<n> copy shortcut in the TUI
+    #[serde(default = "default_true")]
+    pub code_copy_hints: bool,
+
+    /// Render markdown in responses: syntax-highlight fenced code blocks
+    /// that have a language tag (colors under `[colors.code]`)
+    #[serde(default = "default_true")]
+    pub render_markdown: bool,
+}
+
+impl FeaturesConfig {
+    /// How long to hold queries for the local model (None: don't wait)
+    pub fn local_wait_timeout(&self) -> Option<std::time::Duration> {
+        self.wait_for_local
+            .then(|| std::time::Duration::from_secs(self.local_wait_timeout_secs))
+    }
+}
+
+fn default_local_wait_timeout() -> u64 {
+    120
+}
+
+fn default_max_concurrent_tools() -> usize {
+    crate::tools::executor::DEFAULT_MAX_CONCURRENT_TOOLS
+}
+
+/// Narrowest allowed `max_output_width`
+pub const MIN_OUTPUT_WIDTH: u16 = 20;
+
+/// Amount of startup output shown in the REPL
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StartupVerbosity {
+    /// Only a single "ready" line
+    Quiet,
+    /// Warnings plus the ready line
+    #[default]
+    Normal,
+    /// Every startup step
+    Verbose,
+}
+
+/// Placement of the message column when `max_output_width` is narrower than
+/// the terminal
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputAlign {
+    #[default]
+    Left,
+    Center,
+}
+
+/// Spinner animation for the generating indicator
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SpinnerStyle {
+    #[default]
+    Dots,
+    Braille,
+    Ascii,
 }
 
 impl Default for FeaturesConfig {
@@ -36,6 +165,21 @@ impl Default for FeaturesConfig {
             debug_logging: false,       // Disabled by default
             #[cfg(target_os = "macos")]
             gui_automation: false,     // Disabled by default (requires permissions)
+            ascii_only: None,          // Auto-detect from locale
+            startup_verbosity: StartupVerbosity::default(),
+            spinner_style: SpinnerStyle::default(),
+            tts_enabled: false,        // Opt-in
+            tts_command: None,         // Platform default
+            wait_for_local: false,     // Forward while the model loads
+            local_wait_timeout_secs: default_local_wait_timeout(),
+            idle_exit_minutes: 0,      // Never exit on idle
+            max_output_width: None,    // Use the full terminal width
+            output_align: OutputAlign::default(),
+            max_concurrent_tools: default_max_concurrent_tools(),
+            show_thinking: true,       // Collapsed above the response
+            code_line_numbers: false,
+            code_copy_hints: true,
+            render_markdown: true,
         }
     }
 }
@@ -81,14 +225,270 @@ pub struct Config {
 
     /// MCP (Model Context Protocol) server configurations
     pub mcp_servers: HashMap<String, crate::tools::mcp::McpServerConfig>,
+
+    /// Background LoRA training settings (daemon)
+    pub training: TrainingConfig,
+
+    /// Output token limit for teachers without their own `max_tokens`
+    pub default_max_tokens: u32,
+
+    /// Input history policy (what gets saved to ~/.shammah/history.txt)
+    pub history: HistoryConfig,
+
+    /// Teacher sampling temperature, per query category
+    pub temperature: TemperatureConfig,
+
+    /// Embedding backend for semantic similarity
+    pub embeddings: EmbeddingConfig,
+
+    /// Automatic conversation compaction near the context limit
+    pub compaction: CompactionConfig,
+
+    /// Per-query audit log (opt-in)
+    pub audit: AuditConfig,
+
+    /// Tool execution settings (timeouts)
+    pub tools: ToolsConfig,
+
+    /// System prompt sent on every teacher call
+    pub constitution: ConstitutionConfig,
 }
 
-/// Server configuration for daemon mode
-#[derive(Debug, Clone)]
+/// Which submitted lines are kept in the input history
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HistoryConfig {
+    /// Maximum entries kept in the history file
+    pub max_entries: usize,
+    /// Don't store lines starting with a space (like HISTCONTROL=ignorespace)
+    pub ignore_space: bool,
+    /// Don't store a line identical to the previous entry
+    pub ignore_dups: bool,
+    /// Don't store lines that look like they contain API keys or tokens
+    pub filter_secrets: bool,
+    /// Keep only the most recent copy of repeated lines in the history file
+    pub dedupe: bool,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: 1000,
+            ignore_space: true,
+            ignore_dups: true,
+            filter_secrets: true,
+            dedupe: true,
+        }
+    }
+}
+
+/// Summarize older turns when the conversation nears the context limit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CompactionConfig {
+    /// Compact automatically (otherwise the oldest turns are just trimmed)
+    pub enabled: bool,
+    /// Fraction of the context window that triggers compaction (0.8 = 80%)
+    pub threshold: f32,
+    /// Most recent turns kept verbatim (`/compact 3` overrides it for one run)
+    pub keep_turns: usize,
+}
+
+impl Default for CompactionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            threshold: 0.8,
+            keep_turns: 2,
+        }
+    }
+}
+
+impl CompactionConfig {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if !(self.threshold > 0.0 && self.threshold <= 1.0) {
+            anyhow::bail!("compaction.threshold must be between 0.0 and 1.0 (got {})", self.threshold);
+        }
+        if self.keep_turns == 0 {
+            anyhow::bail!("compaction.keep_turns must be at least 1");
+        }
+        Ok(())
+    }
+}
+
+/// Tool execution settings
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ToolsConfig {
+    /// Timeout in seconds per tool name, overriding the built-in defaults
+    /// (bash 30, web_fetch/web_search 15, read/glob/grep 5, others 30; 0 = no timeout)
+    pub timeouts: ToolTimeouts,
+    /// Search backend for the web_search tool
+    pub web_search: WebSearchConfig,
+    /// Infinite-loop guards for the tool loop
+    pub limits: ToolLimits,
+    /// Tools never registered or offered to the teacher (e.g. ["bash", "save_and_exec"])
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub disabled: Vec<String>,
+    /// Run without asking, per tool name or category ("read_only", "mutating"),
+    /// e.g. `read = true, bash = false` (default: read-only tools only)
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub auto_approve: HashMap<String, bool>,
+    /// Record executed tools in ~/.shammah/tool_audit.jsonl: unset logs bash,
+    /// save_and_exec and restart_session; true logs every tool; false nothing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audit_log: Option<bool>,
+    /// Regexes whose matches are masked in the tool audit log (e.g. "sk-[A-Za-z0-9-]+")
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub audit_redact: Vec<String>,
+}
+
+/// When the tool loop gives up (`[tools.limits]`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ToolLimits {
+    /// Teacher round-trips with tool calls per query
+    pub max_iterations: u32,
+    /// Iterations in a row that may use the same tool
+    pub max_consecutive_same_tool: usize,
+    /// Calls allowed with the same tool and identical input
+    pub max_identical_calls: usize,
+}
+
+impl Default for ToolLimits {
+    fn default() -> Self {
+        Self {
+            max_iterations: 25,
+            max_consecutive_same_tool: 3,
+            max_identical_calls: 2,
+        }
+    }
+}
+
+impl ToolLimits {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.max_iterations == 0 {
+            anyhow::bail!("tools.limits.max_iterations must be greater than 0");
+        }
+        if self.max_consecutive_same_tool == 0 {
+            anyhow::bail!("tools.limits.max_consecutive_same_tool must be greater than 0");
+        }
+        if self.max_identical_calls == 0 {
+            anyhow::bail!("tools.limits.max_identical_calls must be greater than 0");
+        }
+        Ok(())
+    }
+}
+
+/// Teacher system prompt, loaded from a markdown file at startup
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConstitutionConfig {
+    /// Markdown file sent as the system prompt on every teacher call
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+}
+
+impl ConstitutionConfig {
+    /// Read the constitution, if one is configured
+    ///
+    /// A missing, unreadable or empty file is logged and skipped rather
+    /// than stopping startup.
+    pub fn load(&self) -> Option<String> {
+        let path = self.path.as_ref()?;
+        match std::fs::read_to_string(path) {
+            Ok(text) if !text.trim().is_empty() => {
+                tracing::info!("Loaded constitution from {}", path.display());
+                Some(text)
+            }
+            Ok(_) => {
+                tracing::warn!(
+                    "Constitution file {} is empty, continuing without a system prompt",
+                    path.display()
+                );
+                None
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Could not read constitution file {} ({}), continuing without a system prompt",
+                    path.display(),
+                    e
+                );
+                None
+            }
+        }
+    }
+}
+
+/// Default teacher output token limit
+pub const DEFAULT_TEACHER_MAX_TOKENS: u32 = 4096;
+
+fn default_max_tokens() -> u32 {
+    DEFAULT_TEACHER_MAX_TOKENS
+}
+
+/// Background LoRA training settings for the daemon's training worker
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TrainingConfig {
+    /// Start a training run once this many examples are buffered
+    pub batch_threshold: usize,
+    /// Start a training run after this many minutes, even below the threshold
+    pub batch_timeout_minutes: u64,
+    /// Maximum training subprocesses running at once
+    pub max_concurrent_training: usize,
+    /// How teacher responses are scored before the local model learns from
+    /// them: "heuristic" (default) or "semantic"
+    pub quality_scorer: QualityScorerKind,
+}
+
+impl Default for TrainingConfig {
+    fn default() -> Self {
+        Self {
+            batch_threshold: 10,
+            batch_timeout_minutes: 5,
+            max_concurrent_training: 1,
+            quality_scorer: QualityScorerKind::default(),
+        }
+    }
+}
+
+impl TrainingConfig {
+    /// Check that the batch settings can actually trigger training
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.batch_threshold == 0 {
+            anyhow::bail!("training.batch_threshold must be at least 1");
+        }
+        if self.batch_timeout_minutes == 0 {
+            anyhow::bail!("training.batch_timeout_minutes must be at least 1");
+        }
+        if self.max_concurrent_training == 0 {
+            anyhow::bail!("training.max_concurrent_training must be at least 1");
+        }
+        Ok(())
+    }
+}
+
+/// Log output format for the daemon log file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Human-readable text lines
+    #[default]
+    Text,
+    /// Newline-delimited JSON (for Loki/ELK ingestion)
+    Json,
+}
+
+/// Server configuration for daemon mode (`[server]` in config.toml)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ServerConfig {
-    /// Enable daemon mode
+    /// Enable daemon mode (set when running `shammah daemon`)
+    #[serde(skip)]
     pub enabled: bool,
-    /// Bind address (e.g., "127.0.0.1:8000")
+    /// Bind address ("127.0.0.1:8000", or "unix:/path/to/socket"; set by `--bind`)
+    #[serde(skip)]
     pub bind_address: String,
     /// Maximum number of concurrent sessions
     pub max_sessions: usize,
@@ -98,6 +498,14 @@ pub struct ServerConfig {
     pub auth_enabled: bool,
     /// Valid API keys for authentication
     pub api_keys: Vec<String>,
+    /// Local generations running at once; further requests queue
+    pub max_concurrent_inference: usize,
+    /// Teacher calls running at once; further requests queue
+    pub max_concurrent_teacher: usize,
+    /// Seconds a queued request waits for a slot before getting HTTP 429
+    pub queue_timeout_secs: u64,
+    /// Output format for ~/.shammah/daemon.log (default: text)
+    pub log_format: LogFormat,
 }
 
 /// Client configuration for connecting to daemon
@@ -105,7 +513,7 @@ pub struct ServerConfig {
 pub struct ClientConfig {
     /// Use daemon client mode instead of loading model locally
     pub use_daemon: bool,
-    /// Daemon bind address to connect to
+    /// Daemon bind address to connect to (`host:port` or `unix:/path`)
     pub daemon_address: String,
     /// Auto-spawn daemon if not running
     pub auto_spawn: bool,
@@ -122,6 +530,10 @@ impl Default for ServerConfig {
             session_timeout_minutes: 30,
             auth_enabled: false,
             api_keys: vec![],
+            max_concurrent_inference: 4,
+            max_concurrent_teacher: 16,
+            queue_timeout_secs: 30,
+            log_format: LogFormat::default(),
         }
     }
 }
@@ -139,25 +551,125 @@ impl Default for ClientConfig {
 
 
 /// A single teacher entry with provider and settings
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TeacherEntry {
-    /// Provider name: "claude", "openai", "grok", "gemini", "mistral", "groq"
+    /// Provider name: "claude", "openai", "grok", "gemini", "mistral", "groq", "ollama"
     pub provider: String,
 
-    /// API key for this provider
+    /// API key for this provider (not needed for "ollama")
+    #[serde(default)]
     pub api_key: String,
 
-    /// Optional model override (uses provider default if not specified)
+    /// Optional model override (uses provider default if not specified;
+    /// required for "ollama")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub model: Option<String>,
 
-    /// Optional base URL (for custom endpoints)
+    /// Optional base URL (for custom endpoints; "ollama" defaults to
+    /// http://127.0.0.1:11434)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub base_url: Option<String>,
 
     /// Optional name/label for this teacher (for UI/logging)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+
+    /// Optional prompt caching settings (only honored by providers that
+    /// support cache breakpoints, currently Claude)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_caching: Option<PromptCachingConfig>,
+
+    /// Optional output token limit for this teacher
+    /// (falls back to the global `default_max_tokens`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+
+    /// Optional load-sharing pool: teachers with the same pool name (and
+    /// provider) take turns serving requests, skipping rate-limited keys
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pool: Option<String>,
+
+    /// Optional thinking budget: "off", "low", "medium", "high" or a token
+    /// count (Claude extended thinking, OpenAI reasoning effort)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thinking: Option<ThinkingLevel>,
+
+    /// Optional attempts per request, including the first (default 3)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_retries: Option<u32>,
+
+    /// Optional backoff before the first retry in milliseconds (default
+    /// 1000, doubling with jitter for each retry after)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_delay_ms: Option<u64>,
+
+    /// Optional request timeout in seconds (default 120, 300 for "ollama");
+    /// streaming responses only fail after this long without any data
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_timeout_seconds: Option<u64>,
+}
+
+impl TeacherEntry {
+    /// Retry settings for this teacher's requests
+    pub fn retry_config(&self) -> RetryConfig {
+        RetryConfig::with_overrides(self.max_retries, self.base_delay_ms)
+    }
+
+    /// Request timeout override for this teacher (None = provider default)
+    pub fn request_timeout(&self) -> Option<std::time::Duration> {
+        self.request_timeout_seconds.map(std::time::Duration::from_secs)
+    }
+
+    /// Output token limit for this teacher, given the global default
+    pub fn effective_max_tokens(&self, default_max_tokens: u32) -> u32 {
+        self.max_tokens.unwrap_or(default_max_tokens)
+    }
+
+    /// API key with `${VAR}` references resolved (`api_key` keeps them as
+    /// written, so saving the config never stores the secret)
+    pub fn resolved_api_key(&self) -> String {
+        super::dotenv::expand_env_vars(&self.api_key)
+    }
+
+    /// Base URL with `${VAR}` references resolved
+    pub fn resolved_base_url(&self) -> Option<String> {
+        self.base_url.as_deref().map(super::dotenv::expand_env_vars)
+    }
+}
+
+/// Prompt caching controls for a teacher provider
+///
+/// Anthropic caches the request prefix up to each `cache_control` breakpoint.
+/// Providers without explicit cache breakpoints ignore these settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PromptCachingConfig {
+    /// Master switch for cache breakpoints
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Place a breakpoint after the system prompt
+    #[serde(default = "default_true")]
+    pub cache_system_prompt: bool,
+
+    /// Place a breakpoint after the stable conversation prefix
+    /// (everything before the newest message)
+    #[serde(default = "default_true")]
+    pub cache_conversation_prefix: bool,
+
+    /// Place a breakpoint after the tool definitions
+    #[serde(default = "default_true")]
+    pub cache_tools: bool,
+}
+
+impl Default for PromptCachingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            cache_system_prompt: true,
+            cache_conversation_prefix: true,
+            cache_tools: true,
+        }
+    }
 }
 
 
@@ -177,7 +689,7 @@ impl Config {
         // Validate each teacher entry
         for (idx, teacher) in self.teachers.iter().enumerate() {
             // Validate provider name
-            let valid_providers = ["claude", "openai", "grok", "gemini", "mistral", "groq"];
+            let valid_providers = ["claude", "openai", "grok", "gemini", "mistral", "groq", "ollama"];
             if !valid_providers.contains(&teacher.provider.as_str()) {
                 anyhow::bail!(errors::wrap_error_with_suggestion(
                     format!("Invalid provider '{}' in teacher[{}]", teacher.provider, idx),
@@ -190,15 +702,37 @@ impl Config {
                 ));
             }
 
+            if teacher.request_timeout_seconds == Some(0) {
+                anyhow::bail!(
+                    "request_timeout_seconds must be at least 1 (teacher[{}])",
+                    idx
+                );
+            }
+
+            // Ollama runs locally without a key, but has no default model
+            if teacher.provider == "ollama" {
+                if teacher.model.as_deref().is_none_or(|model| model.trim().is_empty()) {
+                    anyhow::bail!(errors::wrap_error_with_suggestion(
+                        format!("Ollama teacher[{}] has no model", idx),
+                        "Set the model to one you've pulled, e.g.:\n  \
+                         [[teachers]]\n  \
+                         provider = \"ollama\"\n  \
+                         model = \"qwen2.5:7b\""
+                    ));
+                }
+                continue;
+            }
+
             // Validate API key is not empty
-            if teacher.api_key.trim().is_empty() {
+            let api_key = teacher.resolved_api_key();
+            if api_key.trim().is_empty() {
                 anyhow::bail!(errors::api_key_invalid_error(&teacher.provider));
             }
 
             // Validate API key format based on provider
             match teacher.provider.as_str() {
                 "claude" => {
-                    if !teacher.api_key.starts_with("sk-ant-") {
+                    if !api_key.starts_with("sk-ant-") {
                         anyhow::bail!(errors::wrap_error_with_suggestion(
                             format!("Claude API key has incorrect format (teacher[{}])", idx),
                             "Claude API keys start with 'sk-ant-'\n\n\
@@ -206,12 +740,12 @@ impl Config {
                              https://console.anthropic.com/"
                         ));
                     }
-                    if teacher.api_key.len() < 20 {
+                    if api_key.len() < 20 {
                         anyhow::bail!("Claude API key is too short (should be ~100+ characters)");
                     }
                 }
                 "openai" | "groq" => {
-                    if !teacher.api_key.starts_with("sk-") {
+                    if !api_key.starts_with("sk-") {
                         anyhow::bail!(errors::wrap_error_with_suggestion(
                             format!("{} API key has incorrect format (teacher[{}])", teacher.provider, idx),
                             &format!(
@@ -224,7 +758,7 @@ impl Config {
                     }
                 }
                 "gemini" => {
-                    if teacher.api_key.len() < 30 {
+                    if api_key.len() < 30 {
                         anyhow::bail!("Gemini API key is too short");
                     }
                 }
@@ -233,21 +767,22 @@ impl Config {
         }
 
         // Validate bind address format
-        if !self.server.bind_address.contains(':') {
+        if crate::daemon::BindAddress::parse(&self.server.bind_address).is_err() {
             anyhow::bail!(errors::wrap_error_with_suggestion(
                 format!("Invalid bind address: '{}'", self.server.bind_address),
-                "Bind address should be in format 'IP:PORT'\n\
+                "Bind address should be in format 'IP:PORT' or 'unix:/path/to/socket'\n\
                  Examples:\n  \
                  • 127.0.0.1:8000\n  \
                  • 0.0.0.0:11435\n  \
-                 • localhost:8080"
+                 • localhost:8080\n  \
+                 • unix:/tmp/shammah.sock"
             ));
         }
 
-        if !self.client.daemon_address.contains(':') {
+        if crate::daemon::BindAddress::parse(&self.client.daemon_address).is_err() {
             anyhow::bail!(errors::wrap_error_with_suggestion(
                 format!("Invalid daemon address: '{}'", self.client.daemon_address),
-                "Daemon address should be in format 'IP:PORT'\n\
+                "Daemon address should be in format 'IP:PORT' or 'unix:/path/to/socket'\n\
                  Example: 127.0.0.1:11435"
             ));
         }
@@ -269,6 +804,59 @@ impl Config {
             anyhow::bail!("session_timeout_minutes must be greater than 0");
         }
 
+        if self.server.max_concurrent_inference == 0 || self.server.max_concurrent_teacher == 0 {
+            anyhow::bail!(
+                "server.max_concurrent_inference and server.max_concurrent_teacher must be greater than 0"
+            );
+        }
+
+        self.backend.validate()?;
+        self.training.validate()?;
+        self.temperature.validate()?;
+        self.embeddings.validate()?;
+        self.compaction.validate()?;
+        self.tools.limits.validate()?;
+        crate::tools::audit::compile_redactions(&self.tools.audit_redact)?;
+
+        if self.default_max_tokens == 0 {
+            anyhow::bail!("default_max_tokens must be greater than 0");
+        }
+        if self.features.max_concurrent_tools == 0 {
+            anyhow::bail!("features.max_concurrent_tools must be greater than 0");
+        }
+        if let Some(width) = self.features.max_output_width {
+            if width < MIN_OUTPUT_WIDTH {
+                anyhow::bail!(
+                    "features.max_output_width must be at least {} (got {})",
+                    MIN_OUTPUT_WIDTH,
+                    width
+                );
+            }
+        }
+        for teacher in &self.teachers {
+            if teacher.max_tokens == Some(0) {
+                anyhow::bail!(
+                    "max_tokens for teacher '{}' must be greater than 0",
+                    teacher.name.as_deref().unwrap_or(&teacher.provider)
+                );
+            }
+            if let Some(pool) = &teacher.pool {
+                let first = self
+                    .teachers
+                    .iter()
+                    .find(|t| t.pool.as_ref() == Some(pool))
+                    .expect("teacher is in its own pool");
+                if first.provider != teacher.provider {
+                    anyhow::bail!(
+                        "Teacher pool '{}' mixes providers ({} and {}); pooled teachers must share a provider",
+                        pool,
+                        first.provider,
+                        teacher.provider
+                    );
+                }
+            }
+        }
+
         if self.client.timeout_seconds == 0 {
             anyhow::bail!("timeout_seconds must be greater than 0");
         }
@@ -320,6 +908,15 @@ impl Config {
             teachers,
             features,
             mcp_servers: HashMap::new(), // No MCP servers by default
+            training: TrainingConfig::default(),
+            default_max_tokens: DEFAULT_TEACHER_MAX_TOKENS,
+            history: HistoryConfig::default(),
+            temperature: TemperatureConfig::default(),
+            embeddings: EmbeddingConfig::default(),
+            compaction: CompactionConfig::default(),
+            audit: AuditConfig::default(),
+            tools: ToolsConfig::default(),
+            constitution: ConstitutionConfig::default(),
         }
     }
 
@@ -330,28 +927,40 @@ impl Config {
 
     /// Save configuration to TOML file at ~/.shammah/config.toml
     pub fn save(&self) -> anyhow::Result<()> {
-        use std::fs;
-
         let home = dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?;
         let config_dir = home.join(".shammah");
-        let config_path = config_dir.join("config.toml");
 
         // Create directory if it doesn't exist
-        fs::create_dir_all(&config_dir)?;
+        std::fs::create_dir_all(&config_dir)?;
+
+        self.save_to(&config_dir.join("config.toml"))
+    }
 
+    /// Save configuration to a TOML file
+    pub fn save_to(&self, config_path: &std::path::Path) -> anyhow::Result<()> {
         // Create serializable config
         let toml_config = TomlConfig {
             streaming_enabled: self.features.streaming_enabled, // Use features value
             tui_enabled: self.tui_enabled,
             backend: self.backend.clone(),
             client: Some(self.client.clone()),
+            server: self.server.clone(),
             teachers: self.teachers.clone(),
             colors: Some(self.colors.clone()),
             features: Some(self.features.clone()),
+            training: self.training.clone(),
+            default_max_tokens: self.default_max_tokens,
+            history: self.history.clone(),
+            temperature: self.temperature.clone(),
+            embeddings: self.embeddings.clone(),
+            compaction: self.compaction.clone(),
+            audit: self.audit.clone(),
+            tools: self.tools.clone(),
+            constitution: self.constitution.clone(),
         };
 
         let toml_string = toml::to_string_pretty(&toml_config)?;
-        fs::write(&config_path, toml_string)?;
+        std::fs::write(config_path, toml_string)?;
 
         tracing::info!("Configuration saved to {:?}", config_path);
         Ok(())
@@ -366,9 +975,29 @@ struct TomlConfig {
     backend: BackendConfig,
     #[serde(skip_serializing_if = "Option::is_none")]
     client: Option<ClientConfig>,
+    #[serde(default)]
+    server: ServerConfig,
     teachers: Vec<TeacherEntry>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     colors: Option<ColorScheme>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     features: Option<FeaturesConfig>,
+    #[serde(default)]
+    training: TrainingConfig,
+    #[serde(default = "default_max_tokens")]
+    default_max_tokens: u32,
+    #[serde(default)]
+    history: HistoryConfig,
+    #[serde(default)]
+    temperature: TemperatureConfig,
+    #[serde(default)]
+    embeddings: EmbeddingConfig,
+    #[serde(default)]
+    compaction: CompactionConfig,
+    #[serde(default)]
+    audit: AuditConfig,
+    #[serde(default)]
+    tools: ToolsConfig,
+    #[serde(default)]
+    constitution: ConstitutionConfig,
 }
@@ -0,0 +1,159 @@
+// Thinking budget - extended thinking / reasoning effort for teachers
+//
+// A teacher's `thinking` setting is a level ("off", "low", "medium",
+// "high") or an explicit token budget. Claude gets it as a `thinking`
+// block with `budget_tokens`; OpenAI reasoning models get it as
+// `reasoning_effort`. Providers without a reasoning control ignore it.
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Smallest thinking budget Anthropic accepts
+pub const MIN_THINKING_BUDGET: u32 = 1024;
+
+/// How much a teacher should think before answering
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "ThinkingSetting", into = "ThinkingSetting")]
+pub enum ThinkingLevel {
+    Off,
+    Low,
+    Medium,
+    High,
+    /// Explicit budget in tokens
+    Budget(u32),
+}
+
+/// Config form: a level name or a token count
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum ThinkingSetting {
+    Tokens(u32),
+    Level(String),
+}
+
+impl ThinkingLevel {
+    /// Parse a level name or token budget (e.g. "medium", "8000")
+    pub fn parse(value: &str) -> Result<Self> {
+        let value = value.trim().to_lowercase();
+        if let Ok(tokens) = value.parse::<u32>() {
+            return Ok(Self::from_tokens(tokens));
+        }
+        Ok(match value.as_str() {
+            "off" | "none" => ThinkingLevel::Off,
+            "low" => ThinkingLevel::Low,
+            "medium" => ThinkingLevel::Medium,
+            "high" => ThinkingLevel::High,
+            _ => bail!(
+                "Invalid thinking level '{}': expected off, low, medium, high or a token budget",
+                value
+            ),
+        })
+    }
+
+    fn from_tokens(tokens: u32) -> Self {
+        if tokens == 0 {
+            ThinkingLevel::Off
+        } else {
+            ThinkingLevel::Budget(tokens)
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        *self != ThinkingLevel::Off
+    }
+
+    /// Thinking budget in tokens (Claude), None when off
+    pub fn budget_tokens(&self) -> Option<u32> {
+        let budget = match self {
+            ThinkingLevel::Off => return None,
+            ThinkingLevel::Low => 2048,
+            ThinkingLevel::Medium => 8192,
+            ThinkingLevel::High => 16384,
+            ThinkingLevel::Budget(tokens) => *tokens,
+        };
+        Some(budget.max(MIN_THINKING_BUDGET))
+    }
+
+    /// Reasoning effort (OpenAI), None when off
+    pub fn reasoning_effort(&self) -> Option<&'static str> {
+        match self {
+            ThinkingLevel::Off => None,
+            ThinkingLevel::Low => Some("low"),
+            ThinkingLevel::Medium => Some("medium"),
+            ThinkingLevel::High => Some("high"),
+            ThinkingLevel::Budget(tokens) if *tokens < 4096 => Some("low"),
+            ThinkingLevel::Budget(tokens) if *tokens < 16384 => Some("medium"),
+            ThinkingLevel::Budget(_) => Some("high"),
+        }
+    }
+}
+
+impl fmt::Display for ThinkingLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThinkingLevel::Off => write!(f, "off"),
+            ThinkingLevel::Low => write!(f, "low"),
+            ThinkingLevel::Medium => write!(f, "medium"),
+            ThinkingLevel::High => write!(f, "high"),
+            ThinkingLevel::Budget(tokens) => write!(f, "{} tokens", tokens),
+        }
+    }
+}
+
+impl TryFrom<ThinkingSetting> for ThinkingLevel {
+    type Error = anyhow::Error;
+
+    fn try_from(setting: ThinkingSetting) -> Result<Self> {
+        match setting {
+            ThinkingSetting::Tokens(tokens) => Ok(Self::from_tokens(tokens)),
+            ThinkingSetting::Level(level) => Self::parse(&level),
+        }
+    }
+}
+
+impl From<ThinkingLevel> for ThinkingSetting {
+    fn from(level: ThinkingLevel) -> Self {
+        match level {
+            ThinkingLevel::Budget(tokens) => ThinkingSetting::Tokens(tokens),
+            other => ThinkingSetting::Level(other.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Deserialize, Serialize)]
+    struct Teacher {
+        thinking: ThinkingLevel,
+    }
+
+    #[test]
+    fn test_parse_thinking_level() {
+        assert_eq!(ThinkingLevel::parse("High").unwrap(), ThinkingLevel::High);
+        assert_eq!(ThinkingLevel::parse("0").unwrap(), ThinkingLevel::Off);
+        assert_eq!(ThinkingLevel::parse("5000").unwrap(), ThinkingLevel::Budget(5000));
+        assert!(ThinkingLevel::parse("lots").is_err());
+
+        let teacher: Teacher = toml::from_str(r#"thinking = "medium""#).unwrap();
+        assert_eq!(teacher.thinking, ThinkingLevel::Medium);
+        let teacher: Teacher = toml::from_str("thinking = 12000").unwrap();
+        assert_eq!(teacher.thinking, ThinkingLevel::Budget(12000));
+        assert!(toml::from_str::<Teacher>(r#"thinking = "max""#).is_err());
+        assert_eq!(toml::to_string(&teacher).unwrap().trim(), "thinking = 12000");
+    }
+
+    #[test]
+    fn test_thinking_mappings() {
+        assert_eq!(ThinkingLevel::Off.budget_tokens(), None);
+        assert_eq!(ThinkingLevel::Medium.budget_tokens(), Some(8192));
+        assert_eq!(ThinkingLevel::Budget(100).budget_tokens(), Some(MIN_THINKING_BUDGET));
+
+        assert_eq!(ThinkingLevel::Off.reasoning_effort(), None);
+        assert_eq!(ThinkingLevel::Low.reasoning_effort(), Some("low"));
+        assert_eq!(ThinkingLevel::Budget(10_000).reasoning_effort(), Some("medium"));
+        assert_eq!(ThinkingLevel::Budget(32_000).reasoning_effort(), Some("high"));
+    }
+}
@@ -1,7 +1,8 @@
 // Daemon lifecycle management
 //
 // Handles PID file creation/removal, process existence checks,
-// and graceful shutdown coordination.
+// and graceful shutdown coordination. The daemon's bind address is kept
+// next to the PID file so `daemon-status` knows where to reach it.
 
 use anyhow::{Context, Result};
 use std::fs;
@@ -39,13 +40,37 @@ impl DaemonLifecycle {
         Ok(())
     }
 
-    /// Remove PID file (called on shutdown)
+    /// Record the address the daemon is listening on
+    pub fn write_bind_address(&self, bind_address: &str) -> Result<()> {
+        let addr_file = self.addr_file();
+        fs::write(&addr_file, bind_address)
+            .with_context(|| format!("Failed to write address file: {}", addr_file.display()))?;
+        Ok(())
+    }
+
+    /// Address recorded by the running daemon (None for daemons that predate it)
+    pub fn read_bind_address(&self) -> Result<Option<String>> {
+        let addr_file = self.addr_file();
+        if !addr_file.exists() {
+            return Ok(None);
+        }
+        let address = fs::read_to_string(&addr_file)
+            .with_context(|| format!("Failed to read address file: {}", addr_file.display()))?;
+        Ok(Some(address.trim().to_string()).filter(|address| !address.is_empty()))
+    }
+
+    /// Remove PID and address files (called on shutdown)
     pub fn cleanup(&self) -> Result<()> {
         if self.pid_file.exists() {
             fs::remove_file(&self.pid_file)
                 .with_context(|| format!("Failed to remove PID file: {}", self.pid_file.display()))?;
             info!("Daemon PID file removed");
         }
+        let addr_file = self.addr_file();
+        if addr_file.exists() {
+            fs::remove_file(&addr_file)
+                .with_context(|| format!("Failed to remove address file: {}", addr_file.display()))?;
+        }
         Ok(())
     }
 
@@ -81,6 +106,11 @@ impl DaemonLifecycle {
         &self.pid_file
     }
 
+    /// Address file path (daemon.addr, next to the PID file)
+    pub fn addr_file(&self) -> PathBuf {
+        self.pid_file.with_extension("addr")
+    }
+
     /// Stop the daemon gracefully
     ///
     /// Attempts graceful shutdown:
@@ -233,9 +263,18 @@ mod tests {
         // Check running
         assert!(lifecycle.is_running());
 
+        // Bind address
+        assert_eq!(lifecycle.read_bind_address().unwrap(), None);
+        lifecycle.write_bind_address("127.0.0.1:9000").unwrap();
+        assert_eq!(
+            lifecycle.read_bind_address().unwrap().as_deref(),
+            Some("127.0.0.1:9000")
+        );
+
         // Cleanup
         lifecycle.cleanup().unwrap();
         assert!(!pid_file.exists());
+        assert!(!lifecycle.addr_file().exists());
         assert!(!lifecycle.is_running());
     }
 
@@ -0,0 +1,112 @@
+// Daemon log output helpers
+//
+// Wraps the daemon.log writer so API keys and bearer tokens never reach disk,
+// regardless of whether the text or JSON formatter produced the line.
+
+use std::io::{self, Write};
+
+/// Prefixes of provider API keys that should never be written to logs
+const SECRET_PREFIXES: &[&str] = &["sk-ant-", "sk-", "xai-", "gsk_", "AIza", "Bearer "];
+
+/// Minimum token length (after the prefix) before a match is treated as a secret
+const MIN_SECRET_LEN: usize = 8;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Mask API keys and bearer tokens in a log line
+///
+/// Keeps the prefix so the provider is still identifiable
+/// (e.g. `sk-ant-[REDACTED]`).
+pub fn redact_secrets(line: &str) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut rest = line;
+
+    'outer: while !rest.is_empty() {
+        let at_boundary = result
+            .chars()
+            .last()
+            .map_or(true, |c| !is_token_char(c));
+
+        if at_boundary {
+            for prefix in SECRET_PREFIXES {
+                if let Some(after) = rest.strip_prefix(prefix) {
+                    let token_len = after
+                        .find(|c: char| !is_token_char(c))
+                        .unwrap_or(after.len());
+                    if token_len >= MIN_SECRET_LEN {
+                        result.push_str(prefix);
+                        result.push_str(REDACTED);
+                        rest = &after[token_len..];
+                        continue 'outer;
+                    }
+                }
+            }
+        }
+
+        let ch = rest.chars().next().unwrap();
+        result.push(ch);
+        rest = &rest[ch.len_utf8()..];
+    }
+
+    result
+}
+
+fn is_token_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_'
+}
+
+/// Writer that redacts secrets before forwarding to the inner writer
+///
+/// The tracing fmt layer writes each event with a single `write_all`, so
+/// redacting per call sees whole lines in both text and JSON formats.
+pub struct RedactingWriter<W> {
+    inner: W,
+}
+
+impl<W: Write> RedactingWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+}
+
+impl<W: Write> Write for RedactingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let redacted = redact_secrets(&String::from_utf8_lossy(buf));
+        self.inner.write_all(redacted.as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_secrets() {
+        assert_eq!(
+            redact_secrets("api_key=sk-ant-api03-abcdefghijkl done"),
+            "api_key=sk-ant-[REDACTED] done"
+        );
+        assert_eq!(
+            redact_secrets(r#"{"fields":{"auth":"Bearer abcdefgh12345678"}}"#),
+            r#"{"fields":{"auth":"Bearer [REDACTED]"}}"#
+        );
+        // Short tokens and embedded prefixes are left alone
+        assert_eq!(redact_secrets("task-sk-1 sk-short"), "task-sk-1 sk-short");
+        assert_eq!(redact_secrets("no secrets here ✓"), "no secrets here ✓");
+    }
+
+    #[test]
+    fn test_redacting_writer() {
+        let mut buf = Vec::new();
+        {
+            let mut writer = RedactingWriter::new(&mut buf);
+            writer.write_all(b"key xai-0123456789abcdef\n").unwrap();
+        }
+        assert_eq!(String::from_utf8(buf).unwrap(), "key xai-[REDACTED]\n");
+    }
+}
@@ -3,8 +3,12 @@
 // This module provides daemon lifecycle management, auto-spawn capabilities,
 // and utilities for running Shammah as a persistent background service.
 
+pub mod address;
 pub mod lifecycle;
+pub mod logging;
 pub mod spawn;
 
+pub use address::BindAddress;
 pub use lifecycle::DaemonLifecycle;
-pub use spawn::{ensure_daemon_running, spawn_daemon};
+pub use logging::{redact_secrets, RedactingWriter};
+pub use spawn::{ensure_daemon_running, spawn_daemon, DaemonReadiness};
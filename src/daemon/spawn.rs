@@ -5,30 +5,65 @@
 
 use anyhow::{bail, Context, Result};
 use std::process::{Command, Stdio};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tracing::{debug, info, warn};
 
+use super::address::BindAddress;
 use super::lifecycle::DaemonLifecycle;
+use crate::client::DaemonTransport;
 use crate::errors;
+use crate::server::ReadinessStatus;
 
 /// Default daemon bind address
 const DEFAULT_BIND: &str = "127.0.0.1:11435";
 
+/// How long a single health check waits for a response
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// How long to wait for the local model (the first run downloads it)
+const READY_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// How often `/ready` is polled while the local model loads
+const READY_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// What `ensure_daemon_running` waits for before returning
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DaemonReadiness {
+    /// The process answers `/health` (enough for routed queries, which are
+    /// forwarded to the teacher while the model loads)
+    Alive,
+    /// `/ready` reports the local model loaded (for local-only requests)
+    LocalModel,
+}
+
 /// Ensure daemon is running, spawning if necessary
 ///
 /// This function:
 /// 1. Checks if daemon is responding to health checks
 /// 2. If not, checks PID file for stale process
 /// 3. If daemon not running, spawns it
-/// 4. Waits for daemon to become ready (max 10 seconds)
+/// 4. Waits for daemon to become healthy (max 10 seconds)
+/// 5. With `DaemonReadiness::LocalModel`, waits for `/ready` too
 ///
 /// Returns Ok(()) if daemon is ready, error otherwise.
-pub async fn ensure_daemon_running(bind_address: Option<&str>) -> Result<()> {
+pub async fn ensure_daemon_running(
+    bind_address: Option<&str>,
+    readiness: DaemonReadiness,
+) -> Result<()> {
     let bind = bind_address.unwrap_or(DEFAULT_BIND);
-    let base_url = format!("http://{}", bind);
+    let transport = DaemonTransport::new(BindAddress::parse(bind)?, HEALTH_CHECK_TIMEOUT)?;
+
+    ensure_daemon_alive(bind, &transport).await?;
+    if readiness == DaemonReadiness::LocalModel {
+        wait_for_local_model(&transport, READY_TIMEOUT).await?;
+    }
+    Ok(())
+}
 
+/// Steps 1-4 of `ensure_daemon_running`
+async fn ensure_daemon_alive(bind: &str, transport: &DaemonTransport) -> Result<()> {
     // Quick health check first
-    if health_check_succeeds(&base_url).await {
+    if health_check_succeeds(transport).await {
         debug!("Daemon already running and healthy");
         return Ok(());
     }
@@ -41,7 +76,7 @@ pub async fn ensure_daemon_running(bind_address: Option<&str>) -> Result<()> {
         info!("Daemon process exists, waiting for health check...");
         tokio::time::sleep(Duration::from_secs(2)).await;
 
-        if health_check_succeeds(&base_url).await {
+        if health_check_succeeds(transport).await {
             info!("Daemon now healthy");
             return Ok(());
         }
@@ -65,7 +100,7 @@ pub async fn ensure_daemon_running(bind_address: Option<&str>) -> Result<()> {
     for attempt in 0..20 {
         tokio::time::sleep(Duration::from_millis(500)).await;
 
-        if health_check_succeeds(&base_url).await {
+        if health_check_succeeds(transport).await {
             info!("Daemon started successfully");
             return Ok(());
         }
@@ -156,30 +191,73 @@ pub fn spawn_daemon(bind_address: &str) -> Result<()> {
 }
 
 /// Check if daemon health endpoint responds
-async fn health_check_succeeds(base_url: &str) -> bool {
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_millis(500))
-        .build()
-        .expect("Failed to build HTTP client");
+async fn health_check_succeeds(transport: &DaemonTransport) -> bool {
+    let address = transport.address();
 
-    let url = format!("{}/health", base_url);
-
-    match client.get(&url).send().await {
+    match transport.get("/health", None).await {
         Ok(response) if response.status().is_success() => {
-            debug!(url = %url, "Health check succeeded");
+            debug!(address = %address, "Health check succeeded");
             true
         }
         Ok(response) => {
-            debug!(url = %url, status = %response.status(), "Health check failed");
+            debug!(address = %address, status = %response.status(), "Health check failed");
             false
         }
         Err(e) => {
-            debug!(url = %url, error = %e, "Health check request failed");
+            debug!(address = %address, error = %e, "Health check request failed");
             false
         }
     }
 }
 
+/// Poll `/ready` until the daemon's local model is loaded
+///
+/// Fails fast if loading failed or there's no local model, since waiting
+/// can't fix either.
+async fn wait_for_local_model(transport: &DaemonTransport, timeout: Duration) -> Result<()> {
+    let start = Instant::now();
+    let mut last_message = String::new();
+    loop {
+        let response = transport
+            .get("/ready", None)
+            .await
+            .context("Daemon stopped responding while its local model loaded")?;
+        // Daemons from before /ready only report /health
+        if response.status().as_u16() == 404 {
+            debug!("Daemon has no /ready endpoint, treating it as ready");
+            return Ok(());
+        }
+        let status: ReadinessStatus = response
+            .json()
+            .await
+            .context("Failed to parse readiness response")?;
+
+        if status.ready {
+            info!("Daemon's local model is ready");
+            return Ok(());
+        }
+        if status.is_unavailable() {
+            bail!("The daemon's local model is unavailable: {}", status.message);
+        }
+        if start.elapsed() >= timeout {
+            bail!(errors::wrap_error_with_suggestion(
+                format!(
+                    "Daemon's local model not ready after {}s ({})",
+                    timeout.as_secs(),
+                    status.message
+                ),
+                "Check progress with: shammah daemon-status\n\
+                 Or check logs: tail -f ~/.shammah/daemon.log"
+            ));
+        }
+        if status.message != last_message {
+            info!(status = %status.message, "Waiting for the daemon's local model");
+            last_message = status.message;
+        }
+        tokio::time::sleep(READY_POLL_INTERVAL).await;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -187,7 +265,75 @@ mod tests {
     #[tokio::test]
     async fn test_health_check_fails_for_invalid_url() {
         // Non-existent server should fail health check
-        let result = health_check_succeeds("http://127.0.0.1:99999").await;
-        assert!(!result);
+        let transport = DaemonTransport::new(
+            BindAddress::Tcp("127.0.0.1:99999".to_string()),
+            HEALTH_CHECK_TIMEOUT,
+        )
+        .unwrap();
+        assert!(!health_check_succeeds(&transport).await);
+    }
+
+    /// Transport to a Unix socket daemon whose /ready always reports `state`
+    #[cfg(unix)]
+    fn daemon_in_state(
+        dir: &tempfile::TempDir,
+        state: crate::models::GeneratorState,
+    ) -> DaemonTransport {
+        use axum::http::StatusCode;
+
+        let status = ReadinessStatus::from_state(&state);
+        let code = if status.ready {
+            StatusCode::OK
+        } else {
+            StatusCode::SERVICE_UNAVAILABLE
+        };
+        let app = axum::Router::new().route(
+            "/ready",
+            axum::routing::get(move || async move { (code, axum::Json(status)) }),
+        );
+
+        let socket = dir.path().join("daemon.sock");
+        let listener = tokio::net::UnixListener::bind(&socket).unwrap();
+        tokio::spawn(crate::server::serve_unix(listener, app));
+        DaemonTransport::new(BindAddress::Unix(socket), HEALTH_CHECK_TIMEOUT).unwrap()
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_wait_for_local_model_times_out_while_downloading() {
+        let dir = tempfile::tempdir().unwrap();
+        let transport = daemon_in_state(
+            &dir,
+            crate::models::GeneratorState::Downloading {
+                model_name: "Qwen 2.5 3B".to_string(),
+                progress: crate::models::DownloadProgressSnapshot {
+                    file_name: "model.onnx".to_string(),
+                    current_file: 2,
+                    total_files: 4,
+                },
+            },
+        );
+
+        let error = wait_for_local_model(&transport, Duration::ZERO)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("Downloading Qwen 2.5 3B (2/4): model.onnx"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_wait_for_local_model_fails_fast_when_unavailable() {
+        let dir = tempfile::tempdir().unwrap();
+        let transport = daemon_in_state(
+            &dir,
+            crate::models::GeneratorState::Failed {
+                error: "out of memory".to_string(),
+            },
+        );
+
+        let error = wait_for_local_model(&transport, READY_TIMEOUT)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("unavailable: ✗ Failed: out of memory"));
     }
 }
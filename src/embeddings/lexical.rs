@@ -0,0 +1,62 @@
+// Lexical embedder: hashed bag of words
+//
+// Each distinct word (lowercased, punctuation trimmed, 3+ characters) sets
+// one of `DIMENSIONS` buckets, so cosine similarity measures word overlap.
+// No model, no network: the default backend and the fallback.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use std::collections::HashSet;
+
+use super::Embedder;
+
+/// Vector size (large enough that bucket collisions are rare for short texts)
+pub const DIMENSIONS: usize = 1024;
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LexicalEmbedder;
+
+impl LexicalEmbedder {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn embed_text(&self, text: &str) -> Vec<f32> {
+        let mut vector = vec![0.0; DIMENSIONS];
+        for word in tokenize(text) {
+            vector[bucket(&word)] = 1.0;
+        }
+        vector
+    }
+}
+
+#[async_trait]
+impl Embedder for LexicalEmbedder {
+    fn name(&self) -> &str {
+        "lexical"
+    }
+
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        Ok(texts.iter().map(|text| self.embed_text(text)).collect())
+    }
+}
+
+/// Tokenize text into normalized words
+fn tokenize(text: &str) -> HashSet<String> {
+    text.to_lowercase()
+        .split_whitespace()
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()))
+        .filter(|w| !w.is_empty() && w.len() > 2) // Skip very short words
+        .map(String::from)
+        .collect()
+}
+
+/// FNV-1a, so vectors are stable across runs and Rust versions
+fn bucket(word: &str) -> usize {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in word.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    (hash % DIMENSIONS as u64) as usize
+}
@@ -0,0 +1,277 @@
+// Embeddings
+//
+// Single entry point for turning text into vectors, so semantic features
+// (response similarity, caches, de-duplication) share one backend and one
+// cache. The backend is chosen in the `[embeddings]` config section:
+//
+// - `lexical` (default): hashed bag-of-words, no model download
+// - `onnx`: a small sentence-transformer run locally with ONNX Runtime
+// - `remote`: an OpenAI-compatible `/v1/embeddings` endpoint
+//
+// The local generator model isn't used: its ONNX export only exposes logits
+// and the KV cache, not hidden states that could be pooled into embeddings.
+
+mod lexical;
+mod onnx;
+mod remote;
+
+pub use lexical::LexicalEmbedder;
+pub use onnx::OnnxEmbedder;
+pub use remote::RemoteEmbedder;
+
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use lru::LruCache;
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+
+/// Turns text into fixed-size vectors
+#[async_trait]
+pub trait Embedder: Send + Sync {
+    /// Backend name for logs and status output
+    fn name(&self) -> &str;
+
+    /// Embed a batch of texts, one vector per text, in order
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>>;
+}
+
+/// Which embedder to use
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EmbeddingBackend {
+    #[default]
+    Lexical,
+    Onnx,
+    Remote,
+}
+
+/// Embedding settings (`[embeddings]` in config.toml)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EmbeddingConfig {
+    pub backend: EmbeddingBackend,
+    /// HuggingFace repo (onnx) or model name (remote); backend default if unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// API base URL for the remote backend, without `/v1`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// API key for the remote backend (supports `${VAR}`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    /// Recently embedded strings kept in memory (0 = no cache)
+    pub cache_size: usize,
+}
+
+impl Default for EmbeddingConfig {
+    fn default() -> Self {
+        Self {
+            backend: EmbeddingBackend::default(),
+            model: None,
+            base_url: None,
+            api_key: None,
+            cache_size: 1024,
+        }
+    }
+}
+
+impl EmbeddingConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.backend == EmbeddingBackend::Remote
+            && self.base_url.is_none()
+            && self.api_key.as_deref().map_or(true, str::is_empty)
+        {
+            bail!("embeddings.backend = \"remote\" needs an api_key (or a base_url for a local server)");
+        }
+        Ok(())
+    }
+
+    /// API key with `${VAR}` references resolved
+    pub fn resolved_api_key(&self) -> Option<String> {
+        self.api_key.as_deref().map(crate::config::expand_env_vars)
+    }
+}
+
+/// An embedder plus an LRU cache of recent results
+pub struct EmbeddingService {
+    embedder: Arc<dyn Embedder>,
+    cache: Option<Mutex<LruCache<String, Arc<Vec<f32>>>>>,
+}
+
+impl EmbeddingService {
+    pub fn new(embedder: Arc<dyn Embedder>, cache_size: usize) -> Self {
+        Self {
+            embedder,
+            cache: NonZeroUsize::new(cache_size).map(|size| Mutex::new(LruCache::new(size))),
+        }
+    }
+
+    /// Build the configured backend (models load lazily, on first use)
+    pub fn from_config(config: &EmbeddingConfig) -> Result<Self> {
+        let embedder: Arc<dyn Embedder> = match config.backend {
+            EmbeddingBackend::Lexical => Arc::new(LexicalEmbedder::new()),
+            EmbeddingBackend::Onnx => Arc::new(OnnxEmbedder::new(
+                config.model.as_deref().unwrap_or(onnx::DEFAULT_MODEL),
+            )),
+            EmbeddingBackend::Remote => Arc::new(RemoteEmbedder::new(
+                config.base_url.as_deref().unwrap_or(remote::DEFAULT_BASE_URL),
+                config.resolved_api_key().unwrap_or_default(),
+                config.model.as_deref().unwrap_or(remote::DEFAULT_MODEL),
+            )?),
+        };
+        Ok(Self::new(embedder, config.cache_size))
+    }
+
+    pub fn backend_name(&self) -> &str {
+        self.embedder.name()
+    }
+
+    /// Embed one text (cached)
+    pub async fn embed(&self, text: &str) -> Result<Arc<Vec<f32>>> {
+        let mut vectors = self.embed_many(&[text]).await?;
+        Ok(vectors.remove(0))
+    }
+
+    /// Embed several texts, sending only cache misses to the backend
+    pub async fn embed_many(&self, texts: &[&str]) -> Result<Vec<Arc<Vec<f32>>>> {
+        let mut results: Vec<Option<Arc<Vec<f32>>>> = texts.iter().map(|t| self.cached(t)).collect();
+
+        let mut misses: Vec<String> = Vec::new();
+        for (text, result) in texts.iter().zip(&results) {
+            if result.is_none() && !misses.iter().any(|m| m == text) {
+                misses.push(text.to_string());
+            }
+        }
+
+        if !misses.is_empty() {
+            let vectors = self.embedder.embed(&misses).await?;
+            if vectors.len() != misses.len() {
+                bail!(
+                    "{} embedder returned {} vectors for {} texts",
+                    self.embedder.name(),
+                    vectors.len(),
+                    misses.len()
+                );
+            }
+            let fresh: Vec<(String, Arc<Vec<f32>>)> = misses
+                .into_iter()
+                .zip(vectors.into_iter().map(Arc::new))
+                .collect();
+
+            for (text, result) in texts.iter().zip(results.iter_mut()) {
+                if result.is_none() {
+                    *result = fresh.iter().find(|(t, _)| t == text).map(|(_, v)| Arc::clone(v));
+                }
+            }
+            if let Some(cache) = &self.cache {
+                let mut cache = cache.lock().unwrap();
+                for (text, vector) in fresh {
+                    cache.put(text, vector);
+                }
+            }
+        }
+
+        Ok(results.into_iter().map(|r| r.expect("every text embedded")).collect())
+    }
+
+    /// Cosine similarity of two texts, clamped to 0.0..=1.0
+    ///
+    /// Two blank texts count as identical.
+    pub async fn similarity(&self, text1: &str, text2: &str) -> Result<f64> {
+        if text1.trim().is_empty() && text2.trim().is_empty() {
+            return Ok(1.0);
+        }
+        let vectors = self.embed_many(&[text1, text2]).await?;
+        Ok(cosine_similarity(&vectors[0], &vectors[1]).clamp(0.0, 1.0))
+    }
+
+    fn cached(&self, text: &str) -> Option<Arc<Vec<f32>>> {
+        self.cache.as_ref()?.lock().unwrap().get(text).cloned()
+    }
+}
+
+/// Cosine similarity (0.0 if either vector is zero or the sizes differ)
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f64 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    let (mut dot, mut norm_a, mut norm_b) = (0.0f64, 0.0f64, 0.0f64);
+    for (x, y) in a.iter().zip(b) {
+        let (x, y) = (*x as f64, *y as f64);
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a * norm_b).sqrt()
+}
+
+static SERVICE: Mutex<Option<Arc<EmbeddingService>>> = Mutex::new(None);
+
+/// Set up the shared embedding service from config (call once at startup)
+///
+/// Falls back to the lexical backend if the configured one can't be built.
+pub fn init(config: &EmbeddingConfig) {
+    let service = EmbeddingService::from_config(config).unwrap_or_else(|e| {
+        tracing::warn!("Embedding backend unavailable, using lexical: {}", e);
+        EmbeddingService::new(Arc::new(LexicalEmbedder::new()), config.cache_size)
+    });
+    *SERVICE.lock().unwrap() = Some(Arc::new(service));
+}
+
+/// Shared embedding service (lexical defaults if `init` wasn't called)
+pub fn global() -> Arc<EmbeddingService> {
+    let mut service = SERVICE.lock().unwrap();
+    Arc::clone(service.get_or_insert_with(|| {
+        Arc::new(EmbeddingService::from_config(&EmbeddingConfig::default()).expect("lexical embedder"))
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Counts texts sent to the backend
+    struct Counting(AtomicUsize);
+
+    #[async_trait]
+    impl Embedder for Counting {
+        fn name(&self) -> &str {
+            "counting"
+        }
+
+        async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+            self.0.fetch_add(texts.len(), Ordering::SeqCst);
+            Ok(texts.iter().map(|t| vec![t.len() as f32, 1.0]).collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cache_skips_backend() {
+        let backend = Arc::new(Counting(AtomicUsize::new(0)));
+        let service = EmbeddingService::new(backend.clone(), 2);
+
+        service.embed_many(&["a", "bb", "a"]).await.unwrap();
+        assert_eq!(backend.0.load(Ordering::SeqCst), 2);
+
+        service.embed("bb").await.unwrap();
+        assert_eq!(backend.0.load(Ordering::SeqCst), 2);
+
+        // "ccc" evicts the least recently used entry ("a")
+        service.embed("ccc").await.unwrap();
+        service.embed("a").await.unwrap();
+        assert_eq!(backend.0.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn test_cosine_similarity() {
+        assert!((cosine_similarity(&[1.0, 0.0], &[2.0, 0.0]) - 1.0).abs() < 1e-9);
+        assert_eq!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]), 0.0);
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 0.0]), 0.0);
+        assert_eq!(cosine_similarity(&[1.0], &[1.0, 0.0]), 0.0);
+    }
+}
@@ -0,0 +1,173 @@
+// ONNX embedder: sentence-transformer run with ONNX Runtime
+//
+// Downloads the model's `onnx/model.onnx` and `tokenizer.json` from
+// HuggingFace on first use, then mean-pools `last_hidden_state` over the
+// attention mask and L2-normalizes, as sentence-transformers does.
+
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use hf_hub::{api::sync::Api, Repo, RepoType};
+use ort::{
+    session::{builder::GraphOptimizationLevel, Session},
+    value::{DynValue, Value},
+};
+use std::sync::{Arc, Mutex};
+use tokenizers::{PaddingParams, Tokenizer, TruncationParams};
+use tokio::sync::OnceCell;
+
+use super::Embedder;
+
+/// Small (~90MB), fast, 384 dimensions
+pub const DEFAULT_MODEL: &str = "sentence-transformers/all-MiniLM-L6-v2";
+
+/// Longest input, in tokens (the model's position limit)
+const MAX_TOKENS: usize = 256;
+
+struct LoadedModel {
+    session: Session,
+    tokenizer: Tokenizer,
+}
+
+pub struct OnnxEmbedder {
+    repo_id: String,
+    model: OnceCell<Arc<Mutex<LoadedModel>>>,
+}
+
+impl OnnxEmbedder {
+    pub fn new(repo_id: &str) -> Self {
+        Self {
+            repo_id: repo_id.to_string(),
+            model: OnceCell::new(),
+        }
+    }
+
+    async fn model(&self) -> Result<Arc<Mutex<LoadedModel>>> {
+        let model = self
+            .model
+            .get_or_try_init(|| async {
+                let repo_id = self.repo_id.clone();
+                let loaded = tokio::task::spawn_blocking(move || load(&repo_id))
+                    .await
+                    .context("Embedding model load task failed")??;
+                Ok::<_, anyhow::Error>(Arc::new(Mutex::new(loaded)))
+            })
+            .await?;
+        Ok(Arc::clone(model))
+    }
+}
+
+#[async_trait]
+impl Embedder for OnnxEmbedder {
+    fn name(&self) -> &str {
+        "onnx"
+    }
+
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let model = self.model().await?;
+        let texts = texts.to_vec();
+        tokio::task::spawn_blocking(move || run(&mut model.lock().unwrap(), &texts))
+            .await
+            .context("Embedding task failed")?
+    }
+}
+
+fn load(repo_id: &str) -> Result<LoadedModel> {
+    tracing::info!("Loading embedding model {}", repo_id);
+
+    let repo = Api::new()?.repo(Repo::new(repo_id.to_string(), RepoType::Model));
+    let model_path = repo
+        .get("onnx/model.onnx")
+        .with_context(|| format!("Failed to download onnx/model.onnx from {}", repo_id))?;
+    let tokenizer_path = repo
+        .get("tokenizer.json")
+        .with_context(|| format!("Failed to download tokenizer.json from {}", repo_id))?;
+
+    let mut tokenizer = Tokenizer::from_file(&tokenizer_path)
+        .map_err(|e| anyhow::anyhow!("Failed to load embedding tokenizer: {}", e))?;
+    tokenizer.with_padding(Some(PaddingParams::default()));
+    tokenizer
+        .with_truncation(Some(TruncationParams {
+            max_length: MAX_TOKENS,
+            ..Default::default()
+        }))
+        .map_err(|e| anyhow::anyhow!("Failed to configure embedding tokenizer: {}", e))?;
+
+    let session = Session::builder()?
+        .with_optimization_level(GraphOptimizationLevel::Level3)?
+        .commit_from_file(&model_path)
+        .context("Failed to create ONNX session for embedding model")?;
+
+    Ok(LoadedModel { session, tokenizer })
+}
+
+fn run(model: &mut LoadedModel, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+    let encodings = model
+        .tokenizer
+        .encode_batch(texts.to_vec(), true)
+        .map_err(|e| anyhow::anyhow!("Failed to tokenize for embedding: {}", e))?;
+
+    let batch = encodings.len();
+    let seq_len = encodings.first().map_or(0, |e| e.len());
+    let flatten = |field: fn(&tokenizers::Encoding) -> &[u32]| -> Vec<i64> {
+        encodings
+            .iter()
+            .flat_map(|e| field(e).iter().map(|&v| v as i64))
+            .collect()
+    };
+    let input_ids = flatten(tokenizers::Encoding::get_ids);
+    let attention_mask = flatten(tokenizers::Encoding::get_attention_mask);
+    let token_type_ids = flatten(tokenizers::Encoding::get_type_ids);
+
+    // Only bind the inputs this export declares (not all have token_type_ids)
+    let tensor = |data: &[i64]| -> Result<DynValue> {
+        let array = ndarray::Array2::from_shape_vec((batch, seq_len), data.to_vec())
+            .context("Failed to create ndarray for embedding input")?;
+        Ok(Value::from_array(array)?.into_dyn())
+    };
+    let mut inputs: Vec<(String, DynValue)> = Vec::new();
+    for input in model.session.inputs() {
+        let data = match input.name() {
+            "input_ids" => &input_ids,
+            "attention_mask" => &attention_mask,
+            "token_type_ids" => &token_type_ids,
+            other => bail!("Unsupported embedding model input: {}", other),
+        };
+        inputs.push((input.name().to_string(), tensor(data)?));
+    }
+
+    let outputs = model.session.run(inputs)?;
+    let hidden = outputs
+        .get("last_hidden_state")
+        .ok_or_else(|| anyhow::anyhow!("Embedding model has no last_hidden_state output"))?;
+    let (shape, data) = hidden
+        .try_extract_tensor::<f32>()
+        .context("Failed to extract embedding output")?;
+    if shape.len() != 3 {
+        bail!("Expected 3D embedding output, got shape: {:?}", shape);
+    }
+    let hidden_dim = shape[2] as usize;
+
+    let mut vectors = Vec::with_capacity(batch);
+    for row in 0..batch {
+        let mut pooled = vec![0.0f32; hidden_dim];
+        let mut tokens = 0.0f32;
+        for pos in 0..seq_len {
+            if attention_mask[row * seq_len + pos] == 0 {
+                continue;
+            }
+            let offset = (row * seq_len + pos) * hidden_dim;
+            for (sum, value) in pooled.iter_mut().zip(&data[offset..offset + hidden_dim]) {
+                *sum += value;
+            }
+            tokens += 1.0;
+        }
+
+        let norm = pooled.iter().map(|v| v * v).sum::<f32>().sqrt();
+        if tokens > 0.0 && norm > 0.0 {
+            pooled.iter_mut().for_each(|v| *v /= norm);
+        }
+        vectors.push(pooled);
+    }
+
+    Ok(vectors)
+}
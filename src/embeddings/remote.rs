@@ -0,0 +1,98 @@
+// Remote embedder: OpenAI-compatible /v1/embeddings
+//
+// Works with OpenAI and with local servers that expose the same endpoint
+// (Ollama, llama.cpp, vLLM).
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use super::Embedder;
+
+pub const DEFAULT_BASE_URL: &str = "https://api.openai.com";
+pub const DEFAULT_MODEL: &str = "text-embedding-3-small";
+
+const REQUEST_TIMEOUT_SECS: u64 = 30;
+
+pub struct RemoteEmbedder {
+    client: Client,
+    base_url: String,
+    api_key: String,
+    model: String,
+}
+
+impl RemoteEmbedder {
+    pub fn new(base_url: &str, api_key: String, model: &str) -> Result<Self> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .build()
+            .context("Failed to create HTTP client")?;
+
+        Ok(Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key,
+            model: model.to_string(),
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct EmbeddingRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+#[async_trait]
+impl Embedder for RemoteEmbedder {
+    fn name(&self) -> &str {
+        "remote"
+    }
+
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let url = format!("{}/v1/embeddings", self.base_url);
+        let mut request = self.client.post(&url).json(&EmbeddingRequest {
+            model: &self.model,
+            input: texts,
+        });
+        if !self.api_key.is_empty() {
+            request = request.header("Authorization", format!("Bearer {}", self.api_key));
+        }
+
+        let response = request
+            .send()
+            .await
+            .context("Failed to send embedding request")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_body = response.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "Embedding request failed\n\nStatus: {}\nBody: {}",
+                status,
+                error_body
+            );
+        }
+
+        let mut response: EmbeddingResponse = response
+            .json()
+            .await
+            .context("Failed to parse embedding response")?;
+        response.data.sort_by_key(|d| d.index);
+
+        Ok(response.data.into_iter().map(|d| d.embedding).collect())
+    }
+}
@@ -0,0 +1,325 @@
+// Embeddable query engine
+//
+// Runs one-shot queries through the same routing as the REPL and daemon
+// (crisis rules, threshold router, local model, teacher fallback) without
+// any terminal or TUI code, so Shammah can be used as a library:
+//
+//     let engine = Engine::new(&load_config()?)?;
+//     engine.load_local_model().await?; // optional: teacher-only without it
+//     let answer = engine.query("What is a monad?").await?;
+//     println!("[{}] {}", answer.route, answer.text);
+//
+// The engine doesn't learn from its queries or write metrics; each query is
+// answered on its own, without conversation history.
+
+use anyhow::Result;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+use crate::claude::{ClaudeClient, Message, MessageRequest};
+use crate::config::{BackendConfig, Config};
+use crate::local::{LocalGenerator, LocalTimeout};
+use crate::models::{BootstrapLoader, GeneratorState, ThresholdRouter};
+use crate::providers::{create_provider, TokenUsage};
+use crate::router::{CrisisDetector, ForwardReason, RouteDecision, Router};
+
+/// A routed answer to one query
+#[derive(Debug, Clone)]
+pub struct EngineResponse {
+    pub text: String,
+    /// "local", "teacher", or "crisis" (a configured crisis response)
+    pub route: String,
+    /// Why a teacher-routed query didn't stay local (e.g. "no_match")
+    pub forward_reason: Option<String>,
+    /// Model that answered (None for crisis responses)
+    pub model: Option<String>,
+    pub latency: Duration,
+    /// Token counts reported by the teacher (None for local answers)
+    pub usage: Option<TokenUsage>,
+}
+
+/// Routes and answers queries without the REPL
+pub struct Engine {
+    router: Router,
+    claude_client: ClaudeClient,
+    local_generator: Arc<RwLock<LocalGenerator>>,
+    generator_state: Arc<RwLock<GeneratorState>>,
+    backend: BackendConfig,
+}
+
+impl Engine {
+    /// Engine using `config`'s teachers and backend settings
+    ///
+    /// Uses the threshold router saved in ~/.shammah/models (if any) and the
+    /// user's crisis rules, like the REPL and daemon. The local model isn't
+    /// loaded until `load_local_model`.
+    pub fn new(config: &Config) -> Result<Self> {
+        let provider = create_provider(&config.teachers, config.default_max_tokens)?;
+        let claude_client = ClaudeClient::with_provider(provider);
+        let claude_client = match config.constitution.load() {
+            Some(constitution) => claude_client.with_system_prompt(constitution),
+            None => claude_client,
+        };
+
+        let threshold_router = dirs::home_dir()
+            .map(|home| home.join(".shammah/models/threshold_router.json"))
+            .filter(|path| path.exists())
+            .and_then(|path| match ThresholdRouter::load(&path) {
+                Ok(router) => Some(router),
+                Err(e) => {
+                    tracing::warn!(error = %e, "Failed to load threshold router, creating new one");
+                    None
+                }
+            })
+            .unwrap_or_else(ThresholdRouter::new);
+        let router =
+            Router::new(threshold_router).with_crisis_detector(CrisisDetector::load_or_default());
+
+        Ok(Self::with_client(config, claude_client).with_router(router))
+    }
+
+    /// Engine sending teacher queries through `claude_client`
+    ///
+    /// Starts with a fresh router and the built-in crisis rules.
+    pub fn with_client(config: &Config, claude_client: ClaudeClient) -> Self {
+        let backend = config.backend.clone();
+        let local_generator = LocalGenerator::new()
+            .with_max_response_chars(backend.max_response_chars)
+            .with_generation_timeout(backend.local_generation_timeout())
+            .with_context_length(backend.context_length)
+            .with_confidence_threshold(backend.local_confidence_threshold);
+
+        Self {
+            router: Router::new(ThresholdRouter::new()),
+            claude_client,
+            local_generator: Arc::new(RwLock::new(local_generator)),
+            generator_state: Arc::new(RwLock::new(GeneratorState::NotAvailable)),
+            backend,
+        }
+    }
+
+    /// Use a different router
+    pub fn with_router(mut self, router: Router) -> Self {
+        self.router = router;
+        self
+    }
+
+    /// Load (downloading if needed) the local model from the backend config
+    ///
+    /// Until this succeeds, every query that isn't a crisis goes to the teacher.
+    pub async fn load_local_model(&self) -> Result<()> {
+        if !self.backend.enabled {
+            anyhow::bail!("Local model is disabled (backend.enabled = false)");
+        }
+
+        let loader = BootstrapLoader::new(Arc::clone(&self.generator_state), None)
+            .with_hub(self.backend.hub());
+        let loaded = loader
+            .load_generator_async(
+                self.backend.inference_provider,
+                self.backend.model_family,
+                self.backend.model_size,
+                self.backend.execution_target,
+                self.backend.model_repo.clone(),
+            )
+            .await;
+        if let Err(e) = loaded {
+            *self.generator_state.write().await = GeneratorState::Failed {
+                error: e.to_string(),
+            };
+            return Err(e);
+        }
+
+        if let GeneratorState::Ready { model, .. } = &*self.generator_state.read().await {
+            let mut generator = self.local_generator.write().await;
+            *generator = generator.reloaded(Some(Arc::clone(model)));
+        }
+        Ok(())
+    }
+
+    /// Whether the local model is loaded
+    pub async fn local_model_ready(&self) -> bool {
+        self.generator_state.read().await.is_ready()
+    }
+
+    pub fn router(&self) -> &Router {
+        &self.router
+    }
+
+    /// Route and answer a single query
+    pub async fn query(&self, query: &str) -> Result<EngineResponse> {
+        let start = Instant::now();
+
+        if let Some(crisis) = self.router.check_crisis(query) {
+            if let Some(response) = crisis.canned_response() {
+                return Ok(EngineResponse {
+                    text: response.to_string(),
+                    route: "crisis".to_string(),
+                    forward_reason: Some(ForwardReason::Crisis.as_str().to_string()),
+                    model: None,
+                    latency: start.elapsed(),
+                    usage: None,
+                });
+            }
+        }
+
+        let local_model = match &*self.generator_state.read().await {
+            GeneratorState::Ready { model_name, .. } => Some(model_name.clone()),
+            _ => None,
+        };
+        let decision = self
+            .router
+            .route_with_generator_check(query, local_model.is_some());
+
+        // Teacher circuit open: answer locally instead of failing fast
+        let decision = match decision {
+            RouteDecision::Forward { reason }
+                if !matches!(reason, ForwardReason::Crisis)
+                    && local_model.is_some()
+                    && self.claude_client.is_circuit_open() =>
+            {
+                RouteDecision::Local {
+                    pattern_id: "teacher_circuit_open".to_string(),
+                    confidence: 0.0,
+                }
+            }
+            decision => decision,
+        };
+
+        let forward_reason = match decision {
+            RouteDecision::Forward { reason } => reason.as_str().to_string(),
+            RouteDecision::Local { .. } => {
+                // Generation is CPU-bound and synchronous: keep it (and the
+                // generator lock) off the async runtime
+                let local_generator = Arc::clone(&self.local_generator);
+                let local_query = query.to_string();
+                let generated = tokio::task::spawn_blocking(move || {
+                    let handle = tokio::runtime::Handle::current();
+                    let mut generator = handle.block_on(local_generator.write());
+                    generator.try_generate_from_pattern(&local_query)
+                })
+                .await?;
+                match generated {
+                    Ok(Some(text)) => {
+                        return Ok(EngineResponse {
+                            text,
+                            route: "local".to_string(),
+                            forward_reason: None,
+                            model: local_model,
+                            latency: start.elapsed(),
+                            usage: None,
+                        });
+                    }
+                    Ok(None) => "insufficient_confidence".to_string(),
+                    Err(e) => match e.downcast_ref::<LocalTimeout>() {
+                        Some(timeout) => {
+                            self.router.route_local_timeout(query, timeout.after);
+                            ForwardReason::LocalTimeout.as_str().to_string()
+                        }
+                        None => return Err(e),
+                    },
+                }
+            }
+        };
+
+        let request = MessageRequest::with_context(vec![Message::user(query)]);
+        let response = self.claude_client.send_message(&request).await?;
+        Ok(EngineResponse {
+            text: response.text(),
+            route: "teacher".to_string(),
+            forward_reason: Some(forward_reason),
+            model: Some(response.model),
+            latency: start.elapsed(),
+            usage: response.usage,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::claude::ContentBlock;
+    use crate::providers::{LlmProvider, ProviderRequest, ProviderResponse, StreamChunk};
+    use tokio::sync::mpsc;
+
+    struct EchoProvider;
+
+    #[async_trait::async_trait]
+    impl LlmProvider for EchoProvider {
+        async fn send_message(&self, request: &ProviderRequest) -> Result<ProviderResponse> {
+            Ok(ProviderResponse {
+                id: "test".to_string(),
+                model: "echo-model".to_string(),
+                content: vec![ContentBlock::Text {
+                    text: format!("echo: {}", request.messages[0].text()),
+                }],
+                stop_reason: Some("end_turn".to_string()),
+                role: "assistant".to_string(),
+                provider: "echo".to_string(),
+                usage: Some(TokenUsage {
+                    input_tokens: 3,
+                    output_tokens: 5,
+                }),
+            })
+        }
+
+        async fn send_message_stream(
+            &self,
+            _request: &ProviderRequest,
+        ) -> Result<mpsc::Receiver<Result<StreamChunk>>> {
+            anyhow::bail!("not supported")
+        }
+
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        fn default_model(&self) -> &str {
+            "echo-model"
+        }
+
+        fn supports_streaming(&self) -> bool {
+            false
+        }
+
+        fn supports_tools(&self) -> bool {
+            false
+        }
+    }
+
+    fn engine() -> Engine {
+        Engine::with_client(
+            &Config::new(vec![]),
+            ClaudeClient::with_provider(Box::new(EchoProvider)),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_query_without_local_model_goes_to_teacher() {
+        let response = engine().query("What is a monad?").await.unwrap();
+        assert_eq!(response.text, "echo: What is a monad?");
+        assert_eq!(response.route, "teacher");
+        assert_eq!(response.forward_reason.as_deref(), Some("model_not_ready"));
+        assert_eq!(response.model.as_deref(), Some("echo-model"));
+        assert_eq!(response.usage.map(|u| u.output_tokens), Some(5));
+    }
+
+    #[tokio::test]
+    async fn test_crisis_response() {
+        let detector = CrisisDetector::from_toml(
+            r#"
+            action = "respond"
+            response = "Please reach out."
+            "#,
+        )
+        .unwrap();
+        let engine = engine()
+            .with_router(Router::new(ThresholdRouter::new()).with_crisis_detector(detector));
+
+        let response = engine.query("I want to end my life").await.unwrap();
+        assert_eq!(response.text, "Please reach out.");
+        assert_eq!(response.route, "crisis");
+        assert!(response.model.is_none());
+    }
+}
@@ -0,0 +1,135 @@
+// Feedback export (`shammah feedback export`)
+//
+// Writes feedback entries as JSONL (one entry per line, same shape as the
+// log) or as a flat CSV with a header row, for analysis outside shammah.
+
+use anyhow::{bail, Context, Result};
+use std::io::Write;
+use std::str::FromStr;
+
+use super::{FeedbackEntry, FeedbackRating};
+
+/// CSV columns, in order
+pub const CSV_COLUMNS: [&str; 6] = ["timestamp", "query", "response", "rating", "weight", "note"];
+
+/// Output format for an export
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    #[default]
+    Jsonl,
+    Csv,
+}
+
+impl FromStr for ExportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(format: &str) -> Result<Self> {
+        match format.to_lowercase().as_str() {
+            "jsonl" | "json" => Ok(ExportFormat::Jsonl),
+            "csv" => Ok(ExportFormat::Csv),
+            other => bail!("Unknown export format '{}' (use jsonl or csv)", other),
+        }
+    }
+}
+
+/// Write `entries` to `out` in `format`
+pub fn write_entries(
+    entries: &[FeedbackEntry],
+    format: ExportFormat,
+    out: &mut dyn Write,
+) -> Result<()> {
+    match format {
+        ExportFormat::Jsonl => {
+            for entry in entries {
+                let json =
+                    serde_json::to_string(entry).context("Failed to serialize feedback entry")?;
+                writeln!(out, "{}", json)?;
+            }
+        }
+        ExportFormat::Csv => {
+            writeln!(out, "{}", CSV_COLUMNS.join(","))?;
+            for entry in entries {
+                let rating = match entry.rating {
+                    FeedbackRating::Good => "good",
+                    FeedbackRating::Bad => "bad",
+                };
+                let row = [
+                    entry.timestamp.to_string(),
+                    csv_field(&entry.query),
+                    csv_field(&entry.response),
+                    rating.to_string(),
+                    entry.weight.to_string(),
+                    csv_field(entry.note.as_deref().unwrap_or("")),
+                ];
+                writeln!(out, "{}", row.join(","))?;
+            }
+        }
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// Quote a CSV field if it contains a delimiter, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries() -> Vec<FeedbackEntry> {
+        vec![
+            FeedbackEntry {
+                timestamp: 1_700_000_000,
+                query: "Explain \"borrowing\", briefly".to_string(),
+                response: "Line one\nLine two".to_string(),
+                rating: FeedbackRating::Bad,
+                weight: 10.0,
+                note: Some("too long".to_string()),
+            },
+            FeedbackEntry {
+                timestamp: 1_700_000_100,
+                query: "2+2".to_string(),
+                response: "4".to_string(),
+                rating: FeedbackRating::Good,
+                weight: 1.0,
+                note: None,
+            },
+        ]
+    }
+
+    fn export(format: ExportFormat) -> String {
+        let mut out = Vec::new();
+        write_entries(&entries(), format, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_export_csv() {
+        let csv = export(ExportFormat::Csv);
+        assert_eq!(
+            csv,
+            "timestamp,query,response,rating,weight,note\n\
+             1700000000,\"Explain \"\"borrowing\"\", briefly\",\"Line one\nLine two\",bad,10,too long\n\
+             1700000100,2+2,4,good,1,\n"
+        );
+    }
+
+    #[test]
+    fn test_export_jsonl_round_trips() {
+        let jsonl = export(ExportFormat::Jsonl);
+        let parsed: Vec<FeedbackEntry> = jsonl
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].response, "Line one\nLine two");
+        assert_eq!("CSV".parse::<ExportFormat>().unwrap(), ExportFormat::Csv);
+        assert!("xml".parse::<ExportFormat>().is_err());
+    }
+}
@@ -3,11 +3,19 @@
 // Users can rate responses to collect training data for LoRA fine-tuning.
 // Feedback is logged to ~/.shammah/feedback.jsonl
 
-use anyhow::{Context, Result};
+pub mod export;
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local, NaiveDate, TimeZone};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs::{self, OpenOptions};
 use std::io::Write;
 use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::clock::{Clock, SharedClock, SystemClock};
 
 /// Feedback rating for a response
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
@@ -31,12 +39,47 @@ impl FeedbackRating {
     /// Get display string
     pub fn display_str(&self) -> &'static str {
         match self {
-            FeedbackRating::Good => "👍 Good",
-            FeedbackRating::Bad => "👎 Bad",
+            FeedbackRating::Good => crate::cli::glyphs::glyph("👍 Good", "[+] Good"),
+            FeedbackRating::Bad => crate::cli::glyphs::glyph("👎 Bad", "[-] Bad"),
         }
     }
 }
 
+impl FromStr for FeedbackRating {
+    type Err = anyhow::Error;
+
+    fn from_str(rating: &str) -> Result<Self> {
+        match rating.to_lowercase().as_str() {
+            "good" => Ok(FeedbackRating::Good),
+            "bad" => Ok(FeedbackRating::Bad),
+            other => bail!("Unknown rating '{}' (use good or bad)", other),
+        }
+    }
+}
+
+/// Which entries to load
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FeedbackFilter {
+    /// Only entries at or after this Unix timestamp
+    pub since: Option<u64>,
+    /// Only entries with this rating
+    pub rating: Option<FeedbackRating>,
+}
+
+impl FeedbackFilter {
+    pub fn matches(&self, entry: &FeedbackEntry) -> bool {
+        self.since.is_none_or(|since| entry.timestamp >= since)
+            && self.rating.is_none_or(|rating| entry.rating == rating)
+    }
+}
+
+/// Entries from a lenient load, and how many lines couldn't be parsed
+#[derive(Debug, Clone, Default)]
+pub struct FilteredEntries {
+    pub entries: Vec<FeedbackEntry>,
+    pub skipped: usize,
+}
+
 /// Feedback entry logged to JSONL
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FeedbackEntry {
@@ -56,13 +99,15 @@ pub struct FeedbackEntry {
 }
 
 impl FeedbackEntry {
-    /// Create a new feedback entry
+    /// Create a new feedback entry, timestamped now
     pub fn new(query: String, response: String, rating: FeedbackRating) -> Self {
+        Self::new_at(query, response, rating, &SystemClock)
+    }
+
+    /// Create a new feedback entry, timestamped by `clock`
+    pub fn new_at(query: String, response: String, rating: FeedbackRating, clock: &dyn Clock) -> Self {
         Self {
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
+            timestamp: clock.unix_secs(),
             query,
             response,
             weight: rating.training_weight(),
@@ -78,9 +123,82 @@ impl FeedbackEntry {
     }
 }
 
+/// Summary of the feedback log (`shammah feedback stats`)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FeedbackStats {
+    pub total: usize,
+    pub good: usize,
+    pub bad: usize,
+    /// Entries per local calendar day, oldest first
+    pub per_day: BTreeMap<NaiveDate, usize>,
+    /// Lines that couldn't be parsed
+    pub skipped: usize,
+}
+
+impl FeedbackStats {
+    /// Tally `entries`, bucketing days in `tz`
+    pub fn from_entries<Tz: TimeZone>(entries: &[FeedbackEntry], tz: &Tz) -> Self {
+        let mut stats = Self::default();
+        for entry in entries {
+            stats.total += 1;
+            match entry.rating {
+                FeedbackRating::Good => stats.good += 1,
+                FeedbackRating::Bad => stats.bad += 1,
+            }
+            if let Some(time) = DateTime::from_timestamp(entry.timestamp as i64, 0) {
+                *stats.per_day.entry(time.with_timezone(tz).date_naive()).or_default() += 1;
+            }
+        }
+        stats
+    }
+
+    /// Fraction of entries rated good (0.0 without entries)
+    pub fn good_ratio(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.good as f64 / self.total as f64
+        }
+    }
+
+    /// Readable summary with a per-day histogram
+    pub fn format(&self) -> String {
+        if self.total == 0 {
+            return "No feedback recorded yet.".to_string();
+        }
+
+        let mut lines = vec![
+            format!("Feedback entries: {}", self.total),
+            format!(
+                "  Good: {}  Bad: {}  ({:.1}% good)",
+                self.good,
+                self.bad,
+                self.good_ratio() * 100.0
+            ),
+            String::new(),
+            "Entries per day:".to_string(),
+        ];
+        let max = self.per_day.values().copied().max().unwrap_or(1);
+        let width = max.to_string().len();
+        for (day, count) in &self.per_day {
+            let bar = (count * STATS_BAR_WIDTH).div_ceil(max);
+            lines.push(format!("  {}  {:>width$}  {}", day, count, "█".repeat(bar), width = width));
+        }
+        if self.skipped > 0 {
+            lines.push(String::new());
+            lines.push(format!("⚠️  Skipped {} unreadable line(s)", self.skipped));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Width of the longest histogram bar
+const STATS_BAR_WIDTH: usize = 30;
+
 /// Feedback logger - writes feedback to JSONL file
 pub struct FeedbackLogger {
     file_path: PathBuf,
+    clock: SharedClock,
 }
 
 impl FeedbackLogger {
@@ -95,7 +213,26 @@ impl FeedbackLogger {
 
         let file_path = shammah_dir.join("feedback.jsonl");
 
-        Ok(Self { file_path })
+        Ok(Self::with_path(file_path))
+    }
+
+    /// Create a feedback logger writing to `file_path`
+    pub fn with_path(file_path: PathBuf) -> Self {
+        Self {
+            file_path,
+            clock: crate::clock::system_clock(),
+        }
+    }
+
+    /// Use `clock` for entry timestamps and time windows
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Create an entry timestamped by this logger's clock
+    pub fn entry(&self, query: String, response: String, rating: FeedbackRating) -> FeedbackEntry {
+        FeedbackEntry::new_at(query, response, rating, self.clock.as_ref())
     }
 
     /// Log a feedback entry
@@ -154,11 +291,58 @@ impl FeedbackLogger {
 
         Ok(entries)
     }
+
+    /// Load the entries matching `filter`, skipping lines that don't parse
+    ///
+    /// Unlike `load_all`, a corrupt line doesn't fail the load; it's counted
+    /// in `skipped` instead.
+    pub fn load_filtered(&self, filter: &FeedbackFilter) -> Result<FilteredEntries> {
+        let mut loaded = FilteredEntries::default();
+        if !self.file_path.exists() {
+            return Ok(loaded);
+        }
+
+        let contents = fs::read_to_string(&self.file_path)
+            .context("Failed to read feedback log")?;
+
+        for line in contents.lines().filter(|l| !l.trim().is_empty()) {
+            match serde_json::from_str::<FeedbackEntry>(line) {
+                Ok(entry) if filter.matches(&entry) => loaded.entries.push(entry),
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::debug!("Skipping unreadable feedback entry: {}", e);
+                    loaded.skipped += 1;
+                }
+            }
+        }
+
+        Ok(loaded)
+    }
+
+    /// Summarize the log, bucketing days in the local timezone
+    pub fn compute_stats(&self) -> Result<FeedbackStats> {
+        let loaded = self.load_filtered(&FeedbackFilter::default())?;
+        let mut stats = FeedbackStats::from_entries(&loaded.entries, &Local);
+        stats.skipped = loaded.skipped;
+        Ok(stats)
+    }
+
+    /// Load the entries logged within the last `window`
+    pub fn load_since(&self, window: Duration) -> Result<Vec<FeedbackEntry>> {
+        let cutoff = self.clock.unix_secs().saturating_sub(window.as_secs());
+        Ok(self
+            .load_all()?
+            .into_iter()
+            .filter(|entry| entry.timestamp >= cutoff)
+            .collect())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clock::FixedClock;
+    use std::sync::Arc;
 
     #[test]
     fn test_feedback_rating_weights() {
@@ -191,4 +375,94 @@ mod tests {
 
         assert_eq!(entry.note, Some("Wrong algorithm".to_string()));
     }
+
+    #[test]
+    fn test_load_filtered_skips_corrupt_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let logger = FeedbackLogger::with_path(dir.path().join("feedback.jsonl"))
+            .with_clock(Arc::new(FixedClock::at_unix(1_700_000_000)));
+        logger.log(&logger.entry("a".to_string(), "1".to_string(), FeedbackRating::Good)).unwrap();
+        fs::OpenOptions::new()
+            .append(true)
+            .open(logger.path())
+            .unwrap()
+            .write_all(b"{not json\n\n")
+            .unwrap();
+        logger.log(&logger.entry("b".to_string(), "2".to_string(), FeedbackRating::Bad)).unwrap();
+
+        assert!(logger.load_all().is_err());
+
+        let all = logger.load_filtered(&FeedbackFilter::default()).unwrap();
+        assert_eq!(all.entries.len(), 2);
+        assert_eq!(all.skipped, 1);
+
+        let bad = FeedbackFilter { rating: Some("bad".parse().unwrap()), ..Default::default() };
+        let loaded = logger.load_filtered(&bad).unwrap();
+        assert_eq!(loaded.entries.len(), 1);
+        assert_eq!(loaded.entries[0].query, "b");
+
+        let later = FeedbackFilter { since: Some(1_700_000_001), rating: None };
+        assert!(logger.load_filtered(&later).unwrap().entries.is_empty());
+    }
+
+    #[test]
+    fn test_feedback_stats() {
+        let day = 86_400;
+        let entry = |timestamp: u64, rating| FeedbackEntry {
+            timestamp,
+            query: "q".to_string(),
+            response: "r".to_string(),
+            rating,
+            weight: rating.training_weight(),
+            note: None,
+        };
+        let entries = vec![
+            entry(1_700_000_000, FeedbackRating::Good),
+            entry(1_700_000_000 + 60, FeedbackRating::Good),
+            entry(1_700_000_000 + 2 * day, FeedbackRating::Bad),
+            entry(1_700_000_000 + 2 * day, FeedbackRating::Good),
+        ];
+
+        let stats = FeedbackStats::from_entries(&entries, &chrono::Utc);
+        assert_eq!((stats.total, stats.good, stats.bad), (4, 3, 1));
+        assert_eq!(stats.good_ratio(), 0.75);
+        let days: Vec<_> = stats.per_day.iter().map(|(d, n)| (d.to_string(), *n)).collect();
+        assert_eq!(days, vec![("2023-11-14".to_string(), 2), ("2023-11-16".to_string(), 2)]);
+
+        let summary = stats.format();
+        assert!(summary.contains("Good: 3  Bad: 1  (75.0% good)"));
+        assert!(summary.contains("2023-11-16  2  ██████"));
+
+        assert_eq!(FeedbackStats::default().format(), "No feedback recorded yet.");
+    }
+
+    #[test]
+    fn test_compute_stats_empty_log() {
+        let dir = tempfile::tempdir().unwrap();
+        let logger = FeedbackLogger::with_path(dir.path().join("feedback.jsonl"));
+        let stats = logger.compute_stats().unwrap();
+        assert_eq!(stats.total, 0);
+        assert_eq!(stats.good_ratio(), 0.0);
+    }
+
+    #[test]
+    fn test_load_since_uses_clock() {
+        let dir = tempfile::tempdir().unwrap();
+        let clock = Arc::new(FixedClock::at_unix(1_700_000_000));
+        let logger = FeedbackLogger::with_path(dir.path().join("feedback.jsonl")).with_clock(clock.clone());
+
+        let old = logger.entry("old".to_string(), "a".to_string(), FeedbackRating::Good);
+        assert_eq!(old.timestamp, 1_700_000_000);
+        logger.log(&old).unwrap();
+
+        clock.advance(chrono::Duration::hours(2));
+        logger
+            .log(&logger.entry("new".to_string(), "b".to_string(), FeedbackRating::Bad))
+            .unwrap();
+
+        let recent = logger.load_since(Duration::from_secs(3600)).unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].query, "new");
+        assert_eq!(logger.load_since(Duration::from_secs(3 * 3600)).unwrap().len(), 2);
+    }
 }
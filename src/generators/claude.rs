@@ -6,6 +6,8 @@ use std::sync::Arc;
 use tokio::sync::mpsc;
 
 use crate::claude::{ClaudeClient, ContentBlock, Message, MessageRequest};
+use crate::models::TemperatureConfig;
+use crate::providers::ContextPreview;
 use crate::tools::types::ToolDefinition;
 
 use super::{
@@ -16,6 +18,7 @@ use super::{
 pub struct ClaudeGenerator {
     client: Arc<ClaudeClient>,
     capabilities: GeneratorCapabilities,
+    temperatures: TemperatureConfig,
 }
 
 impl ClaudeGenerator {
@@ -28,9 +31,35 @@ impl ClaudeGenerator {
                 supports_conversation: true,
                 max_context_messages: Some(50),
             },
+            temperatures: TemperatureConfig::default(),
         }
     }
 
+    /// Set per-category sampling temperatures
+    pub fn with_temperatures(mut self, temperatures: TemperatureConfig) -> Self {
+        self.temperatures = temperatures;
+        self
+    }
+
+    /// Build a request, with the temperature for the user's query category
+    fn build_request(
+        &self,
+        messages: Vec<Message>,
+        tools: Option<Vec<ToolDefinition>>,
+    ) -> MessageRequest {
+        let mut request = MessageRequest::with_context(messages);
+        if let Some(tools) = tools {
+            request = request.with_tools(tools);
+        }
+        if let Some(temperature) = request
+            .latest_user_query()
+            .and_then(|query| self.temperatures.for_query(&query))
+        {
+            request = request.with_temperature(temperature);
+        }
+        request
+    }
+
     /// Convert Claude MessageResponse to unified GeneratorResponse
     fn convert_to_unified(
         &self,
@@ -85,24 +114,29 @@ impl Generator for ClaudeGenerator {
         messages: Vec<Message>,
         tools: Option<Vec<ToolDefinition>>,
     ) -> Result<GeneratorResponse> {
-        let mut request = MessageRequest::with_context(messages);
-        if let Some(tools) = tools {
-            request = request.with_tools(tools);
-        }
+        let request = self.build_request(messages, tools);
 
         let response = self.client.send_message(&request).await?;
         Ok(self.convert_to_unified(response))
     }
 
+    async fn generate_with_temperature(
+        &self,
+        messages: Vec<Message>,
+        tools: Option<Vec<ToolDefinition>>,
+        temperature: f32,
+    ) -> Result<GeneratorResponse> {
+        let request = self.build_request(messages, tools).with_temperature(temperature);
+        let response = self.client.send_message(&request).await?;
+        Ok(self.convert_to_unified(response))
+    }
+
     async fn generate_stream(
         &self,
         messages: Vec<Message>,
         tools: Option<Vec<ToolDefinition>>,
     ) -> Result<Option<mpsc::Receiver<Result<StreamChunk>>>> {
-        let mut request = MessageRequest::with_context(messages);
-        if let Some(tools) = tools {
-            request = request.with_tools(tools);
-        }
+        let request = self.build_request(messages, tools);
 
         // Get the streaming receiver from Claude client
         let rx = self.client.send_message_stream(&request).await?;
@@ -116,4 +150,16 @@ impl Generator for ClaudeGenerator {
     fn name(&self) -> &str {
         "Claude API"
     }
+
+    fn is_available(&self) -> bool {
+        !self.client.is_circuit_open()
+    }
+
+    fn preview_context(
+        &self,
+        messages: Vec<Message>,
+        tools: Option<Vec<ToolDefinition>>,
+    ) -> Option<ContextPreview> {
+        Some(self.client.preview_context(&self.build_request(messages, tools)))
+    }
 }
@@ -6,6 +6,7 @@ use serde_json::Value;
 use tokio::sync::mpsc;
 
 use crate::claude::{ContentBlock, Message};
+use crate::providers::ContextPreview;
 use crate::tools::types::ToolDefinition;
 
 // Re-export implementations
@@ -22,6 +23,19 @@ pub trait Generator: Send + Sync {
         tools: Option<Vec<ToolDefinition>>,
     ) -> Result<GeneratorResponse>;
 
+    /// Generate with an explicit sampling temperature (used by /vary)
+    ///
+    /// Generators without a temperature setting ignore it; their sampling
+    /// is already random, so each call still gives a different response.
+    async fn generate_with_temperature(
+        &self,
+        messages: Vec<Message>,
+        tools: Option<Vec<ToolDefinition>>,
+        _temperature: f32,
+    ) -> Result<GeneratorResponse> {
+        self.generate(messages, tools).await
+    }
+
     /// Stream response if supported (returns None if not supported)
     async fn generate_stream(
         &self,
@@ -34,6 +48,25 @@ pub trait Generator: Send + Sync {
 
     /// Get generator name for logging
     fn name(&self) -> &str;
+
+    /// Whether requests can currently be served
+    ///
+    /// False while a teacher's circuit breaker is open, so callers can route
+    /// to another generator instead of failing fast.
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    /// What a request for these messages would send, without sending it
+    ///
+    /// Only generators backed by a teacher provider have one to show (/context).
+    fn preview_context(
+        &self,
+        _messages: Vec<Message>,
+        _tools: Option<Vec<ToolDefinition>>,
+    ) -> Option<ContextPreview> {
+        None
+    }
 }
 
 /// Generator capabilities (what features are supported)
@@ -76,7 +109,10 @@ pub struct ResponseMetadata {
 #[derive(Debug, Clone)]
 pub enum StreamChunk {
     TextDelta(String),                      // Incremental text
+    ThinkingDelta(String),                  // Incremental thinking (display only)
     ContentBlockComplete(ContentBlock),     // Complete tool_use or text block
+    OutputTokens(u32),                      // Output tokens so far, if the provider counts them
+    StopReason(String),                     // Why generation stopped, in Messages API terms
 }
 
 /// Tool use request from generator
@@ -155,7 +155,7 @@ impl QwenGenerator {
             // 2. Generate response
             let output = self.generate_text(&prompt).await?;
 
-            tracing::debug!("Generated output ({} chars): {}", output.len(), &output[..output.len().min(100)]);
+            tracing::debug!("Generated output ({} chars): {}", output.len(), crate::text::truncate_chars(&output, 100));
 
             // 3. Check for tool calls
             if !ToolCallParser::has_tool_calls(&output) {
@@ -5,8 +5,11 @@
 pub mod claude;
 pub mod cli;
 pub mod client; // HTTP client for daemon communication (Phase 8)
+pub mod clock; // Injectable time source for feedback and metrics
 pub mod config;
 pub mod daemon; // Daemon lifecycle and auto-spawn (Phase 8)
+pub mod embeddings; // Pluggable text embeddings (similarity, caches)
+pub mod engine; // One-shot routed queries for library use (no REPL/TUI)
 pub mod errors; // User-friendly error messages
 pub mod feedback; // Response feedback system for LoRA training
 pub mod generators; // Unified generator interface
@@ -17,5 +20,8 @@ pub mod models; // Phase 2: Neural network models
 pub mod providers; // Multi-provider LLM support
 pub mod router;
 pub mod server; // HTTP daemon mode (Phase 1)
+pub mod text; // UTF-8 safe slicing and token streaming
 pub mod tools; // Tool execution system
 pub mod training; // Batch training and checkpoints (Phase 2)
+
+pub use engine::{Engine, EngineResponse};
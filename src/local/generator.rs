@@ -5,11 +5,15 @@
 // Phase 3: Style transfer and quality matching
 
 use crate::local::patterns::PatternClassifier;
+use crate::local::response_limit::{truncate_response, ResponseLimiter};
+use crate::local::timeout::{is_local_timeout, Deadline};
 use crate::models::adapters::{AdapterRegistry, LocalModelAdapter};
 use crate::models::learning::{
     LearningModel, ModelExpectation, ModelPrediction, ModelStats, PredictionData,
 };
-use crate::models::GeneratorModel;
+use crate::models::persistence::{read_state_with_backup, write_state_atomic};
+use crate::models::{GeneratorModel, ToolPromptFormatter};
+use crate::tools::types::ToolDefinition;
 use crate::training::batch_trainer::BatchTrainer;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
@@ -18,6 +22,21 @@ use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+/// New tokens for a plain answer
+const MAX_NEW_TOKENS: usize = 100;
+
+/// New tokens when tools are offered (room for a `<tool_use>` block and its JSON)
+const TOOL_MAX_NEW_TOKENS: usize = 256;
+
+/// How many new tokens a generation may produce
+pub fn max_new_tokens(tools: &[ToolDefinition]) -> usize {
+    if tools.is_empty() {
+        MAX_NEW_TOKENS
+    } else {
+        TOOL_MAX_NEW_TOKENS
+    }
+}
+
 /// Response template for a pattern
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct ResponseTemplate {
@@ -39,6 +58,8 @@ pub struct TemplateGenerator {
     system_prompt: String,
     /// Model adapter for formatting prompts and cleaning output
     model_adapter: Box<dyn LocalModelAdapter>,
+    /// Neural generation stops here (set per request by LocalGenerator)
+    deadline: Option<Deadline>,
 }
 
 /// A response learned from Claude
@@ -51,6 +72,11 @@ struct LearnedResponse {
 }
 
 impl TemplateGenerator {
+    /// The neural model backing generation, if one is loaded
+    pub fn neural_generator(&self) -> Option<&Arc<RwLock<GeneratorModel>>> {
+        self.neural_generator.as_ref()
+    }
+
     /// Create new response generator without neural models
     pub fn new(pattern_classifier: PatternClassifier) -> Self {
         Self::with_models(pattern_classifier, None, "Qwen") // Default to Qwen
@@ -106,36 +132,36 @@ impl TemplateGenerator {
             neural_generator,
             system_prompt,
             model_adapter,
+            deadline: None,
         }
     }
 
+    /// Set the deadline for the next neural generation (None = no limit)
+    pub fn set_deadline(&mut self, deadline: Option<Deadline>) {
+        self.deadline = deadline;
+    }
+
     /// Generate a response with streaming callback
     ///
     /// Calls the callback for each generated token with (token_id, token_text).
+    /// With `max_response_chars`, generation stops at the first sentence
+    /// boundary past the limit (see `response_limit`).
     pub fn generate_streaming<F>(
         &mut self,
         messages: &[crate::claude::Message],
+        max_response_chars: Option<usize>,
         token_callback: F,
     ) -> Result<Option<crate::generators::GeneratorResponse>>
     where
         F: FnMut(u32, &str) + Send + 'static,
     {
-        // Extract the user's last message
-        let query = messages
-            .iter()
-            .rev()
-            .find(|m| m.role == "user")
-            .and_then(|m| {
-                m.content.iter().find_map(|block| match block {
-                    crate::claude::ContentBlock::Text { text } => Some(text.as_str()),
-                    _ => None,
-                })
-            })
+        // The conversation, as one query
+        let query = super::conversation_query(messages)
             .ok_or_else(|| anyhow::anyhow!("No user message found"))?;
 
         // Try neural generator with streaming
         if let Some(generator) = &self.neural_generator {
-            match self.try_neural_generate_streaming(query, generator, token_callback) {
+            match self.try_neural_generate_streaming(&query, generator, max_response_chars, token_callback) {
                 Ok(neural_response) => {
                     // Convert to GeneratorResponse format
                     use crate::generators::ResponseMetadata;
@@ -159,6 +185,7 @@ impl TemplateGenerator {
 
                     return Ok(Some(response));
                 }
+                Err(e) if is_local_timeout(&e) => return Err(e),
                 Err(e) => {
                     tracing::warn!("Neural streaming generation failed: {}", e);
                     return Ok(None);
@@ -177,24 +204,17 @@ impl TemplateGenerator {
 
         // 1. Try neural generator FIRST - ALWAYS show the output if generation succeeds
         if let Some(generator) = &self.neural_generator {
-            match self.try_neural_generate(query, generator) {
+            match self.try_neural_generate(query, &[], generator) {
                 Ok(neural_response) => {
                     // Return neural response (quality score used internally for routing)
-                    let quality_score = if neural_response.len() < 10 {
-                        0.5 // Lower confidence for very short responses
-                    } else if neural_response.starts_with("[Error:") {
-                        0.3 // Low confidence for error responses
-                    } else {
-                        0.9 // High confidence for normal responses
-                    };
-
                     return Ok(GeneratedResponse {
+                        confidence: neural_confidence(&neural_response),
                         text: neural_response, // Clean output without debug prefixes
                         method: "neural".to_string(),
-                        confidence: quality_score,
                         pattern: pattern.as_str().to_string(),
                     });
                 }
+                Err(e) if is_local_timeout(&e) => return Err(e),
                 Err(e) => {
                     // Neural generation failed entirely - show the full error with context
                     let full_error = format!("{:#}", e); // Use alternate display for full error chain
@@ -236,6 +256,37 @@ impl TemplateGenerator {
         ))
     }
 
+    /// Generate a response that may call `tools`
+    ///
+    /// The tool definitions are added to the system prompt (see
+    /// `ToolPromptFormatter`) and any `<tool_use>` blocks are left in the
+    /// returned text for the caller to parse. Tool calls need the neural
+    /// model, so there is no learned-response fallback; without tools this is
+    /// `generate`.
+    pub fn generate_with_tools(
+        &mut self,
+        query: &str,
+        tools: &[ToolDefinition],
+    ) -> Result<GeneratedResponse> {
+        if tools.is_empty() {
+            return self.generate(query);
+        }
+
+        let (pattern, _) = self.pattern_classifier.classify(query);
+        let generator = self
+            .neural_generator
+            .as_ref()
+            .context("No neural model available for local tool use")?;
+        let text = self.try_neural_generate(query, tools, generator)?;
+
+        Ok(GeneratedResponse {
+            confidence: neural_confidence(&text),
+            text,
+            method: "neural".to_string(),
+            pattern: pattern.as_str().to_string(),
+        })
+    }
+
     /// Load constitution from file or use default
     fn load_constitution() -> String {
         let home = dirs::home_dir().expect("Could not determine home directory");
@@ -268,11 +319,26 @@ impl TemplateGenerator {
         self.model_adapter.format_chat_prompt(&self.system_prompt, user_query)
     }
 
+    /// The prompt the model sees for `query`, with `tools` described in the
+    /// system prompt (see `ToolPromptFormatter`)
+    pub fn prompt_for(&self, query: &str, tools: &[ToolDefinition]) -> String {
+        if tools.is_empty() {
+            return self.format_chat_prompt(query);
+        }
+        let system_prompt = format!(
+            "{}{}",
+            self.system_prompt,
+            ToolPromptFormatter::format_tools_for_prompt(tools)
+        );
+        self.model_adapter.format_chat_prompt(&system_prompt, query)
+    }
+
     /// Try to generate response using neural model with streaming
     fn try_neural_generate_streaming<F>(
         &self,
         query: &str,
         generator: &Arc<RwLock<GeneratorModel>>,
+        max_response_chars: Option<usize>,
         mut token_callback: F,
     ) -> Result<String>
     where
@@ -305,11 +371,22 @@ impl TemplateGenerator {
 
         let input_ids = encoding.get_ids().to_vec();
 
+        let mut limiter = max_response_chars.map(ResponseLimiter::new);
+        let deadline = self.deadline;
+        let timed_out = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let timed_out_flag = Arc::clone(&timed_out);
+
         // Generate with streaming callback (filter special tokens)
         let output_ids = onnx_model.generate_stream(
             &input_ids,
-            100, // max 100 new tokens
+            MAX_NEW_TOKENS,
             Box::new(move |token_id, token_text| {
+                // Stop promptly once the local generation timeout passes
+                if deadline.is_some_and(|deadline| deadline.expired()) {
+                    timed_out_flag.store(true, std::sync::atomic::Ordering::Relaxed);
+                    return false;
+                }
+
                 // Filter out special tokens (template markers, control characters)
                 // Only stream actual content tokens
                 let is_special = token_text.contains("<|")  // Qwen ChatML tokens like <|im_end|>
@@ -322,12 +399,30 @@ impl TemplateGenerator {
                     || token_text.contains("\\boxed")  // LaTeX formatting
                     || token_text.trim().is_empty();  // Skip whitespace-only tokens
 
-                if !is_special {
-                    token_callback(token_id, token_text);
+                if is_special {
+                    return true;
+                }
+
+                match limiter.as_mut() {
+                    Some(limiter) => {
+                        let step = limiter.push(token_text);
+                        if !step.emit.is_empty() {
+                            token_callback(token_id, &step.emit);
+                        }
+                        !step.stop
+                    }
+                    None => {
+                        token_callback(token_id, token_text);
+                        true
+                    }
                 }
             }),
         )?;
 
+        if let Some(deadline) = deadline.filter(|_| timed_out.load(std::sync::atomic::Ordering::Relaxed)) {
+            return Err(deadline.timeout_error().into());
+        }
+
         // Decode full output
         let raw_response = onnx_model.tokenizer()
             .decode(&output_ids, true)
@@ -336,7 +431,11 @@ impl TemplateGenerator {
         // Clean output using model adapter
         let clean_response = self.model_adapter.clean_output(&raw_response);
 
-        Ok(clean_response)
+        // Keep the returned text consistent with what was streamed
+        Ok(match max_response_chars {
+            Some(max_chars) => truncate_response(&clean_response, max_chars),
+            None => clean_response,
+        })
     }
 
     /// Get the model adapter for external use (e.g., streaming cleaning)
@@ -350,12 +449,14 @@ impl TemplateGenerator {
     fn try_neural_generate(
         &self,
         query: &str,
+        tools: &[ToolDefinition],
         generator: &Arc<RwLock<GeneratorModel>>,
     ) -> Result<String> {
         tracing::info!("[neural_gen] Starting neural generation for query: {}", query);
 
         // Format query with system prompt using chat template
-        let formatted_prompt = self.format_chat_prompt(query);
+        let formatted_prompt = self.prompt_for(query, tools);
+        let max_new_tokens = max_new_tokens(tools);
         tracing::debug!("[neural_gen] Formatted prompt length: {} chars", formatted_prompt.len());
 
         // Generate with neural model (try non-blocking lock)
@@ -364,10 +465,10 @@ impl TemplateGenerator {
             .try_write()
             .map_err(|_| anyhow::anyhow!("Generator model is locked"))?;
 
-        tracing::info!("[neural_gen] Lock acquired, starting generation (max 100 tokens)...");
+        tracing::info!("[neural_gen] Lock acquired, starting generation (max {} tokens)...", max_new_tokens);
 
         // Use generate_text() which handles tokenization internally
-        let raw_response = gen.generate_text(&formatted_prompt, 100)?; // max 100 new tokens
+        let raw_response = gen.generate_text_until(&formatted_prompt, max_new_tokens, self.deadline)?;
 
         tracing::info!("[neural_gen] Raw response length: {} chars", raw_response.len());
 
@@ -416,7 +517,7 @@ impl TemplateGenerator {
 
         // NEW: Also add to BatchTrainer for neural training
         if let Some(trainer) = batch_trainer {
-            if quality_score >= 0.7 {
+            if quality_score >= crate::models::quality::LEARN_THRESHOLD {
                 use crate::training::batch_trainer::TrainingExample;
 
                 let example = TrainingExample::new(
@@ -437,6 +538,17 @@ impl TemplateGenerator {
     }
 }
 
+/// Confidence in a neural response (used internally for routing)
+fn neural_confidence(text: &str) -> f64 {
+    if text.len() < 10 {
+        0.5 // Lower confidence for very short responses
+    } else if text.starts_with("[Error:") {
+        0.3 // Low confidence for error responses
+    } else {
+        0.9 // High confidence for normal responses
+    }
+}
+
 /// Generated response with metadata
 #[derive(Debug, Clone)]
 pub struct GeneratedResponse {
@@ -500,7 +612,7 @@ impl LearningModel for TemplateGenerator {
 
     fn save(&self, path: &Path) -> Result<()> {
         let json = serde_json::to_string_pretty(self)?;
-        std::fs::write(path, json).context("Failed to save response generator")
+        write_state_atomic(path, &json).context("Failed to save response generator")
     }
 
     fn load(path: &Path) -> Result<Self>
@@ -511,9 +623,7 @@ impl LearningModel for TemplateGenerator {
             anyhow::bail!("File not found: {}", path.display());
         }
 
-        let json = std::fs::read_to_string(path)?;
-        let loaded: TemplateGenerator = serde_json::from_str(&json)?;
-        Ok(loaded)
+        read_state_with_backup(path)
     }
 
     fn name(&self) -> &str {
@@ -561,6 +671,7 @@ impl<'de> serde::Deserialize<'de> for TemplateGenerator {
             neural_generator: None,
             system_prompt: Self::load_constitution(),
             model_adapter: AdapterRegistry::get_adapter("Qwen"), // Default to Qwen
+            deadline: None,
         })
     }
 }
@@ -5,25 +5,61 @@
 
 pub mod generator;
 pub mod patterns;
+pub mod response_limit;
+pub mod timeout;
 
 pub use generator::{GeneratedResponse, TemplateGenerator};
 pub use patterns::{PatternClassifier, QueryPattern};
+pub use response_limit::{truncate_response, ResponseLimiter, TRUNCATION_MARKER};
+pub use timeout::{is_local_timeout, LocalTimeout};
 
 use crate::claude::Message;
 use crate::generators::{GeneratorResponse, Generator};
 use crate::models::adapters::LocalModelAdapter;
 use crate::models::{GeneratorModel, TextTokenizer};
+use crate::cli::recent_turns_start;
 use crate::tools::types::ToolDefinition;
 use crate::training::batch_trainer::BatchTrainer;
 use anyhow::Result;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 use tokio::sync::RwLock;
+use timeout::Deadline;
+
+/// Default minimum confidence for answering locally (`backend.local_confidence_threshold`)
+pub const DEFAULT_LOCAL_CONFIDENCE_THRESHOLD: f64 = 0.7;
+
+/// Describe the local model: its loading state, and once ready, what was
+/// loaded and which adapter the generator applied (/model-info, `GET /model`)
+pub async fn model_info(
+    generator_state: &RwLock<crate::models::GeneratorState>,
+    local_generator: &RwLock<LocalGenerator>,
+) -> crate::models::ModelInfo {
+    let adapter = local_generator.read().await.loaded_adapter().map(Path::to_path_buf);
+    let state = generator_state.read().await;
+    crate::models::ModelInfo::from_state(&state, adapter.as_deref()).await
+}
 
 /// Local generation system that coordinates pattern classification and response generation
 pub struct LocalGenerator {
     pattern_classifier: PatternClassifier,
     response_generator: TemplateGenerator,
     enabled: bool,
+    /// Soft response length limit (None = unlimited)
+    max_response_chars: Option<usize>,
+    /// Give up on a local generation after this long (None = unlimited)
+    generation_timeout: Option<Duration>,
+    /// Context window in tokens (None = what the model reports)
+    context_length: Option<usize>,
+    /// Minimum classification and response confidence for a local answer
+    confidence_threshold: f64,
+    /// Where trained LoRA adapters appear (None: ~/.shammah/adapters)
+    adapters_dir: Option<PathBuf>,
+    /// Modification time of the newest adapter handed to the model
+    adapter_mtime: Option<SystemTime>,
+    /// Adapter the model is running with (None = base model)
+    loaded_adapter: Option<PathBuf>,
 }
 
 impl LocalGenerator {
@@ -60,6 +96,122 @@ impl LocalGenerator {
             pattern_classifier,
             response_generator,
             enabled: true,
+            max_response_chars: None,
+            generation_timeout: None,
+            context_length: None,
+            confidence_threshold: DEFAULT_LOCAL_CONFIDENCE_THRESHOLD,
+            adapters_dir: None,
+            adapter_mtime: None,
+            loaded_adapter: None,
+        }
+    }
+
+    /// Set the default soft response length limit (`backend.max_response_chars`)
+    pub fn with_max_response_chars(mut self, max_response_chars: Option<usize>) -> Self {
+        self.max_response_chars = max_response_chars;
+        self
+    }
+
+    /// Set the local generation timeout (`backend.local_generation_timeout_ms`)
+    pub fn with_generation_timeout(mut self, generation_timeout: Option<Duration>) -> Self {
+        self.generation_timeout = generation_timeout;
+        self
+    }
+
+    /// Override the model's context window (`backend.context_length`)
+    pub fn with_context_length(mut self, context_length: Option<usize>) -> Self {
+        self.context_length = context_length;
+        self
+    }
+
+    /// Set the minimum confidence for local answers (`backend.local_confidence_threshold`)
+    pub fn with_confidence_threshold(mut self, confidence_threshold: f64) -> Self {
+        self.confidence_threshold = confidence_threshold;
+        self
+    }
+
+    /// Watch a different directory for trained adapters
+    pub fn with_adapters_dir(mut self, adapters_dir: PathBuf) -> Self {
+        self.adapters_dir = Some(adapters_dir);
+        self
+    }
+
+    /// Rebuild around a newly loaded model, keeping the configured limits
+    ///
+    /// The adapter is not carried over: the new model picks up the latest
+    /// adapter on its first generation.
+    pub fn reloaded(&self, neural_generator: Option<Arc<RwLock<GeneratorModel>>>) -> Self {
+        let mut generator = Self::with_models(neural_generator)
+            .with_max_response_chars(self.max_response_chars)
+            .with_generation_timeout(self.generation_timeout)
+            .with_context_length(self.context_length)
+            .with_confidence_threshold(self.confidence_threshold);
+        generator.adapters_dir = self.adapters_dir.clone();
+        generator
+    }
+
+    /// Modification time of the adapter last handed to the model
+    pub fn adapter_mtime(&self) -> Option<SystemTime> {
+        self.adapter_mtime
+    }
+
+    /// LoRA adapter the model loaded successfully (None = base model)
+    pub fn loaded_adapter(&self) -> Option<&Path> {
+        self.loaded_adapter.as_deref()
+    }
+
+    /// Start the timeout clock for one generation
+    fn start_deadline(&mut self) {
+        let deadline = self.generation_timeout.map(Deadline::start);
+        self.response_generator.set_deadline(deadline);
+    }
+
+    /// Drop the oldest turns until the prompt fits the model's context window
+    ///
+    /// Prompt sizes come from the model's tokenizer, with room left for the
+    /// answer. Without a loaded model or a known context length the messages
+    /// are used as they are.
+    fn fit_context(&self, messages: &[Message], tools: &[ToolDefinition]) -> Vec<Message> {
+        let Some(model) = self.response_generator.neural_generator() else {
+            return messages.to_vec();
+        };
+        let Ok(model) = model.try_read() else {
+            return messages.to_vec();
+        };
+        let Some(context_length) = self.context_length.or_else(|| model.context_length()) else {
+            return messages.to_vec();
+        };
+
+        let budget = context_length.saturating_sub(generator::max_new_tokens(tools));
+        let prompt_tokens = |messages: &[Message]| {
+            let query = conversation_query(messages).unwrap_or_default();
+            model.count_tokens(&self.response_generator.prompt_for(&query, tools))
+        };
+        match fit_to_budget(messages, budget, prompt_tokens) {
+            Ok(fitted) => {
+                if fitted.len() < messages.len() {
+                    tracing::debug!(
+                        context_length,
+                        budget,
+                        kept = fitted.len(),
+                        dropped = messages.len() - fitted.len(),
+                        "Dropped old turns to fit the local model's context window"
+                    );
+                }
+                fitted
+            }
+            Err(e) => {
+                tracing::debug!("Couldn't measure the local prompt, sending it whole: {}", e);
+                messages.to_vec()
+            }
+        }
+    }
+
+    /// Apply the response length limit (per-request override wins)
+    fn limit_response(&self, text: String, max_response_chars: Option<usize>) -> String {
+        match max_response_chars.or(self.max_response_chars) {
+            Some(max_chars) => truncate_response(&text, max_chars),
+            None => text,
         }
     }
 
@@ -78,6 +230,18 @@ impl LocalGenerator {
 
     /// Try to generate a local response from patterns
     pub fn try_generate_from_pattern(&mut self, query: &str) -> Result<Option<String>> {
+        self.try_generate_from_pattern_limited(query, None)
+    }
+
+    /// Try to generate a local response, overriding the response length limit
+    ///
+    /// Returns `Err(LocalTimeout)` if generation ran past the timeout; other
+    /// failures are `Ok(None)` (forward to the teacher).
+    pub fn try_generate_from_pattern_limited(
+        &mut self,
+        query: &str,
+        max_response_chars: Option<usize>,
+    ) -> Result<Option<String>> {
         if !self.enabled {
             return Ok(None);
         }
@@ -86,20 +250,22 @@ impl LocalGenerator {
         let (pattern, confidence) = self.pattern_classifier.classify(query);
 
         // Only try local generation if confidence is high enough
-        if confidence < 0.7 {
+        if confidence < self.confidence_threshold {
             return Ok(None);
         }
 
         // Try to generate response
+        self.start_deadline();
         match self.response_generator.generate(query) {
             Ok(response) => {
                 // Only return if confidence is high enough
-                if response.confidence >= 0.7 {
-                    Ok(Some(response.text))
+                if response.confidence >= self.confidence_threshold {
+                    Ok(Some(self.limit_response(response.text, max_response_chars)))
                 } else {
                     Ok(None)
                 }
             }
+            Err(e) if is_local_timeout(&e) => Err(e),
             Err(_) => Ok(None),
         }
     }
@@ -108,9 +274,11 @@ impl LocalGenerator {
     ///
     /// Calls the callback for each generated token with (token_id, token_text).
     /// This enables Server-Sent Events streaming to the client.
+    /// `max_response_chars` overrides the configured limit for this request.
     pub fn try_generate_from_pattern_streaming<F>(
         &mut self,
         messages: &[Message],
+        max_response_chars: Option<usize>,
         token_callback: F,
     ) -> Result<Option<GeneratorResponse>>
     where
@@ -124,17 +292,40 @@ impl LocalGenerator {
         }
 
         // Delegate to response generator with streaming callback
-        self.response_generator.generate_streaming(messages, token_callback)
+        let messages = self.fit_context(messages, &[]);
+        let max_response_chars = max_response_chars.or(self.max_response_chars);
+        self.start_deadline();
+        self.response_generator
+            .generate_streaming(&messages, max_response_chars, token_callback)
+    }
+
+    /// Run a short throwaway generation to warm up the loaded model
+    ///
+    /// ONNX Runtime allocates buffers and optimizes the graph lazily, so the
+    /// first generation after loading is much slower than the rest. Returns
+    /// how long the warmup took; fails if the model produced nothing.
+    pub fn warm_up(&mut self) -> Result<Duration> {
+        let started = std::time::Instant::now();
+        // Stop at the first sentence of the answer
+        let response = self.try_generate_from_pattern_streaming(&[Message::user("Hi")], Some(1), |_, _| {})?;
+        if response.is_none() {
+            anyhow::bail!("Local model produced no output");
+        }
+        Ok(started.elapsed())
     }
 
     /// Try to generate a response from patterns with tools
     ///
     /// This method is used by the daemon to support tool execution.
-    /// Delegates to the neural generator (ONNX model) if available.
+    /// Delegates to the neural generator (ONNX model) if available. Tool
+    /// definitions go into the prompt, and `<tool_use>` blocks in the output
+    /// come back as `tool_uses` (and `ToolUse` content blocks) for the caller
+    /// to execute. Output with a malformed tool call is `Ok(None)`.
     pub fn try_generate_from_pattern_with_tools(
         &mut self,
         messages: &[Message],
         tools: Option<Vec<ToolDefinition>>,
+        max_response_chars: Option<usize>,
     ) -> Result<Option<GeneratorResponse>> {
         // Check for newer adapter before generation
         self.check_and_reload_adapter()?;
@@ -143,96 +334,109 @@ impl LocalGenerator {
             return Ok(None);
         }
 
-        // Extract the user's last message
-        let query = messages
-            .iter()
-            .rev()
-            .find(|m| m.role == "user")
-            .and_then(|m| {
-                m.content.iter().find_map(|block| match block {
-                    crate::claude::ContentBlock::Text { text } => Some(text.as_str()),
-                    _ => None,
-                })
-            })
+        // The conversation (as much as fits the context window) as one query
+        let tools = tools.unwrap_or_default();
+        let messages = self.fit_context(messages, &tools);
+        let query = conversation_query(&messages)
             .ok_or_else(|| anyhow::anyhow!("No user message found"))?;
 
         // Generate using the response generator (which tries neural model first)
-        match self.response_generator.generate(query) {
-            Ok(generated) => {
-                // Convert generated response to GeneratorResponse format
-                use crate::generators::ResponseMetadata;
-
-                let response = GeneratorResponse {
-                    text: generated.text.clone(),
-                    content_blocks: vec![crate::claude::ContentBlock::Text {
-                        text: generated.text.clone(),
-                    }],
-                    tool_uses: vec![], // TODO: Support tool use when integrated with QwenGenerator
-                    metadata: ResponseMetadata {
-                        generator: "qwen-local".to_string(),
-                        model: "Qwen2.5-1.5B-Instruct".to_string(), // TODO: Get from config
-                        confidence: Some(generated.confidence),
-                        stop_reason: None,
-                        input_tokens: None,
-                        output_tokens: Some(generated.text.split_whitespace().count() as u32),
-                        latency_ms: None,
-                    },
-                };
-
-                Ok(Some(response))
-            }
+        self.start_deadline();
+        let generated = match self.response_generator.generate_with_tools(&query, &tools) {
+            Ok(generated) => generated,
+            Err(e) if is_local_timeout(&e) => return Err(e),
             Err(e) => {
                 tracing::warn!("Local generation failed: {}", e);
-                Ok(None)
+                return Ok(None);
             }
-        }
+        };
+
+        // Split off tool calls before limiting, so truncation can't cut one in half
+        let (text, tool_uses) = match split_tool_calls(&generated.text) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                tracing::warn!("Local model produced an unparseable tool call: {:#}", e);
+                return Ok(None);
+            }
+        };
+        let text = self.limit_response(text, max_response_chars);
+        Ok(Some(local_response(text, tool_uses, generated.confidence)))
     }
 
-    /// Check if a newer adapter is available and reload if so
+    /// Load the newest adapter into the model if it changed since the last check
+    ///
+    /// Runs before each generation, so an adapter written by a finished
+    /// training run is picked up by the running daemon without a restart.
     fn check_and_reload_adapter(&mut self) -> Result<()> {
-        let adapters_dir = dirs::home_dir()
-            .ok_or_else(|| anyhow::anyhow!("Cannot determine home directory"))?
-            .join(".shammah")
-            .join("adapters");
+        let adapters_dir = match &self.adapters_dir {
+            Some(dir) => dir.clone(),
+            None => dirs::home_dir()
+                .ok_or_else(|| anyhow::anyhow!("Cannot determine home directory"))?
+                .join(".shammah")
+                .join("adapters"),
+        };
 
         if !adapters_dir.exists() {
             return Ok(());
         }
 
-        // Find latest adapter
-        if let Some(latest_adapter) = self.find_latest_adapter(&adapters_dir)? {
-            // TODO: Track last loaded adapter timestamp and only reload if newer
-            // For now, we skip reload since adapter loading isn't implemented yet
-            tracing::debug!(
-                "Found adapter: {} (reload not yet implemented)",
-                latest_adapter.display()
-            );
+        let Some((latest_adapter, modified)) = Self::find_latest_adapter(&adapters_dir)? else {
+            return Ok(());
+        };
+        if self.adapter_mtime.is_some_and(|loaded| modified <= loaded) {
+            return Ok(());
+        }
+
+        if let Some(model) = self.response_generator.neural_generator() {
+            // Generation runs under the model lock; if it's busy, try on the next request
+            let Ok(mut model) = model.try_write() else {
+                tracing::debug!("Model busy, deferring adapter reload");
+                return Ok(());
+            };
+            match model.load_lora(&latest_adapter) {
+                Ok(()) => {
+                    tracing::info!(adapter = %latest_adapter.display(), "Loaded new LoRA adapter");
+                    self.loaded_adapter = Some(latest_adapter.clone());
+                }
+                Err(e) => tracing::warn!(
+                    adapter = %latest_adapter.display(),
+                    "New LoRA adapter found but the model couldn't load it: {}",
+                    e
+                ),
+            }
         }
 
+        // Recorded even if loading failed, so a bad adapter isn't retried every request
+        self.adapter_mtime = Some(modified);
         Ok(())
     }
 
-    /// Find the most recent adapter in the adapters directory
-    fn find_latest_adapter(&self, adapters_dir: &std::path::Path) -> Result<Option<std::path::PathBuf>> {
+    /// Find the most recent adapter in the adapters directory, with its mtime
+    fn find_latest_adapter(adapters_dir: &Path) -> Result<Option<(PathBuf, SystemTime)>> {
         use std::fs;
 
-        let mut latest: Option<(std::path::PathBuf, std::time::SystemTime)> = None;
+        let mut latest: Option<(PathBuf, SystemTime)> = None;
 
         for entry in fs::read_dir(adapters_dir)? {
             let entry = entry?;
             let path = entry.path();
 
-            if path.extension().and_then(|s| s.to_str()) == Some("safetensors") {
+            // Skip adapters a training run is still writing (latest.partial-<run>.safetensors)
+            let is_partial = path
+                .file_name()
+                .and_then(|s| s.to_str())
+                .is_some_and(|name| name.contains(".partial-"));
+            if path.extension().and_then(|s| s.to_str()) == Some("safetensors") && !is_partial {
                 let metadata = fs::metadata(&path)?;
                 let modified = metadata.modified()?;
 
-                if latest.is_none() || modified > latest.as_ref().unwrap().1 {
+                if latest.as_ref().map_or(true, |(_, newest)| modified > *newest) {
                     latest = Some((path, modified));
                 }
             }
         }
 
-        Ok(latest.map(|(path, _)| path))
+        Ok(latest)
     }
 
     /// Learn from a Claude response
@@ -290,6 +494,13 @@ impl LocalGenerator {
             pattern_classifier,
             response_generator,
             enabled: true,
+            max_response_chars: None,
+            generation_timeout: None,
+            context_length: None,
+            confidence_threshold: DEFAULT_LOCAL_CONFIDENCE_THRESHOLD,
+            adapters_dir: None,
+            adapter_mtime: None,
+            loaded_adapter: None,
         })
     }
 }
@@ -300,6 +511,147 @@ impl Default for LocalGenerator {
     }
 }
 
+/// The conversation as a single query for the local model
+///
+/// A lone user message is just its text. Longer conversations (earlier
+/// turns, tool calls and their results) become a "User:"/"Assistant:"
+/// transcript. System messages are left out; the local model has its own
+/// system prompt.
+fn conversation_query(messages: &[Message]) -> Option<String> {
+    use crate::claude::ContentBlock;
+
+    let first_text = |message: &Message| {
+        message.content.iter().find_map(|block| match block {
+            ContentBlock::Text { text } => Some(text.clone()),
+            _ => None,
+        })
+    };
+    let turns: Vec<&Message> = messages.iter().filter(|m| m.role != "system").collect();
+    if !turns.iter().any(|m| m.role == "user" && first_text(m).is_some()) {
+        return None;
+    }
+    if let [only] = turns[..] {
+        return first_text(only);
+    }
+
+    let mut transcript = String::new();
+    for message in turns {
+        let label = match message.role.as_str() {
+            "user" => "User",
+            "assistant" => "Assistant",
+            _ => continue,
+        };
+        let mut body = String::new();
+        for block in &message.content {
+            match block {
+                ContentBlock::Text { text } => body.push_str(text),
+                ContentBlock::ToolUse { name, input, .. } => body.push_str(&format!(
+                    "[Called tool '{}' with params: {}]",
+                    name,
+                    serde_json::to_string(input).unwrap_or_default()
+                )),
+                ContentBlock::ToolResult { content, is_error, .. } => {
+                    if *is_error == Some(true) {
+                        body.push_str(&format!("[Tool Error: {}]", content));
+                    } else {
+                        body.push_str(&format!("[Tool Result: {}]", content));
+                    }
+                }
+                _ => {}
+            }
+        }
+        if !body.is_empty() {
+            transcript.push_str(&format!("{}: {}\n\n", label, body));
+        }
+    }
+    Some(transcript.trim_end().to_string())
+}
+
+/// The most recent turns of `messages` whose prompt fits in `budget` tokens
+///
+/// Turns start at a prompt the user typed (`recent_turns_start`), so a tool
+/// call is never kept without its result or the prompt that led to it.
+/// System messages always stay, ahead of the turns. The latest turn is
+/// always kept, even over budget.
+fn fit_to_budget(
+    messages: &[Message],
+    budget: usize,
+    prompt_tokens: impl Fn(&[Message]) -> Result<usize>,
+) -> Result<Vec<Message>> {
+    if prompt_tokens(messages)? <= budget {
+        return Ok(messages.to_vec());
+    }
+
+    let (system, conversation): (Vec<Message>, Vec<Message>) =
+        messages.iter().cloned().partition(|m| m.role == "system");
+    let recent = |start: usize| -> Vec<Message> {
+        system.iter().chain(&conversation[start..]).cloned().collect()
+    };
+
+    let mut fitted = recent(recent_turns_start(&conversation, 1));
+    for turns in 2.. {
+        let start = recent_turns_start(&conversation, turns);
+        let candidate = recent(start);
+        if candidate.len() == fitted.len() || prompt_tokens(&candidate)? > budget {
+            break;
+        }
+        fitted = candidate;
+        if start == 0 {
+            break;
+        }
+    }
+    Ok(fitted)
+}
+
+/// Split local model output into its text and the `<tool_use>` calls in it
+fn split_tool_calls(output: &str) -> Result<(String, Vec<crate::generators::ToolUse>)> {
+    use crate::models::ToolCallParser;
+
+    if !ToolCallParser::has_tool_calls(output) {
+        return Ok((output.to_string(), Vec::new()));
+    }
+    let tool_uses = ToolCallParser::parse(output)?
+        .into_iter()
+        .map(|tool_use| crate::generators::ToolUse {
+            id: tool_use.id,
+            name: tool_use.name,
+            input: tool_use.input,
+        })
+        .collect();
+    Ok((ToolCallParser::extract_text(output), tool_uses))
+}
+
+/// A local answer as a `GeneratorResponse` (text block, then one block per tool call)
+fn local_response(
+    text: String,
+    tool_uses: Vec<crate::generators::ToolUse>,
+    confidence: f64,
+) -> GeneratorResponse {
+    use crate::claude::ContentBlock;
+    use crate::generators::ResponseMetadata;
+
+    let mut content_blocks = Vec::new();
+    if !text.is_empty() || tool_uses.is_empty() {
+        content_blocks.push(ContentBlock::Text { text: text.clone() });
+    }
+    content_blocks.extend(tool_uses.iter().map(|tool_use| tool_use.to_content_block()));
+
+    GeneratorResponse {
+        metadata: ResponseMetadata {
+            generator: "qwen-local".to_string(),
+            model: "Qwen2.5-1.5B-Instruct".to_string(), // TODO: Get from config
+            confidence: Some(confidence),
+            stop_reason: (!tool_uses.is_empty()).then(|| "tool_use".to_string()),
+            input_tokens: None,
+            output_tokens: Some(text.split_whitespace().count() as u32),
+            latency_ms: None,
+        },
+        text,
+        content_blocks,
+        tool_uses,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -333,6 +685,60 @@ mod tests {
         assert!(result.unwrap().is_none()); // Should forward to Claude
     }
 
+    #[test]
+    fn test_confidence_threshold_gates_local_answers() {
+        // Nothing is ever confident enough for a threshold of 1.0
+        let strict = LocalGenerator::new().with_confidence_threshold(1.0);
+        let mut reloaded = strict.reloaded(None);
+        assert_eq!(reloaded.confidence_threshold, 1.0);
+        assert!(reloaded.try_generate_from_pattern("Hello!").unwrap().is_none());
+
+        let mut config = crate::config::BackendConfig::default();
+        assert_eq!(config.local_confidence_threshold, DEFAULT_LOCAL_CONFIDENCE_THRESHOLD);
+        config.local_confidence_threshold = 1.5;
+        config.clamp_local_confidence_threshold();
+        assert_eq!(config.local_confidence_threshold, 1.0);
+        config.local_confidence_threshold = -0.2;
+        config.clamp_local_confidence_threshold();
+        assert_eq!(config.local_confidence_threshold, 0.0);
+    }
+
+    #[test]
+    fn test_warm_up_needs_a_model() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut generator = LocalGenerator::new().with_adapters_dir(dir.path().to_path_buf());
+        assert!(generator.warm_up().is_err());
+        assert!(crate::config::BackendConfig::default().warmup);
+    }
+
+    #[test]
+    fn test_newer_adapter_advances_tracked_mtime() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut generator = LocalGenerator::new().with_adapters_dir(dir.path().to_path_buf());
+        generator.check_and_reload_adapter().unwrap();
+        assert_eq!(generator.adapter_mtime(), None);
+
+        let first = SystemTime::now() - Duration::from_secs(60);
+        let file = std::fs::File::create(dir.path().join("first.safetensors")).unwrap();
+        file.set_modified(first).unwrap();
+        generator.check_and_reload_adapter().unwrap();
+        assert_eq!(generator.adapter_mtime(), Some(first));
+
+        // An in-progress training output doesn't count
+        std::fs::write(dir.path().join("latest.partial-1a2b3c4d.safetensors"), b"").unwrap();
+        generator.check_and_reload_adapter().unwrap();
+        assert_eq!(generator.adapter_mtime(), Some(first));
+
+        let second = first + Duration::from_secs(30);
+        let file = std::fs::File::create(dir.path().join("latest.safetensors")).unwrap();
+        file.set_modified(second).unwrap();
+        generator.check_and_reload_adapter().unwrap();
+        assert_eq!(generator.adapter_mtime(), Some(second));
+
+        // A rebuilt generator re-applies the adapter to its new model
+        assert_eq!(generator.reloaded(None).adapter_mtime(), None);
+    }
+
     #[test]
     fn test_learn_from_claude() {
         let mut generator = LocalGenerator::new();
@@ -348,4 +754,138 @@ mod tests {
         // Learning should not crash
         // (Response may or may not be used for local generation depending on confidence)
     }
+    #[test]
+    fn test_tool_calls_in_local_output() {
+        let output = r#"Let me look at that file.
+
+<tool_use>
+  <name>read</name>
+  <parameters>{"file_path": "src/main.rs"}</parameters>
+</tool_use>"#;
+
+        let (text, tool_uses) = split_tool_calls(output).unwrap();
+        let response = local_response(text, tool_uses, 0.9);
+
+        assert_eq!(response.text, "Let me look at that file.");
+        assert_eq!(response.tool_uses.len(), 1);
+        let tool_use = &response.tool_uses[0];
+        assert_eq!(tool_use.name, "read");
+        assert_eq!(tool_use.input, serde_json::json!({"file_path": "src/main.rs"}));
+        assert_eq!(response.metadata.stop_reason.as_deref(), Some("tool_use"));
+        match &response.content_blocks[..] {
+            [crate::claude::ContentBlock::Text { text }, crate::claude::ContentBlock::ToolUse { id, name, .. }] => {
+                assert_eq!(text, "Let me look at that file.");
+                assert_eq!(id, &tool_use.id);
+                assert_eq!(name, "read");
+            }
+            blocks => panic!("unexpected content blocks: {:?}", blocks),
+        }
+
+        // Plain answers stay a single text block
+        let (text, tool_uses) = split_tool_calls("Just an answer.").unwrap();
+        let response = local_response(text, tool_uses, 0.9);
+        assert!(response.tool_uses.is_empty());
+        assert_eq!(response.content_blocks.len(), 1);
+        assert_eq!(response.metadata.stop_reason, None);
+
+        // A tool call with broken JSON isn't passed on
+        assert!(split_tool_calls("<tool_use><name>read</name><parameters>{oops</parameters></tool_use>").is_err());
+    }
+
+    fn text(role: &str, text: &str) -> Message {
+        Message {
+            role: role.to_string(),
+            content: vec![crate::claude::ContentBlock::Text { text: text.to_string() }],
+        }
+    }
+
+    #[test]
+    fn test_conversation_query() {
+        assert_eq!(conversation_query(&[text("user", "Hi")]).as_deref(), Some("Hi"));
+        assert_eq!(conversation_query(&[text("assistant", "Hello")]), None);
+
+        let messages = [
+            text("system", "Be brief"),
+            text("user", "Read main.rs"),
+            Message {
+                role: "assistant".to_string(),
+                content: vec![crate::claude::ContentBlock::ToolUse {
+                    id: "t1".to_string(),
+                    name: "read".to_string(),
+                    input: serde_json::json!({"file_path": "main.rs"}),
+                }],
+            },
+            Message {
+                role: "user".to_string(),
+                content: vec![crate::claude::ContentBlock::ToolResult {
+                    tool_use_id: "t1".to_string(),
+                    content: "fn main() {}".to_string(),
+                    is_error: None,
+                }],
+            },
+        ];
+        assert_eq!(
+            conversation_query(&messages).unwrap(),
+            "User: Read main.rs\n\n\
+             Assistant: [Called tool 'read' with params: {\"file_path\":\"main.rs\"}]\n\n\
+             User: [Tool Result: fn main() {}]"
+        );
+    }
+
+    #[test]
+    fn test_fit_to_budget_drops_oldest_turns() {
+        let messages: Vec<Message> = std::iter::once(text("system", "sys"))
+            .chain((1..=5).flat_map(|turn| {
+                [text("user", &format!("q{}", turn)), text("assistant", &format!("a{}", turn))]
+            }))
+            .chain(std::iter::once(text("user", "q6")))
+            .collect();
+        // One "token" per message
+        let tokens = |messages: &[Message]| Ok(messages.len());
+
+        // Fits: untouched
+        assert_eq!(fit_to_budget(&messages, 12, tokens).unwrap().len(), 12);
+
+        // System prompt kept, then the most recent turns that fit
+        let fitted = fit_to_budget(&messages, 5, tokens).unwrap();
+        let roles: Vec<&str> = fitted.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, ["system", "user", "assistant", "user"]);
+        assert_eq!(conversation_query(&fitted).unwrap(), "User: q5\n\nAssistant: a5\n\nUser: q6");
+
+        // Nothing fits: the latest turn is still sent
+        assert_eq!(fit_to_budget(&messages, 1, tokens).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_fit_to_budget_keeps_tool_turns_whole() {
+        let tool_use = Message {
+            role: "assistant".to_string(),
+            content: vec![crate::claude::ContentBlock::ToolUse {
+                id: "toolu_1".to_string(),
+                name: "read".to_string(),
+                input: serde_json::json!({ "file_path": "a.rs" }),
+            }],
+        };
+        let tool_result = Message {
+            role: "user".to_string(),
+            content: vec![crate::claude::ContentBlock::tool_result(
+                "toolu_1".to_string(),
+                "fn main() {}".to_string(),
+                None,
+            )],
+        };
+        let messages = vec![
+            text("user", "q1"),
+            text("assistant", "a1"),
+            text("user", "Read a.rs"),
+            tool_use,
+            tool_result,
+        ];
+        let tokens = |messages: &[Message]| Ok(messages.len());
+
+        // The latest turn is the prompt, the call and its result
+        let fitted = fit_to_budget(&messages, 2, tokens).unwrap();
+        assert_eq!(fitted.len(), 3);
+        assert_eq!(fitted[0].text(), "Read a.rs");
+    }
 }
@@ -0,0 +1,224 @@
+// Soft response length limit for local generation
+//
+// Local models sometimes ramble well past a useful answer. Once a response
+// exceeds `max_response_chars`, generation continues only until the next
+// sentence boundary, then stops and appends a truncation marker (closing any
+// open code fence first so the markdown stays well-formed).
+
+/// Marker appended to truncated responses
+pub const TRUNCATION_MARKER: &str = "…[truncated]";
+
+/// How far past the limit to wait for a sentence boundary before cutting anyway
+const MAX_OVERRUN_CHARS: usize = 200;
+
+/// Result of feeding a chunk through the limiter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LimitStep {
+    /// Text to forward to the client (may include the truncation suffix)
+    pub emit: String,
+    /// Generation should stop after this chunk
+    pub stop: bool,
+}
+
+/// Streaming enforcement of a soft character limit
+#[derive(Debug, Clone)]
+pub struct ResponseLimiter {
+    max_chars: usize,
+    text: String,
+    char_count: usize,
+    stopped: bool,
+}
+
+impl ResponseLimiter {
+    pub fn new(max_chars: usize) -> Self {
+        Self {
+            max_chars,
+            text: String::new(),
+            char_count: 0,
+            stopped: false,
+        }
+    }
+
+    /// Whether the limit has been hit
+    pub fn is_truncated(&self) -> bool {
+        self.stopped
+    }
+
+    /// Feed the next generated chunk
+    pub fn push(&mut self, chunk: &str) -> LimitStep {
+        if self.stopped {
+            return LimitStep {
+                emit: String::new(),
+                stop: true,
+            };
+        }
+
+        let start = self.text.len();
+        self.text.push_str(chunk);
+        self.char_count += chunk.chars().count();
+
+        if self.char_count <= self.max_chars {
+            return LimitStep {
+                emit: chunk.to_string(),
+                stop: false,
+            };
+        }
+
+        // Punctuation ending the previous chunk only counts once we see what follows
+        let limit_byte = byte_offset(&self.text, self.max_chars);
+        let prev_char = self.text[..start].char_indices().last().map_or(0, |(i, _)| i);
+        let search_from = limit_byte.max(prev_char);
+
+        let cut = find_sentence_end(&self.text, search_from).or_else(|| {
+            (self.char_count > self.max_chars + MAX_OVERRUN_CHARS).then(|| {
+                hard_cut(
+                    &self.text,
+                    limit_byte,
+                    byte_offset(&self.text, self.max_chars + MAX_OVERRUN_CHARS),
+                )
+            })
+        });
+
+        match cut {
+            None => LimitStep {
+                emit: chunk.to_string(),
+                stop: false,
+            },
+            Some(cut) => {
+                // Already-streamed text can't be taken back
+                self.text.truncate(cut.max(start));
+                let suffix = truncation_suffix(&self.text);
+                let mut emit = self.text[start..].to_string();
+                emit.push_str(&suffix);
+                self.text.push_str(&suffix);
+                self.stopped = true;
+
+                LimitStep { emit, stop: true }
+            }
+        }
+    }
+}
+
+/// Apply the limit to a complete (non-streamed) response
+pub fn truncate_response(text: &str, max_chars: usize) -> String {
+    ResponseLimiter::new(max_chars).push(text).emit
+}
+
+/// Byte offset of the `n`th char (or the end of the string)
+fn byte_offset(text: &str, n: usize) -> usize {
+    text.char_indices().nth(n).map_or(text.len(), |(i, _)| i)
+}
+
+/// First sentence end at or after `from`: after `.`/`!`/`?` followed by
+/// whitespace, or before a newline
+fn find_sentence_end(text: &str, from: usize) -> Option<usize> {
+    let mut chars = text[from..].char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if c == '\n' {
+            return Some(from + i);
+        }
+        if matches!(c, '.' | '!' | '?') {
+            if let Some(&(_, next)) = chars.peek() {
+                if next.is_whitespace() {
+                    return Some(from + i + c.len_utf8());
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Cut at the last whitespace between the limit and the hard cap
+fn hard_cut(text: &str, limit: usize, cap: usize) -> usize {
+    text[limit..cap]
+        .rfind(char::is_whitespace)
+        .map_or(cap, |i| limit + i)
+}
+
+fn has_open_code_fence(text: &str) -> bool {
+    text.lines()
+        .filter(|line| line.trim_start().starts_with("```"))
+        .count()
+        % 2
+        == 1
+}
+
+/// Close an open code fence, then append the truncation marker
+fn truncation_suffix(text: &str) -> String {
+    let mut suffix = String::new();
+    if has_open_code_fence(text) {
+        if !text.ends_with('\n') {
+            suffix.push('\n');
+        }
+        suffix.push_str("```\n");
+    } else if !text.is_empty() && !text.ends_with(char::is_whitespace) {
+        suffix.push(' ');
+    }
+    suffix.push_str(TRUNCATION_MARKER);
+    suffix
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_under_limit_unchanged() {
+        assert_eq!(truncate_response("Short answer.", 100), "Short answer.");
+
+        let mut limiter = ResponseLimiter::new(100);
+        let step = limiter.push("Hello");
+        assert_eq!(step.emit, "Hello");
+        assert!(!step.stop);
+        assert!(!limiter.is_truncated());
+    }
+
+    #[test]
+    fn test_stops_at_sentence_boundary() {
+        let text = "First sentence here. Second sentence runs past the limit. Third.";
+        assert_eq!(
+            truncate_response(text, 25),
+            "First sentence here. Second sentence runs past the limit. …[truncated]"
+        );
+        // Decimal points are not sentence boundaries
+        assert_eq!(
+            truncate_response("Pi is 3.14159 roughly! More text", 6),
+            "Pi is 3.14159 roughly! …[truncated]"
+        );
+    }
+
+    #[test]
+    fn test_streaming_boundary_across_chunks() {
+        let mut limiter = ResponseLimiter::new(10);
+        let mut output = String::new();
+        for chunk in ["The answer", " is", " forty-two", ".", " Also", " more"] {
+            let step = limiter.push(chunk);
+            output.push_str(&step.emit);
+            if step.stop {
+                break;
+            }
+        }
+        assert_eq!(output, "The answer is forty-two. …[truncated]");
+        assert!(limiter.is_truncated());
+        assert!(limiter.push(" ignored").emit.is_empty());
+    }
+
+    #[test]
+    fn test_closes_open_code_fence() {
+        let text = "Example:\n```rust\nfn main() {\n    println!(\"hi\");\n}\n```";
+        let truncated = truncate_response(text, 20);
+        assert_eq!(
+            truncated,
+            "Example:\n```rust\nfn main() {\n```\n…[truncated]"
+        );
+        assert!(!has_open_code_fence(&truncated));
+    }
+
+    #[test]
+    fn test_hard_cut_without_boundary() {
+        let text = "word ".repeat(100);
+        let truncated = truncate_response(&text, 10);
+        assert!(truncated.ends_with(TRUNCATION_MARKER));
+        assert!(truncated.chars().count() <= 10 + MAX_OVERRUN_CHARS + TRUNCATION_MARKER.len());
+    }
+}
@@ -0,0 +1,221 @@
+// Local generation timeout
+//
+// A small model can stall on a hard prompt. With
+// `backend.local_generation_timeout_ms` set, each local generation gets a
+// deadline: the token loop checks it between tokens and stops once it
+// passes, returning `LocalTimeout` so the caller can forward the query to
+// the teacher instead (route trace reason `local_timeout`).
+
+use anyhow::Result;
+use std::time::{Duration, Instant};
+
+use crate::claude::Message;
+use crate::generators::{Generator, GeneratorResponse};
+use crate::tools::types::ToolDefinition;
+
+/// Error returned when local generation runs past its deadline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocalTimeout {
+    pub after: Duration,
+}
+
+impl std::fmt::Display for LocalTimeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Local generation timed out after {}ms", self.after.as_millis())
+    }
+}
+
+impl std::error::Error for LocalTimeout {}
+
+/// Whether an error (or its cause) is a local generation timeout
+pub fn is_local_timeout(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| cause.is::<LocalTimeout>())
+}
+
+/// Point in time after which local generation should stop
+#[derive(Debug, Clone, Copy)]
+pub struct Deadline {
+    started: Instant,
+    timeout: Duration,
+}
+
+impl Deadline {
+    pub fn start(timeout: Duration) -> Self {
+        Self {
+            started: Instant::now(),
+            timeout,
+        }
+    }
+
+    pub fn expired(&self) -> bool {
+        self.started.elapsed() >= self.timeout
+    }
+
+    /// The error to return once the deadline has passed
+    pub fn timeout_error(&self) -> LocalTimeout {
+        LocalTimeout { after: self.timeout }
+    }
+
+    /// `Err(LocalTimeout)` once the deadline has passed
+    pub fn check(&self) -> Result<()> {
+        if self.expired() {
+            return Err(self.timeout_error().into());
+        }
+        Ok(())
+    }
+}
+
+/// Which generator produced a `generate_with_fallback` response
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Answered {
+    Local,
+    /// The local model timed out and the teacher answered
+    TeacherAfterTimeout,
+}
+
+/// Generate with the local model, forwarding to the teacher if it takes
+/// longer than `timeout`
+///
+/// The async timeout covers generators that can't check a deadline
+/// themselves; the local model's own deadline (see `Deadline`) is what stops
+/// token generation and frees the model. Other local errors are returned
+/// as-is.
+pub async fn generate_with_fallback(
+    local: &dyn Generator,
+    teacher: &dyn Generator,
+    messages: Vec<Message>,
+    tools: Option<Vec<ToolDefinition>>,
+    timeout: Duration,
+) -> Result<(GeneratorResponse, Answered)> {
+    let result = match tokio::time::timeout(timeout, local.generate(messages.clone(), tools.clone())).await {
+        Ok(result) => result,
+        Err(_) => Err(LocalTimeout { after: timeout }.into()),
+    };
+
+    match result {
+        Ok(response) => Ok((response, Answered::Local)),
+        Err(e) if is_local_timeout(&e) => {
+            tracing::warn!("{}, forwarding to {}", e, teacher.name());
+            let response = teacher.generate(messages, tools).await?;
+            Ok((response, Answered::TeacherAfterTimeout))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators::{GeneratorCapabilities, ResponseMetadata, StreamChunk};
+    use async_trait::async_trait;
+    use tokio::sync::mpsc;
+
+    struct MockGenerator {
+        name: &'static str,
+        delay: Duration,
+        capabilities: GeneratorCapabilities,
+    }
+
+    impl MockGenerator {
+        fn new(name: &'static str, delay: Duration) -> Self {
+            Self {
+                name,
+                delay,
+                capabilities: GeneratorCapabilities {
+                    supports_streaming: false,
+                    supports_tools: false,
+                    supports_conversation: true,
+                    max_context_messages: None,
+                },
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Generator for MockGenerator {
+        async fn generate(
+            &self,
+            _messages: Vec<Message>,
+            _tools: Option<Vec<ToolDefinition>>,
+        ) -> Result<GeneratorResponse> {
+            tokio::time::sleep(self.delay).await;
+            Ok(GeneratorResponse {
+                text: format!("answer from {}", self.name),
+                content_blocks: vec![],
+                tool_uses: vec![],
+                metadata: ResponseMetadata {
+                    generator: self.name.to_string(),
+                    model: self.name.to_string(),
+                    confidence: None,
+                    stop_reason: None,
+                    input_tokens: None,
+                    output_tokens: None,
+                    latency_ms: None,
+                },
+            })
+        }
+
+        async fn generate_stream(
+            &self,
+            _messages: Vec<Message>,
+            _tools: Option<Vec<ToolDefinition>>,
+        ) -> Result<Option<mpsc::Receiver<Result<StreamChunk>>>> {
+            Ok(None)
+        }
+
+        fn capabilities(&self) -> &GeneratorCapabilities {
+            &self.capabilities
+        }
+
+        fn name(&self) -> &str {
+            self.name
+        }
+    }
+
+    #[tokio::test]
+    async fn test_slow_local_falls_back_to_teacher() {
+        let local = MockGenerator::new("local", Duration::from_secs(5));
+        let teacher = MockGenerator::new("teacher", Duration::ZERO);
+
+        let started = Instant::now();
+        let (response, answered) = generate_with_fallback(
+            &local,
+            &teacher,
+            vec![Message::user("hard question")],
+            None,
+            Duration::from_millis(50),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(answered, Answered::TeacherAfterTimeout);
+        assert_eq!(response.text, "answer from teacher");
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_fast_local_answers() {
+        let local = MockGenerator::new("local", Duration::ZERO);
+        let teacher = MockGenerator::new("teacher", Duration::ZERO);
+
+        let (response, answered) =
+            generate_with_fallback(&local, &teacher, vec![Message::user("hi")], None, Duration::from_secs(5))
+                .await
+                .unwrap();
+
+        assert_eq!(answered, Answered::Local);
+        assert_eq!(response.text, "answer from local");
+    }
+
+    #[test]
+    fn test_deadline() {
+        let deadline = Deadline::start(Duration::ZERO);
+        assert!(deadline.expired());
+        let error = deadline.check().unwrap_err();
+        assert!(is_local_timeout(&error));
+        assert!(is_local_timeout(&error.context("while generating")));
+
+        assert!(Deadline::start(Duration::from_secs(60)).check().is_ok());
+        assert!(!is_local_timeout(&anyhow::anyhow!("other failure")));
+    }
+}
@@ -8,13 +8,14 @@ use std::path::PathBuf;
 use std::sync::Arc;
 
 use shammah::claude::ClaudeClient;
+use shammah::cli::color::paint;
 use shammah::cli::output_layer::OutputManagerLayer;
 use shammah::cli::{ConversationHistory, Repl};
 use shammah::config::{load_config, Config};
 use shammah::metrics::MetricsLogger;
 use shammah::models::ThresholdRouter;
 use shammah::providers::create_provider;
-use shammah::router::Router;
+use shammah::router::{CrisisDetector, Router};
 use tracing_subscriber::prelude::*;
 
 #[derive(Parser, Debug)]
@@ -44,6 +45,40 @@ struct Args {
     /// Direct mode - talk directly to teacher API, bypass daemon
     #[arg(long = "direct")]
     direct: bool,
+
+    /// Speak each complete response with the configured TTS command
+    #[arg(long = "tts")]
+    tts: bool,
+
+    /// Hold queries until the local model has loaded instead of forwarding them
+    #[arg(long = "wait-for-local")]
+    wait_for_local: bool,
+
+    /// Time routing, generation, tools and rendering; print a waterfall after each response
+    #[arg(long = "profile")]
+    profile: bool,
+
+    /// Don't load .env files (./.env, ~/.shammah/.env); use the process environment only
+    #[arg(long = "no-dotenv", global = true)]
+    no_dotenv: bool,
+
+    /// With piped input: print the answer as JSON (response, route, latency_ms, model)
+    #[arg(long = "json")]
+    json: bool,
+
+    /// Teacher model for this run, overriding the configured one
+    #[arg(long = "model")]
+    model: Option<String>,
+
+    /// Don't send tool definitions to the teacher (toggle in the REPL with /tools on|off)
+    ///
+    /// `shammah query` never offers tools, so it accepts this flag as a no-op.
+    #[arg(long = "no-tools", global = true)]
+    no_tools: bool,
+
+    /// Plain output without ANSI colors (also set by NO_COLOR, or when stdout isn't a terminal)
+    #[arg(long = "no-color", global = true)]
+    no_color: bool,
 }
 
 #[derive(Parser, Debug)]
@@ -75,6 +110,48 @@ enum Command {
     Query {
         /// Query text
         query: String,
+        /// Print the answer as JSON (response, route, latency_ms, model)
+        #[arg(long)]
+        json: bool,
+        /// Teacher model for this query, overriding the configured one
+        #[arg(long)]
+        model: Option<String>,
+    },
+    /// Re-run the user turns of a saved session through the local model
+    Replay {
+        /// Saved session file (e.g. ~/.shammah/sessions/autosave.json)
+        #[arg(long)]
+        session: PathBuf,
+        /// Score new answers against the recorded ones and flag regressions
+        #[arg(long)]
+        compare: bool,
+        /// Similarity below which a turn is flagged as diverged
+        #[arg(long, default_value_t = shammah::cli::replay::DEFAULT_DIVERGENCE_THRESHOLD)]
+        threshold: f64,
+        /// Also write the report as JSON to this file
+        #[arg(long)]
+        report: Option<PathBuf>,
+    },
+    /// Check the audit log's hash chain for edited or missing entries
+    AuditVerify {
+        /// Audit log to check (default: audit.path from config, or ~/.shammah/audit.jsonl)
+        #[arg(long)]
+        path: Option<PathBuf>,
+    },
+    /// Feedback log commands
+    Feedback {
+        #[command(subcommand)]
+        feedback_command: FeedbackCommand,
+    },
+    /// Downloaded model commands
+    Models {
+        #[command(subcommand)]
+        models_command: ModelsCommand,
+    },
+    /// Config file commands
+    Config {
+        #[command(subcommand)]
+        config_command: ConfigCommand,
     },
 }
 
@@ -84,13 +161,55 @@ enum TrainCommand {
     Setup,
 }
 
+#[derive(Parser, Debug)]
+enum FeedbackCommand {
+    /// Export ~/.shammah/feedback.jsonl as JSONL or CSV
+    Export {
+        /// Output format: jsonl or csv
+        #[arg(long, default_value = "jsonl")]
+        format: shammah::feedback::export::ExportFormat,
+        /// Only entries at or after this Unix timestamp
+        #[arg(long)]
+        since: Option<u64>,
+        /// Only entries with this rating: good or bad
+        #[arg(long)]
+        rating: Option<shammah::feedback::FeedbackRating>,
+        /// Write to this file instead of stdout
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Summarize ratings (good/bad counts, entries per day)
+    Stats,
+}
+
+#[derive(Parser, Debug)]
+enum ModelsCommand {
+    /// List models in ~/.shammah/models and adapters in ~/.shammah/adapters
+    List,
+}
+
+#[derive(Parser, Debug)]
+enum ConfigCommand {
+    /// Check the config file, listing every problem found (exits nonzero if any)
+    Validate,
+    /// Print the config file path
+    Path,
+}
+
 /// Create a ClaudeClient with the configured provider
 ///
 /// This function creates a provider based on the teacher configuration
 /// and wraps it in a ClaudeClient for backwards compatibility.
-fn create_claude_client_with_provider(config: &Config) -> Result<ClaudeClient> {
-    let provider = create_provider(&config.teachers)?;
-    Ok(ClaudeClient::with_provider(provider))
+fn create_claude_client_with_provider(config: &Config, model: Option<&str>) -> Result<ClaudeClient> {
+    let provider = create_provider(&config.teachers, config.default_max_tokens)?;
+    let mut client = ClaudeClient::with_provider(provider);
+    if let Some(model) = model {
+        client = client.with_model(model);
+    }
+    Ok(match config.constitution.load() {
+        Some(constitution) => client.with_system_prompt(constitution),
+        None => client,
+    })
 }
 
 #[tokio::main]
@@ -106,6 +225,22 @@ async fn main() -> Result<()> {
     // Parse command-line arguments
     let args = Args::parse();
 
+    // Propagate --no-dotenv via the environment so an auto-spawned daemon honors it
+    if args.no_dotenv {
+        std::env::set_var(shammah::config::NO_DOTENV_VAR, "1");
+    }
+
+    shammah::cli::color::init(args.no_color);
+
+    // Errors carry ANSI styling; print them ourselves so it can be stripped
+    if let Err(e) = run(args).await {
+        eprintln!("Error: {}", shammah::cli::color::for_terminal(format!("{:?}", e)));
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+async fn run(args: Args) -> Result<()> {
     // Dispatch based on command
     match args.command {
         Some(Command::Setup) => {
@@ -126,8 +261,28 @@ async fn main() -> Result<()> {
         Some(Command::Train { train_command }) => {
             return run_train_command(train_command).await;
         }
-        Some(Command::Query { query }) => {
-            return run_query(&query).await;
+        Some(Command::Query { query, json, model }) => {
+            return run_query(&query, json, model.as_deref()).await;
+        }
+        Some(Command::Replay {
+            session,
+            compare,
+            threshold,
+            report,
+        }) => {
+            return run_replay(&session, compare, threshold, report.as_deref()).await;
+        }
+        Some(Command::AuditVerify { path }) => {
+            return run_audit_verify(path);
+        }
+        Some(Command::Feedback { feedback_command }) => {
+            return run_feedback_command(feedback_command);
+        }
+        Some(Command::Models { models_command }) => {
+            return run_models_command(models_command);
+        }
+        Some(Command::Config { config_command }) => {
+            return run_config_command(config_command);
         }
         None => {
             // Fall through to REPL mode (check for piped input first)
@@ -146,7 +301,7 @@ async fn main() -> Result<()> {
         }
 
         // Run query via daemon
-        return run_query(input.trim()).await;
+        return run_query(input.trim(), args.json, args.model.as_deref()).await;
     }
 
     // CRITICAL: Create and configure OutputManager BEFORE initializing tracing
@@ -183,8 +338,8 @@ async fn main() -> Result<()> {
     let mut config = match load_config() {
         Ok(cfg) => cfg,
         Err(e) => {
-            eprintln!("{}", e);
-            eprintln!("\n\x1b[1;33m⚠️  Running first-time setup wizard...\x1b[0m\n");
+            eprintln!("{}", shammah::cli::color::for_terminal(e.to_string()));
+            eprintln!("\n{}\n", paint("1;33", "⚠️  Running first-time setup wizard..."));
 
             // Run setup wizard
             use shammah::cli::show_setup_wizard;
@@ -216,16 +371,57 @@ async fn main() -> Result<()> {
                 debug_logging: result.debug_logging,
                 #[cfg(target_os = "macos")]
                 gui_automation: false, // Not yet implemented in wizard
+                ascii_only: None,
+                startup_verbosity: Default::default(),
+                spinner_style: Default::default(),
+                tts_enabled: false,
+                tts_command: None,
+                wait_for_local: false,
+                local_wait_timeout_secs: 120,
+                idle_exit_minutes: 0,
+                max_output_width: None,
+                output_align: Default::default(),
+                max_concurrent_tools: shammah::tools::executor::DEFAULT_MAX_CONCURRENT_TOOLS,
+                show_thinking: true,
+                code_line_numbers: false,
+                code_copy_hints: true,
+                render_markdown: true,
             };
             // Update deprecated streaming_enabled field for backward compat
             new_config.streaming_enabled = new_config.features.streaming_enabled;
             new_config.save()?;
 
-            eprintln!("\n\x1b[1;32m✓ Configuration saved!\x1b[0m\n");
+            eprintln!("\n{}\n", paint("1;32", "✓ Configuration saved!"));
             new_config
         }
     };
 
+    // Swap Unicode glyphs for ASCII on terminals that can't render them
+    shammah::cli::glyphs::init(config.features.ascii_only);
+    shammah::cli::spinner::init(config.features.spinner_style);
+    shammah::cli::thinking::init(config.features.show_thinking);
+    shammah::cli::code_blocks::init(
+        config.features.code_line_numbers,
+        config.features.code_copy_hints,
+        config.features.render_markdown,
+    );
+    shammah::cli::history::init(config.history.clone());
+    shammah::cli::idle::init(config.features.idle_exit_minutes);
+    shammah::cli::tts::init(
+        args.tts || config.features.tts_enabled,
+        config.features.tts_command.clone(),
+    );
+    shammah::embeddings::init(&config.embeddings);
+    shammah::cli::profile::init(args.profile);
+    shammah::metrics::audit::init(&config.audit);
+    shammah::cli::global_output::set_startup_verbosity(config.features.startup_verbosity);
+    use shammah::config::StartupVerbosity;
+    let startup_verbosity = config.features.startup_verbosity;
+
+    if args.wait_for_local {
+        config.features.wait_for_local = true;
+    }
+
     // Override TUI setting if --raw or --no-tui flag is provided
     if args.raw_mode || args.no_tui {
         config.tui_enabled = false;
@@ -271,10 +467,13 @@ async fn main() -> Result<()> {
     };
 
     // Create router
-    let router = Router::new(threshold_router);
+    let router = Router::new(threshold_router).with_crisis_detector(CrisisDetector::load_or_default());
 
     // Create Claude client
-    let claude_client = create_claude_client_with_provider(&config)?;
+    let claude_client = create_claude_client_with_provider(&config, args.model.as_deref())?;
+    if let Some(model) = &args.model {
+        output_manager.write_status(format!("✓ Teacher model: {} (--model)", model));
+    }
 
     // Create metrics logger
     let metrics_logger = MetricsLogger::new(config.metrics_dir.clone())?;
@@ -290,8 +489,10 @@ async fn main() -> Result<()> {
         };
         match DaemonClient::connect(daemon_config).await {
             Ok(client) => {
-                output_manager.write_status("✓ Connected to daemon");
-                Some(Arc::new(client))
+                if startup_verbosity >= StartupVerbosity::Verbose {
+                    output_manager.write_status("✓ Connected to daemon");
+                }
+                Some(Arc::new(client.with_teacher_model(args.model.clone())))
             }
             Err(e) => {
                 if std::env::var("SHAMMAH_DEBUG").is_ok() {
@@ -301,15 +502,37 @@ async fn main() -> Result<()> {
             }
         }
     } else {
-        if args.direct && io::stdout().is_terminal() {
+        if args.direct
+            && io::stdout().is_terminal()
+            && startup_verbosity >= StartupVerbosity::Normal
+        {
             output_manager.write_status("⚠️  Direct mode - bypassing daemon, using teacher API");
         }
         None
     };
 
+    let backend_desc = if daemon_client.is_some() {
+        "daemon"
+    } else {
+        "teacher API"
+    };
+
     // Create and run REPL (with full TUI support)
     // Pass daemon_client so Repl knows whether to suppress local model logs
     let mut repl = Repl::new(config, claude_client, router, metrics_logger, daemon_client).await;
+    if args.no_tools {
+        repl.set_tools_enabled(false);
+    }
+
+    // Check for an autosave left behind by a crashed session (before the
+    // normal session below is restored and removed)
+    use shammah::cli::autosave::{self, ConversationAutosave, AUTOSAVE_DEBOUNCE};
+    let autosave = ConversationAutosave::default_path()
+        .map(|path| Arc::new(ConversationAutosave::new(path, AUTOSAVE_DEBOUNCE)));
+    let restorable_autosave = autosave
+        .as_ref()
+        .filter(|autosave| autosave.is_restorable(args.restore_session.as_deref()))
+        .cloned();
 
     // Restore session if requested
     if let Some(session_path) = args.restore_session {
@@ -331,6 +554,39 @@ async fn main() -> Result<()> {
         }
     }
 
+    // Offer to restore an autosave newer than the normal session
+    if let Some(saved) = restorable_autosave {
+        use shammah::cli::menu::Menu;
+        let restore = Menu::confirm(
+            "The previous session exited unexpectedly. Restore its conversation?",
+            true,
+        )
+        .unwrap_or(false);
+
+        if restore {
+            match saved.load() {
+                Ok(history) => {
+                    repl.restore_conversation(history);
+                    output_manager.write_status("✓ Restored conversation from autosave");
+                }
+                Err(e) => {
+                    output_manager.write_status(format!("⚠️  Failed to restore autosave: {}", e));
+                }
+            }
+        }
+    }
+
+    if let Some(autosave) = autosave.clone() {
+        repl.enable_autosave(Arc::clone(&autosave)).await;
+        autosave::install_global_autosave(autosave);
+    }
+
+    // Single ready line (the only startup output with startup_verbosity = "quiet")
+    output_manager.write_status(format!(
+        "✓ Shammah ready (using {}) - type /help for commands",
+        backend_desc
+    ));
+
     // Run REPL (with full TUI event loop)
     if std::env::var("SHAMMAH_DEBUG").is_ok() {
         eprintln!("[DEBUG] Starting REPL with full TUI...");
@@ -339,6 +595,13 @@ async fn main() -> Result<()> {
     // Use event loop mode (has all TUI features)
     repl.run_event_loop(args.initial_prompt).await?;
 
+    // Clean exit: nothing to recover next time
+    if let Some(autosave) = autosave {
+        if let Err(e) = autosave.clear() {
+            tracing::warn!("{}", e);
+        }
+    }
+
     if std::env::var("SHAMMAH_DEBUG").is_ok() {
         eprintln!("[DEBUG] REPL exited, returning from main");
     }
@@ -361,6 +624,11 @@ fn install_panic_handler() {
             terminal::Clear(terminal::ClearType::FromCursorDown)
         );
 
+        // Persist any debounced conversation changes before dying
+        if let Some(autosave) = shammah::cli::autosave::global_autosave() {
+            autosave.try_flush();
+        }
+
         // Call the default panic handler
         default_panic(info);
     }));
@@ -404,7 +672,7 @@ fn init_tracing() {
 /// Run HTTP daemon server
 /// Start the daemon in background
 async fn run_daemon_start(bind_address: String) -> Result<()> {
-    use shammah::daemon::{DaemonLifecycle, ensure_daemon_running};
+    use shammah::daemon::{DaemonLifecycle, DaemonReadiness, ensure_daemon_running};
 
     let lifecycle = DaemonLifecycle::new()?;
 
@@ -421,7 +689,7 @@ async fn run_daemon_start(bind_address: String) -> Result<()> {
     println!("Logs: ~/.shammah/daemon.log");
 
     // Use ensure_daemon_running to spawn and wait for health check
-    ensure_daemon_running(Some(&bind_address)).await?;
+    ensure_daemon_running(Some(&bind_address), DaemonReadiness::Alive).await?;
 
     // Get PID for display
     let pid = lifecycle.read_pid()?;
@@ -455,31 +723,38 @@ fn run_daemon_stop() -> Result<()> {
 
 /// Show daemon status
 async fn run_daemon_status() -> Result<()> {
-    use shammah::daemon::DaemonLifecycle;
+    use shammah::client::DaemonTransport;
+    use shammah::daemon::{BindAddress, DaemonLifecycle};
 
     let lifecycle = DaemonLifecycle::new()?;
 
     // Check if daemon is running
     if !lifecycle.is_running() {
-        println!("\x1b[1;33m⚠ Daemon is not running\x1b[0m");
+        println!("{}", paint("1;33", "⚠ Daemon is not running"));
         println!("\nStart the daemon with:");
-        println!("  \x1b[1;36mshammah daemon-start\x1b[0m");
+        println!("  {}", paint("1;36", "shammah daemon-start"));
         return Ok(());
     }
 
     // Get PID
     let pid = lifecycle.read_pid()?;
 
-    // Query health endpoint
-    let client = reqwest::Client::new();
-    let daemon_url = format!("http://127.0.0.1:11435/health");
+    // Where the daemon says it's listening, else where clients would connect
+    let configured = BindAddress::parse(&load_config()?.client.daemon_address)?;
+    let address = match lifecycle.read_bind_address()? {
+        Some(persisted) => BindAddress::parse(&persisted).with_context(|| {
+            format!("Invalid address in {}", lifecycle.addr_file().display())
+        })?,
+        None => configured.clone(),
+    };
 
-    let response = client
-        .get(&daemon_url)
-        .timeout(std::time::Duration::from_secs(5))
-        .send()
+    // Query health endpoint
+    let timeout = std::time::Duration::from_secs(5);
+    let transport = DaemonTransport::new(address.clone(), timeout)?;
+    let response = transport
+        .get("/health", Some(timeout))
         .await
-        .context("Failed to connect to daemon")?;
+        .with_context(|| format!("Failed to connect to daemon at {}", address))?;
 
     if !response.status().is_success() {
         anyhow::bail!("Daemon returned error status: {}", response.status());
@@ -491,6 +766,28 @@ async fn run_daemon_status() -> Result<()> {
         status: String,
         uptime_seconds: u64,
         active_sessions: usize,
+        #[serde(default)]
+        training_state: Option<String>,
+        #[serde(default)]
+        teacher_circuit: Option<TeacherCircuit>,
+        #[serde(default)]
+        in_flight: Option<InFlight>,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct InFlight {
+        local: usize,
+        local_limit: usize,
+        teacher: usize,
+        teacher_limit: usize,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct TeacherCircuit {
+        state: String,
+        consecutive_failures: usize,
+        #[serde(default)]
+        retry_in_seconds: Option<u64>,
     }
 
     let health: HealthStatus = response
@@ -499,15 +796,54 @@ async fn run_daemon_status() -> Result<()> {
         .context("Failed to parse health response")?;
 
     // Display status
-    println!("\x1b[1;32m✓ Daemon Status\x1b[0m");
+    println!("{}", paint("1;32", "✓ Daemon Status"));
     println!();
-    println!("  Status:          \x1b[1;32m{}\x1b[0m", health.status);
+    println!("  Status:          {}", paint("1;32", &health.status));
     println!("  PID:             {}", pid);
     println!("  Uptime:          {}s", health.uptime_seconds);
     println!("  Active Sessions: {}", health.active_sessions);
-    println!("  Bind Address:    127.0.0.1:11435");
+    if let Some(training_state) = &health.training_state {
+        println!("  Training:        {}", training_state);
+    }
+    if let Some(circuit) = &health.teacher_circuit {
+        match circuit.retry_in_seconds {
+            Some(retry_in) => println!(
+                "  Teacher:         {} ({} failures, retrying in {}s)",
+                paint("1;31", format!("circuit {}", circuit.state)),
+                circuit.consecutive_failures,
+                retry_in
+            ),
+            None => println!("  Teacher:         circuit {}", circuit.state),
+        }
+    }
+    if let Some(in_flight) = &health.in_flight {
+        println!(
+            "  In Flight:       local {}/{}, teacher {}/{}",
+            in_flight.local, in_flight.local_limit, in_flight.teacher, in_flight.teacher_limit
+        );
+    }
+    // Older daemons have no /ready
+    if let Ok(response) = transport.get("/ready", Some(timeout)).await {
+        if let Ok(readiness) = response.json::<shammah::server::ReadinessStatus>().await {
+            println!("  Local Model:     {}", readiness.message);
+        }
+    }
+    println!("  Bind Address:    {}", address);
     println!();
 
+    if address.to_string() != configured.to_string() {
+        anyhow::bail!(
+            "The daemon is listening on {} but client.daemon_address in config.toml is {}, \
+             so `shammah` won't reach it.\n\
+             Set client.daemon_address = \"{}\", or restart the daemon with \
+             `shammah daemon-stop && shammah daemon-start --bind {}`.",
+            address,
+            configured,
+            address,
+            configured
+        );
+    }
+
     Ok(())
 }
 
@@ -523,7 +859,7 @@ async fn run_train_setup() -> Result<()> {
     use std::path::PathBuf;
     use std::process::Command;
 
-    println!("\x1b[1;36m🔧 Setting up Python environment for LoRA training\x1b[0m\n");
+    println!("{}\n", paint("1;36", "🔧 Setting up Python environment for LoRA training"));
 
     // Determine paths
     let home = dirs::home_dir()
@@ -616,10 +952,10 @@ async fn run_train_setup() -> Result<()> {
     }
 
     // Success message
-    println!("\n\x1b[1;32m✅ Setup complete!\x1b[0m\n");
-    println!("Python environment ready at: \x1b[1m{}\x1b[0m", venv_dir.display());
+    println!("\n{}\n", paint("1;32", "✅ Setup complete!"));
+    println!("Python environment ready at: {}", paint("1", venv_dir.display()));
     println!("\nTo use the training scripts:");
-    println!("  \x1b[1;36m~/.shammah/venv/bin/python scripts/train_lora.py\x1b[0m");
+    println!("  {}", paint("1;36", "~/.shammah/venv/bin/python scripts/train_lora.py"));
     println!("\nTraining will run automatically when you provide feedback.");
 
     Ok(())
@@ -636,7 +972,9 @@ async fn run_daemon(bind_address: String) -> Result<()> {
 
     // Check if debug logging is enabled in config (before setting up tracing)
     // This allows the debug_logging feature flag to control log verbosity
+    let mut log_format = shammah::config::LogFormat::default();
     if let Ok(temp_config) = load_config() {
+        log_format = temp_config.server.log_format;
         if temp_config.features.debug_logging {
             // Set RUST_LOG to debug if not already set by user
             if std::env::var("RUST_LOG").is_err() {
@@ -657,12 +995,35 @@ async fn run_daemon(bind_address: String) -> Result<()> {
         .open(&log_path)
         .with_context(|| format!("Failed to open daemon log: {}", log_path.display()))?;
 
-    // Create a file logger layer
-    use tracing_subscriber::fmt::writer::MakeWriter;
+    // Create a file logger layer (secrets are redacted before hitting disk)
+    use shammah::config::LogFormat;
+    use shammah::daemon::RedactingWriter;
     let file_writer = Arc::new(log_file);
-    let file_layer = tracing_subscriber::fmt::layer()
-        .with_writer(move || file_writer.clone())
-        .with_ansi(false);  // No ANSI colors in log file
+    let make_writer = move || RedactingWriter::new(file_writer.clone());
+
+    // Only one of these is Some, depending on [server] log_format
+    let (text_layer, json_layer) = match log_format {
+        LogFormat::Text => (
+            Some(
+                tracing_subscriber::fmt::layer()
+                    .with_writer(make_writer)
+                    .with_ansi(false), // No ANSI colors in log file
+            ),
+            None,
+        ),
+        LogFormat::Json => (
+            None,
+            Some(
+                tracing_subscriber::fmt::layer()
+                    .json()
+                    // Event fields (session_id, error, ...) as top-level keys
+                    .flatten_event(true)
+                    .with_current_span(true)
+                    .with_span_list(true)
+                    .with_writer(make_writer),
+            ),
+        ),
+    };
 
     // Add file layer to tracing
     use tracing_subscriber::prelude::*;
@@ -671,7 +1032,8 @@ async fn run_daemon(bind_address: String) -> Result<()> {
 
     tracing_subscriber::registry()
         .with(env_filter)
-        .with(file_layer)
+        .with(text_layer)
+        .with(json_layer)
         .init();
 
     eprintln!("Daemon logs: {}", log_path.display());
@@ -693,8 +1055,9 @@ async fn run_daemon(bind_address: String) -> Result<()> {
         anyhow::bail!(shammah::errors::daemon_already_running_error(existing_pid));
     }
 
-    // Write PID file
+    // Write PID file (and where we're listening, for daemon-status)
     lifecycle.write_pid()?;
+    lifecycle.write_bind_address(&bind_address)?;
     tracing::info!(pid = std::process::id(), "Daemon PID file written");
 
     // Load configuration
@@ -729,10 +1092,10 @@ async fn run_daemon(bind_address: String) -> Result<()> {
     };
 
     // Create router
-    let router = Router::new(threshold_router);
+    let router = Router::new(threshold_router).with_crisis_detector(CrisisDetector::load_or_default());
 
     // Create Claude client
-    let claude_client = create_claude_client_with_provider(&config)?;
+    let claude_client = create_claude_client_with_provider(&config, None)?;
 
     // Create metrics logger
     let metrics_logger = MetricsLogger::new(config.metrics_dir.clone())?;
@@ -740,7 +1103,9 @@ async fn run_daemon(bind_address: String) -> Result<()> {
     // Initialize BootstrapLoader for progressive Qwen model loading
     output_progress!("⏳ Initializing Qwen model (background)...");
     let generator_state = Arc::new(RwLock::new(GeneratorState::Initializing));
-    let bootstrap_loader = Arc::new(BootstrapLoader::new(Arc::clone(&generator_state), None));
+    let bootstrap_loader = Arc::new(
+        BootstrapLoader::new(Arc::clone(&generator_state), None).with_hub(config.backend.hub()),
+    );
 
     // Start background model loading (unless backend is disabled for proxy-only mode)
     if config.backend.enabled {
@@ -773,13 +1138,20 @@ async fn run_daemon(bind_address: String) -> Result<()> {
     }
 
     // Create local generator (will receive model when ready)
-    let local_generator = Arc::new(RwLock::new(LocalGenerator::new()));
+    let local_generator = Arc::new(RwLock::new(
+        LocalGenerator::new()
+            .with_max_response_chars(config.backend.max_response_chars)
+            .with_generation_timeout(config.backend.local_generation_timeout())
+            .with_context_length(config.backend.context_length)
+            .with_confidence_threshold(config.backend.local_confidence_threshold),
+    ));
 
     // Monitor generator state and inject model when ready
     let gen_clone = Arc::clone(&local_generator);
     let state_monitor = Arc::clone(&generator_state);
+    let warmup = config.backend.warmup;
     tokio::spawn(async move {
-        loop {
+        let ready = loop {
             tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
 
             let state = state_monitor.read().await;
@@ -787,17 +1159,30 @@ async fn run_daemon(bind_address: String) -> Result<()> {
                 // Inject Qwen model into LocalGenerator
                 // Note: tokenizer is now embedded in GeneratorModel backend
                 let mut gen = gen_clone.write().await;
-                *gen = LocalGenerator::with_models(
+                *gen = gen.reloaded(
                     Some(Arc::clone(model)), // Tokenizer is embedded in GeneratorModel
                 );
 
                 output_status!("✓ Qwen model ready - local generation enabled");
-                break; // Stop monitoring once injected
+                break true; // Stop monitoring once injected
             } else if matches!(
                 *state,
                 GeneratorState::Failed { .. } | GeneratorState::NotAvailable
             ) {
-                break; // Stop monitoring on failure
+                break false; // Stop monitoring on failure
+            }
+        };
+
+        // Warm up the model so the first query isn't slow. The model works
+        // either way, so a failure here leaves the state Ready.
+        if ready && warmup {
+            let result = tokio::task::spawn_blocking(move || gen_clone.blocking_write().warm_up()).await;
+            match result {
+                Ok(Ok(elapsed)) => {
+                    tracing::info!(latency_ms = elapsed.as_millis() as u64, "Local model warmed up");
+                }
+                Ok(Err(e)) => tracing::warn!("Local model warmup failed: {:#}", e),
+                Err(e) => tracing::warn!("Local model warmup task failed: {}", e),
             }
         }
     });
@@ -818,6 +1203,9 @@ async fn run_daemon(bind_address: String) -> Result<()> {
         session_timeout_minutes: config.server.session_timeout_minutes,
         auth_enabled: config.server.auth_enabled,
         api_keys: config.server.api_keys.clone(),
+        max_concurrent_inference: config.server.max_concurrent_inference,
+        max_concurrent_teacher: config.server.max_concurrent_teacher,
+        queue_timeout: std::time::Duration::from_secs(config.server.queue_timeout_secs),
     };
 
     // Create and start agent server (with LocalGenerator support)
@@ -833,16 +1221,20 @@ async fn run_daemon(bind_address: String) -> Result<()> {
         training_coordinator,
     )?;
 
-    // Set up graceful shutdown handling
-    let server_handle = tokio::spawn(async move {
-        server.serve().await
-    });
+    // Set up graceful shutdown handling. Server logs (including request
+    // spans) sit under a span carrying the pid and bind address.
+    use tracing::Instrument;
+    let daemon_span = tracing::info_span!("daemon", pid = std::process::id(), bind = %bind_address);
+    let server_handle = tokio::spawn(server.serve().instrument(daemon_span));
 
     // Wait for shutdown signal (Ctrl+C or SIGTERM)
     tokio::select! {
         _ = tokio::signal::ctrl_c() => {
             tracing::info!("Received SIGINT, shutting down gracefully");
         }
+        _ = wait_for_sigterm() => {
+            tracing::info!("Received SIGTERM, shutting down gracefully");
+        }
         result = server_handle => {
             match result {
                 Ok(Ok(())) => {
@@ -865,48 +1257,312 @@ async fn run_daemon(bind_address: String) -> Result<()> {
     Ok(())
 }
 
-/// Run a single query
+/// Resolve when the process receives SIGTERM (systemd stop, `daemon-stop`)
+#[cfg(unix)]
+async fn wait_for_sigterm() {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    match signal(SignalKind::terminate()) {
+        Ok(mut sigterm) => {
+            sigterm.recv().await;
+        }
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to install SIGTERM handler");
+            std::future::pending::<()>().await;
+        }
+    }
+}
+
+/// No SIGTERM outside unix; only Ctrl+C stops the daemon
+#[cfg(not(unix))]
+async fn wait_for_sigterm() {
+    std::future::pending::<()>().await;
+}
+
 /// Run a single query (daemon-only mode)
-async fn run_query(query: &str) -> Result<()> {
+///
+/// With `json`, prints one JSON object instead of the plain answer. `model`
+/// overrides the teacher model (for the daemon's teacher calls too).
+async fn run_query(query: &str, json: bool, model: Option<&str>) -> Result<()> {
     use shammah::client::DaemonClient;
-    use shammah::daemon::ensure_daemon_running;
+    use shammah::daemon::{ensure_daemon_running, DaemonReadiness};
 
     // Load configuration
     let config = load_config()?;
+    if let Some(model) = model {
+        eprintln!("Teacher model: {} (--model)", model);
+    }
 
     // Ensure daemon is running (auto-spawn if needed)
-    if let Err(e) = ensure_daemon_running(Some(&config.client.daemon_address)).await {
+    // Queries are forwarded while the model loads, so the process being up is enough
+    if let Err(e) =
+        ensure_daemon_running(Some(&config.client.daemon_address), DaemonReadiness::Alive).await
+    {
         eprintln!("⚠️  Daemon failed to start: {}", e);
         eprintln!("   Using teacher API directly (no local model)");
-        return run_query_teacher_only(query, &config).await;
+        return run_query_teacher_only(query, &config, json, model).await;
     }
 
     // Create daemon client
     let daemon_config = shammah::client::DaemonConfig::from_client_config(&config.client);
-    let client = DaemonClient::connect(daemon_config).await?;
+    let client = DaemonClient::connect(daemon_config)
+        .await?
+        .with_teacher_model(model.map(str::to_string));
 
     // Send query to daemon
-    let response = client.query_text(query).await?;
-    println!("{}", response);
+    if json {
+        let result = client.query_json(query).await?;
+        println!("{}", serde_json::to_string(&result)?);
+    } else if config.features.streaming_enabled {
+        // Print the answer as it is generated
+        use shammah::generators::StreamChunk;
+        use std::io::Write;
+
+        let mut rx = client.query_text_stream(query).await?;
+        let mut stdout = std::io::stdout();
+        while let Some(chunk) = rx.recv().await {
+            if let StreamChunk::TextDelta(text) = chunk? {
+                print!("{}", text);
+                stdout.flush()?;
+            }
+        }
+        println!();
+    } else {
+        let response = client.query_text(query).await?;
+        println!("{}", response);
+    }
+
+    Ok(())
+}
+
+/// Replay a saved session through the daemon's local model
+async fn run_replay(
+    session: &std::path::Path,
+    compare: bool,
+    threshold: f64,
+    report_path: Option<&std::path::Path>,
+) -> Result<()> {
+    use shammah::cli::replay::{recorded_turns, replay_turns};
+    use shammah::client::DaemonClient;
+    use shammah::daemon::{ensure_daemon_running, DaemonReadiness};
+
+    let history = ConversationHistory::load(session)
+        .with_context(|| format!("Failed to load session {}", session.display()))?;
+    let turns = recorded_turns(&history);
+    if turns.is_empty() {
+        anyhow::bail!("Session {} has no answered user turns to replay", session.display());
+    }
+
+    let config = load_config()?;
+    ensure_daemon_running(Some(&config.client.daemon_address), DaemonReadiness::LocalModel)
+        .await
+        .context("Replay needs the daemon's local model")?;
+    let daemon_config = shammah::client::DaemonConfig::from_client_config(&config.client);
+    let client = DaemonClient::connect(daemon_config).await?;
+
+    eprintln!("Replaying {} turn(s) through the local model...", turns.len());
+    let client = &client;
+    let report = replay_turns(&turns, threshold, |prompt| async move {
+        client.query_local_only(&prompt).await
+    })
+    .await;
+
+    if compare {
+        println!("{}", report.format());
+    } else {
+        for turn in &report.turns {
+            println!("### Turn {}: {}\n", turn.turn, turn.prompt);
+            match (&turn.replayed, &turn.error) {
+                (Some(response), _) => println!("{}\n", response),
+                (None, Some(error)) => println!("[error: {}]\n", error),
+                (None, None) => println!(),
+            }
+        }
+    }
+
+    if let Some(path) = report_path {
+        let json = serde_json::to_string_pretty(&report)?;
+        std::fs::write(path, json)
+            .with_context(|| format!("Failed to write report {}", path.display()))?;
+        eprintln!("Report written to {}", path.display());
+    }
+
+    Ok(())
+}
+
+/// Verify the audit log (exits non-zero if the chain is broken)
+fn run_audit_verify(path: Option<PathBuf>) -> Result<()> {
+    let path = match path {
+        Some(path) => path,
+        None => load_config()?
+            .audit
+            .log_path()
+            .context("Could not determine home directory")?,
+    };
+
+    let report = shammah::metrics::audit::verify(&path)?;
+    println!("{}", report.format(&path));
+    if !report.is_intact() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn run_feedback_command(feedback_command: FeedbackCommand) -> Result<()> {
+    match feedback_command {
+        FeedbackCommand::Export {
+            format,
+            since,
+            rating,
+            output,
+        } => run_feedback_export(format, since, rating, output.as_deref()),
+        FeedbackCommand::Stats => {
+            let stats = shammah::feedback::FeedbackLogger::new()?.compute_stats()?;
+            println!("{}", stats.format());
+            Ok(())
+        }
+    }
+}
+
+fn run_models_command(models_command: ModelsCommand) -> Result<()> {
+    match models_command {
+        ModelsCommand::List => run_models_list(),
+    }
+}
+
+fn run_config_command(config_command: ConfigCommand) -> Result<()> {
+    match config_command {
+        ConfigCommand::Validate => run_config_validate(),
+        ConfigCommand::Path => {
+            println!("{}", shammah::config::config_path()?.display());
+            Ok(())
+        }
+    }
+}
+
+/// Check ~/.shammah/config.toml, exiting nonzero on any problem
+fn run_config_validate() -> Result<()> {
+    let path = shammah::config::config_path()?;
+    if !path.exists() {
+        eprintln!("No config file at {} (run 'shammah setup' to create one)", path.display());
+        std::process::exit(1);
+    }
+
+    // ${VAR} references resolve against .env files, as in load_config
+    shammah::config::ensure_dotenv_loaded();
+    let problems = match shammah::config::check_config_file(&path) {
+        Ok(problems) => problems,
+        Err(e) => {
+            let error = shammah::cli::color::for_terminal(format!("{:#}", e));
+            eprintln!("{}: {}", path.display(), error);
+            std::process::exit(1);
+        }
+    };
+    if problems.is_empty() {
+        println!("OK: {}", path.display());
+        return Ok(());
+    }
+
+    eprintln!("{}: {} problem(s)", path.display(), problems.len());
+    for problem in &problems {
+        eprintln!("  - {}", problem);
+    }
+    std::process::exit(1);
+}
 
+/// Print downloaded models and adapters, marking the configured one
+fn run_models_list() -> Result<()> {
+    use shammah::models::inventory::{self, ModelKind};
+
+    let home = dirs::home_dir().context("Failed to determine home directory")?;
+    let shammah_dir = home.join(".shammah");
+    let config = load_config()?;
+    let Some(entries) = inventory::scan(
+        &shammah_dir.join("models"),
+        &shammah_dir.join("adapters"),
+        config.backend.model_repo.as_deref(),
+    )?
+    else {
+        println!("no models downloaded.");
+        return Ok(());
+    };
+
+    for (kind, heading) in [(ModelKind::Model, "Models"), (ModelKind::Adapter, "Adapters")] {
+        let listed: Vec<_> = entries.iter().filter(|e| e.kind == kind).collect();
+        println!("{} ({}):", heading, listed.len());
+        if listed.is_empty() {
+            println!("  (none)");
+        }
+        for entry in listed {
+            println!("{}", entry.format());
+        }
+        println!();
+    }
+    if let Some(repo) = &config.backend.model_repo {
+        if !entries.iter().any(|e| e.active) {
+            println!("Configured model {} is not downloaded yet.", repo);
+        }
+    }
+    Ok(())
+}
+
+/// Export the feedback log, filtered, to stdout or a file
+fn run_feedback_export(
+    format: shammah::feedback::export::ExportFormat,
+    since: Option<u64>,
+    rating: Option<shammah::feedback::FeedbackRating>,
+    output: Option<&std::path::Path>,
+) -> Result<()> {
+    use shammah::feedback::{FeedbackFilter, FeedbackLogger};
+
+    let logger = FeedbackLogger::new()?;
+    let loaded = logger.load_filtered(&FeedbackFilter { since, rating })?;
+
+    match output {
+        Some(path) => {
+            let file = std::fs::File::create(path)
+                .with_context(|| format!("Failed to create {}", path.display()))?;
+            let mut out = std::io::BufWriter::new(file);
+            shammah::feedback::export::write_entries(&loaded.entries, format, &mut out)?;
+            eprintln!("Exported {} feedback entries to {}", loaded.entries.len(), path.display());
+        }
+        None => {
+            let mut out = std::io::BufWriter::new(std::io::stdout().lock());
+            shammah::feedback::export::write_entries(&loaded.entries, format, &mut out)?;
+        }
+    }
+
+    if loaded.skipped > 0 {
+        eprintln!(
+            "⚠️  Skipped {} unreadable line{} in {}",
+            loaded.skipped,
+            if loaded.skipped == 1 { "" } else { "s" },
+            logger.path().display()
+        );
+    }
     Ok(())
 }
 
 /// Run query using teacher API only (fallback when daemon fails)
-async fn run_query_teacher_only(query: &str, config: &Config) -> Result<()> {
+async fn run_query_teacher_only(
+    query: &str,
+    config: &Config,
+    json: bool,
+    model: Option<&str>,
+) -> Result<()> {
     use shammah::claude::{MessageRequest, ContentBlock};
 
     eprintln!("⚠️  Running in teacher-only mode (no local model)");
 
     // Create teacher client
-    let claude_client = create_claude_client_with_provider(config)?;
+    let claude_client = create_claude_client_with_provider(config, model)?;
 
     // Create simple request
     let request = MessageRequest {
         model: config.active_teacher()
             .and_then(|t| t.model.clone())
             .unwrap_or_else(|| "claude-sonnet-4-5-20250929".to_string()),
-        max_tokens: 8000,
+        max_tokens: None, // Teacher's configured limit
         messages: vec![shammah::claude::Message {
             role: "user".to_string(),
             content: vec![ContentBlock::Text {
@@ -914,10 +1570,15 @@ async fn run_query_teacher_only(query: &str, config: &Config) -> Result<()> {
             }],
         }],
         tools: None,
+        temperature: config.temperature.for_query(query),
+        system: None,
     };
 
     // Send to teacher API
+    let start = std::time::Instant::now();
     let response = claude_client.send_message(&request).await?;
+    let latency_ms = start.elapsed().as_millis() as u64;
+    let model = response.model.clone();
 
     // Extract text from response
     let text = response.content
@@ -929,7 +1590,17 @@ async fn run_query_teacher_only(query: &str, config: &Config) -> Result<()> {
         .collect::<Vec<_>>()
         .join("\n");
 
-    println!("{}", text);
+    if json {
+        let result = shammah::client::QueryResult {
+            response: text,
+            route: "teacher".to_string(),
+            latency_ms,
+            model,
+        };
+        println!("{}", serde_json::to_string(&result)?);
+    } else {
+        println!("{}", text);
+    }
 
     Ok(())
 }
@@ -973,6 +1644,21 @@ async fn run_setup() -> Result<()> {
         debug_logging: result.debug_logging,
         #[cfg(target_os = "macos")]
         gui_automation: false, // Not yet implemented in wizard
+        ascii_only: config.features.ascii_only,
+        startup_verbosity: config.features.startup_verbosity,
+        spinner_style: config.features.spinner_style,
+        tts_enabled: config.features.tts_enabled,
+        tts_command: config.features.tts_command.clone(),
+        wait_for_local: config.features.wait_for_local,
+        local_wait_timeout_secs: config.features.local_wait_timeout_secs,
+        idle_exit_minutes: config.features.idle_exit_minutes,
+        max_output_width: config.features.max_output_width,
+        output_align: config.features.output_align,
+        max_concurrent_tools: config.features.max_concurrent_tools,
+        show_thinking: config.features.show_thinking,
+        code_line_numbers: config.features.code_line_numbers,
+        code_copy_hints: config.features.code_copy_hints,
+        render_markdown: config.features.render_markdown,
     };
     // Update deprecated streaming_enabled field for backward compat
     config.streaming_enabled = config.features.streaming_enabled;
@@ -0,0 +1,436 @@
+// Audit log
+//
+// Opt-in record of every query for teams that need to show what was asked
+// and answered: who asked, the query (or its hash), how it was routed, which
+// tools ran with what arguments, which files they changed, and a hash of the
+// response. Entries are appended to `~/.shammah/audit.jsonl`.
+//
+// The log is tamper-evident: each entry stores the hash of the previous one
+// and its own hash over all of its fields, so editing, removing or
+// reordering entries breaks the chain (`shammah audit-verify` reports where).
+// Dropping entries from the end can't be detected from the file alone.
+//
+// Like profiling, queries are recorded in two steps: `begin` when a query is
+// submitted, `finish` when it completes, fails or is cancelled. Everything
+// is a no-op while the log is disabled.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use uuid::Uuid;
+
+use crate::tools::types::ToolUse;
+
+/// `prev_hash` of the first entry
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Bytes read from the end of the log to find the last entry
+const TAIL_BYTES: u64 = 64 * 1024;
+
+/// Audit settings (`[audit]` in config.toml)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AuditConfig {
+    /// Record an entry per query (default: false)
+    pub enabled: bool,
+    /// Who is asking (default: $USER)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identity: Option<String>,
+    /// Log file (default: ~/.shammah/audit.jsonl)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    /// Store only a SHA-256 of each query instead of its text
+    pub hash_queries: bool,
+    /// Store only a SHA-256 of each tool's arguments
+    pub hash_tool_inputs: bool,
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            identity: None,
+            path: None,
+            hash_queries: false,
+            hash_tool_inputs: false,
+        }
+    }
+}
+
+impl AuditConfig {
+    /// Log file in use
+    pub fn log_path(&self) -> Option<PathBuf> {
+        self.path
+            .clone()
+            .or_else(|| dirs::home_dir().map(|home| home.join(".shammah").join("audit.jsonl")))
+    }
+
+    fn identity(&self) -> String {
+        self.identity
+            .clone()
+            .filter(|identity| !identity.trim().is_empty())
+            .or_else(|| std::env::var("USER").ok())
+            .or_else(|| std::env::var("USERNAME").ok())
+            .unwrap_or_else(|| "unknown".to_string())
+    }
+}
+
+/// A tool call made while answering a query
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditToolCall {
+    pub name: String,
+    /// Tool arguments, or `"sha256:<hex>"` with `hash_tool_inputs`
+    pub input: Value,
+    pub is_error: bool,
+}
+
+/// How a query ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditOutcome {
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+/// One line of audit.jsonl
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Position in the log, from 1
+    pub seq: u64,
+    pub timestamp: DateTime<Utc>,
+    pub identity: String,
+    pub query_id: Uuid,
+    /// Query text (omitted with `hash_queries`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    pub query_hash: String,
+    /// "local", "teacher", "forward" (user-forced), "daemon", ... (empty if never routed)
+    pub route: String,
+    pub tools: Vec<AuditToolCall>,
+    pub files_modified: Vec<String>,
+    pub outcome: AuditOutcome,
+    /// Hash of the response (or of the error message for failed queries)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_hash: Option<String>,
+    pub prev_hash: String,
+    /// Hash of this entry with `hash` empty
+    pub hash: String,
+}
+
+impl AuditEntry {
+    /// Hash over every field except `hash` itself
+    pub fn compute_hash(&self) -> String {
+        let mut unhashed = self.clone();
+        unhashed.hash = String::new();
+        sha256_hex(&serde_json::to_string(&unhashed).expect("audit entries serialize"))
+    }
+}
+
+/// Result of checking the chain (`shammah audit-verify`)
+#[derive(Debug, Default)]
+pub struct AuditVerification {
+    pub entries: usize,
+    /// One message per break, with its line number
+    pub problems: Vec<String>,
+}
+
+impl AuditVerification {
+    pub fn is_intact(&self) -> bool {
+        self.problems.is_empty()
+    }
+
+    pub fn format(&self, path: &Path) -> String {
+        if self.is_intact() {
+            return format!("✓ {}: {} entries, chain intact", path.display(), self.entries);
+        }
+        let mut out = format!(
+            "✗ {}: {} entries, {} problem(s):",
+            path.display(),
+            self.entries,
+            self.problems.len()
+        );
+        for problem in &self.problems {
+            out.push_str(&format!("\n  - {}", problem));
+        }
+        out
+    }
+}
+
+/// Query being answered
+struct ActiveQuery {
+    query_id: Uuid,
+    query: String,
+    route: String,
+    tools: Vec<AuditToolCall>,
+    files_modified: Vec<String>,
+}
+
+static CONFIG: Mutex<Option<AuditConfig>> = Mutex::new(None);
+static ACTIVE: Mutex<Vec<ActiveQuery>> = Mutex::new(Vec::new());
+/// Serializes appends within this process
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Set up audit logging from config (call once at startup)
+pub fn init(config: &AuditConfig) {
+    *CONFIG.lock().unwrap() = config.enabled.then(|| config.clone());
+}
+
+pub fn is_enabled() -> bool {
+    CONFIG.lock().unwrap().is_some()
+}
+
+/// Start recording a query
+pub fn begin(query_id: Uuid, query: &str) {
+    if !is_enabled() {
+        return;
+    }
+    ACTIVE.lock().unwrap().push(ActiveQuery {
+        query_id,
+        query: query.to_string(),
+        route: String::new(),
+        tools: Vec::new(),
+        files_modified: Vec::new(),
+    });
+}
+
+/// Record where a query was routed
+pub fn set_route(query_id: Uuid, route: &str) {
+    with_active(query_id, |active| active.route = route.to_string());
+}
+
+/// Record a tool call and the files it changed
+pub fn record_tool(query_id: Uuid, tool_use: &ToolUse, is_error: bool, files: Vec<PathBuf>) {
+    let Some(hash_inputs) = CONFIG.lock().unwrap().as_ref().map(|c| c.hash_tool_inputs) else {
+        return;
+    };
+    let input = if hash_inputs {
+        Value::String(format!("sha256:{}", sha256_hex(&tool_use.input.to_string())))
+    } else {
+        tool_use.input.clone()
+    };
+    with_active(query_id, |active| {
+        active.tools.push(AuditToolCall {
+            name: tool_use.name.clone(),
+            input,
+            is_error,
+        });
+        for file in files {
+            let file = file.display().to_string();
+            if !active.files_modified.contains(&file) {
+                active.files_modified.push(file);
+            }
+        }
+    });
+}
+
+/// Finish a query and append its entry to the log
+///
+/// `text` is the response, or the error message for failed queries.
+pub fn finish(query_id: Uuid, outcome: AuditOutcome, text: Option<&str>) {
+    let Some(config) = CONFIG.lock().unwrap().clone() else {
+        return;
+    };
+    let active = {
+        let mut queries = ACTIVE.lock().unwrap();
+        let Some(index) = queries.iter().position(|a| a.query_id == query_id) else {
+            return;
+        };
+        queries.remove(index)
+    };
+
+    if let Err(e) = append(&config, active, outcome, text) {
+        tracing::warn!("Failed to write audit log: {}", e);
+    }
+}
+
+fn with_active(query_id: Uuid, update: impl FnOnce(&mut ActiveQuery)) {
+    if let Some(active) = ACTIVE.lock().unwrap().iter_mut().find(|a| a.query_id == query_id) {
+        update(active);
+    }
+}
+
+fn append(config: &AuditConfig, active: ActiveQuery, outcome: AuditOutcome, text: Option<&str>) -> Result<()> {
+    let path = config.log_path().context("Could not determine home directory")?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let _guard = WRITE_LOCK.lock().unwrap();
+    // Re-read the tail each time so other sessions' entries extend the chain
+    let (seq, prev_hash) = match last_entry(&path)? {
+        Some(last) => (last.seq + 1, last.hash),
+        None => (1, GENESIS_HASH.to_string()),
+    };
+
+    let mut entry = AuditEntry {
+        seq,
+        timestamp: Utc::now(),
+        identity: config.identity(),
+        query_id: active.query_id,
+        query: (!config.hash_queries).then(|| active.query.clone()),
+        query_hash: sha256_hex(&active.query),
+        route: active.route,
+        tools: active.tools,
+        files_modified: active.files_modified,
+        outcome,
+        response_hash: text.map(sha256_hex),
+        prev_hash,
+        hash: String::new(),
+    };
+    entry.hash = entry.compute_hash();
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+    Ok(())
+}
+
+/// Last entry in the log (None for a missing or empty log)
+fn last_entry(path: &Path) -> Result<Option<AuditEntry>> {
+    let mut file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e).with_context(|| format!("Failed to open {}", path.display())),
+    };
+    let len = file.metadata()?.len();
+    file.seek(SeekFrom::Start(len.saturating_sub(TAIL_BYTES)))?;
+    let mut tail = Vec::new();
+    file.read_to_end(&mut tail)?;
+
+    let tail = String::from_utf8_lossy(&tail);
+    match tail.lines().rev().find(|line| !line.trim().is_empty()) {
+        Some(line) => Ok(Some(serde_json::from_str(line).with_context(|| {
+            format!("Last entry of {} is unreadable; run `shammah audit-verify`", path.display())
+        })?)),
+        None => Ok(None),
+    }
+}
+
+/// Check every entry's hash and its link to the previous entry
+pub fn verify(path: &Path) -> Result<AuditVerification> {
+    let file = std::fs::File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut report = AuditVerification::default();
+    let mut expected_seq = 1;
+    let mut prev_hash = GENESIS_HASH.to_string();
+
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        let line_number = index + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry: AuditEntry = match serde_json::from_str(&line) {
+            Ok(entry) => entry,
+            Err(e) => {
+                report.problems.push(format!("line {}: unreadable entry ({})", line_number, e));
+                continue;
+            }
+        };
+        report.entries += 1;
+
+        if entry.seq != expected_seq {
+            report.problems.push(format!(
+                "line {}: sequence {} where {} was expected (entries missing or reordered)",
+                line_number, entry.seq, expected_seq
+            ));
+        }
+        if entry.prev_hash != prev_hash {
+            report.problems.push(format!(
+                "line {}: doesn't link to the previous entry (entries missing, inserted or reordered)",
+                line_number
+            ));
+        }
+        if entry.compute_hash() != entry.hash {
+            report
+                .problems
+                .push(format!("line {}: contents don't match the entry hash (modified)", line_number));
+        }
+
+        expected_seq = entry.seq + 1;
+        prev_hash = entry.hash;
+    }
+
+    Ok(report)
+}
+
+fn sha256_hex(text: &str) -> String {
+    format!("{:x}", Sha256::digest(text.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn write_log(path: &Path, count: usize) {
+        let config = AuditConfig {
+            enabled: true,
+            identity: Some("alice".to_string()),
+            path: Some(path.to_path_buf()),
+            hash_queries: false,
+            hash_tool_inputs: false,
+        };
+        for i in 0..count {
+            let active = ActiveQuery {
+                query_id: Uuid::new_v4(),
+                query: format!("query {}", i),
+                route: "teacher".to_string(),
+                tools: vec![AuditToolCall {
+                    name: "read".to_string(),
+                    input: json!({"file_path": "src/main.rs"}),
+                    is_error: false,
+                }],
+                files_modified: Vec::new(),
+            };
+            append(&config, active, AuditOutcome::Completed, Some("answer")).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_chain_verifies() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        write_log(&path, 3);
+
+        let report = verify(&path).unwrap();
+        assert_eq!(report.entries, 3);
+        assert!(report.is_intact(), "{:?}", report.problems);
+
+        let last = last_entry(&path).unwrap().unwrap();
+        assert_eq!(last.seq, 3);
+        assert_eq!(last.query.as_deref(), Some("query 2"));
+        assert_eq!(last.response_hash, Some(sha256_hex("answer")));
+    }
+
+    #[test]
+    fn test_verify_detects_tampering() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        write_log(&path, 3);
+        let original = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = original.lines().collect();
+
+        // Edited query
+        std::fs::write(&path, original.replace("query 1", "query X")).unwrap();
+        let report = verify(&path).unwrap();
+        assert_eq!(report.problems.len(), 1);
+        assert!(report.problems[0].contains("line 2") && report.problems[0].contains("modified"));
+
+        // Removed entry: the next one no longer links up
+        std::fs::write(&path, format!("{}\n{}\n", lines[0], lines[2])).unwrap();
+        let report = verify(&path).unwrap();
+        assert_eq!(report.entries, 2);
+        assert!(report.problems.iter().all(|p| p.starts_with("line 2")));
+        assert_eq!(report.problems.len(), 2);
+    }
+}
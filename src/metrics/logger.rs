@@ -1,16 +1,17 @@
 // Metrics logger
 
 use anyhow::{Context, Result};
-use chrono::Utc;
 use sha2::{Digest, Sha256};
 use std::fs::{self, OpenOptions};
 use std::io::Write;
 use std::path::PathBuf;
 
 use super::types::RequestMetric;
+use crate::clock::SharedClock;
 
 pub struct MetricsLogger {
     metrics_dir: PathBuf,
+    clock: SharedClock,
 }
 
 impl MetricsLogger {
@@ -23,12 +24,26 @@ impl MetricsLogger {
             )
         })?;
 
-        Ok(Self { metrics_dir })
+        Ok(Self {
+            metrics_dir,
+            clock: crate::clock::system_clock(),
+        })
+    }
+
+    /// Use `clock` to decide which day's file is "today"
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Today's date (UTC), as used in log file names
+    fn today(&self) -> String {
+        self.clock.now().format("%Y-%m-%d").to_string()
     }
 
     /// Log a request metric to today's JSONL file
     pub fn log(&self, metric: &RequestMetric) -> Result<()> {
-        let today = Utc::now().format("%Y-%m-%d").to_string();
+        let today = self.today();
         let log_file = self.metrics_dir.join(format!("{}.jsonl", today));
 
         let mut file = OpenOptions::new()
@@ -74,8 +89,7 @@ impl MetricsLogger {
 
     /// Get summary statistics for today
     pub fn get_today_summary(&self) -> Result<MetricsSummary> {
-        let today = Utc::now().format("%Y-%m-%d").to_string();
-        let metrics = self.read_metrics(&today)?;
+        let metrics = self.read_metrics(&self.today())?;
 
         let total = metrics.len();
         let local_count = metrics
@@ -142,6 +156,7 @@ pub struct MetricsSummary {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clock::FixedClock;
 
     #[test]
     fn test_hash_query() {
@@ -153,4 +168,38 @@ mod tests {
         assert_ne!(hash1, hash3);
         assert_eq!(hash1.len(), 64); // SHA256 produces 64 hex chars
     }
+
+    #[test]
+    fn test_today_follows_clock() {
+        let dir = tempfile::tempdir().unwrap();
+        let clock = std::sync::Arc::new(FixedClock::at_unix(1_700_000_000)); // 2023-11-14
+        let logger = MetricsLogger::new(dir.path().to_path_buf())
+            .unwrap()
+            .with_clock(clock.clone());
+
+        let metric = |decision: &str| {
+            RequestMetric::new(
+                MetricsLogger::hash_query("q"),
+                decision.to_string(),
+                None,
+                None,
+                None,
+                100,
+                Default::default(),
+                None,
+                None,
+            )
+        };
+        logger.log(&metric("local")).unwrap();
+        logger.log(&metric("forward")).unwrap();
+        assert!(dir.path().join("2023-11-14.jsonl").exists());
+
+        let summary = logger.get_today_summary().unwrap();
+        assert_eq!(summary.total, 2);
+        assert_eq!(summary.local_count, 1);
+
+        // A new day starts an empty summary
+        clock.advance(chrono::Duration::days(1));
+        assert_eq!(logger.get_today_summary().unwrap().total, 0);
+    }
 }
@@ -1,11 +1,13 @@
 // Metrics module
 // Public interface for logging and tracking metrics
 
+pub mod audit; // Tamper-evident per-query audit log
 mod logger;
 mod similarity;
 mod trends;
 mod types;
 
+pub use audit::AuditConfig;
 pub use logger::MetricsLogger;
 pub use similarity::semantic_similarity;
 pub use trends::{TrainingTrends, Trend};
@@ -1,7 +1,10 @@
 // Training trends tracking for monitoring model improvement over time
 
+use chrono::{DateTime, Duration, Utc};
 use std::collections::VecDeque;
 
+use crate::clock::SharedClock;
+
 /// Trend direction
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Trend {
@@ -13,8 +16,11 @@ pub enum Trend {
 /// Tracks rolling window of training metrics
 pub struct TrainingTrends {
     recent_quality_scores: VecDeque<f64>,
+    /// When each quality score was recorded (parallel to the scores)
+    quality_times: VecDeque<DateTime<Utc>>,
     recent_similarities: VecDeque<f64>,
     window_size: usize,
+    clock: SharedClock,
 }
 
 impl TrainingTrends {
@@ -22,18 +28,28 @@ impl TrainingTrends {
     pub fn new(window_size: usize) -> Self {
         Self {
             recent_quality_scores: VecDeque::with_capacity(window_size),
+            quality_times: VecDeque::with_capacity(window_size),
             recent_similarities: VecDeque::with_capacity(window_size),
             window_size,
+            clock: crate::clock::system_clock(),
         }
     }
 
+    /// Use `clock` to timestamp measurements
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
     /// Add a new measurement to the window
     pub fn add_measurement(&mut self, quality: f64, similarity: Option<f64>) {
         // Add quality score
         if self.recent_quality_scores.len() >= self.window_size {
             self.recent_quality_scores.pop_front();
+            self.quality_times.pop_front();
         }
         self.recent_quality_scores.push_back(quality);
+        self.quality_times.push_back(self.clock.now());
 
         // Add similarity score if provided
         if let Some(sim) = similarity {
@@ -52,6 +68,29 @@ impl TrainingTrends {
         self.recent_quality_scores.iter().sum::<f64>() / self.recent_quality_scores.len() as f64
     }
 
+    /// Average quality of the measurements taken within the last `period`
+    ///
+    /// `None` if there were none.
+    pub fn avg_quality_since(&self, period: Duration) -> Option<f64> {
+        let cutoff = self.clock.now() - period;
+        let recent: Vec<f64> = self
+            .quality_times
+            .iter()
+            .zip(&self.recent_quality_scores)
+            .filter(|(time, _)| **time >= cutoff)
+            .map(|(_, quality)| *quality)
+            .collect();
+        if recent.is_empty() {
+            return None;
+        }
+        Some(recent.iter().sum::<f64>() / recent.len() as f64)
+    }
+
+    /// When the latest measurement was recorded
+    pub fn last_measurement_at(&self) -> Option<DateTime<Utc>> {
+        self.quality_times.back().copied()
+    }
+
     /// Get average similarity score over the window
     pub fn avg_similarity(&self) -> f64 {
         if self.recent_similarities.is_empty() {
@@ -109,6 +148,7 @@ impl TrainingTrends {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clock::{Clock, FixedClock};
 
     #[test]
     fn test_empty_trends() {
@@ -187,4 +227,21 @@ mod tests {
 
         assert_eq!(trends.quality_trend(), Trend::Stable);
     }
+
+    #[test]
+    fn test_avg_quality_since_uses_clock() {
+        let clock = std::sync::Arc::new(FixedClock::at_unix(1_700_000_000));
+        let mut trends = TrainingTrends::new(10).with_clock(clock.clone());
+        assert_eq!(trends.avg_quality_since(Duration::hours(1)), None);
+
+        trends.add_measurement(0.2, None);
+        clock.advance(Duration::hours(2));
+        trends.add_measurement(0.8, None);
+        trends.add_measurement(0.6, None);
+
+        let last_hour = trends.avg_quality_since(Duration::hours(1)).unwrap();
+        assert!((last_hour - 0.7).abs() < 0.01);
+        assert!((trends.avg_quality_since(Duration::hours(3)).unwrap() - trends.avg_quality()).abs() < 0.01);
+        assert_eq!(trends.last_measurement_at(), Some(clock.now()));
+    }
 }
@@ -2,11 +2,14 @@
 // Enables REPL to start in <100ms while model loads in background
 
 use anyhow::{anyhow, Context, Result};
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 
-use super::download::{DownloadProgress, ModelDownloader};
+use super::download::{DownloadProgress, HubSettings, ModelDownloader};
 use super::generator_new::GeneratorModel;
 use super::model_selector::{ModelSelector, QwenSize};
 use crate::config::ExecutionTarget;
@@ -43,7 +46,7 @@ pub enum GeneratorState {
 }
 
 /// Snapshot of download progress for state updates
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DownloadProgressSnapshot {
     pub file_name: String,
     pub current_file: usize,
@@ -56,6 +59,18 @@ impl GeneratorState {
         matches!(self, GeneratorState::Ready { .. })
     }
 
+    /// Short machine-readable name of the state (as reported by `/ready`)
+    pub fn kind(&self) -> &'static str {
+        match self {
+            GeneratorState::Initializing => "initializing",
+            GeneratorState::Downloading { .. } => "downloading",
+            GeneratorState::Loading { .. } => "loading",
+            GeneratorState::Ready { .. } => "ready",
+            GeneratorState::Failed { .. } => "failed",
+            GeneratorState::NotAvailable => "not_available",
+        }
+    }
+
     /// Get human-readable status message
     pub fn status_message(&self) -> String {
         match self {
@@ -86,16 +101,193 @@ impl GeneratorState {
     }
 }
 
+/// Details of the local model, for /model-info and `GET /model`
+///
+/// Only `state` and `message` are always set; model details appear once the
+/// model is ready, download progress while it downloads.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModelInfo {
+    /// Generator state (`GeneratorState::kind`, e.g. "ready")
+    pub state: String,
+    /// Human-readable state
+    pub message: String,
+    /// Model being downloaded, loaded or served
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Model family, e.g. "Qwen 2.5"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub family: Option<String>,
+    /// Parameter count within the family, e.g. "3B"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<String>,
+    /// Execution target the model runs on, e.g. "CoreML (ANE)"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    /// Context window in tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_length: Option<usize>,
+    /// Weights file the model was loaded from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    /// LoRA adapter applied to the model (None = base model)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub adapter: Option<PathBuf>,
+    /// Download progress while the model is downloading
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub download: Option<DownloadProgressSnapshot>,
+}
+
+impl ModelInfo {
+    /// Describe `state`; `adapter` is the LoRA adapter the local generator
+    /// applied, if any
+    ///
+    /// Waits for the model lock if a generation is running.
+    pub async fn from_state(state: &GeneratorState, adapter: Option<&Path>) -> Self {
+        let mut info = Self {
+            state: state.kind().to_string(),
+            message: state.status_message(),
+            ..Self::default()
+        };
+        match state {
+            GeneratorState::Downloading {
+                model_name,
+                progress,
+            } => {
+                info.model = Some(model_name.clone());
+                info.download = Some(progress.clone());
+            }
+            GeneratorState::Loading { model_name } => info.model = Some(model_name.clone()),
+            GeneratorState::Ready { model, model_name } => {
+                info.model = Some(model_name.clone());
+                let model = model.read().await;
+                if let GeneratorConfig::Pretrained(config) = model.config() {
+                    info.family = Some(config.family.name().to_string());
+                    info.size = Some(config.size.to_size_string(config.family).to_string());
+                    info.device = Some(config.target.name().to_string());
+                }
+                info.context_length = model.context_length();
+                info.path = model.model_path().map(Path::to_path_buf);
+                info.adapter = adapter.map(Path::to_path_buf);
+            }
+            _ => {}
+        }
+        info
+    }
+
+    /// Multi-line summary for the REPL
+    pub fn format(&self) -> String {
+        if self.state != "ready" {
+            return format!(
+                "Local model: {} ({})\n  {}",
+                self.model.as_deref().unwrap_or("none"),
+                self.state,
+                self.message
+            );
+        }
+
+        let mut lines = vec![format!(
+            "Local model: {} (ready)",
+            self.model.as_deref().unwrap_or("unknown")
+        )];
+        let mut field = |label: &str, value: Option<String>| {
+            lines.push(format!("  {:<9} {}", label, value.unwrap_or_else(|| "unknown".to_string())));
+        };
+        field("Family:", self.family.clone());
+        field("Size:", self.size.clone());
+        field("Device:", self.device.clone());
+        field("Context:", self.context_length.map(|tokens| format!("{} tokens", tokens)));
+        if let Some(path) = &self.path {
+            field("File:", Some(path.display().to_string()));
+        }
+        field(
+            "Adapter:",
+            Some(match &self.adapter {
+                Some(adapter) => adapter.display().to_string(),
+                None => "none (base model)".to_string(),
+            }),
+        );
+        lines.join("\n")
+    }
+}
+
+/// How often `wait_for_ready` re-checks the generator state
+const READY_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How a wait for the local model ended
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LocalWaitOutcome {
+    /// Model finished loading
+    Ready,
+    /// Still loading when the timeout passed
+    TimedOut,
+    /// Loading failed, or there is no local model (reason for the notice)
+    Unavailable(String),
+    /// Caller gave up (e.g. Ctrl+C)
+    Cancelled,
+}
+
+/// Wait for the local model to finish loading, polling the shared state
+///
+/// Returns as soon as the model is ready, loading fails, `timeout` passes or
+/// `is_cancelled` resolves to true. `on_progress` is called on each poll
+/// with the current (not yet ready) state and the time waited so far.
+pub async fn wait_for_ready<C, F>(
+    state: &RwLock<GeneratorState>,
+    timeout: Duration,
+    mut is_cancelled: C,
+    mut on_progress: impl FnMut(&GeneratorState, Duration),
+) -> LocalWaitOutcome
+where
+    C: FnMut() -> F,
+    F: Future<Output = bool>,
+{
+    let start = Instant::now();
+    loop {
+        {
+            let current = state.read().await;
+            match &*current {
+                GeneratorState::Ready { .. } => return LocalWaitOutcome::Ready,
+                GeneratorState::Failed { error } => {
+                    return LocalWaitOutcome::Unavailable(error.clone())
+                }
+                GeneratorState::NotAvailable => {
+                    return LocalWaitOutcome::Unavailable("no local model available".to_string())
+                }
+                loading => on_progress(loading, start.elapsed()),
+            }
+        }
+
+        if is_cancelled().await {
+            return LocalWaitOutcome::Cancelled;
+        }
+        if start.elapsed() >= timeout {
+            return LocalWaitOutcome::TimedOut;
+        }
+        tokio::time::sleep(READY_POLL_INTERVAL.min(timeout)).await;
+    }
+}
+
 /// Background task that loads generator asynchronously
 pub struct BootstrapLoader {
     state: Arc<RwLock<GeneratorState>>,
     output: Option<Arc<OutputManager>>,
+    hub: HubSettings,
 }
 
 impl BootstrapLoader {
     /// Create new bootstrap loader with shared state
     pub fn new(state: Arc<RwLock<GeneratorState>>, output: Option<Arc<OutputManager>>) -> Self {
-        Self { state, output }
+        Self {
+            state,
+            output,
+            hub: HubSettings::default(),
+        }
+    }
+
+    /// Download models from / cache them in the configured locations
+    pub fn with_hub(mut self, hub: HubSettings) -> Self {
+        self.hub = hub;
+        self
     }
 
     /// Get reference to the generator state
@@ -105,10 +297,8 @@ impl BootstrapLoader {
 
     /// Check if HuggingFace token exists and is valid
     fn check_hf_token() -> Result<()> {
-        let token_path = dirs::cache_dir()
-            .ok_or_else(|| anyhow!("Could not determine cache directory"))?
-            .join("huggingface")
-            .join("token");
+        // $HF_HOME/token, or ~/.cache/huggingface/token
+        let token_path = hf_hub::Cache::from_env().token_path();
 
         if !token_path.exists() {
             return Err(anyhow!(
@@ -174,6 +364,7 @@ impl BootstrapLoader {
             size: model_size,
             target: execution_target,
             repo_override: model_repo.clone(),
+            hub: self.hub.clone(),
         };
 
         // Step 4: Load using UnifiedModelLoader (handles download + loading)
@@ -201,7 +392,8 @@ impl BootstrapLoader {
                 output.write_progress(format!("  └─ Initializing {}...", model_name_clone));
             }
 
-            let loader = UnifiedModelLoader::new()?;
+            let hub = load_config.hub.clone();
+            let loader = UnifiedModelLoader::with_hub(hub.clone())?;
 
             // This will download if not cached
             let text_gen = loader.load(load_config)?;
@@ -214,6 +406,7 @@ impl BootstrapLoader {
                 size: model_size,
                 target: execution_target,
                 repo_override: model_repo.clone(),
+                hub,
             });
 
             // Actually, UnifiedModelLoader.load() returns Box<dyn TextGeneration>
@@ -298,6 +491,58 @@ mod tests {
         assert!(state.read().await.status_message().contains("Loading"));
     }
 
+    #[tokio::test]
+    async fn test_model_info_before_ready() {
+        let state = GeneratorState::Downloading {
+            model_name: "Qwen 2.5 3B".to_string(),
+            progress: DownloadProgressSnapshot {
+                file_name: "model.onnx".to_string(),
+                current_file: 2,
+                total_files: 4,
+            },
+        };
+        let info = ModelInfo::from_state(&state, None).await;
+        assert_eq!(info.state, "downloading");
+        assert_eq!(info.download.as_ref().unwrap().current_file, 2);
+        assert!(info.format().contains("Downloading Qwen 2.5 3B (2/4): model.onnx"));
+
+        // Details only appear once ready
+        let json = serde_json::to_value(&info).unwrap();
+        assert!(json.get("device").is_none());
+        assert!(json.get("adapter").is_none());
+
+        let info = ModelInfo::from_state(&GeneratorState::NotAvailable, None).await;
+        assert!(info.format().starts_with("Local model: none (not_available)"));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_ready_outcomes() {
+        let state = RwLock::new(GeneratorState::Loading {
+            model_name: "Qwen2.5-1.5B-Instruct".to_string(),
+        });
+        let mut polls = 0;
+        let outcome = wait_for_ready(
+            &state,
+            Duration::from_millis(20),
+            || async { false },
+            |_, _| polls += 1,
+        )
+        .await;
+        assert_eq!(outcome, LocalWaitOutcome::TimedOut);
+        assert!(polls >= 1);
+
+        let outcome =
+            wait_for_ready(&state, Duration::from_secs(60), || async { true }, |_, _| {}).await;
+        assert_eq!(outcome, LocalWaitOutcome::Cancelled);
+
+        *state.write().await = GeneratorState::Failed {
+            error: "out of memory".to_string(),
+        };
+        let outcome =
+            wait_for_ready(&state, Duration::from_secs(60), || async { false }, |_, _| {}).await;
+        assert_eq!(outcome, LocalWaitOutcome::Unavailable("out of memory".to_string()));
+    }
+
     #[test]
     fn test_download_progress_snapshot() {
         let progress = DownloadProgressSnapshot {
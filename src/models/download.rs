@@ -2,13 +2,115 @@
 // Uses HuggingFace Hub for download management and caching
 
 use anyhow::{anyhow, Context, Result};
-use hf_hub::{api::sync::Api, Repo, RepoType};
+use hf_hub::{
+    api::sync::{Api, ApiBuilder},
+    Cache, Repo, RepoType,
+};
 use indicatif::{ProgressBar, ProgressStyle};
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc;
 
 use super::model_selector::QwenSize;
 
+/// HuggingFace Hub endpoint used when neither config nor `HF_ENDPOINT` sets one
+pub const DEFAULT_HF_ENDPOINT: &str = "https://huggingface.co";
+
+/// Where models are downloaded from and cached (`[backend] models_dir` /
+/// `[backend] hf_endpoint`)
+///
+/// Unset fields fall back to the standard `HF_HOME` / `HF_ENDPOINT`
+/// environment variables, then to the HuggingFace defaults.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HubSettings {
+    /// Model cache directory, laid out like the HuggingFace hub cache
+    /// (`models--<org>--<name>/snapshots/...`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub models_dir: Option<PathBuf>,
+
+    /// Hub endpoint, e.g. a mirror or an internal proxy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+}
+
+impl HubSettings {
+    /// Resolved model cache directory: models_dir, then `$HF_HOME/hub`, then
+    /// ~/.cache/huggingface/hub
+    pub fn cache_dir(&self) -> PathBuf {
+        resolve_cache_dir(
+            self.models_dir.as_deref(),
+            std::env::var_os("HF_HOME"),
+            dirs::home_dir(),
+        )
+    }
+
+    /// Resolved hub endpoint: hf_endpoint, then `HF_ENDPOINT`, then huggingface.co
+    pub fn endpoint(&self) -> String {
+        resolve_endpoint(self.endpoint.as_deref(), std::env::var("HF_ENDPOINT").ok())
+    }
+
+    /// Hub client for these settings
+    ///
+    /// The access token is still read from the standard location
+    /// (`$HF_HOME/token` or ~/.cache/huggingface/token).
+    pub fn api(&self) -> Result<Api> {
+        ApiBuilder::from_env()
+            .with_cache_dir(self.cache_dir())
+            .with_endpoint(self.endpoint())
+            .build()
+            .context("Failed to create HuggingFace Hub client")
+    }
+
+    /// Create the model cache directory if needed and check it is writable
+    pub fn ensure_writable(&self) -> Result<PathBuf> {
+        let dir = self.cache_dir();
+        ensure_dir_writable(&dir).with_context(|| {
+            format!(
+                "Model directory {} is not writable
+                 Set [backend] models_dir in ~/.shammah/config.toml (or HF_HOME) \
+                 to a writable directory",
+                dir.display()
+            )
+        })?;
+        Ok(dir)
+    }
+}
+
+fn resolve_cache_dir(
+    models_dir: Option<&Path>,
+    hf_home: Option<OsString>,
+    home: Option<PathBuf>,
+) -> PathBuf {
+    if let Some(dir) = models_dir {
+        return dir.to_path_buf();
+    }
+    if let Some(hf_home) = hf_home.filter(|value| !value.is_empty()) {
+        return PathBuf::from(hf_home).join("hub");
+    }
+    home.unwrap_or_default()
+        .join(".cache")
+        .join("huggingface")
+        .join("hub")
+}
+
+fn resolve_endpoint(endpoint: Option<&str>, hf_endpoint: Option<String>) -> String {
+    endpoint
+        .map(str::to_string)
+        .or(hf_endpoint)
+        .map(|endpoint| endpoint.trim_end_matches('/').to_string())
+        .filter(|endpoint| !endpoint.is_empty())
+        .unwrap_or_else(|| DEFAULT_HF_ENDPOINT.to_string())
+}
+
+fn ensure_dir_writable(dir: &Path) -> Result<()> {
+    std::fs::create_dir_all(dir).context("Failed to create directory")?;
+    let probe = dir.join(format!(".shammah-write-test-{}", std::process::id()));
+    std::fs::write(&probe, b"").context("Failed to write to directory")?;
+    std::fs::remove_file(&probe).ok();
+    Ok(())
+}
+
 /// Download progress events sent via channel
 #[derive(Debug, Clone)]
 pub enum DownloadProgress {
@@ -32,25 +134,30 @@ pub enum DownloadProgress {
 
 /// Model downloader with HuggingFace Hub integration
 pub struct ModelDownloader {
-    cache_dir: Option<PathBuf>,
+    hub: HubSettings,
 }
 
 impl ModelDownloader {
-    /// Create new downloader (uses default HF cache: ~/.cache/huggingface/)
+    /// Create new downloader (uses HF_HOME / HF_ENDPOINT or the default HF cache)
     pub fn new() -> Result<Self> {
-        Ok(Self { cache_dir: None })
+        Ok(Self {
+            hub: HubSettings::default(),
+        })
+    }
+
+    /// Create downloader for configured hub settings
+    pub fn with_hub(hub: HubSettings) -> Self {
+        Self { hub }
     }
 
     /// Create downloader with custom cache directory
     pub fn with_cache_dir(cache_dir: PathBuf) -> Result<Self> {
         std::fs::create_dir_all(&cache_dir).context("Failed to create cache directory")?;
 
-        // Set HF_HOME environment variable to control cache location
-        std::env::set_var("HF_HOME", &cache_dir);
-
-        Ok(Self {
-            cache_dir: Some(cache_dir),
-        })
+        Ok(Self::with_hub(HubSettings {
+            models_dir: Some(cache_dir),
+            endpoint: None,
+        }))
     }
 
     /// Download model with progress tracking (generic for any model family)
@@ -87,13 +194,17 @@ impl ModelDownloader {
         })
         .ok();
 
-        // Create API instance (cache dir controlled by HF_HOME env var if set)
-        let api = Api::new()?;
+        let api = self.hub.api()?;
 
         // Get repository reference
         let repo = api.repo(Repo::new(repo_id.to_string(), RepoType::Model));
 
-        tracing::info!("Downloading {} to cache...", repo_id);
+        tracing::info!(
+            "Downloading {} from {} to {}...",
+            repo_id,
+            self.hub.endpoint(),
+            self.hub.cache_dir().display()
+        );
 
         let mut downloaded_files = Vec::new();
 
@@ -324,40 +435,17 @@ impl ModelDownloader {
 
     /// Check if model is already cached
     pub fn is_cached(&self, model_size: QwenSize) -> bool {
-        // Temporarily set HF_HOME if custom cache dir specified
-        let _guard = self.cache_dir.as_ref().map(|dir| {
-            let old_val = std::env::var("HF_HOME").ok();
-            std::env::set_var("HF_HOME", dir);
-            old_val
-        });
-
-        let api = match Api::new() {
-            Ok(api) => api,
-            Err(_) => return false,
-        };
-
         let model_id = model_size.model_id();
-        let repo = api.repo(Repo::new(model_id.to_string(), RepoType::Model));
+        let repo = Cache::new(self.hub.cache_dir())
+            .repo(Repo::new(model_id.to_string(), RepoType::Model));
 
         // Check if required files exist in cache
-        let result = repo.get("config.json").is_ok() && repo.get("tokenizer.json").is_ok();
-
-        // Restore old HF_HOME if it was set
-        if let Some(Some(old_val)) = _guard {
-            std::env::set_var("HF_HOME", old_val);
-        }
-
-        result
+        repo.get("config.json").is_some() && repo.get("tokenizer.json").is_some()
     }
 
-    /// Get cache directory path
+    /// Get cache directory path (HuggingFace hub layout)
     pub fn cache_dir(&self) -> PathBuf {
-        self.cache_dir.clone().unwrap_or_else(|| {
-            dirs::home_dir()
-                .unwrap_or_default()
-                .join(".cache")
-                .join("huggingface")
-        })
+        self.hub.cache_dir()
     }
 }
 
@@ -387,6 +475,56 @@ mod tests {
         std::fs::remove_dir_all(temp_dir).ok();
     }
 
+    #[test]
+    fn test_hub_settings_resolution() {
+        let home = Some(PathBuf::from("/home/user"));
+
+        // Config wins over the environment
+        assert_eq!(
+            resolve_cache_dir(
+                Some(Path::new("/data/models")),
+                Some("/hf".into()),
+                home.clone()
+            ),
+            PathBuf::from("/data/models")
+        );
+        assert_eq!(
+            resolve_cache_dir(None, Some("/hf".into()), home.clone()),
+            PathBuf::from("/hf/hub")
+        );
+        assert_eq!(
+            resolve_cache_dir(None, None, home),
+            PathBuf::from("/home/user/.cache/huggingface/hub")
+        );
+
+        assert_eq!(
+            resolve_endpoint(Some("https://mirror.example/"), Some("https://env".into())),
+            "https://mirror.example"
+        );
+        assert_eq!(resolve_endpoint(None, Some("https://env".into())), "https://env");
+        assert_eq!(resolve_endpoint(None, None), DEFAULT_HF_ENDPOINT);
+    }
+
+    #[test]
+    fn test_ensure_writable() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let hub = HubSettings {
+            models_dir: Some(temp_dir.path().join("models")),
+            endpoint: None,
+        };
+        assert_eq!(hub.ensure_writable().unwrap(), temp_dir.path().join("models"));
+
+        // A regular file can't hold models
+        let file = temp_dir.path().join("file");
+        std::fs::write(&file, "").unwrap();
+        let hub = HubSettings {
+            models_dir: Some(file.join("models")),
+            endpoint: None,
+        };
+        let error = format!("{:#}", hub.ensure_writable().unwrap_err());
+        assert!(error.contains("is not writable"), "{}", error);
+    }
+
     #[test]
     fn test_is_cached() {
         let downloader = ModelDownloader::new().unwrap();
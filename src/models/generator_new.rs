@@ -3,13 +3,16 @@
 
 use anyhow::Result;
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 use super::common::{GeneratorConfig, Saveable};
 use super::unified_loader::UnifiedModelLoader;
+use crate::local::timeout::Deadline;
 
 /// Text generation trait - abstraction over different generator backends
-/// Callback type for streaming generation
-pub type TokenCallback = Box<dyn FnMut(u32, &str) + Send>;
+/// Callback type for streaming generation (return false to stop generating)
+pub type TokenCallback = Box<dyn FnMut(u32, &str) -> bool + Send>;
 
 pub trait TextGeneration: Send + Sync {
     /// Generate text from input tokens
@@ -17,7 +20,8 @@ pub trait TextGeneration: Send + Sync {
 
     /// Generate text with token-by-token callback for streaming
     ///
-    /// The callback receives each generated token ID and its decoded text.
+    /// The callback receives each generated token ID and its decoded text,
+    /// and returns false to stop generation early.
     /// Default implementation just calls regular generate (no streaming support).
     fn generate_stream(
         &mut self,
@@ -77,7 +81,7 @@ impl GeneratorModel {
                     load_config.target.name()
                 );
 
-                let loader = UnifiedModelLoader::new()?;
+                let loader = UnifiedModelLoader::with_hub(load_config.hub.clone())?;
                 loader.load(load_config.clone())?
             }
         };
@@ -99,6 +103,17 @@ impl GeneratorModel {
     ///
     /// For ONNX models, this uses the model's built-in tokenizer.
     pub fn generate_text(&mut self, prompt: &str, max_new_tokens: usize) -> Result<String> {
+        self.generate_text_until(prompt, max_new_tokens, None)
+    }
+
+    /// Like `generate_text`, but stops between tokens once `deadline` passes
+    /// and returns `LocalTimeout` (see `local::timeout`)
+    pub fn generate_text_until(
+        &mut self,
+        prompt: &str,
+        max_new_tokens: usize,
+        deadline: Option<Deadline>,
+    ) -> Result<String> {
         // Downcast to LoadedOnnxModel to access tokenizer
         // This is safe because we only support ONNX models in Phase 5
         use super::loaders::onnx::LoadedOnnxModel;
@@ -118,7 +133,28 @@ impl GeneratorModel {
         }; // onnx_model borrow ends here
 
         // Generate tokens (requires mutable borrow of self)
-        let output_ids = self.generate(&input_ids, max_new_tokens)?;
+        let output_ids = match deadline {
+            None => self.generate(&input_ids, max_new_tokens)?,
+            Some(deadline) => {
+                let timed_out = Arc::new(AtomicBool::new(false));
+                let flag = Arc::clone(&timed_out);
+                let output_ids = self.backend.generate_stream(
+                    &input_ids,
+                    max_new_tokens,
+                    Box::new(move |_token_id, _token_text| {
+                        if deadline.expired() {
+                            flag.store(true, Ordering::Relaxed);
+                            return false;
+                        }
+                        true
+                    }),
+                )?;
+                if timed_out.load(Ordering::Relaxed) {
+                    return Err(deadline.timeout_error().into());
+                }
+                output_ids
+            }
+        };
 
         // Decode output (scope the borrow again)
         let response = {
@@ -140,6 +176,42 @@ impl GeneratorModel {
         self.backend.name()
     }
 
+    /// Number of tokens `text` encodes to with the model's tokenizer
+    pub fn count_tokens(&self, text: &str) -> Result<usize> {
+        use super::loaders::onnx::LoadedOnnxModel;
+
+        let onnx_model = self
+            .backend
+            .as_any()
+            .downcast_ref::<LoadedOnnxModel>()
+            .ok_or_else(|| anyhow::anyhow!("Backend is not an ONNX model"))?;
+        let encoding = onnx_model
+            .tokenizer()
+            .encode(text, true)
+            .map_err(|e| anyhow::anyhow!("Failed to encode text: {}", e))?;
+        Ok(encoding.len())
+    }
+
+    /// Context window in tokens (None if the model doesn't state it)
+    pub fn context_length(&self) -> Option<usize> {
+        use super::loaders::onnx::LoadedOnnxModel;
+
+        self.backend
+            .as_any()
+            .downcast_ref::<LoadedOnnxModel>()
+            .and_then(|model| model.context_length())
+    }
+
+    /// File the weights were loaded from (None for non-ONNX backends)
+    pub fn model_path(&self) -> Option<&Path> {
+        use super::loaders::onnx::LoadedOnnxModel;
+
+        self.backend
+            .as_any()
+            .downcast_ref::<LoadedOnnxModel>()
+            .map(|model| model.model_path())
+    }
+
     /// Get mutable reference to backend (for accessing ONNX model directly)
     pub fn backend_mut(&mut self) -> &mut dyn TextGeneration {
         self.backend.as_mut()
@@ -257,6 +329,7 @@ mod tests {
     #[ignore] // Requires downloaded Qwen model
     fn test_generator_qwen() {
         use crate::models::unified_loader::{ModelLoadConfig, ModelFamily, ModelSize};
+        use crate::models::HubSettings;
         use crate::config::ExecutionTarget;
 
         let config = GeneratorConfig::Pretrained(ModelLoadConfig {
@@ -265,6 +338,7 @@ mod tests {
             size: ModelSize::Small,
             target: ExecutionTarget::Cpu,
             repo_override: None,
+            hub: HubSettings::default(),
         });
 
         let generator = GeneratorModel::new(config);
@@ -0,0 +1,271 @@
+// Inventory of downloaded models and trained adapters (`shammah models list`)
+//
+// Scans `~/.shammah/models` and `~/.shammah/adapters` one level deep. Each
+// subdirectory or weights file is one entry; router/validator state and
+// sidecar metadata files are skipped. Family and size are read off the name
+// (`Qwen2.5-1.5B-Instruct`), falling back to the saved `ModelMetadata`.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use walkdir::WalkDir;
+
+use super::persistence::{load_model_metadata, ModelMetadata};
+use super::unified_loader::ModelFamily;
+
+/// File extensions that hold model weights
+const WEIGHT_EXTENSIONS: &[&str] = &["onnx", "safetensors", "bin", "gguf"];
+
+/// Where an entry was found
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelKind {
+    Model,
+    Adapter,
+}
+
+/// One downloaded model or adapter
+#[derive(Debug, Clone)]
+pub struct ModelEntry {
+    pub kind: ModelKind,
+    pub name: String,
+    pub path: PathBuf,
+    pub family: Option<ModelFamily>,
+    /// Parameter count as written in the name ("1.5B", "7b")
+    pub size: Option<String>,
+    /// Bytes on disk (recursive for directories)
+    pub bytes: u64,
+    pub modified: Option<SystemTime>,
+    pub metadata: Option<ModelMetadata>,
+    /// Matches the configured `backend.model_repo`
+    pub active: bool,
+}
+
+/// Scan `models_dir` and `adapters_dir`, flagging the entry for `active_repo`
+///
+/// Returns None if the models directory doesn't exist; a missing adapters
+/// directory just contributes no entries.
+pub fn scan(
+    models_dir: &Path,
+    adapters_dir: &Path,
+    active_repo: Option<&str>,
+) -> Result<Option<Vec<ModelEntry>>> {
+    if !models_dir.is_dir() {
+        return Ok(None);
+    }
+    let mut entries = scan_dir(models_dir, ModelKind::Model, active_repo)?;
+    if adapters_dir.is_dir() {
+        entries.extend(scan_dir(adapters_dir, ModelKind::Adapter, None)?);
+    }
+    Ok(Some(entries))
+}
+
+fn scan_dir(dir: &Path, kind: ModelKind, active_repo: Option<&str>) -> Result<Vec<ModelEntry>> {
+    let mut entries = Vec::new();
+    for entry in
+        std::fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?
+    {
+        let path = entry?.path();
+        let is_weights = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| WEIGHT_EXTENSIONS.contains(&ext));
+        if !path.is_dir() && !is_weights {
+            continue;
+        }
+
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let metadata = if is_weights {
+            load_model_metadata(&path).ok()
+        } else {
+            None
+        };
+        let family = family_from_name(&name).or_else(|| {
+            metadata
+                .as_ref()
+                .and_then(|m| family_from_name(&m.model_type))
+        });
+        entries.push(ModelEntry {
+            kind,
+            size: size_from_name(&name),
+            family,
+            bytes: disk_usage(&path),
+            modified: std::fs::metadata(&path).and_then(|m| m.modified()).ok(),
+            metadata,
+            active: active_repo.is_some_and(|repo| matches_repo(&name, repo)),
+            name,
+            path,
+        });
+    }
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(entries)
+}
+
+/// Total size of the files under `path`
+fn disk_usage(path: &Path) -> u64 {
+    WalkDir::new(path)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// Guess the model family from a directory, file or model type name
+fn family_from_name(name: &str) -> Option<ModelFamily> {
+    let name = name.to_lowercase();
+    [
+        ("qwen", ModelFamily::Qwen2),
+        ("gemma", ModelFamily::Gemma2),
+        ("llama", ModelFamily::Llama3),
+        ("mistral", ModelFamily::Mistral),
+        ("phi", ModelFamily::Phi),
+        ("deepseek", ModelFamily::DeepSeek),
+    ]
+    .into_iter()
+    .find(|(needle, _)| name.contains(needle))
+    .map(|(_, family)| family)
+}
+
+/// Parameter count token from a name, e.g. "1.5B" in `Qwen2.5-1.5B-Instruct`
+fn size_from_name(name: &str) -> Option<String> {
+    name.split(['-', '_', ' '])
+        .find(|token| {
+            token.len() > 1
+                && token.ends_with(['B', 'b'])
+                && token[..token.len() - 1]
+                    .chars()
+                    .all(|c| c.is_ascii_digit() || c == '.')
+                && token.starts_with(|c: char| c.is_ascii_digit())
+        })
+        .map(str::to_string)
+}
+
+/// Whether an on-disk name is the download of `repo` ("org/name")
+///
+/// Accepts the bare model name, the repo with `/` flattened to `--`, and the
+/// HuggingFace cache layout (`models--org--name`).
+fn matches_repo(name: &str, repo: &str) -> bool {
+    let flattened = repo.replace('/', "--");
+    let model_name = repo.rsplit('/').next().unwrap_or(repo);
+    name == model_name || name == flattened || name == format!("models--{}", flattened)
+}
+
+/// Human-readable byte count ("1.4 GB")
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+impl ModelEntry {
+    /// Multi-line listing for this entry
+    pub fn format(&self) -> String {
+        let marker = if self.active { "*" } else { " " };
+        let family = self.family.map(|f| f.name()).unwrap_or("unknown");
+        let modified = self
+            .modified
+            .map(|time| {
+                DateTime::<Local>::from(time)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string()
+            })
+            .unwrap_or_else(|| "-".to_string());
+        let mut out = format!(
+            "{} {}{}\n    family: {}  size: {}  on disk: {} ({} bytes)  modified: {}\n    path: {}",
+            marker,
+            self.name,
+            if self.active { "  (active)" } else { "" },
+            family,
+            self.size.as_deref().unwrap_or("-"),
+            format_bytes(self.bytes),
+            self.bytes,
+            modified,
+            self.path.display()
+        );
+        if let Some(metadata) = &self.metadata {
+            out.push_str(&format!(
+                "\n    metadata: {} (step {}, saved {})",
+                metadata.model_type, metadata.training_step, metadata.timestamp
+            ));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_name_parsing() {
+        assert_eq!(
+            family_from_name("Qwen2.5-1.5B-Instruct"),
+            Some(ModelFamily::Qwen2)
+        );
+        assert_eq!(
+            size_from_name("Qwen2.5-1.5B-Instruct").as_deref(),
+            Some("1.5B")
+        );
+        assert_eq!(size_from_name("gemma-2-9b-it").as_deref(), Some("9b"));
+        assert_eq!(size_from_name("latest.safetensors"), None);
+        assert_eq!(family_from_name("latest.safetensors"), None);
+
+        let repo = "onnx-community/Qwen2.5-1.5B-Instruct";
+        assert!(matches_repo("Qwen2.5-1.5B-Instruct", repo));
+        assert!(matches_repo(
+            "models--onnx-community--Qwen2.5-1.5B-Instruct",
+            repo
+        ));
+        assert!(!matches_repo("Qwen2.5-3B-Instruct", repo));
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+    }
+
+    #[test]
+    fn test_scan() {
+        let dir = tempfile::tempdir().unwrap();
+        let models = dir.path().join("models");
+        let adapters = dir.path().join("adapters");
+        assert!(scan(&models, &adapters, None).unwrap().is_none());
+
+        let model_dir = models.join("Qwen2.5-1.5B-Instruct");
+        fs::create_dir_all(model_dir.join("onnx")).unwrap();
+        fs::write(model_dir.join("onnx").join("model.onnx"), vec![0u8; 100]).unwrap();
+        fs::write(model_dir.join("config.json"), "{}").unwrap();
+        fs::write(models.join("threshold_router.json"), "{}").unwrap();
+
+        let entries = scan(
+            &models,
+            &adapters,
+            Some("onnx-community/Qwen2.5-1.5B-Instruct"),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].active);
+        assert_eq!(entries[0].bytes, 102);
+        assert_eq!(entries[0].kind, ModelKind::Model);
+
+        fs::create_dir_all(&adapters).unwrap();
+        fs::write(adapters.join("latest.safetensors"), vec![0u8; 10]).unwrap();
+        let entries = scan(&models, &adapters, None).unwrap().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].kind, ModelKind::Adapter);
+        assert!(!entries[0].active);
+    }
+}
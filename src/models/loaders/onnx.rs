@@ -12,18 +12,28 @@ use tokenizers::Tokenizer;
 use tracing::{debug, info, warn};
 
 use super::onnx_config::{ExecutionProvider as ConfigExecutionProvider, ModelSize, OnnxLoadConfig};
-use crate::models::download::{DownloadProgress, ModelDownloader};
+use crate::models::download::{DownloadProgress, HubSettings, ModelDownloader};
 use crate::models::generator_new::TextGeneration;
 
 /// ONNX model loader - downloads and loads models from HuggingFace
 pub struct OnnxLoader {
     cache_dir: PathBuf,
+    endpoint: Option<String>,
 }
 
 impl OnnxLoader {
     /// Create new ONNX loader with cache directory
     pub fn new(cache_dir: PathBuf) -> Self {
-        Self { cache_dir }
+        Self {
+            cache_dir,
+            endpoint: None,
+        }
+    }
+
+    /// Download from this HuggingFace Hub endpoint (None = HF_ENDPOINT or huggingface.co)
+    pub fn with_endpoint(mut self, endpoint: Option<String>) -> Self {
+        self.endpoint = endpoint;
+        self
     }
 
     /// Create ONNX Runtime session with execution providers
@@ -157,6 +167,7 @@ impl OnnxLoader {
 
         // Step 3: Load tokenizer
         let tokenizer = self.load_tokenizer(&model_dir)?;
+        let context_length = read_context_length(&model_dir);
 
         // Step 4: Create ONNX Runtime session
         let session = self.create_session(&model_path, config)?;
@@ -169,6 +180,7 @@ impl OnnxLoader {
             model_name: config.model_name.clone(),
             model_size: config.size,
             model_path,
+            context_length,
         })
     }
 
@@ -180,7 +192,10 @@ impl OnnxLoader {
         let repo = config.huggingface_repo();
         info!("Downloading from HuggingFace: {}", repo);
 
-        let downloader = ModelDownloader::new()?;
+        let downloader = ModelDownloader::with_hub(HubSettings {
+            models_dir: Some(self.cache_dir.clone()),
+            endpoint: self.endpoint.clone(),
+        });
 
         // Estimate size based on model size
         let estimated_size_gb = match config.size {
@@ -227,6 +242,17 @@ pub struct LoadedOnnxModel {
     model_name: String,
     model_size: ModelSize,
     model_path: PathBuf,
+    /// Context window in tokens, if config.json states it
+    context_length: Option<usize>,
+}
+
+/// Context window from a model's config.json (`max_position_embeddings`)
+fn read_context_length(model_dir: &Path) -> Option<usize> {
+    let config = std::fs::read_to_string(model_dir.join("config.json")).ok()?;
+    let config: serde_json::Value = serde_json::from_str(&config).ok()?;
+    config["max_position_embeddings"]
+        .as_u64()
+        .and_then(|tokens| usize::try_from(tokens).ok())
 }
 
 impl LoadedOnnxModel {
@@ -281,6 +307,11 @@ impl LoadedOnnxModel {
         &self.model_path
     }
 
+    /// Context window in tokens (None if the model config doesn't say)
+    pub fn context_length(&self) -> Option<usize> {
+        self.context_length
+    }
+
     /// Autoregressive text generation with KV cache (Phase 5.1)
     fn generate_autoregressive(&mut self, input_ids: &[u32], max_new_tokens: usize) -> Result<Vec<u32>> {
         self.generate_autoregressive_with_callback(input_ids, max_new_tokens, None)
@@ -291,7 +322,7 @@ impl LoadedOnnxModel {
         &mut self,
         input_ids: &[u32],
         max_new_tokens: usize,
-        mut token_callback: Option<Box<dyn FnMut(u32, &str) + Send>>,
+        mut token_callback: Option<crate::models::TokenCallback>,
     ) -> Result<Vec<u32>> {
         info!("ONNX autoregressive generation: {} input tokens, max {} new tokens",
               input_ids.len(), max_new_tokens);
@@ -308,6 +339,8 @@ impl LoadedOnnxModel {
         let mut past_key_values: Vec<(DynValue, DynValue)> = Vec::new();
         let mut past_seq_len = 0;
 
+        let mut text_stream = crate::text::TokenTextStream::new();
+
         // Generation loop
         for step in 0..max_new_tokens {
             debug!("Generation step {}/{}", step + 1, max_new_tokens);
@@ -357,10 +390,23 @@ impl LoadedOnnxModel {
 
             // 6. Call streaming callback if provided
             if let Some(ref mut callback) = token_callback {
-                // Decode just this token to text
-                let token_text = self.tokenizer.decode(&[next_token], false)
-                    .unwrap_or_else(|_| format!("[token_{}]", next_token));
-                callback(next_token, &token_text);
+                // Decode incrementally so characters split across tokens
+                // (emoji, CJK) are emitted whole, never as U+FFFD halves
+                let decoded = text_stream.push(next_token, |ids| {
+                    self.tokenizer
+                        .decode(ids, false)
+                        .map_err(|e| anyhow::anyhow!("Failed to decode token: {}", e))
+                });
+                let token_text = match decoded {
+                    Ok(Some(text)) => text,
+                    // Partial character: wait for the token that completes it
+                    Ok(None) => continue,
+                    Err(_) => format!("[token_{}]", next_token),
+                };
+                if !callback(next_token, &token_text) {
+                    info!("Streaming callback requested stop");
+                    break;
+                }
             }
         }
 
@@ -716,6 +762,22 @@ mod tests {
     use super::*;
     use crate::models::loaders::onnx_config::ExecutionProvider;
 
+    #[test]
+    fn test_read_context_length() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(read_context_length(dir.path()), None);
+
+        std::fs::write(
+            dir.path().join("config.json"),
+            r#"{"model_type": "qwen2", "max_position_embeddings": 32768}"#,
+        )
+        .unwrap();
+        assert_eq!(read_context_length(dir.path()), Some(32768));
+
+        std::fs::write(dir.path().join("config.json"), r#"{"model_type": "qwen2"}"#).unwrap();
+        assert_eq!(read_context_length(dir.path()), None);
+    }
+
     #[test]
     fn test_execution_providers_default() {
         let providers = ExecutionProvider::default_for_platform();
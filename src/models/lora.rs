@@ -496,6 +496,12 @@ impl TrainingCoordinator {
         Ok(())
     }
 
+    /// Use a different training queue file (default: ~/.shammah/training_queue.jsonl)
+    pub fn with_queue_path(mut self, queue_path: std::path::PathBuf) -> Self {
+        self.queue_path = queue_path;
+        self
+    }
+
     /// Get training queue path
     pub fn queue_path(&self) -> &std::path::Path {
         &self.queue_path
@@ -7,12 +7,14 @@ pub mod common;
 pub mod compatibility; // Model compatibility matrix (which models work with which targets)
 pub mod download;
 pub mod generator_new; // New unified generator (ONNX-based)
+pub mod inventory; // Downloaded models and adapters (`shammah models list`)
 pub mod learning;
 pub mod loaders; // ONNX model loader
 pub mod lora; // LoRA fine-tuning configuration (Python training, Phase 5)
 pub mod manager;
 pub mod model_selector;
 pub mod persistence;
+pub mod quality; // Quality scoring for teacher responses (`[training] quality_scorer`)
 pub mod sampling; // Context-aware sampling system
 pub mod threshold_router;
 pub mod threshold_validator;
@@ -22,7 +24,10 @@ pub mod tool_prompt;  // Phase 6: Format tool definitions for model prompts
 pub mod unified_loader; // Generic loader for ONNX models
 
 pub use adapters::{AdapterRegistry, LocalModelAdapter, GenerationConfig as AdapterGenerationConfig};
-pub use bootstrap::{BootstrapLoader, DownloadProgressSnapshot, GeneratorState};
+pub use bootstrap::{
+    wait_for_ready, BootstrapLoader, DownloadProgressSnapshot, GeneratorState, LocalWaitOutcome,
+    ModelInfo,
+};
 #[allow(deprecated)]
 pub use common::{
     device_info, get_device_with_preference, is_metal_available, DevicePreference,
@@ -32,7 +37,7 @@ pub use compatibility::{
     get_available_sizes, get_compatible_families, get_repository, get_supported_targets,
     is_compatible, ModelCompatibility,
 };
-pub use download::{DownloadProgress, ModelDownloader};
+pub use download::{DownloadProgress, HubSettings, ModelDownloader};
 pub use generator_new::{GeneratorModel, TextGeneration, TokenCallback};
 pub use learning::{LearningModel, ModelExpectation, ModelPrediction, ModelStats, PredictionData};
 pub use lora::{
@@ -42,7 +47,9 @@ pub use lora::{
 pub use manager::{ModelManager, OverallStats, TrainingReport};
 pub use model_selector::{ModelSelector, QwenSize};
 pub use persistence::{load_model_metadata, model_exists, save_model_with_metadata, ModelMetadata};
-pub use sampling::{ComparisonResult, QueryCategory, Sampler, SamplingConfig, SamplingDecision};
+pub use sampling::{
+    ComparisonResult, QueryCategory, Sampler, SamplingConfig, SamplingDecision, TemperatureConfig,
+};
 pub use threshold_router::{
     QueryCategory as ThresholdQueryCategory, ThresholdRouter, ThresholdRouterStats,
 };
@@ -1,10 +1,12 @@
 // Model persistence utilities
-// Handles saving/loading weights + configuration
+// Handles saving/loading weights + configuration, and crash-safe JSON
+// state files (router stats, validator stats, learned responses)
 
 use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use super::common::ModelConfig;
 
@@ -77,6 +79,65 @@ pub fn model_exists(weights_path: &Path) -> bool {
     weights_path.exists() && metadata_path.exists()
 }
 
+/// `path` with `suffix` appended to its file name (`x.json` -> `x.json.bak`)
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+/// Backup copy kept next to a state file written by [`write_state_atomic`]
+pub fn backup_path(path: &Path) -> PathBuf {
+    with_suffix(path, ".bak")
+}
+
+/// Write a state file so a crash mid-save never leaves it truncated
+///
+/// The current file is copied to `<file>.bak` first, then the new contents
+/// go to a temp file in the same directory and are renamed into place.
+pub fn write_state_atomic(path: &Path, contents: &str) -> Result<()> {
+    if path.exists() {
+        fs::copy(path, backup_path(path))
+            .with_context(|| format!("Failed to back up {}", path.display()))?;
+    }
+
+    let temp_path = with_suffix(path, ".tmp");
+    let mut file = fs::File::create(&temp_path)
+        .with_context(|| format!("Failed to create {}", temp_path.display()))?;
+    std::io::Write::write_all(&mut file, contents.as_bytes())?;
+    file.sync_all()?;
+    drop(file);
+
+    fs::rename(&temp_path, path)
+        .with_context(|| format!("Failed to replace {}", path.display()))
+}
+
+/// Read a JSON state file, falling back to its `.bak` copy if it won't parse
+pub fn read_state_with_backup<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let read = |path: &Path| -> Result<T> {
+        let json = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&json).with_context(|| format!("Failed to parse {}", path.display()))
+    };
+
+    match read(path) {
+        Ok(value) => Ok(value),
+        Err(e) => {
+            let backup = backup_path(path);
+            if !backup.exists() {
+                return Err(e);
+            }
+            let value = read(&backup).map_err(|_| e)?;
+            tracing::warn!(
+                "{} is unreadable, restored state from {}",
+                path.display(),
+                backup.display()
+            );
+            Ok(value)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -110,4 +171,22 @@ mod tests {
         // Should exist now
         assert!(model_exists(path));
     }
+
+    #[test]
+    fn test_state_file_falls_back_to_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state.json");
+
+        write_state_atomic(&path, "[1]").unwrap();
+        write_state_atomic(&path, "[1,2]").unwrap();
+        assert_eq!(fs::read_to_string(backup_path(&path)).unwrap(), "[1]");
+        assert_eq!(read_state_with_backup::<Vec<u32>>(&path).unwrap(), vec![1, 2]);
+
+        // Truncated primary (killed mid-write by an older version)
+        fs::write(&path, "[1,").unwrap();
+        assert_eq!(read_state_with_backup::<Vec<u32>>(&path).unwrap(), vec![1]);
+
+        fs::write(backup_path(&path), "").unwrap();
+        assert!(read_state_with_backup::<Vec<u32>>(&path).is_err());
+    }
 }
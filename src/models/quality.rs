@@ -0,0 +1,242 @@
+// Quality scoring for teacher responses
+//
+// The score (0.0-1.0) decides how much a teacher response counts when the
+// local generator learns from it: responses below LEARN_THRESHOLD are kept
+// out of neural training, and only the best-scoring responses per pattern
+// are remembered. Which scorer runs is picked with `[training] quality_scorer`.
+
+use serde::{Deserialize, Serialize};
+
+/// Minimum score for a response to be used as a training example
+pub const LEARN_THRESHOLD: f64 = 0.7;
+
+/// Which quality scorer to use (`[training] quality_scorer`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QualityScorerKind {
+    /// Rule-based: length, refusals, code blocks
+    #[default]
+    Heuristic,
+    /// Agreement between the local and teacher answers (heuristic when
+    /// there is no local answer)
+    Semantic,
+}
+
+impl QualityScorerKind {
+    /// Build the configured scorer
+    pub fn build(self) -> Box<dyn QualityScorer> {
+        match self {
+            QualityScorerKind::Heuristic => Box::new(HeuristicScorer::default()),
+            QualityScorerKind::Semantic => Box::new(SemanticSimilarityScorer::default()),
+        }
+    }
+}
+
+/// Scores a teacher response before the local model learns from it
+#[async_trait::async_trait]
+pub trait QualityScorer: Send + Sync {
+    /// Name used in config and logs
+    fn name(&self) -> &'static str;
+
+    /// Score `response` to `query`, from 0.0 (don't learn from it) to 1.0
+    ///
+    /// `local_response` is the local model's answer to the same query, if
+    /// one was generated.
+    async fn score(&self, query: &str, response: &str, local_response: Option<&str>) -> f64;
+}
+
+/// Openings that mark a response as a refusal rather than an answer
+const REFUSAL_MARKERS: &[&str] = &[
+    "i can't help with",
+    "i cannot help with",
+    "i can't assist",
+    "i cannot assist",
+    "i'm not able to help",
+    "i am not able to help",
+    "i'm unable to",
+    "i am unable to",
+    "i won't be able to",
+    "sorry, but i can't",
+    "sorry, but i cannot",
+];
+
+/// Only the start of a response is checked for refusals, so an answer that
+/// quotes one isn't penalized
+const REFUSAL_WINDOW_CHARS: usize = 200;
+
+/// Rule-based scorer; every adjustment is a public field so the weighting
+/// can be read and tuned
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeuristicScorer {
+    /// Score of a plain answer of reasonable length
+    pub base: f64,
+    /// Responses shorter than this (in characters) lose `short_penalty`
+    pub min_chars: usize,
+    pub short_penalty: f64,
+    /// Responses longer than this (in characters) lose `long_penalty`
+    pub max_chars: usize,
+    pub long_penalty: f64,
+    /// Added when the response contains a fenced code block
+    pub code_bonus: f64,
+    /// Score of a refusal, whatever else it contains
+    pub refusal_score: f64,
+}
+
+impl Default for HeuristicScorer {
+    fn default() -> Self {
+        Self {
+            base: LEARN_THRESHOLD,
+            min_chars: 20,
+            short_penalty: 0.3,
+            max_chars: 8000,
+            long_penalty: 0.1,
+            code_bonus: 0.2,
+            refusal_score: 0.1,
+        }
+    }
+}
+
+impl HeuristicScorer {
+    /// Score without the async wrapper
+    pub fn score_text(&self, response: &str) -> f64 {
+        let text = response.trim();
+        if text.is_empty() {
+            return 0.0;
+        }
+        if is_refusal(text) {
+            return self.refusal_score;
+        }
+
+        let chars = text.chars().count();
+        let mut score = self.base;
+        if chars < self.min_chars {
+            score -= self.short_penalty;
+        }
+        if chars > self.max_chars {
+            score -= self.long_penalty;
+        }
+        if has_code_block(text) {
+            score += self.code_bonus;
+        }
+        score.clamp(0.0, 1.0)
+    }
+}
+
+#[async_trait::async_trait]
+impl QualityScorer for HeuristicScorer {
+    fn name(&self) -> &'static str {
+        "heuristic"
+    }
+
+    async fn score(&self, _query: &str, response: &str, _local_response: Option<&str>) -> f64 {
+        self.score_text(response)
+    }
+}
+
+/// Scores by how closely the local answer matched the teacher's, using the
+/// shared embedding service (`metrics::semantic_similarity`)
+///
+/// Without a local answer to compare, or if embedding fails, it falls back
+/// to `fallback`.
+#[derive(Debug, Clone, Default)]
+pub struct SemanticSimilarityScorer {
+    pub fallback: HeuristicScorer,
+}
+
+#[async_trait::async_trait]
+impl QualityScorer for SemanticSimilarityScorer {
+    fn name(&self) -> &'static str {
+        "semantic"
+    }
+
+    async fn score(&self, _query: &str, response: &str, local_response: Option<&str>) -> f64 {
+        let Some(local_response) = local_response else {
+            return self.fallback.score_text(response);
+        };
+        match crate::metrics::semantic_similarity(local_response, response).await {
+            Ok(similarity) => similarity.clamp(0.0, 1.0),
+            Err(e) => {
+                tracing::warn!("Semantic quality scoring failed, using heuristics: {}", e);
+                self.fallback.score_text(response)
+            }
+        }
+    }
+}
+
+fn is_refusal(text: &str) -> bool {
+    let opening: String = text
+        .chars()
+        .take(REFUSAL_WINDOW_CHARS)
+        .collect::<String>()
+        .to_lowercase()
+        .replace('’', "'");
+    REFUSAL_MARKERS.iter().any(|marker| opening.contains(marker))
+}
+
+fn has_code_block(text: &str) -> bool {
+    text.lines()
+        .filter(|line| line.trim_start().starts_with("```"))
+        .count()
+        >= 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heuristic_scores() {
+        let scorer = HeuristicScorer::default();
+
+        let plain = "Use `Vec::with_capacity` when you know the final size up front.";
+        assert_eq!(scorer.score_text(plain), LEARN_THRESHOLD);
+
+        let code = "Like this:\n\n```rust\nlet v = Vec::with_capacity(10);\n```";
+        assert!(scorer.score_text(code) > scorer.score_text(plain));
+
+        assert!(scorer.score_text("Yes.") < LEARN_THRESHOLD);
+        assert!(scorer.score_text(&"word ".repeat(2000)) < LEARN_THRESHOLD);
+        assert_eq!(scorer.score_text("   "), 0.0);
+    }
+
+    #[test]
+    fn test_refusals_score_low() {
+        let scorer = HeuristicScorer::default();
+        let refusal = "I’m sorry, but I can’t help with that request. \
+                       ```\nnothing here\n```";
+        assert_eq!(scorer.score_text(refusal), scorer.refusal_score);
+
+        // A refusal quoted deep in an answer doesn't count
+        let quoted = format!("{}\n\nThe bot replied \"I can't help with that\".", "x".repeat(300));
+        assert!(scorer.score_text(&quoted) >= LEARN_THRESHOLD);
+    }
+
+    #[tokio::test]
+    async fn test_semantic_scorer() {
+        let scorer = QualityScorerKind::Semantic.build();
+        assert_eq!(scorer.name(), "semantic");
+
+        let response = "Rust is a systems programming language";
+        assert_eq!(scorer.score("q", response, Some(response)).await, 1.0);
+        assert!(scorer.score("q", response, Some("Python web framework")).await < 0.3);
+
+        // No local answer: heuristic fallback
+        assert_eq!(
+            scorer.score("q", response, None).await,
+            HeuristicScorer::default().score_text(response)
+        );
+    }
+
+    #[test]
+    fn test_scorer_kind_config() {
+        #[derive(Deserialize)]
+        struct Training {
+            #[serde(default)]
+            quality_scorer: QualityScorerKind,
+        }
+        let parsed: Training = toml::from_str("quality_scorer = \"semantic\"").unwrap();
+        assert_eq!(parsed.quality_scorer, QualityScorerKind::Semantic);
+        let parsed: Training = toml::from_str("").unwrap();
+        assert_eq!(parsed.quality_scorer.build().name(), "heuristic");
+    }
+}
@@ -1,9 +1,10 @@
 // Context-Aware Sampling System
 // Samples 5% of queries to Claude with full conversation context for validation
 
-use anyhow::{Context, Result};
+use anyhow::Result;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Sampling configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -47,6 +48,33 @@ pub enum QueryCategory {
 }
 
 impl QueryCategory {
+    /// Every category, in display order
+    pub const ALL: [QueryCategory; 5] = [
+        QueryCategory::Architecture,
+        QueryCategory::Security,
+        QueryCategory::Performance,
+        QueryCategory::Testing,
+        QueryCategory::General,
+    ];
+
+    /// Lowercase name, as used for config keys
+    pub fn name(&self) -> &'static str {
+        match self {
+            QueryCategory::Architecture => "architecture",
+            QueryCategory::Security => "security",
+            QueryCategory::Performance => "performance",
+            QueryCategory::Testing => "testing",
+            QueryCategory::General => "general",
+        }
+    }
+
+    /// Parse a category name (case-insensitive)
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|category| category.name().eq_ignore_ascii_case(name.trim()))
+    }
+
     /// Detect category from query text
     pub fn from_query(query: &str) -> Self {
         let query_lower = query.to_lowercase();
@@ -116,6 +144,98 @@ impl QueryCategory {
     }
 }
 
+/// Teacher sampling temperature, optionally overridden per query category
+///
+/// Configured in `[temperature]`, e.g. `default = 0.7` plus
+/// `[temperature.categories]` with `security = 0.2`, `architecture = 0.9`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TemperatureConfig {
+    /// Temperature for categories without an override (None: provider default)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<f32>,
+
+    /// Overrides keyed by category name (see `QueryCategory::name`)
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub categories: BTreeMap<String, f32>,
+}
+
+impl TemperatureConfig {
+    /// Highest temperature any supported provider accepts
+    pub const MAX: f32 = 2.0;
+
+    /// Temperature for a category, falling back to the default
+    pub fn for_category(&self, category: QueryCategory) -> Option<f32> {
+        self.categories
+            .iter()
+            .find(|(name, _)| QueryCategory::from_name(name) == Some(category))
+            .map(|(_, temperature)| *temperature)
+            .or(self.default)
+    }
+
+    /// Classify a query and return its category's temperature
+    pub fn for_query(&self, query: &str) -> Option<f32> {
+        let category = QueryCategory::from_query(query);
+        let temperature = self.for_category(category);
+        tracing::debug!(
+            "Query category {} -> temperature {:?}",
+            category.name(),
+            temperature
+        );
+        temperature
+    }
+
+    /// Reject unknown category names and out-of-range temperatures
+    pub fn validate(&self) -> Result<()> {
+        let check = |key: &str, temperature: f32| {
+            if !(0.0..=Self::MAX).contains(&temperature) {
+                anyhow::bail!(
+                    "{} ({}) must be between 0.0 and {}",
+                    key,
+                    temperature,
+                    Self::MAX
+                );
+            }
+            Ok(())
+        };
+
+        if let Some(default) = self.default {
+            check("temperature.default", default)?;
+        }
+        for (name, temperature) in &self.categories {
+            if QueryCategory::from_name(name).is_none() {
+                let names: Vec<&str> = QueryCategory::ALL.iter().map(|c| c.name()).collect();
+                anyhow::bail!(
+                    "Unknown query category '{}' in [temperature.categories] (expected one of: {})",
+                    name,
+                    names.join(", ")
+                );
+            }
+            check(&format!("temperature.categories.{}", name), *temperature)?;
+        }
+        Ok(())
+    }
+
+    /// Human-readable mapping, one line per category (for /sampling)
+    pub fn format_mapping(&self) -> String {
+        let mut out = String::from("Teacher temperature by query category:\n");
+        for category in QueryCategory::ALL {
+            let has_override = self
+                .categories
+                .keys()
+                .any(|name| QueryCategory::from_name(name) == Some(category));
+            let value = match self.for_category(category) {
+                Some(temperature) if has_override => format!("{:.2}", temperature),
+                Some(temperature) => format!("{:.2} (default)", temperature),
+                None => "provider default".to_string(),
+            };
+            out.push_str(&format!("  {:<14}{}\n", category.name(), value));
+        }
+        out.push_str("\nSet in ~/.shammah/config.toml under [temperature] and [temperature.categories].");
+        out
+    }
+}
+
 /// Sampling decision
 #[derive(Debug, Clone)]
 pub struct SamplingDecision {
@@ -289,6 +409,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_temperature_follows_query_category() {
+        let config = TemperatureConfig {
+            default: Some(0.7),
+            categories: BTreeMap::from([
+                ("security".to_string(), 0.2),
+                ("Architecture".to_string(), 0.9),
+            ]),
+        };
+        config.validate().unwrap();
+
+        assert_eq!(config.for_query("Is this vulnerable to SQL injection?"), Some(0.2));
+        assert_eq!(config.for_query("How should I design the architecture?"), Some(0.9));
+        // No override: global default
+        assert_eq!(config.for_query("How can I optimize this?"), Some(0.7));
+        assert_eq!(config.for_category(QueryCategory::General), Some(0.7));
+
+        // Nothing configured: leave it to the provider
+        assert_eq!(TemperatureConfig::default().for_query("What's 2+2?"), None);
+    }
+
+    #[test]
+    fn test_temperature_config_validation() {
+        let unknown = TemperatureConfig {
+            categories: BTreeMap::from([("brainstorming".to_string(), 1.0)]),
+            ..Default::default()
+        };
+        assert!(unknown.validate().is_err());
+
+        let too_hot = TemperatureConfig {
+            default: Some(3.0),
+            ..Default::default()
+        };
+        assert!(too_hot.validate().is_err());
+    }
+
     #[test]
     fn test_sampling_multipliers() {
         let config = SamplingConfig::default();
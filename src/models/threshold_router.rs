@@ -1,6 +1,7 @@
 // Threshold-based Router - Simple statistics-based routing
 // Shows immediate improvement without neural network training overhead
 
+use super::persistence::{read_state_with_backup, write_state_atomic};
 use anyhow::Result;
 use fs2::FileExt;
 use serde::{Deserialize, Serialize};
@@ -389,11 +390,9 @@ impl ThresholdRouter {
             serde_json::from_str(&json)?
         };
 
-        // Write atomically (write to temp, then rename)
-        let temp_path = path.with_extension("tmp");
+        // Write atomically (backup, write to temp, then rename)
         let json = serde_json::to_string_pretty(&to_save)?;
-        std::fs::write(&temp_path, json)?;
-        std::fs::rename(temp_path, path)?;
+        write_state_atomic(path, &json)?;
 
         // Mark that we've saved (using atomic bool for thread safety)
         self.has_saved_this_session.store(true, Ordering::Relaxed);
@@ -449,8 +448,7 @@ impl ThresholdRouter {
     /// Load router state from disk
     /// Generates a new session ID to represent this program run
     pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let json = std::fs::read_to_string(path)?;
-        let mut router: ThresholdRouter = serde_json::from_str(&json)?;
+        let mut router: ThresholdRouter = read_state_with_backup(path.as_ref())?;
         // Generate NEW session ID for this program run
         router.session_id = Uuid::new_v4().to_string();
         // Mark as not yet saved in this session
@@ -1,6 +1,7 @@
 // Threshold-based Validator - Simple heuristics for quality assessment
 // Uses rule-based checks instead of neural network
 
+use super::persistence::{read_state_with_backup, write_state_atomic};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -342,14 +343,12 @@ impl ThresholdValidator {
     /// Save validator state
     pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
         let json = serde_json::to_string_pretty(&self.stats)?;
-        std::fs::write(path, json)?;
-        Ok(())
+        write_state_atomic(path.as_ref(), &json)
     }
 
     /// Load validator state
     pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let json = std::fs::read_to_string(path)?;
-        let stats = serde_json::from_str(&json)?;
+        let stats = read_state_with_backup(path.as_ref())?;
         Ok(Self {
             stats,
             min_length: 20,
@@ -110,7 +110,7 @@ impl ToolPromptFormatter {
             // Truncate very long results
             let content = if result.content.len() > 2000 {
                 format!("{}...\n\n(truncated, {} total characters)",
-                    &result.content[..2000],
+                    crate::text::truncate_chars(&result.content, 2000),
                     result.content.len())
             } else {
                 result.content.clone()
@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 use crate::config::ExecutionTarget;
-use super::download::ModelDownloader;
+use super::download::{HubSettings, ModelDownloader};
 use super::generator_new::TextGeneration;
 use super::loaders::onnx::{OnnxLoader, LoadedOnnxModel};
 use super::loaders::onnx_config::{OnnxLoadConfig, ModelSize as OnnxModelSize};
@@ -65,6 +65,9 @@ pub struct ModelLoadConfig {
     pub target: ExecutionTarget,
     /// Optional: override HuggingFace repository (for custom models)
     pub repo_override: Option<String>,
+    /// Where models are downloaded from and cached
+    #[serde(default)]
+    pub hub: HubSettings,
 }
 
 impl ModelLoadConfig {
@@ -201,12 +204,7 @@ struct ModelCache {
 }
 
 impl ModelCache {
-    fn new() -> Result<Self> {
-        // Use standard HuggingFace cache location
-        let cache_root = dirs::home_dir()
-            .context("Failed to determine home directory")?
-            .join(".cache/huggingface/hub");
-
+    fn new(cache_root: PathBuf) -> Result<Self> {
         std::fs::create_dir_all(&cache_root)
             .context("Failed to create HuggingFace cache directory")?;
 
@@ -279,14 +277,21 @@ impl ModelCache {
 pub struct UnifiedModelLoader {
     downloader: ModelDownloader,
     cache: ModelCache,
+    hub: HubSettings,
 }
 
 impl UnifiedModelLoader {
-    /// Create new unified loader
+    /// Create new unified loader (HF_HOME / HF_ENDPOINT or the default HF cache)
     pub fn new() -> Result<Self> {
+        Self::with_hub(HubSettings::default())
+    }
+
+    /// Create unified loader for configured hub settings
+    pub fn with_hub(hub: HubSettings) -> Result<Self> {
         Ok(Self {
-            downloader: ModelDownloader::new()?,
-            cache: ModelCache::new()?,
+            downloader: ModelDownloader::with_hub(hub.clone()),
+            cache: ModelCache::new(hub.cache_dir())?,
+            hub,
         })
     }
 
@@ -306,7 +311,8 @@ impl UnifiedModelLoader {
                 let onnx_config = self.to_onnx_config(&config)?;
 
                 // Load via ONNX
-                let onnx_loader = OnnxLoader::new(onnx_config.cache_dir.clone());
+                let onnx_loader = OnnxLoader::new(onnx_config.cache_dir.clone())
+                    .with_endpoint(self.hub.endpoint.clone());
                 let model = onnx_loader
                     .load_model_sync(&onnx_config)
                     .context("Failed to load ONNX model")?;
@@ -372,10 +378,7 @@ impl UnifiedModelLoader {
 
     /// Convert ModelLoadConfig to OnnxLoadConfig (Phase 5 helper)
     fn to_onnx_config(&self, config: &ModelLoadConfig) -> Result<OnnxLoadConfig> {
-        // Get cache directory
-        let cache_dir = dirs::home_dir()
-            .context("Failed to determine home directory")?
-            .join(".cache/huggingface/hub");
+        let cache_dir = self.hub.cache_dir();
 
         // Map unified ModelSize to ONNX ModelSize
         let onnx_size = match config.size {
@@ -631,9 +634,7 @@ impl UnifiedModelLoader {
         tracing::info!("Loading model via ONNX Runtime (Phase 3)");
 
         // Create cache directory
-        let cache_dir = dirs::home_dir()
-            .context("Failed to determine home directory")?
-            .join(".cache/huggingface/hub");
+        let cache_dir = self.hub.cache_dir();
 
         std::fs::create_dir_all(&cache_dir)
             .context("Failed to create cache directory")?;
@@ -647,7 +648,8 @@ impl UnifiedModelLoader {
         };
 
         // Create ONNX loader
-        let loader = OnnxLoader::new(config.cache_dir.clone());
+        let loader = OnnxLoader::new(config.cache_dir.clone())
+            .with_endpoint(self.hub.endpoint.clone());
 
         // Load model
         let model = loader.load_model_sync(&config)
@@ -685,6 +687,7 @@ mod tests {
             size: ModelSize::Small,
             target: ExecutionTarget::Cpu,
             repo_override: None,
+            hub: HubSettings::default(),
         };
         let repo = loader.resolve_repository(&config).unwrap();
         assert_eq!(repo, "onnx-community/Qwen2.5-1.5B-Instruct");
@@ -696,6 +699,7 @@ mod tests {
             size: ModelSize::Small,
             target: ExecutionTarget::Cpu,
             repo_override: None,
+            hub: HubSettings::default(),
         };
         let repo = loader.resolve_repository(&config).unwrap();
         assert_eq!(repo, "onnx-community/gemma-3-270m-it-ONNX");
@@ -707,6 +711,7 @@ mod tests {
             size: ModelSize::Medium,
             target: ExecutionTarget::Cpu,
             repo_override: None,
+            hub: HubSettings::default(),
         };
         let repo = loader.resolve_repository(&config).unwrap();
         assert_eq!(repo, "onnx-community/Llama-3.2-3B-Instruct-ONNX");
@@ -724,6 +729,7 @@ mod tests {
             size: ModelSize::Medium,
             target: ExecutionTarget::CoreML,
             repo_override: None,
+            hub: HubSettings::default(),
         };
         let repo = loader.resolve_repository(&config).unwrap();
         assert_eq!(repo, "onnx-community/Qwen2.5-Coder-3B-Instruct");
@@ -739,6 +745,7 @@ mod tests {
             size: ModelSize::Small,
             target: ExecutionTarget::Cpu,
             repo_override: Some("custom-org/custom-model".to_string()),
+            hub: HubSettings::default(),
         };
         let repo = loader.resolve_repository(&config).unwrap();
         assert_eq!(repo, "custom-org/custom-model");
@@ -0,0 +1,345 @@
+// Circuit breaker for teacher API calls
+//
+// When the teacher API is down, every query would otherwise wait for its own
+// slow failure. After `failure_threshold` consecutive failures within
+// `failure_window`, the circuit opens and calls fail fast for `cooldown`.
+// After the cooldown a single probe call is let through (half-open): success
+// closes the circuit, failure re-opens it.
+
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Circuit breaker thresholds
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures that open the circuit
+    pub failure_threshold: usize,
+    /// Failures older than this don't count toward the threshold
+    pub failure_window: Duration,
+    /// How long the circuit stays open before probing again
+    pub cooldown: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            failure_window: Duration::from_secs(60),
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Circuit state, as reported in health output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Calls go through normally
+    Closed,
+    /// Calls fail fast until the cooldown ends
+    Open,
+    /// Cooldown over; one probe call is testing recovery
+    HalfOpen,
+}
+
+impl std::fmt::Display for CircuitState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CircuitState::Closed => write!(f, "closed"),
+            CircuitState::Open => write!(f, "open"),
+            CircuitState::HalfOpen => write!(f, "half-open"),
+        }
+    }
+}
+
+/// Snapshot of breaker state
+#[derive(Debug, Clone, Serialize)]
+pub struct CircuitBreakerStatus {
+    pub state: CircuitState,
+    pub consecutive_failures: usize,
+    /// Seconds until the next probe (open circuits only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_in_seconds: Option<u64>,
+}
+
+/// Error returned while the circuit is open
+#[derive(Debug, Clone)]
+pub struct CircuitOpenError {
+    pub provider: String,
+    pub retry_in: Duration,
+}
+
+impl std::fmt::Display for CircuitOpenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Teacher unavailable ({}): circuit open after repeated failures, retrying in {}s",
+            self.provider,
+            self.retry_in.as_secs().max(1)
+        )
+    }
+}
+
+impl std::error::Error for CircuitOpenError {}
+
+#[derive(Debug)]
+enum Inner {
+    Closed { failures: VecDeque<Instant> },
+    Open { until: Instant, failures: usize },
+    /// `probe_started` guards against a probe that never reports back
+    HalfOpen { probe_started: Instant, failures: usize },
+}
+
+/// Thread-safe circuit breaker shared by all clones of a teacher client
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    inner: Mutex<Inner>,
+}
+
+impl CircuitBreaker {
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            inner: Mutex::new(Inner::Closed {
+                failures: VecDeque::new(),
+            }),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Check whether a call may proceed
+    ///
+    /// Returns the remaining cooldown if the circuit is open. Once the
+    /// cooldown has passed, the first caller becomes the half-open probe.
+    pub fn try_acquire(&self) -> Result<(), Duration> {
+        self.try_acquire_at(Instant::now())
+    }
+
+    fn try_acquire_at(&self, now: Instant) -> Result<(), Duration> {
+        let mut inner = self.lock();
+        match *inner {
+            Inner::Closed { .. } => Ok(()),
+            Inner::Open { until, failures } => {
+                if now >= until {
+                    tracing::info!("Teacher circuit half-open: probing recovery");
+                    *inner = Inner::HalfOpen {
+                        probe_started: now,
+                        failures,
+                    };
+                    Ok(())
+                } else {
+                    Err(until - now)
+                }
+            }
+            Inner::HalfOpen {
+                probe_started,
+                failures,
+            } => {
+                // Allow a new probe if the previous one never reported back
+                if now.duration_since(probe_started) >= self.config.cooldown {
+                    *inner = Inner::HalfOpen {
+                        probe_started: now,
+                        failures,
+                    };
+                    Ok(())
+                } else {
+                    Err(Duration::ZERO)
+                }
+            }
+        }
+    }
+
+    /// Fail fast with a `CircuitOpenError` if calls to `provider` are blocked
+    pub fn check(&self, provider: &str) -> anyhow::Result<()> {
+        self.try_acquire().map_err(|retry_in| {
+            anyhow::Error::new(CircuitOpenError {
+                provider: provider.to_string(),
+                retry_in,
+            })
+        })
+    }
+
+    /// Record the outcome of a call made after `check`
+    pub fn record<T>(&self, result: &anyhow::Result<T>) {
+        match result {
+            Ok(_) => self.record_success(),
+            Err(_) => self.record_failure(),
+        }
+    }
+
+    /// Record a successful call (closes the circuit)
+    pub fn record_success(&self) {
+        let mut inner = self.lock();
+        if !matches!(*inner, Inner::Closed { .. }) {
+            tracing::info!("Teacher circuit closed: provider recovered");
+        }
+        *inner = Inner::Closed {
+            failures: VecDeque::new(),
+        };
+    }
+
+    /// Record a failed call
+    pub fn record_failure(&self) {
+        self.record_failure_at(Instant::now());
+    }
+
+    fn record_failure_at(&self, now: Instant) {
+        let mut inner = self.lock();
+        let next = match &mut *inner {
+            Inner::Closed { failures } => {
+                failures.push_back(now);
+                while failures
+                    .front()
+                    .is_some_and(|first| now.duration_since(*first) > self.config.failure_window)
+                {
+                    failures.pop_front();
+                }
+
+                if failures.len() >= self.config.failure_threshold {
+                    tracing::warn!(
+                        failures = failures.len(),
+                        cooldown_secs = self.config.cooldown.as_secs(),
+                        "Teacher circuit open"
+                    );
+                    Some(Inner::Open {
+                        until: now + self.config.cooldown,
+                        failures: failures.len(),
+                    })
+                } else {
+                    None
+                }
+            }
+            Inner::HalfOpen { failures, .. } => {
+                tracing::warn!("Teacher circuit re-opened: probe failed");
+                Some(Inner::Open {
+                    until: now + self.config.cooldown,
+                    failures: *failures + 1,
+                })
+            }
+            // A call that started before the circuit opened
+            Inner::Open { failures, .. } => {
+                *failures += 1;
+                None
+            }
+        };
+
+        if let Some(next) = next {
+            *inner = next;
+        }
+    }
+
+    /// Whether calls are currently failing fast (open and still cooling down)
+    pub fn is_open(&self) -> bool {
+        matches!(&*self.lock(), Inner::Open { until, .. } if Instant::now() < *until)
+    }
+
+    /// Current state for health/status output
+    pub fn status(&self) -> CircuitBreakerStatus {
+        self.status_at(Instant::now())
+    }
+
+    fn status_at(&self, now: Instant) -> CircuitBreakerStatus {
+        match &*self.lock() {
+            Inner::Closed { failures } => CircuitBreakerStatus {
+                state: CircuitState::Closed,
+                consecutive_failures: failures.len(),
+                retry_in_seconds: None,
+            },
+            Inner::Open { until, failures } => CircuitBreakerStatus {
+                state: CircuitState::Open,
+                consecutive_failures: *failures,
+                retry_in_seconds: Some(until.saturating_duration_since(now).as_secs()),
+            },
+            Inner::HalfOpen { failures, .. } => CircuitBreakerStatus {
+                state: CircuitState::HalfOpen,
+                consecutive_failures: *failures,
+                retry_in_seconds: None,
+            },
+        }
+    }
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new(CircuitBreakerConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker() -> CircuitBreaker {
+        CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 3,
+            failure_window: Duration::from_secs(60),
+            cooldown: Duration::from_secs(30),
+        })
+    }
+
+    #[test]
+    fn test_opens_after_consecutive_failures() {
+        let breaker = breaker();
+        let start = Instant::now();
+
+        breaker.record_failure_at(start);
+        breaker.record_failure_at(start);
+        assert!(breaker.try_acquire_at(start).is_ok());
+
+        breaker.record_failure_at(start);
+        assert_eq!(breaker.status_at(start).state, CircuitState::Open);
+        let retry_in = breaker.try_acquire_at(start + Duration::from_secs(10)).unwrap_err();
+        assert_eq!(retry_in, Duration::from_secs(20));
+    }
+
+    #[test]
+    fn test_success_resets_and_window_expires() {
+        let breaker = breaker();
+        let start = Instant::now();
+
+        breaker.record_failure_at(start);
+        breaker.record_failure_at(start);
+        breaker.record_success();
+        breaker.record_failure_at(start);
+        assert_eq!(breaker.status_at(start).state, CircuitState::Closed);
+
+        // Failures outside the window don't accumulate
+        let later = start + Duration::from_secs(120);
+        breaker.record_failure_at(later);
+        breaker.record_failure_at(later);
+        assert_eq!(breaker.status_at(later).state, CircuitState::Closed);
+        assert_eq!(breaker.status_at(later).consecutive_failures, 2);
+    }
+
+    #[test]
+    fn test_half_open_probe() {
+        let breaker = breaker();
+        let start = Instant::now();
+        for _ in 0..3 {
+            breaker.record_failure_at(start);
+        }
+
+        // After cooldown exactly one probe is allowed
+        let after = start + Duration::from_secs(31);
+        assert!(breaker.try_acquire_at(after).is_ok());
+        assert_eq!(breaker.status_at(after).state, CircuitState::HalfOpen);
+        assert!(breaker.try_acquire_at(after).is_err());
+
+        // Failed probe re-opens for a full cooldown
+        breaker.record_failure_at(after);
+        assert_eq!(breaker.status_at(after).state, CircuitState::Open);
+        assert!(breaker.try_acquire_at(after + Duration::from_secs(29)).is_err());
+
+        // Successful probe closes
+        let later = after + Duration::from_secs(31);
+        assert!(breaker.try_acquire_at(later).is_ok());
+        breaker.record_success();
+        assert_eq!(breaker.status_at(later).state, CircuitState::Closed);
+    }
+}
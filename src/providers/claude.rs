@@ -8,15 +8,16 @@ use std::collections::HashMap;
 use std::time::Duration;
 use tokio::sync::mpsc;
 
-use super::types::{ProviderRequest, ProviderResponse, StreamChunk};
+use super::types::{CacheBreakpoint, ProviderRequest, ProviderResponse, StreamChunk};
+use super::timeout::{check_timeout, send_stream_request, with_idle_timeout, DEFAULT_REQUEST_TIMEOUT_SECS};
 use super::LlmProvider;
-use crate::claude::retry::with_retry;
+use crate::claude::retry::{with_retry_config, HttpStatusError, RetryConfig};
 use crate::claude::streaming::StreamEvent;
-use crate::claude::types::{ContentBlock, MessageRequest};
+use crate::claude::types::{ContentBlock, Message, MessageRequest};
+use crate::config::{PromptCachingConfig, ThinkingLevel, DEFAULT_TEACHER_MAX_TOKENS};
 
 const CLAUDE_API_URL: &str = "https://api.anthropic.com/v1/messages";
 const ANTHROPIC_VERSION: &str = "2023-06-01";
-const REQUEST_TIMEOUT_SECS: u64 = 60;
 
 /// Helper struct for building blocks during streaming
 struct BlockBuilder {
@@ -24,6 +25,8 @@ struct BlockBuilder {
     id: Option<String>,
     name: Option<String>,
     accumulated: String,
+    /// Signature of a thinking block
+    signature: String,
 }
 
 /// Claude API provider
@@ -32,22 +35,31 @@ struct BlockBuilder {
 #[derive(Clone)]
 pub struct ClaudeProvider {
     client: Client,
+    request_timeout: Duration,
     api_key: String,
     default_model: String,
+    max_tokens: u32,
+    prompt_caching: PromptCachingConfig,
+    thinking: Option<ThinkingLevel>,
+    retry: RetryConfig,
 }
 
 impl ClaudeProvider {
     /// Create a new Claude provider
     pub fn new(api_key: String) -> Result<Self> {
         let client = Client::builder()
-            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
             .build()
             .context("Failed to create HTTP client")?;
 
         Ok(Self {
             client,
+            request_timeout: Duration::from_secs(DEFAULT_REQUEST_TIMEOUT_SECS),
             api_key,
             default_model: "claude-sonnet-4-20250514".to_string(),
+            max_tokens: DEFAULT_TEACHER_MAX_TOKENS,
+            prompt_caching: PromptCachingConfig::default(),
+            thinking: None,
+            retry: RetryConfig::default(),
         })
     }
 
@@ -57,6 +69,53 @@ impl ClaudeProvider {
         self
     }
 
+    /// Create with custom output token limit (used when a request doesn't set one)
+    pub fn with_max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = max_tokens;
+        self
+    }
+
+    /// Set the request timeout (`request_timeout_seconds`); for streaming it
+    /// bounds the wait between chunks rather than the whole response
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    /// Create with custom prompt caching settings
+    pub fn with_prompt_caching(mut self, prompt_caching: PromptCachingConfig) -> Self {
+        self.prompt_caching = prompt_caching;
+        self
+    }
+
+    /// Create with an extended thinking budget (used when a request doesn't set one)
+    pub fn with_thinking(mut self, thinking: ThinkingLevel) -> Self {
+        self.thinking = Some(thinking);
+        self
+    }
+
+    /// Set the retry attempts and backoff (`max_retries` / `base_delay_ms`)
+    pub fn with_retry_config(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Thinking budget in tokens for `request`, if thinking applies
+    fn thinking_budget(&self, request: &ProviderRequest, model: &str) -> Option<u32> {
+        let budget = request.thinking.or(self.thinking)?.budget_tokens()?;
+        if !supports_thinking(model) {
+            tracing::debug!("Model {} has no extended thinking, ignoring thinking budget", model);
+            return None;
+        }
+        if continues_tool_use_without_thinking(&request.messages) {
+            // The API wants the tool-calling turn to start with its thinking
+            // block; without one, finish this tool loop without thinking
+            tracing::debug!("Tool loop started without thinking, not enabling it mid-loop");
+            return None;
+        }
+        Some(budget)
+    }
+
     /// Convert ProviderRequest to Claude's MessageRequest format
     fn to_message_request(&self, request: &ProviderRequest) -> MessageRequest {
         let model = if request.model.is_empty() {
@@ -67,19 +126,44 @@ impl ClaudeProvider {
 
         let mut msg_req = MessageRequest {
             model,
-            max_tokens: request.max_tokens,
+            max_tokens: Some(request.max_tokens.unwrap_or(self.max_tokens)),
             messages: request.messages.clone(),
             tools: request.tools.clone(),
+            temperature: request.temperature,
+            system: request.system.clone(),
         };
 
         msg_req
     }
 
+    /// Build the JSON request body sent to the Messages API
+    ///
+    /// System messages are lifted into the top-level `system` field (the API
+    /// rejects them inside `messages`) after the request's own system prompt,
+    /// the thinking budget is added, then cache breakpoints are applied.
+    fn to_request_json(&self, request: &ProviderRequest) -> Result<serde_json::Value> {
+        let msg_request = self.to_message_request(request);
+        let thinking_budget = self.thinking_budget(request, &msg_request.model);
+        let mut request_json = serde_json::to_value(&msg_request)?;
+
+        extract_system_prompt(&mut request_json);
+
+        if let Some(budget) = thinking_budget {
+            apply_thinking(&mut request_json, budget);
+        }
+
+        if self.prompt_caching.enabled {
+            apply_cache_breakpoints(&mut request_json, &self.prompt_caching);
+        }
+
+        Ok(request_json)
+    }
+
     /// Send a single message request (no retry)
     async fn send_message_once(&self, request: &ProviderRequest) -> Result<ProviderResponse> {
-        let msg_request = self.to_message_request(request);
+        let request_json = self.to_request_json(request)?;
 
-        tracing::debug!("Sending request to Claude API: {:?}", msg_request);
+        tracing::debug!("Sending request to Claude API: {}", request_json);
 
         let response = self
             .client
@@ -87,27 +171,33 @@ impl ClaudeProvider {
             .header("x-api-key", &self.api_key)
             .header("anthropic-version", ANTHROPIC_VERSION)
             .header("content-type", "application/json")
-            .json(&msg_request)
+            .json(&request_json)
+            .timeout(self.request_timeout)
             .send()
             .await
+            .map_err(|e| check_timeout(e, self.request_timeout))
             .context("Failed to send request to Claude API")?;
 
         let status = response.status();
 
         if !status.is_success() {
-            let error_body = response.text().await.unwrap_or_default();
-            anyhow::bail!(
-                "Claude API request failed\n\nStatus: {}\nBody: {}",
-                status,
-                error_body
-            );
+            return Err(HttpStatusError::from_response("Claude API request", response).await.into());
         }
 
-        let message_response: crate::claude::types::MessageResponse = response
+        let response_json: serde_json::Value = response
             .json()
             .await
+            .map_err(|e| check_timeout(e, self.request_timeout))
             .context("Failed to parse Claude API response")?;
 
+        if let Some(usage) = response_json.get("usage") {
+            log_cache_usage(usage);
+        }
+
+        let message_response: crate::claude::types::MessageResponse =
+            serde_json::from_value(response_json)
+                .context("Failed to parse Claude API response")?;
+
         tracing::debug!("Received response: {:?}", message_response);
 
         // Convert to ProviderResponse
@@ -118,6 +208,7 @@ impl ClaudeProvider {
             stop_reason: message_response.stop_reason,
             role: message_response.role,
             provider: "claude".to_string(),
+            usage: message_response.usage,
         })
     }
 
@@ -128,39 +219,34 @@ impl ClaudeProvider {
     ) -> Result<mpsc::Receiver<Result<StreamChunk>>> {
         let (tx, rx) = mpsc::channel(100);
 
-        let msg_request = self.to_message_request(request);
-
         // Convert to JSON and add stream: true
-        let mut request_json = serde_json::to_value(&msg_request)?;
+        let mut request_json = self.to_request_json(request)?;
         request_json["stream"] = serde_json::json!(true);
 
         tracing::debug!("Sending streaming request to Claude API");
 
-        let response = self
+        let request = self
             .client
             .post(CLAUDE_API_URL)
             .header("x-api-key", &self.api_key)
             .header("anthropic-version", ANTHROPIC_VERSION)
             .header("content-type", "application/json")
-            .json(&request_json)
-            .send()
+            .json(&request_json);
+        let response = send_stream_request(request, self.request_timeout)
             .await
             .context("Failed to send streaming request to Claude API")?;
 
         let status = response.status();
         if !status.is_success() {
-            let error_body = response.text().await.unwrap_or_default();
-            anyhow::bail!(
-                "Claude API streaming request failed\n\nStatus: {}\nBody: {}",
-                status,
-                error_body
-            );
+            return Err(HttpStatusError::from_response("Claude API streaming request", response).await.into());
         }
 
+        let idle = self.request_timeout;
+
         // Spawn task to parse SSE stream with block tracking
         tokio::spawn(async move {
             tracing::debug!("[STREAM] Streaming task started");
-            let mut stream = response.bytes_stream();
+            let mut stream = with_idle_timeout(response.bytes_stream(), idle);
             let mut buffer = Vec::new();
 
             // Track blocks being built (index -> BlockBuilder)
@@ -193,6 +279,37 @@ impl ClaudeProvider {
                                     break;
                                 }
 
+                                // message_start carries the usage (including cache stats)
+                                if json_str.contains("\"message_start\"") {
+                                    if let Ok(value) = serde_json::from_str::<serde_json::Value>(json_str) {
+                                        if let Some(usage) = value.pointer("/message/usage") {
+                                            log_cache_usage(usage);
+                                        }
+                                    }
+                                }
+
+                                // message_delta carries the output token count and stop reason
+                                if json_str.contains("\"message_delta\"") {
+                                    let value = serde_json::from_str::<serde_json::Value>(json_str).ok();
+                                    let output_tokens = value
+                                        .as_ref()
+                                        .and_then(|value| value.pointer("/usage/output_tokens")?.as_u64())
+                                        .map(|tokens| StreamChunk::OutputTokens(tokens as u32));
+                                    let stop_reason = value
+                                        .as_ref()
+                                        .and_then(|value| value.pointer("/delta/stop_reason")?.as_str())
+                                        .map(|reason| StreamChunk::StopReason(reason.to_string()));
+                                    for chunk in output_tokens.into_iter().chain(stop_reason) {
+                                        if tx.send(Ok(chunk)).await.is_err() {
+                                            done = true;
+                                            break;
+                                        }
+                                    }
+                                    if done {
+                                        break;
+                                    }
+                                }
+
                                 // Parse event
                                 if let Ok(event) = serde_json::from_str::<StreamEvent>(json_str) {
                                     tracing::debug!("Stream event: {}", event.event_type);
@@ -206,7 +323,9 @@ impl ClaudeProvider {
                                                         block_type: cb.block_type,
                                                         id: cb.id,
                                                         name: cb.name,
-                                                        accumulated: String::new(),
+                                                        // Redacted thinking arrives whole
+                                                        accumulated: cb.data.unwrap_or_default(),
+                                                        signature: String::new(),
                                                     },
                                                 );
                                                 tracing::debug!(
@@ -244,6 +363,26 @@ impl ClaudeProvider {
                                                                 builder.accumulated.push_str(&json);
                                                             }
                                                         }
+                                                        "thinking_delta" => {
+                                                            if let Some(thinking) = delta.thinking {
+                                                                builder.accumulated.push_str(&thinking);
+                                                                if tx
+                                                                    .send(Ok(StreamChunk::ThinkingDelta(
+                                                                        thinking,
+                                                                    )))
+                                                                    .await
+                                                                    .is_err()
+                                                                {
+                                                                    done = true;
+                                                                    break;
+                                                                }
+                                                            }
+                                                        }
+                                                        "signature_delta" => {
+                                                            if let Some(signature) = delta.signature {
+                                                                builder.signature.push_str(&signature);
+                                                            }
+                                                        }
                                                         _ => {}
                                                     }
                                                 }
@@ -268,6 +407,15 @@ impl ClaudeProvider {
                                                             input,
                                                         }
                                                     }
+                                                    "thinking" => ContentBlock::Thinking {
+                                                        thinking: builder.accumulated,
+                                                        signature: builder.signature,
+                                                    },
+                                                    "redacted_thinking" => {
+                                                        ContentBlock::RedactedThinking {
+                                                            data: builder.accumulated,
+                                                        }
+                                                    }
                                                     _ => continue,
                                                 };
 
@@ -313,17 +461,209 @@ impl ClaudeProvider {
     }
 }
 
+/// Move `role: "system"` messages into the top-level `system` field, after
+/// the request's own system prompt
+fn extract_system_prompt(request_json: &mut serde_json::Value) {
+    let mut system_blocks = request_json
+        .as_object_mut()
+        .and_then(|obj| obj.remove("system"))
+        .map(content_to_blocks)
+        .unwrap_or_default();
+
+    if let Some(messages) = request_json
+        .get_mut("messages")
+        .and_then(|m| m.as_array_mut())
+    {
+        messages.retain_mut(|msg| {
+            if msg.get("role").and_then(|r| r.as_str()) != Some("system") {
+                return true;
+            }
+            if let Some(content) = msg.get_mut("content") {
+                system_blocks.extend(content_to_blocks(content.take()));
+            }
+            false
+        });
+    }
+
+    if !system_blocks.is_empty() {
+        request_json["system"] = serde_json::Value::Array(system_blocks);
+    }
+}
+
+/// Whether `model` supports extended thinking (Claude 3.7 and later)
+fn supports_thinking(model: &str) -> bool {
+    !model.starts_with("claude-3-") || model.starts_with("claude-3-7")
+}
+
+/// Whether the request continues a tool loop whose assistant turn has no
+/// thinking block (the API rejects enabling thinking there)
+fn continues_tool_use_without_thinking(messages: &[Message]) -> bool {
+    let Some(last) = messages.last() else {
+        return false;
+    };
+    if last.role != "user" || !last.has_tool_results() {
+        return false;
+    }
+    messages
+        .iter()
+        .rev()
+        .find(|msg| msg.role == "assistant")
+        .is_some_and(|msg| {
+            msg.content.iter().any(ContentBlock::is_tool_use)
+                && !msg.content.first().is_some_and(ContentBlock::is_thinking)
+        })
+}
+
+/// Enable extended thinking with a budget of `budget` tokens
+///
+/// `max_tokens` covers the thinking and the answer, so it's raised when the
+/// budget would leave no room for an answer. Thinking also requires the
+/// default temperature.
+fn apply_thinking(request_json: &mut serde_json::Value, budget: u32) {
+    let max_tokens = request_json
+        .get("max_tokens")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(DEFAULT_TEACHER_MAX_TOKENS as u64) as u32;
+    if max_tokens <= budget {
+        request_json["max_tokens"] = serde_json::json!(budget + max_tokens);
+    }
+    request_json["thinking"] = serde_json::json!({
+        "type": "enabled",
+        "budget_tokens": budget,
+    });
+    if let Some(obj) = request_json.as_object_mut() {
+        obj.remove("temperature");
+    }
+}
+
+/// Insert `cache_control` breakpoints according to the caching config
+///
+/// Anthropic allows at most 4 breakpoints per request; this places up to 3:
+/// after the tool definitions, after the system prompt, and at the end of the
+/// stable conversation prefix (the message before the newest one).
+fn apply_cache_breakpoints(request_json: &mut serde_json::Value, config: &PromptCachingConfig) {
+    if config.cache_tools {
+        if let Some(last_tool) = request_json
+            .get_mut("tools")
+            .and_then(|t| t.as_array_mut())
+            .and_then(|tools| tools.last_mut())
+        {
+            mark_ephemeral(last_tool);
+        }
+    }
+
+    if config.cache_system_prompt {
+        if let Some(last_block) = request_json
+            .get_mut("system")
+            .and_then(|s| s.as_array_mut())
+            .and_then(|blocks| blocks.last_mut())
+        {
+            mark_ephemeral(last_block);
+        }
+    }
+
+    if config.cache_conversation_prefix {
+        if let Some(messages) = request_json
+            .get_mut("messages")
+            .and_then(|m| m.as_array_mut())
+        {
+            if messages.len() >= 2 {
+                let prefix_end = messages.len() - 2;
+                if let Some(content) = messages[prefix_end].get_mut("content") {
+                    let mut blocks = content_to_blocks(content.take());
+                    if let Some(last_block) = blocks.last_mut() {
+                        mark_ephemeral(last_block);
+                    }
+                    *content = serde_json::Value::Array(blocks);
+                }
+            }
+        }
+    }
+}
+
+/// Read back the breakpoints `apply_cache_breakpoints` placed
+///
+/// Message positions are mapped back to `request.messages`, which still
+/// contains the system messages lifted out of the JSON body.
+fn find_cache_breakpoints(
+    request_json: &serde_json::Value,
+    request: &ProviderRequest,
+) -> Vec<CacheBreakpoint> {
+    let has_breakpoint = |blocks: Option<&serde_json::Value>| {
+        blocks
+            .and_then(|b| b.as_array())
+            .and_then(|b| b.last())
+            .is_some_and(|block| block.get("cache_control").is_some())
+    };
+
+    let mut breakpoints = Vec::new();
+    if has_breakpoint(request_json.get("tools")) {
+        breakpoints.push(CacheBreakpoint::Tools);
+    }
+    if has_breakpoint(request_json.get("system")) {
+        breakpoints.push(CacheBreakpoint::System);
+    }
+
+    let request_indices = request
+        .messages
+        .iter()
+        .enumerate()
+        .filter(|(_, msg)| msg.role != "system")
+        .map(|(idx, _)| idx);
+    if let Some(messages) = request_json.get("messages").and_then(|m| m.as_array()) {
+        for (msg, idx) in messages.iter().zip(request_indices) {
+            if has_breakpoint(msg.get("content")) {
+                breakpoints.push(CacheBreakpoint::Message(idx));
+            }
+        }
+    }
+    breakpoints
+}
+
+/// Normalize message content (string or array) into an array of blocks
+fn content_to_blocks(content: serde_json::Value) -> Vec<serde_json::Value> {
+    match content {
+        serde_json::Value::String(text) => {
+            vec![serde_json::json!({ "type": "text", "text": text })]
+        }
+        serde_json::Value::Array(blocks) => blocks,
+        serde_json::Value::Null => Vec::new(),
+        other => vec![other],
+    }
+}
+
+fn mark_ephemeral(block: &mut serde_json::Value) {
+    if let Some(obj) = block.as_object_mut() {
+        obj.insert(
+            "cache_control".to_string(),
+            serde_json::json!({ "type": "ephemeral" }),
+        );
+    }
+}
+
+/// Log prompt cache statistics from a Messages API `usage` object
+fn log_cache_usage(usage: &serde_json::Value) {
+    let field = |name: &str| usage.get(name).and_then(|v| v.as_u64()).unwrap_or(0);
+
+    tracing::info!(
+        input_tokens = field("input_tokens"),
+        cache_creation_input_tokens = field("cache_creation_input_tokens"),
+        cache_read_input_tokens = field("cache_read_input_tokens"),
+        "Claude prompt cache usage"
+    );
+}
+
 #[async_trait]
 impl LlmProvider for ClaudeProvider {
     async fn send_message(&self, request: &ProviderRequest) -> Result<ProviderResponse> {
-        with_retry(|| self.send_message_once(request)).await
+        with_retry_config(&self.retry, || self.send_message_once(request)).await
     }
 
     async fn send_message_stream(
         &self,
         request: &ProviderRequest,
     ) -> Result<mpsc::Receiver<Result<StreamChunk>>> {
-        with_retry(|| self.send_message_stream_once(request)).await
+        with_retry_config(&self.retry, || self.send_message_stream_once(request)).await
     }
 
     fn name(&self) -> &str {
@@ -341,6 +681,13 @@ impl LlmProvider for ClaudeProvider {
     fn supports_tools(&self) -> bool {
         true
     }
+
+    fn cache_breakpoints(&self, request: &ProviderRequest) -> Vec<CacheBreakpoint> {
+        match self.to_request_json(request) {
+            Ok(request_json) => find_cache_breakpoints(&request_json, request),
+            Err(_) => Vec::new(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -358,4 +705,213 @@ mod tests {
         let provider = ClaudeProvider::new("test-key".to_string()).unwrap();
         assert_eq!(provider.name(), "claude");
     }
+
+    fn conversation_request() -> ProviderRequest {
+        use crate::claude::types::Message;
+
+        ProviderRequest::new(vec![
+            Message {
+                role: "system".to_string(),
+                content: vec![ContentBlock::text("You are helpful.")],
+            },
+            Message::user("First question"),
+            Message::assistant("First answer"),
+            Message::user("Second question"),
+        ])
+    }
+
+    #[test]
+    fn test_cache_breakpoints_applied() {
+        let provider = ClaudeProvider::new("test-key".to_string()).unwrap();
+        let json = provider.to_request_json(&conversation_request()).unwrap();
+
+        // System prompt lifted out of messages and marked
+        assert_eq!(json["messages"].as_array().unwrap().len(), 3);
+        assert_eq!(json["system"][0]["text"], "You are helpful.");
+        assert_eq!(json["system"][0]["cache_control"]["type"], "ephemeral");
+
+        // Stable prefix ends at the assistant turn before the newest message
+        assert_eq!(json["messages"][1]["content"][0]["cache_control"]["type"], "ephemeral");
+        assert!(json["messages"][2]["content"].is_string());
+    }
+
+    #[test]
+    fn test_system_prompt_before_system_messages() {
+        let provider = ClaudeProvider::new("test-key".to_string()).unwrap();
+        let request = conversation_request().with_system("Follow the constitution.");
+        let json = provider.to_request_json(&request).unwrap();
+
+        assert_eq!(json["system"][0]["text"], "Follow the constitution.");
+        assert_eq!(json["system"][1]["text"], "You are helpful.");
+        assert_eq!(json["messages"].as_array().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_image_block_in_request() {
+        use crate::claude::types::{ImageSource, Message};
+
+        let provider = ClaudeProvider::new("test-key".to_string()).unwrap();
+        let message = Message::user("What does this show?").add_content(ContentBlock::Image {
+            source: ImageSource::Url {
+                url: "https://example.com/chart.png".to_string(),
+            },
+        });
+        let json = provider.to_request_json(&ProviderRequest::new(vec![message])).unwrap();
+
+        let image = &json["messages"][0]["content"][1];
+        assert_eq!(image["type"], "image");
+        assert_eq!(image["source"]["type"], "url");
+        assert_eq!(image["source"]["url"], "https://example.com/chart.png");
+    }
+
+    #[test]
+    fn test_cache_breakpoints_reported() {
+        let provider = ClaudeProvider::new("test-key".to_string()).unwrap();
+
+        // Message index counts the system message still in the request
+        assert_eq!(
+            provider.cache_breakpoints(&conversation_request()),
+            vec![CacheBreakpoint::System, CacheBreakpoint::Message(2)]
+        );
+    }
+
+    #[test]
+    fn test_cache_breakpoints_disabled() {
+        let provider = ClaudeProvider::new("test-key".to_string())
+            .unwrap()
+            .with_prompt_caching(PromptCachingConfig {
+                enabled: false,
+                ..Default::default()
+            });
+        let json = provider.to_request_json(&conversation_request()).unwrap();
+
+        assert!(json["system"][0].get("cache_control").is_none());
+        assert!(json["messages"][1]["content"].is_string());
+        assert!(provider.cache_breakpoints(&conversation_request()).is_empty());
+    }
+
+    #[test]
+    fn test_thinking_budget_in_request() {
+        let provider = ClaudeProvider::new("test-key".to_string()).unwrap();
+        let json = provider.to_request_json(&conversation_request()).unwrap();
+        assert!(json.get("thinking").is_none());
+
+        let request = conversation_request()
+            .with_temperature(0.2)
+            .with_thinking(ThinkingLevel::Medium);
+        let json = provider.to_request_json(&request).unwrap();
+        assert_eq!(json["thinking"]["type"], "enabled");
+        assert_eq!(json["thinking"]["budget_tokens"], 8192);
+        // Room left for the answer, and no custom temperature
+        assert_eq!(json["max_tokens"], 8192 + DEFAULT_TEACHER_MAX_TOKENS);
+        assert!(json.get("temperature").is_none());
+
+        // Teacher config applies unless the request overrides it
+        let provider = provider.with_thinking(ThinkingLevel::Budget(2000));
+        let json = provider.to_request_json(&conversation_request()).unwrap();
+        assert_eq!(json["thinking"]["budget_tokens"], 2000);
+        assert_eq!(json["max_tokens"], DEFAULT_TEACHER_MAX_TOKENS);
+        let json = provider
+            .to_request_json(&conversation_request().with_thinking(ThinkingLevel::Off))
+            .unwrap();
+        assert!(json.get("thinking").is_none());
+
+        // Older models ignore it
+        let json = provider
+            .to_request_json(&conversation_request().with_model("claude-3-5-haiku-latest"))
+            .unwrap();
+        assert!(json.get("thinking").is_none());
+    }
+
+    #[test]
+    fn test_thinking_not_enabled_mid_tool_loop() {
+        let provider = ClaudeProvider::new("test-key".to_string())
+            .unwrap()
+            .with_thinking(ThinkingLevel::Low);
+        let tool_use = ContentBlock::ToolUse {
+            id: "toolu_1".to_string(),
+            name: "read".to_string(),
+            input: serde_json::json!({ "file_path": "a.rs" }),
+        };
+        let tool_result = Message::with_content(
+            "user",
+            vec![ContentBlock::tool_result("toolu_1".to_string(), "ok".to_string(), None)],
+        );
+
+        let request = ProviderRequest::new(vec![
+            Message::user("Read a.rs"),
+            Message::with_content("assistant", vec![tool_use.clone()]),
+            tool_result.clone(),
+        ]);
+        assert!(provider.to_request_json(&request).unwrap().get("thinking").is_none());
+
+        // A loop that started with thinking keeps it
+        let thinking = ContentBlock::Thinking {
+            thinking: "Need the file".to_string(),
+            signature: "sig".to_string(),
+        };
+        let request = ProviderRequest::new(vec![
+            Message::user("Read a.rs"),
+            Message::with_content("assistant", vec![thinking, tool_use]),
+            tool_result,
+        ]);
+        let json = provider.to_request_json(&request).unwrap();
+        assert_eq!(json["thinking"]["budget_tokens"], 2048);
+        assert_eq!(json["messages"][1]["content"][0]["type"], "thinking");
+        assert_eq!(json["messages"][1]["content"][0]["signature"], "sig");
+    }
+
+    #[test]
+    fn test_thinking_kept_through_stored_tool_loop() {
+        use crate::cli::ConversationHistory;
+
+        let provider = ClaudeProvider::new("test-key".to_string())
+            .unwrap()
+            .with_thinking(ThinkingLevel::Low);
+
+        // Blocks as the event loop collects them from a streamed tool turn
+        let streamed = vec![
+            ContentBlock::Thinking {
+                thinking: "Need the file".to_string(),
+                signature: "sig".to_string(),
+            },
+            ContentBlock::ToolUse {
+                id: "toolu_1".to_string(),
+                name: "read".to_string(),
+                input: serde_json::json!({ "file_path": "a.rs" }),
+            },
+        ];
+        let mut conversation = ConversationHistory::new();
+        conversation.add_user_message("Read a.rs".to_string());
+        conversation.add_message(Message::with_content("assistant", streamed));
+        conversation.add_message(Message::with_content(
+            "user",
+            vec![ContentBlock::tool_result("toolu_1".to_string(), "ok".to_string(), None)],
+        ));
+
+        let request = ProviderRequest::new(conversation.get_messages());
+        let json = provider.to_request_json(&request).unwrap();
+        assert_eq!(json["thinking"]["type"], "enabled");
+        assert_eq!(json["messages"][1]["content"][0]["type"], "thinking");
+    }
+
+    #[test]
+    fn test_thinking_block_in_response() {
+        let response: crate::claude::types::MessageResponse = serde_json::from_value(serde_json::json!({
+            "id": "msg_1",
+            "type": "message",
+            "role": "assistant",
+            "model": "claude-sonnet-4-20250514",
+            "stop_reason": "end_turn",
+            "content": [
+                { "type": "thinking", "thinking": "Let me see", "signature": "sig" },
+                { "type": "redacted_thinking", "data": "abc" },
+                { "type": "text", "text": "Answer" }
+            ]
+        }))
+        .unwrap();
+        assert_eq!(response.text(), "Answer");
+        assert!(response.content[0].is_thinking());
+        assert!(response.content[1].is_thinking());
+    }
 }
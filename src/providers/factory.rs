@@ -5,8 +5,11 @@
 use anyhow::{anyhow, bail, Context, Result};
 
 use super::claude::ClaudeProvider;
+use super::failover::FailoverProvider;
 use super::gemini::GeminiProvider;
+use super::ollama::{OllamaProvider, DEFAULT_OLLAMA_URL};
 use super::openai::OpenAIProvider;
+use super::pool::TeacherPool;
 use super::LlmProvider;
 use crate::config::TeacherEntry;
 
@@ -14,69 +17,187 @@ use crate::config::TeacherEntry;
 ///
 /// The first provider in the returned list is the active teacher.
 /// Additional providers are available for easy switching via config reordering.
-pub fn create_providers(teachers: &[TeacherEntry]) -> Result<Vec<Box<dyn LlmProvider>>> {
+/// Teachers sharing a `pool` name become one `TeacherPool`, placed where the
+/// pool's first member appears.
+///
+/// `default_max_tokens` is the output token limit for teachers that don't set
+/// their own `max_tokens`.
+pub fn create_providers(
+    teachers: &[TeacherEntry],
+    default_max_tokens: u32,
+) -> Result<Vec<Box<dyn LlmProvider>>> {
+    Ok(create_labelled_providers(teachers, default_max_tokens)?
+        .into_iter()
+        .map(|(_, provider)| provider)
+        .collect())
+}
+
+/// `create_providers`, with the label each teacher is logged under
+fn create_labelled_providers(
+    teachers: &[TeacherEntry],
+    default_max_tokens: u32,
+) -> Result<Vec<(String, Box<dyn LlmProvider>)>> {
     if teachers.is_empty() {
         bail!("No teacher providers configured");
     }
 
-    teachers
-        .iter()
-        .enumerate()
-        .map(|(idx, entry)| {
-            create_provider_from_entry(entry)
-                .with_context(|| format!("Failed to create teacher provider #{}", idx + 1))
-        })
-        .collect()
+    let create = |idx: usize, entry: &TeacherEntry| {
+        create_provider_from_entry(entry, default_max_tokens)
+            .with_context(|| format!("Failed to create teacher provider #{}", idx + 1))
+    };
+
+    let mut providers: Vec<(String, Box<dyn LlmProvider>)> = Vec::new();
+    let mut pools_built: Vec<&str> = Vec::new();
+    for (idx, entry) in teachers.iter().enumerate() {
+        let Some(pool) = entry.pool.as_deref() else {
+            providers.push((teacher_label(entry), create(idx, entry)?));
+            continue;
+        };
+        if pools_built.contains(&pool) {
+            continue;
+        }
+        pools_built.push(pool);
+
+        let members = teachers
+            .iter()
+            .enumerate()
+            .filter(|(_, member)| member.pool.as_deref() == Some(pool))
+            .map(|(idx, member)| Ok((pool_member_label(member), create(idx, member)?)))
+            .collect::<Result<Vec<_>>>()?;
+        providers.push((format!("pool {}", pool), Box::new(TeacherPool::new(pool, members))));
+    }
+    Ok(providers)
+}
+
+/// How a teacher is named in failover logs
+fn teacher_label(entry: &TeacherEntry) -> String {
+    match (&entry.name, &entry.model) {
+        (Some(name), _) => name.clone(),
+        (None, Some(model)) => format!("{} ({})", entry.provider, model),
+        (None, None) => entry.provider.clone(),
+    }
+}
+
+/// How a pool member is shown in /provider-latency (never the full key)
+fn pool_member_label(entry: &TeacherEntry) -> String {
+    entry.name.clone().unwrap_or_else(|| {
+        format!("key …{}", crate::text::last_chars(&entry.resolved_api_key(), 4))
+    })
 }
 
 /// Create a single provider from a teacher entry
-fn create_provider_from_entry(entry: &TeacherEntry) -> Result<Box<dyn LlmProvider>> {
-    match entry.provider.as_str() {
+fn create_provider_from_entry(
+    entry: &TeacherEntry,
+    default_max_tokens: u32,
+) -> Result<Box<dyn LlmProvider>> {
+    match entry.provider.to_ascii_lowercase().as_str() {
         "claude" => {
-            let mut provider = ClaudeProvider::new(entry.api_key.clone())?;
+            let mut provider = ClaudeProvider::new(entry.resolved_api_key())?;
             if let Some(model) = &entry.model {
                 provider = provider.with_model(model.clone());
             }
+            let max_tokens = output_token_limit(entry, default_max_tokens, provider.default_model());
+            provider = provider.with_max_tokens(max_tokens);
+            if let Some(timeout) = entry.request_timeout() {
+                provider = provider.with_request_timeout(timeout);
+            }
+            provider = provider.with_retry_config(entry.retry_config());
+            if let Some(caching) = &entry.prompt_caching {
+                provider = provider.with_prompt_caching(caching.clone());
+            }
+            if let Some(thinking) = entry.thinking {
+                provider = provider.with_thinking(thinking);
+            }
             Ok(Box::new(provider))
         }
 
         "openai" => {
-            let mut provider = OpenAIProvider::new_openai(entry.api_key.clone())?;
+            let mut provider = OpenAIProvider::new_openai(entry.resolved_api_key())?;
             if let Some(model) = &entry.model {
                 provider = provider.with_model(model.clone());
             }
+            let max_tokens = output_token_limit(entry, default_max_tokens, provider.default_model());
+            provider = provider.with_max_tokens(max_tokens);
+            if let Some(timeout) = entry.request_timeout() {
+                provider = provider.with_request_timeout(timeout);
+            }
+            provider = provider.with_retry_config(entry.retry_config());
+            if let Some(thinking) = entry.thinking {
+                provider = provider.with_thinking(thinking);
+            }
             Ok(Box::new(provider))
         }
 
         "grok" => {
-            let mut provider = OpenAIProvider::new_grok(entry.api_key.clone())?;
+            let mut provider = OpenAIProvider::new_grok(entry.resolved_api_key())?;
             if let Some(model) = &entry.model {
                 provider = provider.with_model(model.clone());
             }
+            let max_tokens = output_token_limit(entry, default_max_tokens, provider.default_model());
+            provider = provider.with_max_tokens(max_tokens);
+            if let Some(timeout) = entry.request_timeout() {
+                provider = provider.with_request_timeout(timeout);
+            }
+            provider = provider.with_retry_config(entry.retry_config());
             Ok(Box::new(provider))
         }
 
         "gemini" => {
-            let mut provider = GeminiProvider::new(entry.api_key.clone())?;
+            let mut provider = GeminiProvider::new(entry.resolved_api_key())?;
             if let Some(model) = &entry.model {
                 provider = provider.with_model(model.clone());
             }
+            let max_tokens = output_token_limit(entry, default_max_tokens, provider.default_model());
+            provider = provider.with_max_tokens(max_tokens);
+            if let Some(timeout) = entry.request_timeout() {
+                provider = provider.with_request_timeout(timeout);
+            }
+            provider = provider.with_retry_config(entry.retry_config());
             Ok(Box::new(provider))
         }
 
         "mistral" => {
-            let mut provider = OpenAIProvider::new_mistral(entry.api_key.clone())?;
+            let mut provider = OpenAIProvider::new_mistral(entry.resolved_api_key())?;
             if let Some(model) = &entry.model {
                 provider = provider.with_model(model.clone());
             }
+            let max_tokens = output_token_limit(entry, default_max_tokens, provider.default_model());
+            provider = provider.with_max_tokens(max_tokens);
+            if let Some(timeout) = entry.request_timeout() {
+                provider = provider.with_request_timeout(timeout);
+            }
+            provider = provider.with_retry_config(entry.retry_config());
             Ok(Box::new(provider))
         }
 
         "groq" => {
-            let mut provider = OpenAIProvider::new_groq(entry.api_key.clone())?;
+            let mut provider = OpenAIProvider::new_groq(entry.resolved_api_key())?;
             if let Some(model) = &entry.model {
                 provider = provider.with_model(model.clone());
             }
+            let max_tokens = output_token_limit(entry, default_max_tokens, provider.default_model());
+            provider = provider.with_max_tokens(max_tokens);
+            if let Some(timeout) = entry.request_timeout() {
+                provider = provider.with_request_timeout(timeout);
+            }
+            provider = provider.with_retry_config(entry.retry_config());
+            Ok(Box::new(provider))
+        }
+
+        "ollama" => {
+            let model = entry
+                .model
+                .clone()
+                .ok_or_else(|| anyhow!("Ollama teachers need a model (e.g. model = \"qwen2.5:7b\")"))?;
+            let base_url = entry.resolved_base_url();
+            let base_url = base_url.as_deref().unwrap_or(DEFAULT_OLLAMA_URL);
+            let mut provider = OllamaProvider::new(base_url, model)?;
+            let max_tokens = output_token_limit(entry, default_max_tokens, provider.default_model());
+            provider = provider.with_max_tokens(max_tokens);
+            if let Some(timeout) = entry.request_timeout() {
+                provider = provider.with_request_timeout(timeout);
+            }
+            provider = provider.with_retry_config(entry.retry_config());
             Ok(Box::new(provider))
         }
 
@@ -84,20 +205,63 @@ fn create_provider_from_entry(entry: &TeacherEntry) -> Result<Box<dyn LlmProvide
     }
 }
 
-/// Create a fallback chain with all teachers in priority order
+/// Resolve a teacher's output token limit, clamped to the model's known maximum
+fn output_token_limit(entry: &TeacherEntry, default_max_tokens: u32, model: &str) -> u32 {
+    let requested = entry.effective_max_tokens(default_max_tokens);
+    match known_output_limit(model) {
+        Some(limit) if requested > limit => {
+            tracing::warn!(
+                "max_tokens {} exceeds the {}-token output limit of {}, clamping",
+                requested,
+                limit,
+                model
+            );
+            limit
+        }
+        _ => requested,
+    }
+}
+
+/// Maximum output tokens for well-known teacher models (None if unknown)
+fn known_output_limit(model: &str) -> Option<u32> {
+    const LIMITS: &[(&str, u32)] = &[
+        ("claude-opus-4", 32_000),
+        ("claude-sonnet-4", 64_000),
+        ("claude-haiku-4", 64_000),
+        ("claude-3-7-sonnet", 64_000),
+        ("claude-3-5-sonnet", 8_192),
+        ("claude-3-5-haiku", 8_192),
+        ("claude-3-", 4_096),
+        ("gpt-4o", 16_384),
+        ("gpt-4-turbo", 4_096),
+        ("gpt-4.1", 32_768),
+        ("gemini-2.5", 65_536),
+        ("gemini-2.0", 8_192),
+        ("gemini-1.5", 8_192),
+    ];
+
+    LIMITS
+        .iter()
+        .find(|(prefix, _)| model.starts_with(prefix))
+        .map(|(_, limit)| *limit)
+}
+
+/// Create the teacher provider, failing over between teachers in priority order
 ///
 /// The first teacher is the primary provider, additional teachers are fallbacks.
-/// If the primary fails, the system will try the next teacher automatically.
-pub fn create_provider(teachers: &[TeacherEntry]) -> Result<Box<dyn LlmProvider>> {
-    let providers = create_providers(teachers)?;
+/// If the primary is unavailable (server error, rate limit, timeout), the next
+/// teacher is tried automatically; see `FailoverProvider`.
+pub fn create_provider(
+    teachers: &[TeacherEntry],
+    default_max_tokens: u32,
+) -> Result<Box<dyn LlmProvider>> {
+    let mut providers = create_labelled_providers(teachers, default_max_tokens)?;
 
     if providers.len() == 1 {
-        // Single provider - return directly (no fallback needed)
-        Ok(providers.into_iter().next().unwrap())
+        // Single provider - return directly (no failover needed)
+        Ok(providers.pop().unwrap().1)
     } else {
-        // Multiple providers - wrap in fallback chain
-        use super::FallbackChain;
-        Ok(Box::new(FallbackChain::new(providers)))
+        Ok(Box::new(FailoverProvider::new(providers)))
     }
 }
 
@@ -181,29 +345,27 @@ mod tests {
     //     assert_eq!(provider.unwrap().name(), "grok");
     // }
 
-    // FIXME: Test disabled due to missing ProviderSettings type
-    // #[test]
-    // fn test_create_gemini_provider() {
-    //     let mut settings = HashMap::new();
-    //     settings.insert(
-    //         "gemini".to_string(),
-    //         ProviderSettings {
-    //             api_key: "test-key".to_string(),
-    //             model: None,
-    //             base_url: None,
-    //         },
-    //     );
-
-    //     let config = TeacherConfig {
-    //         provider: Some("gemini".to_string()),
-    //         settings,
-    //         teachers: vec![],
-    //     };
-
-    //     let provider = create_provider(&config);
-    //     assert!(provider.is_ok());
-    //     assert_eq!(provider.unwrap().name(), "gemini");
-    // }
+    #[test]
+    fn test_create_gemini_provider() {
+        let teachers = vec![TeacherEntry {
+            provider: "gemini".to_string(),
+            api_key: "test-key".to_string(),
+            model: Some("gemini-2.5-flash".to_string()),
+            base_url: None,
+            name: None,
+            prompt_caching: None,
+            max_tokens: None,
+            pool: None,
+            thinking: None,
+            max_retries: Some(1),
+            base_delay_ms: None,
+            request_timeout_seconds: None,
+        }];
+
+        let provider = create_provider(&teachers, crate::config::DEFAULT_TEACHER_MAX_TOKENS).unwrap();
+        assert_eq!(provider.name(), "gemini");
+        assert_eq!(provider.default_model(), "gemini-2.5-flash");
+    }
 
     // FIXME: Test disabled due to missing TeacherConfig type (replaced by Config)
     // #[test]
@@ -231,6 +393,7 @@ mod tests {
     //                 model: Some("gpt-4o".to_string()),
     //                 base_url: None,
     //                 name: Some("GPT-4o".to_string()),
+    //                 prompt_caching: None,
     //             },
     //             TeacherEntry {
     //                 provider: "claude".to_string(),
@@ -238,6 +401,7 @@ mod tests {
     //                 model: None,
     //                 base_url: None,
     //                 name: Some("Claude Sonnet".to_string()),
+    //                 prompt_caching: None,
     //             },
     //         ],
     //     };
@@ -261,6 +425,7 @@ mod tests {
     //                 model: Some("gpt-4o".to_string()),
     //                 base_url: None,
     //                 name: Some("GPT-4o (active)".to_string()),
+    //                 prompt_caching: None,
     //             },
     //             TeacherEntry {
     //                 provider: "claude".to_string(),
@@ -268,6 +433,7 @@ mod tests {
     //                 model: None,
     //                 base_url: None,
     //                 name: Some("Claude (backup)".to_string()),
+    //                 prompt_caching: None,
     //             },
     //         ],
     //     };
@@ -312,6 +478,13 @@ mod tests {
                 model: Some("gpt-4o".to_string()),
                 base_url: None,
                 name: Some("GPT-4o (best)".to_string()),
+                prompt_caching: None,
+                max_tokens: None,
+                pool: None,
+                thinking: None,
+                max_retries: None,
+                base_delay_ms: None,
+                request_timeout_seconds: None,
             },
             TeacherEntry {
                 provider: "openai".to_string(),
@@ -319,10 +492,17 @@ mod tests {
                 model: Some("gpt-4o-mini".to_string()),
                 base_url: None,
                 name: Some("GPT-4o-mini (cheaper)".to_string()),
+                prompt_caching: None,
+                max_tokens: None,
+                pool: None,
+                thinking: None,
+                max_retries: None,
+                base_delay_ms: None,
+                request_timeout_seconds: None,
             },
         ];
 
-        let providers = create_providers(&teachers).unwrap();
+        let providers = create_providers(&teachers, 4096).unwrap();
         assert_eq!(providers.len(), 2);
         assert_eq!(providers[0].name(), "openai");
         assert_eq!(providers[1].name(), "openai");
@@ -330,4 +510,94 @@ mod tests {
         assert_eq!(providers[0].default_model(), "gpt-4o");
         assert_eq!(providers[1].default_model(), "gpt-4o-mini");
     }
+
+    #[test]
+    fn test_pooled_teachers_become_one_provider() {
+        let entry = |api_key: &str, pool: Option<&str>| TeacherEntry {
+            provider: "openai".to_string(),
+            api_key: api_key.to_string(),
+            model: None,
+            base_url: None,
+            name: None,
+            prompt_caching: None,
+            max_tokens: None,
+            pool: pool.map(str::to_string),
+            thinking: None,
+            max_retries: None,
+            base_delay_ms: None,
+            request_timeout_seconds: None,
+        };
+        let teachers = vec![
+            entry("sk-key-1", Some("keys")),
+            entry("sk-key-2", None),
+            entry("sk-key-3", Some("keys")),
+        ];
+
+        let providers = create_providers(&teachers, 4096).unwrap();
+        assert_eq!(providers.len(), 2);
+        assert_eq!(providers[0].name(), "openai");
+
+        let pool = crate::providers::pool_statuses()
+            .into_iter()
+            .find(|s| s.name == "keys")
+            .unwrap();
+        let labels: Vec<&str> = pool.members.iter().map(|m| m.label.as_str()).collect();
+        assert_eq!(labels, ["key …ey-1", "key …ey-3"]);
+    }
+
+    #[test]
+    fn test_ollama_teacher_needs_model_not_key() {
+        let mut entry = TeacherEntry {
+            provider: "ollama".to_string(),
+            api_key: String::new(),
+            model: Some("qwen2.5:7b".to_string()),
+            base_url: Some("http://gpu-box:11434".to_string()),
+            name: None,
+            prompt_caching: None,
+            max_tokens: None,
+            pool: None,
+            thinking: None,
+            max_retries: None,
+            base_delay_ms: None,
+            request_timeout_seconds: None,
+        };
+
+        let provider = create_provider_from_entry(&entry, 4096).unwrap();
+        assert_eq!(provider.name(), "ollama");
+        assert_eq!(provider.default_model(), "qwen2.5:7b");
+        assert!(provider.supports_tools());
+
+        entry.model = None;
+        assert!(create_provider_from_entry(&entry, 4096).is_err());
+    }
+
+    #[test]
+    fn test_output_token_limit_uses_teacher_override_and_clamps() {
+        let mut entry = TeacherEntry {
+            provider: "claude".to_string(),
+            api_key: "test-key".to_string(),
+            model: None,
+            base_url: None,
+            name: None,
+            prompt_caching: None,
+            max_tokens: None,
+            pool: None,
+            thinking: None,
+            max_retries: None,
+            base_delay_ms: None,
+            request_timeout_seconds: None,
+        };
+
+        assert_eq!(output_token_limit(&entry, 4096, "claude-sonnet-4-20250514"), 4096);
+
+        entry.max_tokens = Some(16_000);
+        assert_eq!(output_token_limit(&entry, 4096, "claude-sonnet-4-20250514"), 16_000);
+
+        // Clamped to the model's known maximum
+        entry.max_tokens = Some(100_000);
+        assert_eq!(output_token_limit(&entry, 4096, "gpt-4o-mini"), 16_384);
+
+        // Unknown models are passed through unchanged
+        assert_eq!(output_token_limit(&entry, 4096, "llama-3.1-70b-versatile"), 100_000);
+    }
 }
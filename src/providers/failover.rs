@@ -0,0 +1,333 @@
+// Failover across configured teachers
+//
+// With more than one teacher in config, requests go to the first one and
+// move down the list only when a teacher looks unavailable: a server error,
+// rate limit, timeout or network failure. Other 4xx responses mean the
+// request itself is wrong, so they are returned straight away rather than
+// sent to every teacher in turn. Each request logs which teacher served it.
+
+use anyhow::{anyhow, Result};
+use tokio::sync::mpsc;
+
+use super::timeout::RequestTimeoutError;
+use super::{CacheBreakpoint, LlmProvider, ProviderRequest, ProviderResponse, StreamChunk};
+use crate::claude::retry::HttpStatusError;
+
+/// Teachers tried in priority order
+pub struct FailoverProvider {
+    teachers: Vec<(String, Box<dyn LlmProvider>)>,
+}
+
+impl FailoverProvider {
+    /// Build from labelled providers in priority order (labels name teachers in logs)
+    pub fn new(teachers: Vec<(String, Box<dyn LlmProvider>)>) -> Self {
+        Self { teachers }
+    }
+
+    pub fn len(&self) -> usize {
+        self.teachers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.teachers.is_empty()
+    }
+
+    /// Labels in the order teachers are tried
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        self.teachers.iter().map(|(label, _)| label.as_str())
+    }
+
+    fn primary(&self) -> Option<&dyn LlmProvider> {
+        self.teachers.first().map(|(_, teacher)| teacher.as_ref())
+    }
+
+    /// The request as sent to one teacher (its own model unless the caller picked one)
+    fn request_for(&self, teacher: &dyn LlmProvider, request: &ProviderRequest) -> ProviderRequest {
+        let mut request = request.clone();
+        if request.model.is_empty() || request.model == self.default_model() {
+            request.model = teacher.default_model().to_string();
+        }
+        request
+    }
+
+    fn served(&self, idx: usize) {
+        let label = &self.teachers[idx].0;
+        if idx == 0 {
+            tracing::info!("Teacher '{}' served the request", label);
+        } else {
+            tracing::info!(
+                "Teacher '{}' served the request after {} teacher(s) failed",
+                label,
+                idx
+            );
+        }
+    }
+
+    /// Decide whether a failed teacher should be skipped, logging the decision
+    fn fail_over(&self, idx: usize, error: &anyhow::Error) -> bool {
+        let label = &self.teachers[idx].0;
+        if !should_fail_over(error) {
+            tracing::warn!("Teacher '{}' rejected the request, not failing over: {}", label, error);
+            return false;
+        }
+        if idx + 1 < self.teachers.len() {
+            tracing::warn!(
+                "Teacher '{}' failed, trying '{}': {}",
+                label,
+                self.teachers[idx + 1].0,
+                error
+            );
+        }
+        true
+    }
+
+    fn exhausted(&self, last_error: Option<anyhow::Error>) -> anyhow::Error {
+        match last_error {
+            Some(e) => e.context(format!("All {} teachers failed", self.teachers.len())),
+            None => anyhow!("No teachers configured"),
+        }
+    }
+}
+
+/// Whether an error means the teacher is unavailable (try the next one) rather
+/// than that the request is bad
+///
+/// Server errors, 408/425/429, timeouts and network failures fail over; any
+/// other 4xx does not.
+pub fn should_fail_over(error: &anyhow::Error) -> bool {
+    if error.chain().any(|e| e.is::<RequestTimeoutError>()) {
+        return true;
+    }
+    match error.chain().find_map(|e| e.downcast_ref::<HttpStatusError>()) {
+        Some(http) => http.is_transient(),
+        // No response at all (connection refused, DNS, ...)
+        None => true,
+    }
+}
+
+#[async_trait::async_trait]
+impl LlmProvider for FailoverProvider {
+    async fn send_message(&self, request: &ProviderRequest) -> Result<ProviderResponse> {
+        let mut last_error = None;
+        for (idx, (_, teacher)) in self.teachers.iter().enumerate() {
+            match teacher.send_message(&self.request_for(teacher.as_ref(), request)).await {
+                Ok(response) => {
+                    self.served(idx);
+                    return Ok(response);
+                }
+                Err(e) if self.fail_over(idx, &e) => last_error = Some(e),
+                Err(e) => return Err(e),
+            }
+        }
+        Err(self.exhausted(last_error))
+    }
+
+    /// Fails over only while starting the stream; errors once chunks are
+    /// flowing reach the caller through the channel
+    async fn send_message_stream(
+        &self,
+        request: &ProviderRequest,
+    ) -> Result<mpsc::Receiver<Result<StreamChunk>>> {
+        let mut last_error = None;
+        for (idx, (_, teacher)) in self.teachers.iter().enumerate() {
+            match teacher
+                .send_message_stream(&self.request_for(teacher.as_ref(), request))
+                .await
+            {
+                Ok(rx) => {
+                    self.served(idx);
+                    return Ok(rx);
+                }
+                Err(e) if self.fail_over(idx, &e) => last_error = Some(e),
+                Err(e) => return Err(e),
+            }
+        }
+        Err(self.exhausted(last_error))
+    }
+
+    fn name(&self) -> &str {
+        self.primary().map_or("failover", |p| p.name())
+    }
+
+    fn default_model(&self) -> &str {
+        self.primary().map_or("default", |p| p.default_model())
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.primary().is_some_and(|p| p.supports_streaming())
+    }
+
+    fn supports_tools(&self) -> bool {
+        self.primary().is_some_and(|p| p.supports_tools())
+    }
+
+    fn cache_breakpoints(&self, request: &ProviderRequest) -> Vec<CacheBreakpoint> {
+        self.primary()
+            .map(|p| p.cache_breakpoints(&self.request_for(p, request)))
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::claude::types::ContentBlock;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    /// How a mock teacher answers
+    #[derive(Clone, Copy)]
+    enum Reply {
+        Ok,
+        Status(u16),
+        Timeout,
+    }
+
+    struct MockTeacher {
+        name: &'static str,
+        reply: Reply,
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl MockTeacher {
+        fn result(&self) -> Result<()> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            match self.reply {
+                Reply::Ok => Ok(()),
+                Reply::Status(status) => Err(HttpStatusError {
+                    what: "Mock API request".to_string(),
+                    status: reqwest::StatusCode::from_u16(status).unwrap(),
+                    retry_after: None,
+                    body: String::new(),
+                }
+                .into()),
+                Reply::Timeout => Err(RequestTimeoutError {
+                    after: Duration::from_secs(1),
+                    idle: false,
+                }
+                .into()),
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl LlmProvider for MockTeacher {
+        async fn send_message(&self, _request: &ProviderRequest) -> Result<ProviderResponse> {
+            self.result()?;
+            Ok(ProviderResponse {
+                id: "test-id".to_string(),
+                model: "test-model".to_string(),
+                content: vec![ContentBlock::Text {
+                    text: "Test response".to_string(),
+                }],
+                stop_reason: Some("end_turn".to_string()),
+                role: "assistant".to_string(),
+                provider: self.name.to_string(),
+                usage: None,
+            })
+        }
+
+        async fn send_message_stream(
+            &self,
+            _request: &ProviderRequest,
+        ) -> Result<mpsc::Receiver<Result<StreamChunk>>> {
+            self.result()?;
+            let (tx, rx) = mpsc::channel(1);
+            tx.send(Ok(StreamChunk::TextDelta(self.name.to_string()))).await.unwrap();
+            Ok(rx)
+        }
+
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn default_model(&self) -> &str {
+            "test-model"
+        }
+    }
+
+    fn failover(replies: &[(&'static str, Reply)]) -> (FailoverProvider, Vec<Arc<AtomicUsize>>) {
+        let calls: Vec<Arc<AtomicUsize>> = replies.iter().map(|_| Arc::new(AtomicUsize::new(0))).collect();
+        let teachers = replies
+            .iter()
+            .zip(&calls)
+            .map(|(&(name, reply), calls)| {
+                let teacher: Box<dyn LlmProvider> = Box::new(MockTeacher {
+                    name,
+                    reply,
+                    calls: Arc::clone(calls),
+                });
+                (name.to_string(), teacher)
+            })
+            .collect();
+        (FailoverProvider::new(teachers), calls)
+    }
+
+    fn request() -> ProviderRequest {
+        ProviderRequest {
+            messages: vec![],
+            system: None,
+            model: String::new(),
+            max_tokens: Some(100),
+            temperature: None,
+            tools: None,
+            thinking: None,
+            stream: false,
+        }
+    }
+
+    fn count(calls: &[Arc<AtomicUsize>]) -> Vec<usize> {
+        calls.iter().map(|c| c.load(Ordering::SeqCst)).collect()
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_when_teacher_unavailable() {
+        let (provider, calls) = failover(&[
+            ("primary", Reply::Status(503)),
+            ("second", Reply::Timeout),
+            ("third", Reply::Ok),
+        ]);
+        let response = provider.send_message(&request()).await.unwrap();
+        assert_eq!(response.provider, "third");
+        assert_eq!(count(&calls), vec![1, 1, 1]);
+
+        let mut rx = provider.send_message_stream(&request()).await.unwrap();
+        match rx.recv().await {
+            Some(Ok(StreamChunk::TextDelta(text))) => assert_eq!(text, "third"),
+            other => panic!("unexpected chunk: {:?}", other),
+        }
+
+        // The primary answering stops the search
+        let (provider, calls) = failover(&[("primary", Reply::Ok), ("second", Reply::Ok)]);
+        assert_eq!(provider.send_message(&request()).await.unwrap().provider, "primary");
+        assert_eq!(count(&calls), vec![1, 0]);
+    }
+
+    #[tokio::test]
+    async fn test_client_errors_do_not_fail_over() {
+        let (provider, calls) = failover(&[("primary", Reply::Status(400)), ("second", Reply::Ok)]);
+        let error = provider.send_message(&request()).await.unwrap_err();
+        assert!(error.to_string().contains("400"));
+        assert_eq!(count(&calls), vec![1, 0]);
+
+        let (provider, calls) = failover(&[("primary", Reply::Status(404)), ("second", Reply::Ok)]);
+        assert!(provider.send_message_stream(&request()).await.is_err());
+        assert_eq!(count(&calls), vec![1, 0]);
+
+        // Rate limits are the provider being busy, not a bad request
+        let (provider, _) = failover(&[("primary", Reply::Status(429)), ("second", Reply::Ok)]);
+        assert_eq!(provider.send_message(&request()).await.unwrap().provider, "second");
+    }
+
+    #[tokio::test]
+    async fn test_all_teachers_fail() {
+        let (provider, calls) = failover(&[
+            ("primary", Reply::Status(500)),
+            ("second", Reply::Status(502)),
+        ]);
+        let error = provider.send_message(&request()).await.unwrap_err();
+        assert!(format!("{:#}", error).contains("All 2 teachers failed"));
+        assert_eq!(count(&calls), vec![1, 1]);
+    }
+}
@@ -1,14 +1,19 @@
 // Fallback chain for automatic provider retry
 //
-// Tries providers in priority order until one succeeds
+// Tries providers in priority order until one succeeds. Superseded by
+// `FailoverProvider`, which `create_providers` builds for multi-teacher
+// configs and which stops on client errors instead of trying every provider.
+
+#![allow(deprecated)]
 
 use anyhow::{Context, Result};
 use std::sync::Arc;
 use tokio::sync::mpsc;
 
-use super::{LlmProvider, ProviderRequest, ProviderResponse, StreamChunk};
+use super::{CacheBreakpoint, LlmProvider, ProviderRequest, ProviderResponse, StreamChunk};
 
 /// A chain of providers to try in order
+#[deprecated(note = "use FailoverProvider, which only moves on when a teacher is unavailable")]
 pub struct FallbackChain {
     providers: Arc<Vec<Box<dyn LlmProvider>>>,
 }
@@ -55,9 +60,11 @@ impl FallbackChain {
             let provider_request = ProviderRequest {
                 model: provider.default_model().to_string(),
                 messages: request.messages.clone(),
+                system: request.system.clone(),
                 max_tokens: request.max_tokens,
                 tools: request.tools.clone(),
                 temperature: request.temperature,
+                thinking: request.thinking,
                 stream: request.stream,
             };
 
@@ -116,9 +123,11 @@ impl FallbackChain {
             let provider_request = ProviderRequest {
                 model: provider.default_model().to_string(),
                 messages: request.messages.clone(),
+                system: request.system.clone(),
                 max_tokens: request.max_tokens,
                 tools: request.tools.clone(),
                 temperature: request.temperature,
+                thinking: request.thinking,
                 stream: request.stream,
             };
 
@@ -193,6 +202,12 @@ impl LlmProvider for FallbackChain {
             .map(|p| p.supports_tools())
             .unwrap_or(false)
     }
+
+    fn cache_breakpoints(&self, request: &ProviderRequest) -> Vec<CacheBreakpoint> {
+        self.primary_provider()
+            .map(|p| p.cache_breakpoints(request))
+            .unwrap_or_default()
+    }
 }
 
 #[cfg(test)]
@@ -231,6 +246,7 @@ mod tests {
                 stop_reason: Some("end_turn".to_string()),
                 role: "assistant".to_string(),
                 provider: self.name.clone(),
+                usage: None,
             })
         }
 
@@ -276,10 +292,12 @@ mod tests {
         let chain = FallbackChain::new(providers);
         let request = ProviderRequest {
             messages: vec![],
+            system: None,
             model: String::new(),
-            max_tokens: 100,
+            max_tokens: Some(100),
             temperature: None,
             tools: None,
+            thinking: None,
             stream: false,
         };
 
@@ -298,10 +316,12 @@ mod tests {
         let chain = FallbackChain::new(providers);
         let request = ProviderRequest {
             messages: vec![],
+            system: None,
             model: String::new(),
-            max_tokens: 100,
+            max_tokens: Some(100),
             temperature: None,
             tools: None,
+            thinking: None,
             stream: false,
         };
 
@@ -320,10 +340,12 @@ mod tests {
         let chain = FallbackChain::new(providers);
         let request = ProviderRequest {
             messages: vec![],
+            system: None,
             model: String::new(),
-            max_tokens: 100,
+            max_tokens: Some(100),
             temperature: None,
             tools: None,
+            thinking: None,
             stream: false,
         };
 
@@ -341,10 +363,12 @@ mod tests {
         let chain = FallbackChain::new(providers);
         let request = ProviderRequest {
             messages: vec![],
+            system: None,
             model: String::new(),
-            max_tokens: 100,
+            max_tokens: Some(100),
             temperature: None,
             tools: None,
+            thinking: None,
             stream: true,
         };
 
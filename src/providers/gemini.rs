@@ -12,12 +12,15 @@ use std::time::Duration;
 use tokio::sync::mpsc;
 use uuid::Uuid;
 
-use super::types::{ProviderRequest, ProviderResponse, StreamChunk};
+use super::types::{
+    stop_reason_from_finish_reason, ProviderRequest, ProviderResponse, StreamChunk, TokenUsage,
+};
+use super::timeout::{check_timeout, send_stream_request, with_idle_timeout, DEFAULT_REQUEST_TIMEOUT_SECS};
 use super::LlmProvider;
-use crate::claude::retry::with_retry;
+use crate::claude::retry::{with_retry_config, HttpStatusError, RetryConfig};
 use crate::claude::types::ContentBlock;
+use crate::config::DEFAULT_TEACHER_MAX_TOKENS;
 
-const REQUEST_TIMEOUT_SECS: u64 = 60;
 const GEMINI_BASE_URL: &str = "https://generativelanguage.googleapis.com/v1beta";
 
 /// Google Gemini API provider
@@ -26,22 +29,27 @@ const GEMINI_BASE_URL: &str = "https://generativelanguage.googleapis.com/v1beta"
 #[derive(Clone)]
 pub struct GeminiProvider {
     client: Client,
+    request_timeout: Duration,
     api_key: String,
     default_model: String,
+    max_tokens: u32,
+    retry: RetryConfig,
 }
 
 impl GeminiProvider {
     /// Create a new Gemini provider
     pub fn new(api_key: String) -> Result<Self> {
         let client = Client::builder()
-            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
             .build()
             .context("Failed to create HTTP client")?;
 
         Ok(Self {
             client,
+            request_timeout: Duration::from_secs(DEFAULT_REQUEST_TIMEOUT_SECS),
             api_key,
             default_model: "gemini-2.0-flash-exp".to_string(),
+            max_tokens: DEFAULT_TEACHER_MAX_TOKENS,
+            retry: RetryConfig::default(),
         })
     }
 
@@ -51,6 +59,25 @@ impl GeminiProvider {
         self
     }
 
+    /// Create with custom output token limit (used when a request doesn't set one)
+    pub fn with_max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = max_tokens;
+        self
+    }
+
+    /// Set the request timeout (`request_timeout_seconds`); for streaming it
+    /// bounds the wait between chunks rather than the whole response
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    /// Set the retry attempts and backoff (`max_retries` / `base_delay_ms`)
+    pub fn with_retry_config(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
     /// Convert ProviderRequest to Gemini API format
     fn to_gemini_request(&self, request: &ProviderRequest) -> GeminiRequest {
         let model = if request.model.is_empty() {
@@ -59,10 +86,30 @@ impl GeminiProvider {
             request.model.clone()
         };
 
+        // Gemini takes system prompts separately, not as a content role
+        let system_parts: Vec<GeminiPart> = request
+            .system
+            .iter()
+            .cloned()
+            .chain(
+                request
+                    .messages
+                    .iter()
+                    .filter(|msg| msg.role == "system")
+                    .map(|msg| msg.text()),
+            )
+            .map(|text| GeminiPart::Text { text })
+            .collect();
+        let system_instruction = (!system_parts.is_empty()).then(|| GeminiContent {
+            role: "user".to_string(),
+            parts: system_parts,
+        });
+
         // Convert messages to Gemini's contents format
         let contents: Vec<GeminiContent> = request
             .messages
             .iter()
+            .filter(|msg| msg.role != "system")
             .map(|msg| {
                 // Gemini uses "model" instead of "assistant"
                 let role = if msg.role == "assistant" {
@@ -75,21 +122,21 @@ impl GeminiProvider {
                 let parts: Vec<GeminiPart> = msg
                     .content
                     .iter()
-                    .map(|block| match block {
-                        ContentBlock::Text { text } => GeminiPart::Text {
+                    .filter_map(|block| match block {
+                        ContentBlock::Text { text } => Some(GeminiPart::Text {
                             text: text.clone(),
-                        },
-                        ContentBlock::ToolUse { id: _, name, input } => GeminiPart::FunctionCall {
+                        }),
+                        ContentBlock::ToolUse { id: _, name, input } => Some(GeminiPart::FunctionCall {
                             function_call: GeminiFunctionCall {
                                 name: name.clone(),
                                 args: input.clone(),
                             },
-                        },
+                        }),
                         ContentBlock::ToolResult {
                             tool_use_id,
                             content,
                             is_error,
-                        } => GeminiPart::FunctionResponse {
+                        } => Some(GeminiPart::FunctionResponse {
                             function_response: GeminiFunctionResponse {
                                 name: tool_use_id.clone(),
                                 response: serde_json::json!({
@@ -97,7 +144,13 @@ impl GeminiProvider {
                                     "is_error": is_error.unwrap_or(false),
                                 }),
                             },
-                        },
+                        }),
+                        ContentBlock::Image { .. } => {
+                            tracing::warn!("Gemini provider doesn't support images yet, dropping image block");
+                            None
+                        }
+                        // Claude's thinking blocks mean nothing to Gemini
+                        ContentBlock::Thinking { .. } | ContentBlock::RedactedThinking { .. } => None,
                     })
                     .collect();
 
@@ -139,13 +192,14 @@ impl GeminiProvider {
 
         let generation_config = GeminiGenerationConfig {
             temperature: request.temperature,
-            max_output_tokens: Some(request.max_tokens as i32),
+            max_output_tokens: Some(request.max_tokens.unwrap_or(self.max_tokens) as i32),
             ..Default::default()
         };
 
         GeminiRequest {
             model,
             contents,
+            system_instruction,
             tools,
             generation_config: Some(generation_config),
         }
@@ -157,6 +211,10 @@ impl GeminiProvider {
         response: GeminiResponse,
         model: String,
     ) -> Result<ProviderResponse> {
+        let usage = response.usage_metadata.map(|usage| TokenUsage {
+            input_tokens: usage.prompt_token_count,
+            output_tokens: usage.candidates_token_count,
+        });
         let candidate = response
             .candidates
             .into_iter()
@@ -195,6 +253,7 @@ impl GeminiProvider {
             stop_reason: candidate.finish_reason,
             role: "assistant".to_string(), // Convert "model" back to "assistant"
             provider: "gemini".to_string(),
+            usage,
         })
     }
 
@@ -215,24 +274,22 @@ impl GeminiProvider {
             .post(&url)
             .header("content-type", "application/json")
             .json(&gemini_request)
+            .timeout(self.request_timeout)
             .send()
             .await
+            .map_err(|e| check_timeout(e, self.request_timeout))
             .context("Failed to send request to Gemini API")?;
 
         let status = response.status();
 
         if !status.is_success() {
-            let error_body = response.text().await.unwrap_or_default();
-            anyhow::bail!(
-                "Gemini API request failed\n\nStatus: {}\nBody: {}",
-                status,
-                error_body
-            );
+            return Err(HttpStatusError::from_response("Gemini API request", response).await.into());
         }
 
         let gemini_response: GeminiResponse = response
             .json()
             .await
+            .map_err(|e| check_timeout(e, self.request_timeout))
             .context("Failed to parse Gemini API response")?;
 
         tracing::debug!("Received response: {:?}", gemini_response);
@@ -257,29 +314,28 @@ impl GeminiProvider {
 
         tracing::debug!("Sending streaming request to Gemini API");
 
-        let response = self
+        let request = self
             .client
             .post(&url)
             .header("content-type", "application/json")
-            .json(&gemini_request)
-            .send()
+            .json(&gemini_request);
+        let response = send_stream_request(request, self.request_timeout)
             .await
             .context("Failed to send streaming request to Gemini API")?;
 
         let status = response.status();
         if !status.is_success() {
-            let error_body = response.text().await.unwrap_or_default();
-            anyhow::bail!(
-                "Gemini API streaming request failed\n\nStatus: {}\nBody: {}",
-                status,
-                error_body
-            );
+            return Err(HttpStatusError::from_response("Gemini API streaming request", response)
+                .await
+                .into());
         }
 
+        let idle = self.request_timeout;
+
         // Spawn task to parse streaming response
         tokio::spawn(async move {
             tracing::debug!("[STREAM] Gemini streaming task started");
-            let mut stream = response.bytes_stream();
+            let mut stream = with_idle_timeout(response.bytes_stream(), idle);
             let mut buffer = Vec::new();
             let mut accumulated_text = String::new();
             let mut done = false;
@@ -345,8 +401,10 @@ impl GeminiProvider {
                                         }
 
                                         // Check for finish
-                                        if candidate.finish_reason.is_some() {
+                                        if let Some(finish_reason) = candidate.finish_reason {
                                             tracing::debug!("[STREAM] Stream completed");
+                                            let stop_reason = stop_reason_from_finish_reason(&finish_reason);
+                                            let _ = tx.send(Ok(StreamChunk::StopReason(stop_reason))).await;
                                             done = true;
                                             break;
                                         }
@@ -382,14 +440,14 @@ impl GeminiProvider {
 #[async_trait]
 impl LlmProvider for GeminiProvider {
     async fn send_message(&self, request: &ProviderRequest) -> Result<ProviderResponse> {
-        with_retry(|| self.send_message_once(request)).await
+        with_retry_config(&self.retry, || self.send_message_once(request)).await
     }
 
     async fn send_message_stream(
         &self,
         request: &ProviderRequest,
     ) -> Result<mpsc::Receiver<Result<StreamChunk>>> {
-        with_retry(|| self.send_message_stream_once(request)).await
+        with_retry_config(&self.retry, || self.send_message_stream_once(request)).await
     }
 
     fn name(&self) -> &str {
@@ -417,6 +475,8 @@ struct GeminiRequest {
     model: String, // Used in URL, not in body
     contents: Vec<GeminiContent>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    system_instruction: Option<GeminiContent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     tools: Option<Vec<GeminiTools>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     generation_config: Option<GeminiGenerationConfig>,
@@ -487,6 +547,16 @@ struct GeminiGenerationConfig {
 #[derive(Debug, Clone, Deserialize)]
 struct GeminiResponse {
     candidates: Vec<GeminiCandidate>,
+    #[serde(rename = "usageMetadata", default)]
+    usage_metadata: Option<GeminiUsageMetadata>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct GeminiUsageMetadata {
+    #[serde(rename = "promptTokenCount", default)]
+    prompt_token_count: u64,
+    #[serde(rename = "candidatesTokenCount", default)]
+    candidates_token_count: u64,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -14,6 +14,7 @@ pub mod types;
 pub mod claude;
 pub mod openai;
 pub mod gemini;
+pub mod ollama;
 
 // Provider factory
 pub mod factory;
@@ -21,16 +22,37 @@ pub mod factory;
 // Fallback chain (not used in student-teacher architecture)
 pub mod fallback_chain;
 
+// Failover between configured teachers on server errors and timeouts
+pub mod failover;
+
 // Teacher session management with context optimization
 pub mod teacher_session;
 
+// Fail-fast protection when the teacher API is down
+pub mod circuit_breaker;
+
+// Round-robin load sharing across several keys for one provider
+pub mod pool;
+
+// Request and stream-idle timeouts (request_timeout_seconds)
+pub mod timeout;
+
 // Re-export commonly used types
+pub use circuit_breaker::{
+    CircuitBreaker, CircuitBreakerConfig, CircuitBreakerStatus, CircuitOpenError, CircuitState,
+};
 pub use factory::{create_provider, create_providers};
+pub use failover::FailoverProvider;
+#[allow(deprecated)]
 pub use fallback_chain::FallbackChain;
+pub use pool::{pool_statuses, PoolMemberStatus, PoolStatus, TeacherPool};
 pub use teacher_session::{
-    ConversationState, OptimizationStats, TeacherContextConfig, TeacherSession,
+    ContextPreview, ConversationState, MessageFate, OptimizationStats, TeacherContextConfig,
+    TeacherSession, TrimmedMessage,
+};
+pub use types::{
+    CacheBreakpoint, ProviderRequest, ProviderResponse, StreamChunk, TemperatureOverride, ThinkingOverride, TokenUsage,
 };
-pub use types::{ProviderRequest, ProviderResponse, StreamChunk};
 
 /// Trait for LLM providers
 ///
@@ -67,6 +89,14 @@ pub trait LlmProvider: Send + Sync {
     fn supports_tools(&self) -> bool {
         true // Most modern providers support tools
     }
+
+    /// Where prompt-cache breakpoints would go if `request` were sent
+    ///
+    /// Providers without explicit cache control (caching is automatic or
+    /// unsupported) return none.
+    fn cache_breakpoints(&self, _request: &ProviderRequest) -> Vec<CacheBreakpoint> {
+        Vec::new()
+    }
 }
 
 /// Helper to convert provider response to format compatible with existing code
@@ -79,6 +109,7 @@ impl From<ProviderResponse> for crate::claude::types::MessageResponse {
             content: response.content,
             model: response.model,
             stop_reason: response.stop_reason,
+            usage: response.usage,
         }
     }
 }
@@ -0,0 +1,714 @@
+// Ollama provider implementation
+//
+// Talks to a local (or remote) Ollama server's native /api/chat endpoint.
+// Ollama streams newline-delimited JSON objects rather than SSE, and returns
+// tool calls whole (with arguments as an object, and without IDs), so it
+// gets its own request conversion and stream parser.
+
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use futures::stream::StreamExt;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use super::types::{
+    stop_reason_from_finish_reason, ProviderRequest, ProviderResponse, StreamChunk, TokenUsage,
+};
+use super::timeout::{check_timeout, send_stream_request, with_idle_timeout};
+use super::LlmProvider;
+use crate::claude::retry::{with_retry_config, HttpStatusError, RetryConfig};
+use crate::claude::types::ContentBlock;
+use crate::config::DEFAULT_TEACHER_MAX_TOKENS;
+
+/// Local models can take a while to load on first use
+const REQUEST_TIMEOUT_SECS: u64 = 300;
+
+/// Where `ollama serve` listens by default
+pub const DEFAULT_OLLAMA_URL: &str = "http://127.0.0.1:11434";
+
+/// Model families with function calling support in Ollama
+const TOOL_MODEL_FAMILIES: &[&str] = &[
+    "llama3.1",
+    "llama3.2",
+    "llama3.3",
+    "llama4",
+    "qwen2",
+    "qwen2.5",
+    "qwen2.5-coder",
+    "qwen3",
+    "qwq",
+    "mistral",
+    "mistral-nemo",
+    "mistral-small",
+    "mistral-large",
+    "mixtral",
+    "command-r",
+    "command-r-plus",
+    "firefunction-v2",
+    "hermes3",
+    "granite3",
+    "granite3.1-dense",
+    "granite3.2",
+    "granite3.3",
+    "smollm2",
+    "nemotron",
+    "devstral",
+    "gpt-oss",
+];
+
+/// Whether an Ollama model tag (e.g. "qwen2.5:7b") supports tool calling
+fn model_supports_tools(model: &str) -> bool {
+    // Drop any namespace ("library/", "user/") and the size tag (":7b")
+    let name = model.rsplit('/').next().unwrap_or(model);
+    let family = name.split(':').next().unwrap_or(name).to_lowercase();
+    TOOL_MODEL_FAMILIES.contains(&family.as_str())
+}
+
+/// Ollama provider
+///
+/// No API key; the model comes from the teacher entry (Ollama has no
+/// universal default).
+#[derive(Clone)]
+pub struct OllamaProvider {
+    client: Client,
+    request_timeout: Duration,
+    base_url: String,
+    default_model: String,
+    max_tokens: u32,
+    retry: RetryConfig,
+}
+
+impl OllamaProvider {
+    /// Create a provider for `model` on the server at `base_url`
+    pub fn new(base_url: impl Into<String>, model: impl Into<String>) -> Result<Self> {
+        let client = Client::builder()
+            .build()
+            .context("Failed to create HTTP client")?;
+
+        Ok(Self {
+            client,
+            request_timeout: Duration::from_secs(REQUEST_TIMEOUT_SECS),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            default_model: model.into(),
+            max_tokens: DEFAULT_TEACHER_MAX_TOKENS,
+            retry: RetryConfig::default(),
+        })
+    }
+
+    /// Set the output token limit used when a request doesn't set one
+    pub fn with_max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = max_tokens;
+        self
+    }
+
+    /// Set the retry attempts and backoff (`max_retries` / `base_delay_ms`)
+    pub fn with_retry_config(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Set the request timeout (`request_timeout_seconds`); for streaming it
+    /// bounds the wait between chunks rather than the whole response
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    /// Convert ProviderRequest to Ollama's /api/chat format
+    fn to_ollama_request(&self, request: &ProviderRequest, stream: bool) -> OllamaRequest {
+        let model = if request.model.is_empty() {
+            self.default_model.clone()
+        } else {
+            request.model.clone()
+        };
+
+        // Tool results carry only the call ID; Ollama wants the tool name
+        let tool_names: HashMap<&str, &str> = request
+            .messages
+            .iter()
+            .flat_map(|msg| &msg.content)
+            .filter_map(|block| match block {
+                ContentBlock::ToolUse { id, name, .. } => Some((id.as_str(), name.as_str())),
+                _ => None,
+            })
+            .collect();
+
+        let mut messages = Vec::new();
+        if let Some(system) = &request.system {
+            messages.push(OllamaMessage {
+                role: "system".to_string(),
+                content: system.clone(),
+                tool_calls: None,
+                tool_name: None,
+            });
+        }
+        for msg in &request.messages {
+            let mut text_parts = Vec::new();
+            let mut tool_calls = Vec::new();
+            let mut tool_results = Vec::new();
+
+            for block in &msg.content {
+                match block {
+                    ContentBlock::Text { text } => text_parts.push(text.as_str()),
+                    ContentBlock::ToolUse { name, input, .. } => tool_calls.push(OllamaToolCall {
+                        function: OllamaFunctionCall {
+                            name: name.clone(),
+                            arguments: input.clone(),
+                        },
+                    }),
+                    ContentBlock::ToolResult {
+                        tool_use_id,
+                        content,
+                        ..
+                    } => tool_results.push(OllamaMessage {
+                        role: "tool".to_string(),
+                        content: content.clone(),
+                        tool_calls: None,
+                        tool_name: tool_names.get(tool_use_id.as_str()).map(|name| name.to_string()),
+                    }),
+                    ContentBlock::Image { .. } => {
+                        tracing::warn!("Ollama provider doesn't support images yet, dropping image block")
+                    }
+                    // Claude's thinking blocks mean nothing to other providers
+                    ContentBlock::Thinking { .. } | ContentBlock::RedactedThinking { .. } => {}
+                }
+            }
+
+            if !text_parts.is_empty() || !tool_calls.is_empty() {
+                messages.push(OllamaMessage {
+                    role: msg.role.clone(),
+                    content: text_parts.join("\n"),
+                    tool_calls: (!tool_calls.is_empty()).then_some(tool_calls),
+                    tool_name: None,
+                });
+            }
+            messages.extend(tool_results);
+        }
+
+        // Ollama rejects tools for models without function calling
+        let tools = request.tools.as_ref().filter(|_| model_supports_tools(&model)).map(|tool_defs| {
+            tool_defs
+                .iter()
+                .map(|tool| {
+                    let parameters = match serde_json::to_value(&tool.input_schema) {
+                        Ok(value) => value,
+                        Err(e) => {
+                            tracing::warn!(
+                                "Failed to convert tool schema for '{}': {}",
+                                tool.name,
+                                e
+                            );
+                            serde_json::json!({})
+                        }
+                    };
+
+                    OllamaTool {
+                        tool_type: "function".to_string(),
+                        function: OllamaFunction {
+                            name: tool.name.clone(),
+                            description: tool.description.clone(),
+                            parameters,
+                        },
+                    }
+                })
+                .collect()
+        });
+
+        OllamaRequest {
+            model,
+            messages,
+            tools,
+            stream,
+            options: OllamaOptions {
+                temperature: request.temperature,
+                num_predict: Some(request.max_tokens.unwrap_or(self.max_tokens)),
+            },
+        }
+    }
+
+    /// Send a single message request (no retry)
+    async fn send_message_once(&self, request: &ProviderRequest) -> Result<ProviderResponse> {
+        let ollama_request = self.to_ollama_request(request, false);
+        let url = format!("{}/api/chat", self.base_url);
+
+        tracing::debug!("Sending request to Ollama: {:?}", ollama_request);
+
+        let response = self
+            .client
+            .post(&url)
+            .json(&ollama_request)
+            .timeout(self.request_timeout)
+            .send()
+            .await
+            .map_err(|e| check_timeout(e, self.request_timeout))
+            .with_context(|| format!("Failed to send request to Ollama at {}", self.base_url))?;
+
+        if !response.status().is_success() {
+            return Err(HttpStatusError::from_response("Ollama request", response).await.into());
+        }
+
+        let chunk: OllamaChunk = response
+            .json()
+            .await
+            .map_err(|e| check_timeout(e, self.request_timeout))
+            .context("Failed to parse Ollama response")?;
+
+        tracing::debug!("Received response: {:?}", chunk);
+
+        if let Some(error) = chunk.error {
+            bail!("Ollama error: {}", error);
+        }
+
+        let usage = match (chunk.prompt_eval_count, chunk.eval_count) {
+            (None, None) => None,
+            (input, output) => Some(TokenUsage {
+                input_tokens: input.unwrap_or(0),
+                output_tokens: output.unwrap_or(0),
+            }),
+        };
+        let mut content = Vec::new();
+        let mut stop_reason = chunk.done_reason;
+        if let Some(message) = chunk.message {
+            if !message.content.is_empty() {
+                content.push(ContentBlock::Text {
+                    text: message.content,
+                });
+            }
+            for call in message.tool_calls.unwrap_or_default() {
+                content.push(call.into_tool_use());
+                stop_reason = Some("tool_use".to_string());
+            }
+        }
+
+        Ok(ProviderResponse {
+            id: format!("ollama-{}", Uuid::new_v4()), // Ollama doesn't provide response IDs
+            model: chunk.model.unwrap_or(ollama_request.model),
+            content,
+            stop_reason,
+            role: "assistant".to_string(),
+            provider: "ollama".to_string(),
+            usage,
+        })
+    }
+
+    /// Send a message with streaming response (no retry)
+    async fn send_message_stream_once(
+        &self,
+        request: &ProviderRequest,
+    ) -> Result<mpsc::Receiver<Result<StreamChunk>>> {
+        let (tx, rx) = mpsc::channel(100);
+
+        let ollama_request = self.to_ollama_request(request, true);
+        let url = format!("{}/api/chat", self.base_url);
+
+        tracing::debug!("Sending streaming request to Ollama");
+
+        let request = self
+            .client
+            .post(&url)
+            .json(&ollama_request);
+        let response = send_stream_request(request, self.request_timeout)
+            .await
+            .with_context(|| format!("Failed to send streaming request to Ollama at {}", self.base_url))?;
+
+        if !response.status().is_success() {
+            return Err(HttpStatusError::from_response("Ollama streaming request", response).await.into());
+        }
+
+        let idle = self.request_timeout;
+
+        // Spawn task to parse the NDJSON stream
+        tokio::spawn(async move {
+            tracing::debug!("[STREAM] Ollama streaming task started");
+            let mut stream = with_idle_timeout(response.bytes_stream(), idle);
+            let mut parser = NdjsonParser::default();
+            let mut accumulated_text = String::new();
+
+            'stream: while let Some(chunk) = stream.next().await {
+                let bytes = match chunk {
+                    Ok(bytes) => bytes,
+                    Err(e) => {
+                        tracing::error!("Stream error: {}", e);
+                        let _ = tx.send(Err(e.into())).await;
+                        break;
+                    }
+                };
+
+                for line in parser.push(&bytes) {
+                    let chunk = match serde_json::from_str::<OllamaChunk>(&line) {
+                        Ok(chunk) => chunk,
+                        Err(e) => {
+                            tracing::debug!("[STREAM] Skipping unparseable Ollama line: {}", e);
+                            continue;
+                        }
+                    };
+                    if let Some(error) = chunk.error {
+                        let _ = tx.send(Err(anyhow::anyhow!("Ollama error: {}", error))).await;
+                        break 'stream;
+                    }
+
+                    if let Some(message) = chunk.message {
+                        if !message.content.is_empty() {
+                            accumulated_text.push_str(&message.content);
+                            if tx.send(Ok(StreamChunk::TextDelta(message.content))).await.is_err() {
+                                break 'stream;
+                            }
+                        }
+                        // Tool calls arrive whole, not as argument deltas
+                        for call in message.tool_calls.unwrap_or_default() {
+                            let block = call.into_tool_use();
+                            if tx.send(Ok(StreamChunk::ContentBlockComplete(block))).await.is_err() {
+                                break 'stream;
+                            }
+                        }
+                    }
+
+                    if let Some(eval_count) = chunk.eval_count {
+                        let _ = tx.send(Ok(StreamChunk::OutputTokens(eval_count as u32))).await;
+                    }
+
+                    if chunk.done {
+                        tracing::debug!("[STREAM] Stream completed ({:?})", chunk.done_reason);
+                        if let Some(done_reason) = &chunk.done_reason {
+                            let stop_reason = stop_reason_from_finish_reason(done_reason);
+                            let _ = tx.send(Ok(StreamChunk::StopReason(stop_reason))).await;
+                        }
+                        break 'stream;
+                    }
+                }
+            }
+
+            // Send final complete block if we have text
+            if !accumulated_text.is_empty() {
+                let block = ContentBlock::Text {
+                    text: accumulated_text,
+                };
+                let _ = tx.send(Ok(StreamChunk::ContentBlockComplete(block))).await;
+            }
+
+            tracing::debug!("[STREAM] Ollama streaming task finished");
+        });
+
+        Ok(rx)
+    }
+}
+
+#[async_trait]
+impl LlmProvider for OllamaProvider {
+    async fn send_message(&self, request: &ProviderRequest) -> Result<ProviderResponse> {
+        with_retry_config(&self.retry, || self.send_message_once(request)).await
+    }
+
+    async fn send_message_stream(
+        &self,
+        request: &ProviderRequest,
+    ) -> Result<mpsc::Receiver<Result<StreamChunk>>> {
+        with_retry_config(&self.retry, || self.send_message_stream_once(request)).await
+    }
+
+    fn name(&self) -> &str {
+        "ollama"
+    }
+
+    fn default_model(&self) -> &str {
+        &self.default_model
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    fn supports_tools(&self) -> bool {
+        model_supports_tools(&self.default_model)
+    }
+}
+
+/// Splits a byte stream into complete newline-delimited JSON lines
+#[derive(Debug, Default)]
+struct NdjsonParser {
+    buffer: Vec<u8>,
+}
+
+impl NdjsonParser {
+    /// Add bytes and return the lines they complete (blank lines skipped)
+    fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.buffer.extend_from_slice(bytes);
+        let mut lines = Vec::new();
+        while let Some(newline_pos) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line_bytes: Vec<u8> = self.buffer.drain(..=newline_pos).collect();
+            let line = String::from_utf8_lossy(&line_bytes).trim().to_string();
+            if !line.is_empty() {
+                lines.push(line);
+            }
+        }
+        lines
+    }
+}
+
+// Ollama API types
+
+#[derive(Debug, Clone, Serialize)]
+struct OllamaRequest {
+    model: String,
+    messages: Vec<OllamaMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<OllamaTool>>,
+    stream: bool,
+    options: OllamaOptions,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct OllamaMessage {
+    role: String,
+    #[serde(default)]
+    content: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tool_calls: Option<Vec<OllamaToolCall>>,
+    /// Tool a "tool" message answers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tool_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct OllamaToolCall {
+    function: OllamaFunctionCall,
+}
+
+impl OllamaToolCall {
+    fn into_tool_use(self) -> ContentBlock {
+        // Ollama doesn't provide tool call IDs
+        ContentBlock::ToolUse {
+            id: format!("ollama_{}_{}", self.function.name, Uuid::new_v4()),
+            name: self.function.name,
+            input: self.function.arguments,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct OllamaFunctionCall {
+    name: String,
+    /// An object, unlike OpenAI's JSON string
+    arguments: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct OllamaTool {
+    #[serde(rename = "type")]
+    tool_type: String,
+    function: OllamaFunction,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct OllamaFunction {
+    name: String,
+    description: String,
+    parameters: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct OllamaOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    /// Output token limit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_predict: Option<u32>,
+}
+
+/// A /api/chat response, or one line of a streamed one
+#[derive(Debug, Clone, Deserialize)]
+struct OllamaChunk {
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    message: Option<OllamaMessage>,
+    #[serde(default)]
+    done: bool,
+    #[serde(default)]
+    done_reason: Option<String>,
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    prompt_eval_count: Option<u64>,
+    #[serde(default)]
+    eval_count: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::claude::types::Message;
+    use crate::providers::timeout::RequestTimeoutError;
+    use crate::tools::types::{ToolDefinition, ToolInputSchema};
+    use serde_json::json;
+
+    fn provider(model: &str) -> OllamaProvider {
+        OllamaProvider::new(DEFAULT_OLLAMA_URL, model).unwrap()
+    }
+
+    #[test]
+    fn test_provider_basics() {
+        let ollama = OllamaProvider::new("http://localhost:11434/", "qwen2.5:7b").unwrap();
+        assert_eq!(ollama.name(), "ollama");
+        assert_eq!(ollama.default_model(), "qwen2.5:7b");
+        assert_eq!(ollama.base_url, "http://localhost:11434");
+        assert!(ollama.supports_tools());
+
+        assert!(!provider("gemma2:9b").supports_tools());
+        assert!(model_supports_tools("library/llama3.1:latest"));
+        assert!(!model_supports_tools("llama2"));
+    }
+
+    #[test]
+    fn test_request_conversion() {
+        let request = ProviderRequest::new(vec![
+            Message::user("List the files"),
+            Message {
+                role: "assistant".to_string(),
+                content: vec![ContentBlock::ToolUse {
+                    id: "call_1".to_string(),
+                    name: "glob".to_string(),
+                    input: json!({ "pattern": "*.rs" }),
+                }],
+            },
+            Message {
+                role: "user".to_string(),
+                content: vec![ContentBlock::ToolResult {
+                    tool_use_id: "call_1".to_string(),
+                    content: "main.rs".to_string(),
+                    is_error: None,
+                }],
+            },
+        ])
+        .with_tools(vec![ToolDefinition {
+            name: "glob".to_string(),
+            description: "Find files".to_string(),
+            input_schema: ToolInputSchema::simple(vec![("pattern", "Glob pattern")]),
+        }])
+        .with_temperature(0.2);
+
+        let json = serde_json::to_value(provider("qwen2.5:7b").to_ollama_request(&request, true)).unwrap();
+        assert_eq!(json["model"], "qwen2.5:7b");
+        assert_eq!(json["stream"], true);
+        assert_eq!(json["options"]["num_predict"], DEFAULT_TEACHER_MAX_TOKENS);
+        assert_eq!(json["messages"][1]["tool_calls"][0]["function"]["arguments"]["pattern"], "*.rs");
+        assert_eq!(json["messages"][2]["role"], "tool");
+        assert_eq!(json["messages"][2]["tool_name"], "glob");
+        assert_eq!(json["messages"][2]["content"], "main.rs");
+        assert_eq!(json["tools"][0]["function"]["name"], "glob");
+
+        // Models without function calling get no tools
+        let json = serde_json::to_value(provider("gemma2:9b").to_ollama_request(&request, false)).unwrap();
+        assert!(json.get("tools").is_none());
+    }
+
+    #[test]
+    fn test_ndjson_stream_parsing() {
+        let mut parser = NdjsonParser::default();
+        assert!(parser.push(br#"{"message":{"role":"assistant","content":"Hel"#).is_empty());
+        let lines = parser.push(b"lo\"},\"done\":false}\n\n{\"message\":{\"role\":\"assistant\",\"content\":\"\",\"tool_calls\":[{\"function\":{\"name\":\"read\",\"arguments\":{\"file_path\":\"a.rs\"}}}]},\"done\":false}\n");
+        assert_eq!(lines.len(), 2);
+
+        let first: OllamaChunk = serde_json::from_str(&lines[0]).unwrap();
+        assert_eq!(first.message.unwrap().content, "Hello");
+
+        let second: OllamaChunk = serde_json::from_str(&lines[1]).unwrap();
+        let call = second.message.unwrap().tool_calls.unwrap().remove(0);
+        match call.into_tool_use() {
+            ContentBlock::ToolUse { id, name, input } => {
+                assert!(id.starts_with("ollama_read_"));
+                assert_eq!(name, "read");
+                assert_eq!(input["file_path"], "a.rs");
+            }
+            other => panic!("unexpected block: {:?}", other),
+        }
+
+        let done: OllamaChunk =
+            serde_json::from_str(&parser.push(b"{\"done\":true,\"done_reason\":\"stop\"}\n")[0]).unwrap();
+        assert!(done.done);
+        assert_eq!(done.done_reason.as_deref(), Some("stop"));
+    }
+
+    /// A local server that answers each connection with `reply` (if any)
+    /// and then goes quiet
+    async fn stalling_server(reply: &'static str) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut held = Vec::new();
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut request = [0u8; 4096];
+                let _ = socket.read(&mut request).await;
+                let _ = socket.write_all(reply.as_bytes()).await;
+                held.push(socket);
+            }
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout() {
+        let url = stalling_server("").await;
+        let ollama = OllamaProvider::new(url, "qwen2.5:7b")
+            .unwrap()
+            .with_request_timeout(Duration::from_millis(200));
+
+        let request = ProviderRequest::new(vec![Message::user("Hi")]);
+        let error = ollama.send_message_once(&request).await.unwrap_err();
+        let timeout = error
+            .chain()
+            .find_map(|e| e.downcast_ref::<RequestTimeoutError>())
+            .expect("timeout error");
+        assert!(!timeout.idle);
+        assert!(format!("{:#}", error).contains("timed out after 0.2s"));
+    }
+
+    #[tokio::test]
+    async fn test_missing_model_is_not_retried() {
+        let url = stalling_server(
+            "HTTP/1.1 404 Not Found\r\n\
+             Content-Type: application/json\r\n\
+             Content-Length: 40\r\n\
+             Connection: close\r\n\r\n\
+             {\"error\":\"model 'qwen2.5:7b' not found\"}",
+        )
+        .await;
+        let ollama = OllamaProvider::new(url, "qwen2.5:7b").unwrap();
+
+        let request = ProviderRequest::new(vec![Message::user("Hi")]);
+        let error = ollama.send_message(&request).await.unwrap_err();
+        let http = error.downcast_ref::<HttpStatusError>().expect("status error");
+        assert_eq!(http.status.as_u16(), 404);
+        assert!(!http.is_transient());
+        assert!(http.body.contains("not found"));
+    }
+
+    #[tokio::test]
+    async fn test_stream_idle_timeout() {
+        let url = stalling_server(
+            "HTTP/1.1 200 OK\r\n\
+             Content-Type: application/x-ndjson\r\n\
+             Transfer-Encoding: chunked\r\n\r\n\
+             40\r\n{\"message\":{\"role\":\"assistant\",\"content\":\"Hello\"},\"done\":false}\n\r\n",
+        )
+        .await;
+        let ollama = OllamaProvider::new(url, "qwen2.5:7b")
+            .unwrap()
+            .with_request_timeout(Duration::from_millis(200));
+
+        let request = ProviderRequest::new(vec![Message::user("Hi")]).with_stream(true);
+        let mut rx = ollama.send_message_stream_once(&request).await.unwrap();
+        let first = rx.recv().await.unwrap().unwrap();
+        assert!(matches!(first, StreamChunk::TextDelta(ref text) if text == "Hello"));
+
+        let error = rx.recv().await.unwrap().unwrap_err();
+        assert!(error.downcast_ref::<RequestTimeoutError>().unwrap().idle);
+        assert!(error.to_string().contains("Stream stalled"));
+    }
+}
@@ -11,12 +11,15 @@ use serde::{Deserialize, Serialize};
 use std::time::Duration;
 use tokio::sync::mpsc;
 
-use super::types::{ProviderRequest, ProviderResponse, StreamChunk};
+use super::types::{
+    stop_reason_from_finish_reason, ProviderRequest, ProviderResponse, StreamChunk, TokenUsage,
+};
+use super::timeout::{check_timeout, send_stream_request, with_idle_timeout, DEFAULT_REQUEST_TIMEOUT_SECS};
 use super::LlmProvider;
-use crate::claude::retry::with_retry;
+use crate::claude::retry::{with_retry_config, HttpStatusError, RetryConfig};
 use crate::claude::types::ContentBlock;
+use crate::config::{ThinkingLevel, DEFAULT_TEACHER_MAX_TOKENS};
 
-const REQUEST_TIMEOUT_SECS: u64 = 60;
 
 /// OpenAI API provider
 ///
@@ -24,10 +27,14 @@ const REQUEST_TIMEOUT_SECS: u64 = 60;
 #[derive(Clone)]
 pub struct OpenAIProvider {
     client: Client,
+    request_timeout: Duration,
     api_key: String,
     base_url: String,
     default_model: String,
     provider_name: String,
+    max_tokens: u32,
+    thinking: Option<ThinkingLevel>,
+    retry: RetryConfig,
 }
 
 impl OpenAIProvider {
@@ -78,19 +85,57 @@ impl OpenAIProvider {
         self
     }
 
+    /// Set the output token limit used when a request doesn't set one
+    pub fn with_max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = max_tokens;
+        self
+    }
+
+    /// Set the request timeout (`request_timeout_seconds`); for streaming it
+    /// bounds the wait between chunks rather than the whole response
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    /// Set the reasoning effort used when a request doesn't set one
+    pub fn with_thinking(mut self, thinking: ThinkingLevel) -> Self {
+        self.thinking = Some(thinking);
+        self
+    }
+
+    /// Set the retry attempts and backoff (`max_retries` / `base_delay_ms`)
+    pub fn with_retry_config(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Reasoning effort for `request`, if the provider and model take one
+    fn reasoning_effort(&self, request: &ProviderRequest, model: &str) -> Option<&'static str> {
+        let effort = request.thinking.or(self.thinking)?.reasoning_effort()?;
+        if self.provider_name != "openai" || !is_reasoning_model(model) {
+            tracing::debug!("{} model {} has no reasoning effort, ignoring thinking budget", self.provider_name, model);
+            return None;
+        }
+        Some(effort)
+    }
+
     /// Create a provider with custom settings
     fn new(api_key: String, base_url: String, default_model: String, provider_name: String) -> Result<Self> {
         let client = Client::builder()
-            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
             .build()
             .context("Failed to create HTTP client")?;
 
         Ok(Self {
             client,
+            request_timeout: Duration::from_secs(DEFAULT_REQUEST_TIMEOUT_SECS),
             api_key,
             base_url,
             default_model,
             provider_name,
+            max_tokens: DEFAULT_TEACHER_MAX_TOKENS,
+            thinking: None,
+            retry: RetryConfig::default(),
         })
     }
 
@@ -106,9 +151,17 @@ impl OpenAIProvider {
         // Need to handle mixed content (text + tool results) by creating separate messages
         let mut messages: Vec<OpenAIMessage> = Vec::new();
 
+        if let Some(system) = &request.system {
+            messages.push(OpenAIMessage::Regular {
+                role: "system".to_string(),
+                content: system.clone(),
+            });
+        }
+
         for msg in &request.messages {
             // Separate text content from tool results
             let mut text_parts = Vec::new();
+            let mut image_urls = Vec::new();
             let mut tool_results = Vec::new();
 
             for block in &msg.content {
@@ -116,6 +169,9 @@ impl OpenAIProvider {
                     ContentBlock::Text { text } => {
                         text_parts.push(text.as_str());
                     }
+                    ContentBlock::Image { source } => {
+                        image_urls.push(source.to_url());
+                    }
                     ContentBlock::ToolResult {
                         tool_use_id,
                         content,
@@ -127,11 +183,29 @@ impl OpenAIProvider {
                         // Tool use blocks are in assistant messages, handled in response
                         // OpenAI includes them in the assistant message via tool_calls field
                     }
+                    ContentBlock::Thinking { .. } | ContentBlock::RedactedThinking { .. } => {
+                        // Claude's thinking blocks mean nothing to other providers
+                    }
                 }
             }
 
-            // Add regular message if there's text content
-            if !text_parts.is_empty() {
+            // Images need the content-parts form; plain text stays a string
+            if !image_urls.is_empty() {
+                let mut parts = Vec::new();
+                if !text_parts.is_empty() {
+                    parts.push(OpenAIContentPart::Text {
+                        text: text_parts.join("\n"),
+                    });
+                }
+                parts.extend(image_urls.into_iter().map(|url| OpenAIContentPart::ImageUrl {
+                    image_url: OpenAIImageUrl { url },
+                }));
+                messages.push(OpenAIMessage::Multipart {
+                    role: msg.role.clone(),
+                    content: parts,
+                });
+            } else if !text_parts.is_empty() {
+                // Add regular message if there's text content
                 messages.push(OpenAIMessage::Regular {
                     role: msg.role.clone(),
                     content: text_parts.join("\n"),
@@ -183,11 +257,22 @@ impl OpenAIProvider {
                 .collect()
         });
 
+        let max_tokens = request.max_tokens.unwrap_or(self.max_tokens);
+        let reasoning_effort = self.reasoning_effort(request, &model);
+
+        // Reasoning models take max_completion_tokens and no temperature
+        let (max_tokens, max_completion_tokens, temperature) = match reasoning_effort {
+            Some(_) => (None, Some(max_tokens), None),
+            None => (Some(max_tokens), None, request.temperature),
+        };
+
         OpenAIRequest {
             model,
             messages,
-            max_tokens: Some(request.max_tokens),
-            temperature: request.temperature,
+            max_tokens,
+            max_completion_tokens,
+            temperature,
+            reasoning_effort,
             tools,
             stream: request.stream,
         }
@@ -232,6 +317,10 @@ impl OpenAIProvider {
             stop_reason: choice.finish_reason,
             role: choice.message.role,
             provider: self.provider_name.clone(),
+            usage: response.usage.map(|usage| TokenUsage {
+                input_tokens: usage.prompt_tokens,
+                output_tokens: usage.completion_tokens,
+            }),
         })
     }
 
@@ -248,24 +337,22 @@ impl OpenAIProvider {
             .header("Authorization", format!("Bearer {}", self.api_key))
             .header("content-type", "application/json")
             .json(&openai_request)
+            .timeout(self.request_timeout)
             .send()
             .await
+            .map_err(|e| check_timeout(e, self.request_timeout))
             .context("Failed to send request to OpenAI API")?;
 
         let status = response.status();
 
         if !status.is_success() {
-            let error_body = response.text().await.unwrap_or_default();
-            anyhow::bail!(
-                "OpenAI API request failed\n\nStatus: {}\nBody: {}",
-                status,
-                error_body
-            );
+            return Err(HttpStatusError::from_response("OpenAI API request", response).await.into());
         }
 
         let openai_response: OpenAIResponse = response
             .json()
             .await
+            .map_err(|e| check_timeout(e, self.request_timeout))
             .context("Failed to parse OpenAI API response")?;
 
         tracing::debug!("Received response: {:?}", openai_response);
@@ -287,30 +374,27 @@ impl OpenAIProvider {
 
         tracing::debug!("Sending streaming request to OpenAI API");
 
-        let response = self
+        let request = self
             .client
             .post(&url)
             .header("Authorization", format!("Bearer {}", self.api_key))
             .header("content-type", "application/json")
-            .json(&openai_request)
-            .send()
+            .json(&openai_request);
+        let response = send_stream_request(request, self.request_timeout)
             .await
             .context("Failed to send streaming request to OpenAI API")?;
 
         let status = response.status();
         if !status.is_success() {
-            let error_body = response.text().await.unwrap_or_default();
-            anyhow::bail!(
-                "OpenAI API streaming request failed\n\nStatus: {}\nBody: {}",
-                status,
-                error_body
-            );
+            return Err(HttpStatusError::from_response("OpenAI API streaming request", response).await.into());
         }
 
+        let idle = self.request_timeout;
+
         // Spawn task to parse SSE stream
         tokio::spawn(async move {
             tracing::debug!("[STREAM] OpenAI streaming task started");
-            let mut stream = response.bytes_stream();
+            let mut stream = with_idle_timeout(response.bytes_stream(), idle);
             let mut buffer = Vec::new();
             let mut accumulated_text = String::new();
             let mut done = false;
@@ -379,6 +463,14 @@ impl OpenAIProvider {
                                                 }
                                             }
                                         }
+
+                                        if let Some(finish_reason) = choice.finish_reason {
+                                            let stop_reason = stop_reason_from_finish_reason(&finish_reason);
+                                            if tx.send(Ok(StreamChunk::StopReason(stop_reason))).await.is_err() {
+                                                done = true;
+                                                break;
+                                            }
+                                        }
                                     }
                                 }
                             }
@@ -403,14 +495,14 @@ impl OpenAIProvider {
 #[async_trait]
 impl LlmProvider for OpenAIProvider {
     async fn send_message(&self, request: &ProviderRequest) -> Result<ProviderResponse> {
-        with_retry(|| self.send_message_once(request)).await
+        with_retry_config(&self.retry, || self.send_message_once(request)).await
     }
 
     async fn send_message_stream(
         &self,
         request: &ProviderRequest,
     ) -> Result<mpsc::Receiver<Result<StreamChunk>>> {
-        with_retry(|| self.send_message_stream_once(request)).await
+        with_retry_config(&self.retry, || self.send_message_stream_once(request)).await
     }
 
     fn name(&self) -> &str {
@@ -439,8 +531,12 @@ struct OpenAIRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     max_tokens: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    max_completion_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     temperature: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    reasoning_effort: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     tools: Option<Vec<OpenAITool>>,
     #[serde(skip_serializing_if = "is_false")]
     stream: bool,
@@ -450,6 +546,13 @@ fn is_false(b: &bool) -> bool {
     !*b
 }
 
+/// Whether `model` is an OpenAI reasoning model (o-series, gpt-5)
+fn is_reasoning_model(model: &str) -> bool {
+    let mut chars = model.chars();
+    (chars.next() == Some('o') && chars.next().is_some_and(|c| c.is_ascii_digit()))
+        || model.starts_with("gpt-5")
+}
+
 /// OpenAI message format - supports both regular messages and tool messages
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -466,6 +569,23 @@ enum OpenAIMessage {
         tool_call_id: String,
         name: String,
     },
+    /// User message with images (text and image_url parts)
+    Multipart {
+        role: String,
+        content: Vec<OpenAIContentPart>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum OpenAIContentPart {
+    Text { text: String },
+    ImageUrl { image_url: OpenAIImageUrl },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct OpenAIImageUrl {
+    url: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -487,6 +607,16 @@ struct OpenAIResponse {
     id: String,
     model: String,
     choices: Vec<OpenAIChoice>,
+    #[serde(default)]
+    usage: Option<OpenAIUsage>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct OpenAIUsage {
+    #[serde(default)]
+    prompt_tokens: u64,
+    #[serde(default)]
+    completion_tokens: u64,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -557,6 +687,7 @@ struct OpenAIFunctionDelta {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::claude::types::{ImageSource, Message};
 
     #[test]
     fn test_openai_provider_creation() {
@@ -578,4 +709,80 @@ mod tests {
         let grok = OpenAIProvider::new_grok("test-key".to_string()).unwrap();
         assert_eq!(grok.name(), "grok");
     }
+
+    #[test]
+    fn test_system_prompt_prepended() {
+        let request =
+            ProviderRequest::new(vec![Message::user("Hi")]).with_system("Follow the constitution.");
+        let openai = OpenAIProvider::new_openai("test-key".to_string()).unwrap();
+        let json = serde_json::to_value(openai.to_openai_request(&request)).unwrap();
+
+        assert_eq!(json["messages"][0]["role"], "system");
+        assert_eq!(json["messages"][0]["content"], "Follow the constitution.");
+        assert_eq!(json["messages"][1]["role"], "user");
+    }
+
+    #[test]
+    fn test_image_blocks_become_image_url_parts() {
+        let message = Message::user("What is in this screenshot?").add_content(ContentBlock::Image {
+            source: ImageSource::Base64 {
+                media_type: "image/png".to_string(),
+                data: "iVBORw0KGgo=".to_string(),
+            },
+        });
+        let request = ProviderRequest::new(vec![message]);
+        let openai = OpenAIProvider::new_openai("test-key".to_string()).unwrap();
+        let json = serde_json::to_value(openai.to_openai_request(&request)).unwrap();
+
+        let content = &json["messages"][0]["content"];
+        assert_eq!(content[0]["type"], "text");
+        assert_eq!(content[0]["text"], "What is in this screenshot?");
+        assert_eq!(content[1]["type"], "image_url");
+        assert_eq!(content[1]["image_url"]["url"], "data:image/png;base64,iVBORw0KGgo=");
+    }
+
+    #[test]
+    fn test_reasoning_effort_in_request() {
+        let request = ProviderRequest::new(vec![Message::user("Hi")])
+            .with_temperature(0.3)
+            .with_thinking(ThinkingLevel::High);
+
+        let openai = OpenAIProvider::new_openai("test-key".to_string())
+            .unwrap()
+            .with_model("o3-mini");
+        let json = serde_json::to_value(openai.to_openai_request(&request)).unwrap();
+        assert_eq!(json["reasoning_effort"], "high");
+        assert_eq!(json["max_completion_tokens"], DEFAULT_TEACHER_MAX_TOKENS);
+        assert!(json.get("max_tokens").is_none());
+        assert!(json.get("temperature").is_none());
+
+        // Non-reasoning models and other providers ignore it
+        let gpt4o = OpenAIProvider::new_openai("test-key".to_string()).unwrap();
+        let json = serde_json::to_value(gpt4o.to_openai_request(&request)).unwrap();
+        assert!(json.get("reasoning_effort").is_none());
+        assert_eq!(json["max_tokens"], DEFAULT_TEACHER_MAX_TOKENS);
+
+        let mistral = OpenAIProvider::new_mistral("test-key".to_string())
+            .unwrap()
+            .with_thinking(ThinkingLevel::Low);
+        let json = serde_json::to_value(mistral.to_openai_request(&request)).unwrap();
+        assert!(json.get("reasoning_effort").is_none());
+
+        // Teacher config applies when the request doesn't set it
+        let o4 = OpenAIProvider::new_openai("test-key".to_string())
+            .unwrap()
+            .with_model("o4-mini")
+            .with_thinking(ThinkingLevel::Budget(2000));
+        let json = serde_json::to_value(o4.to_openai_request(&ProviderRequest::new(vec![Message::user("Hi")]))).unwrap();
+        assert_eq!(json["reasoning_effort"], "low");
+    }
+
+    #[test]
+    fn test_finish_reason_maps_to_stop_reason() {
+        assert_eq!(stop_reason_from_finish_reason("stop"), "end_turn");
+        assert_eq!(stop_reason_from_finish_reason("length"), "max_tokens");
+        assert_eq!(stop_reason_from_finish_reason("tool_calls"), "tool_use");
+        assert_eq!(stop_reason_from_finish_reason("MAX_TOKENS"), "max_tokens");
+        assert_eq!(stop_reason_from_finish_reason("content_filter"), "content_filter");
+    }
 }
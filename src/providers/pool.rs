@@ -0,0 +1,466 @@
+// Teacher pools: spread requests across several keys for one provider
+//
+// Teachers that share a `pool` name in config are wrapped in one
+// `TeacherPool`. Each request goes to the next member in round-robin order.
+// The cursor lives in a registry keyed by pool name, so every client built
+// from the same config (REPL, teacher session, daemon) rotates together. A
+// member that answers 429 is benched for `RATE_LIMIT_COOLDOWN` and the
+// request moves on to the next key; other errors are returned as-is so the
+// circuit breaker and fallback chain still see them.
+
+use anyhow::{anyhow, Result};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+use super::{CacheBreakpoint, LlmProvider, ProviderRequest, ProviderResponse, StreamChunk};
+use crate::claude::retry::HttpStatusError;
+
+/// How long a rate-limited key is skipped
+pub const RATE_LIMIT_COOLDOWN: Duration = Duration::from_secs(60);
+
+static POOLS: Mutex<Vec<Arc<PoolState>>> = Mutex::new(Vec::new());
+
+/// How a pooled request went, for the member's stats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    Success,
+    RateLimited,
+    Failed,
+}
+
+#[derive(Debug, Clone, Default)]
+struct MemberStats {
+    label: String,
+    requests: u64,
+    failures: u64,
+    total_latency: Duration,
+    last_latency: Option<Duration>,
+    throttled_until: Option<Instant>,
+}
+
+/// Rotation cursor and per-member stats for one pool
+#[derive(Debug)]
+pub struct PoolState {
+    name: String,
+    provider: String,
+    cursor: AtomicUsize,
+    members: Mutex<Vec<MemberStats>>,
+}
+
+/// One pool member, as shown by /provider-latency
+#[derive(Debug, Clone)]
+pub struct PoolMemberStatus {
+    pub label: String,
+    pub requests: u64,
+    pub failures: u64,
+    /// Mean time to response (stream start for streaming requests)
+    pub avg_latency: Option<Duration>,
+    pub last_latency: Option<Duration>,
+    /// Time left on a rate-limit cooldown
+    pub throttled_for: Option<Duration>,
+}
+
+/// Rotation state of one pool
+#[derive(Debug, Clone)]
+pub struct PoolStatus {
+    pub name: String,
+    pub provider: String,
+    /// Member the next request starts with
+    pub next: usize,
+    pub members: Vec<PoolMemberStatus>,
+}
+
+impl PoolState {
+    /// Shared state for a pool, reused by every client with the same pool
+    fn shared(name: &str, provider: &str, labels: Vec<String>) -> Arc<PoolState> {
+        let mut pools = POOLS.lock().unwrap();
+        if let Some(existing) = pools.iter().find(|p| p.name == name) {
+            let same_members = existing
+                .members
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|m| &m.label)
+                .eq(labels.iter());
+            if same_members {
+                return Arc::clone(existing);
+            }
+        }
+
+        let state = Arc::new(PoolState::new(name, provider, labels));
+        pools.retain(|p| p.name != name);
+        pools.push(Arc::clone(&state));
+        state
+    }
+
+    fn new(name: &str, provider: &str, labels: Vec<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            provider: provider.to_string(),
+            cursor: AtomicUsize::new(0),
+            members: Mutex::new(
+                labels
+                    .into_iter()
+                    .map(|label| MemberStats {
+                        label,
+                        ..Default::default()
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Members to try for the next request: available keys in rotation
+    /// order, then throttled keys by soonest recovery (as a last resort)
+    fn next_order_at(&self, now: Instant) -> Vec<usize> {
+        let members = self.members.lock().unwrap();
+        let count = members.len();
+        if count == 0 {
+            return Vec::new();
+        }
+        let start = self.cursor.fetch_add(1, Ordering::Relaxed) % count;
+
+        let (mut available, mut throttled): (Vec<usize>, Vec<usize>) = (0..count)
+            .map(|offset| (start + offset) % count)
+            .partition(|&idx| members[idx].throttled_until.map_or(true, |until| until <= now));
+        throttled.sort_by_key(|&idx| members[idx].throttled_until);
+        available.extend(throttled);
+        available
+    }
+
+    fn record_at(&self, idx: usize, latency: Duration, outcome: Outcome, now: Instant) {
+        let mut members = self.members.lock().unwrap();
+        let Some(member) = members.get_mut(idx) else {
+            return;
+        };
+        member.requests += 1;
+        match outcome {
+            Outcome::Success => {
+                member.total_latency += latency;
+                member.last_latency = Some(latency);
+                member.throttled_until = None;
+            }
+            Outcome::RateLimited => {
+                member.failures += 1;
+                member.throttled_until = Some(now + RATE_LIMIT_COOLDOWN);
+            }
+            Outcome::Failed => member.failures += 1,
+        }
+    }
+
+    fn status_at(&self, now: Instant) -> PoolStatus {
+        let members = self.members.lock().unwrap();
+        let next = match members.len() {
+            0 => 0,
+            count => self.cursor.load(Ordering::Relaxed) % count,
+        };
+        PoolStatus {
+            name: self.name.clone(),
+            provider: self.provider.clone(),
+            next,
+            members: members
+                .iter()
+                .map(|m| {
+                    let successes = m.requests - m.failures;
+                    PoolMemberStatus {
+                        label: m.label.clone(),
+                        requests: m.requests,
+                        failures: m.failures,
+                        avg_latency: (successes > 0).then(|| m.total_latency / successes as u32),
+                        last_latency: m.last_latency,
+                        throttled_for: m
+                            .throttled_until
+                            .filter(|until| *until > now)
+                            .map(|until| until - now),
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Rotation state of every configured pool
+pub fn pool_statuses() -> Vec<PoolStatus> {
+    let now = Instant::now();
+    POOLS
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|pool| pool.status_at(now))
+        .collect()
+}
+
+/// Whether an error is the provider saying "slow down" (HTTP 429)
+pub fn is_rate_limited(error: &anyhow::Error) -> bool {
+    error
+        .chain()
+        .filter_map(|e| e.downcast_ref::<HttpStatusError>())
+        .any(|http| http.status == reqwest::StatusCode::TOO_MANY_REQUESTS)
+}
+
+/// Several teachers for one provider, used in turn
+pub struct TeacherPool {
+    members: Vec<Box<dyn LlmProvider>>,
+    state: Arc<PoolState>,
+}
+
+impl TeacherPool {
+    /// Build a pool from labelled providers (labels identify keys in status output)
+    pub fn new(name: &str, members: Vec<(String, Box<dyn LlmProvider>)>) -> Self {
+        let (labels, members): (Vec<String>, Vec<Box<dyn LlmProvider>>) = members.into_iter().unzip();
+        let provider = members.first().map_or("", |m| m.name());
+        let state = PoolState::shared(name, provider, labels);
+        Self { members, state }
+    }
+
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// The request as sent to one member (its own model unless the caller picked one)
+    fn request_for(&self, member: &dyn LlmProvider, request: &ProviderRequest) -> ProviderRequest {
+        let mut request = request.clone();
+        if request.model.is_empty() || request.model == self.default_model() {
+            request.model = member.default_model().to_string();
+        }
+        request
+    }
+
+    fn record(&self, idx: usize, started: Instant, outcome: Outcome) {
+        let now = Instant::now();
+        self.state.record_at(idx, now - started, outcome, now);
+        if outcome == Outcome::RateLimited {
+            tracing::warn!(
+                "Teacher pool '{}': member {} rate limited, skipping it for {}s",
+                self.state.name,
+                idx + 1,
+                RATE_LIMIT_COOLDOWN.as_secs()
+            );
+        }
+    }
+
+    fn exhausted(&self, last_error: Option<anyhow::Error>) -> anyhow::Error {
+        match last_error {
+            Some(e) => e.context(format!("Every key in teacher pool '{}' is rate limited", self.state.name)),
+            None => anyhow!("Teacher pool '{}' has no members", self.state.name),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl LlmProvider for TeacherPool {
+    async fn send_message(&self, request: &ProviderRequest) -> Result<ProviderResponse> {
+        let mut last_error = None;
+        for idx in self.state.next_order_at(Instant::now()) {
+            let member = self.members[idx].as_ref();
+            let started = Instant::now();
+            match member.send_message(&self.request_for(member, request)).await {
+                Ok(response) => {
+                    self.record(idx, started, Outcome::Success);
+                    return Ok(response);
+                }
+                Err(e) if is_rate_limited(&e) => {
+                    self.record(idx, started, Outcome::RateLimited);
+                    last_error = Some(e);
+                }
+                Err(e) => {
+                    self.record(idx, started, Outcome::Failed);
+                    return Err(e);
+                }
+            }
+        }
+        Err(self.exhausted(last_error))
+    }
+
+    async fn send_message_stream(
+        &self,
+        request: &ProviderRequest,
+    ) -> Result<mpsc::Receiver<Result<StreamChunk>>> {
+        let mut last_error = None;
+        for idx in self.state.next_order_at(Instant::now()) {
+            let member = self.members[idx].as_ref();
+            let started = Instant::now();
+            match member.send_message_stream(&self.request_for(member, request)).await {
+                Ok(rx) => {
+                    self.record(idx, started, Outcome::Success);
+                    return Ok(rx);
+                }
+                Err(e) if is_rate_limited(&e) => {
+                    self.record(idx, started, Outcome::RateLimited);
+                    last_error = Some(e);
+                }
+                Err(e) => {
+                    self.record(idx, started, Outcome::Failed);
+                    return Err(e);
+                }
+            }
+        }
+        Err(self.exhausted(last_error))
+    }
+
+    fn name(&self) -> &str {
+        self.members.first().map_or("pool", |m| m.name())
+    }
+
+    fn default_model(&self) -> &str {
+        self.members.first().map_or("default", |m| m.default_model())
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.members.iter().all(|m| m.supports_streaming())
+    }
+
+    fn supports_tools(&self) -> bool {
+        self.members.iter().all(|m| m.supports_tools())
+    }
+
+    fn cache_breakpoints(&self, request: &ProviderRequest) -> Vec<CacheBreakpoint> {
+        self.members
+            .first()
+            .map(|m| m.cache_breakpoints(&self.request_for(m.as_ref(), request)))
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::claude::types::ContentBlock;
+
+    fn status_error(status: u16, body: &str) -> anyhow::Error {
+        HttpStatusError {
+            what: "Mock API request".to_string(),
+            status: reqwest::StatusCode::from_u16(status).unwrap(),
+            retry_after: None,
+            body: body.to_string(),
+        }
+        .into()
+    }
+
+    /// Answers with its label, or a 429 when `throttled`
+    struct MockKey {
+        label: &'static str,
+        throttled: bool,
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait::async_trait]
+    impl LlmProvider for MockKey {
+        async fn send_message(&self, _request: &ProviderRequest) -> Result<ProviderResponse> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.throttled {
+                return Err(status_error(429, "{}"));
+            }
+            Ok(ProviderResponse {
+                id: "test-id".to_string(),
+                model: "test-model".to_string(),
+                content: vec![ContentBlock::Text {
+                    text: self.label.to_string(),
+                }],
+                stop_reason: Some("end_turn".to_string()),
+                role: "assistant".to_string(),
+                provider: "mock".to_string(),
+                usage: None,
+            })
+        }
+
+        async fn send_message_stream(
+            &self,
+            _request: &ProviderRequest,
+        ) -> Result<mpsc::Receiver<Result<StreamChunk>>> {
+            anyhow::bail!("streaming not mocked")
+        }
+
+        fn name(&self) -> &str {
+            "mock"
+        }
+
+        fn default_model(&self) -> &str {
+            "test-model"
+        }
+    }
+
+    fn pool(name: &str, keys: &[(&'static str, bool)]) -> (TeacherPool, Vec<Arc<AtomicUsize>>) {
+        let calls: Vec<Arc<AtomicUsize>> = keys.iter().map(|_| Arc::new(AtomicUsize::new(0))).collect();
+        let members = keys
+            .iter()
+            .zip(&calls)
+            .map(|(&(label, throttled), calls)| {
+                let provider: Box<dyn LlmProvider> = Box::new(MockKey {
+                    label,
+                    throttled,
+                    calls: Arc::clone(calls),
+                });
+                (label.to_string(), provider)
+            })
+            .collect();
+        (TeacherPool::new(name, members), calls)
+    }
+
+    fn request() -> ProviderRequest {
+        ProviderRequest {
+            messages: vec![],
+            system: None,
+            model: String::new(),
+            max_tokens: Some(100),
+            temperature: None,
+            tools: None,
+            thinking: None,
+            stream: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_round_robin() {
+        let (pool, _) = pool("test-round-robin", &[("a", false), ("b", false), ("c", false)]);
+
+        let mut served = Vec::new();
+        for _ in 0..4 {
+            served.push(pool.send_message(&request()).await.unwrap().text());
+        }
+        assert_eq!(served, ["a", "b", "c", "a"]);
+    }
+
+    #[tokio::test]
+    async fn test_skips_rate_limited_key() {
+        let (pool, calls) = pool("test-rate-limit", &[("a", true), ("b", false)]);
+
+        // "a" answers 429, so the request moves on to "b"
+        assert_eq!(pool.send_message(&request()).await.unwrap().text(), "b");
+        // "a" is benched: the next request (which would start at "b") and the
+        // one after (which would start at "a") both skip it
+        assert_eq!(pool.send_message(&request()).await.unwrap().text(), "b");
+        assert_eq!(pool.send_message(&request()).await.unwrap().text(), "b");
+        assert_eq!(calls[0].load(Ordering::SeqCst), 1);
+
+        let status = pool_statuses()
+            .into_iter()
+            .find(|s| s.name == "test-rate-limit")
+            .unwrap();
+        assert!(status.members[0].throttled_for.is_some());
+        assert_eq!(status.members[1].requests, 3);
+    }
+
+    #[tokio::test]
+    async fn test_all_keys_rate_limited() {
+        let (pool, _) = pool("test-exhausted", &[("a", true), ("b", true)]);
+        let error = pool.send_message(&request()).await.unwrap_err();
+        assert!(is_rate_limited(&error));
+        assert!(format!("{}", error).contains("test-exhausted"));
+    }
+
+    #[test]
+    fn test_rate_limited_means_429() {
+        assert!(is_rate_limited(&status_error(429, "")));
+        assert!(is_rate_limited(&status_error(429, "").context("Teacher failed")));
+        // A bad request that mentions rate limits is still a bad request
+        assert!(!is_rate_limited(&status_error(400, "See the rate limit docs")));
+        assert!(!is_rate_limited(&anyhow!("Status: 429")));
+    }
+}
@@ -4,9 +4,12 @@
 // configurable truncation strategies for long conversations.
 
 use anyhow::Result;
+use std::sync::Arc;
 use tokio::sync::mpsc;
 
-use super::{LlmProvider, ProviderRequest, ProviderResponse, StreamChunk};
+use super::{
+    CacheBreakpoint, CircuitBreaker, LlmProvider, ProviderRequest, ProviderResponse, StreamChunk,
+};
 use crate::claude::types::{ContentBlock, Message};
 
 /// Teacher session with context tracking
@@ -19,6 +22,7 @@ pub struct TeacherSession {
     provider: Box<dyn LlmProvider>,
     state: ConversationState,
     config: TeacherContextConfig,
+    breaker: Arc<CircuitBreaker>,
 }
 
 /// Tracks the state of conversation with teacher
@@ -79,6 +83,7 @@ impl TeacherSession {
             provider,
             state: ConversationState::default(),
             config: TeacherContextConfig::default(),
+            breaker: Arc::new(CircuitBreaker::default()),
         }
     }
 
@@ -88,9 +93,16 @@ impl TeacherSession {
             provider,
             state: ConversationState::default(),
             config,
+            breaker: Arc::new(CircuitBreaker::default()),
         }
     }
 
+    /// Share a circuit breaker (e.g. with the ClaudeClient for the same teacher)
+    pub fn with_circuit_breaker(mut self, breaker: Arc<CircuitBreaker>) -> Self {
+        self.breaker = breaker;
+        self
+    }
+
     /// Send message with context tracking (Level 1: Minimal)
     ///
     /// Tracks new vs repeated context for metrics and logging.
@@ -120,7 +132,10 @@ impl TeacherSession {
         );
 
         // Send to teacher (full context)
-        let response = self.provider.send_message(request).await?;
+        self.breaker.check(self.provider.name())?;
+        let result = self.provider.send_message(request).await;
+        self.breaker.record(&result);
+        let response = result?;
 
         // Update state
         self.state.last_teacher_message_count = total_messages;
@@ -150,7 +165,10 @@ impl TeacherSession {
             "Teacher streaming context metrics"
         );
 
-        let receiver = self.provider.send_message_stream(request).await?;
+        self.breaker.check(self.provider.name())?;
+        let result = self.provider.send_message_stream(request).await;
+        self.breaker.record(&result);
+        let receiver = result?;
 
         // Update state
         self.state.last_teacher_message_count = total_messages;
@@ -286,6 +304,92 @@ impl TeacherSession {
         self.send_message(&optimized_request).await
     }
 
+    /// Dry run: what would be sent for `request`, without sending it (/context)
+    ///
+    /// Streaming requests go out as-is (see `send_message_stream`); others are
+    /// optimized as in `send_message_with_optimization`.
+    pub fn preview_context(&self, request: &ProviderRequest) -> ContextPreview {
+        if request.stream {
+            return ContextPreview::as_sent(self.provider.as_ref(), request.clone());
+        }
+
+        let sent = self.optimize_context(request);
+        let (fates, origins) = self.trace_optimization(&request.messages);
+        debug_assert_eq!(origins.len(), sent.messages.len());
+
+        let trimmed = request
+            .messages
+            .iter()
+            .zip(fates)
+            .enumerate()
+            .filter(|(_, (_, fate))| *fate != MessageFate::Sent)
+            .map(|(index, (message, fate))| TrimmedMessage {
+                index,
+                message: message.clone(),
+                fate,
+            })
+            .collect();
+
+        ContextPreview {
+            provider: self.provider.name().to_string(),
+            cache_breakpoints: self.provider.cache_breakpoints(&sent),
+            request: sent,
+            origins,
+            trimmed,
+            optimized: true,
+        }
+    }
+
+    /// What `optimize_context` does to each message, and where each message
+    /// it keeps came from
+    ///
+    /// Mirrors `drop_old_tool_results` then `truncate_with_system_preserved`,
+    /// tracking positions instead of copying messages.
+    fn trace_optimization(&self, messages: &[Message]) -> (Vec<MessageFate>, Vec<usize>) {
+        let mut fates = vec![MessageFate::Sent; messages.len()];
+
+        if self.config.tool_result_retention_turns > 0 {
+            let cutoff_index = messages
+                .len()
+                .saturating_sub(self.config.tool_result_retention_turns * 2);
+            for (idx, msg) in messages.iter().enumerate() {
+                let tool_results = if idx < cutoff_index {
+                    count_tool_results(std::slice::from_ref(msg))
+                } else {
+                    0
+                };
+                if tool_results == msg.content.len() {
+                    fates[idx] = MessageFate::Dropped;
+                } else if tool_results > 0 {
+                    fates[idx] = MessageFate::ToolResultsDropped;
+                }
+            }
+        }
+
+        let kept: Vec<usize> = (0..messages.len())
+            .filter(|&idx| fates[idx] != MessageFate::Dropped)
+            .collect();
+        let max_messages = self.config.max_context_turns * 2;
+        if max_messages == 0 || kept.len() <= max_messages {
+            return (fates, kept);
+        }
+
+        // Truncation moves system messages to the front
+        let (system, non_system): (Vec<usize>, Vec<usize>) = kept
+            .into_iter()
+            .partition(|&idx| messages[idx].role == "system");
+        let recent_start = non_system.len().saturating_sub(max_messages);
+        for &idx in &non_system[..recent_start] {
+            fates[idx] = MessageFate::Dropped;
+        }
+
+        let origins = system
+            .into_iter()
+            .chain(non_system[recent_start..].iter().copied())
+            .collect();
+        (fates, origins)
+    }
+
     /// Apply all optimization strategies
     fn optimize_context(&self, request: &ProviderRequest) -> ProviderRequest {
         let mut messages = request.messages.clone();
@@ -341,44 +445,7 @@ impl TeacherSession {
 
     /// Truncate to recent turns, always preserving system prompts
     fn truncate_with_system_preserved(&self, messages: &[Message]) -> Vec<Message> {
-        if messages.is_empty() || self.config.max_context_turns == 0 {
-            return messages.to_vec();
-        }
-
-        let max_messages = self.config.max_context_turns * 2;
-
-        if messages.len() <= max_messages {
-            return messages.to_vec();
-        }
-
-        let mut result = Vec::new();
-
-        // Step 1: Collect all system messages
-        let system_messages: Vec<Message> = messages
-            .iter()
-            .filter(|msg| msg.role == "system")
-            .cloned()
-            .collect();
-
-        // Step 2: Get recent non-system messages
-        let non_system_messages: Vec<&Message> = messages
-            .iter()
-            .filter(|msg| msg.role != "system")
-            .collect();
-
-        let recent_start = non_system_messages
-            .len()
-            .saturating_sub(max_messages);
-        let recent_messages: Vec<Message> = non_system_messages[recent_start..]
-            .iter()
-            .map(|&m| m.clone())
-            .collect();
-
-        // Step 3: Combine (system first, then recent)
-        result.extend(system_messages);
-        result.extend(recent_messages);
-
-        result
+        keep_recent_turns(messages, self.config.max_context_turns)
     }
 
     /// Get optimization statistics
@@ -406,6 +473,84 @@ pub struct OptimizationStats {
     pub estimated_savings_percent: f64,
 }
 
+/// What context optimization did to one message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageFate {
+    Sent,
+    /// Sent with its old tool results removed
+    ToolResultsDropped,
+    /// Not sent at all
+    Dropped,
+}
+
+/// A conversation message that was cut from, or cut down in, a request
+#[derive(Debug, Clone)]
+pub struct TrimmedMessage {
+    /// Position in the original request
+    pub index: usize,
+    pub message: Message,
+    pub fate: MessageFate,
+}
+
+/// The messages a teacher call would send, from a dry run (/context)
+#[derive(Debug, Clone)]
+pub struct ContextPreview {
+    pub provider: String,
+    /// The request exactly as it would be sent
+    pub request: ProviderRequest,
+    /// Position in the original request of each message in `request`
+    pub origins: Vec<usize>,
+    /// Original messages left out or cut down
+    pub trimmed: Vec<TrimmedMessage>,
+    pub cache_breakpoints: Vec<CacheBreakpoint>,
+    /// False when the request goes out without context optimization
+    pub optimized: bool,
+}
+
+impl ContextPreview {
+    /// Preview of a request sent without optimization
+    pub fn as_sent(provider: &dyn LlmProvider, request: ProviderRequest) -> Self {
+        Self {
+            provider: provider.name().to_string(),
+            cache_breakpoints: provider.cache_breakpoints(&request),
+            origins: (0..request.messages.len()).collect(),
+            request,
+            trimmed: Vec::new(),
+            optimized: false,
+        }
+    }
+
+    /// Whether a cache breakpoint sits at the end of `request.messages[index]`
+    pub fn breakpoint_after(&self, index: usize) -> bool {
+        self.cache_breakpoints.contains(&CacheBreakpoint::Message(index))
+    }
+}
+
+/// Keep the last `max_turns` turns (two messages each) plus every system
+/// message, which moves to the front (0 = keep everything)
+fn keep_recent_turns(messages: &[Message], max_turns: usize) -> Vec<Message> {
+    let max_messages = max_turns * 2;
+    if max_turns == 0 || messages.len() <= max_messages {
+        return messages.to_vec();
+    }
+
+    // Step 1: Collect all system messages
+    let system_messages = messages.iter().filter(|msg| msg.role == "system");
+
+    // Step 2: Get recent non-system messages
+    let non_system_messages: Vec<&Message> = messages
+        .iter()
+        .filter(|msg| msg.role != "system")
+        .collect();
+    let recent_start = non_system_messages.len().saturating_sub(max_messages);
+
+    // Step 3: Combine (system first, then recent)
+    system_messages
+        .chain(non_system_messages[recent_start..].iter().copied())
+        .cloned()
+        .collect()
+}
+
 /// Count tool results in messages
 fn count_tool_results(messages: &[Message]) -> usize {
     messages
@@ -435,6 +580,7 @@ mod tests {
                 stop_reason: Some("end_turn".to_string()),
                 role: "assistant".to_string(),
                 provider: "mock".to_string(),
+                usage: None,
             })
         }
 
@@ -473,6 +619,7 @@ mod tests {
 
         // First call: 2 messages (new)
         let request1 = ProviderRequest {
+            system: None,
             messages: vec![
                 Message {
                     role: "user".to_string(),
@@ -488,9 +635,10 @@ mod tests {
                 },
             ],
             model: String::new(),
-            max_tokens: 100,
+            max_tokens: Some(100),
             temperature: None,
             tools: None,
+            thinking: None,
             stream: false,
         };
 
@@ -501,6 +649,7 @@ mod tests {
 
         // Second call: 4 messages (2 new, 2 repeated)
         let request2 = ProviderRequest {
+            system: None,
             messages: vec![
                 Message {
                     role: "user".to_string(),
@@ -528,9 +677,10 @@ mod tests {
                 },
             ],
             model: String::new(),
-            max_tokens: 100,
+            max_tokens: Some(100),
             temperature: None,
             tools: None,
+            thinking: None,
             stream: false,
         };
 
@@ -548,6 +698,7 @@ mod tests {
         let mut session = TeacherSession::new(provider);
 
         let request = ProviderRequest {
+            system: None,
             messages: vec![Message {
                 role: "user".to_string(),
                 content: vec![ContentBlock::Text {
@@ -555,9 +706,10 @@ mod tests {
                 }],
             }],
             model: String::new(),
-            max_tokens: 100,
+            max_tokens: Some(100),
             temperature: None,
             tools: None,
+            thinking: None,
             stream: false,
         };
 
@@ -622,10 +774,12 @@ mod tests {
 
         let request = ProviderRequest {
             messages: messages.clone(),
+            system: None,
             model: String::new(),
-            max_tokens: 100,
+            max_tokens: Some(100),
             temperature: None,
             tools: None,
+            thinking: None,
             stream: false,
         };
 
@@ -684,11 +838,13 @@ mod tests {
         ];
 
         let request = ProviderRequest {
+            system: None,
             messages,
             model: String::new(),
-            max_tokens: 100,
+            max_tokens: Some(100),
             temperature: None,
             tools: None,
+            thinking: None,
             stream: false,
         };
 
@@ -843,11 +999,13 @@ mod tests {
         ];
 
         let request = ProviderRequest {
+            system: None,
             messages,
             model: String::new(),
-            max_tokens: 100,
+            max_tokens: Some(100),
             temperature: None,
             tools: None,
+            thinking: None,
             stream: false,
         };
 
@@ -867,4 +1025,72 @@ mod tests {
             }));
         assert!(has_recent_tool_result);
     }
+
+    #[test]
+    fn test_preview_context_traces_trimming() {
+        let config = TeacherContextConfig {
+            max_context_turns: 3,
+            tool_result_retention_turns: 1,
+            prompt_caching_enabled: true,
+        };
+        let session = TeacherSession::with_config(Box::new(MockProvider), config);
+
+        let tool_result = |id: &str| ContentBlock::ToolResult {
+            tool_use_id: id.to_string(),
+            content: "result".to_string(),
+            is_error: None,
+        };
+        let messages = vec![
+            Message::user("q0"),
+            Message::assistant("a0"),
+            Message::user("q1"),
+            Message {
+                role: "user".to_string(),
+                content: vec![tool_result("old")],
+            },
+            Message {
+                role: "system".to_string(),
+                content: vec![ContentBlock::text("System")],
+            },
+            Message {
+                role: "user".to_string(),
+                content: vec![tool_result("older"), ContentBlock::text("q2")],
+            },
+            Message::assistant("a2"),
+            Message::user("q3"),
+            Message::assistant("a3"),
+            Message {
+                role: "user".to_string(),
+                content: vec![tool_result("recent")],
+            },
+        ];
+        let request = ProviderRequest::new(messages);
+
+        let preview = session.preview_context(&request);
+        assert!(preview.optimized);
+        assert_eq!(preview.request.messages.len(), session.optimize_context(&request).messages.len());
+        assert_eq!(preview.origins, vec![4, 2, 5, 6, 7, 8, 9]);
+        for (sent, &origin) in preview.request.messages.iter().zip(&preview.origins) {
+            assert_eq!(sent.role, request.messages[origin].role);
+            assert_eq!(sent.text(), request.messages[origin].text());
+        }
+
+        let trimmed: Vec<(usize, MessageFate)> =
+            preview.trimmed.iter().map(|t| (t.index, t.fate)).collect();
+        assert_eq!(
+            trimmed,
+            vec![
+                (0, MessageFate::Dropped),
+                (1, MessageFate::Dropped),
+                (3, MessageFate::Dropped),
+                (5, MessageFate::ToolResultsDropped),
+            ]
+        );
+
+        // Streaming requests aren't optimized
+        let preview = session.preview_context(&request.clone().with_stream(true));
+        assert!(!preview.optimized);
+        assert_eq!(preview.request.messages.len(), 10);
+        assert!(preview.trimmed.is_empty());
+    }
 }
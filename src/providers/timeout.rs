@@ -0,0 +1,104 @@
+// Request timeouts for teacher providers
+//
+// A whole request (`request_timeout_seconds` per teacher) is bounded for
+// non-streaming calls. Streaming calls bound the wait for the response
+// headers and then the gap between chunks, so a long generation that keeps
+// producing output is never cut off.
+
+use anyhow::Result;
+use futures::stream::{BoxStream, Stream, StreamExt};
+use std::time::Duration;
+
+/// Request timeout for teachers that don't set `request_timeout_seconds`
+pub const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 120;
+
+/// A teacher request that took longer than its timeout
+#[derive(Debug)]
+pub struct RequestTimeoutError {
+    pub after: Duration,
+    /// A stream went quiet (rather than the whole request taking too long)
+    pub idle: bool,
+}
+
+impl std::fmt::Display for RequestTimeoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.idle {
+            write!(f, "Stream stalled: no data for {}s", self.after.as_secs_f64())?;
+        } else {
+            write!(f, "Request timed out after {}s", self.after.as_secs_f64())?;
+        }
+        write!(f, " (raise request_timeout_seconds for this teacher in config.toml if it needs longer)")
+    }
+}
+
+impl std::error::Error for RequestTimeoutError {}
+
+/// Turn a reqwest timeout into a `RequestTimeoutError`, passing other errors through
+pub fn check_timeout(error: reqwest::Error, after: Duration) -> anyhow::Error {
+    if error.is_timeout() {
+        RequestTimeoutError { after, idle: false }.into()
+    } else {
+        error.into()
+    }
+}
+
+/// Send a streaming request, waiting at most `timeout` for the response headers
+pub async fn send_stream_request(
+    request: reqwest::RequestBuilder,
+    timeout: Duration,
+) -> Result<reqwest::Response> {
+    match tokio::time::timeout(timeout, request.send()).await {
+        Ok(response) => Ok(response?),
+        Err(_) => Err(RequestTimeoutError {
+            after: timeout,
+            idle: false,
+        }
+        .into()),
+    }
+}
+
+/// Wrap a response body stream so it fails once no chunk arrives for `idle`
+///
+/// The stream ends after yielding the timeout error.
+pub fn with_idle_timeout<S, T, E>(stream: S, idle: Duration) -> BoxStream<'static, Result<T>>
+where
+    S: Stream<Item = std::result::Result<T, E>> + Send + 'static,
+    T: Send + 'static,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    futures::stream::unfold(Some(Box::pin(stream)), move |state| async move {
+        let mut stream = state?;
+        match tokio::time::timeout(idle, stream.next()).await {
+            Ok(Some(item)) => Some((item.map_err(anyhow::Error::from), Some(stream))),
+            Ok(None) => None,
+            Err(_) => Some((Err(RequestTimeoutError { after: idle, idle: true }.into()), None)),
+        }
+    })
+    .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_idle_timeout_resets_per_chunk() {
+        // Three chunks 30ms apart finish well past a 50ms idle limit
+        let chunks = futures::stream::iter(0..3).then(|i| async move {
+            tokio::time::sleep(Duration::from_millis(30)).await;
+            Ok::<_, std::io::Error>(i)
+        });
+        let items: Vec<_> = with_idle_timeout(chunks, Duration::from_millis(50)).collect().await;
+        assert_eq!(items.len(), 3);
+        assert!(items.iter().all(|item| item.is_ok()));
+
+        // A stream that goes quiet fails once, then ends
+        let stalled = futures::stream::iter(vec![Ok::<_, std::io::Error>(1)])
+            .chain(futures::stream::pending());
+        let items: Vec<_> = with_idle_timeout(stalled, Duration::from_millis(20)).collect().await;
+        assert_eq!(items.len(), 2);
+        let error = items[1].as_ref().unwrap_err();
+        assert!(error.downcast_ref::<RequestTimeoutError>().unwrap().idle);
+        assert!(error.to_string().contains("request_timeout_seconds"));
+    }
+}
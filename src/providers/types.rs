@@ -4,8 +4,10 @@
 // allowing the rest of the codebase to work with a unified interface.
 
 use crate::claude::types::{ContentBlock, Message};
+use crate::config::ThinkingLevel;
 use crate::tools::types::ToolDefinition;
 use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
 
 /// Unified request format for all LLM providers
 ///
@@ -16,11 +18,16 @@ pub struct ProviderRequest {
     /// Conversation messages (using Claude's Message format as the common denominator)
     pub messages: Vec<Message>,
 
+    /// System prompt, sent ahead of any system messages in `messages`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system: Option<String>,
+
     /// Model name (provider-specific)
     pub model: String,
 
-    /// Maximum tokens to generate
-    pub max_tokens: u32,
+    /// Maximum tokens to generate (None uses the provider's configured limit)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
 
     /// Tool definitions (optional)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -30,6 +37,10 @@ pub struct ProviderRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub temperature: Option<f32>,
 
+    /// Thinking budget (None uses the provider's configured setting)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thinking: Option<ThinkingLevel>,
+
     /// Whether to stream the response
     #[serde(skip)]
     pub stream: bool,
@@ -40,14 +51,22 @@ impl ProviderRequest {
     pub fn new(messages: Vec<Message>) -> Self {
         Self {
             messages,
+            system: None,
             model: String::new(), // Will be set by provider
-            max_tokens: 4096,
+            max_tokens: None,
             tools: None,
             temperature: None,
+            thinking: None,
             stream: false,
         }
     }
 
+    /// Set the system prompt
+    pub fn with_system(mut self, system: impl Into<String>) -> Self {
+        self.system = Some(system.into());
+        self
+    }
+
     /// Set the model name
     pub fn with_model(mut self, model: impl Into<String>) -> Self {
         self.model = model.into();
@@ -56,7 +75,7 @@ impl ProviderRequest {
 
     /// Set max tokens
     pub fn with_max_tokens(mut self, max_tokens: u32) -> Self {
-        self.max_tokens = max_tokens;
+        self.max_tokens = Some(max_tokens);
         self
     }
 
@@ -77,6 +96,84 @@ impl ProviderRequest {
         self.temperature = Some(temperature);
         self
     }
+
+    /// Set the thinking budget
+    pub fn with_thinking(mut self, thinking: ThinkingLevel) -> Self {
+        self.thinking = Some(thinking);
+        self
+    }
+}
+
+/// Session override of the teachers' thinking setting (/think)
+///
+/// Shared by every clone of a client; `None` leaves each teacher's
+/// configured setting in effect.
+#[derive(Debug, Default)]
+pub struct ThinkingOverride {
+    level: RwLock<Option<ThinkingLevel>>,
+}
+
+impl ThinkingOverride {
+    pub fn get(&self) -> Option<ThinkingLevel> {
+        *self.level.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn set(&self, level: Option<ThinkingLevel>) {
+        *self.level.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = level;
+    }
+}
+
+/// Range accepted for a temperature override
+pub const TEMPERATURE_RANGE: std::ops::RangeInclusive<f32> = 0.0..=2.0;
+
+/// One-shot temperature for the next teacher call (/temp)
+///
+/// Shared by every clone of a client. The request that uses it takes it,
+/// so later calls go back to the configured temperatures.
+#[derive(Debug, Default)]
+pub struct TemperatureOverride {
+    value: RwLock<Option<f32>>,
+}
+
+impl TemperatureOverride {
+    pub fn get(&self) -> Option<f32> {
+        *self.value.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Set (or clear, with None) the override; values outside 0.0-2.0 are rejected
+    pub fn set(&self, value: Option<f32>) -> anyhow::Result<()> {
+        if let Some(value) = value {
+            if !TEMPERATURE_RANGE.contains(&value) {
+                anyhow::bail!(
+                    "Temperature must be between {:.1} and {:.1}, got {}",
+                    TEMPERATURE_RANGE.start(),
+                    TEMPERATURE_RANGE.end(),
+                    value
+                );
+            }
+        }
+        *self.value.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = value;
+        Ok(())
+    }
+
+    /// Use the override up, clearing it
+    pub fn take(&self) -> Option<f32> {
+        self.value
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take()
+    }
+}
+
+/// A prompt-cache breakpoint placed in a request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheBreakpoint {
+    /// After the tool definitions
+    Tools,
+    /// After the system prompt
+    System,
+    /// At the end of `request.messages[index]`
+    Message(usize),
 }
 
 /// Unified response format from LLM providers
@@ -101,6 +198,21 @@ pub struct ProviderResponse {
 
     /// Provider name (e.g., "claude", "openai", "gemini")
     pub provider: String,
+
+    /// Tokens billed for the request, when the provider reports them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<TokenUsage>,
+}
+
+/// Input/output token counts for one teacher call
+///
+/// Deserializes straight from a Messages API `usage` object (the cache
+/// fields there are ignored).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct TokenUsage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
 }
 
 impl ProviderResponse {
@@ -146,3 +258,18 @@ impl ProviderResponse {
 ///
 /// Re-export from generators module for convenience
 pub use crate::generators::StreamChunk;
+
+/// A provider's finish reason in Messages API terms
+///
+/// OpenAI-style "stop", "length" and "tool_calls" (and Gemini's upper-case
+/// spellings) become "end_turn", "max_tokens" and "tool_use"; anything else
+/// passes through unchanged.
+pub fn stop_reason_from_finish_reason(finish_reason: &str) -> String {
+    match finish_reason.to_ascii_lowercase().as_str() {
+        "stop" => "end_turn",
+        "length" | "max_tokens" => "max_tokens",
+        "tool_calls" | "function_call" => "tool_use",
+        _ => finish_reason,
+    }
+    .to_string()
+}
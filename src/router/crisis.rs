@@ -0,0 +1,255 @@
+// Crisis detection
+//
+// Queries that mention self-harm or similar crises never go to the local
+// model. Depending on the configured action they are forwarded to the teacher
+// or answered with a canned safe response without calling any model.
+//
+// Rules come from ~/.shammah/crisis_keywords.toml when it exists; any field
+// left out of that file (or the whole file) falls back to DEFAULT_RULES.
+
+use anyhow::{Context, Result};
+use regex::{Regex, RegexBuilder};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Built-in rules, also the reference for writing crisis_keywords.toml
+const DEFAULT_RULES: &str = r#"
+# What to do with a matching query:
+#   "forward" - send it to the teacher (never the local model)
+#   "respond" - answer with `response` below, without calling any model
+action = "forward"
+
+# Canned reply for action = "respond" (and for local-only daemon requests,
+# which can't be forwarded)
+response = """
+It sounds like you might be going through something really painful right now. You don't have to face it alone.
+
+If you are in immediate danger, please call your local emergency number. In the US you can call or text 988 (Suicide & Crisis Lifeline); elsewhere, https://findahelpline.com lists free, confidential helplines by country.
+
+Reaching out to someone you trust - a friend, family member, or counselor - can also help."""
+
+# Case-insensitive, matched as whole words (any whitespace between words)
+keywords = [
+    "suicide",
+    "suicidal",
+    "kill myself",
+    "killing myself",
+    "end my life",
+    "ending my life",
+    "take my own life",
+    "want to die",
+    "better off dead",
+    "no reason to live",
+    "self-harm",
+    "self harm",
+    "hurt myself",
+    "hurting myself",
+    "cut myself",
+    "cutting myself",
+    "overdose on",
+]
+
+# Case-insensitive regular expressions
+patterns = [
+    '\b(end|ending)\s+it\s+all\b',
+    '''\bdon'?t\s+want\s+to\s+(live|be\s+alive|wake\s+up)\b''',
+    '\b(nothing|no\s+one)\s+to\s+live\s+for\b',
+]
+"#;
+
+/// What happens to a query that matches a crisis rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CrisisAction {
+    /// Send it to the teacher
+    Forward,
+    /// Answer with the canned response
+    Respond,
+}
+
+/// A query that matched a crisis rule
+#[derive(Debug, Clone)]
+pub struct CrisisMatch {
+    /// Keyword or pattern that matched
+    pub trigger: String,
+    pub action: CrisisAction,
+    /// Canned safe response (used for `Respond`, or when forwarding is impossible)
+    pub response: String,
+}
+
+impl CrisisMatch {
+    /// The canned response, if this match should be answered without a model
+    pub fn canned_response(&self) -> Option<&str> {
+        match self.action {
+            CrisisAction::Respond => Some(&self.response),
+            CrisisAction::Forward => None,
+        }
+    }
+}
+
+/// On-disk rules; missing fields fall back to the built-in ones
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CrisisRules {
+    action: Option<CrisisAction>,
+    response: Option<String>,
+    keywords: Option<Vec<String>>,
+    patterns: Option<Vec<String>>,
+}
+
+/// Keyword/regex crisis detector
+#[derive(Debug, Clone)]
+pub struct CrisisDetector {
+    action: CrisisAction,
+    response: String,
+    /// (trigger as written in the rules, compiled matcher)
+    rules: Vec<(String, Regex)>,
+}
+
+impl CrisisDetector {
+    /// Build a detector from TOML rules (see `DEFAULT_RULES`)
+    pub fn from_toml(text: &str) -> Result<Self> {
+        let rules: CrisisRules = toml::from_str(text).context("Invalid crisis rules")?;
+        let defaults: CrisisRules =
+            toml::from_str(DEFAULT_RULES).expect("built-in crisis rules are valid TOML");
+
+        let mut compiled = Vec::new();
+        for keyword in rules.keywords.or(defaults.keywords).unwrap_or_default() {
+            let words: Vec<String> = keyword.split_whitespace().map(regex::escape).collect();
+            if words.is_empty() {
+                continue;
+            }
+            let regex = RegexBuilder::new(&format!(r"\b{}\b", words.join(r"\s+")))
+                .case_insensitive(true)
+                .build()
+                .with_context(|| format!("Invalid crisis keyword: {}", keyword))?;
+            compiled.push((keyword, regex));
+        }
+        for pattern in rules.patterns.or(defaults.patterns).unwrap_or_default() {
+            let regex = RegexBuilder::new(&pattern)
+                .case_insensitive(true)
+                .build()
+                .with_context(|| format!("Invalid crisis pattern: {}", pattern))?;
+            compiled.push((pattern, regex));
+        }
+
+        Ok(Self {
+            action: rules.action.or(defaults.action).unwrap_or(CrisisAction::Forward),
+            response: rules
+                .response
+                .or(defaults.response)
+                .unwrap_or_default()
+                .trim()
+                .to_string(),
+            rules: compiled,
+        })
+    }
+
+    /// Load rules from a TOML file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::from_toml(&text).with_context(|| format!("Failed to load {}", path.display()))
+    }
+
+    /// User rules file: ~/.shammah/crisis_keywords.toml
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| home.join(".shammah").join("crisis_keywords.toml"))
+    }
+
+    /// Load the user rules file if there is one, else the built-in rules
+    ///
+    /// A broken rules file is logged and ignored rather than leaving crisis
+    /// detection off.
+    pub fn load_or_default() -> Self {
+        let Some(path) = Self::default_path().filter(|path| path.exists()) else {
+            return Self::default();
+        };
+        match Self::load(&path) {
+            Ok(detector) => {
+                tracing::info!(
+                    path = %path.display(),
+                    rules = detector.rule_count(),
+                    "Loaded crisis rules"
+                );
+                detector
+            }
+            Err(e) => {
+                tracing::warn!("{:#}; using built-in crisis rules", e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Check a query against the rules (first match wins)
+    pub fn check(&self, query: &str) -> Option<CrisisMatch> {
+        self.rules
+            .iter()
+            .find(|(_, regex)| regex.is_match(query))
+            .map(|(trigger, _)| CrisisMatch {
+                trigger: trigger.clone(),
+                action: self.action,
+                response: self.response.clone(),
+            })
+    }
+
+    pub fn action(&self) -> CrisisAction {
+        self.action
+    }
+
+    /// Number of keywords plus patterns
+    pub fn rule_count(&self) -> usize {
+        self.rules.len()
+    }
+}
+
+impl Default for CrisisDetector {
+    fn default() -> Self {
+        Self::from_toml(DEFAULT_RULES).expect("built-in crisis rules compile")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_rules() {
+        let detector = CrisisDetector::default();
+        assert_eq!(detector.action(), CrisisAction::Forward);
+
+        let crisis = detector.check("I think I want to  KILL myself").unwrap();
+        assert_eq!(crisis.trigger, "kill myself");
+        assert!(crisis.canned_response().is_none());
+        assert!(crisis.response.contains("988"));
+
+        assert!(detector.check("I just don't want to be alive anymore").is_some());
+        assert!(detector.check("How do I kill a process on port 8080?").is_none());
+    }
+
+    #[test]
+    fn test_user_rules_override_defaults() {
+        let detector = CrisisDetector::from_toml(
+            r#"
+            action = "respond"
+            response = "  Please reach out to someone.  "
+            keywords = ["hopeless"]
+            "#,
+        )
+        .unwrap();
+
+        let crisis = detector.check("Everything feels hopeless").unwrap();
+        assert_eq!(crisis.canned_response(), Some("Please reach out to someone."));
+        // Keywords replaced, patterns kept from the defaults
+        assert!(detector.check("I want to kill myself").is_none());
+        assert!(detector.check("I want to end it all").is_some());
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        assert!(CrisisDetector::from_toml(r#"patterns = ["(unclosed"]"#).is_err());
+        assert!(CrisisDetector::from_toml(r#"action = "ignore""#).is_err());
+        assert!(CrisisDetector::from_toml(r#"keyword = ["typo"]"#).is_err());
+    }
+}
@@ -1,5 +1,6 @@
 // Routing decision logic
 
+use super::crisis::{CrisisDetector, CrisisMatch};
 use crate::models::{ThresholdRouter, ThresholdRouterStats};
 use anyhow::Result;
 use std::path::Path;
@@ -9,6 +10,10 @@ pub enum ForwardReason {
     NoMatch,
     LowConfidence,
     ModelNotReady, // New: Model is still loading/downloading
+    UserForced,    // User asked for the teacher (/forward, /mode forward)
+    LocalTimeout,  // Local generation ran past backend.local_generation_timeout_ms
+    Crisis,        // Query matched a crisis rule (never answered locally)
+    Image,         // Query has image attachments (the local model is text-only)
 }
 
 impl ForwardReason {
@@ -17,6 +22,10 @@ impl ForwardReason {
             ForwardReason::NoMatch => "no_match",
             ForwardReason::LowConfidence => "low_confidence",
             ForwardReason::ModelNotReady => "model_not_ready",
+            ForwardReason::UserForced => "user_forced",
+            ForwardReason::LocalTimeout => "local_timeout",
+            ForwardReason::Crisis => "crisis",
+            ForwardReason::Image => "image",
         }
     }
 }
@@ -31,17 +40,55 @@ pub enum RouteDecision {
 #[derive(Clone)]
 pub struct Router {
     threshold_router: ThresholdRouter,
+    crisis_detector: CrisisDetector,
 }
 
 impl Router {
+    /// Create a router with the built-in crisis rules
     pub fn new(threshold_router: ThresholdRouter) -> Self {
         Self {
             threshold_router,
+            crisis_detector: CrisisDetector::default(),
         }
     }
 
+    /// Use a different crisis detector (e.g. `CrisisDetector::load_or_default()`)
+    pub fn with_crisis_detector(mut self, crisis_detector: CrisisDetector) -> Self {
+        self.crisis_detector = crisis_detector;
+        self
+    }
+
+    pub fn crisis_detector(&self) -> &CrisisDetector {
+        &self.crisis_detector
+    }
+
+    /// Check a query against the crisis rules
+    ///
+    /// Callers handle canned responses (`CrisisMatch::canned_response`)
+    /// before routing; `route` itself forwards any crisis query.
+    pub fn check_crisis(&self, query: &str) -> Option<CrisisMatch> {
+        self.crisis_detector.check(query)
+    }
+
+    /// Forward decision for crisis queries, if this is one
+    fn route_crisis(&self, query: &str) -> Option<RouteDecision> {
+        let crisis = self.check_crisis(query)?;
+        tracing::warn!(
+            trigger = %crisis.trigger,
+            "Routing decision: FORWARD (crisis rule matched)"
+        );
+        Some(RouteDecision::Forward {
+            reason: ForwardReason::Crisis,
+        })
+    }
+
     /// Make a routing decision for a query
     pub fn route(&self, query: &str) -> RouteDecision {
+        // Layer 0: Crisis queries never go to the local model
+        if let Some(decision) = self.route_crisis(query) {
+            return decision;
+        }
+
         // Layer 1: Data-driven routing - use threshold model
         if self.threshold_router.should_try_local(query) {
             let stats = self.threshold_router.stats();
@@ -71,7 +118,11 @@ impl Router {
         query: &str,
         generator_is_ready: bool,
     ) -> RouteDecision {
-        // Layer 0: Check if generator is ready (progressive bootstrap)
+        if let Some(decision) = self.route_crisis(query) {
+            return decision;
+        }
+
+        // Check if generator is ready (progressive bootstrap)
         if !generator_is_ready {
             tracing::info!("Routing decision: FORWARD (model not ready yet)");
             return RouteDecision::Forward {
@@ -83,6 +134,49 @@ impl Router {
         self.route(query)
     }
 
+    /// Routing decision when the user explicitly forwards a query to the teacher
+    ///
+    /// Skips the threshold check entirely, but still produces a route trace so
+    /// forced forwards show up in logs and metrics (and are learned from like
+    /// any other forward).
+    pub fn route_forced_forward(&self, query: &str) -> RouteDecision {
+        tracing::info!(
+            query_len = query.len(),
+            "Routing decision: FORWARD (user forced)"
+        );
+        RouteDecision::Forward {
+            reason: ForwardReason::UserForced,
+        }
+    }
+
+    /// Routing decision for a query with images
+    ///
+    /// The local model only reads text, so these always go to the teacher.
+    pub fn route_image(&self, query: &str) -> RouteDecision {
+        tracing::info!(
+            query_len = query.len(),
+            "Routing decision: FORWARD (query has images)"
+        );
+        RouteDecision::Forward {
+            reason: ForwardReason::Image,
+        }
+    }
+
+    /// Routing decision after local generation timed out
+    ///
+    /// The query was already routed locally; this records the fallback to
+    /// the teacher as its own route trace.
+    pub fn route_local_timeout(&self, query: &str, after: std::time::Duration) -> RouteDecision {
+        tracing::info!(
+            query_len = query.len(),
+            timeout_ms = after.as_millis() as u64,
+            "Routing decision: FORWARD (local generation timed out)"
+        );
+        RouteDecision::Forward {
+            reason: ForwardReason::LocalTimeout,
+        }
+    }
+
     /// Learn from a local generation attempt
     pub fn learn_local_attempt(&mut self, query: &str, was_successful: bool) {
         self.threshold_router
@@ -120,7 +214,6 @@ impl Router {
     }
 }
 
-// FIXME: Tests disabled - CrisisDetector was removed from the routing system
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -130,15 +223,34 @@ mod tests {
         assert_eq!(ForwardReason::NoMatch.as_str(), "no_match");
         assert_eq!(ForwardReason::LowConfidence.as_str(), "low_confidence");
         assert_eq!(ForwardReason::ModelNotReady.as_str(), "model_not_ready");
+        assert_eq!(ForwardReason::UserForced.as_str(), "user_forced");
+        assert_eq!(ForwardReason::LocalTimeout.as_str(), "local_timeout");
+        assert_eq!(ForwardReason::Crisis.as_str(), "crisis");
+        assert_eq!(ForwardReason::Image.as_str(), "image");
     }
 
-    // #[test]
-    // fn test_route_with_generator_check_not_ready() {
-    //     // FIXME: CrisisDetector no longer exists
-    // }
+    #[test]
+    fn test_route_with_generator_check_not_ready() {
+        let router = Router::new(ThresholdRouter::new());
+        assert!(matches!(
+            router.route_with_generator_check("What is 2+2?", false),
+            RouteDecision::Forward {
+                reason: ForwardReason::ModelNotReady
+            }
+        ));
+    }
 
-    // #[test]
-    // fn test_route_with_generator_check_ready() {
-    //     // FIXME: CrisisDetector no longer exists
-    // }
+    #[test]
+    fn test_crisis_queries_always_forward() {
+        let router = Router::new(ThresholdRouter::new());
+        for generator_is_ready in [false, true] {
+            assert!(matches!(
+                router.route_with_generator_check("I want to end my life", generator_is_ready),
+                RouteDecision::Forward {
+                    reason: ForwardReason::Crisis
+                }
+            ));
+        }
+        assert!(router.check_crisis("What is 2+2?").is_none());
+    }
 }
@@ -1,10 +1,12 @@
 // Router module
 // Public interface for routing decisions
 
+mod crisis;
 mod decision;
 mod hybrid_router;
 mod model_router;
 
+pub use crisis::{CrisisAction, CrisisDetector, CrisisMatch};
 pub use decision::{ForwardReason, RouteDecision, Router};
 pub use hybrid_router::{HybridRouter, HybridRouterStats, HybridStrategy};
 pub use model_router::ModelRouter;
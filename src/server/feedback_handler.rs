@@ -12,6 +12,8 @@ use std::sync::Arc;
 use tokio::sync::mpsc;
 use tracing::{info, warn};
 
+use super::training_worker::{TrainingAction, TrainingState};
+use super::AgentServer;
 use crate::models::WeightedExample;
 
 /// Request body for /v1/feedback endpoint
@@ -99,20 +101,102 @@ pub struct TrainingStatusResponse {
     pub queue_length: usize,
     /// Whether training is currently active
     pub training_active: bool,
+    /// Worker state: "idle", "training", or "paused"
+    pub state: TrainingState,
     /// Optional last training timestamp
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_training: Option<String>,
 }
 
+impl TrainingStatusResponse {
+    fn from_server(server: &AgentServer, state: TrainingState) -> Self {
+        let queue_length = server
+            .training_coordinator()
+            .buffer()
+            .map(|buffer| buffer.len())
+            .unwrap_or(0);
+
+        Self {
+            queue_length,
+            training_active: state == TrainingState::Training,
+            state,
+            last_training: None,
+        }
+    }
+}
+
 /// Handle GET /v1/training/status - Get training queue status
-pub async fn handle_training_status() -> Json<TrainingStatusResponse> {
-    // TODO: Implement actual status tracking
-    // For now, return placeholder data
-    Json(TrainingStatusResponse {
-        queue_length: 0,
-        training_active: false,
-        last_training: None,
-    })
+pub async fn handle_training_status(
+    State(server): State<Arc<AgentServer>>,
+) -> Json<TrainingStatusResponse> {
+    let state = server.training_handle().state();
+    Json(TrainingStatusResponse::from_server(&server, state))
+}
+
+/// Handle POST /v1/training/pause - Stop starting new training batches
+pub async fn handle_training_pause(
+    State(server): State<Arc<AgentServer>>,
+) -> Result<Json<TrainingStatusResponse>, Response> {
+    control_training(server, TrainingAction::Pause).await
+}
+
+/// Handle POST /v1/training/resume - Resume training batches
+pub async fn handle_training_resume(
+    State(server): State<Arc<AgentServer>>,
+) -> Result<Json<TrainingStatusResponse>, Response> {
+    control_training(server, TrainingAction::Resume).await
+}
+
+/// Handle POST /v1/training/cancel - Terminate the running training subprocess
+pub async fn handle_training_cancel(
+    State(server): State<Arc<AgentServer>>,
+) -> Result<Json<TrainingStatusResponse>, Response> {
+    control_training(server, TrainingAction::Cancel).await
+}
+
+/// Handle POST /v1/training/reload - Apply the config file's [training] settings
+///
+/// Re-reads the config, so batch settings change without restarting the
+/// daemon. Invalid settings are rejected and the current ones stay.
+pub async fn handle_training_reload(
+    State(server): State<Arc<AgentServer>>,
+) -> Result<Json<TrainingStatusResponse>, Response> {
+    info!("Received training config reload request");
+
+    let config = crate::config::load_config()
+        .and_then(|config| config.training.validate().map(|()| config.training))
+        .map_err(|e| training_error(StatusCode::BAD_REQUEST, e))?;
+    server
+        .training_handle()
+        .reconfigure(config)
+        .map_err(|e| training_error(StatusCode::SERVICE_UNAVAILABLE, e))?;
+
+    let state = server.training_handle().state();
+    Ok(Json(TrainingStatusResponse::from_server(&server, state)))
+}
+
+async fn control_training(
+    server: Arc<AgentServer>,
+    action: TrainingAction,
+) -> Result<Json<TrainingStatusResponse>, Response> {
+    info!(?action, "Received training control request");
+
+    match server.training_handle().send(action).await {
+        Ok(state) => Ok(Json(TrainingStatusResponse::from_server(&server, state))),
+        Err(e) => Err(training_error(StatusCode::SERVICE_UNAVAILABLE, e)),
+    }
+}
+
+fn training_error(status: StatusCode, error: anyhow::Error) -> Response {
+    warn!(error = %error, "Training control request failed");
+    (
+        status,
+        Json(FeedbackResponse {
+            status: "error".to_string(),
+            message: Some(error.to_string()),
+        }),
+    )
+        .into_response()
 }
 
 #[cfg(test)]
@@ -10,12 +10,16 @@ use axum::{
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
-use super::AgentServer;
+use super::{AgentServer, InFlight, InferenceKind, ServerBusy};
+use crate::models::{DownloadProgressSnapshot, GeneratorState, ModelInfo};
 use crate::claude::{ContentBlock, Message};
 
 /// Create the main application router
 pub fn create_router(server: Arc<AgentServer>) -> Router {
-    use super::feedback_handler::{handle_feedback, handle_training_status};
+    use super::feedback_handler::{
+        handle_feedback, handle_training_cancel, handle_training_pause, handle_training_reload,
+        handle_training_resume, handle_training_status,
+    };
     use super::openai_handlers::{handle_chat_completions, handle_list_models};
 
     // Get training sender for feedback endpoint
@@ -24,7 +28,6 @@ pub fn create_router(server: Arc<AgentServer>) -> Router {
     // Create feedback router with training_tx state
     let feedback_router = Router::new()
         .route("/v1/feedback", post(handle_feedback))
-        .route("/v1/training/status", post(handle_training_status))
         .with_state(training_tx);
 
     // Create main router with server state
@@ -32,13 +35,26 @@ pub fn create_router(server: Arc<AgentServer>) -> Router {
         // Claude-compatible endpoints
         .route("/v1/messages", post(handle_message))
         .route("/v1/session/:id", get(get_session).delete(delete_session))
+        .route("/v1/session/:id/usage", get(get_session_usage))
         .route("/v1/status", get(get_status))
         // OpenAI-compatible endpoints
         .route("/v1/chat/completions", post(handle_chat_completions))
         .route("/v1/models", get(handle_list_models))
+        // Training control
+        .route(
+            "/v1/training/status",
+            get(handle_training_status).post(handle_training_status),
+        )
+        .route("/v1/training/pause", post(handle_training_pause))
+        .route("/v1/training/resume", post(handle_training_resume))
+        .route("/v1/training/cancel", post(handle_training_cancel))
+        .route("/v1/training/reload", post(handle_training_reload))
         // Health and metrics
         .route("/health", get(health_check))
+        .route("/ready", get(readiness_check))
+        .route("/model", get(model_info))
         .route("/metrics", get(metrics_endpoint))
+        .route("/metrics/prometheus", get(prometheus_metrics))
         .with_state(server)
         // Merge feedback router
         .merge(feedback_router)
@@ -60,6 +76,9 @@ pub struct MessageRequest {
     /// Session ID for conversation continuity
     #[serde(default)]
     pub session_id: Option<String>,
+    /// Soft response length limit for local generation (overrides config)
+    #[serde(default)]
+    pub max_response_chars: Option<usize>,
 }
 
 /// Response body for /v1/messages endpoint (Claude-compatible)
@@ -104,12 +123,31 @@ async fn handle_message(
     // Add to conversation history
     session.conversation.add_message(user_message.clone());
 
-    // Process query through router
+    // Teacher requests carry the caller's system prompt, if any
+    let teacher_request = |messages| {
+        let mut claude_request = ClaudeRequest::with_context(messages);
+        claude_request.system = request.system.clone();
+        claude_request
+    };
+
+    // Process query through router (crisis queries with a canned response
+    // skip both models; the rest of them are forwarded)
     let router = server.router().read().await;
-    let decision = router.route(&user_text);
+    let canned_response = router
+        .check_crisis(&user_text)
+        .and_then(|crisis| crisis.canned_response().map(str::to_string));
+    let decision = if user_message.has_images() {
+        router.route_image(&user_text)
+    } else {
+        router.route(&user_text)
+    };
 
-    let (response_text, routing_decision) = match decision {
-        RouteDecision::Forward { reason } => {
+    let (response_text, routing_decision) = match (canned_response, decision) {
+        (Some(response), _) => {
+            tracing::warn!(session_id = %session.id, "Crisis rule matched, sending configured response");
+            (response, "crisis".to_string())
+        }
+        (None, RouteDecision::Forward { reason }) => {
             let reason_str = format!("{:?}", reason);
             tracing::info!(
                 session_id = %session.id,
@@ -118,21 +156,21 @@ async fn handle_message(
             );
 
             // Build Claude API request with full conversation context
-            let claude_request = ClaudeRequest::with_context(session.conversation.get_messages());
+            let claude_request = teacher_request(session.conversation.get_messages());
 
             // Forward to Claude
-            let response = server.claude_client().send_message(&claude_request).await?;
+            let response = server.send_to_teacher(&claude_request).await?;
+            session.usage.record(&response.model, response.usage);
 
             // Extract text from response
             let text = response.text();
 
             (text, "forward".to_string())
         }
-        RouteDecision::Local { .. } => {
+        (None, RouteDecision::Local { .. }) => {
             tracing::info!(session_id = %session.id, "Handling locally");
 
             // Check if local generator is ready
-            use crate::models::GeneratorState;
             let state = server.generator_state().read().await;
 
             match &*state {
@@ -142,9 +180,10 @@ async fn handle_message(
                     tracing::info!(session_id = %session.id, "Using local Qwen model");
 
                     // Use local generator (need write lock for try_generate)
+                    let local_slot = server.inference_limits().acquire(InferenceKind::Local).await?;
                     let mut generator = server.local_generator().write().await;
 
-                    match generator.try_generate_from_pattern(&user_text) {
+                    match generator.try_generate_from_pattern_limited(&user_text, request.max_response_chars) {
                         Ok(Some(response_text)) => {
                             (response_text, "local".to_string())
                         }
@@ -155,9 +194,11 @@ async fn handle_message(
                                 "Local confidence too low, falling back to Claude"
                             );
                             drop(generator); // Release lock
+                            drop(local_slot);
 
-                            let claude_request = ClaudeRequest::with_context(session.conversation.get_messages());
-                            let response = server.claude_client().send_message(&claude_request).await?;
+                            let claude_request = teacher_request(session.conversation.get_messages());
+                            let response = server.send_to_teacher(&claude_request).await?;
+                            session.usage.record(&response.model, response.usage);
                             let text = response.text();
 
                             (text, "confidence_fallback".to_string())
@@ -169,13 +210,23 @@ async fn handle_message(
                                 "Local generation failed, falling back to Claude"
                             );
                             drop(generator); // Release lock
+                            drop(local_slot);
+
+                            let routing = match e.downcast_ref::<crate::local::LocalTimeout>() {
+                                Some(timeout) => {
+                                    server.router().read().await.route_local_timeout(&user_text, timeout.after);
+                                    crate::router::ForwardReason::LocalTimeout.as_str()
+                                }
+                                None => "local_error_fallback",
+                            };
 
                             // Fall back to Claude on error
-                            let claude_request = ClaudeRequest::with_context(session.conversation.get_messages());
-                            let response = server.claude_client().send_message(&claude_request).await?;
+                            let claude_request = teacher_request(session.conversation.get_messages());
+                            let response = server.send_to_teacher(&claude_request).await?;
+                            session.usage.record(&response.model, response.usage);
                             let text = response.text();
 
-                            (text, "local_error_fallback".to_string())
+                            (text, routing.to_string())
                         }
                     }
                 }
@@ -187,8 +238,9 @@ async fn handle_message(
                     drop(state); // Release lock
 
                     // Model not ready yet, forward to Claude
-                    let claude_request = ClaudeRequest::with_context(session.conversation.get_messages());
-                    let response = server.claude_client().send_message(&claude_request).await?;
+                    let claude_request = teacher_request(session.conversation.get_messages());
+                    let response = server.send_to_teacher(&claude_request).await?;
+                    session.usage.record(&response.model, response.usage);
                     let text = response.text();
 
                     (text, "loading_fallback".to_string())
@@ -202,8 +254,9 @@ async fn handle_message(
                     drop(state); // Release lock
 
                     // Model failed to load, forward to Claude
-                    let claude_request = ClaudeRequest::with_context(session.conversation.get_messages());
-                    let response = server.claude_client().send_message(&claude_request).await?;
+                    let claude_request = teacher_request(session.conversation.get_messages());
+                    let response = server.send_to_teacher(&claude_request).await?;
+                    session.usage.record(&response.model, response.usage);
                     let text = response.text();
 
                     (text, "failed_fallback".to_string())
@@ -216,8 +269,9 @@ async fn handle_message(
                     drop(state); // Release lock
 
                     // No model available, forward to Claude
-                    let claude_request = ClaudeRequest::with_context(session.conversation.get_messages());
-                    let response = server.claude_client().send_message(&claude_request).await?;
+                    let claude_request = teacher_request(session.conversation.get_messages());
+                    let response = server.send_to_teacher(&claude_request).await?;
+                    session.usage.record(&response.model, response.usage);
                     let text = response.text();
 
                     (text, "unavailable_fallback".to_string())
@@ -226,7 +280,24 @@ async fn handle_message(
         }
     };
 
-    let elapsed_ms = start_time.elapsed().as_millis() as u64;
+    let elapsed = start_time.elapsed();
+    let elapsed_ms = elapsed.as_millis() as u64;
+    server.query_metrics().record_query(&routing_decision, elapsed);
+
+    // Score the response; teacher answers also teach the local generator
+    // (canned crisis responses don't)
+    let quality_score = server
+        .quality_scorer()
+        .score(&user_text, &response_text, None)
+        .await;
+    if !matches!(routing_decision.as_str(), "local" | "crisis") {
+        server.local_generator().write().await.learn_from_claude(
+            &user_text,
+            &response_text,
+            quality_score,
+            None,
+        );
+    }
 
     // Log metrics
     let query_hash = crate::metrics::MetricsLogger::hash_query(&user_text);
@@ -240,7 +311,7 @@ async fn handle_message(
         ResponseComparison {
             local_response: None,
             claude_response: response_text.clone(),
-            quality_score: 1.0,
+            quality_score,
             similarity_score: None,
             divergence: None,
         },
@@ -301,6 +372,30 @@ pub struct SessionInfo {
     pub message_count: usize,
 }
 
+/// Handle GET /v1/session/:id/usage - Teacher tokens and estimated cost
+async fn get_session_usage(
+    State(server): State<Arc<AgentServer>>,
+    Path(session_id): Path<String>,
+) -> Result<Json<SessionUsageInfo>, AppError> {
+    let session = server
+        .session_manager()
+        .get(&session_id)
+        .ok_or_else(|| anyhow::anyhow!("Session not found"))?;
+
+    Ok(Json(SessionUsageInfo {
+        id: session.id,
+        usage: session.usage,
+    }))
+}
+
+/// Session usage information
+#[derive(Debug, Serialize)]
+pub struct SessionUsageInfo {
+    pub id: String,
+    #[serde(flatten)]
+    pub usage: super::SessionUsage,
+}
+
 /// Handle DELETE /v1/session/:id - Delete session
 async fn delete_session(
     State(server): State<Arc<AgentServer>>,
@@ -348,8 +443,6 @@ pub struct StatusResponse {
 async fn get_status(
     State(server): State<Arc<AgentServer>>,
 ) -> Result<Json<StatusResponse>, AppError> {
-    use crate::models::GeneratorState;
-
     let state = server.generator_state().read().await;
 
     let generator_status = match &*state {
@@ -384,12 +477,17 @@ async fn get_status(
     Ok(Json(response))
 }
 
-/// Health check response
+/// Health check response (the process is up; see `/ready` for the local model)
 #[derive(Debug, Serialize)]
 pub struct HealthStatus {
     pub status: String,
     pub uptime_seconds: u64,
     pub active_sessions: usize,
+    pub training_state: super::TrainingState,
+    /// Teacher API circuit breaker (open = failing fast)
+    pub teacher_circuit: crate::providers::CircuitBreakerStatus,
+    /// Local generations and teacher calls running now, with their caps
+    pub in_flight: InFlight,
 }
 
 /// Handle GET /health - Health check endpoint
@@ -401,11 +499,73 @@ pub async fn health_check(
         status: "healthy".to_string(),
         uptime_seconds: 0, // Placeholder
         active_sessions: server.session_manager().active_count(),
+        training_state: server.training_handle().state(),
+        teacher_circuit: server.claude_client().circuit_status(),
+        in_flight: server.inference_limits().in_flight(),
     };
 
     Ok(Json(status))
 }
 
+/// Readiness response: whether the local model can serve requests
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadinessStatus {
+    pub ready: bool,
+    /// Generator state (`GeneratorState::kind`, e.g. "downloading")
+    pub state: String,
+    /// Human-readable state, e.g. "Downloading Qwen 2.5 3B (1/4): model.onnx"
+    pub message: String,
+    /// Model being downloaded, loaded or served
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Download progress while the model is downloading
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub download: Option<DownloadProgressSnapshot>,
+}
+
+impl ReadinessStatus {
+    pub fn from_state(state: &GeneratorState) -> Self {
+        let (model, download) = match state {
+            GeneratorState::Downloading {
+                model_name,
+                progress,
+            } => (Some(model_name.clone()), Some(progress.clone())),
+            GeneratorState::Loading { model_name } | GeneratorState::Ready { model_name, .. } => {
+                (Some(model_name.clone()), None)
+            }
+            _ => (None, None),
+        };
+        Self {
+            ready: state.is_ready(),
+            state: state.kind().to_string(),
+            message: state.status_message(),
+            model,
+            download,
+        }
+    }
+
+    /// Whether waiting won't help (loading failed, or no local model)
+    pub fn is_unavailable(&self) -> bool {
+        matches!(self.state.as_str(), "failed" | "not_available")
+    }
+}
+
+/// Handle GET /model - what the local model is, or how far loading has got
+pub async fn model_info(State(server): State<Arc<AgentServer>>) -> Json<ModelInfo> {
+    Json(crate::local::model_info(server.generator_state(), server.local_generator()).await)
+}
+
+/// Handle GET /ready - 200 once the local model is loaded, 503 until then
+pub async fn readiness_check(State(server): State<Arc<AgentServer>>) -> Response {
+    let status = ReadinessStatus::from_state(&*server.generator_state().read().await);
+    let code = if status.ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (code, Json(status)).into_response()
+}
+
 /// Handle GET /metrics - Prometheus metrics endpoint
 pub async fn metrics_endpoint(
     State(_server): State<Arc<AgentServer>>,
@@ -418,11 +578,30 @@ pub async fn metrics_endpoint(
     Ok((StatusCode::OK, metrics).into_response())
 }
 
+/// Handle GET /metrics/prometheus - query, tool and latency metrics in
+/// Prometheus text exposition format
+pub async fn prometheus_metrics(State(server): State<Arc<AgentServer>>) -> Response {
+    let router_stats = server.router().read().await.stats();
+    let body = server.query_metrics().render(&router_stats);
+    (
+        StatusCode::OK,
+        [(
+            axum::http::header::CONTENT_TYPE,
+            "text/plain; version=0.0.4; charset=utf-8",
+        )],
+        body,
+    )
+        .into_response()
+}
+
 /// Application error wrapper for proper HTTP error responses
 pub struct AppError(anyhow::Error);
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
+        if let Some(busy) = self.0.downcast_ref::<ServerBusy>() {
+            return busy.into_response();
+        }
         tracing::error!(error = %self.0, "Request failed");
 
         let error_message = self.0.to_string();
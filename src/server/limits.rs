@@ -0,0 +1,163 @@
+// Inference concurrency limits
+//
+// The local model can only run so many generations at once, so requests
+// queue for a slot (`[server] max_concurrent_inference`). Teacher calls have
+// their own, larger pool. A request that can't get a slot within
+// `queue_timeout_secs` is turned away with 429 and Retry-After instead of
+// piling up behind the model.
+
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Json, Response};
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Which pool a request draws from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InferenceKind {
+    Local,
+    Teacher,
+}
+
+impl InferenceKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            InferenceKind::Local => "local model",
+            InferenceKind::Teacher => "teacher",
+        }
+    }
+}
+
+/// No slot freed up within the queue timeout (turned into a 429)
+#[derive(Debug, Clone, Copy)]
+pub struct ServerBusy {
+    pub kind: InferenceKind,
+    /// Suggested wait before retrying
+    pub retry_after: Duration,
+}
+
+impl std::fmt::Display for ServerBusy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Server busy: too many concurrent {} requests, retry in {}s",
+            self.kind.as_str(),
+            self.retry_after.as_secs()
+        )
+    }
+}
+
+impl std::error::Error for ServerBusy {}
+
+impl IntoResponse for ServerBusy {
+    fn into_response(self) -> Response {
+        let body = serde_json::json!({
+            "error": {
+                "message": self.to_string(),
+                "type": "rate_limit_error"
+            }
+        });
+        (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(header::RETRY_AFTER, self.retry_after.as_secs().to_string())],
+            Json(body),
+        )
+            .into_response()
+    }
+}
+
+/// Requests holding a slot right now (reported by /health)
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct InFlight {
+    pub local: usize,
+    pub local_limit: usize,
+    pub teacher: usize,
+    pub teacher_limit: usize,
+}
+
+/// Slot pools for local generation and teacher calls
+#[derive(Debug)]
+pub struct InferenceLimits {
+    local: Arc<Semaphore>,
+    local_limit: usize,
+    teacher: Arc<Semaphore>,
+    teacher_limit: usize,
+    queue_timeout: Duration,
+}
+
+impl InferenceLimits {
+    /// Pools of `local_limit` and `teacher_limit` slots (at least 1 each)
+    pub fn new(local_limit: usize, teacher_limit: usize, queue_timeout: Duration) -> Self {
+        let local_limit = local_limit.max(1);
+        let teacher_limit = teacher_limit.max(1);
+        Self {
+            local: Arc::new(Semaphore::new(local_limit)),
+            local_limit,
+            teacher: Arc::new(Semaphore::new(teacher_limit)),
+            teacher_limit,
+            queue_timeout,
+        }
+    }
+
+    /// Wait up to the queue timeout for a slot, released when dropped
+    pub async fn acquire(&self, kind: InferenceKind) -> Result<OwnedSemaphorePermit, ServerBusy> {
+        let semaphore = match kind {
+            InferenceKind::Local => &self.local,
+            InferenceKind::Teacher => &self.teacher,
+        };
+        match tokio::time::timeout(self.queue_timeout, Arc::clone(semaphore).acquire_owned()).await
+        {
+            Ok(permit) => Ok(permit.expect("inference semaphore is never closed")),
+            Err(_) => {
+                tracing::warn!(kind = kind.as_str(), "No inference slot free, rejecting request");
+                Err(ServerBusy {
+                    kind,
+                    retry_after: Duration::from_secs(self.queue_timeout.as_secs().max(1)),
+                })
+            }
+        }
+    }
+
+    pub fn in_flight(&self) -> InFlight {
+        InFlight {
+            local: self.local_limit - self.local.available_permits(),
+            local_limit: self.local_limit,
+            teacher: self.teacher_limit - self.teacher.available_permits(),
+            teacher_limit: self.teacher_limit,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_acquire_and_release() {
+        let limits = InferenceLimits::new(1, 2, Duration::from_millis(20));
+
+        let permit = limits.acquire(InferenceKind::Local).await.unwrap();
+        let _teacher = limits.acquire(InferenceKind::Teacher).await.unwrap();
+        let in_flight = limits.in_flight();
+        assert_eq!((in_flight.local, in_flight.teacher), (1, 1));
+
+        let busy = limits.acquire(InferenceKind::Local).await.unwrap_err();
+        assert_eq!(busy.kind, InferenceKind::Local);
+        assert_eq!(busy.retry_after, Duration::from_secs(1));
+
+        drop(permit);
+        assert!(limits.acquire(InferenceKind::Local).await.is_ok());
+    }
+
+    #[test]
+    fn test_busy_response() {
+        let busy = ServerBusy {
+            kind: InferenceKind::Teacher,
+            retry_after: Duration::from_secs(10),
+        };
+        let response = busy.into_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "10");
+    }
+}
@@ -3,37 +3,46 @@
 
 mod feedback_handler;
 mod handlers;
+mod limits;
 mod middleware;
 mod openai_handlers;
 pub mod openai_types; // Public for client access
+mod prometheus;
 mod session;
 mod training_worker;
+mod usage;
 
 pub use feedback_handler::{handle_feedback, handle_training_status};
-pub use handlers::{create_router, health_check, metrics_endpoint};
+pub use handlers::{create_router, health_check, metrics_endpoint, readiness_check, ReadinessStatus};
+pub use limits::{InFlight, InferenceKind, InferenceLimits, ServerBusy};
 pub use middleware::auth_middleware;
 pub use openai_handlers::{handle_chat_completions, handle_list_models};
 pub use openai_types::*;
+pub use prometheus::QueryMetrics;
 pub use session::{SessionManager, SessionState};
-pub use training_worker::TrainingWorker;
+pub use training_worker::{TrainingAction, TrainingHandle, TrainingState, TrainingWorker};
+pub use usage::{estimate_cost_usd, SessionUsage};
 
 use anyhow::Result;
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 use tower_http::trace::TraceLayer;
 
-use crate::claude::ClaudeClient;
-use crate::config::Config;
+use crate::claude::{ClaudeClient, MessageRequest, MessageResponse};
+use crate::daemon::BindAddress;
+use crate::config::{Config, TrainingConfig};
 use crate::local::LocalGenerator;
 use crate::metrics::MetricsLogger;
-use crate::models::{BootstrapLoader, GeneratorState, TrainingCoordinator};
+use crate::models::quality::QualityScorer;
+use crate::models::{BootstrapLoader, GeneratorState, TemperatureConfig, TrainingCoordinator};
 use crate::router::Router;
 
 /// Configuration for the HTTP server
 #[derive(Debug, Clone)]
 pub struct ServerConfig {
-    /// Bind address (e.g., "127.0.0.1:8000")
+    /// Bind address ("127.0.0.1:8000", or "unix:/path/to/socket")
     pub bind_address: String,
     /// Maximum number of concurrent sessions
     pub max_sessions: usize,
@@ -43,6 +52,12 @@ pub struct ServerConfig {
     pub auth_enabled: bool,
     /// Valid API keys for authentication
     pub api_keys: Vec<String>,
+    /// Local generations running at once
+    pub max_concurrent_inference: usize,
+    /// Teacher calls running at once
+    pub max_concurrent_teacher: usize,
+    /// How long a request waits for a slot before getting HTTP 429
+    pub queue_timeout: Duration,
 }
 
 impl Default for ServerConfig {
@@ -53,6 +68,9 @@ impl Default for ServerConfig {
             session_timeout_minutes: 30,
             auth_enabled: false,
             api_keys: vec![],
+            max_concurrent_inference: 4,
+            max_concurrent_teacher: 16,
+            queue_timeout: Duration::from_secs(30),
         }
     }
 }
@@ -65,8 +83,12 @@ pub struct AgentServer {
     router: Arc<RwLock<Router>>,
     /// Metrics logger (shared)
     metrics_logger: Arc<MetricsLogger>,
+    /// In-memory counters for the Prometheus endpoint
+    query_metrics: Arc<QueryMetrics>,
     /// Session manager
     session_manager: Arc<SessionManager>,
+    /// Concurrency caps for local generation and teacher calls
+    inference_limits: Arc<InferenceLimits>,
     /// Server configuration
     config: ServerConfig,
     /// Local generator (Qwen model with LoRA)
@@ -79,6 +101,14 @@ pub struct AgentServer {
     training_coordinator: Arc<TrainingCoordinator>,
     /// Training examples sender (for feedback endpoint)
     training_tx: Arc<tokio::sync::mpsc::UnboundedSender<crate::models::WeightedExample>>,
+    /// Training worker control handle (pause/resume/cancel)
+    training_handle: TrainingHandle,
+    /// Batch settings for the training worker (`[training]` in config.toml)
+    training_config: TrainingConfig,
+    /// Teacher temperature per query category (`[temperature]` in config.toml)
+    temperature_config: TemperatureConfig,
+    /// Scores teacher responses before the local generator learns from them
+    quality_scorer: Arc<dyn QualityScorer>,
 }
 
 impl AgentServer {
@@ -106,19 +136,29 @@ impl AgentServer {
             claude_client: Arc::new(claude_client),
             router: Arc::new(RwLock::new(router)),
             metrics_logger: Arc::new(metrics_logger),
+            query_metrics: Arc::new(QueryMetrics::new()),
             session_manager: Arc::new(session_manager),
+            inference_limits: Arc::new(InferenceLimits::new(
+                server_config.max_concurrent_inference,
+                server_config.max_concurrent_teacher,
+                server_config.queue_timeout,
+            )),
             config: server_config,
             local_generator,
             bootstrap_loader,
             generator_state,
             training_coordinator,
             training_tx: Arc::new(training_tx),
+            training_handle: TrainingHandle::disconnected(),
+            training_config: config.training.clone(),
+            temperature_config: config.temperature.clone(),
+            quality_scorer: Arc::from(config.training.quality_scorer.build()),
         })
     }
 
     /// Start the HTTP server
     pub async fn serve(mut self) -> Result<()> {
-        let addr: SocketAddr = self.config.bind_address.parse()?;
+        let addr = BindAddress::parse(&self.config.bind_address)?;
 
         // Create training worker channel
         let (training_tx, training_rx) = tokio::sync::mpsc::unbounded_channel();
@@ -128,9 +168,9 @@ impl AgentServer {
         let worker = TrainingWorker::new(
             training_rx,
             Arc::clone(&self.training_coordinator),
-            10,  // batch_threshold: trigger after 10 examples
-            5,   // batch_timeout_minutes: trigger after 5 minutes
+            &self.training_config,
         );
+        self.training_handle = worker.handle();
 
         tokio::spawn(async move {
             worker.run().await;
@@ -163,7 +203,7 @@ impl AgentServer {
                             tracing::info!("Acquiring write lock on LocalGenerator...");
                             let mut gen = local_gen_clone.write().await;
                             tracing::info!("Write lock acquired, creating new LocalGenerator...");
-                            *gen = LocalGenerator::with_models(Some(model_clone));
+                            *gen = gen.reloaded(Some(model_clone));
                             tracing::info!("LocalGenerator updated");
                         }
                     ).await {
@@ -189,14 +229,32 @@ impl AgentServer {
         // Create application state
         let app_state = Arc::new(self);
 
-        // Build router
-        let app = create_router(app_state).layer(TraceLayer::new_for_http());
+        // Build router. Request spans are children of the caller's span (the
+        // daemon's pid/bind span), since axum serves each connection in its
+        // own task and spans don't follow spawned tasks on their own.
+        let parent = tracing::Span::current();
+        let app = create_router(app_state).layer(TraceLayer::new_for_http().make_span_with(
+            move |request: &axum::extract::Request| {
+                tracing::info_span!(
+                    parent: &parent,
+                    "request",
+                    method = %request.method(),
+                    uri = %request.uri(),
+                )
+            },
+        ));
 
         tracing::info!("Starting Shammah agent server on {}", addr);
 
         // Start server
-        let listener = tokio::net::TcpListener::bind(addr).await?;
-        axum::serve(listener, app).await?;
+        match addr {
+            BindAddress::Tcp(address) => {
+                let addr: SocketAddr = address.parse()?;
+                let listener = tokio::net::TcpListener::bind(addr).await?;
+                axum::serve(listener, app).await?;
+            }
+            BindAddress::Unix(path) => serve_unix_path(&path, app).await?,
+        }
 
         Ok(())
     }
@@ -216,21 +274,54 @@ impl AgentServer {
         &self.metrics_logger
     }
 
+    /// Get reference to the Prometheus counters
+    pub fn query_metrics(&self) -> &Arc<QueryMetrics> {
+        &self.query_metrics
+    }
+
     /// Get reference to session manager
     pub fn session_manager(&self) -> &Arc<SessionManager> {
         &self.session_manager
     }
 
+    /// Get reference to the inference concurrency limits
+    pub fn inference_limits(&self) -> &Arc<InferenceLimits> {
+        &self.inference_limits
+    }
+
+    /// Send `request` to the teacher once a teacher slot is free
+    ///
+    /// Fails with `ServerBusy` when no slot frees up within the queue timeout.
+    pub async fn send_to_teacher(&self, request: &MessageRequest) -> Result<MessageResponse> {
+        let _slot = self.inference_limits.acquire(InferenceKind::Teacher).await?;
+        self.claude_client.send_message(request).await
+    }
+
     /// Get reference to training examples sender
     pub fn training_tx(&self) -> &Arc<tokio::sync::mpsc::UnboundedSender<crate::models::WeightedExample>> {
         &self.training_tx
     }
 
+    /// Get reference to training worker control handle
+    pub fn training_handle(&self) -> &TrainingHandle {
+        &self.training_handle
+    }
+
     /// Get server configuration
     pub fn config(&self) -> &ServerConfig {
         &self.config
     }
 
+    /// Get teacher temperature settings
+    pub fn temperature_config(&self) -> &TemperatureConfig {
+        &self.temperature_config
+    }
+
+    /// Quality scorer for teacher responses (`[training] quality_scorer`)
+    pub fn quality_scorer(&self) -> &Arc<dyn QualityScorer> {
+        &self.quality_scorer
+    }
+
     /// Get reference to local generator
     pub fn local_generator(&self) -> &Arc<RwLock<LocalGenerator>> {
         &self.local_generator
@@ -251,3 +342,67 @@ impl AgentServer {
         &self.training_coordinator
     }
 }
+
+/// Bind a Unix socket at `path` and serve `app` on it
+///
+/// A socket file left behind by an earlier daemon is removed first; the
+/// socket is made owner-only, since it accepts the same requests as the
+/// TCP port.
+#[cfg(unix)]
+async fn serve_unix_path(path: &std::path::Path, app: axum::Router) -> Result<()> {
+    use anyhow::Context;
+    use std::os::unix::fs::PermissionsExt;
+
+    if path.exists() {
+        std::fs::remove_file(path)
+            .with_context(|| format!("Failed to remove stale socket {}", path.display()))?;
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let listener = tokio::net::UnixListener::bind(path)
+        .with_context(|| format!("Failed to bind Unix socket {}", path.display()))?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+
+    serve_unix(listener, app).await;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+async fn serve_unix_path(path: &std::path::Path, _app: axum::Router) -> Result<()> {
+    anyhow::bail!("Unix socket {} is not supported on this platform", path.display())
+}
+
+/// Serve `app` over HTTP/1.1 on a Unix socket listener
+///
+/// axum 0.7's `serve` only takes a TCP listener, so connections are
+/// accepted here and handed to hyper one at a time.
+#[cfg(unix)]
+pub(crate) async fn serve_unix(listener: tokio::net::UnixListener, app: axum::Router) {
+    use hyper::body::Incoming;
+    use hyper_util::rt::TokioIo;
+    use tower::Service;
+
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(e) => {
+                tracing::warn!("Failed to accept Unix socket connection: {}", e);
+                continue;
+            }
+        };
+
+        let app = app.clone();
+        tokio::spawn(async move {
+            let service = hyper::service::service_fn(move |request: hyper::Request<Incoming>| {
+                app.clone().call(request)
+            });
+            if let Err(e) = hyper::server::conn::http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                tracing::debug!("Unix socket connection ended with error: {}", e);
+            }
+        });
+    }
+}
@@ -16,7 +16,7 @@ use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
 
 use super::openai_types::*;
-use super::AgentServer;
+use super::{AgentServer, InferenceKind, ServerBusy};
 use crate::claude::{ContentBlock, Message};
 use crate::router::RouteDecision;
 use crate::tools::types::ToolDefinition as InternalToolDefinition;
@@ -182,10 +182,85 @@ async fn buffer_and_clean_tokens(
     }
 }
 
-/// Handle streaming chat completions (SSE)
+/// One `chat.completion.chunk` frame
+fn completion_chunk(
+    id: &str,
+    created: i64,
+    model: &str,
+    delta: serde_json::Value,
+    finish_reason: Option<&str>,
+) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "object": "chat.completion.chunk",
+        "created": created,
+        "model": model,
+        "choices": [{
+            "index": 0,
+            "delta": delta,
+            "finish_reason": finish_reason
+        }]
+    })
+}
+
+/// Replay a complete response as chunks (for responses that arrive whole)
+fn completion_chunks(response: &ChatCompletionResponse) -> Vec<serde_json::Value> {
+    let chunk = |delta, finish_reason| {
+        completion_chunk(&response.id, response.created, &response.model, delta, finish_reason)
+    };
+    let Some(choice) = response.choices.first() else {
+        return vec![chunk(serde_json::json!({}), Some("stop"))];
+    };
+
+    let mut delta = serde_json::json!({
+        "role": "assistant",
+        "content": choice.message.content.as_deref().unwrap_or(""),
+    });
+    if let Some(tool_calls) = &choice.message.tool_calls {
+        let indexed: Vec<serde_json::Value> = tool_calls
+            .iter()
+            .enumerate()
+            .map(|(index, call)| {
+                let mut call = serde_json::to_value(call).unwrap_or_default();
+                call["index"] = index.into();
+                call
+            })
+            .collect();
+        delta["tool_calls"] = indexed.into();
+    }
+
+    vec![
+        chunk(delta, None),
+        chunk(serde_json::json!({}), Some(choice.finish_reason.as_str())),
+    ]
+}
+
+/// SSE response from a stream of chunks, terminated by `data: [DONE]`
+fn sse_response<S>(chunks: S) -> Response
+where
+    S: Stream<Item = serde_json::Value> + Send + 'static,
+{
+    use futures::StreamExt;
+
+    let events = chunks
+        .map(|chunk| Event::default().json_data(chunk).unwrap())
+        .chain(stream::once(async { Event::default().data("[DONE]") }))
+        .map(Ok::<_, Infallible>);
+    Sse::new(events).into_response()
+}
+
+/// Handle streaming chat completions (SSE) from the local model
+///
+/// Tokens from the local generator are forwarded as `chat.completion.chunk`
+/// frames. With `teacher_fallback` (routed requests), a generation that
+/// produces nothing is answered by the teacher instead, sent as one chunk
+/// once the whole teacher response has arrived, and the result is recorded
+/// like a non-streaming completion.
 async fn handle_chat_completions_streaming(
     server: Arc<AgentServer>,
     request: ChatCompletionRequest,
+    teacher_fallback: bool,
+    start_time: Instant,
 ) -> Result<Response, Response> {
     // Validate request
     if request.messages.is_empty() {
@@ -195,14 +270,6 @@ async fn handle_chat_completions_streaming(
         ));
     }
 
-    // Check if local-only mode requested
-    if !request.local_only.unwrap_or(false) {
-        return Err(error_response(
-            "streaming is only supported for local-only queries",
-            "invalid_request_error",
-        ));
-    }
-
     // Convert OpenAI messages to internal format
     let internal_messages = convert_messages_to_internal(&request.messages)
         .map_err(|e| error_response(&e.to_string(), "invalid_request_error"))?;
@@ -224,6 +291,12 @@ async fn handle_chat_completions_streaming(
     }
     drop(state);
 
+    let local_slot = server
+        .inference_limits()
+        .acquire(InferenceKind::Local)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
     // Create bounded channel for streaming tokens with backpressure
     // Buffer size of 2 allows one token to be consumed while another is being generated
     let (tx, rx) = mpsc::channel::<String>(2);
@@ -232,6 +305,13 @@ async fn handle_chat_completions_streaming(
     let (cleaned_tx, cleaned_rx) = mpsc::channel::<String>(2);
 
     let model_name = request.model.clone();
+    let max_response_chars = request.max_response_chars;
+    let fallback_tx = tx.clone();
+    let fallback_model = request.teacher_model.clone();
+    let user_query = last_user_query(&request.messages).to_string();
+    let fallback_temperature = request
+        .temperature
+        .or_else(|| server.temperature_config().for_query(last_user_query(&request.messages)));
 
     // Get model adapter for cleaning
     let model_adapter = {
@@ -250,6 +330,12 @@ async fn handle_chat_completions_streaming(
     // backpressure - generation will pause if the HTTP stream can't keep up.
     let server_clone = server.clone();
     tokio::spawn(async move {
+        // Accumulate response for logging (and to tell whether anything was sent)
+        let accumulated_response = std::sync::Arc::new(std::sync::Mutex::new(String::new()));
+        let accumulated_clone = accumulated_response.clone();
+        let fallback_messages = internal_messages.clone();
+        let generation_server = server_clone.clone();
+
         // Run CPU-bound generation on blocking thread pool
         let result = tokio::task::spawn_blocking(move || {
             // Create runtime handle for async operations inside blocking context
@@ -257,15 +343,11 @@ async fn handle_chat_completions_streaming(
 
             // Get generator (need to use block_on since we're in blocking context)
             let mut generator = handle.block_on(async {
-                server_clone.local_generator().write().await
+                generation_server.local_generator().write().await
             });
 
-            // Accumulate response for logging
-            let accumulated_response = std::sync::Arc::new(std::sync::Mutex::new(String::new()));
-            let accumulated_clone = accumulated_response.clone();
-
             // Try to generate with streaming callback
-            let result = generator.try_generate_from_pattern_streaming(&internal_messages, move |_token_id, token_text| {
+            generator.try_generate_from_pattern_streaming(&internal_messages, max_response_chars, move |_token_id, token_text| {
                 tracing::debug!("[daemon] Sending token to SSE: {:?}", token_text);
 
                 // Accumulate for logging
@@ -284,85 +366,136 @@ async fn handle_chat_completions_streaming(
                 // Small sleep to pace token delivery and allow async runtime to process
                 // This helps prevent tokens from bunching up even with backpressure
                 std::thread::sleep(std::time::Duration::from_millis(10));
-            });
+            })
+        }).await;
+        drop(local_slot);
 
-            // Log complete response
-            if let Ok(acc) = accumulated_response.lock() {
+        // Log complete response
+        let sent_anything = match accumulated_response.lock() {
+            Ok(acc) => {
                 info!("[DAEMON_RESPONSE] Complete response ({} chars): {:?}", acc.len(), &acc);
+                !acc.is_empty()
             }
+            Err(_) => true,
+        };
 
-            result
-        }).await;
-
-        match result {
-            Ok(Ok(Some(_response))) => {
+        let mut answer = match result {
+            Ok(Ok(Some(response))) => {
                 info!("✓ Streaming generation completed");
+                Some(("local", response.content_blocks))
             }
             Ok(Ok(None)) => {
                 warn!("❌ Streaming generation returned None");
+                None
+            }
+            Ok(Err(e)) if crate::local::is_local_timeout(&e) => {
+                // The stream just ends; partial output has already been sent
+                warn!("❌ Streaming generation stopped (local_timeout): {}", e);
+                None
             }
             Ok(Err(e)) => {
                 warn!("❌ Streaming generation error: {}", e);
+                None
             }
             Err(e) => {
                 warn!("❌ Blocking task error: {}", e);
+                None
+            }
+        };
+
+        if teacher_fallback && !sent_anything {
+            warn!("Local streaming produced nothing, falling back to teacher");
+            let mut claude_request = crate::claude::MessageRequest::with_context(fallback_messages);
+            claude_request.temperature = fallback_temperature;
+            if let Some(model) = fallback_model {
+                claude_request.model = model;
+            }
+            match server_clone.send_to_teacher(&claude_request).await {
+                Ok(response) => {
+                    let _ = fallback_tx.send(extract_text_from_blocks(&response.content)).await;
+                    answer = Some(("fallback", response.content));
+                }
+                Err(e) => warn!("❌ Teacher fallback failed: {}", e),
+            }
+        }
+
+        // Same bookkeeping as complete_chat: routed results are recorded in
+        // full, local-only ones only count towards the query metrics
+        if let Some((routing_decision, content_blocks)) = answer {
+            let elapsed = start_time.elapsed();
+            if teacher_fallback {
+                record_chat_result(&server_clone, routing_decision, elapsed, &user_query, &content_blocks);
+            } else {
+                server_clone.query_metrics().record_query("local", elapsed);
             }
         }
     });
 
     // Create SSE stream from cleaned token receiver
-    // State: (receiver, model_name, done_flag)
-    let stream = stream::unfold((cleaned_rx, model_name, false), |(mut rx, model_name, done)| async move {
-        if done {
-            // Already sent final chunk, terminate stream
-            return None;
-        }
-
-        match rx.recv().await {
-            Some(token_text) => {
-                // Format as OpenAI streaming chunk
-                let chunk = serde_json::json!({
-                    "id": format!("chatcmpl-{}", uuid::Uuid::new_v4()),
-                    "object": "chat.completion.chunk",
-                    "created": chrono::Utc::now().timestamp(),
-                    "model": &model_name,
-                    "choices": [{
-                        "index": 0,
-                        "delta": {
-                            "content": token_text
-                        },
-                        "finish_reason": null
-                    }]
-                });
-
-                Some((
-                    Ok::<_, Infallible>(Event::default().json_data(chunk).unwrap()),
-                    (rx, model_name, false), // Continue streaming
-                ))
+    // State: (receiver, first_chunk, done_flag); id and created are shared by every chunk
+    let id = format!("chatcmpl-{}", uuid::Uuid::new_v4());
+    let created = chrono::Utc::now().timestamp();
+    let chunks = stream::unfold((cleaned_rx, true, false), move |(mut rx, first, done)| {
+        let id = id.clone();
+        let model_name = model_name.clone();
+        async move {
+            if done {
+                // Already sent final chunk, terminate stream
+                return None;
             }
-            None => {
-                // Send final chunk with finish_reason
-                let chunk = serde_json::json!({
-                    "id": format!("chatcmpl-{}", uuid::Uuid::new_v4()),
-                    "object": "chat.completion.chunk",
-                    "created": chrono::Utc::now().timestamp(),
-                    "model": &model_name,
-                    "choices": [{
-                        "index": 0,
-                        "delta": {},
-                        "finish_reason": "stop"
-                    }]
-                });
 
-                Some((
-                    Ok::<_, Infallible>(Event::default().json_data(chunk).unwrap()),
-                    (rx, model_name, true), // Mark done, will terminate on next call
-                ))
+            match rx.recv().await {
+                Some(token_text) => {
+                    // The first chunk also carries the role
+                    let delta = if first {
+                        serde_json::json!({ "role": "assistant", "content": token_text })
+                    } else {
+                        serde_json::json!({ "content": token_text })
+                    };
+                    Some((
+                        completion_chunk(&id, created, &model_name, delta, None),
+                        (rx, false, false), // Continue streaming
+                    ))
+                }
+                None => {
+                    // Send final chunk with finish_reason
+                    Some((
+                        completion_chunk(&id, created, &model_name, serde_json::json!({}), Some("stop")),
+                        (rx, first, true), // Mark done, will terminate on next call
+                    ))
+                }
             }
         }
     });
 
-    Ok(Sse::new(stream).into_response())
+    Ok(sse_response(chunks))
+}
+
+/// Whether a routed (not local-only) streaming request can stream tokens
+/// from the local model: no tools, not forced to the teacher, model ready,
+/// and the router picks local
+async fn streams_locally(server: &AgentServer, request: &ChatCompletionRequest) -> bool {
+    if request.tools.is_some() || request.forward_only.unwrap_or(false) {
+        return false;
+    }
+    if !matches!(
+        &*server.generator_state().read().await,
+        crate::models::GeneratorState::Ready { .. }
+    ) {
+        return false;
+    }
+    let decision = server.router().read().await.route(last_user_query(&request.messages));
+    matches!(decision, RouteDecision::Local { .. })
+}
+
+/// Text of the latest user message ("" if none)
+fn last_user_query(messages: &[ChatMessage]) -> &str {
+    messages
+        .iter()
+        .rev()
+        .find(|m| m.role == "user")
+        .and_then(|m| m.content.as_deref())
+        .unwrap_or("")
 }
 
 /// Handle POST /v1/chat/completions - OpenAI-compatible chat endpoint
@@ -380,26 +513,79 @@ pub async fn handle_chat_completions(
         );
     }
 
+    // Crisis queries never reach the local model: answer with the configured
+    // response (always, for local-only requests), otherwise let routing
+    // forward them to the teacher
+    let crisis = server.router().read().await.check_crisis(last_user_query(&request.messages));
+    if let Some(crisis) = crisis {
+        let canned = match crisis.canned_response() {
+            Some(response) => Some(response),
+            None if request.local_only.unwrap_or(false) => Some(crisis.response.as_str()),
+            None => None,
+        };
+        if let Some(text) = canned {
+            warn!(trigger = %crisis.trigger, "Crisis rule matched, sending configured response");
+            let mut response =
+                match convert_response_to_openai(vec![ContentBlock::text(text)], &request.model) {
+                    Ok(response) => response,
+                    Err(error_resp) => return error_resp,
+                };
+            response.route = Some("crisis".to_string());
+            response.latency_ms = Some(start_time.elapsed().as_millis() as u64);
+            server.query_metrics().record_query("crisis", start_time.elapsed());
+            return if request.stream {
+                sse_response(stream::iter(completion_chunks(&response)))
+            } else {
+                Json(response).into_response()
+            };
+        }
+    }
+
     // Handle streaming requests
     if request.stream {
-        match handle_chat_completions_streaming(server, request).await {
-            Ok(response) => return response,
-            Err(error_resp) => return error_resp,
+        let local_only = request.local_only.unwrap_or(false);
+        if local_only || streams_locally(&server, &request).await {
+            return match handle_chat_completions_streaming(server, request, !local_only, start_time).await {
+                Ok(response) => response,
+                Err(error_resp) => error_resp,
+            };
         }
+
+        // Teacher and tool-call responses are not streamed incrementally: the
+        // whole response is fetched (and recorded) first, then replayed as chunks
+        return match complete_chat(server, request, start_time).await {
+            Ok(response) => sse_response(stream::iter(completion_chunks(&response))),
+            Err(error_resp) => error_resp,
+        };
+    }
+
+    match complete_chat(server, request, start_time).await {
+        Ok(response) => Json(response).into_response(),
+        Err(error_resp) => error_resp,
     }
+}
+
+/// Answer a non-streaming chat completion (local-only, or routed)
+async fn complete_chat(
+    server: Arc<AgentServer>,
+    request: ChatCompletionRequest,
+    start_time: Instant,
+) -> Result<ChatCompletionResponse, Response> {
 
     // Check if local-only mode requested
     if request.local_only.unwrap_or(false) {
-        match handle_local_only_query(server, request).await {
-            Ok(json_resp) => return json_resp.into_response(),
-            Err(error_resp) => return error_resp,
-        }
+        return handle_local_only_query(server.clone(), request).await.map(|Json(mut response)| {
+            let elapsed = start_time.elapsed();
+            server.query_metrics().record_query("local", elapsed);
+            response.latency_ms = Some(elapsed.as_millis() as u64);
+            response
+        });
     }
 
     // Convert OpenAI messages to internal format (now handles tool calls/results)
     let internal_messages = match convert_messages_to_internal(&request.messages) {
         Ok(messages) => messages,
-        Err(e) => return error_response(&e.to_string(), "invalid_request_error"),
+        Err(e) => return Err(error_response(&e.to_string(), "invalid_request_error")),
     };
 
     // Convert OpenAI tools to internal format
@@ -414,31 +600,58 @@ pub async fn handle_chat_completions(
         .and_then(|m| m.content.as_deref())
         .unwrap_or("");
 
-    // Route decision
+    // Client-supplied temperature wins; otherwise use the query category's
+    let temperature = request
+        .temperature
+        .or_else(|| server.temperature_config().for_query(user_query));
+
+    // Route decision (forward_only skips the router but still traces the decision)
     let router = server.router().read().await;
-    let decision = router.route(user_query);
+    let decision = if request.forward_only.unwrap_or(false) {
+        router.route_forced_forward(user_query)
+    } else {
+        router.route(user_query)
+    };
     drop(router);
 
-    let (content_blocks, routing_decision) = match decision {
+    // Teacher circuit open: try the local model rather than failing fast
+    // (user-forced forwards still go to the teacher and get the clear error)
+    let decision = match decision {
+        RouteDecision::Forward { reason }
+            if !matches!(
+                reason,
+                crate::router::ForwardReason::UserForced | crate::router::ForwardReason::Crisis
+            ) && server.claude_client().is_circuit_open() =>
+        {
+            info!("Teacher circuit open, routing to local model instead");
+            RouteDecision::Local {
+                pattern_id: "teacher_circuit_open".to_string(),
+                confidence: 0.0,
+            }
+        }
+        decision => decision,
+    };
+
+    let (content_blocks, routing_decision, model) = match decision {
         RouteDecision::Forward { reason } => {
             info!("☁️  ROUTING TO TEACHER API (reason: {:?})", reason);
 
             // Forward to Claude with tools
             let mut claude_request = crate::claude::MessageRequest::with_context(internal_messages.clone());
+            claude_request.temperature = temperature;
+            if let Some(model) = &request.teacher_model {
+                claude_request.model = model.clone();
+            }
             if let Some(tools) = internal_tools.clone() {
                 claude_request = claude_request.with_tools(tools);
             }
 
-            let response = match server
-                .claude_client()
-                .send_message(&claude_request)
-                .await
-            {
+            let response = match server.send_to_teacher(&claude_request).await {
                 Ok(resp) => resp,
-                Err(e) => return error_response(&e.to_string(), "api_error"),
+                Err(e) => return Err(teacher_error(e)),
             };
 
-            (response.content, "forward")
+            (response.content, "forward", response.model)
         }
         RouteDecision::Local { .. } => {
             info!("🤖 ROUTING TO LOCAL MODEL");
@@ -448,59 +661,77 @@ pub async fn handle_chat_completions(
             let state = server.generator_state().read().await;
 
             match &*state {
-                GeneratorState::Ready { .. } => {
+                GeneratorState::Ready { model_name, .. } => {
+                    let model_name = model_name.clone();
                     drop(state);
 
                     // Try local generation with tools
+                    let local_slot = match server.inference_limits().acquire(InferenceKind::Local).await {
+                        Ok(slot) => slot,
+                        Err(busy) => return Err(busy.into_response()),
+                    };
                     let mut generator = server.local_generator().write().await;
-                    match generator.try_generate_from_pattern_with_tools(&internal_messages, internal_tools.clone()) {
+                    match generator.try_generate_from_pattern_with_tools(&internal_messages, internal_tools.clone(), request.max_response_chars) {
                         Ok(Some(response)) => {
                             info!("✓ LOCAL MODEL RESPONDED");
-                            (response.content_blocks, "local")
+                            (response.content_blocks, "local", model_name)
                         }
                         Ok(None) => {
                             // Fall back to teacher
                             drop(generator);
+                            drop(local_slot);
                             warn!("❌ Local generation returned None, falling back to teacher");
 
                             let mut claude_request =
                                 crate::claude::MessageRequest::with_context(internal_messages.clone());
+                            claude_request.temperature = temperature;
+                            if let Some(model) = &request.teacher_model {
+                                claude_request.model = model.clone();
+                            }
                             if let Some(tools) = internal_tools.clone() {
                                 claude_request = claude_request.with_tools(tools);
                             }
 
-                            let response = match server
-                                .claude_client()
-                                .send_message(&claude_request)
-                                .await
-                            {
+                            let response = match server.send_to_teacher(&claude_request).await {
                                 Ok(resp) => resp,
-                                Err(e) => return error_response(&e.to_string(), "api_error"),
+                                Err(e) => return Err(teacher_error(e)),
                             };
 
-                            (response.content, "fallback")
+                            (response.content, "fallback", response.model)
                         }
                         Err(e) => {
                             // Fall back to teacher
                             drop(generator);
+                            drop(local_slot);
                             warn!("❌ Local generation error: {}, falling back to teacher", e);
+                            let timeout = e.downcast_ref::<crate::local::LocalTimeout>().copied();
+                            if let Some(timeout) = timeout {
+                                server.router().read().await.route_local_timeout(user_query, timeout.after);
+                            }
 
                             let mut claude_request =
                                 crate::claude::MessageRequest::with_context(internal_messages.clone());
+                            claude_request.temperature = temperature;
+                            if let Some(model) = &request.teacher_model {
+                                claude_request.model = model.clone();
+                            }
                             if let Some(tools) = internal_tools.clone() {
                                 claude_request = claude_request.with_tools(tools);
                             }
 
-                            let response = match server
-                                .claude_client()
-                                .send_message(&claude_request)
-                                .await
-                            {
+                            let response = match server.send_to_teacher(&claude_request).await {
                                 Ok(resp) => resp,
-                                Err(e) => return error_response(&e.to_string(), "api_error"),
+                                Err(e) => return Err(teacher_error(e)),
                             };
 
-                            (response.content, "fallback")
+                            (
+                                response.content,
+                                match timeout {
+                                    Some(_) => crate::router::ForwardReason::LocalTimeout.as_str(),
+                                    None => "fallback",
+                                },
+                                response.model,
+                            )
                         }
                     }
                 }
@@ -511,26 +742,51 @@ pub async fn handle_chat_completions(
 
                     let mut claude_request =
                         crate::claude::MessageRequest::with_context(internal_messages.clone());
+                    claude_request.temperature = temperature;
+                    if let Some(model) = &request.teacher_model {
+                        claude_request.model = model.clone();
+                    }
                     if let Some(tools) = internal_tools {
                         claude_request = claude_request.with_tools(tools);
                     }
 
-                    let response = match server
-                        .claude_client()
-                        .send_message(&claude_request)
-                        .await
-                    {
+                    let response = match server.send_to_teacher(&claude_request).await {
                         Ok(resp) => resp,
-                        Err(e) => return error_response(&e.to_string(), "api_error"),
+                        Err(e) => return Err(teacher_error(e)),
                     };
 
-                    (response.content, "forward")
+                    (response.content, "forward", response.model)
                 }
             }
         }
     };
 
     let elapsed = start_time.elapsed();
+    record_chat_result(&server, routing_decision, elapsed, user_query, &content_blocks);
+
+    // Convert internal response to OpenAI format (handles tool_calls)
+    let mut response = convert_response_to_openai(content_blocks, &model)?;
+    let route = if routing_decision == "local" { "local" } else { "teacher" };
+    response.route = Some(route.to_string());
+    response.latency_ms = Some(elapsed.as_millis() as u64);
+    Ok(response)
+}
+
+/// Record a routed chat result: query/tool metrics, plus the query and
+/// response text as a training example (skipped for tool calls)
+fn record_chat_result(
+    server: &AgentServer,
+    routing_decision: &str,
+    elapsed: std::time::Duration,
+    user_query: &str,
+    content_blocks: &[ContentBlock],
+) {
+    server.query_metrics().record_query(routing_decision, elapsed);
+    for block in content_blocks {
+        if let ContentBlock::ToolUse { name, .. } = block {
+            server.query_metrics().record_tool(name);
+        }
+    }
     info!(
         routing = routing_decision,
         elapsed_ms = elapsed.as_millis(),
@@ -538,8 +794,8 @@ pub async fn handle_chat_completions(
     );
 
     // Automatically collect query/response for training (if not a tool call)
-    if !has_tool_calls(&content_blocks) {
-        let response_text = extract_text_from_blocks(&content_blocks);
+    if !has_tool_calls(content_blocks) {
+        let response_text = extract_text_from_blocks(content_blocks);
         if !user_query.is_empty() && !response_text.is_empty() {
             // Send to training queue (non-blocking)
             let training_tx = server.training_tx();
@@ -557,14 +813,6 @@ pub async fn handle_chat_completions(
             }
         }
     }
-
-    // Convert internal response to OpenAI format (handles tool_calls)
-    let openai_response = match convert_response_to_openai(content_blocks, &request.model) {
-        Ok(resp) => resp,
-        Err(error_resp) => return error_resp,
-    };
-
-    Json(openai_response).into_response()
 }
 
 /// Handle local-only query (bypass routing, direct local model access)
@@ -579,9 +827,10 @@ async fn handle_local_only_query(
     // Check generator state
     let state = server.generator_state().read().await;
 
-    match &*state {
-        GeneratorState::Ready { .. } => {
+    let model_name = match &*state {
+        GeneratorState::Ready { model_name, .. } => {
             // Model ready, proceed (state will be dropped at end of scope)
+            model_name.clone()
         }
         GeneratorState::Initializing | GeneratorState::Downloading { .. } | GeneratorState::Loading { .. } => {
             warn!("Local model not ready: {:?}", &*state);
@@ -615,7 +864,7 @@ async fn handle_local_only_query(
             )
                 .into_response());
         }
-    }
+    };
     // State dropped here automatically
 
     // Extract query from messages
@@ -623,11 +872,16 @@ async fn handle_local_only_query(
         .map_err(|e| error_response(&e.to_string(), "invalid_request_error"))?;
 
     // Generate response (no tools for now - direct generation only)
+    let _local_slot = server
+        .inference_limits()
+        .acquire(InferenceKind::Local)
+        .await
+        .map_err(IntoResponse::into_response)?;
     info!("Acquiring write lock on generator...");
     let mut generator = server.local_generator().write().await;
     info!("Write lock acquired, starting generation...");
 
-    let content_blocks = match generator.try_generate_from_pattern_with_tools(&internal_messages, None) {
+    let content_blocks = match generator.try_generate_from_pattern_with_tools(&internal_messages, None, request.max_response_chars) {
         Ok(Some(response)) => {
             info!("Generation successful, {} content blocks", response.content_blocks.len());
             response.content_blocks
@@ -660,7 +914,8 @@ async fn handle_local_only_query(
 
     // Convert response to OpenAI format
     info!("Converting response to OpenAI format...");
-    let openai_response = convert_response_to_openai(content_blocks, &request.model)?;
+    let mut openai_response = convert_response_to_openai(content_blocks, &model_name)?;
+    openai_response.route = Some("local".to_string());
     info!("Response converted, sending back to client");
 
     Ok(Json(openai_response))
@@ -755,10 +1010,16 @@ fn convert_response_to_openai(
 
                 finish_reason = "tool_calls";
             }
+            ContentBlock::Image { .. } => {
+                // Teachers don't answer with images
+            }
             ContentBlock::ToolResult { .. } => {
                 // Tool results shouldn't appear in assistant responses
                 // They're in user messages
             }
+            ContentBlock::Thinking { .. } | ContentBlock::RedactedThinking { .. } => {
+                // Chat completions have no field for reasoning
+            }
         }
     }
 
@@ -783,6 +1044,8 @@ fn convert_response_to_openai(
             completion_tokens: 0,
             total_tokens: 0,
         },
+        route: None,
+        latency_ms: None,
     };
 
     Ok(response)
@@ -875,6 +1138,14 @@ fn error_response(message: &str, error_type: &str) -> Response {
     (StatusCode::BAD_REQUEST, Json(error)).into_response()
 }
 
+/// Error response for a failed teacher call (429 if no teacher slot freed up)
+fn teacher_error(error: anyhow::Error) -> Response {
+    match error.downcast_ref::<ServerBusy>() {
+        Some(busy) => busy.into_response(),
+        None => error_response(&error.to_string(), "api_error"),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -892,6 +1163,62 @@ mod tests {
         assert_eq!(internal[1].role, "user");
     }
 
+    #[test]
+    fn test_completion_chunks_replay_response() {
+        let blocks = vec![
+            ContentBlock::Text { text: "Checking".to_string() },
+            ContentBlock::ToolUse {
+                id: "call_1".to_string(),
+                name: "read".to_string(),
+                input: serde_json::json!({ "file_path": "a.rs" }),
+            },
+        ];
+        let response = convert_response_to_openai(blocks, "qwen-local").unwrap();
+        let chunks = completion_chunks(&response);
+
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks.iter().all(|c| c["id"] == response.id.as_str()));
+        assert_eq!(chunks[0]["object"], "chat.completion.chunk");
+        let delta = &chunks[0]["choices"][0]["delta"];
+        assert_eq!(delta["role"], "assistant");
+        assert_eq!(delta["content"], "Checking");
+        assert_eq!(delta["tool_calls"][0]["index"], 0);
+        assert_eq!(delta["tool_calls"][0]["function"]["name"], "read");
+        assert!(chunks[0]["choices"][0]["finish_reason"].is_null());
+        assert_eq!(chunks[1]["choices"][0]["finish_reason"], "tool_calls");
+    }
+
+    #[test]
+    fn test_route_fields_are_optional() {
+        let blocks = vec![ContentBlock::Text { text: "4".to_string() }];
+        let mut response = convert_response_to_openai(blocks, "qwen-local").unwrap();
+        let json = serde_json::to_value(&response).unwrap();
+        assert!(json.get("route").is_none() && json.get("latency_ms").is_none());
+
+        // Responses from an older daemon still parse
+        let parsed: ChatCompletionResponse = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.route, None);
+
+        response.route = Some("local".to_string());
+        response.latency_ms = Some(42);
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["route"], "local");
+        assert_eq!(json["latency_ms"], 42);
+    }
+
+    #[tokio::test]
+    async fn test_sse_response_ends_with_done() {
+        let chunk = completion_chunk("chatcmpl-1", 0, "qwen-local", serde_json::json!({ "content": "hi" }), None);
+        let response = sse_response(stream::iter(vec![chunk]));
+        assert_eq!(response.headers()["content-type"], "text/event-stream");
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.starts_with("data: {"));
+        assert!(body.contains("\"content\":\"hi\""));
+        assert!(body.ends_with("data: [DONE]\n\n"));
+    }
+
     #[test]
     fn test_token_buffer_basic() {
         let mut buffer = TokenBuffer::new();
@@ -24,7 +24,7 @@ pub struct ChatCompletionRequest {
     /// Number of completions to generate
     #[serde(skip_serializing_if = "Option::is_none")]
     pub n: Option<u32>,
-    /// Whether to stream responses (not yet supported)
+    /// Stream the response as `chat.completion.chunk` SSE frames
     #[serde(default)]
     pub stream: bool,
     /// Stop sequences
@@ -36,6 +36,15 @@ pub struct ChatCompletionRequest {
     /// Bypass routing and query local model directly (for testing)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub local_only: Option<bool>,
+    /// Bypass routing and forward directly to the teacher (user-forced)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forward_only: Option<bool>,
+    /// Teacher model for this request, overriding the configured one (`--model`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub teacher_model: Option<String>,
+    /// Soft response length limit for local generation (overrides config)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_response_chars: Option<usize>,
 }
 
 /// Chat message in OpenAI format
@@ -115,6 +124,12 @@ pub struct ChatCompletionResponse {
     pub choices: Vec<Choice>,
     /// Usage statistics
     pub usage: Usage,
+    /// Where the answer came from: "local" or "teacher" (shammah extension)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub route: Option<String>,
+    /// Server-side handling time in milliseconds (shammah extension)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
 }
 
 /// Completion choice
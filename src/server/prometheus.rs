@@ -0,0 +1,204 @@
+// Prometheus metrics for the daemon (`GET /metrics/prometheus`)
+//
+// Query and tool counters plus a latency histogram, kept in memory since the
+// daemon started (the JSONL metrics log rolls over daily, which would make
+// the counters go backwards). The local hit ratio comes from the router's
+// own stats, so it covers everything the router has learned from.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::models::ThresholdRouterStats;
+
+/// Upper bounds (seconds) of the query latency histogram buckets
+const LATENCY_BUCKETS: [f64; 10] = [0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0];
+
+#[derive(Debug, Default)]
+struct Counters {
+    /// Queries answered, by route ("local" or "teacher")
+    queries: BTreeMap<String, u64>,
+    /// Tool calls handed back to clients, by tool name
+    tool_calls: BTreeMap<String, u64>,
+    /// Per-bucket latency counts (not cumulative), plus one for +Inf
+    latency_buckets: [u64; LATENCY_BUCKETS.len() + 1],
+    latency_sum: f64,
+    latency_count: u64,
+}
+
+/// Counters behind the Prometheus endpoint
+#[derive(Debug, Default)]
+pub struct QueryMetrics {
+    counters: Mutex<Counters>,
+}
+
+impl QueryMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an answered query
+    ///
+    /// `route` is the routing decision; anything but "local" (forwards and
+    /// every kind of fallback) counts as "teacher".
+    pub fn record_query(&self, route: &str, latency: Duration) {
+        let route = if route == "local" { "local" } else { "teacher" };
+        let seconds = latency.as_secs_f64();
+        let bucket = LATENCY_BUCKETS
+            .iter()
+            .position(|bound| seconds <= *bound)
+            .unwrap_or(LATENCY_BUCKETS.len());
+
+        let mut counters = self.counters.lock().unwrap_or_else(|p| p.into_inner());
+        *counters.queries.entry(route.to_string()).or_default() += 1;
+        counters.latency_buckets[bucket] += 1;
+        counters.latency_sum += seconds;
+        counters.latency_count += 1;
+    }
+
+    /// Record a tool call in a response
+    pub fn record_tool(&self, tool: &str) {
+        let mut counters = self.counters.lock().unwrap_or_else(|p| p.into_inner());
+        *counters.tool_calls.entry(tool.to_string()).or_default() += 1;
+    }
+
+    /// Text exposition format, with the hit ratio from `router_stats`
+    pub fn render(&self, router_stats: &ThresholdRouterStats) -> String {
+        let counters = self.counters.lock().unwrap_or_else(|p| p.into_inner());
+        let mut out = String::new();
+
+        metric_header(
+            &mut out,
+            "shammah_queries_total",
+            "counter",
+            "Queries answered, by route",
+        );
+        for route in ["local", "teacher"] {
+            let count = counters.queries.get(route).copied().unwrap_or(0);
+            let _ = writeln!(
+                out,
+                "shammah_queries_total{{route=\"{}\"}} {}",
+                route, count
+            );
+        }
+
+        metric_header(
+            &mut out,
+            "shammah_tool_executions_total",
+            "counter",
+            "Tool calls returned to clients, by tool",
+        );
+        for (tool, count) in &counters.tool_calls {
+            let _ = writeln!(
+                out,
+                "shammah_tool_executions_total{{tool=\"{}\"}} {}",
+                escape_label(tool),
+                count
+            );
+        }
+
+        metric_header(
+            &mut out,
+            "shammah_local_hit_ratio",
+            "gauge",
+            "Share of routed queries the router sent to the local model",
+        );
+        let _ = writeln!(
+            out,
+            "shammah_local_hit_ratio {}",
+            1.0 - router_stats.forward_rate
+        );
+
+        metric_header(
+            &mut out,
+            "shammah_query_duration_seconds",
+            "histogram",
+            "Time to answer a query",
+        );
+        let mut cumulative = 0;
+        for (bound, count) in LATENCY_BUCKETS.iter().zip(&counters.latency_buckets) {
+            cumulative += count;
+            let _ = writeln!(
+                out,
+                "shammah_query_duration_seconds_bucket{{le=\"{}\"}} {}",
+                bound, cumulative
+            );
+        }
+        let _ = writeln!(
+            out,
+            "shammah_query_duration_seconds_bucket{{le=\"+Inf\"}} {}",
+            counters.latency_count
+        );
+        let _ = writeln!(
+            out,
+            "shammah_query_duration_seconds_sum {}",
+            counters.latency_sum
+        );
+        let _ = writeln!(
+            out,
+            "shammah_query_duration_seconds_count {}",
+            counters.latency_count
+        );
+
+        out
+    }
+}
+
+fn metric_header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+/// Escape a label value (backslash, quote, newline)
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn router_stats(forward_rate: f64) -> ThresholdRouterStats {
+        ThresholdRouterStats {
+            total_queries: 4,
+            total_local_attempts: 1,
+            total_successes: 1,
+            forward_rate,
+            success_rate: 1.0,
+            confidence_threshold: 0.95,
+            min_samples: 2,
+            categories: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let metrics = QueryMetrics::new();
+        metrics.record_query("local", Duration::from_millis(80));
+        metrics.record_query("fallback", Duration::from_millis(1500));
+        metrics.record_query("forward", Duration::from_secs(300));
+        metrics.record_tool("read");
+        metrics.record_tool("read");
+
+        let text = metrics.render(&router_stats(0.75));
+        assert!(text.contains("shammah_queries_total{route=\"local\"} 1\n"));
+        assert!(text.contains("shammah_queries_total{route=\"teacher\"} 2\n"));
+        assert!(text.contains("shammah_tool_executions_total{tool=\"read\"} 2\n"));
+        assert!(text.contains("shammah_local_hit_ratio 0.25\n"));
+        assert!(text.contains("shammah_query_duration_seconds_bucket{le=\"0.1\"} 1\n"));
+        assert!(text.contains("shammah_query_duration_seconds_bucket{le=\"2.5\"} 2\n"));
+        assert!(text.contains("shammah_query_duration_seconds_bucket{le=\"120\"} 2\n"));
+        assert!(text.contains("shammah_query_duration_seconds_bucket{le=\"+Inf\"} 3\n"));
+        assert!(text.contains("shammah_query_duration_seconds_count 3\n"));
+        assert!(text.contains("# TYPE shammah_query_duration_seconds histogram\n"));
+    }
+
+    #[test]
+    fn test_escape_label() {
+        assert_eq!(escape_label("mcp \"x\"\\y"), "mcp \\\"x\\\"\\\\y");
+    }
+}
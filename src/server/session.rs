@@ -1,5 +1,6 @@
 // Session management for concurrent HTTP clients
 
+use super::usage::SessionUsage;
 use crate::cli::ConversationHistory;
 use chrono::{DateTime, Utc};
 use dashmap::DashMap;
@@ -19,6 +20,8 @@ pub struct SessionState {
     pub last_activity: DateTime<Utc>,
     /// Session creation time
     pub created_at: DateTime<Utc>,
+    /// Teacher tokens and estimated cost so far
+    pub usage: SessionUsage,
 }
 
 impl SessionState {
@@ -29,6 +32,7 @@ impl SessionState {
             conversation: ConversationHistory::new(),
             last_activity: Utc::now(),
             created_at: Utc::now(),
+            usage: SessionUsage::default(),
         }
     }
 
@@ -105,6 +109,11 @@ impl SessionManager {
         Ok(session)
     }
 
+    /// Get an existing session without touching or creating it
+    pub fn get(&self, session_id: &str) -> Option<SessionState> {
+        self.sessions.get(session_id).map(|session| session.clone())
+    }
+
     /// Update session state
     pub fn update(&self, session_id: &str, session: SessionState) -> anyhow::Result<()> {
         if let Some(mut entry) = self.sessions.get_mut(session_id) {
@@ -1,16 +1,135 @@
 // Background training worker for daemon
 //
 // Collects weighted examples via mpsc channel and triggers LoRA training
-// when batch threshold is reached or timeout occurs.
+// when batch threshold is reached or timeout occurs. Training can be
+// paused, resumed, cancelled, or reconfigured through a `TrainingHandle`.
+//
+// Each run trains on its own snapshot of the queue file, so up to
+// `max_concurrent_training` runs can proceed without sharing inputs.
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::mpsc;
-use tracing::{debug, error, info};
+use tokio::sync::{mpsc, oneshot};
+use tracing::{debug, error, info, warn};
 
+use crate::config::TrainingConfig;
 use crate::models::{TrainingCoordinator, WeightedExample};
-use crate::training::lora_subprocess::LoRATrainingSubprocess;
+use crate::training::lora_subprocess::{LoRATrainingSubprocess, TrainingJob, TrainingOutcome};
+
+/// Current state of the background training worker
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrainingState {
+    /// Waiting for examples
+    Idle,
+    /// A LoRA training subprocess is running
+    Training,
+    /// New batches are held back until resumed (examples are still buffered)
+    Paused,
+}
+
+impl std::fmt::Display for TrainingState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TrainingState::Idle => write!(f, "idle"),
+            TrainingState::Training => write!(f, "training"),
+            TrainingState::Paused => write!(f, "paused"),
+        }
+    }
+}
+
+/// Control actions accepted by the training worker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrainingAction {
+    /// Stop starting new batches
+    Pause,
+    /// Resume starting batches (immediately if threshold already reached)
+    Resume,
+    /// Terminate the running training subprocess
+    Cancel,
+}
+
+impl TrainingAction {
+    /// Action name as used in the `/v1/training/<action>` endpoints
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TrainingAction::Pause => "pause",
+            TrainingAction::Resume => "resume",
+            TrainingAction::Cancel => "cancel",
+        }
+    }
+}
+
+enum TrainingCommand {
+    Action {
+        action: TrainingAction,
+        reply: oneshot::Sender<TrainingState>,
+    },
+    /// Apply new batch settings (from a config reload)
+    Reconfigure(TrainingConfig),
+}
+
+/// Cloneable handle for controlling and observing the training worker
+#[derive(Clone)]
+pub struct TrainingHandle {
+    command_tx: mpsc::UnboundedSender<TrainingCommand>,
+    state: Arc<std::sync::RwLock<TrainingState>>,
+}
+
+impl TrainingHandle {
+    fn new() -> (Self, mpsc::UnboundedReceiver<TrainingCommand>) {
+        let (command_tx, command_rx) = mpsc::unbounded_channel();
+        let handle = Self {
+            command_tx,
+            state: Arc::new(std::sync::RwLock::new(TrainingState::Idle)),
+        };
+        (handle, command_rx)
+    }
+
+    /// Create a handle that is not connected to any worker
+    ///
+    /// Used as a placeholder until the worker is spawned; actions fail.
+    pub fn disconnected() -> Self {
+        Self::new().0
+    }
+
+    /// Get the current training state
+    pub fn state(&self) -> TrainingState {
+        self.state
+            .read()
+            .map(|state| *state)
+            .unwrap_or(TrainingState::Idle)
+    }
+
+    /// Send a control action and wait for the resulting state
+    pub async fn send(&self, action: TrainingAction) -> Result<TrainingState> {
+        let (reply, reply_rx) = oneshot::channel();
+        self.command_tx
+            .send(TrainingCommand::Action { action, reply })
+            .map_err(|_| anyhow::anyhow!("Training worker is not running"))?;
+        reply_rx.await.context("Training worker dropped control request")
+    }
+
+    /// Apply new training settings to the running worker
+    ///
+    /// Invalid settings are rejected and the current ones stay in effect.
+    pub fn reconfigure(&self, config: TrainingConfig) -> Result<()> {
+        config.validate()?;
+        self.command_tx
+            .send(TrainingCommand::Reconfigure(config))
+            .map_err(|_| anyhow::anyhow!("Training worker is not running"))
+    }
+
+    fn set_state(&self, state: TrainingState) {
+        if let Ok(mut current) = self.state.write() {
+            *current = state;
+        }
+    }
+}
 
 /// Training worker state
 pub struct TrainingWorker {
@@ -24,6 +143,22 @@ pub struct TrainingWorker {
     batch_threshold: usize,
     /// Timeout duration (trigger training after duration if batch not full)
     batch_timeout: Duration,
+    /// Maximum training subprocesses running at once
+    max_concurrent: usize,
+    /// Control channel for pause/resume/cancel
+    command_rx: mpsc::UnboundedReceiver<TrainingCommand>,
+    /// Shared handle (state is published here)
+    handle: TrainingHandle,
+    /// Whether new batches are held back
+    paused: bool,
+    /// Currently running training subprocesses
+    jobs: Vec<RunningJob>,
+}
+
+/// A training subprocess and the queue snapshot it trains on
+struct RunningJob {
+    job: TrainingJob,
+    queue_path: PathBuf,
 }
 
 impl TrainingWorker {
@@ -31,20 +166,30 @@ impl TrainingWorker {
     pub fn new(
         example_rx: mpsc::UnboundedReceiver<WeightedExample>,
         coordinator: Arc<TrainingCoordinator>,
-        batch_threshold: usize,
-        batch_timeout_minutes: u64,
+        config: &TrainingConfig,
     ) -> Self {
         let subprocess = LoRATrainingSubprocess::with_defaults();
+        let (handle, command_rx) = TrainingHandle::new();
 
         Self {
             example_rx,
             coordinator,
             subprocess,
-            batch_threshold,
-            batch_timeout: Duration::from_secs(batch_timeout_minutes * 60),
+            batch_threshold: config.batch_threshold.max(1),
+            batch_timeout: Duration::from_secs(config.batch_timeout_minutes.max(1) * 60),
+            max_concurrent: config.max_concurrent_training.max(1),
+            command_rx,
+            handle,
+            paused: false,
+            jobs: Vec::new(),
         }
     }
 
+    /// Get a handle for controlling this worker
+    pub fn handle(&self) -> TrainingHandle {
+        self.handle.clone()
+    }
+
     /// Run the training worker loop
     ///
     /// This runs indefinitely, accumulating examples and triggering training
@@ -53,12 +198,12 @@ impl TrainingWorker {
         info!(
             batch_threshold = self.batch_threshold,
             timeout_minutes = self.batch_timeout.as_secs() / 60,
+            max_concurrent = self.max_concurrent,
             "Training worker started"
         );
 
         let mut batch = Vec::new();
-        let mut flush_interval = tokio::time::interval(self.batch_timeout);
-        flush_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let mut flush_interval = new_flush_interval(self.batch_timeout);
 
         loop {
             tokio::select! {
@@ -74,20 +219,47 @@ impl TrainingWorker {
 
                     // Check if batch threshold reached
                     if batch.len() >= self.batch_threshold {
-                        info!(count = batch.len(), "Batch threshold reached, triggering training");
-                        if let Err(e) = self.process_batch(&mut batch).await {
-                            error!(error = %e, "Failed to process training batch");
+                        self.start_training(&mut batch, "Batch threshold reached").await;
+                    }
+                }
+
+                // Pause/resume/cancel/reconfigure requests
+                Some(command) = self.command_rx.recv() => match command {
+                    TrainingCommand::Action { action, reply } => {
+                        self.handle_command(action, &mut batch).await;
+                        let _ = reply.send(self.current_state());
+                    }
+                    TrainingCommand::Reconfigure(config) => {
+                        if self.apply_config(&config) {
+                            flush_interval = new_flush_interval(self.batch_timeout);
+                            // The first tick fires immediately; don't flush early
+                            flush_interval.reset();
+                        }
+                        if batch.len() >= self.batch_threshold {
+                            self.start_training(&mut batch, "Batch threshold reached after reconfigure").await;
                         }
                     }
+                },
+
+                // A running subprocess finished
+                (index, outcome) = wait_for_any_job(&mut self.jobs) => {
+                    let finished = self.jobs.remove(index);
+                    info!(?outcome, "Training run finished");
+                    if outcome != TrainingOutcome::Completed {
+                        self.requeue(&finished.queue_path);
+                    }
+                    self.publish_state();
+
+                    // Examples may have piled up while every slot was busy
+                    if batch.len() >= self.batch_threshold {
+                        self.start_training(&mut batch, "Batch threshold reached while training").await;
+                    }
                 }
 
                 // Periodic flush (timeout)
                 _ = flush_interval.tick() => {
                     if !batch.is_empty() {
-                        info!(count = batch.len(), "Batch timeout reached, triggering training");
-                        if let Err(e) = self.process_batch(&mut batch).await {
-                            error!(error = %e, "Failed to process training batch");
-                        }
+                        self.start_training(&mut batch, "Batch timeout reached").await;
                     } else {
                         debug!("Flush interval tick, but batch is empty");
                     }
@@ -96,8 +268,107 @@ impl TrainingWorker {
         }
     }
 
+    /// Start training on the batch unless paused or all training slots are busy
+    ///
+    /// Held-back examples stay in `batch` and the coordinator buffer.
+    async fn start_training(&mut self, batch: &mut Vec<WeightedExample>, reason: &str) {
+        if self.paused {
+            debug!(count = batch.len(), "{}, but training is paused", reason);
+            return;
+        }
+        if self.jobs.len() >= self.max_concurrent {
+            debug!(
+                count = batch.len(),
+                running = self.jobs.len(),
+                "{}, but the maximum number of training runs is active",
+                reason
+            );
+            return;
+        }
+
+        info!(count = batch.len(), "{}, triggering training", reason);
+        match self.process_batch(batch).await {
+            Ok(job) => self.jobs.push(job),
+            Err(e) => error!(error = %e, "Failed to process training batch"),
+        }
+        self.publish_state();
+    }
+
+    /// Apply new batch settings; returns whether the timeout changed
+    fn apply_config(&mut self, config: &TrainingConfig) -> bool {
+        if let Err(e) = config.validate() {
+            warn!(error = %e, "Ignoring invalid training settings");
+            return false;
+        }
+
+        let timeout = Duration::from_secs(config.batch_timeout_minutes * 60);
+        let timeout_changed = timeout != self.batch_timeout;
+
+        self.batch_threshold = config.batch_threshold;
+        self.batch_timeout = timeout;
+        self.max_concurrent = config.max_concurrent_training;
+        info!(
+            batch_threshold = self.batch_threshold,
+            timeout_minutes = config.batch_timeout_minutes,
+            max_concurrent = self.max_concurrent,
+            "Training settings updated"
+        );
+
+        timeout_changed
+    }
+
+    /// Apply a control action
+    async fn handle_command(&mut self, action: TrainingAction, batch: &mut Vec<WeightedExample>) {
+        match action {
+            TrainingAction::Pause => {
+                info!(buffered = batch.len(), "Training paused");
+                self.paused = true;
+            }
+            TrainingAction::Resume => {
+                info!(buffered = batch.len(), "Training resumed");
+                self.paused = false;
+                if batch.len() >= self.batch_threshold {
+                    self.start_training(batch, "Batch threshold reached while paused").await;
+                }
+            }
+            TrainingAction::Cancel => {
+                if self.jobs.is_empty() {
+                    warn!("Cancel requested, but no training run is active");
+                }
+                for mut running in std::mem::take(&mut self.jobs) {
+                    info!("Cancelling training run");
+                    running.job.cancel();
+                    let outcome = running.job.wait().await;
+                    info!(?outcome, "Training run cancelled");
+                    if outcome != TrainingOutcome::Completed {
+                        self.requeue(&running.queue_path);
+                    }
+                }
+            }
+        }
+        self.publish_state();
+    }
+
+    fn current_state(&self) -> TrainingState {
+        if !self.jobs.is_empty() {
+            TrainingState::Training
+        } else if self.paused {
+            TrainingState::Paused
+        } else {
+            TrainingState::Idle
+        }
+    }
+
+    fn publish_state(&self) {
+        self.handle.set_state(self.current_state());
+    }
+
     /// Process accumulated batch of examples
-    async fn process_batch(&self, batch: &mut Vec<WeightedExample>) -> Result<()> {
+    ///
+    /// The queue is moved to a per-run snapshot that is only archived after a
+    /// successful run; a cancelled or failed run's examples are moved back
+    /// into the queue and picked up again by the next batch.
+    async fn process_batch(&self, batch: &mut Vec<WeightedExample>) -> Result<RunningJob> {
         info!(count = batch.len(), "Processing training batch");
 
         // Write to JSONL queue
@@ -112,8 +383,13 @@ impl TrainingWorker {
             .clear_buffer()
             .map_err(|e| anyhow::anyhow!("Failed to clear coordinator buffer: {}", e))?;
 
+        // Snapshot the queue for this run so new examples (and other runs)
+        // don't touch the file while it is being trained on
+        let queue_path = run_queue_path(self.coordinator.queue_path());
+        std::fs::rename(self.coordinator.queue_path(), &queue_path)
+            .with_context(|| format!("Failed to snapshot training queue to {}", queue_path.display()))?;
+
         // Trigger Python training subprocess (non-blocking)
-        let queue_path = self.coordinator.queue_path().to_path_buf();
         let adapter_path = self.get_adapter_path();
 
         info!(
@@ -122,17 +398,36 @@ impl TrainingWorker {
             "Starting LoRA training subprocess"
         );
 
-        self.subprocess
-            .train_async(&queue_path, &adapter_path)
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to start training subprocess: {}", e))?;
+        let job = match self.subprocess.train_async(&queue_path, &adapter_path).await {
+            Ok(job) => job,
+            Err(e) => {
+                self.requeue(&queue_path);
+                anyhow::bail!("Failed to start training subprocess: {}", e);
+            }
+        };
 
         info!("Training subprocess started successfully");
 
         // Clear batch
         batch.clear();
 
-        Ok(())
+        Ok(RunningJob { job, queue_path })
+    }
+
+    /// Move a run's queue snapshot back into the main queue for retraining
+    fn requeue(&self, run_queue: &Path) {
+        if !run_queue.exists() {
+            return;
+        }
+        if let Err(e) = append_file(run_queue, self.coordinator.queue_path())
+            .and_then(|()| std::fs::remove_file(run_queue).map_err(Into::into))
+        {
+            error!(
+                error = %e,
+                queue = %run_queue.display(),
+                "Failed to return training examples to the queue"
+            );
+        }
     }
 
     /// Get adapter output path
@@ -144,6 +439,46 @@ impl TrainingWorker {
     }
 }
 
+fn new_flush_interval(timeout: Duration) -> tokio::time::Interval {
+    let mut interval = tokio::time::interval(timeout);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    interval
+}
+
+/// Wait for any running job to finish (forever if there are none)
+///
+/// Returns the finished job's index and outcome.
+async fn wait_for_any_job(jobs: &mut [RunningJob]) -> (usize, TrainingOutcome) {
+    if jobs.is_empty() {
+        return std::future::pending().await;
+    }
+
+    let waits = jobs.iter_mut().map(|running| Box::pin(running.job.wait()));
+    let (outcome, index, _) = futures::future::select_all(waits).await;
+    (index, outcome)
+}
+
+/// Per-run snapshot path next to the training queue
+fn run_queue_path(queue_path: &Path) -> PathBuf {
+    queue_path.with_file_name(format!(
+        "training_queue_run_{}.jsonl",
+        &uuid::Uuid::new_v4().simple().to_string()[..8]
+    ))
+}
+
+/// Append the contents of `from` to `to`
+fn append_file(from: &Path, to: &Path) -> Result<()> {
+    let contents = std::fs::read(from)
+        .with_context(|| format!("Failed to read {}", from.display()))?;
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(to)
+        .with_context(|| format!("Failed to open {}", to.display()))?;
+    file.write_all(&contents)
+        .with_context(|| format!("Failed to append to {}", to.display()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -153,9 +488,90 @@ mod tests {
         let (_tx, rx) = mpsc::unbounded_channel();
         let coordinator = Arc::new(TrainingCoordinator::new(100, 10, true));
 
-        let worker = TrainingWorker::new(rx, coordinator, 10, 5);
+        let config = TrainingConfig {
+            batch_threshold: 20,
+            batch_timeout_minutes: 2,
+            max_concurrent_training: 2,
+            ..Default::default()
+        };
+
+        let worker = TrainingWorker::new(rx, coordinator, &config);
+
+        assert_eq!(worker.batch_threshold, 20);
+        assert_eq!(worker.batch_timeout, Duration::from_secs(2 * 60));
+        assert_eq!(worker.max_concurrent, 2);
+        assert_eq!(worker.handle().state(), TrainingState::Idle);
+    }
+
+    #[tokio::test]
+    async fn test_pause_resume_keeps_buffered_examples() {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let coordinator = Arc::new(TrainingCoordinator::new(100, 10, true));
+        let worker = TrainingWorker::new(rx, coordinator, &TrainingConfig::default());
+        let handle = worker.handle();
+        tokio::spawn(worker.run());
+
+        assert_eq!(handle.send(TrainingAction::Pause).await.unwrap(), TrainingState::Paused);
+
+        tx.send(WeightedExample::normal("q".into(), "r".into(), String::new()))
+            .unwrap();
+        assert_eq!(handle.state(), TrainingState::Paused);
+
+        assert_eq!(handle.send(TrainingAction::Resume).await.unwrap(), TrainingState::Idle);
+        // Cancel without an active run is a no-op
+        assert_eq!(handle.send(TrainingAction::Cancel).await.unwrap(), TrainingState::Idle);
+    }
+
+    #[tokio::test]
+    async fn test_timeout_triggers_training_below_threshold() {
+        let dir = tempfile::tempdir().unwrap();
+        let queue_path = dir.path().join("training_queue.jsonl");
+        let coordinator = Arc::new(
+            TrainingCoordinator::new(100, 10, true).with_queue_path(queue_path.clone()),
+        );
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let mut worker = TrainingWorker::new(rx, coordinator, &TrainingConfig::default());
+        // Minutes are too slow for a test; the subprocess can't start, so the
+        // snapshot is moved back into the queue
+        worker.batch_timeout = Duration::from_millis(50);
+        worker.subprocess = LoRATrainingSubprocess::new(
+            crate::training::LoRATrainingConfig {
+                script_path: dir.path().join("missing_train_lora.py"),
+                ..Default::default()
+            },
+        );
+        tokio::spawn(worker.run());
+
+        // One example, well below the threshold of 10
+        tx.send(WeightedExample::normal("q".into(), "r".into(), String::new()))
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(300)).await;
+
+        let queued = std::fs::read_to_string(&queue_path).unwrap();
+        assert_eq!(queued.lines().count(), 1);
+        let leftover_snapshots = std::fs::read_dir(dir.path()).unwrap().count();
+        assert_eq!(leftover_snapshots, 1);
+    }
+
+    #[tokio::test]
+    async fn test_reconfigure_rejects_invalid_settings() {
+        let (_tx, rx) = mpsc::unbounded_channel();
+        let coordinator = Arc::new(TrainingCoordinator::new(100, 10, true));
+        let worker = TrainingWorker::new(rx, coordinator, &TrainingConfig::default());
+        let handle = worker.handle();
+
+        let invalid = TrainingConfig {
+            batch_threshold: 0,
+            ..Default::default()
+        };
+        assert!(handle.reconfigure(invalid).is_err());
+        assert!(handle.reconfigure(TrainingConfig::default()).is_ok());
+    }
 
-        assert_eq!(worker.batch_threshold, 10);
-        assert_eq!(worker.batch_timeout, Duration::from_secs(5 * 60));
+    #[tokio::test]
+    async fn test_disconnected_handle_rejects_actions() {
+        let handle = TrainingHandle::disconnected();
+        assert!(handle.send(TrainingAction::Pause).await.is_err());
     }
 }
@@ -0,0 +1,101 @@
+// Per-session teacher token usage and cost estimates
+//
+// Each teacher call a session makes adds its reported token counts, priced
+// from a small table of list prices. Models missing from the table (local
+// Ollama models, new releases) count tokens but add no cost.
+
+use crate::providers::TokenUsage;
+use serde::Serialize;
+
+/// USD per million input and output tokens, by model name prefix
+///
+/// The longest matching prefix wins, so dated releases
+/// (`claude-sonnet-4-5-20250929`) match their family.
+const MODEL_PRICES: &[(&str, f64, f64)] = &[
+    ("claude-opus-4-5", 5.0, 25.0),
+    ("claude-opus-4", 15.0, 75.0),
+    ("claude-sonnet-4", 3.0, 15.0),
+    ("claude-3-7-sonnet", 3.0, 15.0),
+    ("claude-3-5-sonnet", 3.0, 15.0),
+    ("claude-haiku-4-5", 1.0, 5.0),
+    ("claude-3-5-haiku", 0.8, 4.0),
+    ("gpt-4o-mini", 0.15, 0.6),
+    ("gpt-4o", 2.5, 10.0),
+    ("gpt-4.1-mini", 0.4, 1.6),
+    ("gpt-4.1", 2.0, 8.0),
+    ("o3-mini", 1.1, 4.4),
+    ("grok-2", 2.0, 10.0),
+    ("mistral-large", 2.0, 6.0),
+    ("gemini-2.0-flash", 0.1, 0.4),
+    ("gemini-1.5-pro", 1.25, 5.0),
+    ("gemini-1.5-flash", 0.075, 0.3),
+];
+
+/// Estimated cost in USD of `usage` on `model` (0 for unknown models)
+pub fn estimate_cost_usd(model: &str, usage: TokenUsage) -> f64 {
+    MODEL_PRICES
+        .iter()
+        .filter(|(prefix, _, _)| model.starts_with(prefix))
+        .max_by_key(|(prefix, _, _)| prefix.len())
+        .map(|(_, input, output)| {
+            (usage.input_tokens as f64 * input + usage.output_tokens as f64 * output) / 1_000_000.0
+        })
+        .unwrap_or(0.0)
+}
+
+/// Teacher usage accumulated by one session
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct SessionUsage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub estimated_cost_usd: f64,
+}
+
+impl SessionUsage {
+    /// Add a completed teacher call (calls without reported usage add nothing)
+    pub fn record(&mut self, model: &str, usage: Option<TokenUsage>) {
+        let Some(usage) = usage else {
+            return;
+        };
+        self.input_tokens += usage.input_tokens;
+        self.output_tokens += usage.output_tokens;
+        self.estimated_cost_usd += estimate_cost_usd(model, usage);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(input_tokens: u64, output_tokens: u64) -> Option<TokenUsage> {
+        Some(TokenUsage {
+            input_tokens,
+            output_tokens,
+        })
+    }
+
+    #[test]
+    fn test_record_accumulates_tokens_and_cost() {
+        let mut session = SessionUsage::default();
+        session.record("claude-sonnet-4-5-20250929", usage(1_000_000, 100_000));
+        session.record("gpt-4o-mini", usage(1_000_000, 0));
+        session.record("qwen2.5:7b", usage(500, 500));
+        session.record("claude-sonnet-4-5-20250929", None);
+
+        assert_eq!(session.input_tokens, 2_000_500);
+        assert_eq!(session.output_tokens, 100_500);
+        assert!((session.estimated_cost_usd - 4.65).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_longest_prefix_wins() {
+        let tokens = TokenUsage {
+            input_tokens: 1_000_000,
+            output_tokens: 0,
+        };
+        assert_eq!(estimate_cost_usd("gpt-4o-mini-2024-07-18", tokens), 0.15);
+        assert_eq!(estimate_cost_usd("gpt-4o-2024-08-06", tokens), 2.5);
+        assert_eq!(estimate_cost_usd("claude-opus-4-5-20251101", tokens), 5.0);
+        assert_eq!(estimate_cost_usd("unknown-model", tokens), 0.0);
+    }
+}
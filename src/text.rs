@@ -0,0 +1,120 @@
+// UTF-8 safe text helpers
+//
+// Slicing a `str` at a byte offset panics when the offset lands inside a
+// multi-byte character, and decoding one byte-level BPE token at a time can
+// produce half a character (an emoji is often two or more tokens). These
+// helpers cut on char boundaries and hold partial characters back until the
+// rest of the bytes arrive.
+
+use anyhow::Result;
+
+/// Longest prefix of `text` with at most `max_chars` characters
+pub fn truncate_chars(text: &str, max_chars: usize) -> &str {
+    match text.char_indices().nth(max_chars) {
+        Some((end, _)) => &text[..end],
+        None => text,
+    }
+}
+
+/// Longest suffix of `text` with at most `max_chars` characters
+pub fn last_chars(text: &str, max_chars: usize) -> &str {
+    match max_chars.checked_sub(1).and_then(|n| text.char_indices().rev().nth(n)) {
+        Some((start, _)) => &text[start..],
+        None if max_chars == 0 => "",
+        None => text,
+    }
+}
+
+/// `text` cut to `max_chars` characters, with `...` appended when it was cut
+pub fn ellipsize(text: &str, max_chars: usize) -> String {
+    let truncated = truncate_chars(text, max_chars);
+    if truncated.len() < text.len() {
+        format!("{}...", truncated)
+    } else {
+        text.to_string()
+    }
+}
+
+/// Turns generated token ids into text as they arrive, one complete
+/// character at a time
+///
+/// Each step decodes a short window of recent tokens and emits only what's
+/// new. While the window ends in a partial character (decoded as U+FFFD),
+/// nothing is emitted; the bytes are held until a later token completes it.
+/// The window keeps one earlier token so decoders that treat the first token
+/// specially (leading-space handling) still produce the right spacing.
+#[derive(Debug, Default)]
+pub struct TokenTextStream {
+    tokens: Vec<u32>,
+    /// Start of the decode window
+    prefix_offset: usize,
+    /// End of the text already emitted
+    read_offset: usize,
+}
+
+impl TokenTextStream {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a token; returns the newly completed text, if any
+    pub fn push<F>(&mut self, token: u32, decode: F) -> Result<Option<String>>
+    where
+        F: Fn(&[u32]) -> Result<String>,
+    {
+        self.tokens.push(token);
+
+        let prefix = decode(&self.tokens[self.prefix_offset..self.read_offset])?;
+        let text = decode(&self.tokens[self.prefix_offset..])?;
+        if text.len() <= prefix.len() || text.ends_with(char::REPLACEMENT_CHARACTER) {
+            return Ok(None);
+        }
+
+        let new_text = match text.strip_prefix(prefix.as_str()) {
+            Some(new_text) => new_text.to_string(),
+            // Decoding isn't always prefix-stable; fall back to a char count
+            None => text.chars().skip(prefix.chars().count()).collect(),
+        };
+        self.prefix_offset = self.read_offset;
+        self.read_offset = self.tokens.len();
+        Ok(Some(new_text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_on_char_boundaries() {
+        let text = "héllo 😀 wörld";
+        assert_eq!(truncate_chars(text, 7), "héllo 😀");
+        assert_eq!(truncate_chars(text, 100), text);
+        assert_eq!(last_chars(text, 7), "😀 wörld");
+        assert_eq!(last_chars(text, 0), "");
+        assert_eq!(ellipsize("😀😀😀", 2), "😀😀...");
+        assert_eq!(ellipsize("😀😀", 2), "😀😀");
+    }
+
+    #[test]
+    fn test_token_stream_reassembles_split_emoji() {
+        // Byte-level tokens: one byte per token, so the emoji spans four
+        let original = "ok 😀 done";
+        let decode = |ids: &[u32]| -> Result<String> {
+            let bytes: Vec<u8> = ids.iter().map(|&id| id as u8).collect();
+            Ok(String::from_utf8_lossy(&bytes).into_owned())
+        };
+
+        let mut stream = TokenTextStream::new();
+        let mut chunks = Vec::new();
+        for byte in original.bytes() {
+            if let Some(text) = stream.push(byte as u32, decode).unwrap() {
+                chunks.push(text);
+            }
+        }
+
+        assert!(chunks.iter().all(|c| !c.contains(char::REPLACEMENT_CHARACTER)));
+        assert!(chunks.contains(&"😀".to_string()));
+        assert_eq!(chunks.concat(), original);
+    }
+}
@@ -0,0 +1,309 @@
+// Tool-use audit log
+//
+// A record of every tool the agent executed, for security review: the tool,
+// its input, how the call was approved, whether it succeeded, and how long
+// it took. Entries are appended as JSONL to `~/.shammah/tool_audit.jsonl`
+// by the tool runner, whichever front end asked for the tool.
+//
+// `[tools] audit_log` picks what is recorded: unset records the tools that
+// change things outside the conversation (bash, save_and_exec,
+// restart_session), `true` records every tool, `false` nothing. Strings in
+// the input and error matching a `[tools] audit_redact` regex are masked
+// before they are written.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::clock::SharedClock;
+use crate::config::ToolsConfig;
+use crate::tools::executor::ApprovalSource;
+use crate::tools::types::{ToolResult, ToolUse};
+
+/// Tools recorded when `[tools] audit_log` is unset
+pub const AUDITED_BY_DEFAULT: &[&str] = &["bash", "save_and_exec", "restart_session"];
+
+/// Replacement for redacted text
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Longest error message kept in an entry, in characters
+const MAX_ERROR_CHARS: usize = 500;
+
+/// One executed tool
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolAuditEntry {
+    pub timestamp: DateTime<Utc>,
+    pub tool: String,
+    pub tool_use_id: String,
+    /// Tool input, after redaction
+    pub input: Value,
+    pub approval: ApprovalSource,
+    pub success: bool,
+    /// Error message (truncated, redacted) when the tool failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
+/// Which tools get recorded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AuditScope {
+    Default,
+    All,
+}
+
+/// Appends tool executions to the tool audit log
+pub struct ToolAuditLogger {
+    file_path: PathBuf,
+    scope: AuditScope,
+    redactions: Vec<Regex>,
+    clock: SharedClock,
+}
+
+impl ToolAuditLogger {
+    /// Logger for `~/.shammah/tool_audit.jsonl` per `[tools]`, or None when
+    /// `audit_log = false`
+    pub fn new(config: &ToolsConfig) -> Result<Option<Self>> {
+        if config.audit_log == Some(false) {
+            return Ok(None);
+        }
+        let home = dirs::home_dir()
+            .ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?;
+
+        let shammah_dir = home.join(".shammah");
+        fs::create_dir_all(&shammah_dir)
+            .context("Failed to create ~/.shammah directory")?;
+
+        Self::configured(shammah_dir.join("tool_audit.jsonl"), config)
+    }
+
+    /// Logger writing to `file_path` per `[tools]`, or None when `audit_log = false`
+    pub fn configured(file_path: PathBuf, config: &ToolsConfig) -> Result<Option<Self>> {
+        let logger = match config.audit_log {
+            Some(false) => return Ok(None),
+            Some(true) => Self::with_path(file_path).with_all_tools(),
+            None => Self::with_path(file_path),
+        };
+        logger.with_redactions(&config.audit_redact).map(Some)
+    }
+
+    /// Logger writing to `file_path`, recording the default tools
+    pub fn with_path(file_path: PathBuf) -> Self {
+        Self {
+            file_path,
+            scope: AuditScope::Default,
+            redactions: Vec::new(),
+            clock: crate::clock::system_clock(),
+        }
+    }
+
+    /// Record every tool, not just the default ones
+    pub fn with_all_tools(mut self) -> Self {
+        self.scope = AuditScope::All;
+        self
+    }
+
+    /// Mask text matching any of these regexes
+    pub fn with_redactions(mut self, patterns: &[String]) -> Result<Self> {
+        self.redactions = compile_redactions(patterns)?;
+        Ok(self)
+    }
+
+    /// Use `clock` for entry timestamps
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Whether calls to `tool_name` are recorded
+    pub fn audits(&self, tool_name: &str) -> bool {
+        self.scope == AuditScope::All || AUDITED_BY_DEFAULT.contains(&tool_name)
+    }
+
+    /// Build the entry for a finished tool call
+    pub fn entry(
+        &self,
+        tool_use: &ToolUse,
+        approval: &ApprovalSource,
+        result: &Result<ToolResult>,
+        duration: Duration,
+    ) -> ToolAuditEntry {
+        let error = match result {
+            Ok(result) if !result.is_error => None,
+            Ok(result) => Some(result.content.clone()),
+            Err(e) => Some(format!("{:#}", e)),
+        };
+        ToolAuditEntry {
+            timestamp: self.clock.now(),
+            tool: tool_use.name.clone(),
+            tool_use_id: tool_use.id.clone(),
+            input: self.redact_value(&tool_use.input),
+            approval: approval.clone(),
+            success: error.is_none(),
+            error: error.map(|e| crate::text::ellipsize(&self.redact(&e), MAX_ERROR_CHARS)),
+            duration_ms: duration.as_millis() as u64,
+        }
+    }
+
+    /// Append an entry
+    pub fn log(&self, entry: &ToolAuditEntry) -> Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.file_path)
+            .with_context(|| format!("Failed to open tool audit log: {}", self.file_path.display()))?;
+
+        let json = serde_json::to_string(entry)
+            .context("Failed to serialize tool audit entry")?;
+
+        writeln!(file, "{}", json)
+            .context("Failed to write tool audit entry")?;
+
+        Ok(())
+    }
+
+    /// Record a finished tool call if its tool is audited (failures are logged, not returned)
+    pub fn record(
+        &self,
+        tool_use: &ToolUse,
+        approval: &ApprovalSource,
+        result: &Result<ToolResult>,
+        duration: Duration,
+    ) {
+        if !self.audits(&tool_use.name) {
+            return;
+        }
+        if let Err(e) = self.log(&self.entry(tool_use, approval, result, duration)) {
+            tracing::warn!("{:#}", e);
+        }
+    }
+
+    /// Get the path to the tool audit log
+    pub fn path(&self) -> &PathBuf {
+        &self.file_path
+    }
+
+    fn redact(&self, text: &str) -> String {
+        self.redactions
+            .iter()
+            .fold(text.to_string(), |text, regex| regex.replace_all(&text, REDACTED).into_owned())
+    }
+
+    /// Redact every string in a JSON value
+    fn redact_value(&self, value: &Value) -> Value {
+        match value {
+            Value::String(text) => Value::String(self.redact(text)),
+            Value::Array(items) => Value::Array(items.iter().map(|v| self.redact_value(v)).collect()),
+            Value::Object(fields) => Value::Object(
+                fields
+                    .iter()
+                    .map(|(key, v)| (key.clone(), self.redact_value(v)))
+                    .collect(),
+            ),
+            other => other.clone(),
+        }
+    }
+}
+
+/// Compile `[tools] audit_redact` patterns
+pub fn compile_redactions(patterns: &[String]) -> Result<Vec<Regex>> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            Regex::new(pattern)
+                .with_context(|| format!("Invalid [tools] audit_redact pattern '{}'", pattern))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::FixedClock;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    fn bash(command: &str) -> ToolUse {
+        ToolUse {
+            id: "toolu_1".to_string(),
+            name: "bash".to_string(),
+            input: json!({ "command": command, "description": "run it" }),
+        }
+    }
+
+    #[test]
+    fn test_records_redacted_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let now = Utc::now();
+        let logger = ToolAuditLogger::with_path(dir.path().join("tool_audit.jsonl"))
+            .with_redactions(&[r"sk-[A-Za-z0-9]+".to_string(), r"(?i)password=\S+".to_string()])
+            .unwrap()
+            .with_clock(Arc::new(FixedClock::new(now)));
+
+        let tool_use = bash("curl -H 'x-api-key: sk-abc123' 'https://x.y?password=hunter2'");
+        let ok = Ok(ToolResult::success("toolu_1".to_string(), "done".to_string()));
+        logger.record(&tool_use, &ApprovalSource::User, &ok, Duration::from_millis(42));
+
+        let failed = Ok(ToolResult::error("toolu_1".to_string(), "sk-abc123 rejected".to_string()));
+        logger.record(&tool_use, &ApprovalSource::SessionPattern("p1".to_string()), &failed, Duration::ZERO);
+
+        // Read-only tools aren't recorded by default
+        let read = ToolUse {
+            id: "toolu_2".to_string(),
+            name: "read".to_string(),
+            input: json!({ "file_path": "/etc/hosts" }),
+        };
+        logger.record(&read, &ApprovalSource::Automatic, &ok, Duration::ZERO);
+
+        let contents = fs::read_to_string(logger.path()).unwrap();
+        let entries: Vec<ToolAuditEntry> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(entries.len(), 2);
+
+        assert_eq!(entries[0].timestamp, now);
+        assert_eq!(
+            entries[0].input["command"],
+            "curl -H 'x-api-key: [REDACTED]' 'https://x.y?[REDACTED]"
+        );
+        assert_eq!(entries[0].input["description"], "run it");
+        assert_eq!(entries[0].approval, ApprovalSource::User);
+        assert!(entries[0].success);
+        assert_eq!(entries[0].duration_ms, 42);
+
+        assert!(!entries[1].success);
+        assert_eq!(entries[1].error.as_deref(), Some("[REDACTED] rejected"));
+        assert!(contents.contains(r#""approval":{"source":"session_pattern","pattern_id":"p1"}"#));
+        assert!(!contents.contains("sk-abc123"));
+    }
+
+    #[test]
+    fn test_audit_scope_from_config() {
+        let config = ToolsConfig {
+            audit_log: Some(true),
+            ..Default::default()
+        };
+        let logger = ToolAuditLogger::configured(PathBuf::from("unused"), &config).unwrap().unwrap();
+        assert!(logger.audits("read"));
+
+        let logger = ToolAuditLogger::with_path(PathBuf::from("unused"));
+        assert!(logger.audits("bash"));
+        assert!(logger.audits("restart_session"));
+        assert!(!logger.audits("grep"));
+
+        let config = ToolsConfig {
+            audit_log: Some(false),
+            ..Default::default()
+        };
+        assert!(ToolAuditLogger::configured(PathBuf::from("unused"), &config).unwrap().is_none());
+
+        assert!(compile_redactions(&["(unclosed".to_string()]).is_err());
+    }
+}
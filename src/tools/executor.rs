@@ -3,14 +3,20 @@
 // Executes tools with permission checks and multi-turn support
 
 use crate::cli::ConversationHistory;
+use crate::tools::audit::ToolAuditLogger;
 use crate::tools::patterns::{ExactApproval, MatchType, PersistentPatternStore, ToolPattern};
 use crate::tools::permissions::{PermissionCheck, PermissionManager};
+use crate::tools::recent_calls::RecentToolCalls;
 use crate::tools::registry::ToolRegistry;
+use crate::tools::session_changes::SessionChanges;
+use crate::tools::timeouts::ToolTimeouts;
 use crate::tools::types::{ToolResult, ToolUse};
 use anyhow::{Context, Result};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 use tracing::{debug, error, info, instrument, warn};
 
 /// Signature for a tool execution, used for caching approval decisions
@@ -29,17 +35,29 @@ pub struct ToolSignature {
 }
 
 /// Source of approval for a tool execution
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case", tag = "source", content = "pattern_id")]
 pub enum ApprovalSource {
     NotApproved,
+    TaskExact,
+    TaskPattern(String), // Pattern ID
     SessionExact,
     SessionPattern(String), // Pattern ID
     PersistentExact,
     PersistentPattern(String), // Pattern ID
+    /// Approved at the confirmation prompt (never returned by the cache)
+    User,
+    /// Needed no approval, e.g. a read-only tool in plan mode (never returned by the cache)
+    Automatic,
 }
 
 /// Enhanced cache for tool execution approvals with pattern matching and persistence
 pub struct ToolConfirmationCache {
+    // Task-scoped approvals (cleared when the executing task ends)
+    task_scope: Option<String>,
+    task_exact: HashSet<ToolSignature>,
+    task_patterns: Vec<ToolPattern>,
+
     // Session-only approvals (cleared on restart)
     session_exact: HashSet<ToolSignature>,
     session_patterns: Vec<ToolPattern>,
@@ -74,6 +92,9 @@ impl ToolConfirmationCache {
         };
 
         Ok(Self {
+            task_scope: None,
+            task_exact: HashSet::new(),
+            task_patterns: Vec::new(),
             session_exact: HashSet::new(),
             session_patterns: Vec::new(),
             persistent,
@@ -98,13 +119,18 @@ impl ToolConfirmationCache {
             return ApprovalSource::SessionExact;
         }
 
-        // 3. Check persistent patterns
+        // 3. Check task exact
+        if self.task_exact.contains(sig) {
+            return ApprovalSource::TaskExact;
+        }
+
+        // 4. Check persistent patterns
         if let Some(MatchType::Pattern(id)) = self.persistent.matches(sig) {
             self.dirty = true; // Match count was incremented
             return ApprovalSource::PersistentPattern(id);
         }
 
-        // 4. Check session patterns
+        // 5. Check session patterns
         for pattern in &mut self.session_patterns {
             if pattern.matches(sig) {
                 pattern.record_match();
@@ -112,9 +138,46 @@ impl ToolConfirmationCache {
             }
         }
 
+        // 6. Check task patterns
+        for pattern in &mut self.task_patterns {
+            if pattern.matches(sig) {
+                pattern.record_match();
+                return ApprovalSource::TaskPattern(pattern.id.clone());
+            }
+        }
+
         ApprovalSource::NotApproved
     }
 
+    /// Set the current task; task approvals from a different task are dropped
+    ///
+    /// `None` means no task is executing.
+    pub fn set_task_scope(&mut self, task: Option<String>) {
+        if self.task_scope != task {
+            if !self.task_exact.is_empty() || !self.task_patterns.is_empty() {
+                debug!("Task changed, clearing task-scoped approvals");
+            }
+            self.clear_task();
+            self.task_scope = task;
+        }
+    }
+
+    /// Approve exact command until the current task ends
+    pub fn approve_exact_task(&mut self, sig: ToolSignature) {
+        self.task_exact.insert(sig);
+    }
+
+    /// Approve pattern until the current task ends
+    pub fn approve_pattern_task(&mut self, pattern: ToolPattern) {
+        self.task_patterns.push(pattern);
+    }
+
+    /// Clear task-scoped approvals (task completed)
+    pub fn clear_task(&mut self) {
+        self.task_exact.clear();
+        self.task_patterns.clear();
+    }
+
     /// Approve exact command for session only
     pub fn approve_exact_session(&mut self, sig: ToolSignature) {
         self.session_exact.insert(sig);
@@ -151,8 +214,9 @@ impl ToolConfirmationCache {
         Ok(())
     }
 
-    /// Clear session approvals (keep persistent)
+    /// Clear session and task approvals (keep persistent)
     pub fn clear(&mut self) {
+        self.clear_task();
         self.session_exact.clear();
         self.session_patterns.clear();
     }
@@ -182,14 +246,169 @@ impl ToolConfirmationCache {
         self.persistent = PersistentPatternStore::default();
         self.dirty = true;
     }
+
+    /// Count approvals (exact + pattern) recorded for a tool
+    pub fn approval_counts(&self, tool_name: &str) -> ToolApprovalCounts {
+        let task = self
+            .task_exact
+            .iter()
+            .filter(|sig| sig.tool_name == tool_name)
+            .count()
+            + self
+                .task_patterns
+                .iter()
+                .filter(|p| p.tool_name == tool_name)
+                .count();
+
+        let session = self
+            .session_exact
+            .iter()
+            .filter(|sig| sig.tool_name == tool_name)
+            .count()
+            + self
+                .session_patterns
+                .iter()
+                .filter(|p| p.tool_name == tool_name)
+                .count();
+
+        let persistent = self
+            .persistent
+            .exact_approvals
+            .iter()
+            .filter(|a| a.tool_name == tool_name)
+            .count()
+            + self
+                .persistent
+                .patterns
+                .iter()
+                .filter(|p| p.tool_name == tool_name)
+                .count();
+
+        ToolApprovalCounts {
+            task,
+            session,
+            persistent,
+        }
+    }
+}
+
+/// Number of saved approvals for a single tool
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ToolApprovalCounts {
+    /// Approvals that last until the current task ends (/done or mode exit)
+    pub task: usize,
+    /// Approvals that last until the REPL exits
+    pub session: usize,
+    /// Approvals saved to disk (see /patterns)
+    pub persistent: usize,
+}
+
+/// Default for `features.max_concurrent_tools`
+pub const DEFAULT_MAX_CONCURRENT_TOOLS: usize = 4;
+
+/// Limit on how many tools run at once
+///
+/// Clones share the limit, so the event loop can read the in-flight count
+/// while tool tasks hold slots.
+#[derive(Clone)]
+pub struct ToolSlots {
+    semaphore: Arc<Semaphore>,
+    in_flight: Arc<AtomicUsize>,
+    limit: usize,
+}
+
+/// A held execution slot, released when dropped
+pub struct ToolSlot {
+    _permit: OwnedSemaphorePermit,
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl ToolSlots {
+    /// Allow up to `limit` concurrent executions (at least 1)
+    pub fn new(limit: usize) -> Self {
+        let limit = limit.max(1);
+        Self {
+            semaphore: Arc::new(Semaphore::new(limit)),
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            limit,
+        }
+    }
+
+    /// Wait for a free slot
+    pub async fn acquire(&self) -> ToolSlot {
+        let permit = Arc::clone(&self.semaphore)
+            .acquire_owned()
+            .await
+            .expect("tool slot semaphore is never closed");
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        ToolSlot {
+            _permit: permit,
+            in_flight: Arc::clone(&self.in_flight),
+        }
+    }
+
+    /// Tools running right now
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+}
+
+impl Drop for ToolSlot {
+    fn drop(&mut self) {
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Whether `tool_use` is a bash call that only reports its command
+fn is_bash_dry_run(tool_use: &ToolUse, session_dry_run: bool) -> bool {
+    tool_use.name == "bash"
+        && (session_dry_run || crate::tools::implementations::bash::is_dry_run(&tool_use.input))
 }
 
 /// Tool executor - manages tool execution lifecycle
 pub struct ToolExecutor {
-    registry: ToolRegistry,
-    permissions: PermissionManager,
+    registry: Arc<ToolRegistry>,
+    permissions: Arc<PermissionManager>,
     confirmation_cache: ToolConfirmationCache,
     mcp_client: Option<Arc<crate::tools::mcp::McpClient>>,
+    /// Executions per tool name this session (for /tools)
+    usage_counts: Arc<std::sync::Mutex<HashMap<String, usize>>>,
+    /// Files/commands/errors from tool executions (for session summaries)
+    session_changes: Arc<SessionChanges>,
+    /// Latest calls, for /replay-tool
+    recent_calls: Arc<RecentToolCalls>,
+    /// Bound on concurrent executions (features.max_concurrent_tools)
+    slots: ToolSlots,
+    /// Per-tool execution timeouts ([tools.timeouts])
+    timeouts: Arc<ToolTimeouts>,
+    /// Session-wide bash dry-run (/dryrun)
+    bash_dry_run: Arc<AtomicBool>,
+    /// Tool audit log ([tools] audit_log)
+    audit: Option<Arc<ToolAuditLogger>>,
+}
+
+/// The execution half of a `ToolExecutor`, without the approval state
+///
+/// Cheap to clone, so a tool can run without holding the executor lock and
+/// several tools can run at once (up to the executor's slot limit).
+#[derive(Clone)]
+pub struct ToolRunner {
+    registry: Arc<ToolRegistry>,
+    permissions: Arc<PermissionManager>,
+    mcp_client: Option<Arc<crate::tools::mcp::McpClient>>,
+    usage_counts: Arc<std::sync::Mutex<HashMap<String, usize>>>,
+    session_changes: Arc<SessionChanges>,
+    recent_calls: Arc<RecentToolCalls>,
+    slots: ToolSlots,
+    timeouts: Arc<ToolTimeouts>,
+    bash_dry_run: Arc<AtomicBool>,
+    audit: Option<Arc<ToolAuditLogger>>,
+    /// How executions by this runner were approved, for the audit log
+    approval: ApprovalSource,
 }
 
 impl ToolExecutor {
@@ -200,13 +419,38 @@ impl ToolExecutor {
         patterns_path: PathBuf,
     ) -> Result<Self> {
         Ok(Self {
-            registry,
-            permissions,
+            registry: Arc::new(registry),
+            permissions: Arc::new(permissions),
             confirmation_cache: ToolConfirmationCache::new(patterns_path)?,
             mcp_client: None,
+            usage_counts: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            session_changes: Arc::new(SessionChanges::new()),
+            recent_calls: Arc::new(RecentToolCalls::default()),
+            slots: ToolSlots::new(DEFAULT_MAX_CONCURRENT_TOOLS),
+            timeouts: Arc::new(ToolTimeouts::default()),
+            bash_dry_run: Arc::new(AtomicBool::new(false)),
+            audit: None,
         })
     }
 
+    /// Run at most `limit` tools at once
+    pub fn with_max_concurrent_tools(mut self, limit: usize) -> Self {
+        self.slots = ToolSlots::new(limit);
+        self
+    }
+
+    /// Override the built-in per-tool timeouts
+    pub fn with_tool_timeouts(mut self, timeouts: ToolTimeouts) -> Self {
+        self.timeouts = Arc::new(timeouts);
+        self
+    }
+
+    /// Record executed tools in the tool audit log
+    pub fn with_tool_audit(mut self, audit: Option<ToolAuditLogger>) -> Self {
+        self.audit = audit.map(Arc::new);
+        self
+    }
+
     /// Add MCP client to enable MCP tools
     ///
     /// Always returns Self (never fails) - gracefully handles MCP connection errors
@@ -234,6 +478,28 @@ impl ToolExecutor {
         self.mcp_client.as_ref()
     }
 
+    /// The concurrency limit (shared, for the status bar)
+    pub fn slots(&self) -> ToolSlots {
+        self.slots.clone()
+    }
+
+    /// A handle for executing tools without holding the executor
+    pub fn runner(&self) -> ToolRunner {
+        ToolRunner {
+            registry: Arc::clone(&self.registry),
+            permissions: Arc::clone(&self.permissions),
+            mcp_client: self.mcp_client.clone(),
+            usage_counts: Arc::clone(&self.usage_counts),
+            session_changes: Arc::clone(&self.session_changes),
+            recent_calls: Arc::clone(&self.recent_calls),
+            slots: self.slots.clone(),
+            timeouts: Arc::clone(&self.timeouts),
+            bash_dry_run: Arc::clone(&self.bash_dry_run),
+            audit: self.audit.clone(),
+            approval: ApprovalSource::NotApproved,
+        }
+    }
+
     /// Get list of all available tools (built-in + MCP)
     pub async fn list_all_tools(&self) -> Vec<crate::tools::types::ToolDefinition> {
         let mut tools = Vec::new();
@@ -252,7 +518,11 @@ impl ToolExecutor {
         // Add MCP tools if client is available
         if let Some(mcp) = &self.mcp_client {
             let mcp_tools = mcp.list_tools().await;
-            tools.extend(mcp_tools);
+            tools.extend(
+                mcp_tools
+                    .into_iter()
+                    .filter(|tool| !self.registry.is_disabled(&tool.name)),
+            );
         }
 
         tools
@@ -263,6 +533,26 @@ impl ToolExecutor {
         self.confirmation_cache.is_approved(sig)
     }
 
+    /// Set the current task for task-scoped approvals (None = no task)
+    pub fn set_task_scope(&mut self, task: Option<String>) {
+        self.confirmation_cache.set_task_scope(task);
+    }
+
+    /// Approve exact command until the current task ends
+    pub fn approve_exact_task(&mut self, sig: ToolSignature) {
+        self.confirmation_cache.approve_exact_task(sig);
+    }
+
+    /// Approve pattern until the current task ends
+    pub fn approve_pattern_task(&mut self, pattern: ToolPattern) {
+        self.confirmation_cache.approve_pattern_task(pattern);
+    }
+
+    /// Clear task-scoped approvals (task completed)
+    pub fn clear_task_approvals(&mut self) {
+        self.confirmation_cache.clear_task();
+    }
+
     /// Approve exact command for session only
     pub fn approve_exact_session(&mut self, sig: ToolSignature) {
         self.confirmation_cache.approve_exact_session(sig);
@@ -308,8 +598,161 @@ impl ToolExecutor {
         self.confirmation_cache.clear_persistent();
     }
 
-    /// Execute a single tool use
-    #[instrument(skip(self, tool_use, conversation, save_models_fn, batch_trainer, local_generator, tokenizer), fields(tool = %tool_use.name, id = %tool_use.id))]
+    /// Get session and persistent approval counts for a tool
+    pub fn approval_counts(&self, tool_name: &str) -> ToolApprovalCounts {
+        self.confirmation_cache.approval_counts(tool_name)
+    }
+
+    /// Number of times a tool has been executed this session
+    pub fn usage_count(&self, tool_name: &str) -> usize {
+        self.usage_counts
+            .lock()
+            .map(|counts| counts.get(tool_name).copied().unwrap_or(0))
+            .unwrap_or(0)
+    }
+
+    /// Latest tool calls this session (for /replay-tool)
+    pub fn recent_calls(&self) -> Arc<RecentToolCalls> {
+        Arc::clone(&self.recent_calls)
+    }
+
+    /// Tool effects recorded this session (shared, so it can be read without
+    /// locking the executor)
+    pub fn session_changes(&self) -> Arc<SessionChanges> {
+        Arc::clone(&self.session_changes)
+    }
+
+    /// Turn session-wide bash dry-run on or off (/dryrun)
+    ///
+    /// While on, every bash call reports what it would run instead of
+    /// running it.
+    pub fn set_bash_dry_run(&self, enabled: bool) {
+        self.bash_dry_run.store(enabled, Ordering::SeqCst);
+    }
+
+    /// Whether session-wide bash dry-run is on
+    pub fn bash_dry_run(&self) -> bool {
+        self.bash_dry_run.load(Ordering::SeqCst)
+    }
+
+    /// Whether this tool use is a bash dry run (asked for by the caller or
+    /// forced by /dryrun), which spawns nothing and needs no approval
+    pub fn is_dry_run(&self, tool_use: &ToolUse) -> bool {
+        is_bash_dry_run(tool_use, self.bash_dry_run())
+    }
+
+    /// Execute a single tool use (waits for a free slot)
+    pub async fn execute_tool<F>(
+        &self,
+        tool_use: &ToolUse,
+        conversation: Option<&ConversationHistory>,
+        save_models_fn: Option<F>,
+        batch_trainer: Option<
+            Arc<tokio::sync::RwLock<crate::training::batch_trainer::BatchTrainer>>,
+        >,
+        local_generator: Option<Arc<tokio::sync::RwLock<crate::local::LocalGenerator>>>,
+        tokenizer: Option<Arc<crate::models::tokenizer::TextTokenizer>>,
+        repl_mode: Option<Arc<tokio::sync::RwLock<crate::cli::ReplMode>>>,
+        plan_content: Option<Arc<tokio::sync::RwLock<Option<String>>>>,
+    ) -> Result<ToolResult>
+    where
+        F: Fn() -> Result<()> + Send + Sync,
+    {
+        self.runner()
+            .execute_tool(
+                tool_use,
+                conversation,
+                save_models_fn,
+                batch_trainer,
+                local_generator,
+                tokenizer,
+                repl_mode,
+                plan_content,
+            )
+            .await
+    }
+
+    /// Execute multiple tool uses concurrently, at most `max_concurrent_tools`
+    /// at a time
+    ///
+    /// Results are in the same order as `tool_uses`.
+    #[instrument(skip(
+        self,
+        tool_uses,
+        conversation,
+        save_models_fn,
+        batch_trainer,
+        local_generator,
+        tokenizer
+    ))]
+    pub async fn execute_tool_loop<F>(
+        &self,
+        tool_uses: Vec<ToolUse>,
+        conversation: Option<&ConversationHistory>,
+        save_models_fn: Option<F>,
+        batch_trainer: Option<
+            Arc<tokio::sync::RwLock<crate::training::batch_trainer::BatchTrainer>>,
+        >,
+        local_generator: Option<Arc<tokio::sync::RwLock<crate::local::LocalGenerator>>>,
+        tokenizer: Option<Arc<crate::models::tokenizer::TextTokenizer>>,
+        repl_mode: Option<Arc<tokio::sync::RwLock<crate::cli::ReplMode>>>,
+        plan_content: Option<Arc<tokio::sync::RwLock<Option<String>>>>,
+    ) -> Result<Vec<ToolResult>>
+    where
+        F: Fn() -> Result<()> + Send + Sync + Clone,
+    {
+        info!("Executing {} tool(s)", tool_uses.len());
+
+        let runner = self.runner();
+        let executions = tool_uses.iter().map(|tool_use| {
+            runner.execute_tool(
+                tool_use,
+                conversation,
+                save_models_fn.clone(),
+                batch_trainer.clone(),
+                local_generator.clone(),
+                tokenizer.clone(),
+                repl_mode.clone(),
+                plan_content.clone(),
+            )
+        });
+
+        futures::future::join_all(executions).await.into_iter().collect()
+    }
+
+    /// Get reference to registry
+    pub fn registry(&self) -> &ToolRegistry {
+        &self.registry
+    }
+
+    /// Get reference to permissions manager
+    pub fn permissions(&self) -> &PermissionManager {
+        &self.permissions
+    }
+}
+
+impl ToolRunner {
+    /// The concurrency limit these executions share
+    pub fn slots(&self) -> &ToolSlots {
+        &self.slots
+    }
+
+    /// Record executions by this runner as approved by `approval`
+    ///
+    /// Runners start out `NotApproved` (callers that don't check approval).
+    pub fn with_approval(mut self, approval: ApprovalSource) -> Self {
+        self.approval = approval;
+        self
+    }
+
+    fn record_usage(&self, tool_use: &ToolUse) {
+        if let Ok(mut counts) = self.usage_counts.lock() {
+            *counts.entry(tool_use.name.clone()).or_insert(0) += 1;
+        }
+        self.recent_calls.record(tool_use);
+    }
+
+    /// Execute a single tool use, waiting for a free slot first
     pub async fn execute_tool<F>(
         &self,
         tool_use: &ToolUse,
@@ -323,10 +766,122 @@ impl ToolExecutor {
         repl_mode: Option<Arc<tokio::sync::RwLock<crate::cli::ReplMode>>>,
         plan_content: Option<Arc<tokio::sync::RwLock<Option<String>>>>,
     ) -> Result<ToolResult>
+    where
+        F: Fn() -> Result<()> + Send + Sync,
+    {
+        let slot = self.slots.acquire().await;
+        self.execute_tool_in_slot(
+            &slot,
+            tool_use,
+            conversation,
+            save_models_fn,
+            batch_trainer,
+            local_generator,
+            tokenizer,
+            repl_mode,
+            plan_content,
+        )
+        .await
+    }
+
+    /// Execute a single tool use in a slot the caller already holds
+    ///
+    /// Lets callers wait for a slot outside their own timeout.
+    #[instrument(skip(self, _slot, tool_use, conversation, save_models_fn, batch_trainer, local_generator, tokenizer), fields(tool = %tool_use.name, id = %tool_use.id))]
+    pub async fn execute_tool_in_slot<F>(
+        &self,
+        _slot: &ToolSlot,
+        tool_use: &ToolUse,
+        conversation: Option<&ConversationHistory>,
+        save_models_fn: Option<F>,
+        batch_trainer: Option<
+            Arc<tokio::sync::RwLock<crate::training::batch_trainer::BatchTrainer>>,
+        >,
+        local_generator: Option<Arc<tokio::sync::RwLock<crate::local::LocalGenerator>>>,
+        tokenizer: Option<Arc<crate::models::tokenizer::TextTokenizer>>,
+        repl_mode: Option<Arc<tokio::sync::RwLock<crate::cli::ReplMode>>>,
+        plan_content: Option<Arc<tokio::sync::RwLock<Option<String>>>>,
+    ) -> Result<ToolResult>
     where
         F: Fn() -> Result<()> + Send + Sync,
     {
         info!("Executing tool: {}", tool_use.name);
+        // /dryrun: bash reports its command instead of running it
+        let tool_use = if tool_use.name == "bash" && self.bash_dry_run.load(Ordering::SeqCst) {
+            let mut dry_run = tool_use.clone();
+            dry_run.input["dry_run"] = serde_json::Value::Bool(true);
+            std::borrow::Cow::Owned(dry_run)
+        } else {
+            std::borrow::Cow::Borrowed(tool_use)
+        };
+        let tool_use = tool_use.as_ref();
+        self.record_usage(tool_use);
+
+        let pending = self.session_changes.begin(tool_use);
+        let started = std::time::Instant::now();
+        let execution = self.execute_tool_inner(
+            tool_use,
+            conversation,
+            save_models_fn,
+            batch_trainer,
+            local_generator,
+            tokenizer,
+            repl_mode,
+            plan_content,
+        );
+        let result = match self.timeouts.for_tool(&tool_use.name) {
+            Some(limit) => match tokio::time::timeout(limit, execution).await {
+                Ok(result) => result,
+                Err(_) => {
+                    warn!("Tool '{}' timed out after {}s", tool_use.name, limit.as_secs());
+                    Ok(ToolResult::error(
+                        tool_use.id.clone(),
+                        format!(
+                            "Tool '{}' timed out after {}s (raise [tools.timeouts] {} in config.toml if it needs longer)",
+                            tool_use.name,
+                            limit.as_secs(),
+                            tool_use.name
+                        ),
+                    ))
+                }
+            },
+            None => execution.await,
+        };
+        self.session_changes.finish(pending, &result);
+        if let Some(audit) = &self.audit {
+            audit.record(tool_use, &self.approval, &result, started.elapsed());
+        }
+        result
+    }
+
+    async fn execute_tool_inner<F>(
+        &self,
+        tool_use: &ToolUse,
+        conversation: Option<&ConversationHistory>,
+        save_models_fn: Option<F>,
+        batch_trainer: Option<
+            Arc<tokio::sync::RwLock<crate::training::batch_trainer::BatchTrainer>>,
+        >,
+        local_generator: Option<Arc<tokio::sync::RwLock<crate::local::LocalGenerator>>>,
+        tokenizer: Option<Arc<crate::models::tokenizer::TextTokenizer>>,
+        repl_mode: Option<Arc<tokio::sync::RwLock<crate::cli::ReplMode>>>,
+        plan_content: Option<Arc<tokio::sync::RwLock<Option<String>>>>,
+    ) -> Result<ToolResult>
+    where
+        F: Fn() -> Result<()> + Send + Sync,
+    {
+
+        // Disabled tools are never offered, but refuse one that slips through
+        if self.registry.is_disabled(&tool_use.name) {
+            warn!("Refusing disabled tool '{}'", tool_use.name);
+            return Ok(ToolResult::error(
+                tool_use.id.clone(),
+                format!(
+                    "Tool '{}' is disabled ([tools] disabled in config.toml)",
+                    tool_use.name
+                ),
+            ));
+        }
 
         // 1. Check if it's an MCP tool
         if tool_use.name.starts_with("mcp_") {
@@ -369,8 +924,11 @@ impl ToolExecutor {
             PermissionCheck::Allow => {
                 debug!("Tool execution allowed");
             }
+            PermissionCheck::AskUser(_) if self.approval != ApprovalSource::NotApproved => {
+                debug!("Tool execution approved ({:?})", self.approval);
+            }
             PermissionCheck::AskUser(reason) => {
-                // For now, deny if ask required (will implement user prompts in Phase 2)
+                // Callers that don't ask the user (local generation, tool loops) can't run these
                 error!("Tool execution requires user confirmation: {}", reason);
                 return Ok(ToolResult::error(
                     tool_use.id.clone(),
@@ -388,15 +946,16 @@ impl ToolExecutor {
             let current_mode = mode.read().await;
             if let crate::cli::ReplMode::Planning { .. } = &*current_mode {
                 // In planning mode, only allow read-only tools
-                let allowed_tools = ["read", "glob", "grep", "web_fetch", "enter_plan_mode", "present_plan"];
-                if !allowed_tools.contains(&tool_use.name.as_str()) {
+                // (bash dry runs included, since they only describe a command)
+                let allowed_tools = ["read", "glob", "grep", "list_dir", "web_fetch", "web_search", "enter_plan_mode", "present_plan"];
+                if !allowed_tools.contains(&tool_use.name.as_str()) && !is_bash_dry_run(tool_use, false) {
                     drop(current_mode);
                     warn!("Tool '{}' blocked in planning mode", tool_use.name);
                     return Ok(ToolResult::error(
                         tool_use.id.clone(),
                         format!(
                             "Tool '{}' is not allowed in planning mode.\n\
-                             Available tools: read, glob, grep, web_fetch\n\
+                             Available tools: read, glob, grep, list_dir, web_fetch, bash (dry_run only)\n\
                              Use PresentPlan to show your plan for approval.",
                             tool_use.name
                         ),
@@ -433,65 +992,6 @@ impl ToolExecutor {
             }
         }
     }
-
-    /// Execute multiple tool uses in sequence
-    #[instrument(skip(
-        self,
-        tool_uses,
-        conversation,
-        save_models_fn,
-        batch_trainer,
-        local_generator,
-        tokenizer
-    ))]
-    pub async fn execute_tool_loop<F>(
-        &self,
-        tool_uses: Vec<ToolUse>,
-        conversation: Option<&ConversationHistory>,
-        save_models_fn: Option<F>,
-        batch_trainer: Option<
-            Arc<tokio::sync::RwLock<crate::training::batch_trainer::BatchTrainer>>,
-        >,
-        local_generator: Option<Arc<tokio::sync::RwLock<crate::local::LocalGenerator>>>,
-        tokenizer: Option<Arc<crate::models::tokenizer::TextTokenizer>>,
-        repl_mode: Option<Arc<tokio::sync::RwLock<crate::cli::ReplMode>>>,
-        plan_content: Option<Arc<tokio::sync::RwLock<Option<String>>>>,
-    ) -> Result<Vec<ToolResult>>
-    where
-        F: Fn() -> Result<()> + Send + Sync + Clone,
-    {
-        info!("Executing {} tool(s)", tool_uses.len());
-
-        let mut results = Vec::new();
-
-        for tool_use in tool_uses {
-            let result = self
-                .execute_tool(
-                    &tool_use,
-                    conversation,
-                    save_models_fn.clone(),
-                    batch_trainer.clone(),
-                    local_generator.clone(),
-                    tokenizer.clone(),
-                    repl_mode.clone(),
-                    plan_content.clone(),
-                )
-                .await?;
-            results.push(result);
-        }
-
-        Ok(results)
-    }
-
-    /// Get reference to registry
-    pub fn registry(&self) -> &ToolRegistry {
-        &self.registry
-    }
-
-    /// Get reference to permissions manager
-    pub fn permissions(&self) -> &PermissionManager {
-        &self.permissions
-    }
 }
 
 /// Generate a context-specific signature for a tool use
@@ -526,6 +1026,17 @@ pub fn generate_tool_signature(tool_use: &ToolUse, working_dir: &std::path::Path
                 directory: Some(working_dir.display().to_string()),
             }
         }
+        "write" | "edit" => {
+            let file_path = tool_use.input["file_path"].as_str().unwrap_or("");
+            let verb = if tool_use.name == "write" { "writing" } else { "editing" };
+            ToolSignature {
+                tool_name: tool_use.name.clone(),
+                context_key: format!("{} {}", verb, file_path),
+                command: None,
+                args: None,
+                directory: Some(working_dir.display().to_string()),
+            }
+        }
         "glob" => {
             let pattern = tool_use.input["pattern"].as_str().unwrap_or("");
             ToolSignature {
@@ -536,6 +1047,16 @@ pub fn generate_tool_signature(tool_use: &ToolUse, working_dir: &std::path::Path
                 directory: Some(working_dir.display().to_string()),
             }
         }
+        "list_dir" => {
+            let path = tool_use.input.get("path").and_then(|v| v.as_str()).unwrap_or(".");
+            ToolSignature {
+                tool_name: "list_dir".to_string(),
+                context_key: format!("listing {}", path),
+                command: None,
+                args: None,
+                directory: Some(working_dir.display().to_string()),
+            }
+        }
         "grep" => {
             let pattern = tool_use.input["pattern"].as_str().unwrap_or("");
             let path = tool_use
@@ -561,6 +1082,16 @@ pub fn generate_tool_signature(tool_use: &ToolUse, working_dir: &std::path::Path
                 directory: None,
             }
         }
+        "web_search" => {
+            let query = tool_use.input["query"].as_str().unwrap_or("");
+            ToolSignature {
+                tool_name: "web_search".to_string(),
+                context_key: format!("searching for '{}'", query),
+                command: None,
+                args: None,
+                directory: None,
+            }
+        }
         "save_and_exec" => {
             let command = tool_use.input["command"].as_str().unwrap_or("");
 
@@ -594,11 +1125,7 @@ pub fn generate_tool_signature(tool_use: &ToolUse, working_dir: &std::path::Path
         "query_local_model" => {
             // Extract query if present
             let query = tool_use.input["query"].as_str().unwrap_or("");
-            let truncated_query = if query.len() > 50 {
-                format!("{}...", &query[..50])
-            } else {
-                query.to_string()
-            };
+            let truncated_query = crate::text::ellipsize(query, 50);
             ToolSignature {
                 tool_name: tool_use.name.clone(),
                 context_key: format!("query_local_model: {}", truncated_query),
@@ -715,6 +1242,36 @@ mod tests {
         ToolExecutor::new(registry, permissions, temp_path).expect("Failed to create test executor")
     }
 
+    #[test]
+    fn test_task_scoped_approvals_clear_on_task_change() {
+        let mut executor = create_test_executor(true, false);
+        let sig = ToolSignature {
+            tool_name: "bash".to_string(),
+            context_key: "task-scope-test: cargo test".to_string(),
+            command: Some("cargo test".to_string()),
+            args: None,
+            directory: None,
+        };
+
+        executor.set_task_scope(Some("plan-a".to_string()));
+        executor.approve_exact_task(sig.clone());
+        assert_eq!(executor.is_approved(&sig), ApprovalSource::TaskExact);
+        assert_eq!(executor.approval_counts("bash").task, 1);
+
+        // Same task: approval survives
+        executor.set_task_scope(Some("plan-a".to_string()));
+        assert_eq!(executor.is_approved(&sig), ApprovalSource::TaskExact);
+
+        // Task ended: approval is gone
+        executor.set_task_scope(None);
+        assert_eq!(executor.is_approved(&sig), ApprovalSource::NotApproved);
+
+        executor.set_task_scope(Some("plan-b".to_string()));
+        executor.approve_exact_task(sig.clone());
+        executor.clear_task_approvals();
+        assert_eq!(executor.is_approved(&sig), ApprovalSource::NotApproved);
+    }
+
     #[tokio::test]
     async fn test_execute_tool_success() {
         let executor = create_test_executor(true, false);
@@ -739,6 +1296,34 @@ mod tests {
         assert!(result.content.contains("Mock result"));
     }
 
+    #[tokio::test]
+    async fn test_ask_rule_needs_caller_approval() {
+        let mut registry = ToolRegistry::new();
+        registry.register(Box::new(MockTool { should_fail: false }));
+        let temp_path = std::env::temp_dir().join("shammah_test_patterns.json");
+        let executor = ToolExecutor::new(registry, PermissionManager::new(), temp_path).unwrap();
+        let tool_use = ToolUse::new("mock".to_string(), serde_json::json!({"param": "value"}));
+
+        let run = |approval: ApprovalSource| {
+            let runner = executor.runner().with_approval(approval);
+            let tool_use = tool_use.clone();
+            async move {
+                runner
+                    .execute_tool(&tool_use, None, None::<fn() -> Result<()>>, None, None, None, None, None)
+                    .await
+                    .unwrap()
+            }
+        };
+
+        let unapproved = run(ApprovalSource::NotApproved).await;
+        assert!(unapproved.is_error);
+        assert!(unapproved.content.contains("Permission required"));
+
+        // The REPL asked the user (or found a saved approval)
+        assert!(!run(ApprovalSource::User).await.is_error);
+        assert!(!run(ApprovalSource::SessionExact).await.is_error);
+    }
+
     #[tokio::test]
     async fn test_execute_tool_not_found() {
         let executor = create_test_executor(true, false);
@@ -763,6 +1348,32 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("not found"));
     }
 
+    #[tokio::test]
+    async fn test_execute_disabled_tool() {
+        let registry = ToolRegistry::new().with_disabled(vec!["mock".to_string()]);
+        let permissions = PermissionManager::new()
+            .with_default_rule(crate::tools::permissions::PermissionRule::Allow);
+        let temp_path = std::env::temp_dir().join("shammah_test_patterns.json");
+        let executor = ToolExecutor::new(registry, permissions, temp_path).unwrap();
+        let tool_use = ToolUse::new("mock".to_string(), serde_json::json!({}));
+
+        let result = executor
+            .execute_tool(
+                &tool_use,
+                None,
+                None::<fn() -> Result<()>>,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        assert!(result.is_error);
+        assert!(result.content.contains("disabled"));
+    }
+
     #[tokio::test]
     async fn test_execute_tool_permission_denied() {
         let executor = create_test_executor(false, false);
@@ -836,6 +1447,124 @@ mod tests {
         assert_eq!(results.len(), 2);
         assert!(!results[0].is_error);
         assert!(!results[1].is_error);
+        assert_eq!(executor.usage_count("mock"), 2);
+        assert_eq!(executor.usage_count("bash"), 0);
+    }
+
+    // Sleeps briefly and records how many copies ran at the same time
+    struct SlowTool {
+        running: Arc<AtomicUsize>,
+        peak: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Tool for SlowTool {
+        fn name(&self) -> &str {
+            "slow"
+        }
+
+        fn description(&self) -> &str {
+            "A slow mock tool"
+        }
+
+        fn input_schema(&self) -> ToolInputSchema {
+            ToolInputSchema::simple(vec![])
+        }
+
+        async fn execute(&self, _input: Value, _context: &ToolContext<'_>) -> Result<String> {
+            let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(running, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            self.running.fetch_sub(1, Ordering::SeqCst);
+            Ok("done".to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_max_concurrent_tools_caps_execution() {
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let mut registry = ToolRegistry::new();
+        registry.register(Box::new(SlowTool {
+            running: Arc::clone(&running),
+            peak: Arc::clone(&peak),
+        }));
+        let executor = ToolExecutor::new(
+            registry,
+            PermissionManager::new().with_default_rule(crate::tools::permissions::PermissionRule::Allow),
+            std::env::temp_dir().join("shammah_test_slots_patterns.json"),
+        )
+        .unwrap()
+        .with_max_concurrent_tools(2);
+
+        let tool_uses: Vec<ToolUse> = (0..8)
+            .map(|_| ToolUse::new("slow".to_string(), json!({})))
+            .collect();
+        let results = executor
+            .execute_tool_loop(
+                tool_uses.clone(),
+                None,
+                None::<fn() -> Result<()>>,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 8);
+        assert!(results.iter().zip(&tool_uses).all(|(r, t)| r.tool_use_id == t.id && !r.is_error));
+        // Tools ran in parallel, but never more than two at once
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+        assert_eq!(executor.slots().in_flight(), 0);
+        assert_eq!(executor.slots().limit(), 2);
+    }
+
+    struct HangTool;
+
+    #[async_trait]
+    impl Tool for HangTool {
+        fn name(&self) -> &str {
+            "hang"
+        }
+
+        fn description(&self) -> &str {
+            "A mock tool that never finishes"
+        }
+
+        fn input_schema(&self) -> ToolInputSchema {
+            ToolInputSchema::simple(vec![])
+        }
+
+        async fn execute(&self, _input: Value, _context: &ToolContext<'_>) -> Result<String> {
+            tokio::time::sleep(std::time::Duration::from_secs(600)).await;
+            Ok("done".to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tool_timeout_returns_error() {
+        let mut registry = ToolRegistry::new();
+        registry.register(Box::new(HangTool));
+        let executor = ToolExecutor::new(
+            registry,
+            PermissionManager::new().with_default_rule(crate::tools::permissions::PermissionRule::Allow),
+            std::env::temp_dir().join("shammah_test_timeout_patterns.json"),
+        )
+        .unwrap()
+        .with_tool_timeouts(ToolTimeouts::new(HashMap::from([("hang".to_string(), 1)])));
+
+        let tool_use = ToolUse::new("hang".to_string(), json!({}));
+        let result = executor
+            .execute_tool(&tool_use, None, None::<fn() -> Result<()>>, None, None, None, None, None)
+            .await
+            .unwrap();
+
+        assert!(result.is_error);
+        assert!(result.content.contains("Tool 'hang' timed out after 1s"));
+        assert_eq!(executor.slots().in_flight(), 0);
     }
 
     #[test]
@@ -898,9 +1627,12 @@ mod tests {
         executor.approve_exact_session(sig.clone());
         assert_eq!(executor.is_approved(&sig), ApprovalSource::SessionExact);
 
+        assert_eq!(executor.approval_counts("bash").session, 1);
+
         // Clear session approvals
         executor.clear_session_approvals();
         assert_eq!(executor.is_approved(&sig), ApprovalSource::NotApproved);
+        assert_eq!(executor.approval_counts("bash").session, 0);
     }
 
     #[test]
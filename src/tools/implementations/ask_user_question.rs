@@ -39,6 +39,8 @@ impl Tool for AskUserQuestionTool {
          \
          Supports single-select, multi-select, and automatic 'Other' option \
          for free-form text input. Can ask 1-4 questions at once. \
+         The user may decline to answer (status \"cancelled\"); if so, continue \
+         with sensible defaults rather than asking the same question again. \
          \
          Available in all modes including plan mode."
     }
@@ -16,14 +16,24 @@ impl Tool for BashTool {
     }
 
     fn description(&self) -> &str {
-        "Execute bash commands. Use for terminal operations like git, npm, ls, etc."
+        "Execute bash commands. Use for terminal operations like git, npm, ls, etc.\n\
+         Set dry_run to true to show a command without running it: the result is the \
+         command and the directory it would run in. Use dry runs to propose commands \
+         (e.g. while planning) before running them for real. The user can turn on \
+         dry-run for the whole session with /dryrun; results then start with \
+         \"Dry run\" and nothing was executed."
     }
 
     fn input_schema(&self) -> ToolInputSchema {
-        ToolInputSchema::simple(vec![
+        let mut schema = ToolInputSchema::simple(vec![
             ("command", "The bash command to execute"),
             ("description", "Brief description of what this command does"),
-        ])
+        ]);
+        schema.properties["dry_run"] = serde_json::json!({
+            "type": "boolean",
+            "description": "Return the command and working directory instead of running it (default: false)"
+        });
+        schema
     }
 
     async fn execute(&self, input: Value, _context: &ToolContext<'_>) -> Result<String> {
@@ -31,6 +41,15 @@ impl Tool for BashTool {
             .as_str()
             .context("Missing command parameter")?;
 
+        if is_dry_run(&input) {
+            let cwd = std::env::current_dir().context("Failed to get working directory")?;
+            return Ok(format!(
+                "Dry run - not executed\nCommand: {}\nWorking directory: {}",
+                command,
+                cwd.display()
+            ));
+        }
+
         let output = Command::new("bash")
             .arg("-c")
             .arg(command)
@@ -74,6 +93,11 @@ impl Tool for BashTool {
     }
 }
 
+/// Whether a bash tool input asks for a dry run
+pub fn is_dry_run(input: &Value) -> bool {
+    input["dry_run"].as_bool().unwrap_or(false)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -149,4 +173,34 @@ mod tests {
         let output = result.unwrap();
         assert!(output.contains("Exit code:") || output.contains("STDERR"));
     }
+
+    #[tokio::test]
+    async fn test_bash_dry_run() {
+        let tool = BashTool;
+        let marker = std::env::temp_dir().join("shammah_bash_dry_run_marker");
+        let _ = std::fs::remove_file(&marker);
+        let input = serde_json::json!({
+            "command": format!("touch {}", marker.display()),
+            "description": "Create a marker file",
+            "dry_run": true
+        });
+
+        let context = crate::tools::types::ToolContext {
+            conversation: None,
+            save_models: None,
+            batch_trainer: None,
+            local_generator: None,
+            tokenizer: None,
+            repl_mode: None,
+            plan_content: None,
+        };
+
+        let output = tool.execute(input, &context).await.unwrap();
+        assert!(output.starts_with("Dry run"));
+        assert!(output.contains("touch "));
+        assert!(output.contains(&std::env::current_dir().unwrap().display().to_string()));
+        assert!(!marker.exists());
+        assert!(tool.input_schema().properties["dry_run"].is_object());
+        assert!(!tool.input_schema().required.contains(&"dry_run".to_string()));
+    }
 }
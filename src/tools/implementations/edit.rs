@@ -0,0 +1,151 @@
+// Edit tool - exact string replacement in a file
+
+use crate::tools::registry::Tool;
+use crate::tools::types::{ToolContext, ToolInputSchema};
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use serde_json::Value;
+use std::fs;
+
+pub struct EditTool;
+
+/// Replace `old_string` with `new_string` in `contents`
+///
+/// Fails if `old_string` is missing, or appears more than once and
+/// `replace_all` isn't set. Returns the new contents and the number of
+/// replacements.
+fn replace(
+    contents: &str,
+    old_string: &str,
+    new_string: &str,
+    replace_all: bool,
+) -> Result<(String, usize)> {
+    if old_string.is_empty() {
+        bail!("old_string must not be empty (use the write tool to create a file)");
+    }
+    if old_string == new_string {
+        bail!("old_string and new_string are identical; nothing to change");
+    }
+
+    let count = contents.matches(old_string).count();
+    match count {
+        0 => bail!("old_string not found in file (it must match exactly, including whitespace)"),
+        1 => Ok((contents.replacen(old_string, new_string, 1), 1)),
+        _ if replace_all => Ok((contents.replace(old_string, new_string), count)),
+        _ => bail!(
+            "old_string appears {} times in file; include more surrounding context to make it \
+             unique, or set replace_all to replace every occurrence",
+            count
+        ),
+    }
+}
+
+#[async_trait]
+impl Tool for EditTool {
+    fn name(&self) -> &str {
+        "edit"
+    }
+
+    fn description(&self) -> &str {
+        "Edit a file by replacing an exact string. old_string must appear exactly once \
+         unless replace_all is true. Read the file first so old_string matches exactly."
+    }
+
+    fn input_schema(&self) -> ToolInputSchema {
+        let mut schema = ToolInputSchema::simple(vec![
+            ("file_path", "Absolute path to the file to edit"),
+            ("old_string", "The exact text to replace"),
+            ("new_string", "The text to replace it with"),
+        ]);
+        schema.properties["replace_all"] = serde_json::json!({
+            "type": "boolean",
+            "description": "Replace every occurrence of old_string (default false)"
+        });
+        schema
+    }
+
+    async fn execute(&self, input: Value, _context: &ToolContext<'_>) -> Result<String> {
+        let file_path = input["file_path"]
+            .as_str()
+            .context("Missing file_path parameter")?;
+        let old_string = input["old_string"]
+            .as_str()
+            .context("Missing old_string parameter")?;
+        let new_string = input["new_string"]
+            .as_str()
+            .context("Missing new_string parameter")?;
+        let replace_all = input["replace_all"].as_bool().unwrap_or(false);
+
+        let contents = fs::read_to_string(file_path)
+            .with_context(|| format!("Failed to read file: {}", file_path))?;
+        let (updated, count) = replace(&contents, old_string, new_string, replace_all)
+            .with_context(|| format!("Failed to edit {}", file_path))?;
+        fs::write(file_path, updated)
+            .with_context(|| format!("Failed to write file: {}", file_path))?;
+
+        Ok(format!(
+            "Edited {} ({} replacement{})",
+            file_path,
+            count,
+            if count == 1 { "" } else { "s" }
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replace() {
+        let (updated, count) =
+            replace("let a = 1;\nlet b = 2;\n", "b = 2", "b = 3", false).unwrap();
+        assert_eq!(updated, "let a = 1;\nlet b = 3;\n");
+        assert_eq!(count, 1);
+
+        let error = replace("x x", "x", "y", false).unwrap_err();
+        assert!(error.to_string().contains("appears 2 times"));
+        assert_eq!(
+            replace("x x", "x", "y", true).unwrap(),
+            ("y y".to_string(), 2)
+        );
+
+        assert!(replace("abc", "z", "y", false)
+            .unwrap_err()
+            .to_string()
+            .contains("not found"));
+        assert!(replace("abc", "a", "a", false).is_err());
+        assert!(replace("abc", "", "a", false).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_edit_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("main.rs");
+        fs::write(&path, "fn main() {\n    println!(\"hi\");\n}\n").unwrap();
+
+        let context = ToolContext {
+            conversation: None,
+            save_models: None,
+            batch_trainer: None,
+            local_generator: None,
+            tokenizer: None,
+            repl_mode: None,
+            plan_content: None,
+        };
+        let input = serde_json::json!({
+            "file_path": path.display().to_string(),
+            "old_string": "\"hi\"",
+            "new_string": "\"hello\"",
+        });
+
+        let result = EditTool.execute(input.clone(), &context).await.unwrap();
+        assert!(result.ends_with("(1 replacement)"));
+        assert!(fs::read_to_string(&path).unwrap().contains("\"hello\""));
+
+        // Already applied: old_string is gone, and the error says which file
+        let error = EditTool.execute(input, &context).await.unwrap_err();
+        assert!(format!("{:#}", error).contains("not found"));
+        assert!(error.to_string().contains("main.rs"));
+    }
+}
@@ -17,7 +17,7 @@ impl Tool for EnterPlanModeTool {
     fn description(&self) -> &str {
         "Enter read-only planning mode to explore the codebase before making changes. \
          Use this when you need to research and develop an implementation plan. \
-         In plan mode, only read-only tools (Read, Glob, Grep, WebFetch) and \
+         In plan mode, only read-only tools (Read, Glob, Grep, ListDir, WebFetch) and \
          AskUserQuestion are available. Use AskUserQuestion to clarify requirements \
          with the user. When ready, use PresentPlan to show your plan."
     }
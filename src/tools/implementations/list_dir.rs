@@ -0,0 +1,173 @@
+// ListDir tool - lists directory entries with their type and size
+
+use crate::tools::registry::Tool;
+use crate::tools::types::{ToolContext, ToolInputSchema};
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use serde_json::Value;
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// Depth for recursive listings without an explicit max_depth
+const DEFAULT_RECURSIVE_DEPTH: usize = 3;
+
+/// Hard cap on max_depth
+const MAX_DEPTH: usize = 10;
+
+/// Entries returned before the listing is truncated
+const MAX_ENTRIES: usize = 200;
+
+/// Directories that are never listed or descended into
+const IGNORED_DIRS: &[&str] = &[".git"];
+
+pub struct ListDirTool;
+
+#[async_trait]
+impl Tool for ListDirTool {
+    fn name(&self) -> &str {
+        "list_dir"
+    }
+
+    fn description(&self) -> &str {
+        "List the entries of a directory with their type (file/dir/symlink) and size in bytes. \
+         Set recursive to include subdirectories (up to max_depth levels)."
+    }
+
+    fn input_schema(&self) -> ToolInputSchema {
+        let mut schema = ToolInputSchema::simple(vec![]);
+        schema.properties["path"] = serde_json::json!({
+            "type": "string",
+            "description": "Directory to list (default: current directory)"
+        });
+        schema.properties["recursive"] = serde_json::json!({
+            "type": "boolean",
+            "description": "Include subdirectories (default false)"
+        });
+        schema.properties["max_depth"] = serde_json::json!({
+            "type": "integer",
+            "description": format!(
+                "Levels to descend when recursive (default {}, at most {})",
+                DEFAULT_RECURSIVE_DEPTH, MAX_DEPTH
+            )
+        });
+        schema
+    }
+
+    async fn execute(&self, input: Value, _context: &ToolContext<'_>) -> Result<String> {
+        let path = input["path"].as_str().unwrap_or(".");
+        let recursive = input["recursive"].as_bool().unwrap_or(false);
+        let max_depth = if recursive {
+            input["max_depth"]
+                .as_u64()
+                .map_or(DEFAULT_RECURSIVE_DEPTH, |depth| depth as usize)
+                .clamp(1, MAX_DEPTH)
+        } else {
+            1
+        };
+
+        let root = Path::new(path);
+        if !root.is_dir() {
+            bail!("Not a directory: {}", path);
+        }
+
+        let mut lines = Vec::new();
+        let walker = WalkDir::new(root)
+            .min_depth(1)
+            .max_depth(max_depth)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| {
+                !(entry.file_type().is_dir()
+                    && IGNORED_DIRS.iter().any(|name| entry.file_name() == *name))
+            });
+
+        for entry in walker {
+            let entry = entry.with_context(|| format!("Failed to list {}", path))?;
+            if lines.len() >= MAX_ENTRIES {
+                lines.push(format!("... (truncated to {} entries)", MAX_ENTRIES));
+                break;
+            }
+
+            let relative = entry.path().strip_prefix(root).unwrap_or(entry.path());
+            let file_type = entry.file_type();
+            let line = if file_type.is_symlink() {
+                let target = std::fs::read_link(entry.path())
+                    .map(|target| target.display().to_string())
+                    .unwrap_or_else(|_| "?".to_string());
+                format!("symlink  {} -> {}", relative.display(), target)
+            } else if file_type.is_dir() {
+                format!("dir      {}/", relative.display())
+            } else {
+                let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+                format!("file     {} ({} bytes)", relative.display(), size)
+            };
+            lines.push(line);
+        }
+
+        if lines.is_empty() {
+            Ok(format!("{} is empty.", path))
+        } else {
+            Ok(lines.join("\n"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn context() -> ToolContext<'static> {
+        ToolContext {
+            conversation: None,
+            save_models: None,
+            batch_trainer: None,
+            local_generator: None,
+            tokenizer: None,
+            repl_mode: None,
+            plan_content: None,
+        }
+    }
+
+    fn fixture() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("a.txt"), "hello").unwrap();
+        std::fs::create_dir_all(dir.path().join("src/nested")).unwrap();
+        std::fs::write(dir.path().join("src/main.rs"), "").unwrap();
+        std::fs::write(dir.path().join("src/nested/deep.rs"), "").unwrap();
+        std::fs::create_dir(dir.path().join(".git")).unwrap();
+        std::fs::write(dir.path().join(".git/HEAD"), "ref").unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_list_dir_top_level() {
+        let dir = fixture();
+        let input = serde_json::json!({ "path": dir.path().display().to_string() });
+
+        let listing = ListDirTool.execute(input, &context()).await.unwrap();
+        assert_eq!(listing, "file     a.txt (5 bytes)\ndir      src/");
+    }
+
+    #[tokio::test]
+    async fn test_list_dir_recursive_depth() {
+        let dir = fixture();
+        let input = serde_json::json!({
+            "path": dir.path().display().to_string(),
+            "recursive": true,
+            "max_depth": 2,
+        });
+
+        let listing = ListDirTool.execute(input, &context()).await.unwrap();
+        assert!(listing.contains("file     src/main.rs (0 bytes)"));
+        assert!(listing.contains("dir      src/nested/"));
+        assert!(!listing.contains("deep.rs"));
+        assert!(!listing.contains(".git"));
+    }
+
+    #[tokio::test]
+    async fn test_list_dir_not_a_directory() {
+        let input = serde_json::json!({ "path": "Cargo.toml" });
+        assert!(ListDirTool.execute(input, &context()).await.is_err());
+    }
+}
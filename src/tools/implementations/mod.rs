@@ -5,10 +5,16 @@
 // Read-only tools
 pub mod glob;
 pub mod grep;
+pub mod list_dir;
 pub mod read;
 
+// File modification tools
+pub mod edit;
+pub mod write;
+
 // Network tools
 pub mod web_fetch;
+pub mod web_search;
 
 // Command execution
 pub mod bash;
@@ -31,14 +37,18 @@ pub mod gui;
 // Re-exports for convenience
 pub use ask_user_question::AskUserQuestionTool;
 pub use bash::BashTool;
+pub use edit::EditTool;
 pub use enter_plan_mode::EnterPlanModeTool;
 pub use glob::GlobTool;
 pub use grep::GrepTool;
+pub use list_dir::ListDirTool;
 pub use present_plan::PresentPlanTool;
 pub use read::ReadTool;
 pub use restart::RestartTool;
 pub use save_and_exec::SaveAndExecTool;
 pub use web_fetch::WebFetchTool;
+pub use web_search::{SearchProvider, WebSearchConfig, WebSearchTool};
+pub use write::WriteTool;
 
 #[cfg(target_os = "macos")]
 pub use gui::{GuiClickTool, GuiInspectTool, GuiTypeTool};
@@ -78,7 +78,7 @@ impl Tool for PresentPlanTool {
              • **Request changes** with feedback for you to revise\n\
              • **Reject** it to exit plan mode\n\n\
              ⏸️  You are in **read-only planning mode** until the user approves.\n\
-             Only exploration tools (Read, Glob, Grep, WebFetch) are available.\n\n\
+             Only exploration tools (Read, Glob, Grep, ListDir, WebFetch) are available.\n\n\
              If the user requests changes, revise the plan and call PresentPlan again with the updated version.",
             plan_content,
             plan_path.display()
@@ -7,8 +7,18 @@ use async_trait::async_trait;
 use serde_json::Value;
 use std::fs;
 
+/// Characters returned to the model before the file is truncated
+const MAX_TOOL_OUTPUT_CHARS: usize = 10_000;
+
 pub struct ReadTool;
 
+impl ReadTool {
+    /// Read a whole file (no truncation), for commands that chunk it themselves
+    pub fn read_file(file_path: &str) -> Result<String> {
+        fs::read_to_string(file_path).with_context(|| format!("Failed to read file: {}", file_path))
+    }
+}
+
 #[async_trait]
 impl Tool for ReadTool {
     fn name(&self) -> &str {
@@ -28,15 +38,15 @@ impl Tool for ReadTool {
             .as_str()
             .context("Missing file_path parameter")?;
 
-        let contents = fs::read_to_string(file_path)
-            .with_context(|| format!("Failed to read file: {}", file_path))?;
+        let contents = Self::read_file(file_path)?;
 
         // Limit output to first 10,000 chars to avoid token explosion
-        if contents.len() > 10_000 {
+        let truncated = crate::text::truncate_chars(&contents, MAX_TOOL_OUTPUT_CHARS);
+        if truncated.len() < contents.len() {
             Ok(format!(
                 "{}\n\n[File truncated - showing first 10,000 characters of {}]",
-                &contents[..10_000],
-                contents.len()
+                truncated,
+                contents.chars().count()
             ))
         } else {
             Ok(contents)
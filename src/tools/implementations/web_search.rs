@@ -0,0 +1,316 @@
+// WebSearch tool - finds URLs for a query through a configured search API
+//
+// The backend is set under `[tools.web_search]` in config.toml: Brave Search
+// (needs `api_key`) or a SearXNG instance (`base_url`, no key, self-hostable).
+// Without a provider the tool fails with instructions instead of returning
+// an empty result list.
+
+use crate::tools::registry::Tool;
+use crate::tools::types::{ToolContext, ToolInputSchema};
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+const BRAVE_SEARCH_URL: &str = "https://api.search.brave.com/res/v1/web/search";
+/// Results returned when the model doesn't ask for a count
+const DEFAULT_MAX_RESULTS: usize = 5;
+/// Upper bound on `max_results`
+const MAX_RESULTS_LIMIT: usize = 20;
+
+/// Search backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchProvider {
+    Brave,
+    Searxng,
+}
+
+/// `[tools.web_search]` settings
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebSearchConfig {
+    /// Search backend (unset: web_search reports that it isn't configured)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<SearchProvider>,
+    /// API key (Brave)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    /// Instance URL (SearXNG, e.g. http://localhost:8888)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+}
+
+impl WebSearchConfig {
+    /// API key with `${VAR}` references resolved
+    pub fn resolved_api_key(&self) -> Option<String> {
+        self.api_key.as_deref().map(crate::config::expand_env_vars)
+    }
+}
+
+/// One search hit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResult {
+    pub title: String,
+    pub url: String,
+    pub snippet: String,
+}
+
+pub struct WebSearchTool {
+    client: reqwest::Client,
+    config: WebSearchConfig,
+}
+
+impl WebSearchTool {
+    pub fn new(config: WebSearchConfig) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(10))
+                .user_agent("Shammah/0.1.0")
+                .build()
+                .unwrap(),
+            config,
+        }
+    }
+
+    async fn search_brave(&self, query: &str, count: usize) -> Result<Vec<SearchResult>> {
+        let api_key = self
+            .config
+            .resolved_api_key()
+            .context("web_search: provider \"brave\" needs api_key in [tools.web_search]")?;
+        let url = self.config.base_url.as_deref().unwrap_or(BRAVE_SEARCH_URL);
+
+        let response = self
+            .client
+            .get(url)
+            .query(&[("q", query), ("count", &count.to_string())])
+            .header("Accept", "application/json")
+            .header("X-Subscription-Token", &api_key)
+            .send()
+            .await
+            .context("Failed to reach Brave Search")?;
+        let body = json_body(response, "Brave Search").await?;
+        Ok(parse_brave(&body))
+    }
+
+    async fn search_searxng(&self, query: &str) -> Result<Vec<SearchResult>> {
+        let base_url = self
+            .config
+            .base_url
+            .as_deref()
+            .context("web_search: provider \"searxng\" needs base_url in [tools.web_search]")?;
+        let url = format!("{}/search", base_url.trim_end_matches('/'));
+
+        let response = self
+            .client
+            .get(&url)
+            .query(&[("q", query), ("format", "json")])
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach SearXNG at {}", base_url))?;
+        let body = json_body(response, "SearXNG").await?;
+        Ok(parse_searxng(&body))
+    }
+}
+
+/// Read a successful JSON response body
+async fn json_body(response: reqwest::Response, service: &str) -> Result<Value> {
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        bail!("{} returned HTTP {}: {}", service, status, body);
+    }
+    response
+        .json()
+        .await
+        .with_context(|| format!("{} returned invalid JSON", service))
+}
+
+/// Results from a Brave Search response (`web.results[]`)
+fn parse_brave(body: &Value) -> Vec<SearchResult> {
+    results_from(&body["web"]["results"], "description")
+}
+
+/// Results from a SearXNG JSON response (`results[]`)
+fn parse_searxng(body: &Value) -> Vec<SearchResult> {
+    results_from(&body["results"], "content")
+}
+
+fn results_from(results: &Value, snippet_field: &str) -> Vec<SearchResult> {
+    results
+        .as_array()
+        .map(|results| {
+            results
+                .iter()
+                .filter_map(|result| {
+                    Some(SearchResult {
+                        title: strip_tags(result["title"].as_str().unwrap_or_default()),
+                        url: result["url"].as_str()?.to_string(),
+                        snippet: strip_tags(result[snippet_field].as_str().unwrap_or_default()),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Drop inline HTML (Brave wraps matched terms in <strong>)
+fn strip_tags(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+    out.trim().to_string()
+}
+
+/// Numbered title / url / snippet list
+fn format_results(query: &str, results: &[SearchResult]) -> String {
+    if results.is_empty() {
+        return format!("No results for \"{}\"", query);
+    }
+    let mut out = format!("Search results for \"{}\":\n", query);
+    for (i, result) in results.iter().enumerate() {
+        out.push_str(&format!(
+            "\n{}. {}\n   {}\n",
+            i + 1,
+            result.title,
+            result.url
+        ));
+        if !result.snippet.is_empty() {
+            out.push_str(&format!("   {}\n", result.snippet));
+        }
+    }
+    out
+}
+
+#[async_trait]
+impl Tool for WebSearchTool {
+    fn name(&self) -> &str {
+        "web_search"
+    }
+
+    fn description(&self) -> &str {
+        "Search the web. Returns ranked results with title, URL and snippet; \
+         use web_fetch to read a result."
+    }
+
+    fn input_schema(&self) -> ToolInputSchema {
+        let mut schema = ToolInputSchema::simple(vec![("query", "The search query")]);
+        schema.properties["max_results"] = serde_json::json!({
+            "type": "integer",
+            "description": format!(
+                "Number of results (default {}, at most {})",
+                DEFAULT_MAX_RESULTS, MAX_RESULTS_LIMIT
+            )
+        });
+        schema
+    }
+
+    async fn execute(&self, input: Value, _context: &ToolContext<'_>) -> Result<String> {
+        let query = input["query"].as_str().context("Missing query parameter")?;
+        let max_results = input["max_results"]
+            .as_u64()
+            .map(|n| (n as usize).clamp(1, MAX_RESULTS_LIMIT))
+            .unwrap_or(DEFAULT_MAX_RESULTS);
+
+        let mut results = match self.config.provider {
+            Some(SearchProvider::Brave) => self.search_brave(query, max_results).await?,
+            Some(SearchProvider::Searxng) => self.search_searxng(query).await?,
+            None => bail!(
+                "web_search is not configured. Add to ~/.shammah/config.toml:\n\
+                 [tools.web_search]\n\
+                 provider = \"searxng\"  # or \"brave\" with api_key = \"...\"\n\
+                 base_url = \"http://localhost:8888\""
+            ),
+        };
+        results.truncate(max_results);
+        Ok(format_results(query, &results))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_provider_responses() {
+        let brave = serde_json::json!({
+            "web": { "results": [
+                {
+                    "title": "The <strong>Rust</strong> Book",
+                    "url": "https://doc.rust-lang.org/book/",
+                    "description": "Learn <strong>Rust</strong>"
+                },
+                { "title": "No URL" }
+            ]}
+        });
+        assert_eq!(
+            parse_brave(&brave),
+            vec![SearchResult {
+                title: "The Rust Book".to_string(),
+                url: "https://doc.rust-lang.org/book/".to_string(),
+                snippet: "Learn Rust".to_string(),
+            }]
+        );
+
+        let searxng = serde_json::json!({
+            "results": [
+                { "title": "Tokio", "url": "https://tokio.rs", "content": "Async runtime" },
+                { "title": "Serde", "url": "https://serde.rs" }
+            ]
+        });
+        let results = parse_searxng(&searxng);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[1].snippet, "");
+        assert!(parse_searxng(&serde_json::json!({})).is_empty());
+
+        let formatted = format_results("rust", &results);
+        assert!(formatted.contains("1. Tokio\n   https://tokio.rs\n   Async runtime"));
+        assert!(formatted.contains("2. Serde\n   https://serde.rs\n"));
+        assert_eq!(format_results("zzz", &[]), "No results for \"zzz\"");
+    }
+
+    #[tokio::test]
+    async fn test_unconfigured_provider_errors() {
+        let context = ToolContext {
+            conversation: None,
+            save_models: None,
+            batch_trainer: None,
+            local_generator: None,
+            tokenizer: None,
+            repl_mode: None,
+            plan_content: None,
+        };
+        let input = serde_json::json!({ "query": "rust" });
+
+        let error = WebSearchTool::new(WebSearchConfig::default())
+            .execute(input.clone(), &context)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("[tools.web_search]"));
+
+        let config: WebSearchConfig = toml::from_str("provider = \"brave\"").unwrap();
+        assert_eq!(config.provider, Some(SearchProvider::Brave));
+        let error = WebSearchTool::new(config)
+            .execute(input, &context)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("api_key"));
+    }
+
+    #[test]
+    fn test_api_key_env_var_resolved() {
+        std::env::set_var("SHAMMAH_TEST_BRAVE_KEY", "brave-secret");
+        let config: WebSearchConfig =
+            toml::from_str("provider = \"brave\"\napi_key = \"${SHAMMAH_TEST_BRAVE_KEY}\"").unwrap();
+        assert_eq!(config.resolved_api_key().as_deref(), Some("brave-secret"));
+        // The reference itself is what gets saved
+        assert_eq!(config.api_key.as_deref(), Some("${SHAMMAH_TEST_BRAVE_KEY}"));
+    }
+}
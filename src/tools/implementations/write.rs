@@ -0,0 +1,109 @@
+// Write tool - creates or overwrites a file
+
+use crate::tools::registry::Tool;
+use crate::tools::types::{ToolContext, ToolInputSchema};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+pub struct WriteTool;
+
+#[async_trait]
+impl Tool for WriteTool {
+    fn name(&self) -> &str {
+        "write"
+    }
+
+    fn description(&self) -> &str {
+        "Write a file, creating it (and any missing parent directories) or replacing its \
+         contents. Read an existing file first; prefer the edit tool for small changes."
+    }
+
+    fn input_schema(&self) -> ToolInputSchema {
+        ToolInputSchema::simple(vec![
+            ("file_path", "Absolute path to the file to write"),
+            ("content", "The complete new contents of the file"),
+        ])
+    }
+
+    async fn execute(&self, input: Value, _context: &ToolContext<'_>) -> Result<String> {
+        let file_path = input["file_path"]
+            .as_str()
+            .context("Missing file_path parameter")?;
+        let content = input["content"]
+            .as_str()
+            .context("Missing content parameter")?;
+
+        let path = Path::new(file_path);
+        let existed = path.exists();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        fs::write(path, content).with_context(|| format!("Failed to write file: {}", file_path))?;
+
+        let lines = content.lines().count();
+        Ok(format!(
+            "{} {} ({} line{})",
+            if existed { "Overwrote" } else { "Created" },
+            file_path,
+            lines,
+            if lines == 1 { "" } else { "s" }
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> ToolContext<'static> {
+        ToolContext {
+            conversation: None,
+            save_models: None,
+            batch_trainer: None,
+            local_generator: None,
+            tokenizer: None,
+            repl_mode: None,
+            plan_content: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_write_creates_and_overwrites() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("notes.txt");
+        let file_path = path.display().to_string();
+
+        let result = WriteTool
+            .execute(
+                serde_json::json!({ "file_path": file_path, "content": "one\ntwo\n" }),
+                &context(),
+            )
+            .await
+            .unwrap();
+        assert!(result.starts_with("Created"));
+        assert!(result.ends_with("(2 lines)"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "one\ntwo\n");
+
+        let result = WriteTool
+            .execute(
+                serde_json::json!({ "file_path": file_path, "content": "three" }),
+                &context(),
+            )
+            .await
+            .unwrap();
+        assert!(result.starts_with("Overwrote"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "three");
+    }
+
+    #[tokio::test]
+    async fn test_write_missing_content() {
+        let result = WriteTool
+            .execute(serde_json::json!({ "file_path": "/tmp/x" }), &context())
+            .await;
+        assert!(result.unwrap_err().to_string().contains("content"));
+    }
+}
@@ -3,18 +3,29 @@
 // Enables Shammah to execute tools (WebFetch, Bash, Read, etc.) locally
 // instead of only generating text responses.
 
+pub mod audit;
 pub mod executor;
 pub mod implementations;
 pub mod mcp;
 pub mod pattern_matcher;
 pub mod patterns;
 pub mod permissions;
+pub mod recent_calls;
 pub mod registry;
+pub mod self_test;
+pub mod session_changes;
+pub mod timeouts;
 pub mod types;
 
-pub use executor::{generate_tool_signature, ApprovalSource, ToolExecutor, ToolSignature};
+pub use audit::{ToolAuditEntry, ToolAuditLogger};
+pub use executor::{
+    generate_tool_signature, ApprovalSource, ToolExecutor, ToolRunner, ToolSignature, ToolSlot, ToolSlots,
+};
 pub use pattern_matcher::ToolPatternMatcher;
 pub use patterns::{ExactApproval, MatchType, PersistentPatternStore, ToolPattern};
 pub use permissions::{PermissionCheck, PermissionManager, PermissionRule};
+pub use recent_calls::RecentToolCalls;
 pub use registry::{Tool, ToolRegistry};
+pub use session_changes::{ChangeSummary, SessionChanges, SUMMARY_MIN_TOOL_CALLS};
+pub use timeouts::{ToolTimeouts, DEFAULT_TOOL_TIMEOUT};
 pub use types::{ContentBlock, ToolDefinition, ToolInputSchema, ToolResult, ToolUse};
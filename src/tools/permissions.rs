@@ -9,6 +9,15 @@ use serde_json::Value;
 use std::collections::HashMap;
 use tracing::{debug, warn};
 
+/// Tools that only read, auto-approved unless `[tools.auto_approve]` says otherwise
+pub const READ_ONLY_TOOLS: &[&str] = &["read", "glob", "grep", "list_dir", "web_fetch", "web_search"];
+
+/// `[tools.auto_approve]` key covering every tool in READ_ONLY_TOOLS
+pub const READ_ONLY_CATEGORY: &str = "read_only";
+
+/// `[tools.auto_approve]` key covering every other tool (including MCP tools)
+pub const MUTATING_CATEGORY: &str = "mutating";
+
 /// Permission decision for a tool execution
 #[derive(Debug, Clone, PartialEq)]
 pub enum PermissionCheck {
@@ -96,11 +105,51 @@ impl PermissionManager {
         self
     }
 
+    /// Apply `[tools.auto_approve]`
+    ///
+    /// Keys are tool names or the `read_only` / `mutating` categories; a tool
+    /// name wins over its category. Without either, read-only tools are
+    /// allowed and everything else asks.
+    pub fn with_auto_approve(mut self, auto_approve: &HashMap<String, bool>) -> Self {
+        let rule = |allow: bool| if allow { PermissionRule::Allow } else { PermissionRule::Ask };
+
+        let mutating = auto_approve.get(MUTATING_CATEGORY).copied().unwrap_or(false);
+        self.default_rule = rule(mutating);
+
+        let read_only = auto_approve.get(READ_ONLY_CATEGORY).copied().unwrap_or(true);
+        for tool in READ_ONLY_TOOLS {
+            self.set_rule(tool, rule(read_only));
+        }
+
+        for (name, &allow) in auto_approve {
+            if name != READ_ONLY_CATEGORY && name != MUTATING_CATEGORY {
+                self.set_rule(name, rule(allow));
+            }
+        }
+        self
+    }
+
     /// Register tool-specific configuration
     pub fn register_tool_config(&mut self, tool_name: String, config: ToolPermissionConfig) {
         self.configs.insert(tool_name, config);
     }
 
+    /// Set one tool's rule, keeping the rest of its configuration
+    fn set_rule(&mut self, tool_name: &str, rule: PermissionRule) {
+        self.configs.entry(tool_name.to_string()).or_default().rule = rule;
+    }
+
+    /// Get the configured rule for a tool (falls back to the default rule)
+    ///
+    /// Returns `Deny` for tools that are disabled in config.
+    pub fn rule_for(&self, tool_name: &str) -> PermissionRule {
+        match self.configs.get(tool_name) {
+            Some(cfg) if !cfg.enabled => PermissionRule::Deny,
+            Some(cfg) => cfg.rule.clone(),
+            None => self.default_rule.clone(),
+        }
+    }
+
     /// Check if tool execution is permitted
     pub fn check_tool_use(&self, tool_name: &str, input: &Value) -> PermissionCheck {
         // Get tool config or use default
@@ -139,7 +188,7 @@ impl PermissionManager {
     fn check_constitutional_constraints(&self, tool_name: &str, input: &Value) -> Option<String> {
         match tool_name {
             "bash" => self.check_bash_safety(input),
-            "read" => self.check_read_safety(input),
+            "read" | "write" | "edit" => self.check_read_safety(input),
             "web_fetch" => self.check_web_fetch_safety(input),
             _ => None,
         }
@@ -171,7 +220,7 @@ impl PermissionManager {
         None
     }
 
-    /// Check if file read is safe
+    /// Check if file access (read, write or edit) is safe
     fn check_read_safety(&self, input: &Value) -> Option<String> {
         let file_path = input.get("file_path")?.as_str()?;
 
@@ -329,12 +378,15 @@ mod tests {
 
         for file in system_files {
             let input = serde_json::json!({"file_path": file});
-            let check = manager.check_tool_use("read", &input);
-            assert!(
-                matches!(check, PermissionCheck::Deny(_)),
-                "Failed to block: {}",
-                file
-            );
+            for tool in ["read", "write", "edit"] {
+                let check = manager.check_tool_use(tool, &input);
+                assert!(
+                    matches!(check, PermissionCheck::Deny(_)),
+                    "Failed to block {}: {}",
+                    tool,
+                    file
+                );
+            }
         }
     }
 
@@ -390,6 +442,35 @@ mod tests {
         assert!(matches!(check, PermissionCheck::AskUser(_)));
     }
 
+    #[test]
+    fn test_auto_approve_scope() {
+        // Defaults: read-only tools allowed, everything else asks
+        let manager = PermissionManager::new().with_auto_approve(&HashMap::new());
+        assert_eq!(manager.rule_for("read"), PermissionRule::Allow);
+        assert_eq!(manager.rule_for("web_search"), PermissionRule::Allow);
+        assert_eq!(manager.rule_for("bash"), PermissionRule::Ask);
+        assert_eq!(manager.rule_for("mcp_github_create_issue"), PermissionRule::Ask);
+
+        // Tool names win over categories
+        let auto_approve: HashMap<String, bool> = [
+            ("read_only".to_string(), false),
+            ("grep".to_string(), true),
+            ("mutating".to_string(), true),
+            ("bash".to_string(), false),
+        ]
+        .into_iter()
+        .collect();
+        let manager = PermissionManager::new().with_auto_approve(&auto_approve);
+        assert_eq!(manager.rule_for("read"), PermissionRule::Ask);
+        assert_eq!(manager.rule_for("grep"), PermissionRule::Allow);
+        assert_eq!(manager.rule_for("edit"), PermissionRule::Allow);
+        assert_eq!(manager.rule_for("bash"), PermissionRule::Ask);
+        assert!(matches!(
+            manager.check_tool_use("bash", &serde_json::json!({"command": "ls"})),
+            PermissionCheck::AskUser(_)
+        ));
+    }
+
     #[test]
     fn test_disabled_tool() {
         let mut manager = PermissionManager::new();
@@ -406,6 +487,8 @@ mod tests {
         let input = serde_json::json!({"command": "ls"});
         let check = manager.check_tool_use("bash", &input);
         assert!(matches!(check, PermissionCheck::Deny(_)));
+        assert_eq!(manager.rule_for("bash"), PermissionRule::Deny);
+        assert_eq!(manager.rule_for("read"), PermissionRule::Ask);
     }
 
     #[test]
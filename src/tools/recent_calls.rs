@@ -0,0 +1,151 @@
+// Recent tool calls (/replay-tool)
+//
+// Every tool call the executor runs is kept in a small ring buffer so the
+// user can re-run one (e.g. the test command after an edit) without the
+// model proposing it again. Numbered newest first: #1 is the last call.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use super::types::ToolUse;
+
+/// Tool calls remembered for /replay-tool
+pub const RECENT_TOOL_CALLS: usize = 20;
+
+/// Characters of a call's input shown in the list
+const INPUT_PREVIEW_CHARS: usize = 80;
+
+/// Lines of a replayed call's output shown
+const REPLAY_OUTPUT_LINES: usize = 40;
+
+/// Ring buffer of the session's latest tool calls
+#[derive(Debug)]
+pub struct RecentToolCalls {
+    calls: Mutex<VecDeque<ToolUse>>,
+    capacity: usize,
+}
+
+impl Default for RecentToolCalls {
+    fn default() -> Self {
+        Self::new(RECENT_TOOL_CALLS)
+    }
+}
+
+impl RecentToolCalls {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            calls: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity: capacity.max(1),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<ToolUse>> {
+        self.calls.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Remember a call, dropping the oldest when full
+    pub fn record(&self, tool_use: &ToolUse) {
+        let mut calls = self.lock();
+        if calls.len() >= self.capacity {
+            calls.pop_back();
+        }
+        calls.push_front(tool_use.clone());
+    }
+
+    /// Call `number` (1 = most recent)
+    pub fn get(&self, number: usize) -> Option<ToolUse> {
+        number.checked_sub(1).and_then(|index| self.lock().get(index).cloned())
+    }
+
+    /// A copy of `number` with a fresh ID, ready to run again
+    pub fn replay(&self, number: usize) -> Option<ToolUse> {
+        self.get(number)
+            .map(|tool_use| ToolUse::new(tool_use.name, tool_use.input))
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Numbered list, newest first
+    pub fn format_list(&self) -> String {
+        let calls = self.lock();
+        if calls.is_empty() {
+            return "No tool calls yet this session.".to_string();
+        }
+        let mut lines = vec!["Recent tool calls (/replay-tool <n> to run one again):".to_string()];
+        for (index, tool_use) in calls.iter().enumerate() {
+            lines.push(format!("  {:>2}. {}", index + 1, describe(tool_use)));
+        }
+        lines.join("\n")
+    }
+}
+
+/// One-line description of a call: tool name and its main input
+pub fn describe(tool_use: &ToolUse) -> String {
+    let key = ["command", "file_path", "path", "pattern", "url"]
+        .iter()
+        .find_map(|key| tool_use.input.get(*key).and_then(|v| v.as_str()));
+    let input = match key {
+        Some(value) => value.to_string(),
+        None => tool_use.input.to_string(),
+    };
+    format!(
+        "{} {}",
+        tool_use.name,
+        crate::text::ellipsize(&input.split_whitespace().collect::<Vec<_>>().join(" "), INPUT_PREVIEW_CHARS)
+    )
+}
+
+/// A replayed call's outcome for display
+pub fn format_replay_result(result: &anyhow::Result<String>) -> String {
+    match result {
+        Ok(output) => {
+            let lines: Vec<&str> = output.lines().collect();
+            let mut text = String::from("✓ Replay finished");
+            for line in lines.iter().take(REPLAY_OUTPUT_LINES) {
+                text.push_str("\n    ");
+                text.push_str(line);
+            }
+            if lines.len() > REPLAY_OUTPUT_LINES {
+                text.push_str(&format!("\n    ... ({} more lines)", lines.len() - REPLAY_OUTPUT_LINES));
+            }
+            text
+        }
+        Err(e) => format!("✗ Replay failed: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_recent_calls_ring_buffer() {
+        let recent = RecentToolCalls::new(2);
+        assert!(recent.get(1).is_none());
+
+        recent.record(&ToolUse::new("bash".to_string(), json!({ "command": "cargo test" })));
+        recent.record(&ToolUse::new("read".to_string(), json!({ "file_path": "a.rs" })));
+        recent.record(&ToolUse::new("glob".to_string(), json!({ "pattern": "*.rs" })));
+
+        assert_eq!(recent.len(), 2);
+        assert_eq!(recent.get(1).unwrap().name, "glob");
+        assert_eq!(recent.get(2).unwrap().name, "read");
+        assert!(recent.get(0).is_none());
+        assert!(recent.get(3).is_none());
+
+        let replay = recent.replay(2).unwrap();
+        assert_eq!(replay.input, json!({ "file_path": "a.rs" }));
+        assert_ne!(replay.id, recent.get(2).unwrap().id);
+
+        let list = recent.format_list();
+        assert!(list.contains(" 1. glob *.rs"));
+        assert!(list.contains(" 2. read a.rs"));
+    }
+}
@@ -6,7 +6,7 @@ use crate::tools::types::{ToolContext, ToolDefinition, ToolInputSchema};
 use anyhow::Result;
 use async_trait::async_trait;
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Tool trait - all tools must implement this
 #[async_trait]
@@ -36,6 +36,8 @@ pub trait Tool: Send + Sync {
 /// Registry of available tools
 pub struct ToolRegistry {
     tools: HashMap<String, Box<dyn Tool>>,
+    /// Names `register` skips (`[tools] disabled`)
+    disabled: HashSet<String>,
 }
 
 impl ToolRegistry {
@@ -43,15 +45,37 @@ impl ToolRegistry {
     pub fn new() -> Self {
         Self {
             tools: HashMap::new(),
+            disabled: HashSet::new(),
         }
     }
 
-    /// Register a tool
+    /// Refuse to register (or run) the named tools
+    pub fn with_disabled(mut self, names: impl IntoIterator<Item = String>) -> Self {
+        self.disabled.extend(names);
+        self
+    }
+
+    /// Register a tool (no-op for disabled tools)
     pub fn register(&mut self, tool: Box<dyn Tool>) {
         let name = tool.name().to_string();
+        if self.disabled.contains(&name) {
+            return;
+        }
         self.tools.insert(name, tool);
     }
 
+    /// Whether `name` is disabled by config
+    pub fn is_disabled(&self, name: &str) -> bool {
+        self.disabled.contains(name)
+    }
+
+    /// Disabled tool names, sorted
+    pub fn disabled_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.disabled.iter().cloned().collect();
+        names.sort();
+        names
+    }
+
     /// Get tool by name
     pub fn get(&self, name: &str) -> Option<&dyn Tool> {
         self.tools.get(name).map(|b| b.as_ref())
@@ -135,6 +159,21 @@ mod tests {
         assert_eq!(registry.len(), 1);
     }
 
+    #[test]
+    fn test_disabled_tools_are_not_registered() {
+        let mut registry = ToolRegistry::new().with_disabled(vec!["bash".to_string()]);
+        registry.register(Box::new(MockTool {
+            name: "bash".to_string(),
+        }));
+        registry.register(Box::new(MockTool {
+            name: "read".to_string(),
+        }));
+
+        assert!(!registry.has_tool("bash"));
+        assert!(registry.is_disabled("bash"));
+        assert_eq!(registry.tool_names(), vec!["read".to_string()]);
+    }
+
     #[test]
     fn test_registry_get_tool() {
         let mut registry = ToolRegistry::new();
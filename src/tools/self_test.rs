@@ -0,0 +1,253 @@
+// Tool self-test (/benchmark-tools)
+//
+// Runs each built-in tool once with a fixed, harmless input and checks the
+// output: read a file it wrote to a scratch directory, glob and grep that
+// directory, write and edit files inside it, `echo` through bash and, only
+// when asked, fetch a known URL.
+// Surfaces environment problems (no network, missing binaries, permission
+// errors) before an agent run trips over them. The inputs are fixed, so the
+// tools are called directly, without permission prompts. Tools with side
+// effects (restart, plan mode, GUI, questions) are listed as skipped.
+
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::tools::registry::ToolRegistry;
+use crate::tools::types::ToolContext;
+
+/// URL fetched by the web_fetch check
+pub const NETWORK_CHECK_URL: &str = "https://example.com";
+
+/// Longest a single check may run
+const CHECK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Marker written to the scratch file and searched for by the checks
+const MARKER: &str = "shammah-tool-self-test";
+
+/// Result of one tool's check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckOutcome {
+    Passed,
+    Failed(String),
+    Skipped(String),
+}
+
+/// One line of the report
+#[derive(Debug, Clone)]
+pub struct ToolCheck {
+    pub tool: String,
+    pub outcome: CheckOutcome,
+    pub elapsed: Duration,
+}
+
+impl ToolCheck {
+    fn skipped(tool: &str, reason: &str) -> Self {
+        Self {
+            tool: tool.to_string(),
+            outcome: CheckOutcome::Skipped(reason.to_string()),
+            elapsed: Duration::ZERO,
+        }
+    }
+}
+
+/// Directory for the checks' scratch file, removed after the run
+fn scratch_dir() -> PathBuf {
+    std::env::temp_dir().join(format!("shammah-tool-check-{}", std::process::id()))
+}
+
+/// Input for a tool's check and the text its output must contain
+fn check_input(tool: &str, scratch: &Path, network: bool) -> Result<Option<(Value, String)>, &'static str> {
+    let file = scratch.join("check.txt");
+    let check = match tool {
+        "read" => (json!({ "file_path": file.display().to_string() }), MARKER.to_string()),
+        "glob" => (
+            json!({ "pattern": scratch.join("*.txt").display().to_string() }),
+            "check.txt".to_string(),
+        ),
+        "grep" => (
+            json!({ "pattern": MARKER, "path": scratch.display().to_string() }),
+            MARKER.to_string(),
+        ),
+        "list_dir" => (
+            json!({ "path": scratch.display().to_string() }),
+            "check.txt".to_string(),
+        ),
+        "bash" => (
+            json!({ "command": format!("echo {}", MARKER), "description": "Tool self-test" }),
+            MARKER.to_string(),
+        ),
+        "write" => (
+            json!({ "file_path": scratch.join("write.txt").display().to_string(), "content": MARKER }),
+            "write.txt".to_string(),
+        ),
+        "edit" => (
+            json!({
+                "file_path": scratch.join("edit.txt").display().to_string(),
+                "old_string": "before",
+                "new_string": MARKER,
+            }),
+            "1 replacement".to_string(),
+        ),
+        "web_fetch" if !network => return Err("network check off (use --network)"),
+        "web_fetch" => (json!({ "url": NETWORK_CHECK_URL }), "Example Domain".to_string()),
+        _ => return Ok(None),
+    };
+    Ok(Some(check))
+}
+
+/// Run the self-test against every tool in `registry`
+///
+/// `network` enables the web_fetch check. Results are sorted by tool name.
+pub async fn run_self_test(registry: &ToolRegistry, network: bool) -> Result<Vec<ToolCheck>> {
+    let scratch = scratch_dir();
+    std::fs::create_dir_all(&scratch)
+        .with_context(|| format!("Failed to create {}", scratch.display()))?;
+    std::fs::write(scratch.join("check.txt"), format!("{}\n", MARKER))
+        .with_context(|| format!("Failed to write to {}", scratch.display()))?;
+    std::fs::write(scratch.join("edit.txt"), "before\n")
+        .with_context(|| format!("Failed to write to {}", scratch.display()))?;
+
+    let context = ToolContext {
+        conversation: None,
+        save_models: None,
+        batch_trainer: None,
+        local_generator: None,
+        tokenizer: None,
+        repl_mode: None,
+        plan_content: None,
+    };
+
+    let mut names = registry.tool_names();
+    names.sort();
+
+    let mut checks = Vec::new();
+    for name in names {
+        let (input, expected) = match check_input(&name, &scratch, network) {
+            Ok(Some(check)) => check,
+            Ok(None) => {
+                checks.push(ToolCheck::skipped(&name, "no safe self-test"));
+                continue;
+            }
+            Err(reason) => {
+                checks.push(ToolCheck::skipped(&name, reason));
+                continue;
+            }
+        };
+        let Some(tool) = registry.get(&name) else {
+            continue;
+        };
+
+        let started = Instant::now();
+        let result = match tokio::time::timeout(CHECK_TIMEOUT, tool.execute(input, &context)).await {
+            Ok(result) => result.and_then(|output| verify_output(&output, &expected)),
+            Err(_) => Err(anyhow::anyhow!("Timed out after {}s", CHECK_TIMEOUT.as_secs())),
+        };
+        checks.push(ToolCheck {
+            tool: name,
+            outcome: match result {
+                Ok(()) => CheckOutcome::Passed,
+                Err(e) => CheckOutcome::Failed(format!("{:#}", e)),
+            },
+            elapsed: started.elapsed(),
+        });
+    }
+
+    if let Err(e) = std::fs::remove_dir_all(&scratch) {
+        tracing::debug!("Failed to remove {}: {}", scratch.display(), e);
+    }
+    Ok(checks)
+}
+
+/// Check a tool's output contains what the check expects
+fn verify_output(output: &str, expected: &str) -> Result<()> {
+    if output.contains(expected) {
+        return Ok(());
+    }
+    let first_line = output.lines().next().unwrap_or("(empty output)");
+    bail!(
+        "Expected '{}' in output, got: {}",
+        expected,
+        crate::text::ellipsize(first_line, 120)
+    )
+}
+
+/// Render the self-test results for display
+pub fn format_report(checks: &[ToolCheck]) -> String {
+    let passed = checks.iter().filter(|c| c.outcome == CheckOutcome::Passed).count();
+    let failed = checks
+        .iter()
+        .filter(|c| matches!(c.outcome, CheckOutcome::Failed(_)))
+        .count();
+
+    let mut lines = vec![format!(
+        "🔧 Tool self-test: {} passed, {} failed, {} skipped",
+        passed,
+        failed,
+        checks.len() - passed - failed
+    )];
+    lines.push(String::new());
+    for check in checks {
+        lines.push(match &check.outcome {
+            CheckOutcome::Passed => {
+                format!("  ✓ {:<18} {}ms", check.tool, check.elapsed.as_millis())
+            }
+            CheckOutcome::Failed(error) => format!(
+                "  ✗ {:<18} {}ms  {}",
+                check.tool,
+                check.elapsed.as_millis(),
+                error
+            ),
+            CheckOutcome::Skipped(reason) => format!("  - {:<18} skipped: {}", check.tool, reason),
+        });
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::implementations::{
+        BashTool, EditTool, EnterPlanModeTool, GlobTool, GrepTool, ListDirTool, ReadTool,
+        WebFetchTool, WriteTool,
+    };
+
+    #[tokio::test]
+    async fn test_self_test_runs_builtin_tools() {
+        let mut registry = ToolRegistry::new();
+        registry.register(Box::new(ReadTool));
+        registry.register(Box::new(GlobTool));
+        registry.register(Box::new(GrepTool));
+        registry.register(Box::new(ListDirTool));
+        registry.register(Box::new(BashTool));
+        registry.register(Box::new(WriteTool));
+        registry.register(Box::new(EditTool));
+        registry.register(Box::new(WebFetchTool::new()));
+        registry.register(Box::new(EnterPlanModeTool));
+
+        let checks = run_self_test(&registry, false).await.unwrap();
+        let outcome = |name: &str| checks.iter().find(|c| c.tool == name).unwrap().outcome.clone();
+
+        assert_eq!(outcome("read"), CheckOutcome::Passed);
+        assert_eq!(outcome("glob"), CheckOutcome::Passed);
+        assert_eq!(outcome("grep"), CheckOutcome::Passed);
+        assert_eq!(outcome("list_dir"), CheckOutcome::Passed);
+        assert_eq!(outcome("bash"), CheckOutcome::Passed);
+        assert_eq!(outcome("write"), CheckOutcome::Passed);
+        assert_eq!(outcome("edit"), CheckOutcome::Passed);
+        assert!(matches!(outcome("web_fetch"), CheckOutcome::Skipped(_)));
+        assert!(matches!(outcome("EnterPlanMode"), CheckOutcome::Skipped(_)));
+
+        let report = format_report(&checks);
+        assert!(report.contains("7 passed, 0 failed, 2 skipped"));
+        assert!(!scratch_dir().exists());
+    }
+
+    #[test]
+    fn test_verify_output() {
+        assert!(verify_output("hello shammah", "shammah").is_ok());
+        let error = verify_output("Exit code: 127\nbash: not found", "shammah").unwrap_err();
+        assert!(error.to_string().contains("Exit code: 127"));
+    }
+}
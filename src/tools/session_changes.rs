@@ -0,0 +1,431 @@
+// Session change tracking
+//
+// Records what each tool execution did to the workspace (files created,
+// modified or deleted; shell commands run; errors) so a long agentic run can
+// end with a short "here's what just happened" summary.
+//
+// File effects are detected by checking which candidate paths exist before and
+// after the tool runs. Candidates come from the tool input: `file_path`/`path`
+// for file-writing tools, and the targets of common file commands (rm, mv, cp,
+// touch, mkdir, tee, output redirection) for bash.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use super::types::{ToolResult, ToolUse};
+
+/// Minimum tool calls in a run before a summary is worth printing
+pub const SUMMARY_MIN_TOOL_CALLS: usize = 3;
+
+/// Maximum entries listed per section in the formatted summary
+const MAX_LISTED: usize = 10;
+
+/// Tools whose `file_path`/`path` input is written to
+const FILE_WRITING_TOOLS: &[&str] = &["write", "edit", "multi_edit", "notebook_edit"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileChangeKind {
+    Created,
+    Modified,
+    Deleted,
+}
+
+/// What a single tool execution did
+#[derive(Debug, Clone)]
+struct ToolRecord {
+    tool: String,
+    tool_use_id: String,
+    command: Option<String>,
+    files: Vec<(PathBuf, FileChangeKind)>,
+    error: Option<String>,
+}
+
+/// Candidate path captured before a tool runs
+#[derive(Debug)]
+struct Candidate {
+    path: PathBuf,
+    existed: bool,
+}
+
+/// Tool execution in progress (returned by `begin`, consumed by `finish`)
+#[derive(Debug)]
+pub struct PendingToolChange {
+    tool: String,
+    tool_use_id: String,
+    command: Option<String>,
+    candidates: Vec<Candidate>,
+}
+
+/// Log of tool effects for this session
+#[derive(Debug, Default)]
+pub struct SessionChanges {
+    records: Mutex<Vec<ToolRecord>>,
+}
+
+impl SessionChanges {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<ToolRecord>> {
+        self.records.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Snapshot candidate paths before a tool runs
+    pub fn begin(&self, tool_use: &ToolUse) -> PendingToolChange {
+        let cwd = std::env::current_dir().unwrap_or_default();
+        // Dry runs don't execute, so there's nothing to record
+        let command = (tool_use.name == "bash"
+            && !crate::tools::implementations::bash::is_dry_run(&tool_use.input))
+            .then(|| tool_use.input["command"].as_str().map(str::to_string))
+            .flatten();
+
+        let paths = if let Some(command) = &command {
+            bash_candidate_paths(command)
+        } else if FILE_WRITING_TOOLS.contains(&tool_use.name.as_str()) {
+            ["file_path", "path", "notebook_path"]
+                .iter()
+                .filter_map(|key| tool_use.input[*key].as_str())
+                .map(str::to_string)
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let candidates = paths
+            .into_iter()
+            .map(|path| {
+                let path = cwd.join(path);
+                let existed = path.exists();
+                Candidate { path, existed }
+            })
+            .collect();
+
+        PendingToolChange {
+            tool: tool_use.name.clone(),
+            tool_use_id: tool_use.id.clone(),
+            command,
+            candidates,
+        }
+    }
+
+    /// Record the outcome of a tool started with `begin`
+    pub fn finish(&self, pending: PendingToolChange, result: &anyhow::Result<ToolResult>) {
+        let error = match result {
+            Ok(result) if result.is_error => Some(result.content.clone()),
+            Ok(_) => None,
+            Err(e) => Some(e.to_string()),
+        };
+
+        let files = pending
+            .candidates
+            .into_iter()
+            .filter_map(|candidate| {
+                let exists = candidate.path.exists();
+                let kind = match (candidate.existed, exists) {
+                    (false, true) => FileChangeKind::Created,
+                    (true, false) => FileChangeKind::Deleted,
+                    // Only count writes that actually went through
+                    (true, true) if error.is_none() => FileChangeKind::Modified,
+                    _ => return None,
+                };
+                Some((candidate.path, kind))
+            })
+            .collect();
+
+        self.lock().push(ToolRecord {
+            tool: pending.tool,
+            tool_use_id: pending.tool_use_id,
+            command: pending.command,
+            files,
+            error,
+        });
+    }
+
+    /// Files a tool call created, modified or deleted
+    pub fn files_for(&self, tool_use_id: &str) -> Vec<PathBuf> {
+        self.lock()
+            .iter()
+            .rev()
+            .find(|record| record.tool_use_id == tool_use_id)
+            .map(|record| record.files.iter().map(|(path, _)| path.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Position to summarize from later (see `summary_since`)
+    pub fn checkpoint(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Summarize tool effects recorded after `checkpoint`
+    pub fn summary_since(&self, checkpoint: usize) -> ChangeSummary {
+        let records = self.lock();
+        let mut summary = ChangeSummary::default();
+
+        for record in records.iter().skip(checkpoint) {
+            summary.tool_calls += 1;
+            for (path, kind) in &record.files {
+                summary.apply(path, *kind);
+            }
+            if let Some(command) = &record.command {
+                summary.commands.push(command.clone());
+            }
+            if let Some(error) = &record.error {
+                summary.errors.push(format!("{}: {}", record.tool, error));
+            }
+        }
+
+        summary
+    }
+}
+
+/// Net effect of a run of tool calls
+#[derive(Debug, Default, Clone)]
+pub struct ChangeSummary {
+    pub tool_calls: usize,
+    /// Final change per file (create + modify = created, create + delete = nothing)
+    pub files: BTreeMap<PathBuf, FileChangeKind>,
+    pub commands: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+impl ChangeSummary {
+    fn apply(&mut self, path: &Path, kind: FileChangeKind) {
+        use FileChangeKind::*;
+
+        let merged = match (self.files.get(path).copied(), kind) {
+            (Some(Created), Modified) => Some(Created),
+            (Some(Created), Deleted) => None,
+            (Some(Deleted), Created) => Some(Modified),
+            (_, kind) => Some(kind),
+        };
+
+        match merged {
+            Some(kind) => {
+                self.files.insert(path.to_path_buf(), kind);
+            }
+            None => {
+                self.files.remove(path);
+            }
+        }
+    }
+
+    fn count(&self, kind: FileChangeKind) -> usize {
+        self.files.values().filter(|k| **k == kind).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tool_calls == 0
+    }
+
+    /// Human-readable recap for the scrollback
+    pub fn format(&self) -> String {
+        let cwd = std::env::current_dir().unwrap_or_default();
+        let mut out = format!(
+            "📋 Session summary: {} tool call{}\n",
+            self.tool_calls,
+            if self.tool_calls == 1 { "" } else { "s" }
+        );
+
+        out.push_str(&format!(
+            "  Files: {} created, {} modified, {} deleted\n",
+            self.count(FileChangeKind::Created),
+            self.count(FileChangeKind::Modified),
+            self.count(FileChangeKind::Deleted)
+        ));
+        let file_lines: Vec<String> = self
+            .files
+            .iter()
+            .map(|(path, kind)| {
+                let marker = match kind {
+                    FileChangeKind::Created => '+',
+                    FileChangeKind::Modified => '~',
+                    FileChangeKind::Deleted => '-',
+                };
+                let display = path.strip_prefix(&cwd).unwrap_or(path);
+                format!("{} {}", marker, display.display())
+            })
+            .collect();
+        push_list(&mut out, &file_lines);
+
+        if !self.commands.is_empty() {
+            out.push_str(&format!("  Commands run: {}\n", self.commands.len()));
+            let lines: Vec<String> = self
+                .commands
+                .iter()
+                .map(|command| format!("$ {}", first_line(command, 70)))
+                .collect();
+            push_list(&mut out, &lines);
+        }
+
+        if !self.errors.is_empty() {
+            out.push_str(&format!("  Errors: {}\n", self.errors.len()));
+            let lines: Vec<String> = self
+                .errors
+                .iter()
+                .map(|error| format!("✗ {}", first_line(error, 70)))
+                .collect();
+            push_list(&mut out, &lines);
+        }
+
+        out.trim_end().to_string()
+    }
+}
+
+fn push_list(out: &mut String, lines: &[String]) {
+    for line in lines.iter().take(MAX_LISTED) {
+        out.push_str("    ");
+        out.push_str(line);
+        out.push('\n');
+    }
+    if lines.len() > MAX_LISTED {
+        out.push_str(&format!("    ... and {} more\n", lines.len() - MAX_LISTED));
+    }
+}
+
+fn first_line(text: &str, max_chars: usize) -> String {
+    let line = text.lines().next().unwrap_or("");
+    if line.chars().count() > max_chars {
+        format!("{}...", line.chars().take(max_chars).collect::<String>())
+    } else {
+        line.to_string()
+    }
+}
+
+/// Paths a shell command may create, modify or delete
+///
+/// Best effort: handles simple invocations of common file commands and output
+/// redirection, split on `&&`, `||`, `;` and `|`.
+fn bash_candidate_paths(command: &str) -> Vec<String> {
+    let mut paths = Vec::new();
+
+    let normalized = command
+        .replace("&&", ";")
+        .replace("||", ";")
+        .replace('|', ";")
+        .replace('\n', ";");
+
+    for segment in normalized.split(';') {
+        let tokens: Vec<String> = segment
+            .split_whitespace()
+            .map(|t| t.trim_matches(|c| c == '"' || c == '\''))
+            .map(str::to_string)
+            .collect();
+
+        // Output redirection: `> file`, `>> file`, `>file`
+        let mut iter = tokens.iter().peekable();
+        while let Some(token) = iter.next() {
+            if token == ">" || token == ">>" || token == "2>" || token == "&>" {
+                if let Some(target) = iter.peek() {
+                    paths.push(target.to_string());
+                }
+            } else if let Some(target) = token.strip_prefix(">>").or_else(|| token.strip_prefix('>')) {
+                if !target.is_empty() && !target.starts_with('&') {
+                    paths.push(target.to_string());
+                }
+            }
+        }
+
+        let Some(program) = tokens.first() else {
+            continue;
+        };
+        let args: Vec<&String> = tokens[1..]
+            .iter()
+            .take_while(|t| !t.starts_with('>') && !t.starts_with("2>") && !t.starts_with("&>"))
+            .filter(|t| !t.starts_with('-'))
+            .collect();
+
+        match program.as_str() {
+            "rm" | "touch" | "mkdir" | "rmdir" | "tee" | "mv" => {
+                paths.extend(args.into_iter().cloned());
+            }
+            // Only the destination of a copy changes
+            "cp" => {
+                if args.len() >= 2 {
+                    paths.extend(args.last().map(|s| s.to_string()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    paths.retain(|p| p != "/dev/null" && !p.contains('*') && !p.contains('$'));
+    paths.dedup();
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn tool_use(name: &str, input: serde_json::Value) -> ToolUse {
+        ToolUse {
+            id: "t1".to_string(),
+            name: name.to_string(),
+            input,
+        }
+    }
+
+    #[test]
+    fn test_bash_candidate_paths() {
+        assert_eq!(
+            bash_candidate_paths("rm -f a.txt b.txt && echo hi > out.log"),
+            vec!["a.txt", "b.txt", "out.log"]
+        );
+        assert_eq!(bash_candidate_paths("cp src.rs dst.rs"), vec!["dst.rs"]);
+        assert_eq!(bash_candidate_paths("cargo build 2>/dev/null"), Vec::<String>::new());
+        assert_eq!(bash_candidate_paths("ls -la | grep foo"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_tracks_file_effects() {
+        let dir = tempfile::tempdir().unwrap();
+        let created = dir.path().join("new.txt");
+        let existing = dir.path().join("existing.txt");
+        std::fs::write(&existing, "old").unwrap();
+
+        let changes = SessionChanges::new();
+        let checkpoint = changes.checkpoint();
+
+        let pending = changes.begin(&tool_use(
+            "write",
+            json!({ "file_path": created.to_str().unwrap() }),
+        ));
+        std::fs::write(&created, "new").unwrap();
+        changes.finish(pending, &Ok(ToolResult::success("t1".into(), "ok".into())));
+
+        let command = format!("rm {}", existing.display());
+        let pending = changes.begin(&tool_use("bash", json!({ "command": command })));
+        std::fs::remove_file(&existing).unwrap();
+        changes.finish(pending, &Ok(ToolResult::success("t2".into(), String::new())));
+
+        let pending = changes.begin(&tool_use("read", json!({ "file_path": "x" })));
+        changes.finish(pending, &Ok(ToolResult::error("t3".into(), "not found".into())));
+
+        let summary = changes.summary_since(checkpoint);
+        assert_eq!(summary.tool_calls, 3);
+        assert_eq!(summary.files.get(&created), Some(&FileChangeKind::Created));
+        assert_eq!(summary.files.get(&existing), Some(&FileChangeKind::Deleted));
+        assert_eq!(summary.commands, vec![command]);
+        assert_eq!(summary.errors, vec!["read: not found".to_string()]);
+
+        let text = summary.format();
+        assert!(text.contains("1 created, 0 modified, 1 deleted"));
+        assert!(text.contains("Commands run: 1"));
+
+        assert!(changes.summary_since(changes.checkpoint()).is_empty());
+    }
+
+    #[test]
+    fn test_merge_changes() {
+        let mut summary = ChangeSummary::default();
+        let path = Path::new("a.txt");
+        summary.apply(path, FileChangeKind::Created);
+        summary.apply(path, FileChangeKind::Modified);
+        assert_eq!(summary.files.get(path), Some(&FileChangeKind::Created));
+        summary.apply(path, FileChangeKind::Deleted);
+        assert!(summary.files.is_empty());
+    }
+}
@@ -0,0 +1,72 @@
+// Per-tool execution timeouts
+//
+// A hung `bash` command or a slow `web_fetch` shouldn't block the REPL
+// forever, so every tool execution runs under a timeout. Built-in tools
+// have their own defaults; `[tools.timeouts]` in config.toml overrides
+// them per tool name (0 disables the timeout for that tool).
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Timeout for tools without a built-in default (MCP tools, write, edit, ...)
+pub const DEFAULT_TOOL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Built-in defaults, in seconds
+const BUILTIN_TIMEOUTS: &[(&str, u64)] = &[
+    ("bash", 30),
+    ("web_fetch", 15),
+    ("web_search", 15),
+    ("read", 5),
+    ("glob", 5),
+    ("grep", 5),
+    ("list_dir", 5),
+];
+
+/// Timeout overrides in seconds, keyed by tool name
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ToolTimeouts {
+    overrides: HashMap<String, u64>,
+}
+
+impl ToolTimeouts {
+    pub fn new(overrides: HashMap<String, u64>) -> Self {
+        Self { overrides }
+    }
+
+    /// Timeout for `tool_name` (None if disabled with 0)
+    pub fn for_tool(&self, tool_name: &str) -> Option<Duration> {
+        let secs = self.overrides.get(tool_name).copied().or_else(|| {
+            BUILTIN_TIMEOUTS
+                .iter()
+                .find(|(name, _)| *name == tool_name)
+                .map(|(_, secs)| *secs)
+        });
+        match secs {
+            Some(0) => None,
+            Some(secs) => Some(Duration::from_secs(secs)),
+            None => Some(DEFAULT_TOOL_TIMEOUT),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_and_overrides() {
+        let defaults = ToolTimeouts::default();
+        assert_eq!(defaults.for_tool("bash"), Some(Duration::from_secs(30)));
+        assert_eq!(defaults.for_tool("web_fetch"), Some(Duration::from_secs(15)));
+        assert_eq!(defaults.for_tool("grep"), Some(Duration::from_secs(5)));
+        assert_eq!(defaults.for_tool("mcp_fs_read"), Some(DEFAULT_TOOL_TIMEOUT));
+
+        let timeouts: ToolTimeouts = toml::from_str("bash = 120\nread = 0\nmcp_fs_read = 10").unwrap();
+        assert_eq!(timeouts.for_tool("bash"), Some(Duration::from_secs(120)));
+        assert_eq!(timeouts.for_tool("read"), None);
+        assert_eq!(timeouts.for_tool("mcp_fs_read"), Some(Duration::from_secs(10)));
+        assert_eq!(timeouts.for_tool("glob"), Some(Duration::from_secs(5)));
+    }
+}
@@ -6,6 +6,7 @@ use anyhow::{Context, Result};
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use tokio::process::Command;
+use tokio::sync::oneshot;
 
 /// Configuration for LoRA training subprocess
 #[derive(Debug, Clone)]
@@ -70,6 +71,50 @@ impl Default for LoRATrainingConfig {
     }
 }
 
+/// How a training subprocess run ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrainingOutcome {
+    /// Adapter was written and the queue archived
+    Completed,
+    /// Subprocess failed to spawn or exited with an error
+    Failed,
+    /// Run was cancelled; partial adapter removed, queue left in place
+    Cancelled,
+}
+
+/// Handle to a running training subprocess
+///
+/// Dropping the handle does not stop training; call `cancel()` to terminate it.
+pub struct TrainingJob {
+    cancel_tx: Option<oneshot::Sender<()>>,
+    done_rx: oneshot::Receiver<TrainingOutcome>,
+}
+
+impl TrainingJob {
+    /// Request cancellation (kills the subprocess)
+    pub fn cancel(&mut self) {
+        if let Some(tx) = self.cancel_tx.take() {
+            let _ = tx.send(());
+        }
+    }
+
+    /// Check whether the run has finished without blocking
+    pub fn try_outcome(&mut self) -> Option<TrainingOutcome> {
+        match self.done_rx.try_recv() {
+            Ok(outcome) => Some(outcome),
+            Err(oneshot::error::TryRecvError::Empty) => None,
+            Err(oneshot::error::TryRecvError::Closed) => Some(TrainingOutcome::Failed),
+        }
+    }
+
+    /// Wait for the run to finish
+    ///
+    /// Must not be called again once it has returned.
+    pub async fn wait(&mut self) -> TrainingOutcome {
+        (&mut self.done_rx).await.unwrap_or(TrainingOutcome::Failed)
+    }
+}
+
 /// LoRA training subprocess manager
 pub struct LoRATrainingSubprocess {
     config: LoRATrainingConfig,
@@ -88,20 +133,24 @@ impl LoRATrainingSubprocess {
 
     /// Trigger background training (non-blocking)
     ///
-    /// Spawns Python training script as a detached subprocess.
+    /// Spawns Python training script as a background subprocess.
     /// Does not block - training runs in background.
     ///
+    /// The adapter is written to a `.partial` file and only renamed into
+    /// place once training succeeds, so a cancelled or failed run never
+    /// leaves a half-written adapter behind.
+    ///
     /// # Arguments
     /// * `queue_path` - Path to training queue JSONL file
     /// * `output_adapter` - Path to save trained adapter (safetensors)
     ///
     /// # Returns
-    /// Ok(()) if subprocess spawned successfully
+    /// A `TrainingJob` handle for cancellation and completion tracking
     pub async fn train_async(
         &self,
         queue_path: &Path,
         output_adapter: &Path,
-    ) -> Result<()> {
+    ) -> Result<TrainingJob> {
         // Validate inputs
         if !queue_path.exists() {
             anyhow::bail!("Training queue not found: {}", queue_path.display());
@@ -120,11 +169,15 @@ impl LoRATrainingSubprocess {
             output_adapter.display()
         );
 
+        // Unique per run so concurrent runs don't overwrite each other's output
+        let run_id = uuid::Uuid::new_v4().simple().to_string();
+        let partial_adapter = partial_adapter_path(output_adapter, &run_id[..8]);
+
         // Build command
         let mut cmd = Command::new("python3");
         cmd.arg(&self.config.script_path)
             .arg(queue_path)
-            .arg(output_adapter)
+            .arg(&partial_adapter)
             .arg("--base-model")
             .arg(&self.config.base_model)
             .arg("--rank")
@@ -150,51 +203,32 @@ impl LoRATrainingSubprocess {
 
         tracing::info!("Training logs will be written to: {}", log_path.display());
 
-        // Spawn and detach (non-blocking)
+        // Spawn in background (non-blocking)
         let queue_path_owned = queue_path.to_path_buf();
         let output_adapter_owned = output_adapter.to_path_buf();
         let log_path_owned = log_path;
+        let (cancel_tx, cancel_rx) = oneshot::channel();
+        let (done_tx, done_rx) = oneshot::channel();
 
         tokio::spawn(async move {
-            match cmd.spawn() {
-                Ok(mut child) => {
-                    tracing::info!("✅ Training subprocess spawned (PID: {:?})", child.id());
-
-                    match child.wait().await {
-                        Ok(status) if status.success() => {
-                            tracing::info!(
-                                "✅ LoRA training completed successfully: {}",
-                                output_adapter_owned.display()
-                            );
-
-                            // Archive training queue after successful training
-                            if let Err(e) = archive_training_queue(&queue_path_owned) {
-                                tracing::warn!("Failed to archive training queue: {}", e);
-                            }
-                        }
-                        Ok(status) => {
-                            tracing::error!(
-                                "❌ LoRA training failed with status: {:?}. Check log: {}",
-                                status,
-                                log_path_owned.display()
-                            );
-                        }
-                        Err(e) => {
-                            tracing::error!("Failed to wait for training subprocess: {}", e);
-                        }
-                    }
-                }
-                Err(e) => {
-                    tracing::error!("Failed to spawn training subprocess: {}", e);
-                    tracing::error!("Make sure Python 3 and required packages are installed:");
-                    tracing::error!("  pip install -r scripts/requirements.txt");
-                }
-            }
+            let outcome = run_training_child(
+                cmd,
+                cancel_rx,
+                &queue_path_owned,
+                &partial_adapter,
+                &output_adapter_owned,
+                &log_path_owned,
+            )
+            .await;
+            let _ = done_tx.send(outcome);
         });
 
         tracing::info!("Training subprocess launched in background");
 
-        Ok(())
+        Ok(TrainingJob {
+            cancel_tx: Some(cancel_tx),
+            done_rx,
+        })
     }
 
     /// Check if Python dependencies are installed
@@ -220,6 +254,97 @@ impl LoRATrainingSubprocess {
     }
 }
 
+/// Run the training child to completion or cancellation
+async fn run_training_child(
+    mut cmd: Command,
+    cancel_rx: oneshot::Receiver<()>,
+    queue_path: &Path,
+    partial_adapter: &Path,
+    output_adapter: &Path,
+    log_path: &Path,
+) -> TrainingOutcome {
+    let mut child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            tracing::error!("Failed to spawn training subprocess: {}", e);
+            tracing::error!("Make sure Python 3 and required packages are installed:");
+            tracing::error!("  pip install -r scripts/requirements.txt");
+            return TrainingOutcome::Failed;
+        }
+    };
+
+    tracing::info!("✅ Training subprocess spawned (PID: {:?})", child.id());
+
+    let status = tokio::select! {
+        status = child.wait() => status,
+        Ok(()) = cancel_rx => {
+            tracing::info!("Cancelling training subprocess (PID: {:?})", child.id());
+            if let Err(e) = child.kill().await {
+                tracing::warn!("Failed to kill training subprocess: {}", e);
+            }
+            remove_partial_adapter(partial_adapter);
+            // Queue is intentionally not archived so the examples are retrained
+            return TrainingOutcome::Cancelled;
+        }
+    };
+
+    match status {
+        Ok(status) if status.success() => {
+            if let Err(e) = std::fs::rename(partial_adapter, output_adapter) {
+                tracing::error!(
+                    "Failed to move trained adapter into place ({}): {}",
+                    output_adapter.display(),
+                    e
+                );
+                remove_partial_adapter(partial_adapter);
+                return TrainingOutcome::Failed;
+            }
+
+            tracing::info!(
+                "✅ LoRA training completed successfully: {}",
+                output_adapter.display()
+            );
+
+            // Archive training queue after successful training
+            if let Err(e) = archive_training_queue(queue_path) {
+                tracing::warn!("Failed to archive training queue: {}", e);
+            }
+            TrainingOutcome::Completed
+        }
+        Ok(status) => {
+            tracing::error!(
+                "❌ LoRA training failed with status: {:?}. Check log: {}",
+                status,
+                log_path.display()
+            );
+            remove_partial_adapter(partial_adapter);
+            TrainingOutcome::Failed
+        }
+        Err(e) => {
+            tracing::error!("Failed to wait for training subprocess: {}", e);
+            remove_partial_adapter(partial_adapter);
+            TrainingOutcome::Failed
+        }
+    }
+}
+
+/// Path the subprocess writes to before the adapter is moved into place
+fn partial_adapter_path(output_adapter: &Path, run_id: &str) -> PathBuf {
+    output_adapter.with_extension(format!("partial-{}.safetensors", run_id))
+}
+
+fn remove_partial_adapter(partial_adapter: &Path) {
+    if partial_adapter.exists() {
+        if let Err(e) = std::fs::remove_file(partial_adapter) {
+            tracing::warn!(
+                "Failed to remove partial adapter {}: {}",
+                partial_adapter.display(),
+                e
+            );
+        }
+    }
+}
+
 /// Archive training queue after successful training
 fn archive_training_queue(queue_path: &Path) -> Result<()> {
     let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
@@ -255,6 +380,15 @@ mod tests {
         assert_eq!(subprocess.config().rank, 16);
     }
 
+    #[test]
+    fn test_partial_adapter_path() {
+        let path = partial_adapter_path(Path::new("/tmp/adapters/latest.safetensors"), "1a2b3c4d");
+        assert_eq!(
+            path,
+            PathBuf::from("/tmp/adapters/latest.partial-1a2b3c4d.safetensors")
+        );
+    }
+
     #[tokio::test]
     async fn test_check_dependencies_runs() {
         let subprocess = LoRATrainingSubprocess::with_defaults();
@@ -6,4 +6,6 @@ pub mod lora_subprocess;  // Phase 6: Python LoRA training subprocess
 
 pub use batch_trainer::{BatchTrainer, TrainingExample, TrainingResult};
 pub use checkpoint::{Checkpoint, CheckpointManager};
-pub use lora_subprocess::{LoRATrainingConfig, LoRATrainingSubprocess};
+pub use lora_subprocess::{
+    LoRATrainingConfig, LoRATrainingSubprocess, TrainingJob, TrainingOutcome,
+};
@@ -4,6 +4,10 @@
 // 1. Each provider in the chain uses its own model ID
 // 2. Model IDs are not inherited from the first provider
 // 3. Fallback logic preserves provider-specific configuration
+//
+// FallbackChain is deprecated in favour of FailoverProvider.
+
+#![allow(deprecated)]
 
 use anyhow::Result;
 use shammah::providers::{LlmProvider, ProviderRequest};
@@ -57,6 +61,7 @@ impl LlmProvider for MockProvider {
             stop_reason: Some("end_turn".to_string()),
             role: "assistant".to_string(),
             provider: self.name.clone(),
+            usage: None,
         })
     }
 
@@ -103,6 +108,7 @@ async fn test_fallback_chain_uses_provider_specific_models() -> Result<()> {
 
     // Create a request with a DIFFERENT model ID (simulating what the first provider might set)
     let request = ProviderRequest {
+        system: None,
         messages: vec![Message {
             role: "user".to_string(),
             content: vec![ContentBlock::Text {
@@ -110,9 +116,10 @@ async fn test_fallback_chain_uses_provider_specific_models() -> Result<()> {
             }],
         }],
         model: "gpt-4".to_string(), // Wrong model for both providers!
-        max_tokens: 100,
+        max_tokens: Some(100),
         tools: None,
         temperature: None,
+        thinking: None,
         stream: false,
     };
 
@@ -161,6 +168,7 @@ async fn test_fallback_chain_tries_providers_in_order() -> Result<()> {
     let chain = shammah::providers::FallbackChain::new(providers);
 
     let request = ProviderRequest {
+        system: None,
         messages: vec![Message {
             role: "user".to_string(),
             content: vec![ContentBlock::Text {
@@ -168,9 +176,10 @@ async fn test_fallback_chain_tries_providers_in_order() -> Result<()> {
             }],
         }],
         model: "default".to_string(),
-        max_tokens: 100,
+        max_tokens: Some(100),
         tools: None,
         temperature: None,
+        thinking: None,
         stream: false,
     };
 
@@ -200,6 +209,7 @@ async fn test_fallback_chain_fails_when_all_providers_fail() -> Result<()> {
     let chain = shammah::providers::FallbackChain::new(providers);
 
     let request = ProviderRequest {
+        system: None,
         messages: vec![Message {
             role: "user".to_string(),
             content: vec![ContentBlock::Text {
@@ -207,9 +217,10 @@ async fn test_fallback_chain_fails_when_all_providers_fail() -> Result<()> {
             }],
         }],
         model: "default".to_string(),
-        max_tokens: 100,
+        max_tokens: Some(100),
         tools: None,
         temperature: None,
+        thinking: None,
         stream: false,
     };
 
@@ -237,6 +248,7 @@ async fn test_fallback_chain_uses_first_provider_when_available() -> Result<()>
     let chain = shammah::providers::FallbackChain::new(providers);
 
     let request = ProviderRequest {
+        system: None,
         messages: vec![Message {
             role: "user".to_string(),
             content: vec![ContentBlock::Text {
@@ -244,9 +256,10 @@ async fn test_fallback_chain_uses_first_provider_when_available() -> Result<()>
             }],
         }],
         model: "default".to_string(),
-        max_tokens: 100,
+        max_tokens: Some(100),
         tools: None,
         temperature: None,
+        thinking: None,
         stream: false,
     };
 
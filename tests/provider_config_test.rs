@@ -18,6 +18,7 @@ fn test_gemini_invalid_model_name() {
         api_key: "test-key".to_string(),
         model: Some("gemini-2.0-flash-exp".to_string()), // Invalid!
         name: Some("Test".to_string()),
+        ..Default::default()
     };
 
     // Note: This test documents CURRENT behavior
@@ -39,6 +40,7 @@ fn test_provider_factory_creates_correct_types() -> Result<()> {
         api_key: "test-key".to_string(),
         model: Some("claude-sonnet-4".to_string()),
         name: Some("Claude".to_string()),
+        ..Default::default()
     };
 
     let gemini_teacher = TeacherEntry {
@@ -46,11 +48,12 @@ fn test_provider_factory_creates_correct_types() -> Result<()> {
         api_key: "test-key".to_string(),
         model: Some("gemini-2.5-flash".to_string()),
         name: Some("Gemini".to_string()),
+        ..Default::default()
     };
 
     // Create providers
-    let claude_provider = providers::create_provider(&[claude_teacher])?;
-    let gemini_provider = providers::create_provider(&[gemini_teacher])?;
+    let claude_provider = providers::create_provider(&[claude_teacher], 4096)?;
+    let gemini_provider = providers::create_provider(&[gemini_teacher], 4096)?;
 
     // Verify provider names
     assert_eq!(claude_provider.name(), "claude");
@@ -72,17 +75,19 @@ fn test_provider_factory_creates_fallback_chain() -> Result<()> {
             api_key: "key1".to_string(),
             model: Some("gemini-2.5-flash".to_string()),
             name: Some("Gemini".to_string()),
+            ..Default::default()
         },
         TeacherEntry {
             provider: "claude".to_string(),
             api_key: "key2".to_string(),
             model: Some("claude-sonnet-4".to_string()),
             name: Some("Claude".to_string()),
+            ..Default::default()
         },
     ];
 
     // Create provider (should be a FallbackChain)
-    let provider = providers::create_provider(&teachers)?;
+    let provider = providers::create_provider(&teachers, 4096)?;
 
     // Verify it uses the first provider's name
     assert_eq!(provider.name(), "gemini");
@@ -101,10 +106,11 @@ fn test_provider_factory_single_teacher() -> Result<()> {
         api_key: "test-key".to_string(),
         model: Some("claude-sonnet-4".to_string()),
         name: Some("Claude".to_string()),
+        ..Default::default()
     }];
 
     // Create provider (should NOT be a FallbackChain)
-    let provider = providers::create_provider(&teachers)?;
+    let provider = providers::create_provider(&teachers, 4096)?;
 
     assert_eq!(provider.name(), "claude");
     assert_eq!(provider.default_model(), "claude-sonnet-4");
@@ -117,7 +123,7 @@ fn test_provider_factory_single_teacher() -> Result<()> {
 fn test_provider_factory_fails_with_no_teachers() {
     let teachers: Vec<TeacherEntry> = vec![];
 
-    let result = providers::create_provider(&teachers);
+    let result = providers::create_provider(&teachers, 4096);
 
     // Should fail - no teachers provided
     assert!(result.is_err());
@@ -132,11 +138,12 @@ fn test_provider_requires_api_key() {
         api_key: "".to_string(), // Empty!
         model: Some("claude-sonnet-4".to_string()),
         name: Some("Claude".to_string()),
+        ..Default::default()
     };
 
     // Provider creation should handle this gracefully
     // (It will fail when making actual API calls)
-    let result = providers::create_provider(&[teacher]);
+    let result = providers::create_provider(&[teacher], 4096);
 
     // Current behavior: accepts empty key, fails at runtime
     // Future improvement: validate at config time
@@ -151,9 +158,10 @@ fn test_provider_model_defaults() -> Result<()> {
         api_key: "test-key".to_string(),
         model: None, // No model specified
         name: Some("Claude".to_string()),
+        ..Default::default()
     };
 
-    let provider = providers::create_provider(&[teacher_without_model])?;
+    let provider = providers::create_provider(&[teacher_without_model], 4096)?;
 
     // Should use provider's default model
     let default = provider.default_model();
@@ -170,6 +178,7 @@ fn test_provider_names_case_insensitive() -> Result<()> {
         api_key: "test-key".to_string(),
         model: Some("claude-sonnet-4".to_string()),
         name: Some("Claude".to_string()),
+        ..Default::default()
     };
 
     let teacher_lower = TeacherEntry {
@@ -177,11 +186,12 @@ fn test_provider_names_case_insensitive() -> Result<()> {
         api_key: "test-key".to_string(),
         model: Some("claude-sonnet-4".to_string()),
         name: Some("Claude".to_string()),
+        ..Default::default()
     };
 
     // Both should work
-    let provider_upper = providers::create_provider(&[teacher_upper])?;
-    let provider_lower = providers::create_provider(&[teacher_lower])?;
+    let provider_upper = providers::create_provider(&[teacher_upper], 4096)?;
+    let provider_lower = providers::create_provider(&[teacher_lower], 4096)?;
 
     // Should normalize to lowercase
     assert_eq!(provider_upper.name().to_lowercase(), "claude");
@@ -198,9 +208,10 @@ fn test_unknown_provider_rejected() {
         api_key: "test-key".to_string(),
         model: Some("some-model".to_string()),
         name: Some("Unknown".to_string()),
+        ..Default::default()
     };
 
-    let result = providers::create_provider(&[teacher]);
+    let result = providers::create_provider(&[teacher], 4096);
 
     // Should fail with unknown provider
     assert!(result.is_err(), "Unknown provider should be rejected");
@@ -214,9 +225,10 @@ fn test_provider_capabilities() -> Result<()> {
         api_key: "test-key".to_string(),
         model: Some("claude-sonnet-4".to_string()),
         name: Some("Claude".to_string()),
+        ..Default::default()
     };
 
-    let provider = providers::create_provider(&[claude_teacher])?;
+    let provider = providers::create_provider(&[claude_teacher], 4096)?;
 
     // Claude should support both streaming and tools
     assert!(provider.supports_streaming(), "Claude should support streaming");
@@ -31,6 +31,7 @@ async fn test_server_creation() {
         session_timeout_minutes: 30,
         auth_enabled: false,
         api_keys: vec![],
+        ..Default::default()
     };
 
     // Create server